@@ -24,6 +24,7 @@ use serde_json::{Map, Value};
 use sha2::{Digest, Sha256};
 
 use super::{local_schema_map, SCHEMA_VERSION, UPSTREAM_TARBALL_SHA256};
+pub use crate::utils::digest::hex_encode;
 
 /// npm registry endpoint that resolves the `latest` dist-tag for the package.
 const NPM_LATEST_URL: &str = "https://registry.npmjs.org/@atlaskit/adf-schema/latest";
@@ -599,22 +600,6 @@ fn strip_transcription_date(s: &str) -> &str {
     }
 }
 
-/// Lower-case hex encoding of a byte slice.
-///
-/// Replaces the `format!("{:x}", Sha256::digest(...))` idiom, which broke when
-/// `sha2` 0.11 changed the digest output type to `hybrid_array::Array`, which
-/// does not implement `LowerHex`.
-#[must_use]
-pub fn hex_encode(bytes: &[u8]) -> String {
-    use std::fmt::Write;
-    bytes
-        .iter()
-        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
-            let _ = write!(s, "{b:02x}");
-            s
-        })
-}
-
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -44,6 +44,7 @@ use crate::atlassian::jira_types::{
 };
 use crate::request_log;
 use crate::utils::http::{retry_429, REQUEST_TIMEOUT};
+use crate::utils::network::NetworkSettings;
 
 /// Internal page size for auto-pagination. Individual API calls request
 /// this many items per page; the `limit` parameter controls the total.
@@ -6501,8 +6502,8 @@ impl AtlassianClient {
     ///
     /// Constructs the Basic Auth header from the email and API token.
     pub fn new(instance_url: &str, email: &str, api_token: &str) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(REQUEST_TIMEOUT)
+        let client = NetworkSettings::load()
+            .apply_reqwest(Client::builder().timeout(REQUEST_TIMEOUT))?
             .build()
             .context("Failed to build HTTP client")?;
 
@@ -0,0 +1,690 @@
+//! Bitbucket Cloud pull request support.
+//!
+//! Repositories whose `origin` remote points at `bitbucket.org` talk to the
+//! Bitbucket REST API (v2.0) through [`BitbucketClient`], an implementation
+//! of [`PullRequestApi`] selected by [`crate::forge::Forge::detect`].
+//!
+//! Credentials are read from the environment or the settings.json `env` map:
+//! either a repository/workspace access token in `BITBUCKET_TOKEN` (sent as a
+//! bearer token), or `BITBUCKET_USERNAME` plus `BITBUCKET_APP_PASSWORD` (sent
+//! as basic auth; an Atlassian API token works as the password with the
+//! account email as the username).
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use serde_json::{json, Value};
+
+use crate::data::context::ReviewComment;
+use crate::data::PullRequest;
+use crate::forge::{
+    commit_hashes, find_marked_comment, http_client, owner_and_repo, review_comments_at, send_json,
+    split_remote_url, MergeStrategy, PullRequestApi,
+};
+use crate::pr_status::PrCheckState;
+use crate::utils::secret::Secret;
+use crate::utils::settings::Settings;
+
+/// Environment/settings key for a Bitbucket access token (bearer auth).
+pub const BITBUCKET_TOKEN: &str = "BITBUCKET_TOKEN";
+
+/// Environment/settings key for the Bitbucket username (basic auth).
+pub const BITBUCKET_USERNAME: &str = "BITBUCKET_USERNAME";
+
+/// Environment/settings key for the Bitbucket app password or API token
+/// (basic auth).
+pub const BITBUCKET_APP_PASSWORD: &str = "BITBUCKET_APP_PASSWORD";
+
+/// Base URL of the Bitbucket Cloud REST API.
+const API_BASE: &str = "https://api.bitbucket.org/2.0";
+
+/// Host name identifying Bitbucket Cloud remotes.
+const BITBUCKET_HOST: &str = "bitbucket.org";
+
+/// Upper bound on comment pages scanned when looking for an existing comment.
+const MAX_COMMENT_PAGES: usize = 10;
+
+/// Upper bound on pages fetched when listing a pull request's commits.
+const MAX_COMMIT_PAGES: usize = 10;
+
+/// A repository hosted on Bitbucket Cloud.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitbucketRepo {
+    /// Workspace slug (the first path component of the repository URL).
+    pub workspace: String,
+    /// Repository slug.
+    pub repo_slug: String,
+}
+
+impl BitbucketRepo {
+    /// Parses a git remote URL, returning `None` unless it points at
+    /// `bitbucket.org`.
+    ///
+    /// Accepts scp-style (`git@bitbucket.org:ws/repo.git`), `ssh://`, and
+    /// `https://` (optionally with `user@`) forms.
+    pub fn from_remote_url(url: &str) -> Option<Self> {
+        let (host, path) = split_remote_url(url)?;
+        if !host.eq_ignore_ascii_case(BITBUCKET_HOST) {
+            return None;
+        }
+        let (workspace, repo_slug) = owner_and_repo(path)?;
+        Some(Self {
+            workspace: workspace.to_string(),
+            repo_slug: repo_slug.to_string(),
+        })
+    }
+
+    fn api_path(&self) -> String {
+        format!(
+            "{API_BASE}/repositories/{}/{}",
+            self.workspace, self.repo_slug
+        )
+    }
+}
+
+/// Credentials for the Bitbucket REST API.
+#[derive(Debug, Clone)]
+pub enum BitbucketAuth {
+    /// Repository or workspace access token.
+    Bearer(Secret),
+    /// Username plus app password (or account email plus API token).
+    Basic {
+        /// Bitbucket username or account email.
+        username: String,
+        /// App password or API token.
+        password: Secret,
+    },
+}
+
+impl BitbucketAuth {
+    fn header_value(&self) -> String {
+        match self {
+            Self::Bearer(token) => format!("Bearer {}", token.expose_secret()),
+            Self::Basic { username, password } => {
+                let credentials = format!("{username}:{}", password.expose_secret());
+                format!(
+                    "Basic {}",
+                    base64::engine::general_purpose::STANDARD.encode(credentials)
+                )
+            }
+        }
+    }
+}
+
+/// Loads Bitbucket credentials from environment variables or settings.json.
+///
+/// `BITBUCKET_TOKEN` takes precedence over the username/app-password pair.
+pub fn load_auth() -> Result<BitbucketAuth> {
+    let settings = Settings::load().unwrap_or_default();
+    auth_from_lookup(|key| settings.get_env_var(key))
+}
+
+fn auth_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<BitbucketAuth> {
+    let lookup = |key: &str| lookup(key).filter(|v| !v.trim().is_empty());
+    if let Some(token) = lookup(BITBUCKET_TOKEN) {
+        return Ok(BitbucketAuth::Bearer(token.into()));
+    }
+    match (lookup(BITBUCKET_USERNAME), lookup(BITBUCKET_APP_PASSWORD)) {
+        (Some(username), Some(password)) => Ok(BitbucketAuth::Basic {
+            username,
+            password: password.into(),
+        }),
+        _ => bail!(
+            "Bitbucket credentials not found.\n\
+             Set {BITBUCKET_TOKEN}, or {BITBUCKET_USERNAME} and {BITBUCKET_APP_PASSWORD}, \
+             in the environment or ~/.omni-dev/settings.json."
+        ),
+    }
+}
+
+/// Client for the Bitbucket pull request endpoints of one repository.
+#[derive(Debug, Clone)]
+pub struct BitbucketClient {
+    client: reqwest::Client,
+    repo: BitbucketRepo,
+    auth: BitbucketAuth,
+}
+
+impl BitbucketClient {
+    /// Creates a client for `repo` with the given credentials.
+    pub fn new(repo: BitbucketRepo, auth: BitbucketAuth) -> Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            repo,
+            auth,
+        })
+    }
+
+    /// Creates a client for `repo` using credentials from [`load_auth`].
+    pub fn from_env(repo: BitbucketRepo) -> Result<Self> {
+        Self::new(repo, load_auth()?)
+    }
+
+    /// Returns the repository this client targets.
+    pub fn repo(&self) -> &BitbucketRepo {
+        &self.repo
+    }
+
+    async fn request(&self, method: &str, url: &str, body: Option<&Value>) -> Result<Value> {
+        send_json(
+            &self.client,
+            self.forge_name(),
+            method,
+            url,
+            &self.auth.header_value(),
+            body,
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl PullRequestApi for BitbucketClient {
+    fn forge_name(&self) -> &'static str {
+        "Bitbucket"
+    }
+
+    async fn verify_access(&self) -> Result<()> {
+        self.request("GET", &self.repo.api_path(), None)
+            .await
+            .map(|_| ())
+            .with_context(|| {
+                format!(
+                    "Bitbucket API cannot access {}/{}",
+                    self.repo.workspace, self.repo.repo_slug
+                )
+            })
+    }
+
+    async fn list_branch_prs(&self, branch: &str) -> Result<Vec<PullRequest>> {
+        let query = format!("source.branch.name=\"{}\"", branch.replace('"', "\\\""));
+        let mut url = url::Url::parse(&format!("{}/pullrequests", self.repo.api_path()))
+            .context("Invalid Bitbucket API URL")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("q", &query);
+            pairs.append_pair("pagelen", "50");
+            pairs.append_pair("state", "OPEN");
+        }
+
+        let response = self.request("GET", url.as_str(), None).await?;
+        Ok(parse_pull_request_page(&response))
+    }
+
+    async fn latest_branch_pr(&self, branch: &str) -> Result<Option<PullRequest>> {
+        let query = format!("source.branch.name=\"{}\"", branch.replace('"', "\\\""));
+        let mut url = url::Url::parse(&format!("{}/pullrequests", self.repo.api_path()))
+            .context("Invalid Bitbucket API URL")?;
+        {
+            let mut pairs = url.query_pairs_mut();
+            pairs.append_pair("q", &query);
+            pairs.append_pair("sort", "-created_on");
+            pairs.append_pair("pagelen", "1");
+            // Without explicit states, only open pull requests are listed.
+            for state in ["OPEN", "MERGED", "DECLINED", "SUPERSEDED"] {
+                pairs.append_pair("state", state);
+            }
+        }
+
+        let response = self.request("GET", url.as_str(), None).await?;
+        Ok(parse_pull_request_page(&response).into_iter().next())
+    }
+
+    async fn create_pr(
+        &self,
+        source: &str,
+        destination: Option<&str>,
+        title: &str,
+        description: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        let body = create_request_body(source, destination, title, description, draft);
+        let response = self
+            .request(
+                "POST",
+                &format!("{}/pullrequests", self.repo.api_path()),
+                Some(&body),
+            )
+            .await?;
+        parse_pull_request(&response).context("Unexpected response creating Bitbucket PR")
+    }
+
+    async fn update_pr(
+        &self,
+        number: u64,
+        title: &str,
+        description: &str,
+        destination: Option<&str>,
+    ) -> Result<PullRequest> {
+        let body = update_request_body(title, description, destination);
+        let response = self
+            .request(
+                "PUT",
+                &format!("{}/pullrequests/{number}", self.repo.api_path()),
+                Some(&body),
+            )
+            .await?;
+        parse_pull_request(&response).context("Unexpected response updating Bitbucket PR")
+    }
+
+    async fn mark_ready(&self, number: u64) -> Result<()> {
+        let path = format!("{}/pullrequests/{number}", self.repo.api_path());
+        let pr = self.request("GET", &path, None).await?;
+        if !pr.get("draft").and_then(Value::as_bool).unwrap_or(false) {
+            return Ok(());
+        }
+        let title = pr
+            .get("title")
+            .and_then(Value::as_str)
+            .context("Bitbucket PR response has no title")?;
+        self.request(
+            "PUT",
+            &path,
+            Some(&json!({ "title": title, "draft": false })),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn pr_commits(&self, number: u64) -> Result<Vec<String>> {
+        let mut hashes = Vec::new();
+        let mut url = Some(format!(
+            "{}/pullrequests/{number}/commits?pagelen=100",
+            self.repo.api_path()
+        ));
+        for _ in 0..MAX_COMMIT_PAGES {
+            let Some(page_url) = url.take() else {
+                break;
+            };
+            let page = self.request("GET", &page_url, None).await?;
+            let values = page
+                .get("values")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            hashes.extend(commit_hashes(values, "/hash"));
+            url = page.get("next").and_then(Value::as_str).map(str::to_string);
+        }
+        Ok(hashes)
+    }
+
+    async fn check_state(&self, number: u64) -> Result<PrCheckState> {
+        let response = self
+            .request(
+                "GET",
+                &format!(
+                    "{}/pullrequests/{number}/statuses?pagelen=100",
+                    self.repo.api_path()
+                ),
+                None,
+            )
+            .await?;
+        Ok(statuses_check_state(&response))
+    }
+
+    async fn review_comments(&self, number: u64) -> Result<Vec<ReviewComment>> {
+        // Newest first, so the one page read holds the latest discussion.
+        let page = self
+            .request(
+                "GET",
+                &format!(
+                    "{}/pullrequests/{number}/comments?pagelen=100&sort=-created_on",
+                    self.repo.api_path()
+                ),
+                None,
+            )
+            .await?;
+        let live: Vec<Value> = page
+            .get("values")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter(|c| !c.get("deleted").and_then(Value::as_bool).unwrap_or(false))
+            .cloned()
+            .collect();
+        let mut comments = review_comments_at(
+            &live,
+            "/user/display_name",
+            "/content/raw",
+            Some("/inline/path"),
+            "/created_on",
+        );
+        comments.reverse();
+        Ok(comments)
+    }
+
+    async fn upsert_pr_comment(&self, number: u64, marker: &str, body: &str) -> Result<()> {
+        let comments_path = format!("{}/pullrequests/{number}/comments", self.repo.api_path());
+        let mut existing = None;
+        let mut url = Some(format!("{comments_path}?pagelen=100"));
+        for _ in 0..MAX_COMMENT_PAGES {
+            let Some(page_url) = url.take() else {
+                break;
+            };
+            let page = self.request("GET", &page_url, None).await?;
+            existing = find_live_comment(&page, marker);
+            if existing.is_some() {
+                break;
+            }
+            url = page.get("next").and_then(Value::as_str).map(str::to_string);
+        }
+        let payload = json!({ "content": { "raw": body } });
+        match existing {
+            Some(id) => {
+                self.request("PUT", &format!("{comments_path}/{id}"), Some(&payload))
+                    .await
+            }
+            None => self.request("POST", &comments_path, Some(&payload)).await,
+        }
+        .map(|_| ())
+    }
+
+    async fn merge_pr(
+        &self,
+        number: u64,
+        strategy: MergeStrategy,
+        message: Option<&str>,
+    ) -> Result<()> {
+        let body = merge_request_body(strategy, message);
+        self.request(
+            "POST",
+            &format!("{}/pullrequests/{number}/merge", self.repo.api_path()),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+/// Builds the JSON body for `POST /pullrequests`.
+fn create_request_body(
+    source: &str,
+    destination: Option<&str>,
+    title: &str,
+    description: &str,
+    draft: bool,
+) -> Value {
+    let mut body = json!({
+        "title": title,
+        "description": description,
+        "source": { "branch": { "name": source } },
+        "draft": draft,
+    });
+    if let Some(destination) = destination {
+        body["destination"] = json!({ "branch": { "name": destination } });
+    }
+    body
+}
+
+/// Builds the JSON body for `PUT /pullrequests/{id}`.
+fn update_request_body(title: &str, description: &str, destination: Option<&str>) -> Value {
+    let mut body = json!({
+        "title": title,
+        "description": description,
+    });
+    if let Some(destination) = destination {
+        body["destination"] = json!({ "branch": { "name": destination } });
+    }
+    body
+}
+
+/// Builds the JSON body for `POST /pullrequests/{id}/merge`.
+fn merge_request_body(strategy: MergeStrategy, message: Option<&str>) -> Value {
+    let merge_strategy = match strategy {
+        MergeStrategy::Squash => "squash",
+        MergeStrategy::Rebase => "rebase_fast_forward",
+        MergeStrategy::Merge => "merge_commit",
+    };
+    let mut body = json!({ "merge_strategy": merge_strategy });
+    if let Some(message) = message {
+        body["message"] = json!(message.trim());
+    }
+    body
+}
+
+/// Finds the comment containing `marker` on one page of pull request
+/// comments, ignoring deleted comments (Bitbucket keeps them, emptied).
+fn find_live_comment(page: &Value, marker: &str) -> Option<u64> {
+    let comments = page.get("values").and_then(Value::as_array)?;
+    find_marked_comment(
+        comments
+            .iter()
+            .filter(|c| !c.get("deleted").and_then(Value::as_bool).unwrap_or(false)),
+        marker,
+        "/content/raw",
+    )
+}
+
+/// Reduces a page of pull request build statuses to one verdict: any
+/// `FAILED` or `STOPPED` build fails, any `INPROGRESS` one is pending.
+fn statuses_check_state(page: &Value) -> PrCheckState {
+    let states: Vec<&str> = page
+        .get("values")
+        .and_then(Value::as_array)
+        .map(|values| {
+            values
+                .iter()
+                .filter_map(|status| status.get("state").and_then(Value::as_str))
+                .collect()
+        })
+        .unwrap_or_default();
+    if states.is_empty() {
+        PrCheckState::None
+    } else if states.iter().any(|s| matches!(*s, "FAILED" | "STOPPED")) {
+        PrCheckState::Failure
+    } else if states.iter().all(|s| *s == "SUCCESSFUL") {
+        PrCheckState::Success
+    } else {
+        PrCheckState::Pending
+    }
+}
+
+/// Extracts the pull requests from a paginated `/pullrequests` response.
+fn parse_pull_request_page(page: &Value) -> Vec<PullRequest> {
+    page.get("values")
+        .and_then(Value::as_array)
+        .map(|values| values.iter().filter_map(parse_pull_request).collect())
+        .unwrap_or_default()
+}
+
+/// Maps a Bitbucket pull request object onto [`PullRequest`].
+///
+/// States are normalised to the upper-case names `gh` reports, with
+/// Bitbucket's `DECLINED` and `SUPERSEDED` reported as `CLOSED`.
+fn parse_pull_request(pr: &Value) -> Option<PullRequest> {
+    let number = pr.get("id").and_then(Value::as_u64)?;
+    let title = pr.get("title").and_then(Value::as_str)?;
+    let state = match pr.get("state").and_then(Value::as_str).unwrap_or("OPEN") {
+        "DECLINED" | "SUPERSEDED" => "CLOSED",
+        other => other,
+    };
+    let url = pr
+        .pointer("/links/html/href")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let body = pr
+        .get("description")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let base = pr
+        .pointer("/destination/branch/name")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    Some(PullRequest {
+        number,
+        title: title.to_string(),
+        state: state.to_string(),
+        url: url.to_string(),
+        body: body.to_string(),
+        base: base.to_string(),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn repo(workspace: &str, slug: &str) -> Option<BitbucketRepo> {
+        Some(BitbucketRepo {
+            workspace: workspace.to_string(),
+            repo_slug: slug.to_string(),
+        })
+    }
+
+    #[test]
+    fn parses_bitbucket_remote_urls() {
+        assert_eq!(
+            BitbucketRepo::from_remote_url("git@bitbucket.org:acme/widgets.git"),
+            repo("acme", "widgets")
+        );
+        assert_eq!(
+            BitbucketRepo::from_remote_url("https://jdoe@bitbucket.org/acme/widgets.git"),
+            repo("acme", "widgets")
+        );
+        assert_eq!(
+            BitbucketRepo::from_remote_url("ssh://git@bitbucket.org/acme/widgets"),
+            repo("acme", "widgets")
+        );
+        assert_eq!(
+            BitbucketRepo::from_remote_url("https://bitbucket.org/acme/widgets/"),
+            repo("acme", "widgets")
+        );
+    }
+
+    #[test]
+    fn ignores_non_bitbucket_remotes() {
+        assert!(BitbucketRepo::from_remote_url("git@github.com:acme/widgets.git").is_none());
+        assert!(BitbucketRepo::from_remote_url("https://github.com/acme/widgets").is_none());
+        assert!(
+            BitbucketRepo::from_remote_url("https://bitbucket.example.com/acme/widgets").is_none()
+        );
+        assert!(BitbucketRepo::from_remote_url("git@bitbucket.org:acme.git").is_none());
+    }
+
+    #[test]
+    fn token_takes_precedence_over_basic_auth() {
+        let auth = auth_from_lookup(|key| match key {
+            BITBUCKET_TOKEN => Some("tok".to_string()),
+            BITBUCKET_USERNAME => Some("jdoe".to_string()),
+            BITBUCKET_APP_PASSWORD => Some("pw".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(auth.header_value(), "Bearer tok");
+    }
+
+    #[test]
+    fn basic_auth_is_base64_encoded() {
+        let auth = auth_from_lookup(|key| match key {
+            BITBUCKET_USERNAME => Some("jdoe".to_string()),
+            BITBUCKET_APP_PASSWORD => Some("pw".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(auth.header_value(), "Basic amRvZTpwdw==");
+        assert!(format!("{auth:?}").contains("<redacted>"));
+    }
+
+    #[test]
+    fn missing_credentials_error() {
+        assert!(auth_from_lookup(|_| None).is_err());
+        assert!(
+            auth_from_lookup(|key| (key == BITBUCKET_USERNAME).then(|| "jdoe".into())).is_err()
+        );
+    }
+
+    #[test]
+    fn maps_pull_request_page() {
+        let page = json!({
+            "values": [
+                {
+                    "id": 7,
+                    "title": "feat: widgets",
+                    "state": "OPEN",
+                    "description": "Adds widgets",
+                    "links": { "html": { "href": "https://bitbucket.org/acme/widgets/pull-requests/7" } },
+                    "destination": { "branch": { "name": "main" } }
+                },
+                { "id": 3, "title": "old", "state": "DECLINED" },
+                { "title": "no id" }
+            ]
+        });
+        let prs = parse_pull_request_page(&page);
+        assert_eq!(prs.len(), 2);
+        assert_eq!(prs[0].number, 7);
+        assert_eq!(prs[0].state, "OPEN");
+        assert_eq!(
+            prs[0].url,
+            "https://bitbucket.org/acme/widgets/pull-requests/7"
+        );
+        assert_eq!(prs[0].body, "Adds widgets");
+        assert_eq!(prs[0].base, "main");
+        assert_eq!(prs[1].state, "CLOSED");
+        assert!(prs[1].base.is_empty());
+    }
+
+    #[test]
+    fn create_body_omits_destination_when_unset() {
+        let body = create_request_body("feature/x", None, "T", "D", true);
+        assert_eq!(body["source"]["branch"]["name"], "feature/x");
+        assert_eq!(body["draft"], true);
+        assert!(body.get("destination").is_none());
+
+        let body = create_request_body("feature/x", Some("develop"), "T", "D", false);
+        assert_eq!(body["destination"]["branch"]["name"], "develop");
+    }
+
+    #[test]
+    fn update_body_carries_destination_only_when_changing() {
+        let body = update_request_body("T", "D", None);
+        assert_eq!(body["title"], "T");
+        assert!(body.get("destination").is_none());
+        let body = update_request_body("T", "D", Some("release"));
+        assert_eq!(body["destination"]["branch"]["name"], "release");
+    }
+
+    #[test]
+    fn merge_body_maps_strategies() {
+        let body = merge_request_body(MergeStrategy::Squash, Some("feat: x\n\nbody\n"));
+        assert_eq!(body["merge_strategy"], "squash");
+        assert_eq!(body["message"], "feat: x\n\nbody");
+        let body = merge_request_body(MergeStrategy::Rebase, None);
+        assert_eq!(body["merge_strategy"], "rebase_fast_forward");
+        assert!(body.get("message").is_none());
+    }
+
+    #[test]
+    fn skips_deleted_comments() {
+        let page = json!({
+            "values": [
+                {"id": 1, "deleted": true, "content": {"raw": "<!-- m -->"}},
+                {"id": 2, "content": {"raw": "report <!-- m -->"}},
+            ]
+        });
+        assert_eq!(find_live_comment(&page, "<!-- m -->"), Some(2));
+        assert_eq!(find_live_comment(&json!({}), "<!-- m -->"), None);
+    }
+
+    #[test]
+    fn reduces_build_statuses() {
+        let page = |states: &[&str]| {
+            let values: Vec<Value> = states.iter().map(|s| json!({ "state": s })).collect();
+            json!({ "values": values })
+        };
+        assert_eq!(statuses_check_state(&page(&[])), PrCheckState::None);
+        assert_eq!(
+            statuses_check_state(&page(&["SUCCESSFUL", "SUCCESSFUL"])),
+            PrCheckState::Success
+        );
+        assert_eq!(
+            statuses_check_state(&page(&["INPROGRESS", "STOPPED"])),
+            PrCheckState::Failure
+        );
+        assert_eq!(
+            statuses_check_state(&page(&["SUCCESSFUL", "INPROGRESS"])),
+            PrCheckState::Pending
+        );
+    }
+}
@@ -113,7 +113,8 @@ pub(crate) fn resolve_existing_token_with(
     }
 }
 
-fn read_token_file(path: &Path) -> Result<String> {
+/// Reads a token file, which on Unix must be `0600` (owner-only).
+pub(crate) fn read_token_file(path: &Path) -> Result<String> {
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
@@ -3,11 +3,17 @@
 pub mod ai;
 pub mod backend;
 pub(crate) mod batch;
+pub mod check_cache;
 pub mod client;
+pub mod command_template;
+pub(crate) mod concurrency;
 pub mod context;
+pub(crate) mod dedup;
 pub(crate) mod diff_pack;
 pub mod error;
+pub(crate) mod job;
 pub mod model_config;
+pub mod prompt_template;
 pub mod prompts;
 pub mod response_schema;
 #[cfg(test)]
@@ -16,6 +16,7 @@ use serde_json::Value;
 use crate::claude::error::ClaudeError;
 use crate::claude::model_config::get_model_registry;
 use crate::request_log;
+use crate::utils::network::NetworkSettings;
 
 /// Default HTTP request timeout for AI API calls.
 ///
@@ -100,10 +101,11 @@ fn timeout_from_secs(raw: Option<String>) -> Duration {
         .map_or(DEFAULT_REQUEST_TIMEOUT, Duration::from_secs)
 }
 
-/// Builds an HTTP client with the resolved request timeout.
+/// Builds an HTTP client with the resolved request timeout and the
+/// configured proxy and TLS settings.
 pub(crate) fn build_http_client() -> Result<Client> {
-    Client::builder()
-        .timeout(request_timeout())
+    NetworkSettings::load()
+        .apply_reqwest(Client::builder().timeout(request_timeout()))?
         .build()
         .context("Failed to build HTTP client")
 }
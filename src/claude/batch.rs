@@ -183,6 +183,8 @@ mod tests {
                 diff_summary: "test.rs | 10 ++++".to_string(),
                 diff_file: tmp.path().to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         (commit, tmp)
@@ -305,6 +307,8 @@ mod tests {
                         byte_len: 300,
                     },
                 ],
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
 
@@ -0,0 +1,341 @@
+//! Per-commit cache of `check` results.
+//!
+//! A commit hash already pins the message, tree, and parents, so a check
+//! verdict only goes stale when the inputs around the commit change: the
+//! guidelines, the valid scopes and commit types, the model, whether
+//! suggestions were requested, whether the cross-commit coherence pass runs,
+//! or the omni-dev version (which owns the prompts). All of those are folded
+//! into the cache key, so repeated CI runs on an unchanged branch only send
+//! new commits to the AI.
+//!
+//! The cache is strictly best effort — read and write failures are logged at
+//! `tracing::debug` and treated as misses.
+
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::data::check::{CheckReport, CommitCheckResult};
+use crate::data::context::{ScopeDefinition, TypeDefinition};
+use crate::utils::digest::{hex_encode, sha256_hex};
+use crate::utils::json_store;
+
+/// Environment variable overriding the cache directory.
+pub const CHECK_CACHE_DIR_ENV: &str = "OMNI_DEV_CHECK_CACHE_DIR";
+
+/// Derives cache keys for one check invocation.
+///
+/// Everything except the commit hash is digested once up front.
+#[derive(Debug, Clone)]
+pub struct CheckCacheKeyer {
+    context_digest: String,
+}
+
+impl CheckCacheKeyer {
+    /// Creates a keyer for the given guidelines, scopes, commit types, model,
+    /// suggestion setting, and coherence setting.
+    pub fn new(
+        guidelines: Option<&str>,
+        scopes: &[ScopeDefinition],
+        commit_types: &[TypeDefinition],
+        model: &str,
+        include_suggestions: bool,
+        coherence: bool,
+    ) -> Self {
+        let guidelines_digest = digest_hex(guidelines.unwrap_or_default());
+        let scopes_digest = digest_hex(&serde_json::to_string(scopes).unwrap_or_default());
+        let types_digest = digest_hex(&serde_json::to_string(commit_types).unwrap_or_default());
+        let mut hasher = Sha256::new();
+        for part in [
+            env!("CARGO_PKG_VERSION"),
+            guidelines_digest.as_str(),
+            scopes_digest.as_str(),
+            types_digest.as_str(),
+            model,
+            if include_suggestions {
+                "suggest"
+            } else {
+                "no-suggest"
+            },
+            if coherence {
+                "coherence"
+            } else {
+                "no-coherence"
+            },
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        Self {
+            context_digest: hex_encode(&hasher.finalize()),
+        }
+    }
+
+    /// Returns the cache key for a full commit hash.
+    pub fn key(&self, commit_hash: &str) -> String {
+        digest_hex(&format!("{}\0{commit_hash}", self.context_digest))
+    }
+}
+
+/// On-disk store of check results, one JSON file per key.
+#[derive(Debug, Clone)]
+pub struct CheckCache {
+    dir: PathBuf,
+}
+
+impl CheckCache {
+    /// Creates a cache rooted at `dir` (created lazily on first write).
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Opens the default cache: `OMNI_DEV_CHECK_CACHE_DIR`, else
+    /// `cache_dir/omni-dev/check`. Returns `None` when neither resolves.
+    pub fn open_default() -> Option<Self> {
+        if let Ok(dir) = std::env::var(CHECK_CACHE_DIR_ENV) {
+            if !dir.is_empty() {
+                return Some(Self::at(dir));
+            }
+        }
+        let base = dirs::cache_dir()?;
+        Some(Self::at(base.join("omni-dev").join("check")))
+    }
+
+    /// Returns the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the cached result for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<CommitCheckResult> {
+        json_store::read_json(&self.entry_path(key), "check cache")
+    }
+
+    /// Stores `result` under `key`. Best effort: failures are only logged.
+    pub fn put(&self, key: &str, result: &CommitCheckResult) {
+        if let Err(e) = json_store::write_json(&self.entry_path(key), result) {
+            tracing::debug!("check cache: failed to store entry: {e}");
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Splits `hashes` into results served from `cache` and the hashes that
+/// still need an AI check. Without a cache every hash is pending.
+pub(crate) fn partition(
+    cache: Option<&CheckCache>,
+    keyer: &CheckCacheKeyer,
+    hashes: &[String],
+) -> (Vec<CommitCheckResult>, Vec<String>) {
+    let Some(cache) = cache else {
+        return (Vec::new(), hashes.to_vec());
+    };
+    let mut cached = Vec::new();
+    let mut pending = Vec::new();
+    for hash in hashes {
+        match cache.get(&keyer.key(hash)) {
+            Some(mut result) => {
+                result.hash.clone_from(hash);
+                cached.push(result);
+            }
+            None => pending.push(hash.clone()),
+        }
+    }
+    (cached, pending)
+}
+
+/// Stores each fresh result under the full hash it resolves to, then merges
+/// cached and fresh results back into `order` (the range's commit order).
+///
+/// Fresh results whose hash matches no commit in `order` are kept at the end
+/// uncached rather than dropped.
+pub(crate) fn store_and_merge(
+    cache: Option<&CheckCache>,
+    keyer: &CheckCacheKeyer,
+    order: &[String],
+    cached: Vec<CommitCheckResult>,
+    fresh: CheckReport,
+) -> CheckReport {
+    let mut slots: Vec<Option<CommitCheckResult>> = vec![None; order.len()];
+    let mut unmatched = Vec::new();
+
+    for result in cached
+        .into_iter()
+        .chain(fresh.commits.into_iter().map(|mut r| {
+            if let Some(full) = resolve_hash(&r.hash, order) {
+                if let Some(cache) = cache {
+                    cache.put(&keyer.key(full), &r);
+                }
+                r.hash = full.to_string();
+            }
+            r
+        }))
+    {
+        match order.iter().position(|h| *h == result.hash) {
+            Some(idx) if slots[idx].is_none() => slots[idx] = Some(result),
+            _ => unmatched.push(result),
+        }
+    }
+
+    let commits = slots.into_iter().flatten().chain(unmatched).collect();
+    CheckReport::new(commits)
+}
+
+/// Resolves a (possibly short) hash from an AI response to a full hash.
+fn resolve_hash<'a>(short: &str, candidates: &'a [String]) -> Option<&'a str> {
+    if short.is_empty() {
+        return None;
+    }
+    candidates
+        .iter()
+        .find(|c| c.starts_with(short) || short.starts_with(c.as_str()))
+        .map(String::as_str)
+}
+
+fn digest_hex(input: &str) -> String {
+    sha256_hex(input)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::data::check::{CommitIssue, IssueSeverity};
+
+    fn make_result(hash: &str, passes: bool) -> CommitCheckResult {
+        CommitCheckResult {
+            hash: hash.to_string(),
+            message: "feat: thing".to_string(),
+            issues: if passes {
+                vec![]
+            } else {
+                vec![CommitIssue {
+                    severity: IssueSeverity::Error,
+                    section: "Format".to_string(),
+                    rule: "type".to_string(),
+                    explanation: "bad".to_string(),
+                }]
+            },
+            suggestion: None,
+            passes,
+            summary: None,
+        }
+    }
+
+    fn scope(name: &str) -> ScopeDefinition {
+        ScopeDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            examples: vec![],
+            file_patterns: vec![],
+        }
+    }
+
+    #[test]
+    fn key_changes_with_every_input() {
+        let base =
+            CheckCacheKeyer::new(Some("g"), &[scope("cli")], &[], "m", true, true).key("abc");
+        let variants = [
+            CheckCacheKeyer::new(Some("g2"), &[scope("cli")], &[], "m", true, true).key("abc"),
+            CheckCacheKeyer::new(None, &[scope("cli")], &[], "m", true, true).key("abc"),
+            CheckCacheKeyer::new(Some("g"), &[scope("api")], &[], "m", true, true).key("abc"),
+            CheckCacheKeyer::new(
+                Some("g"),
+                &[scope("cli")],
+                &[TypeDefinition {
+                    name: "infra".to_string(),
+                    description: String::new(),
+                }],
+                "m",
+                true,
+                true,
+            )
+            .key("abc"),
+            CheckCacheKeyer::new(Some("g"), &[scope("cli")], &[], "m2", true, true).key("abc"),
+            CheckCacheKeyer::new(Some("g"), &[scope("cli")], &[], "m", false, true).key("abc"),
+            CheckCacheKeyer::new(Some("g"), &[scope("cli")], &[], "m", true, false).key("abc"),
+            CheckCacheKeyer::new(Some("g"), &[scope("cli")], &[], "m", true, true).key("abd"),
+        ];
+        for variant in variants {
+            assert_ne!(base, variant);
+        }
+        assert_eq!(
+            base,
+            CheckCacheKeyer::new(Some("g"), &[scope("cli")], &[], "m", true, true).key("abc")
+        );
+    }
+
+    #[test]
+    fn put_then_get_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CheckCache::at(dir.path().join("check"));
+        assert!(cache.get("k").is_none());
+        cache.put("k", &make_result("abc", false));
+        let got = cache.get("k").unwrap();
+        assert_eq!(got.hash, "abc");
+        assert!(!got.passes);
+        assert_eq!(got.issues.len(), 1);
+    }
+
+    #[test]
+    fn corrupt_entry_is_a_miss() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CheckCache::at(dir.path());
+        std::fs::write(dir.path().join("k.json"), "not json").unwrap();
+        assert!(cache.get("k").is_none());
+    }
+
+    #[test]
+    fn partition_without_cache_marks_everything_pending() {
+        let keyer = CheckCacheKeyer::new(None, &[], &[], "m", true, true);
+        let hashes = vec!["a".to_string(), "b".to_string()];
+        let (cached, pending) = partition(None, &keyer, &hashes);
+        assert!(cached.is_empty());
+        assert_eq!(pending, hashes);
+    }
+
+    #[test]
+    fn store_and_merge_caches_fresh_results_and_preserves_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = CheckCache::at(dir.path());
+        let keyer = CheckCacheKeyer::new(None, &[], &[], "m", true, true);
+        let order = vec!["aaaa1111".to_string(), "bbbb2222".to_string()];
+
+        // First run: nothing cached, AI answers with short hashes.
+        let (cached, pending) = partition(Some(&cache), &keyer, &order);
+        assert!(cached.is_empty());
+        assert_eq!(pending.len(), 2);
+        let fresh = CheckReport::new(vec![make_result("bbbb", true), make_result("aaaa", false)]);
+        let report = store_and_merge(Some(&cache), &keyer, &order, cached, fresh);
+        assert_eq!(report.commits[0].hash, "aaaa1111");
+        assert_eq!(report.commits[1].hash, "bbbb2222");
+        assert_eq!(report.summary.error_count, 1);
+
+        // Second run: both served from the cache.
+        let (cached, pending) = partition(Some(&cache), &keyer, &order);
+        assert!(pending.is_empty());
+        let report = store_and_merge(
+            Some(&cache),
+            &keyer,
+            &order,
+            cached,
+            CheckReport::new(vec![]),
+        );
+        assert_eq!(report.commits.len(), 2);
+        assert_eq!(report.commits[0].hash, "aaaa1111");
+        assert!(!report.commits[0].passes);
+    }
+
+    #[test]
+    fn store_and_merge_keeps_unmatched_results() {
+        let keyer = CheckCacheKeyer::new(None, &[], &[], "m", true, true);
+        let order = vec!["aaaa1111".to_string()];
+        let fresh = CheckReport::new(vec![make_result("zzzz", true)]);
+        let report = store_and_merge(None, &keyer, &order, vec![], fresh);
+        assert_eq!(report.commits.len(), 1);
+        assert_eq!(report.commits[0].hash, "zzzz");
+    }
+}
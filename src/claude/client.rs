@@ -3,6 +3,7 @@
 use anyhow::{Context, Result};
 use tracing::{debug, info, warn};
 
+use crate::claude::dedup::Answered;
 use crate::claude::error::is_transient_ai_error as ai_error_is_transient;
 use crate::claude::token_budget::TokenBudget;
 use crate::claude::{ai::bedrock::BedrockAiClient, ai::claude::ClaudeAiClient};
@@ -33,12 +34,48 @@ const AMENDMENT_PARSE_MAX_RETRIES: u32 = 2;
 pub struct ClaudeClient {
     /// AI client implementation.
     ai_client: Box<dyn AiClient>,
+    /// Project commit types from `types.yaml` (empty for the built-in types).
+    commit_types: Vec<crate::data::context::TypeDefinition>,
+    /// Whether dependency-bump commits get a templated message instead of an
+    /// AI-written one.
+    template_dependency_bumps: bool,
+    /// Answers already received, reused for repeated content.
+    answered: Answered,
 }
 
 impl ClaudeClient {
     /// Creates a new Claude client with the provided AI client implementation.
     pub fn new(ai_client: Box<dyn AiClient>) -> Self {
-        Self { ai_client }
+        Self {
+            ai_client,
+            commit_types: Vec::new(),
+            template_dependency_bumps: false,
+            answered: Answered::default(),
+        }
+    }
+
+    /// Sets the project's commit types, honoured by check prompts and
+    /// pre-validation.
+    #[must_use]
+    pub fn with_commit_types(
+        mut self,
+        commit_types: Vec<crate::data::context::TypeDefinition>,
+    ) -> Self {
+        self.commit_types = commit_types;
+        self
+    }
+
+    /// Writes the messages of dependency-bump commits from a template listing
+    /// the updated packages, without asking the AI.
+    #[must_use]
+    pub fn with_dependency_bump_template(mut self, enabled: bool) -> Self {
+        self.template_dependency_bumps = enabled;
+        self
+    }
+
+    /// Returns the project's commit types (empty for the built-in types).
+    pub fn commit_types(&self) -> &[crate::data::context::TypeDefinition] {
+        &self.commit_types
     }
 
     /// Returns metadata about the AI client.
@@ -100,12 +137,17 @@ impl ClaudeClient {
     /// [`AiClientCapabilities::supports_response_schema`](crate::claude::ai::AiClientCapabilities::supports_response_schema)
     /// `= false`, in which case [`schema_if_supported`](Self::schema_if_supported)
     /// at the call site returns `None` and we take the second branch.
+    ///
+    /// A prompt whose earlier response parsed is answered from memory.
     async fn send_with_optional_schema(
         &self,
         system_prompt: &str,
         user_prompt: &str,
         schema: Option<&serde_json::Value>,
     ) -> Result<String> {
+        if let Some(content) = self.answered.response(system_prompt, user_prompt) {
+            return Ok(content);
+        }
         match schema {
             Some(s) => {
                 let opts = RequestOptions::default().with_response_schema(s.clone());
@@ -566,7 +608,7 @@ impl ClaudeClient {
                 )
             })?;
 
-            partial.run_pre_validation_checks(valid_scopes);
+            partial.run_pre_validation_checks(valid_scopes, &self.commit_types);
 
             let partial_view = RepositoryViewForAI::from_repository_view(repo_view.clone())
                 .context("Failed to enhance repository view with diff content")?
@@ -722,12 +764,14 @@ impl ClaudeClient {
             .await
     }
 
-    /// Creates a new Claude client with API key from environment variables.
+    /// Creates a new Claude client with API key from environment variables,
+    /// settings, or the configured `anthropic` secret helper.
     pub fn from_env(model: String) -> Result<Self> {
-        // Try to get API key from environment variables
-        let api_key = std::env::var("CLAUDE_API_KEY")
-            .or_else(|_| std::env::var("ANTHROPIC_API_KEY"))
-            .map_err(|_| ClaudeError::ApiKeyNotFound)?;
+        use crate::utils::env::EnvSource;
+
+        let api_key = crate::utils::settings::SettingsEnv::load()
+            .var_any(&["CLAUDE_API_KEY", "ANTHROPIC_API_KEY"])
+            .ok_or(ClaudeError::ApiKeyNotFound)?;
 
         let ai_client = ClaudeAiClient::new(model, api_key, None)?;
         Ok(Self::new(Box::new(ai_client)))
@@ -754,6 +798,20 @@ impl ClaudeClient {
         repo_view: &RepositoryView,
         fresh: bool,
     ) -> Result<AmendmentFile> {
+        // Dependency bumps are described from their package lists instead,
+        // and commits repeating an answered diff reuse its amendment
+        let (bump_amendments, repo_view) = self.amend_dependency_bumps(repo_view, fresh).await?;
+        let dedup = self.answered.deduplicate(repo_view, fresh);
+        if dedup.view.commits.is_empty() {
+            let mut amendments = self.answered.repeated_amendments(&dedup);
+            amendments.extend(bump_amendments);
+            return Ok(AmendmentFile {
+                schema_version: crate::data::SCHEMA_VERSION,
+                amendments,
+            });
+        }
+        let repo_view = &dedup.view;
+
         // Convert to AI-enhanced view with diff content
         let ai_repo_view =
             RepositoryViewForAI::from_repository_view_with_options(repo_view.clone(), fresh)
@@ -763,28 +821,39 @@ impl ClaudeClient {
         let build_user_prompt = |yaml: &str| prompts::generate_user_prompt(yaml);
 
         // Try full view first; fall back to per-commit split dispatch
-        match self.try_full_diff_budget(&ai_repo_view, &system_prompt, &build_user_prompt)? {
-            Ok(user_prompt) => {
-                self.send_and_parse_amendment_with_retry(&system_prompt, &user_prompt)
-                    .await
-            }
-            Err(_exceeded) => {
-                let mut amendments = Vec::new();
-                for commit in &repo_view.commits {
-                    let amendment = self
-                        .generate_amendment_for_commit(
-                            commit,
-                            &ai_repo_view,
-                            &system_prompt,
-                            &build_user_prompt,
-                            fresh,
-                        )
-                        .await?;
-                    amendments.push(amendment);
+        let mut amendment_file =
+            match self.try_full_diff_budget(&ai_repo_view, &system_prompt, &build_user_prompt)? {
+                Ok(user_prompt) => {
+                    self.send_and_parse_amendment_with_retry(&system_prompt, &user_prompt)
+                        .await?
                 }
-                Ok(AmendmentFile { amendments })
-            }
-        }
+                Err(_exceeded) => {
+                    let mut amendments = Vec::new();
+                    for commit in &repo_view.commits {
+                        let amendment = self
+                            .generate_amendment_for_commit(
+                                commit,
+                                &ai_repo_view,
+                                &system_prompt,
+                                &build_user_prompt,
+                                fresh,
+                            )
+                            .await?;
+                        amendments.push(amendment);
+                    }
+                    AmendmentFile {
+                        schema_version: crate::data::SCHEMA_VERSION,
+                        amendments,
+                    }
+                }
+            };
+        self.answered
+            .remember_amendments(&dedup, &amendment_file.amendments);
+        amendment_file
+            .amendments
+            .extend(self.answered.repeated_amendments(&dedup));
+        amendment_file.amendments.extend(bump_amendments);
+        Ok(amendment_file)
     }
 
     /// Generates contextual commit message amendments with enhanced intelligence.
@@ -812,6 +881,20 @@ impl ClaudeClient {
         context: &CommitContext,
         fresh: bool,
     ) -> Result<AmendmentFile> {
+        // Dependency bumps are described from their package lists instead,
+        // and commits repeating an answered diff reuse its amendment
+        let (bump_amendments, repo_view) = self.amend_dependency_bumps(repo_view, fresh).await?;
+        let dedup = self.answered.deduplicate(repo_view, fresh);
+        if dedup.view.commits.is_empty() {
+            let mut amendments = self.answered.repeated_amendments(&dedup);
+            amendments.extend(bump_amendments);
+            return Ok(AmendmentFile {
+                schema_version: crate::data::SCHEMA_VERSION,
+                amendments,
+            });
+        }
+        let repo_view = &dedup.view;
+
         // Convert to AI-enhanced view with diff content
         let ai_repo_view =
             RepositoryViewForAI::from_repository_view_with_options(repo_view.clone(), fresh)
@@ -838,28 +921,89 @@ impl ClaudeClient {
             |yaml: &str| prompts::generate_contextual_user_prompt(yaml, context);
 
         // Try full view first; fall back to per-commit split dispatch
-        match self.try_full_diff_budget(&ai_repo_view, &system_prompt, &build_user_prompt)? {
-            Ok(user_prompt) => {
-                self.send_and_parse_amendment_with_retry(&system_prompt, &user_prompt)
-                    .await
-            }
-            Err(_exceeded) => {
-                let mut amendments = Vec::new();
-                for commit in &repo_view.commits {
-                    let amendment = self
-                        .generate_amendment_for_commit(
-                            commit,
-                            &ai_repo_view,
-                            &system_prompt,
-                            &build_user_prompt,
-                            fresh,
-                        )
-                        .await?;
-                    amendments.push(amendment);
+        let mut amendment_file =
+            match self.try_full_diff_budget(&ai_repo_view, &system_prompt, &build_user_prompt)? {
+                Ok(user_prompt) => {
+                    self.send_and_parse_amendment_with_retry(&system_prompt, &user_prompt)
+                        .await?
                 }
-                Ok(AmendmentFile { amendments })
-            }
+                Err(_exceeded) => {
+                    let mut amendments = Vec::new();
+                    for commit in &repo_view.commits {
+                        let amendment = self
+                            .generate_amendment_for_commit(
+                                commit,
+                                &ai_repo_view,
+                                &system_prompt,
+                                &build_user_prompt,
+                                fresh,
+                            )
+                            .await?;
+                        amendments.push(amendment);
+                    }
+                    AmendmentFile {
+                        schema_version: crate::data::SCHEMA_VERSION,
+                        amendments,
+                    }
+                }
+            };
+        self.answered
+            .remember_amendments(&dedup, &amendment_file.amendments);
+        amendment_file
+            .amendments
+            .extend(self.answered.repeated_amendments(&dedup));
+        amendment_file.amendments.extend(bump_amendments);
+        Ok(amendment_file)
+    }
+
+    /// Generates the amendments for the dependency-bump commits of
+    /// `repo_view` and returns them with a view of the remaining commits.
+    ///
+    /// Bumps are sent together with the dependency-bump prompt, which lists
+    /// each commit's package updates in place of its lockfile diff, or, with
+    /// [`with_dependency_bump_template`](Self::with_dependency_bump_template),
+    /// written from a template without an AI request.
+    async fn amend_dependency_bumps(
+        &self,
+        repo_view: &RepositoryView,
+        fresh: bool,
+    ) -> Result<(Vec<Amendment>, RepositoryView)> {
+        let (bumps, rest): (Vec<_>, Vec<_>) = repo_view
+            .commits
+            .iter()
+            .cloned()
+            .partition(|commit| !commit.analysis.dependency_updates.is_empty());
+        let rest_view = RepositoryView {
+            commits: rest,
+            ..repo_view.clone()
+        };
+        if bumps.is_empty() {
+            return Ok((Vec::new(), rest_view));
+        }
+        debug!(count = bumps.len(), "Dependency-bump commits found");
+
+        if self.template_dependency_bumps {
+            let amendments = bumps
+                .iter()
+                .map(|commit| Amendment {
+                    commit: commit.hash.clone(),
+                    message: crate::git::dependency_bump::template_message(
+                        &commit.analysis.dependency_updates,
+                    ),
+                    summary: String::new(),
+                })
+                .collect();
+            return Ok((amendments, rest_view));
         }
+
+        let system_prompt =
+            self.adjusted_system_prompt(prompts::DEPENDENCY_BUMP_SYSTEM_PROMPT.to_string());
+        let user_prompt = prompts::generate_dependency_bump_user_prompt(&bumps, fresh);
+        let amendment_file = self
+            .send_and_parse_amendment_with_retry(&system_prompt, &user_prompt)
+            .await
+            .context("Failed to generate dependency-bump commit messages")?;
+        Ok((amendment_file.amendments, rest_view))
     }
 
     /// Parses Claude's YAML response into an AmendmentFile.
@@ -919,7 +1063,11 @@ impl ClaudeClient {
                 .await
             {
                 Ok(content) => match self.parse_amendment_response(&content) {
-                    Ok(amendment_file) => return Ok(amendment_file),
+                    Ok(amendment_file) => {
+                        self.answered
+                            .remember_response(system_prompt, user_prompt, &content);
+                        return Ok(amendment_file);
+                    }
                     Err(e) => {
                         if attempt < AMENDMENT_PARSE_MAX_RETRIES {
                             eprintln!(
@@ -1433,9 +1581,12 @@ impl ClaudeClient {
         max_retries: u32,
     ) -> Result<crate::data::check::CheckReport> {
         // Generate system prompt with scopes
-        let system_prompt = self.adjusted_system_prompt(
-            prompts::generate_check_system_prompt_with_scopes(guidelines, valid_scopes),
-        );
+        let system_prompt =
+            self.adjusted_system_prompt(prompts::generate_check_system_prompt_with_types(
+                guidelines,
+                valid_scopes,
+                &self.commit_types,
+            ));
 
         let build_user_prompt =
             |yaml: &str| prompts::generate_check_user_prompt(yaml, include_suggestions);
@@ -1443,7 +1594,7 @@ impl ClaudeClient {
         let mut ai_repo_view = RepositoryViewForAI::from_repository_view(repo_view.clone())
             .context("Failed to enhance repository view with diff content")?;
         for commit in &mut ai_repo_view.commits {
-            commit.run_pre_validation_checks(valid_scopes);
+            commit.run_pre_validation_checks(valid_scopes, &self.commit_types);
         }
 
         // Try full view first; fall back to per-commit split dispatch
@@ -1461,7 +1612,14 @@ impl ClaudeClient {
                         .await
                     {
                         Ok(content) => match self.parse_check_response(&content, repo_view) {
-                            Ok(report) => return Ok(report),
+                            Ok(report) => {
+                                self.answered.remember_response(
+                                    &system_prompt,
+                                    &user_prompt,
+                                    &content,
+                                );
+                                return Ok(report);
+                            }
                             Err(e) => {
                                 if attempt < max_retries {
                                     eprintln!(
@@ -1499,7 +1657,7 @@ impl ClaudeClient {
                         RepositoryViewForAI::from_repository_view(single_view.clone())
                             .context("Failed to enhance single-commit view with diff content")?;
                     for c in &mut single_ai_view.commits {
-                        c.run_pre_validation_checks(valid_scopes);
+                        c.run_pre_validation_checks(valid_scopes, &self.commit_types);
                     }
 
                     match self.try_full_diff_budget(
@@ -2451,6 +2609,7 @@ mod tests {
         std::fs::write(&diff_path, "+added line\n").unwrap();
 
         crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -2484,6 +2643,8 @@ mod tests {
                     diff_summary: "file.rs | 1 +".to_string(),
                     diff_file: diff_path.to_string_lossy().to_string(),
                     file_diffs: Vec::new(),
+                    submodule_changes: Vec::new(),
+                    dependency_updates: Vec::new(),
                 },
             }],
         }
@@ -2619,6 +2780,7 @@ mod tests {
         std::fs::write(&path_b, &diff_b).unwrap();
 
         crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -2672,6 +2834,8 @@ mod tests {
                             byte_len: diff_b.len(),
                         },
                     ],
+                    submodule_changes: Vec::new(),
+                    dependency_updates: Vec::new(),
                 },
             }],
         }
@@ -2746,6 +2910,68 @@ mod tests {
         assert_eq!(result.unwrap().amendments.len(), 1);
     }
 
+    fn make_dependency_bump_repo_view(dir: &tempfile::TempDir) -> crate::data::RepositoryView {
+        let mut repo_view = make_test_repo_view(dir);
+        let commit = &mut repo_view.commits[0];
+        commit.original_message = "Update Cargo.lock".to_string();
+        commit.analysis.dependency_updates = vec![crate::git::DependencyUpdate {
+            name: "serde".to_string(),
+            from: Some("1.0.200".to_string()),
+            to: Some("1.0.210".to_string()),
+        }];
+        repo_view
+    }
+
+    #[tokio::test]
+    async fn generate_amendments_templates_dependency_bumps_without_ai() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_view = make_dependency_bump_repo_view(&dir);
+
+        // No responses queued: any AI call would fail
+        let client = make_configurable_client(vec![]).with_dependency_bump_template(true);
+        let amendments = client
+            .generate_amendments_with_options(&repo_view, true)
+            .await
+            .unwrap()
+            .amendments;
+
+        assert_eq!(amendments.len(), 1);
+        assert_eq!(
+            amendments[0].message,
+            "chore(deps): bump serde from 1.0.200 to 1.0.210"
+        );
+    }
+
+    #[tokio::test]
+    async fn generate_amendments_sends_dependency_bumps_as_package_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo_view = make_dependency_bump_repo_view(&dir);
+        let hash = format!("{:0>40}", 0);
+
+        let (client, _resp_handle, prompt_handle) =
+            make_configurable_client_with_prompts(vec![Ok(valid_amendment_yaml(
+                &hash,
+                "chore(deps): bump serde from 1.0.200 to 1.0.210",
+            ))]);
+        let amendments = client
+            .generate_amendments_with_options(&repo_view, false)
+            .await
+            .unwrap()
+            .amendments;
+        assert_eq!(amendments.len(), 1);
+
+        let prompts = prompt_handle.prompts();
+        assert_eq!(prompts.len(), 1, "expected exactly one AI call");
+        let (system_prompt, user_prompt) = &prompts[0];
+        assert!(system_prompt.starts_with(crate::claude::prompts::DEPENDENCY_BUMP_SYSTEM_PROMPT));
+        assert!(user_prompt.contains("- bump serde from 1.0.200 to 1.0.210"));
+        assert!(user_prompt.contains("Update Cargo.lock"));
+        assert!(
+            !user_prompt.contains("+added line"),
+            "user prompt must NOT include the lockfile diff: {user_prompt}"
+        );
+    }
+
     // ── check split dispatch tests ──────────────────────────────
 
     fn valid_check_yaml_for(hash: &str, passes: bool) -> String {
@@ -2977,6 +3203,7 @@ mod tests {
         let hash_b = "b".repeat(40);
 
         crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3011,6 +3238,8 @@ mod tests {
                         diff_summary: "a.rs | 1 +".to_string(),
                         diff_file: diff_a.to_string_lossy().to_string(),
                         file_diffs: Vec::new(),
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                 },
                 CommitInfo {
@@ -3032,6 +3261,8 @@ mod tests {
                         diff_summary: "b.rs | 1 +".to_string(),
                         diff_file: diff_b.to_string_lossy().to_string(),
                         file_diffs: Vec::new(),
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                 },
             ],
@@ -3210,6 +3441,7 @@ mod tests {
         std::fs::write(&per_file_b, &file_diff_b).unwrap();
 
         crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3251,6 +3483,8 @@ mod tests {
                             diff_file: per_file_a.to_string_lossy().to_string(),
                             byte_len: file_diff_a.len(),
                         }],
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                 },
                 CommitInfo {
@@ -3279,6 +3513,8 @@ mod tests {
                             diff_file: per_file_b.to_string_lossy().to_string(),
                             byte_len: file_diff_b.len(),
                         }],
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                 },
             ],
@@ -3483,6 +3719,7 @@ mod tests {
             use crate::git::commit::FileChanges;
             use crate::git::{CommitAnalysis, CommitInfo};
             crate::data::RepositoryView {
+                schema_version: crate::data::SCHEMA_VERSION,
                 versions: None,
                 explanation: FieldExplanation::default(),
                 working_directory: WorkingDirectoryInfo {
@@ -3516,6 +3753,8 @@ mod tests {
                         diff_summary: "UNIQUE_STAT_MARKER | 1 +".to_string(),
                         diff_file: diff_path.to_string_lossy().to_string(),
                         file_diffs: Vec::new(),
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                 }],
             }
@@ -3623,6 +3862,8 @@ mod tests {
                     diff_summary: String::new(),
                     diff_file: diff_path.to_string_lossy().to_string(),
                     file_diffs: Vec::new(),
+                    submodule_changes: Vec::new(),
+                    dependency_updates: Vec::new(),
                 },
             }
         };
@@ -3630,6 +3871,7 @@ mod tests {
         // Two commits, each with a ~80KB message → combined ~160KB chars,
         // exceeds the 50K-context-length mock's ~20K-token input budget.
         let repo_view = crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3711,9 +3953,12 @@ mod tests {
                 diff_summary: String::new(),
                 diff_file: diff_path.to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         let repo_view = crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3789,9 +4034,12 @@ mod tests {
                 diff_summary: String::new(),
                 diff_file: diff_path.to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         let repo_view = crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3929,6 +4177,7 @@ mod tests {
         std::fs::write(&per_file_path, &diff_content).unwrap();
 
         crate::data::RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3969,6 +4218,8 @@ mod tests {
                         diff_file: per_file_path.to_string_lossy().to_string(),
                         byte_len: diff_content.len(),
                     }],
+                    submodule_changes: Vec::new(),
+                    dependency_updates: Vec::new(),
                 },
             }],
         }
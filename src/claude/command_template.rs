@@ -0,0 +1,303 @@
+//! User-defined command templates.
+//!
+//! A team codifies an AI-assisted workflow as a markdown prompt in the
+//! `commands/` directory of its context directory, e.g.
+//! `.omni-dev/commands/standup.md`, resolved like the other context files
+//! (`local/`, team, and global copies work too; the first found wins). An
+//! optional YAML front matter block describes the command:
+//!
+//! ```markdown
+//! ---
+//! description: Drafts a standup update for the current branch
+//! ---
+//! Summarise the work on {{branch}}{{#if ticket}} for {{ticket}}{{/if}}.
+//! ```
+//!
+//! The body uses the Handlebars subset of [`super::prompt_template`], with
+//! any variable name: the built-ins in [`VARIABLES`] are filled from the
+//! repository, and the rest are given on the command line.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::context::discovery::resolve_config_file;
+use super::context::{config_resolution_chain, ConfigSourceLabel};
+use super::prompt_template;
+
+/// Directory within the context directory holding command templates.
+pub const COMMANDS_DIR: &str = "commands";
+
+/// File extension of command templates.
+pub const COMMAND_EXTENSION: &str = "md";
+
+/// Variables filled from the repository, with their descriptions.
+pub const VARIABLES: &[(&str, &str)] = &[
+    ("branch", "Current branch name"),
+    (
+        "ticket",
+        "Ticket or issue ID from the branch name, e.g. `PROJ-123`",
+    ),
+    (
+        "scope",
+        "Scope from the branch name, e.g. `auth` in `feat/auth/login`",
+    ),
+];
+
+/// Front matter of a command template.
+#[derive(Debug, Default, Deserialize)]
+struct FrontMatter {
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// A command template found in one of the context directory tiers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandTemplate {
+    /// Command name, the file stem.
+    pub name: String,
+    /// One-line description from the front matter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Tier the template was found in: `local`, `project`, `team`, or
+    /// `global`.
+    pub source: &'static str,
+    /// Path of the template file.
+    pub path: PathBuf,
+    /// Variables the body uses, in order of first use.
+    pub variables: Vec<String>,
+    /// The prompt, without front matter.
+    #[serde(skip)]
+    pub body: String,
+}
+
+impl CommandTemplate {
+    /// Parses the template `content` read from `path`.
+    pub fn parse(name: &str, content: &str, source: &'static str, path: PathBuf) -> Result<Self> {
+        let (front_matter, body) = split_front_matter(content)?;
+        let variables = prompt_template::variables(body)
+            .with_context(|| format!("Invalid command template {}", path.display()))?;
+        Ok(Self {
+            name: name.to_string(),
+            description: front_matter.description,
+            source,
+            path,
+            variables,
+            body: body.to_string(),
+        })
+    }
+
+    /// Renders the body with `values`. Every variable the body uses must be
+    /// either a built-in or given in `values`; built-ins without a value
+    /// render as empty.
+    pub fn render(&self, values: &HashMap<String, String>) -> Result<String> {
+        let missing: Vec<&str> = self
+            .variables
+            .iter()
+            .map(String::as_str)
+            .filter(|name| {
+                !values.contains_key(*name) && !VARIABLES.iter().any(|(known, _)| known == name)
+            })
+            .collect();
+        if !missing.is_empty() {
+            bail!(
+                "Command '{}' needs a value for: {} (pass --var NAME=VALUE)",
+                self.name,
+                missing.join(", ")
+            );
+        }
+        prompt_template::render_open(&self.body, |name| values.get(name).map(String::as_str))
+    }
+}
+
+/// Checks that `name` can name a command: lowercase letters, digits, `-`,
+/// and `_`, starting with a letter or digit.
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if !valid {
+        bail!("Invalid command name '{name}': use lowercase letters, digits, '-', and '_'");
+    }
+    Ok(())
+}
+
+/// Template path relative to the context directory.
+pub fn relative_path(name: &str) -> String {
+    format!("{COMMANDS_DIR}/{name}.{COMMAND_EXTENSION}")
+}
+
+/// Finds the command `name`, resolving `commands/<name>.md` through the
+/// standard config priority.
+pub fn find(context_dir: &Path, name: &str) -> Result<Option<CommandTemplate>> {
+    validate_name(name)?;
+    let relative = relative_path(name);
+    let path = resolve_config_file(context_dir, &relative);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let source = config_resolution_chain(context_dir, &relative)
+        .iter()
+        .find(|label| label.path() == Some(path.as_path()))
+        .map_or("project", source_name);
+    read_template(name, &path, source).map(Some)
+}
+
+/// Lists the commands in every tier, sorted by name. A command defined in
+/// several tiers is listed once, from the tier that wins.
+pub fn list(context_dir: &Path) -> Result<Vec<CommandTemplate>> {
+    let mut templates: Vec<CommandTemplate> = Vec::new();
+    for label in config_resolution_chain(context_dir, COMMANDS_DIR) {
+        let Some(dir) = label.path().filter(|dir| dir.is_dir()) else {
+            continue;
+        };
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(COMMAND_EXTENSION) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if validate_name(name).is_err() || templates.iter().any(|t| t.name == name) {
+                continue;
+            }
+            templates.push(read_template(name, &path, source_name(&label))?);
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(templates)
+}
+
+/// A starting point for a new command template.
+pub fn scaffold(description: &str) -> String {
+    // A JSON string is valid YAML, whatever the description contains.
+    let description = serde_json::Value::from(description);
+    format!(
+        "---\n\
+         description: {description}\n\
+         ---\n\
+         {{{{!-- Variables: {{{{branch}}}}, {{{{ticket}}}}, {{{{scope}}}}, and any passed with --var NAME=VALUE. --}}}}\n\
+         Describe the task for the AI here. The current branch is {{{{branch}}}}\
+         {{{{#if ticket}}}} and the ticket is {{{{ticket}}}}{{{{/if}}}}.\n"
+    )
+}
+
+fn read_template(name: &str, path: &Path, source: &'static str) -> Result<CommandTemplate> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read command template {}", path.display()))?;
+    CommandTemplate::parse(name, &content, source, path.to_path_buf())
+}
+
+/// Short name of the tier `label` stands for.
+fn source_name(label: &ConfigSourceLabel) -> &'static str {
+    match label {
+        ConfigSourceLabel::LocalOverride(_) => "local",
+        ConfigSourceLabel::Project(_) | ConfigSourceLabel::NotFound => "project",
+        ConfigSourceLabel::Team(_) => "team",
+        ConfigSourceLabel::Xdg(_) | ConfigSourceLabel::Global(_) => "global",
+    }
+}
+
+/// Splits a leading `---` front matter block from `content`.
+fn split_front_matter(content: &str) -> Result<(FrontMatter, &str)> {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return Ok((FrontMatter::default(), content));
+    };
+    let (yaml, body) = match rest.find("\n---\n") {
+        Some(end) => (&rest[..end], &rest[end + 5..]),
+        None => match rest.strip_suffix("\n---") {
+            Some(yaml) => (yaml, ""),
+            None => bail!("Command template front matter is missing its closing '---'"),
+        },
+    };
+    let front_matter: FrontMatter =
+        serde_yaml::from_str(yaml).context("Command template front matter is not valid YAML")?;
+    Ok((front_matter, body))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const STANDUP: &str = "---\ndescription: Standup update\n---\n\
+        Summarise {{branch}}{{#if ticket}} ({{ticket}}){{/if}} for {{audience}}.\n";
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let commands = dir.join(COMMANDS_DIR);
+        fs::create_dir_all(&commands).unwrap();
+        fs::write(commands.join(format!("{name}.md")), content).unwrap();
+    }
+
+    #[test]
+    fn parses_front_matter_and_variables() {
+        let template =
+            CommandTemplate::parse("standup", STANDUP, "project", PathBuf::from("x")).unwrap();
+        assert_eq!(template.description.as_deref(), Some("Standup update"));
+        assert_eq!(template.variables, ["branch", "ticket", "audience"]);
+        assert!(template.body.starts_with("Summarise"));
+
+        let plain = CommandTemplate::parse("p", "Just {{branch}}", "project", PathBuf::new());
+        assert_eq!(plain.unwrap().description, None);
+        assert!(CommandTemplate::parse("p", "---\nx: 1\n", "project", PathBuf::new()).is_err());
+    }
+
+    #[test]
+    fn render_requires_values_for_user_variables() {
+        let template =
+            CommandTemplate::parse("standup", STANDUP, "project", PathBuf::from("x")).unwrap();
+        let mut values = HashMap::from([("branch".to_string(), "feat/login".to_string())]);
+        let err = template.render(&values).unwrap_err();
+        assert!(err.to_string().contains("needs a value for: audience"));
+
+        values.insert("audience".to_string(), "the team".to_string());
+        assert_eq!(
+            template.render(&values).unwrap(),
+            "Summarise feat/login for the team.\n"
+        );
+    }
+
+    #[test]
+    fn local_copies_win_and_listing_is_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "standup", STANDUP);
+        write(dir.path(), "audit", "Audit {{scope}}");
+        write(&dir.path().join("local"), "standup", "Local {{branch}}");
+
+        let found = find(dir.path(), "standup").unwrap().unwrap();
+        assert_eq!(found.source, "local");
+        assert_eq!(found.body, "Local {{branch}}");
+        assert!(find(dir.path(), "missing").unwrap().is_none());
+
+        let listed = list(dir.path()).unwrap();
+        let names: Vec<_> = listed
+            .iter()
+            .filter(|t| t.source != "global")
+            .map(|t| (t.name.as_str(), t.source))
+            .collect();
+        assert_eq!(names, [("audit", "project"), ("standup", "local")]);
+    }
+
+    #[test]
+    fn validates_names_and_scaffolds_a_parsable_template() {
+        assert!(validate_name("pr-review_2").is_ok());
+        for name in ["", "-x", "Upper", "../escape", "a b"] {
+            assert!(validate_name(name).is_err(), "{name}");
+        }
+        let template =
+            CommandTemplate::parse("new", &scaffold("Does a thing"), "project", PathBuf::new())
+                .unwrap();
+        assert_eq!(template.description.as_deref(), Some("Does a thing"));
+        assert_eq!(template.variables, ["branch", "ticket"]);
+    }
+}
@@ -0,0 +1,277 @@
+//! Adaptive concurrency for the map phase.
+//!
+//! `twiddle` and `check` send one AI request per batch. A fixed number of
+//! requests in flight is too cautious for an account with generous limits
+//! and enough to trip rate limits on a constrained one, where every 429 costs
+//! a retry. [`AdaptiveLimiter`] starts at the configured concurrency and
+//! adjusts it from what it observes, additive-increase/multiplicative-decrease
+//! style:
+//!
+//! - a rate-limited request halves the limit;
+//! - a successful request whose latency per commit is well above the running
+//!   average lowers it by one, since queueing at the provider shows up as
+//!   latency before it shows up as 429s;
+//! - a full limit's worth of ordinary successes raises it by one, up to
+//!   [`RAMP_FACTOR`] times the configured concurrency.
+//!
+//! Requests already in flight when the limit drops were sent under the old
+//! limit, so their outcomes do not lower it again.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Notify;
+
+use crate::claude::error::is_rate_limit_ai_error;
+
+/// How far above the configured concurrency the limit may ramp.
+pub(crate) const RAMP_FACTOR: usize = 2;
+
+/// Multiple of the average latency per commit above which a successful
+/// request counts as a sign of congestion.
+const LATENCY_TOLERANCE: u32 = 3;
+
+/// Weight of the previous average when folding in a new latency sample
+/// (out of `LATENCY_SMOOTHING + 1`).
+const LATENCY_SMOOTHING: u32 = 3;
+
+/// The limit and the observations it is derived from.
+#[derive(Debug)]
+struct Controller {
+    limit: usize,
+    max: usize,
+    in_flight: usize,
+    /// Ordinary successes since the limit last changed.
+    streak: usize,
+    /// Smoothed latency per commit of successful requests.
+    average: Option<Duration>,
+    /// When the limit last dropped.
+    decreased_at: Option<Instant>,
+}
+
+impl Controller {
+    fn new(initial: usize) -> Self {
+        let initial = initial.max(1);
+        Self {
+            limit: initial,
+            max: initial.saturating_mul(RAMP_FACTOR),
+            in_flight: 0,
+            streak: 0,
+            average: None,
+            decreased_at: None,
+        }
+    }
+
+    fn on_rate_limited(&mut self, started: Instant, now: Instant) {
+        if !self.sent_before_last_decrease(started) {
+            self.decrease_to(self.limit / 2, now);
+        }
+    }
+
+    fn on_success(&mut self, started: Instant, per_commit: Duration, now: Instant) {
+        let average = self.average.unwrap_or(per_commit);
+        self.average = Some((average * LATENCY_SMOOTHING + per_commit) / (LATENCY_SMOOTHING + 1));
+
+        if per_commit > average * LATENCY_TOLERANCE {
+            if !self.sent_before_last_decrease(started) {
+                self.decrease_to(self.limit - 1, now);
+            }
+            return;
+        }
+        self.streak += 1;
+        if self.streak >= self.limit && self.limit < self.max {
+            self.limit += 1;
+            self.streak = 0;
+        }
+    }
+
+    fn decrease_to(&mut self, limit: usize, now: Instant) {
+        self.limit = limit.max(1);
+        self.streak = 0;
+        self.decreased_at = Some(now);
+    }
+
+    fn sent_before_last_decrease(&self, started: Instant) -> bool {
+        self.decreased_at.is_some_and(|at| started < at)
+    }
+}
+
+#[derive(Debug)]
+struct Shared {
+    controller: Mutex<Controller>,
+    released: Notify,
+}
+
+/// Limits concurrent AI requests to a number that tracks the provider's
+/// observed latency and rate limiting.
+#[derive(Debug, Clone)]
+pub(crate) struct AdaptiveLimiter {
+    shared: Arc<Shared>,
+}
+
+impl AdaptiveLimiter {
+    /// Creates a limiter that starts at `initial` concurrent requests (at
+    /// least one) and may ramp up to [`RAMP_FACTOR`] times that.
+    pub(crate) fn new(initial: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                controller: Mutex::new(Controller::new(initial)),
+                released: Notify::new(),
+            }),
+        }
+    }
+
+    /// Returns the current limit.
+    pub(crate) fn limit(&self) -> usize {
+        self.controller().limit
+    }
+
+    /// Waits until a request may be sent under the current limit.
+    pub(crate) async fn acquire(&self) -> Permit {
+        loop {
+            // Created before the check so a release in between is not missed.
+            let released = self.shared.released.notified();
+            {
+                let mut controller = self.controller();
+                if controller.in_flight < controller.limit {
+                    controller.in_flight += 1;
+                    return Permit {
+                        shared: Arc::clone(&self.shared),
+                        started: Instant::now(),
+                    };
+                }
+            }
+            released.await;
+        }
+    }
+
+    fn controller(&self) -> MutexGuard<'_, Controller> {
+        lock(&self.shared)
+    }
+}
+
+fn lock(shared: &Shared) -> MutexGuard<'_, Controller> {
+    shared
+        .controller
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A slot for one in-flight request, released on drop.
+#[derive(Debug)]
+pub(crate) struct Permit {
+    shared: Arc<Shared>,
+    started: Instant,
+}
+
+impl Permit {
+    /// Feeds the outcome of a request covering `commits` commits back into
+    /// the limit. The slot stays held, so a caller that retries within it
+    /// can record each attempt; the next one is timed from here.
+    ///
+    /// Errors other than rate limiting say nothing about load and leave the
+    /// limit alone.
+    pub(crate) fn record<T>(&mut self, result: &anyhow::Result<T>, commits: usize) {
+        let now = Instant::now();
+        let mut controller = lock(&self.shared);
+        match result {
+            Ok(_) => {
+                let commits = u32::try_from(commits.max(1)).unwrap_or(u32::MAX);
+                let per_commit = now.duration_since(self.started) / commits;
+                controller.on_success(self.started, per_commit, now);
+            }
+            Err(e) if is_rate_limit_ai_error(e) => controller.on_rate_limited(self.started, now),
+            Err(_) => {}
+        }
+        let limit = controller.limit;
+        drop(controller);
+        self.started = now;
+        tracing::debug!(limit, "adaptive concurrency updated");
+        // A raised limit admits waiters before this slot is released.
+        self.shared.released.notify_waiters();
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        lock(&self.shared).in_flight -= 1;
+        // Wake every waiter: a raised limit may admit more than one.
+        self.shared.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const FAST: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn ordinary_successes_ramp_up_to_the_ceiling() {
+        let mut controller = Controller::new(2);
+        let start = Instant::now();
+        for _ in 0..20 {
+            controller.on_success(start, FAST, start);
+        }
+        assert_eq!(controller.limit, 2 * RAMP_FACTOR);
+    }
+
+    #[test]
+    fn rate_limits_halve_once_per_round() {
+        let mut controller = Controller::new(8);
+        let sent = Instant::now();
+        let first = sent + Duration::from_secs(1);
+        controller.on_rate_limited(sent, first);
+        assert_eq!(controller.limit, 4);
+
+        // Sent under the old limit: already accounted for.
+        controller.on_rate_limited(sent, first + Duration::from_secs(1));
+        assert_eq!(controller.limit, 4);
+
+        let resent = first + Duration::from_secs(2);
+        controller.on_rate_limited(resent, resent + Duration::from_secs(1));
+        assert_eq!(controller.limit, 2);
+        controller.on_rate_limited(
+            resent + Duration::from_secs(2),
+            resent + Duration::from_secs(3),
+        );
+        controller.on_rate_limited(
+            resent + Duration::from_secs(4),
+            resent + Duration::from_secs(5),
+        );
+        assert_eq!(controller.limit, 1);
+    }
+
+    #[test]
+    fn latency_spikes_step_down() {
+        let mut controller = Controller::new(4);
+        let start = Instant::now();
+        controller.on_success(start, FAST, start);
+        controller.on_success(start, FAST * 10, start + FAST);
+        assert_eq!(controller.limit, 3);
+        assert_eq!(controller.streak, 0);
+    }
+
+    #[tokio::test]
+    async fn acquire_waits_for_a_free_slot() {
+        let limiter = AdaptiveLimiter::new(1);
+        let mut first = limiter.acquire().await;
+
+        let waiter = tokio::spawn({
+            let limiter = limiter.clone();
+            async move {
+                limiter.acquire().await;
+            }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        // One success raises the limit to two, admitting the waiter while
+        // the first slot is still held.
+        first.record(&Ok::<_, anyhow::Error>(()), 1);
+        waiter.await.unwrap();
+        assert_eq!(limiter.limit(), 2);
+        drop(first);
+    }
+}
@@ -1,15 +1,32 @@
 //! Contextual intelligence system for enhanced commit message analysis.
 
 pub mod branch;
+pub mod budget;
+pub mod bundle;
+pub mod cache;
+pub mod codeowners;
 pub mod discovery;
+pub mod doctor;
+pub mod examples;
 pub mod files;
+pub mod learn;
 pub mod patterns;
+pub mod remote;
+pub mod review;
+pub mod scaffold;
+pub mod scope_packs;
+pub mod team;
+pub mod ticket;
+pub mod workspace;
 
 pub use branch::BranchAnalyzer;
 pub use discovery::{
-    config_source_label, load_config_content, load_project_scopes, resolve_context_dir,
-    resolve_context_dir_at, resolve_context_dir_with_source, resolve_context_dir_with_source_at,
-    ConfigDirSource, ConfigSourceLabel, ProjectDiscovery,
+    config_resolution_chain, config_source_label, detect_ecosystem, global_config_dir,
+    load_branch_conventions, load_commit_examples, load_config_content, load_pr_label_config,
+    load_project_scopes, load_project_types, load_prompt_templates, load_work_pattern_rules,
+    resolve_context_dir, resolve_context_dir_at, resolve_context_dir_with_source,
+    resolve_context_dir_with_source_at, ConfigDirSource, ConfigSourceLabel, ProjectDiscovery,
 };
 pub use files::FileAnalyzer;
+pub use learn::{sample_history, LearnedConventions};
 pub use patterns::WorkPatternAnalyzer;
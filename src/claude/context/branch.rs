@@ -4,9 +4,9 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 
 use anyhow::Result;
-use regex::Regex;
+use regex::{Captures, Regex};
 
-use crate::data::context::{BranchContext, WorkType};
+use crate::data::context::{BranchContext, BranchConvention, WorkType};
 
 /// Maximum branch name length considered characteristic of GitHub Flow (short, flat names).
 const GITHUB_FLOW_MAX_BRANCH_LEN: usize = 50;
@@ -17,10 +17,36 @@ pub struct BranchAnalyzer;
 impl BranchAnalyzer {
     /// Analyzes a branch name and extracts context information.
     pub fn analyze(branch_name: &str) -> Result<BranchContext> {
+        Self::analyze_with(branch_name, &[])
+    }
+
+    /// Analyzes a branch name, trying the project's `conventions` (in order)
+    /// before the built-in naming patterns.
+    pub fn analyze_with(
+        branch_name: &str,
+        conventions: &[BranchConvention],
+    ) -> Result<BranchContext> {
         let mut context = BranchContext::default();
 
         // Parse different branch naming conventions
-        if let Some(captures) = STANDARD_BRANCH_PATTERN.captures(branch_name) {
+        if let Some((convention, captures)) = match_convention(branch_name, conventions) {
+            // Project-defined pattern: named captures fill the context
+            context.ticket_id = captures.name("ticket").map(|m| m.as_str().to_string());
+            context.scope = captures.name("scope").map(|m| m.as_str().to_string());
+            context.description = captures
+                .name("description")
+                .map(|m| m.as_str().replace(['-', '_'], " "))
+                .unwrap_or_default();
+
+            context.work_type = captures
+                .name("type")
+                .map(|m| m.as_str())
+                .or(convention.work_type.as_deref())
+                .map(WorkType::from_str)
+                .transpose()?
+                .filter(|work_type| !matches!(work_type, WorkType::Unknown))
+                .unwrap_or_else(|| infer_work_type_from_description(&context.description));
+        } else if let Some(captures) = STANDARD_BRANCH_PATTERN.captures(branch_name) {
             // Standard pattern: type/scope/description or type/description
             context.work_type = captures
                 .name("type")
@@ -139,6 +165,20 @@ static USER_BRANCH_PATTERN: LazyLock<Regex> =
 static TICKET_REFERENCE_PATTERN: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"([A-Z]+-\d+|#\d+|issue-\d+)").unwrap());
 
+/// Returns the first convention whose pattern matches `branch_name`, with its
+/// captures. Invalid patterns never match.
+fn match_convention<'a, 'b>(
+    branch_name: &'b str,
+    conventions: &'a [BranchConvention],
+) -> Option<(&'a BranchConvention, Captures<'b>)> {
+    conventions.iter().find_map(|convention| {
+        let regex = Regex::new(&convention.pattern).ok()?;
+        regex
+            .captures(branch_name)
+            .map(|captures| (convention, captures))
+    })
+}
+
 /// Extracts ticket references from a branch name.
 fn extract_ticket_references(branch_name: &str) -> Option<String> {
     TICKET_REFERENCE_PATTERN
@@ -205,6 +245,50 @@ mod tests {
     use super::*;
     use crate::data::context::WorkType;
 
+    // ── BranchAnalyzer::analyze_with ─────────────────────────────────
+
+    fn convention(pattern: &str, work_type: Option<&str>) -> BranchConvention {
+        BranchConvention {
+            pattern: pattern.to_string(),
+            work_type: work_type.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn custom_convention_fills_named_captures() -> anyhow::Result<()> {
+        let conventions = [convention(
+            r"^users/[^/]+/(?P<ticket>[A-Z]+-\d+)-(?P<description>.+)$",
+            None,
+        )];
+        let ctx = BranchAnalyzer::analyze_with("users/alice/JIRA-1-fix-login-crash", &conventions)?;
+        assert_eq!(ctx.ticket_id, Some("JIRA-1".to_string()));
+        assert_eq!(ctx.description, "Login crash");
+        assert!(matches!(ctx.work_type, WorkType::Fix));
+        Ok(())
+    }
+
+    #[test]
+    fn custom_convention_type_capture_and_default() -> anyhow::Result<()> {
+        let conventions = [
+            convention(
+                r"^(?P<type>[a-z]+)--(?P<scope>[a-z]+)--(?P<description>.+)$",
+                None,
+            ),
+            convention(r"^spike/(?P<description>.+)$", Some("chore")),
+        ];
+        let ctx = BranchAnalyzer::analyze_with("perf--db--faster-queries", &conventions)?;
+        assert!(matches!(ctx.work_type, WorkType::Perf));
+        assert_eq!(ctx.scope, Some("db".to_string()));
+
+        let ctx = BranchAnalyzer::analyze_with("spike/try-new-cache", &conventions)?;
+        assert!(matches!(ctx.work_type, WorkType::Chore));
+
+        // Unmatched branches fall back to the built-in patterns.
+        let ctx = BranchAnalyzer::analyze_with("feat/auth/add-login", &conventions)?;
+        assert_eq!(ctx.scope, Some("auth".to_string()));
+        Ok(())
+    }
+
     // ── BranchAnalyzer::analyze ──────────────────────────────────────
 
     #[test]
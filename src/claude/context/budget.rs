@@ -0,0 +1,356 @@
+//! Token budget for the project context sent with commit and PR prompts.
+//!
+//! Guidelines, ticket descriptions, and review feedback are added to the
+//! prompts whole, so a long `commit-guidelines.md` can take space the diff
+//! needs. With `OMNI_DEV_CONTEXT_TOKEN_BUDGET` set (environment or
+//! `settings.json`, a token count such as `4000`) the budget is filled in
+//! priority order before the prompt is built:
+//!
+//! 1. Scopes and commit types, which are always kept.
+//! 2. The guidelines, then nested `.omni-dev/` guidelines.
+//! 3. Project work patterns.
+//! 4. The ticket description.
+//! 5. Review comments, newest first.
+//! 6. Example commit messages (commit prompts only), best first.
+//!
+//! Text that no longer fits is truncated, or dropped when little room is
+//! left; patterns, comments, and examples are dropped whole. Every trim is recorded so
+//! the context summary can report it.
+
+use tracing::warn;
+
+use crate::claude::token_budget::{estimate_tokens, max_chars_for_tokens};
+use crate::data::context::CommitContext;
+
+/// Setting holding the context budget in tokens.
+pub const CONTEXT_TOKEN_BUDGET_ENV: &str = "OMNI_DEV_CONTEXT_TOKEN_BUDGET";
+
+/// Smallest share worth keeping when a text is truncated; below it the text
+/// is dropped instead.
+const MIN_TRUNCATED_TOKENS: usize = 64;
+
+/// Marker appended to truncated text.
+const TRUNCATION_MARKER: &str = "\n[… truncated to fit the context token budget]";
+
+/// Which prompt the context is trimmed for; each uses different parts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextTarget {
+    /// Commit message prompts (`twiddle`).
+    Commits,
+    /// Pull request description prompts (`create pr`).
+    PullRequest,
+}
+
+/// Upper bound on the tokens the project context may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextBudget {
+    max_tokens: usize,
+}
+
+impl ContextBudget {
+    /// Creates a budget of `max_tokens`.
+    pub fn new(max_tokens: usize) -> Self {
+        Self { max_tokens }
+    }
+
+    /// Reads the budget from settings. An unset, empty, or zero value leaves
+    /// the context untrimmed; an invalid one is warned about and ignored.
+    pub fn from_settings() -> Option<Self> {
+        let raw = crate::utils::settings::get_env_var(CONTEXT_TOKEN_BUDGET_ENV).ok()?;
+        let raw = raw.trim().replace(['_', ','], "");
+        if raw.is_empty() {
+            return None;
+        }
+        match raw.parse::<usize>() {
+            Ok(0) => None,
+            Ok(tokens) => Some(Self::new(tokens)),
+            Err(_) => {
+                warn!(
+                    "Ignoring invalid {CONTEXT_TOKEN_BUDGET_ENV} '{raw}' (expected a token count)"
+                );
+                None
+            }
+        }
+    }
+
+    /// Trims `context` to fit the budget and returns a description of each
+    /// part that was truncated or dropped.
+    pub fn apply(&self, context: &mut CommitContext, target: ContextTarget) -> Vec<String> {
+        let mut trimmer = Trimmer {
+            remaining: self.max_tokens,
+            report: Vec::new(),
+        };
+        let applicable: Vec<std::path::PathBuf> = context
+            .applicable_subtrees()
+            .iter()
+            .map(|subtree| subtree.path.clone())
+            .collect();
+
+        // Scopes and commit types drive validation as well as the prompt, so
+        // they are charged but never trimmed.
+        let scopes: usize = context
+            .project
+            .valid_scopes
+            .iter()
+            .map(|scope| match target {
+                ContextTarget::Commits => {
+                    estimate_tokens(&scope.name) + estimate_tokens(&scope.description)
+                }
+                ContextTarget::PullRequest => estimate_tokens(&scope.name),
+            })
+            .sum();
+        trimmer.charge(scopes);
+        if target == ContextTarget::Commits {
+            let types: usize = context
+                .project
+                .commit_types
+                .iter()
+                .map(|t| estimate_tokens(&t.name) + estimate_tokens(&t.description))
+                .sum();
+            trimmer.charge(types);
+        }
+
+        match target {
+            ContextTarget::Commits => {
+                trimmer.fit_text("commit guidelines", &mut context.project.commit_guidelines);
+            }
+            ContextTarget::PullRequest => {
+                trimmer.fit_text("PR guidelines", &mut context.project.pr_guidelines);
+            }
+        }
+        for subtree in &mut context.project.subtree_contexts {
+            if !applicable.contains(&subtree.path) {
+                continue;
+            }
+            let label = format!("guidelines for {}/", subtree.path.display());
+            match target {
+                ContextTarget::Commits => trimmer.fit_text(&label, &mut subtree.commit_guidelines),
+                ContextTarget::PullRequest => trimmer.fit_text(&label, &mut subtree.pr_guidelines),
+            }
+        }
+
+        let patterns = &mut context.range.project_patterns;
+        let mut kept = 0;
+        for pattern in patterns.iter() {
+            let cost = estimate_tokens(&pattern.name) + estimate_tokens(&pattern.guidance);
+            if !trimmer.fits(cost) {
+                break;
+            }
+            kept += 1;
+        }
+        for dropped in patterns.drain(kept..) {
+            trimmer
+                .report
+                .push(format!("project pattern '{}' dropped", dropped.name));
+        }
+
+        if let Some(ticket) = &mut context.branch.ticket {
+            trimmer.charge(estimate_tokens(&ticket.id) + estimate_tokens(&ticket.title));
+            trimmer.fit_text("ticket description", &mut ticket.description);
+        }
+
+        let comments = &mut context.branch.review_comments;
+        let mut first_kept = comments.len();
+        while first_kept > 0 {
+            let comment = &comments[first_kept - 1];
+            let cost = estimate_tokens(&comment.author) + estimate_tokens(&comment.body);
+            if !trimmer.fits(cost) {
+                break;
+            }
+            first_kept -= 1;
+        }
+        if first_kept > 0 {
+            let tokens: usize = comments[..first_kept]
+                .iter()
+                .map(|c| estimate_tokens(&c.author) + estimate_tokens(&c.body))
+                .sum();
+            trimmer.report.push(format!(
+                "{first_kept} oldest review comment(s) dropped ({tokens} tokens)"
+            ));
+            comments.drain(..first_kept);
+        }
+
+        if target == ContextTarget::Commits {
+            let examples = &mut context.project.commit_examples;
+            let kept = examples
+                .iter()
+                .take_while(|example| trimmer.fits(estimate_tokens(example)))
+                .count();
+            if kept < examples.len() {
+                trimmer.report.push(format!(
+                    "{} example commit message(s) dropped",
+                    examples.len() - kept
+                ));
+                examples.truncate(kept);
+            }
+        }
+
+        trimmer.report
+    }
+}
+
+/// Running state of one trimming pass.
+struct Trimmer {
+    remaining: usize,
+    report: Vec<String>,
+}
+
+impl Trimmer {
+    /// Charges `tokens` that are kept regardless of the budget.
+    fn charge(&mut self, tokens: usize) {
+        self.remaining = self.remaining.saturating_sub(tokens);
+    }
+
+    /// Charges `tokens` if they fit in what remains.
+    fn fits(&mut self, tokens: usize) -> bool {
+        if tokens > self.remaining {
+            return false;
+        }
+        self.remaining -= tokens;
+        true
+    }
+
+    /// Keeps `text` if it fits, otherwise truncates it to the remaining
+    /// budget or, when too little remains, drops it.
+    fn fit_text(&mut self, label: &str, text: &mut Option<String>) {
+        let Some(content) = text.as_deref() else {
+            return;
+        };
+        let tokens = estimate_tokens(content);
+        if self.fits(tokens) {
+            return;
+        }
+        if self.remaining < MIN_TRUNCATED_TOKENS {
+            self.report
+                .push(format!("{label} dropped ({tokens} tokens)"));
+            *text = None;
+            return;
+        }
+        let truncated = truncate_to_tokens(content, self.remaining);
+        let kept = estimate_tokens(&truncated);
+        self.report
+            .push(format!("{label} truncated ({tokens} → {kept} tokens)"));
+        self.remaining = self.remaining.saturating_sub(kept);
+        *text = Some(truncated);
+    }
+}
+
+/// Cuts `text` to fit `tokens`, at a line break where one is close, and
+/// marks the cut.
+fn truncate_to_tokens(text: &str, tokens: usize) -> String {
+    let max = max_chars_for_tokens(tokens).saturating_sub(TRUNCATION_MARKER.len());
+    let mut end = 0;
+    for (index, c) in text.char_indices() {
+        if index + c.len_utf8() > max {
+            break;
+        }
+        end = index + c.len_utf8();
+    }
+    let cut = &text[..end];
+    let cut = match cut.rfind('\n') {
+        Some(newline) if newline >= end / 2 => &cut[..newline],
+        _ => cut,
+    };
+    format!("{}{TRUNCATION_MARKER}", cut.trim_end())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::data::context::{ReviewComment, TicketContext};
+
+    fn comment(body: &str) -> ReviewComment {
+        ReviewComment {
+            author: "alice".to_string(),
+            body: body.to_string(),
+            path: None,
+            created_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn small_context_is_untouched() {
+        let mut context = CommitContext::new();
+        context.project.commit_guidelines = Some("Use conventional commits.".to_string());
+        let report = ContextBudget::new(1000).apply(&mut context, ContextTarget::Commits);
+        assert!(report.is_empty());
+        assert_eq!(
+            context.project.commit_guidelines.as_deref(),
+            Some("Use conventional commits.")
+        );
+    }
+
+    #[test]
+    fn oversized_guidelines_are_truncated_and_lower_priorities_dropped() {
+        let mut context = CommitContext::new();
+        context.project.commit_guidelines = Some("Rule line.\n".repeat(400));
+        context.branch.ticket = Some(TicketContext {
+            id: "PROJ-1".to_string(),
+            title: "Export".to_string(),
+            description: Some("Details ".repeat(100)),
+            url: None,
+        });
+        context.branch.review_comments = vec![comment("old note"), comment("new note")];
+
+        let report = ContextBudget::new(300).apply(&mut context, ContextTarget::Commits);
+
+        let guidelines = context.project.commit_guidelines.as_deref().unwrap();
+        assert!(guidelines.ends_with(TRUNCATION_MARKER));
+        assert!(estimate_tokens(guidelines) <= 300);
+        assert!(report[0].starts_with("commit guidelines truncated ("));
+        assert!(context
+            .branch
+            .ticket
+            .as_ref()
+            .unwrap()
+            .description
+            .is_none());
+        assert!(context.branch.review_comments.is_empty());
+        assert!(report
+            .iter()
+            .any(|r| r.starts_with("ticket description dropped")));
+        assert!(report
+            .iter()
+            .any(|r| r.starts_with("2 oldest review comment(s) dropped")));
+    }
+
+    #[test]
+    fn review_comments_keep_the_newest() {
+        let mut context = CommitContext::new();
+        context.branch.review_comments = vec![
+            comment(&"old ".repeat(50)),
+            comment("newer"),
+            comment("newest"),
+        ];
+        let report = ContextBudget::new(20).apply(&mut context, ContextTarget::PullRequest);
+        let bodies: Vec<&str> = context
+            .branch
+            .review_comments
+            .iter()
+            .map(|c| c.body.as_str())
+            .collect();
+        assert_eq!(bodies, ["newer", "newest"]);
+        assert_eq!(report.len(), 1);
+    }
+
+    #[test]
+    fn examples_are_dropped_last_and_only_for_commits() {
+        let mut context = CommitContext::new();
+        context.project.commit_examples = vec!["feat: a".to_string(), "b ".repeat(100)];
+        let report = ContextBudget::new(40).apply(&mut context, ContextTarget::Commits);
+        assert_eq!(context.project.commit_examples, ["feat: a"]);
+        assert_eq!(report, ["1 example commit message(s) dropped"]);
+
+        context.project.commit_examples.push("c ".repeat(100));
+        ContextBudget::new(1).apply(&mut context, ContextTarget::PullRequest);
+        assert_eq!(context.project.commit_examples.len(), 2);
+    }
+
+    #[test]
+    fn truncation_prefers_line_breaks() {
+        let text = format!("{}\n{}", "a".repeat(300), "b".repeat(300));
+        let truncated = truncate_to_tokens(&text, 300);
+        assert_eq!(truncated, format!("{}{TRUNCATION_MARKER}", "a".repeat(300)));
+    }
+}
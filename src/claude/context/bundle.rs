@@ -0,0 +1,347 @@
+//! Shareable bundles of a project's context directory.
+//!
+//! `omni-dev context export` packs the shared files of `.omni-dev/`
+//! (guidelines, scopes, commit types, labels, conventions, prompt templates,
+//! feature contexts) into a gzipped tarball with a manifest, and
+//! `omni-dev context import` unpacks one into another repository. Personal
+//! `local/` overrides, the cache, and `context.yaml` are never included.
+//!
+//! The manifest (`omni-dev-bundle.yaml`, first in the archive) records the
+//! bundle format version, the omni-dev version that wrote it, and a SHA-256
+//! checksum of every file, so an import can refuse bundles it does not
+//! understand or that were altered.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Component, Path};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::digest::sha256_hex;
+
+/// Name of the manifest inside a bundle.
+pub const MANIFEST_FILE: &str = "omni-dev-bundle.yaml";
+
+/// Bundle layout version written by this build; newer bundles are refused.
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Files at the top of the context directory that a bundle carries.
+pub const BUNDLE_FILES: &[&str] = &[
+    "commit-guidelines.md",
+    "pr-guidelines.md",
+    "scopes.yaml",
+    "types.yaml",
+    "labels.yaml",
+    "branch-conventions.yaml",
+    "work-patterns.yaml",
+    "examples.md",
+    "ignore.yaml",
+    "changelog-rule.yaml",
+    "license-header.txt",
+    "release-notes.md",
+    "models.yaml",
+];
+
+/// Directories whose files a bundle carries (one level, no subdirectories).
+pub const BUNDLE_DIRS: &[&str] = &["prompts", "context/feature-contexts"];
+
+/// Description of a bundle's contents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleManifest {
+    /// Bundle layout version.
+    pub format_version: u32,
+    /// Version of omni-dev that wrote the bundle.
+    pub omni_dev_version: String,
+    /// Creation time as an RFC 3339 timestamp.
+    pub created_at: String,
+    /// Every file in the bundle, sorted by path.
+    pub files: Vec<BundleEntry>,
+}
+
+/// One file listed in a [`BundleManifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleEntry {
+    /// Path relative to the context directory, `/`-separated.
+    pub path: String,
+    /// Lower-case hex SHA-256 of the contents.
+    pub sha256: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+/// A bundle's files keyed by relative path.
+pub type BundleFiles = BTreeMap<String, Vec<u8>>;
+
+/// Returns whether `path` is a location a bundle may carry.
+pub fn is_bundle_path(path: &str) -> bool {
+    let relative = Path::new(path);
+    if !relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return false;
+    }
+    if BUNDLE_FILES.contains(&path) {
+        return true;
+    }
+    match path.rsplit_once('/') {
+        Some((dir, name)) => BUNDLE_DIRS.contains(&dir) && !name.is_empty(),
+        None => false,
+    }
+}
+
+/// Reads the bundle-able files present in `context_dir`.
+///
+/// Only the directory itself is read: files resolved from `local/`, a team
+/// repository, or the global config are not the project's to share.
+pub fn collect_files(context_dir: &Path) -> Result<BundleFiles> {
+    let mut files = BundleFiles::new();
+    for name in BUNDLE_FILES {
+        let path = context_dir.join(name);
+        if path.is_file() {
+            let content = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            files.insert((*name).to_string(), content);
+        }
+    }
+    for dir in BUNDLE_DIRS {
+        let Ok(entries) = std::fs::read_dir(context_dir.join(dir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !path.is_file() || name.starts_with('.') {
+                continue;
+            }
+            let content = std::fs::read(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            files.insert(format!("{dir}/{name}"), content);
+        }
+    }
+    Ok(files)
+}
+
+/// Writes `files` as a gzipped tarball with a manifest to `writer`.
+pub fn write_bundle(files: &BundleFiles, writer: impl Write) -> Result<BundleManifest> {
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        omni_dev_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        files: files
+            .iter()
+            .map(|(path, content)| BundleEntry {
+                path: path.clone(),
+                sha256: sha256_hex(content),
+                size: content.len() as u64,
+            })
+            .collect(),
+    };
+    let manifest_yaml =
+        serde_yaml::to_string(&manifest).context("Failed to serialize bundle manifest")?;
+
+    let gz = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+    let mut builder = tar::Builder::new(gz);
+    append_file(&mut builder, MANIFEST_FILE, manifest_yaml.as_bytes())?;
+    for (path, content) in files {
+        append_file(&mut builder, path, content)?;
+    }
+    builder
+        .into_inner()
+        .context("Failed to finish bundle archive")?
+        .finish()
+        .context("Failed to finish bundle compression")?;
+    Ok(manifest)
+}
+
+fn append_file<W: Write>(builder: &mut tar::Builder<W>, path: &str, content: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(0);
+    builder
+        .append_data(&mut header, path, content)
+        .with_context(|| format!("Failed to add {path} to the bundle"))
+}
+
+/// Reads and verifies a bundle written by [`write_bundle`].
+///
+/// Fails when the manifest is missing or from a newer format, when the
+/// archive holds a path a bundle may not carry or a file the manifest does
+/// not list, or when a checksum does not match.
+pub fn read_bundle(reader: impl Read) -> Result<(BundleManifest, BundleFiles)> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(reader));
+    let mut manifest: Option<BundleManifest> = None;
+    let mut files = BundleFiles::new();
+    for entry in archive.entries().context("Failed to read bundle archive")? {
+        let mut entry = entry.context("Failed to read bundle entry")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry
+            .path()
+            .context("Bundle entry has an invalid path")?
+            .to_string_lossy()
+            .replace('\\', "/");
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .with_context(|| format!("Failed to read {path} from the bundle"))?;
+        if path == MANIFEST_FILE {
+            manifest =
+                Some(serde_yaml::from_slice(&content).context("Failed to parse bundle manifest")?);
+        } else if is_bundle_path(&path) {
+            files.insert(path, content);
+        } else {
+            bail!("Bundle contains an unexpected file: {path}");
+        }
+    }
+
+    let manifest = manifest.with_context(|| format!("Bundle has no {MANIFEST_FILE}"))?;
+    if manifest.format_version > BUNDLE_FORMAT_VERSION {
+        bail!(
+            "Bundle format {} was written by omni-dev {}; this version reads format {BUNDLE_FORMAT_VERSION} and older, so upgrade omni-dev to import it",
+            manifest.format_version,
+            manifest.omni_dev_version
+        );
+    }
+    for entry in &manifest.files {
+        let content = files
+            .get(&entry.path)
+            .with_context(|| format!("Bundle is missing {}", entry.path))?;
+        if sha256_hex(content) != entry.sha256 {
+            bail!("Checksum mismatch for {} in the bundle", entry.path);
+        }
+    }
+    if let Some(path) = files
+        .keys()
+        .find(|path| !manifest.files.iter().any(|entry| &entry.path == *path))
+    {
+        bail!("Bundle file {path} is not listed in the manifest");
+    }
+    Ok((manifest, files))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn sample_files() -> BundleFiles {
+        BundleFiles::from([
+            (
+                "commit-guidelines.md".to_string(),
+                b"# Guidelines\n".to_vec(),
+            ),
+            ("scopes.yaml".to_string(), b"scopes: []\n".to_vec()),
+            (
+                "prompts/twiddle-system.md.hbs".to_string(),
+                b"{{default_prompt}}\n".to_vec(),
+            ),
+        ])
+    }
+
+    fn raw_tarball(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        for (path, content) in entries {
+            append_file(&mut builder, path, content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    #[test]
+    fn bundle_round_trips() {
+        let mut bytes = Vec::new();
+        let written = write_bundle(&sample_files(), &mut bytes).unwrap();
+        assert_eq!(written.files.len(), 3);
+
+        let (manifest, files) = read_bundle(bytes.as_slice()).unwrap();
+        assert_eq!(manifest, written);
+        assert_eq!(manifest.omni_dev_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(files, sample_files());
+    }
+
+    #[test]
+    fn collects_only_shareable_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("scopes.yaml"), "scopes: []\n").unwrap();
+        std::fs::write(dir.path().join("context.yaml"), "extends: x\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("local")).unwrap();
+        std::fs::write(dir.path().join("local/scopes.yaml"), "mine\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("prompts")).unwrap();
+        std::fs::write(dir.path().join("prompts/pr-system.md.hbs"), "x").unwrap();
+
+        let files = collect_files(dir.path()).unwrap();
+        let paths: Vec<&str> = files.keys().map(String::as_str).collect();
+        assert_eq!(paths, ["prompts/pr-system.md.hbs", "scopes.yaml"]);
+    }
+
+    #[test]
+    fn rejects_unsafe_and_unlisted_paths() {
+        assert!(is_bundle_path("scopes.yaml"));
+        assert!(is_bundle_path("context/feature-contexts/auth.yaml"));
+        assert!(!is_bundle_path("../scopes.yaml"));
+        assert!(!is_bundle_path("/etc/passwd"));
+        assert!(!is_bundle_path("local/scopes.yaml"));
+        assert!(!is_bundle_path("prompts/nested/x.md.hbs"));
+
+        let manifest = serde_yaml::to_string(&BundleManifest {
+            format_version: 1,
+            omni_dev_version: "0.0.0".to_string(),
+            created_at: String::new(),
+            files: Vec::new(),
+        })
+        .unwrap();
+        let bytes = raw_tarball(&[
+            (MANIFEST_FILE, manifest.as_bytes()),
+            ("settings.json", b"{}"),
+        ]);
+        let err = read_bundle(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("unexpected file: settings.json"));
+
+        let bytes = raw_tarball(&[(MANIFEST_FILE, manifest.as_bytes()), ("scopes.yaml", b"x")]);
+        let err = read_bundle(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("not listed in the manifest"));
+    }
+
+    #[test]
+    fn rejects_tampered_and_newer_bundles() {
+        let mut manifest = BundleManifest {
+            format_version: 1,
+            omni_dev_version: "0.0.0".to_string(),
+            created_at: String::new(),
+            files: vec![BundleEntry {
+                path: "scopes.yaml".to_string(),
+                sha256: sha256_hex(b"scopes: []\n"),
+                size: 11,
+            }],
+        };
+        let yaml = serde_yaml::to_string(&manifest).unwrap();
+        let bytes = raw_tarball(&[
+            (MANIFEST_FILE, yaml.as_bytes()),
+            ("scopes.yaml", b"changed"),
+        ]);
+        let err = read_bundle(bytes.as_slice()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Checksum mismatch for scopes.yaml"));
+
+        manifest.format_version = BUNDLE_FORMAT_VERSION + 1;
+        let yaml = serde_yaml::to_string(&manifest).unwrap();
+        let bytes = raw_tarball(&[
+            (MANIFEST_FILE, yaml.as_bytes()),
+            ("scopes.yaml", b"scopes: []\n"),
+        ]);
+        let err = read_bundle(bytes.as_slice()).unwrap_err();
+        assert!(err.to_string().contains("upgrade omni-dev"));
+
+        let bytes = raw_tarball(&[("scopes.yaml", b"scopes: []\n")]);
+        assert!(read_bundle(bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,183 @@
+//! On-disk cache of the discovered [`ProjectContext`].
+//!
+//! Discovery reads up to five tiers of every config file, feature contexts,
+//! `CONTRIBUTING.md`, `README.md`, and the ecosystem marker files on every
+//! invocation. The result is stored in `<context_dir>/cache/project-context.json`
+//! together with the size and modification time of each of those sources
+//! (present or not), and is reused only while every stamp still matches.
+//!
+//! Remote `extends:` sources are not stamped, so an entry is also discarded
+//! once it is older than the remote-source cache TTL. The cache is strictly
+//! best effort — read and write failures are logged at `tracing::debug` and
+//! treated as misses.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::context::ProjectContext;
+use crate::utils::json_store;
+
+/// Name of the cache subdirectory inside the context directory.
+pub const CACHE_DIR_NAME: &str = "cache";
+
+/// File name of the cached project context.
+const CACHE_FILE_NAME: &str = "project-context.json";
+
+/// The size and modification time of one discovery source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SourceStamp {
+    path: PathBuf,
+    /// `None` when the path does not exist; creating it invalidates the cache.
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl SourceStamp {
+    fn of(path: &Path) -> Self {
+        let metadata = std::fs::metadata(path).ok();
+        Self {
+            path: path.to_path_buf(),
+            modified: metadata.as_ref().and_then(|m| m.modified().ok()),
+            len: metadata.map_or(0, |m| m.len()),
+        }
+    }
+}
+
+/// The stamps of every source a discovery run may read.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceFingerprint {
+    version: String,
+    stamps: Vec<SourceStamp>,
+}
+
+impl SourceFingerprint {
+    /// Stamps `paths`. Directories are stamped too, which catches files
+    /// being added to or removed from them.
+    pub fn of(paths: &[PathBuf]) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            stamps: paths.iter().map(|p| SourceStamp::of(p)).collect(),
+        }
+    }
+}
+
+/// A cached context and the fingerprint it was discovered under.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fingerprint: SourceFingerprint,
+    context: ProjectContext,
+}
+
+/// The project context cache of one context directory.
+#[derive(Debug, Clone)]
+pub struct ContextCache {
+    dir: PathBuf,
+}
+
+impl ContextCache {
+    /// Creates the cache for `context_dir` (its `cache/` subdirectory is
+    /// created lazily on first write).
+    pub fn for_context_dir(context_dir: &Path) -> Self {
+        Self {
+            dir: context_dir.join(CACHE_DIR_NAME),
+        }
+    }
+
+    /// Returns the cached context if it was stored under `fingerprint` and is
+    /// younger than the remote-source TTL.
+    pub fn load(&self, fingerprint: &SourceFingerprint) -> Option<ProjectContext> {
+        let path = self.entry_path();
+        let age = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())?;
+        if age >= super::remote::CACHE_TTL {
+            tracing::debug!("context cache: entry expired");
+            return None;
+        }
+        let entry: CacheEntry = json_store::read_json(&path, "context cache")?;
+        if entry.fingerprint != *fingerprint {
+            tracing::debug!("context cache: sources changed");
+            return None;
+        }
+        Some(entry.context)
+    }
+
+    /// Stores `context` under `fingerprint`. Best effort: failures are only
+    /// logged.
+    pub fn store(&self, fingerprint: SourceFingerprint, context: ProjectContext) {
+        let entry = CacheEntry {
+            fingerprint,
+            context,
+        };
+        if let Err(e) = json_store::write_ignored_json(&self.entry_path(), &entry) {
+            tracing::debug!("context cache: failed to store entry: {e}");
+        }
+    }
+
+    fn entry_path(&self) -> PathBuf {
+        self.dir.join(CACHE_FILE_NAME)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn context_with_guidelines(text: &str) -> ProjectContext {
+        ProjectContext {
+            commit_guidelines: Some(text.to_string()),
+            ..ProjectContext::default()
+        }
+    }
+
+    #[test]
+    fn round_trips_while_sources_are_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("scopes.yaml");
+        std::fs::write(&source, "scopes: []\n").unwrap();
+        let sources = vec![source];
+        let cache = ContextCache::for_context_dir(dir.path());
+
+        cache.store(
+            SourceFingerprint::of(&sources),
+            context_with_guidelines("be brief"),
+        );
+        let loaded = cache.load(&SourceFingerprint::of(&sources)).unwrap();
+        assert_eq!(loaded.commit_guidelines.as_deref(), Some("be brief"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("cache/.gitignore")).unwrap(),
+            "*\n"
+        );
+    }
+
+    #[test]
+    fn misses_when_a_source_changes_or_appears() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("README.md");
+        let missing = dir.path().join("CONTRIBUTING.md");
+        std::fs::write(&existing, "# Project\n").unwrap();
+        let sources = vec![existing.clone(), missing.clone()];
+        let cache = ContextCache::for_context_dir(dir.path());
+        cache.store(SourceFingerprint::of(&sources), ProjectContext::default());
+
+        std::fs::write(&missing, "## Commit messages\n").unwrap();
+        assert!(cache.load(&SourceFingerprint::of(&sources)).is_none());
+
+        cache.store(SourceFingerprint::of(&sources), ProjectContext::default());
+        std::fs::write(&existing, "# Project, renamed\n").unwrap();
+        assert!(cache.load(&SourceFingerprint::of(&sources)).is_none());
+    }
+
+    #[test]
+    fn ignores_corrupt_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ContextCache::for_context_dir(dir.path());
+        std::fs::create_dir_all(dir.path().join(CACHE_DIR_NAME)).unwrap();
+        std::fs::write(cache.entry_path(), "{not json").unwrap();
+        assert!(cache.load(&SourceFingerprint::of(&[])).is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Scope inference from `CODEOWNERS` files.
+//!
+//! Repositories without a `scopes.yaml` often still describe their module
+//! boundaries in `CODEOWNERS`. Each owned directory becomes a scope named
+//! after its last path component, with the `CODEOWNERS` pattern translated
+//! into the glob syntax used by [`ScopeDefinition::file_patterns`], so
+//! [`crate::git::CommitAnalysis::refine_scope`] can pick the most specific
+//! owned directory for a commit's files.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::data::context::ScopeDefinition;
+
+/// Locations searched for a `CODEOWNERS` file, in GitHub's precedence order
+/// (GitLab's `.gitlab/` location last).
+const CODEOWNERS_LOCATIONS: &[&str] = &[
+    ".github/CODEOWNERS",
+    "CODEOWNERS",
+    "docs/CODEOWNERS",
+    ".gitlab/CODEOWNERS",
+];
+
+/// Returns the first `CODEOWNERS` file found under `repo_path`.
+pub fn find_codeowners(repo_path: &Path) -> Option<PathBuf> {
+    CODEOWNERS_LOCATIONS
+        .iter()
+        .map(|location| repo_path.join(location))
+        .find(|path| path.is_file())
+}
+
+/// Loads scope definitions derived from the repository's `CODEOWNERS` file.
+///
+/// Returns an empty list when no `CODEOWNERS` file exists or it cannot be read.
+pub fn load_codeowners_scopes(repo_path: &Path) -> Vec<ScopeDefinition> {
+    let Some(path) = find_codeowners(repo_path) else {
+        return vec![];
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => parse_codeowners_scopes(&content),
+        Err(e) => {
+            tracing::warn!("Cannot read CODEOWNERS file {}: {e}", path.display());
+            vec![]
+        }
+    }
+}
+
+/// Parses `CODEOWNERS` content into scope definitions.
+///
+/// Only directory-shaped patterns produce scopes; catch-alls (`*`) and bare
+/// extension patterns (`*.js`) name no module and are skipped. Directories
+/// sharing a final component are merged into one scope.
+pub fn parse_codeowners_scopes(content: &str) -> Vec<ScopeDefinition> {
+    let mut scopes: Vec<ScopeDefinition> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        // Comments, blanks, and GitLab `[Section]` headers carry no pattern.
+        if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+            continue;
+        }
+        let line = line.strip_prefix('^').unwrap_or(line);
+        let mut tokens = line.split_whitespace();
+        let Some(pattern) = tokens.next() else {
+            continue;
+        };
+        let owners: Vec<&str> = tokens.take_while(|t| !t.starts_with('#')).collect();
+
+        let Some(name) = scope_name(pattern) else {
+            continue;
+        };
+        let globs = to_globs(pattern);
+
+        if let Some(existing) = scopes.iter_mut().find(|s| s.name == name) {
+            for glob in globs {
+                if !existing.file_patterns.contains(&glob) {
+                    existing.file_patterns.push(glob);
+                }
+            }
+        } else {
+            let description = if owners.is_empty() {
+                format!("Files under {}", pattern.trim_matches('/'))
+            } else {
+                format!(
+                    "Files under {} (owned by {})",
+                    pattern.trim_matches('/'),
+                    owners.join(", ")
+                )
+            };
+            scopes.push(ScopeDefinition {
+                name,
+                description,
+                examples: vec![],
+                file_patterns: globs,
+            });
+        }
+    }
+
+    scopes
+}
+
+/// Derives a scope name from the last literal directory component of a
+/// `CODEOWNERS` pattern, or `None` when the pattern names no directory.
+fn scope_name(pattern: &str) -> Option<String> {
+    let trimmed = pattern.trim_matches('/');
+    let is_dir = pattern.ends_with('/') || pattern.ends_with("/*") || pattern.ends_with("/**");
+    let components: Vec<&str> = trimmed.split('/').filter(|c| !c.is_empty()).collect();
+
+    // A trailing file-ish component (`docs/*.md`, `src/main.rs`) is dropped so
+    // the scope is named after its directory.
+    let dir_components = if is_dir || components.len() <= 1 {
+        &components[..]
+    } else {
+        &components[..components.len() - 1]
+    };
+
+    let name = dir_components
+        .iter()
+        .rev()
+        .find(|c| !c.contains(['*', '?', '[']))?;
+
+    // A single bare component with a dot (`Cargo.toml`) is a file, not a module.
+    if components.len() == 1 && !is_dir && name.contains('.') {
+        return None;
+    }
+
+    Some(name.to_lowercase())
+}
+
+/// Translates a `CODEOWNERS` (gitignore-style) pattern into scope globs.
+pub(crate) fn to_globs(pattern: &str) -> Vec<String> {
+    let anchored = pattern.starts_with('/');
+    let body = pattern.trim_start_matches('/');
+    // Unanchored patterns without an inner slash match at any depth.
+    let prefix = if !anchored && !body.trim_end_matches('/').contains('/') {
+        "**/"
+    } else {
+        ""
+    };
+
+    if let Some(dir) = body.strip_suffix('/') {
+        vec![format!("{prefix}{dir}/**")]
+    } else if body.ends_with("/*") || body.ends_with("/**") || body.contains(['*', '?', '[']) {
+        vec![format!("{prefix}{body}")]
+    } else {
+        // A bare path may name either a file or a directory.
+        vec![format!("{prefix}{body}"), format!("{prefix}{body}/**")]
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directory_patterns_become_scopes() {
+        let scopes = parse_codeowners_scopes(
+            "# Owners\n\
+             * @org/everyone\n\
+             /src/cli/ @org/cli-team\n\
+             /src/git/** @alice @bob\n\
+             *.md @org/docs\n",
+        );
+        let names: Vec<&str> = scopes.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["cli", "git"]);
+        assert_eq!(scopes[0].file_patterns, vec!["src/cli/**"]);
+        assert_eq!(scopes[1].file_patterns, vec!["src/git/**"]);
+        assert!(scopes[1].description.contains("@alice, @bob"));
+    }
+
+    #[test]
+    fn unanchored_directory_matches_any_depth() {
+        let scopes = parse_codeowners_scopes("docs/ @org/docs\n");
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].name, "docs");
+        assert_eq!(scopes[0].file_patterns, vec!["**/docs/**"]);
+    }
+
+    #[test]
+    fn bare_path_covers_file_and_directory() {
+        let scopes = parse_codeowners_scopes("/website @org/web\n");
+        assert_eq!(scopes[0].file_patterns, vec!["website", "website/**"]);
+    }
+
+    #[test]
+    fn file_glob_is_named_after_its_directory() {
+        let scopes = parse_codeowners_scopes("/docs/*.md @org/docs\n");
+        assert_eq!(scopes[0].name, "docs");
+        assert_eq!(scopes[0].file_patterns, vec!["docs/*.md"]);
+    }
+
+    #[test]
+    fn root_files_and_catch_alls_are_skipped() {
+        let scopes = parse_codeowners_scopes("* @a\n*.rs @b\nCargo.toml @c\n");
+        assert!(scopes.is_empty());
+    }
+
+    #[test]
+    fn same_final_component_merges_patterns() {
+        let scopes = parse_codeowners_scopes("/api/tests/ @a\n/web/tests/ @b\n");
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].name, "tests");
+        assert_eq!(
+            scopes[0].file_patterns,
+            vec!["api/tests/**", "web/tests/**"]
+        );
+    }
+
+    #[test]
+    fn gitlab_sections_and_inline_comments_are_ignored() {
+        let scopes = parse_codeowners_scopes("[Backend]\n/server/ @a # primary\n^[Optional]\n");
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].name, "server");
+        assert!(!scopes[0].description.contains('#'));
+    }
+
+    #[test]
+    fn find_codeowners_prefers_github_location() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".github")).unwrap();
+        std::fs::write(dir.path().join("CODEOWNERS"), "/a/ @x\n").unwrap();
+        std::fs::write(dir.path().join(".github/CODEOWNERS"), "/b/ @y\n").unwrap();
+        let scopes = load_codeowners_scopes(dir.path());
+        assert_eq!(scopes[0].name, "b");
+    }
+
+    #[test]
+    fn missing_codeowners_yields_no_scopes() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_codeowners_scopes(dir.path()).is_empty());
+    }
+}
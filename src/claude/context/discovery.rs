@@ -1,5 +1,6 @@
 //! Project context discovery system.
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -8,9 +9,11 @@ use std::fmt;
 use anyhow::{Context, Result};
 use tracing::debug;
 
+use super::cache::{ContextCache, SourceFingerprint};
+use crate::claude::prompt_template::PromptTemplate;
 use crate::data::context::{
-    Ecosystem, FeatureContext, ProjectContext, ProjectConventions, ScopeDefinition,
-    ScopeRequirements,
+    BranchConvention, Ecosystem, FeatureContext, PrLabelConfig, ProjectContext, ProjectConventions,
+    ScopeDefinition, ScopeRequirements, SubtreeContext, TypeDefinition,
 };
 use crate::utils::env::{EnvSource, SystemEnv};
 
@@ -38,13 +41,21 @@ fn xdg_config_dir_with(env: &impl EnvSource, home: Option<&Path>) -> Option<Path
     home.map(|home| home.join(".config").join("omni-dev"))
 }
 
+/// Returns the global omni-dev config directory: `$XDG_CONFIG_HOME/omni-dev/`,
+/// or `~/.config/omni-dev/`. `None` when neither can be determined.
+pub fn global_config_dir() -> Option<PathBuf> {
+    xdg_config_dir_with(&SystemEnv, dirs::home_dir().as_deref())
+}
+
 /// Resolves configuration file path with local override support and global fallback.
 ///
 /// Priority:
 /// 1. `{dir}/local/{filename}` (local override)
 /// 2. `{dir}/{filename}` (shared project config)
-/// 3. `$XDG_CONFIG_HOME/omni-dev/{filename}` (XDG global config)
-/// 4. `$HOME/.omni-dev/{filename}` (legacy global fallback)
+/// 3. `{team}/{filename}` (team repository from `{dir}/context.yaml`, see
+///    [`super::team`])
+/// 4. `$XDG_CONFIG_HOME/omni-dev/{filename}` (XDG global config)
+/// 5. `$HOME/.omni-dev/{filename}` (legacy global fallback)
 pub fn resolve_config_file(dir: &Path, filename: &str) -> PathBuf {
     resolve_config_file_with(dir, filename, &SystemEnv, dirs::home_dir().as_deref())
 }
@@ -69,6 +80,14 @@ fn resolve_config_file_with(
         return project_path;
     }
 
+    // Check the shared team repository
+    if let Some(team_dir) = super::team::team_config_dir(dir) {
+        let team_path = team_dir.join(filename);
+        if team_path.exists() {
+            return team_path;
+        }
+    }
+
     // Check XDG config directory
     if let Some(xdg_dir) = xdg_config_dir_with(env, home) {
         let xdg_path = xdg_dir.join(filename);
@@ -89,6 +108,48 @@ fn resolve_config_file_with(
     project_path
 }
 
+/// Returns every tier [`resolve_config_file`] consults for `filename`, in
+/// priority order.
+fn config_file_candidates(dir: &Path, filename: &str) -> Vec<PathBuf> {
+    config_resolution_chain(dir, filename)
+        .iter()
+        .filter_map(ConfigSourceLabel::path)
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+/// Returns every tier consulted for `filename`, in priority order, whether
+/// or not the file exists there (for diagnostic display).
+pub fn config_resolution_chain(dir: &Path, filename: &str) -> Vec<ConfigSourceLabel> {
+    config_resolution_chain_with(dir, filename, &SystemEnv, dirs::home_dir().as_deref())
+}
+
+/// Inner seam for [`config_resolution_chain`], mirroring
+/// [`resolve_config_file_with`].
+fn config_resolution_chain_with(
+    dir: &Path,
+    filename: &str,
+    env: &impl EnvSource,
+    home: Option<&Path>,
+) -> Vec<ConfigSourceLabel> {
+    let mut chain = vec![
+        ConfigSourceLabel::LocalOverride(dir.join("local").join(filename)),
+        ConfigSourceLabel::Project(dir.join(filename)),
+    ];
+    if let Some(team_dir) = super::team::team_config_dir(dir) {
+        chain.push(ConfigSourceLabel::Team(team_dir.join(filename)));
+    }
+    if let Some(xdg_dir) = xdg_config_dir_with(env, home) {
+        chain.push(ConfigSourceLabel::Xdg(xdg_dir.join(filename)));
+    }
+    if let Some(home_dir) = home {
+        chain.push(ConfigSourceLabel::Global(
+            home_dir.join(".omni-dev").join(filename),
+        ));
+    }
+    chain
+}
+
 /// Walks up from `start` toward the repository root, looking for `.omni-dev/`.
 ///
 /// Returns the first `.omni-dev/` directory found. Stops at the repository
@@ -231,12 +292,19 @@ pub fn resolve_context_dir_at(override_dir: Option<&Path>, repo_root: &Path) ->
 /// Loads a config file's content via the standard resolution chain.
 ///
 /// Uses [`resolve_config_file`] to find the file, then reads its content.
+/// Markdown files declaring `extends:` front matter have the remote source
+/// expanded in place (see [`super::remote`]).
 /// Returns `Ok(None)` if no file exists at any tier.
 pub fn load_config_content(dir: &Path, filename: &str) -> Result<Option<String>> {
     let path = resolve_config_file(dir, filename);
     if path.exists() {
         let content = fs::read_to_string(&path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+        if filename.ends_with(".md") {
+            return Ok(Some(super::remote::resolve_markdown_extends_or_local(
+                &content, filename,
+            )));
+        }
         Ok(Some(content))
     } else {
         Ok(None)
@@ -250,6 +318,8 @@ pub enum ConfigSourceLabel {
     LocalOverride(PathBuf),
     /// Found in `{dir}/{filename}`.
     Project(PathBuf),
+    /// Found in the team repository declared by `{dir}/context.yaml`.
+    Team(PathBuf),
     /// Found in `$XDG_CONFIG_HOME/omni-dev/{filename}`.
     Xdg(PathBuf),
     /// Found in `$HOME/.omni-dev/{filename}`.
@@ -258,11 +328,26 @@ pub enum ConfigSourceLabel {
     NotFound,
 }
 
+impl ConfigSourceLabel {
+    /// Returns the path of the tier, or `None` for [`Self::NotFound`].
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::LocalOverride(p)
+            | Self::Project(p)
+            | Self::Team(p)
+            | Self::Xdg(p)
+            | Self::Global(p) => Some(p),
+            Self::NotFound => None,
+        }
+    }
+}
+
 impl fmt::Display for ConfigSourceLabel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::LocalOverride(p) => write!(f, "Local override: {}", p.display()),
             Self::Project(p) => write!(f, "Project: {}", p.display()),
+            Self::Team(p) => write!(f, "Team (context.yaml): {}", p.display()),
             Self::Xdg(p) => write!(f, "Global (XDG): {}", p.display()),
             Self::Global(p) => write!(f, "Global: {}", p.display()),
             Self::NotFound => write!(f, "(not found)"),
@@ -297,6 +382,13 @@ fn config_source_label_with(
         return ConfigSourceLabel::Project(project_path);
     }
 
+    if let Some(team_dir) = super::team::team_config_dir(dir) {
+        let team_path = team_dir.join(filename);
+        if team_path.exists() {
+            return ConfigSourceLabel::Team(team_path);
+        }
+    }
+
     if let Some(xdg_dir) = xdg_config_dir_with(env, home) {
         let xdg_path = xdg_dir.join(filename);
         if xdg_path.exists() {
@@ -316,10 +408,13 @@ fn config_source_label_with(
 
 /// Loads project scopes from config files, merging ecosystem defaults.
 ///
-/// Resolves `scopes.yaml` via the standard config priority (local → project → home),
-/// then detects the project ecosystem and merges default scopes for that ecosystem.
+/// Resolves `scopes.yaml` via the standard config priority (local → project → home).
+/// When no `scopes.yaml` exists, scopes are inferred from the repository's
+/// workspace packages and `CODEOWNERS` file instead. The scope pack named by
+/// `pack:`, or else the one for the detected ecosystem, is then merged in.
 pub fn load_project_scopes(context_dir: &Path, repo_path: &Path) -> Vec<ScopeDefinition> {
     let scopes_path = resolve_config_file(context_dir, "scopes.yaml");
+    let mut pack = None;
     let mut scopes = if scopes_path.exists() {
         let scopes_yaml = match fs::read_to_string(&scopes_path) {
             Ok(content) => content,
@@ -329,7 +424,14 @@ pub fn load_project_scopes(context_dir: &Path, repo_path: &Path) -> Vec<ScopeDef
             }
         };
         match serde_yaml::from_str::<ScopesConfig>(&scopes_yaml) {
-            Ok(config) => config.scopes,
+            Ok(config) => {
+                let mut scopes = config.scopes;
+                if let Some(spec) = &config.extends {
+                    merge_remote_scopes(&mut scopes, spec);
+                }
+                pack = config.pack;
+                scopes
+            }
             Err(e) => {
                 tracing::warn!(
                     "Ignoring malformed scopes file {}: {e}",
@@ -339,157 +441,358 @@ pub fn load_project_scopes(context_dir: &Path, repo_path: &Path) -> Vec<ScopeDef
             }
         }
     } else {
-        vec![]
+        inferred_scopes(repo_path)
     };
 
-    merge_ecosystem_scopes(&mut scopes, repo_path);
+    merge_pack_scopes(&mut scopes, pack.as_deref(), &detect_ecosystem(repo_path));
     scopes
 }
 
-/// Merges ecosystem-detected default scopes into the given scope list.
+/// Infers scopes for a repository without a `scopes.yaml`: one per workspace
+/// package, then the `CODEOWNERS` directories not already covered by a
+/// package.
+fn inferred_scopes(repo_path: &Path) -> Vec<ScopeDefinition> {
+    let mut scopes = super::workspace::load_workspace_scopes(repo_path);
+    for scope in super::codeowners::load_codeowners_scopes(repo_path) {
+        let covered = scopes.iter().any(|s| {
+            s.name == scope.name
+                || scope
+                    .file_patterns
+                    .iter()
+                    .all(|pattern| s.file_patterns.contains(pattern))
+        });
+        if !covered {
+            scopes.push(scope);
+        }
+    }
+    scopes
+}
+
+/// Merges scopes from a remote `extends:` source, skipping names already
+/// defined locally. Fetch and parse failures are logged and ignored so an
+/// unreachable ruleset never blocks local work.
+fn merge_remote_scopes(scopes: &mut Vec<ScopeDefinition>, spec: &str) {
+    let remote = match super::remote::fetch_source_file(spec, "scopes.yaml") {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Ignoring scopes extends source {spec}: {e:#}");
+            return;
+        }
+    };
+    match serde_yaml::from_str::<ScopesConfig>(&remote) {
+        Ok(config) => {
+            for scope in config.scopes {
+                if !scopes.iter().any(|s| s.name == scope.name) {
+                    scopes.push(scope);
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Ignoring malformed scopes from {spec}: {e}"),
+    }
+}
+
+/// Loads the project's allowed commit types from `types.yaml`.
+///
+/// Resolves `types.yaml` via the standard config priority (local → project →
+/// home). In the default `extend` mode the listed types are added to the
+/// built-in [`DEFAULT_COMMIT_TYPES`](crate::git::commit::DEFAULT_COMMIT_TYPES);
+/// in `replace` mode they are the complete list. Returns an empty list when no
+/// `types.yaml` exists or it is malformed, meaning the built-in types apply.
+pub fn load_project_types(context_dir: &Path) -> Vec<TypeDefinition> {
+    let types_path = resolve_config_file(context_dir, "types.yaml");
+    if !types_path.exists() {
+        return vec![];
+    }
+    let types_yaml = match fs::read_to_string(&types_path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Cannot read types file {}: {e}", types_path.display());
+            return vec![];
+        }
+    };
+    match parse_types_config(&types_yaml) {
+        Ok(types) => types,
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring malformed types file {}: {e}",
+                types_path.display()
+            );
+            vec![]
+        }
+    }
+}
+
+/// Loads the project's branch naming patterns from `branch-conventions.yaml`.
+///
+/// Resolves `branch-conventions.yaml` via the standard config priority
+/// (local → project → home). Entries whose pattern is not a valid regex are
+/// skipped with a warning. Returns an empty list when no file exists or it is
+/// malformed, meaning only the built-in patterns apply.
+pub fn load_branch_conventions(context_dir: &Path) -> Vec<BranchConvention> {
+    let conventions_path = resolve_config_file(context_dir, "branch-conventions.yaml");
+    if !conventions_path.exists() {
+        return vec![];
+    }
+    let conventions_yaml = match fs::read_to_string(&conventions_path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!(
+                "Cannot read branch conventions file {}: {e}",
+                conventions_path.display()
+            );
+            return vec![];
+        }
+    };
+    match serde_yaml::from_str::<BranchConventionsConfig>(&conventions_yaml) {
+        Ok(config) => config
+            .conventions
+            .into_iter()
+            .filter(|convention| match regex::Regex::new(&convention.pattern) {
+                Ok(_) => true,
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping invalid branch pattern '{}' in {}: {e}",
+                        convention.pattern,
+                        conventions_path.display()
+                    );
+                    false
+                }
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring malformed branch conventions file {}: {e}",
+                conventions_path.display()
+            );
+            vec![]
+        }
+    }
+}
+
+/// Loads the project's work pattern rules from `work-patterns.yaml`.
+///
+/// Resolves `work-patterns.yaml` via the standard config priority (local →
+/// project → home). Rules with neither `files` nor `types`, or with an
+/// invalid glob, are skipped with a warning. Returns an empty list when no
+/// file exists or it is malformed.
+pub fn load_work_pattern_rules(context_dir: &Path) -> Vec<WorkPatternRule> {
+    let rules_path = resolve_config_file(context_dir, "work-patterns.yaml");
+    if !rules_path.exists() {
+        return vec![];
+    }
+    let rules_yaml = match fs::read_to_string(&rules_path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!(
+                "Cannot read work patterns file {}: {e}",
+                rules_path.display()
+            );
+            return vec![];
+        }
+    };
+    match serde_yaml::from_str::<WorkPatternsConfig>(&rules_yaml) {
+        Ok(config) => config
+            .patterns
+            .into_iter()
+            .filter(|rule| {
+                if rule.files.is_empty() && rule.types.is_empty() {
+                    tracing::warn!(
+                        "Skipping work pattern '{}' in {}: it needs `files` or `types`",
+                        rule.name,
+                        rules_path.display()
+                    );
+                    return false;
+                }
+                match rule.files.iter().find(|p| globset::Glob::new(p).is_err()) {
+                    Some(pattern) => {
+                        tracing::warn!(
+                            "Skipping work pattern '{}' in {}: invalid glob '{pattern}'",
+                            rule.name,
+                            rules_path.display()
+                        );
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring malformed work patterns file {}: {e}",
+                rules_path.display()
+            );
+            vec![]
+        }
+    }
+}
+
+/// Loads the project's curated commit message examples from `examples.md`.
+///
+/// Resolves `examples.md` via the standard config priority (local → project
+/// → home). Returns an empty list when no file exists or it cannot be read.
+pub fn load_commit_examples(context_dir: &Path) -> Vec<String> {
+    let path = resolve_config_file(context_dir, super::examples::EXAMPLES_FILE);
+    if !path.exists() {
+        return vec![];
+    }
+    match fs::read_to_string(&path) {
+        Ok(content) => super::examples::parse_examples(&content),
+        Err(e) => {
+            tracing::warn!("Cannot read examples file {}: {e}", path.display());
+            vec![]
+        }
+    }
+}
+
+/// Loads the project's system prompt overrides from `prompts/*.md.hbs`.
+///
+/// Each template in [`PromptTemplate::ALL`] resolves via the standard config
+/// priority (local → project → home). A template that cannot be read or does
+/// not parse is skipped with a warning, leaving the built-in prompt in place.
+pub fn load_prompt_templates(context_dir: &Path) -> HashMap<String, String> {
+    let mut templates = HashMap::new();
+    for template in PromptTemplate::ALL {
+        let path = resolve_config_file(context_dir, &template.relative_path());
+        if !path.exists() {
+            continue;
+        }
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Cannot read prompt template {}: {e}", path.display());
+                continue;
+            }
+        };
+        match crate::claude::prompt_template::validate(&content) {
+            Ok(()) => {
+                templates.insert(template.name().to_string(), content);
+            }
+            Err(e) => {
+                tracing::warn!("Ignoring prompt template {}: {e:#}", path.display());
+            }
+        }
+    }
+    templates
+}
+
+/// Loads the project's commit type and scope to PR label mapping from
+/// `labels.yaml`.
+///
+/// Resolves `labels.yaml` via the standard config priority (local → project →
+/// home). Returns the built-in mapping when no `labels.yaml` exists or it is
+/// malformed.
+pub fn load_pr_label_config(context_dir: &Path) -> PrLabelConfig {
+    let labels_path = resolve_config_file(context_dir, "labels.yaml");
+    if !labels_path.exists() {
+        return PrLabelConfig::default();
+    }
+    let labels_yaml = match fs::read_to_string(&labels_path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("Cannot read labels file {}: {e}", labels_path.display());
+            return PrLabelConfig::default();
+        }
+    };
+    match serde_yaml::from_str(&labels_yaml) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring malformed labels file {}: {e}",
+                labels_path.display()
+            );
+            PrLabelConfig::default()
+        }
+    }
+}
+
+/// Parses `types.yaml` content into the effective list of commit types.
+fn parse_types_config(content: &str) -> Result<Vec<TypeDefinition>> {
+    let config: TypesConfig = serde_yaml::from_str(content)?;
+    let mut types = match config.mode {
+        TypesMode::Extend => crate::git::commit::DEFAULT_COMMIT_TYPES
+            .iter()
+            .map(|(name, description)| TypeDefinition {
+                name: (*name).to_string(),
+                description: (*description).to_string(),
+            })
+            .collect(),
+        TypesMode::Replace => Vec::new(),
+    };
+    for custom in config.types {
+        // A project entry re-describing a built-in type replaces it in place.
+        if let Some(existing) = types.iter_mut().find(|t| t.name == custom.name) {
+            *existing = custom;
+        } else {
+            types.push(custom);
+        }
+    }
+    Ok(types)
+}
+
+/// Merges the default scopes of the selected scope pack into the given scope
+/// list, skipping any that already exist by name.
 ///
-/// Detects the project ecosystem from marker files (Cargo.toml, package.json, etc.)
-/// and adds default scopes for that ecosystem, skipping any that already exist by name.
-fn merge_ecosystem_scopes(scopes: &mut Vec<ScopeDefinition>, repo_path: &Path) {
-    let ecosystem_scopes: Vec<(&str, &str, Vec<&str>)> = if repo_path.join("Cargo.toml").exists() {
-        vec![
-            (
-                "cargo",
-                "Cargo.toml and dependency management",
-                vec!["Cargo.toml", "Cargo.lock"],
-            ),
-            (
-                "lib",
-                "Library code and public API",
-                vec!["src/lib.rs", "src/**"],
-            ),
-            (
-                "cli",
-                "Command-line interface",
-                vec!["src/main.rs", "src/cli/**"],
-            ),
-            (
-                "core",
-                "Core application logic",
-                vec!["src/core/**", "src/lib/**"],
-            ),
-            ("test", "Test code", vec!["tests/**", "src/**/test*"]),
-            (
-                "docs",
-                "Documentation",
-                vec!["docs/**", "README.md", "**/*.md"],
-            ),
-            (
-                "ci",
-                "Continuous integration",
-                vec![".github/**", ".gitlab-ci.yml"],
-            ),
-        ]
+/// `selected` is the `pack:` value from `scopes.yaml`; without one, the pack
+/// for `ecosystem` is used (see [`super::scope_packs::select`]).
+fn merge_pack_scopes(
+    scopes: &mut Vec<ScopeDefinition>,
+    selected: Option<&str>,
+    ecosystem: &Ecosystem,
+) {
+    let Some(pack) = super::scope_packs::select(selected, ecosystem) else {
+        return;
+    };
+    for scope in pack.scopes() {
+        if !scopes.iter().any(|s| s.name == scope.name) {
+            scopes.push(scope);
+        }
+    }
+}
+
+/// Guideline files recognised in nested `.omni-dev/` directories.
+const SUBTREE_FILES: &[&str] = &["commit-guidelines.md", "pr-guidelines.md"];
+
+/// Repository documents parsed for conventions.
+const DOCUMENTATION_FILES: &[&str] = &["CONTRIBUTING.md", "README.md"];
+
+/// Detects the project ecosystem from the marker files at `repo_path`.
+pub fn detect_ecosystem(repo_path: &Path) -> Ecosystem {
+    if repo_path.join("Cargo.toml").exists() {
+        Ecosystem::Rust
     } else if repo_path.join("package.json").exists() {
-        vec![
-            (
-                "deps",
-                "Dependencies and package.json",
-                vec!["package.json", "package-lock.json"],
-            ),
-            (
-                "config",
-                "Configuration files",
-                vec!["*.config.js", "*.config.json", ".env*"],
-            ),
-            (
-                "build",
-                "Build system and tooling",
-                vec!["webpack.config.js", "rollup.config.js"],
-            ),
-            (
-                "test",
-                "Test files",
-                vec!["test/**", "tests/**", "**/*.test.js"],
-            ),
-            (
-                "docs",
-                "Documentation",
-                vec!["docs/**", "README.md", "**/*.md"],
-            ),
-        ]
+        Ecosystem::Node
     } else if repo_path.join("pyproject.toml").exists()
         || repo_path.join("requirements.txt").exists()
     {
-        vec![
-            (
-                "deps",
-                "Dependencies and requirements",
-                vec!["requirements.txt", "pyproject.toml", "setup.py"],
-            ),
-            (
-                "config",
-                "Configuration files",
-                vec!["*.ini", "*.cfg", "*.toml"],
-            ),
-            (
-                "test",
-                "Test files",
-                vec!["test/**", "tests/**", "**/*_test.py"],
-            ),
-            (
-                "docs",
-                "Documentation",
-                vec!["docs/**", "README.md", "**/*.md", "**/*.rst"],
-            ),
-        ]
+        Ecosystem::Python
     } else if repo_path.join("go.mod").exists() {
-        vec![
-            (
-                "mod",
-                "Go modules and dependencies",
-                vec!["go.mod", "go.sum"],
-            ),
-            ("cmd", "Command-line applications", vec!["cmd/**"]),
-            ("pkg", "Library packages", vec!["pkg/**"]),
-            ("internal", "Internal packages", vec!["internal/**"]),
-            ("test", "Test files", vec!["**/*_test.go"]),
-            (
-                "docs",
-                "Documentation",
-                vec!["docs/**", "README.md", "**/*.md"],
-            ),
-        ]
+        Ecosystem::Go
     } else if repo_path.join("pom.xml").exists() || repo_path.join("build.gradle").exists() {
-        vec![
-            (
-                "build",
-                "Build system",
-                vec!["pom.xml", "build.gradle", "build.gradle.kts"],
-            ),
-            (
-                "config",
-                "Configuration",
-                vec!["src/main/resources/**", "application.properties"],
-            ),
-            ("test", "Test files", vec!["src/test/**"]),
-            (
-                "docs",
-                "Documentation",
-                vec!["docs/**", "README.md", "**/*.md"],
-            ),
-        ]
+        Ecosystem::Java
+    } else if TERRAFORM_MARKERS
+        .iter()
+        .any(|marker| repo_path.join(marker).exists())
+    {
+        Ecosystem::Terraform
     } else {
-        vec![]
-    };
-
-    for (name, description, patterns) in ecosystem_scopes {
-        if !scopes.iter().any(|s| s.name == name) {
-            scopes.push(ScopeDefinition {
-                name: name.to_string(),
-                description: description.to_string(),
-                examples: vec![],
-                file_patterns: patterns.into_iter().map(String::from).collect(),
-            });
-        }
+        Ecosystem::Generic
     }
 }
 
+/// Root module files that identify a Terraform repository.
+const TERRAFORM_MARKERS: &[&str] = &["main.tf", "versions.tf", "terraform.tf", "providers.tf"];
+
+/// Files whose presence selects the project ecosystem.
+const ECOSYSTEM_MARKERS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "requirements.txt",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+];
+
 /// Project context discovery system.
 pub struct ProjectDiscovery {
     repo_path: PathBuf,
@@ -506,22 +809,162 @@ impl ProjectDiscovery {
     }
 
     /// Discovers all project context.
+    ///
+    /// The result is cached under `<context_dir>/cache/` and reused until one
+    /// of the source files changes (see [`ContextCache`]). Nothing is written
+    /// when the context directory does not exist.
     pub fn discover(&self) -> Result<ProjectContext> {
-        let mut context = ProjectContext::default();
-
-        // 1. Check custom context directory (highest priority)
         let context_dir_path = if self.context_dir.is_absolute() {
             self.context_dir.clone()
         } else {
             self.repo_path.join(&self.context_dir)
         };
+        let mut context = if context_dir_path.is_dir() {
+            self.discover_cached(&context_dir_path)?
+        } else {
+            self.discover_uncached(&context_dir_path)?
+        };
+        // Nested directories are found through the git index, which changes
+        // on every commit, so they are scanned fresh rather than stamped.
+        context.subtree_contexts = self.load_subtree_contexts(&context_dir_path);
+        Ok(context)
+    }
+
+    /// Loads guidelines from nested `.omni-dev/` directories tracked in the
+    /// repository (e.g. `services/payments/.omni-dev/commit-guidelines.md`).
+    ///
+    /// Each file honours a `local/` override inside its own directory, but the
+    /// XDG and home tiers are not consulted. The repository-level context
+    /// directory is skipped.
+    fn load_subtree_contexts(&self, context_dir_path: &Path) -> Vec<SubtreeContext> {
+        let Ok(repo) = git2::Repository::open(&self.repo_path) else {
+            return vec![];
+        };
+        let Ok(index) = repo.index() else {
+            return vec![];
+        };
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for entry in index.iter() {
+            let Ok(path) = std::str::from_utf8(&entry.path) else {
+                continue;
+            };
+            let Some((subtree, file)) = path.rsplit_once("/.omni-dev/") else {
+                continue;
+            };
+            if !SUBTREE_FILES.contains(&file) {
+                continue;
+            }
+            let subtree = PathBuf::from(subtree);
+            if !dirs.contains(&subtree) {
+                dirs.push(subtree);
+            }
+        }
+
+        let mut subtrees = Vec::new();
+        for subtree in dirs {
+            let dir = self.repo_path.join(&subtree).join(".omni-dev");
+            if dir == context_dir_path {
+                continue;
+            }
+            let load = |filename: &str| {
+                let local = dir.join("local").join(filename);
+                let path = if local.exists() {
+                    local
+                } else {
+                    dir.join(filename)
+                };
+                let content = fs::read_to_string(&path).ok()?;
+                Some(super::remote::resolve_markdown_extends_or_local(
+                    &content, filename,
+                ))
+            };
+            let context = SubtreeContext {
+                commit_guidelines: load("commit-guidelines.md"),
+                pr_guidelines: load("pr-guidelines.md"),
+                path: subtree,
+            };
+            if context.commit_guidelines.is_some() || context.pr_guidelines.is_some() {
+                debug!(path = ?context.path, "Loaded subtree context");
+                subtrees.push(context);
+            }
+        }
+        subtrees
+    }
+
+    /// Serves discovery from the context directory's cache, refreshing it
+    /// when a source file changed.
+    fn discover_cached(&self, context_dir_path: &Path) -> Result<ProjectContext> {
+        // Stamp the sources before reading them, so an edit made while
+        // discovery runs invalidates the entry instead of being masked by it.
+        let cache = ContextCache::for_context_dir(context_dir_path);
+        let fingerprint = SourceFingerprint::of(&self.source_paths(context_dir_path));
+        if let Some(context) = cache.load(&fingerprint) {
+            debug!("Using cached project context");
+            return Ok(context);
+        }
+        let context = self.discover_uncached(context_dir_path)?;
+        cache.store(fingerprint, context.clone());
+        Ok(context)
+    }
+
+    /// Lists every path a discovery run may read, present or not.
+    fn source_paths(&self, context_dir_path: &Path) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for filename in [
+            "commit-guidelines.md",
+            "pr-guidelines.md",
+            "scopes.yaml",
+            "types.yaml",
+            "branch-conventions.yaml",
+            "work-patterns.yaml",
+            super::examples::EXAMPLES_FILE,
+        ] {
+            paths.extend(config_file_candidates(context_dir_path, filename));
+        }
+        for template in PromptTemplate::ALL {
+            paths.extend(config_file_candidates(
+                context_dir_path,
+                &template.relative_path(),
+            ));
+        }
+        for contexts_dir in [
+            context_dir_path.join("context").join("feature-contexts"),
+            context_dir_path
+                .join("local")
+                .join("context")
+                .join("feature-contexts"),
+        ] {
+            if let Ok(entries) = fs::read_dir(&contexts_dir) {
+                let mut files: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+                files.sort();
+                paths.extend(files);
+            }
+            paths.push(contexts_dir);
+        }
+        paths.push(context_dir_path.join(super::team::CONTEXT_FILE));
+        paths.extend(
+            DOCUMENTATION_FILES
+                .iter()
+                .chain(ECOSYSTEM_MARKERS)
+                .chain(TERRAFORM_MARKERS)
+                .chain(super::workspace::WORKSPACE_MANIFESTS)
+                .map(|name| self.repo_path.join(name)),
+        );
+        paths
+    }
+
+    /// Runs discovery without consulting the cache.
+    fn discover_uncached(&self, context_dir_path: &Path) -> Result<ProjectContext> {
+        let mut context = ProjectContext::default();
+
+        // 1. Check custom context directory (highest priority)
         debug!(
             context_dir = ?context_dir_path,
             exists = context_dir_path.exists(),
             "Looking for context directory"
         );
         debug!("Loading omni-dev config");
-        self.load_omni_dev_config(&mut context, &context_dir_path)?;
+        self.load_omni_dev_config(&mut context, context_dir_path)?;
         debug!("Config loading completed");
 
         // 2. Standard git configuration files
@@ -547,6 +990,8 @@ impl ProjectDiscovery {
         );
         if guidelines_path.exists() {
             let content = fs::read_to_string(&guidelines_path)?;
+            let content =
+                super::remote::resolve_markdown_extends_or_local(&content, "commit-guidelines.md");
             debug!(bytes = content.len(), "Loaded commit guidelines");
             context.commit_guidelines = Some(content);
         } else {
@@ -562,6 +1007,8 @@ impl ProjectDiscovery {
         );
         if pr_guidelines_path.exists() {
             let content = fs::read_to_string(&pr_guidelines_path)?;
+            let content =
+                super::remote::resolve_markdown_extends_or_local(&content, "pr-guidelines.md");
             debug!(bytes = content.len(), "Loaded PR guidelines");
             context.pr_guidelines = Some(content);
         } else {
@@ -575,6 +1022,10 @@ impl ProjectDiscovery {
             match serde_yaml::from_str::<ScopesConfig>(&scopes_yaml) {
                 Ok(scopes_config) => {
                     context.valid_scopes = scopes_config.scopes;
+                    if let Some(spec) = &scopes_config.extends {
+                        merge_remote_scopes(&mut context.valid_scopes, spec);
+                    }
+                    context.scope_pack = scopes_config.pack;
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -583,8 +1034,16 @@ impl ProjectDiscovery {
                     );
                 }
             }
+        } else {
+            context.valid_scopes = inferred_scopes(&self.repo_path);
         }
 
+        context.commit_types = load_project_types(dir);
+        context.branch_conventions = load_branch_conventions(dir);
+        context.work_pattern_rules = load_work_pattern_rules(dir);
+        context.prompt_templates = load_prompt_templates(dir);
+        context.commit_examples = load_commit_examples(dir);
+
         // Load feature contexts (check both local and standard directories)
         let local_contexts_dir = dir.join("local").join("context").join("feature-contexts");
         let contexts_dir = dir.join("context").join("feature-contexts");
@@ -629,25 +1088,13 @@ impl ProjectDiscovery {
 
     /// Detects project ecosystem and applies conventions.
     fn detect_ecosystem(&self, context: &mut ProjectContext) -> Result<()> {
-        context.ecosystem = if self.repo_path.join("Cargo.toml").exists() {
-            Ecosystem::Rust
-        } else if self.repo_path.join("package.json").exists() {
-            Ecosystem::Node
-        } else if self.repo_path.join("pyproject.toml").exists()
-            || self.repo_path.join("requirements.txt").exists()
-        {
-            Ecosystem::Python
-        } else if self.repo_path.join("go.mod").exists() {
-            Ecosystem::Go
-        } else if self.repo_path.join("pom.xml").exists()
-            || self.repo_path.join("build.gradle").exists()
-        {
-            Ecosystem::Java
-        } else {
-            Ecosystem::Generic
-        };
+        context.ecosystem = detect_ecosystem(&self.repo_path);
 
-        merge_ecosystem_scopes(&mut context.valid_scopes, &self.repo_path);
+        merge_pack_scopes(
+            &mut context.valid_scopes,
+            context.scope_pack.as_deref(),
+            &context.ecosystem,
+        );
 
         Ok(())
     }
@@ -807,10 +1254,85 @@ impl ProjectDiscovery {
     }
 }
 
+/// Configuration structure for types.yaml.
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct TypesConfig {
+    /// Whether `types` extends or replaces the built-in types.
+    #[serde(default)]
+    mode: TypesMode,
+    types: Vec<TypeDefinition>,
+}
+
+/// How `types.yaml` combines with the built-in conventional types.
+#[derive(serde::Deserialize, schemars::JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+enum TypesMode {
+    /// Adds the listed types to the built-in ones.
+    #[default]
+    Extend,
+    /// Uses only the listed types.
+    Replace,
+}
+
+/// Configuration structure for branch-conventions.yaml.
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct BranchConventionsConfig {
+    #[serde(default)]
+    conventions: Vec<BranchConvention>,
+}
+
+/// Configuration structure for work-patterns.yaml.
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct WorkPatternsConfig {
+    #[serde(default)]
+    patterns: Vec<WorkPatternRule>,
+}
+
 /// Configuration structure for scopes.yaml.
-#[derive(serde::Deserialize)]
-struct ScopesConfig {
-    scopes: Vec<ScopeDefinition>,
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub(crate) struct ScopesConfig {
+    /// Remote source whose scopes are merged in (see [`super::remote`]).
+    #[serde(default)]
+    pub(crate) extends: Option<String>,
+    /// Scope pack whose defaults are merged in (see [`super::scope_packs`]).
+    #[serde(default)]
+    pub(crate) pack: Option<String>,
+    #[serde(default)]
+    pub(crate) scopes: Vec<ScopeDefinition>,
+}
+
+/// JSON Schemas of the YAML files read from the context directory, by file
+/// name, for `omni-dev config validate`.
+pub fn context_file_schemas() -> Vec<(&'static str, serde_json::Value)> {
+    fn schema<T: schemars::JsonSchema>() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(T)).unwrap_or(serde_json::Value::Null)
+    }
+    vec![
+        ("scopes.yaml", schema::<ScopesConfig>()),
+        ("types.yaml", schema::<TypesConfig>()),
+        ("labels.yaml", schema::<PrLabelConfig>()),
+        (
+            "branch-conventions.yaml",
+            schema::<BranchConventionsConfig>(),
+        ),
+        ("work-patterns.yaml", schema::<WorkPatternsConfig>()),
+        (
+            crate::git::generated::IGNORE_FILE,
+            crate::git::generated::ignore_schema(),
+        ),
+        (
+            crate::git::changelog_rule::CHANGELOG_RULE_FILE,
+            crate::git::changelog_rule::rule_schema(),
+        ),
+        (
+            super::team::CONTEXT_FILE,
+            schema::<super::team::TeamContextConfig>(),
+        ),
+    ]
 }
 
 /// Extracts commit types from a line.
@@ -915,7 +1437,7 @@ mod tests {
         assert_ne!(resolved, base.join("local").join("scopes.yaml"));
     }
 
-    // ── merge_ecosystem_scopes ───────────────────────────────────────
+    // ── merge_pack_scopes ────────────────────────────────────────────
 
     #[test]
     fn rust_ecosystem_detected() -> anyhow::Result<()> {
@@ -926,7 +1448,7 @@ mod tests {
         std::fs::write(dir.path().join("Cargo.toml"), "[package]")?;
 
         let mut scopes = vec![];
-        merge_ecosystem_scopes(&mut scopes, dir.path());
+        merge_pack_scopes(&mut scopes, None, &detect_ecosystem(dir.path()));
 
         let names: Vec<&str> = scopes.iter().map(|s| s.name.as_str()).collect();
         assert!(names.contains(&"cargo"), "missing 'cargo' scope");
@@ -947,7 +1469,7 @@ mod tests {
         std::fs::write(dir.path().join("package.json"), "{}")?;
 
         let mut scopes = vec![];
-        merge_ecosystem_scopes(&mut scopes, dir.path());
+        merge_pack_scopes(&mut scopes, None, &detect_ecosystem(dir.path()));
 
         let names: Vec<&str> = scopes.iter().map(|s| s.name.as_str()).collect();
         assert!(names.contains(&"deps"), "missing 'deps' scope");
@@ -964,7 +1486,7 @@ mod tests {
         std::fs::write(dir.path().join("go.mod"), "module example")?;
 
         let mut scopes = vec![];
-        merge_ecosystem_scopes(&mut scopes, dir.path());
+        merge_pack_scopes(&mut scopes, None, &detect_ecosystem(dir.path()));
 
         let names: Vec<&str> = scopes.iter().map(|s| s.name.as_str()).collect();
         assert!(names.contains(&"mod"), "missing 'mod' scope");
@@ -987,7 +1509,7 @@ mod tests {
             examples: vec![],
             file_patterns: vec!["custom/**".to_string()],
         }];
-        merge_ecosystem_scopes(&mut scopes, dir.path());
+        merge_pack_scopes(&mut scopes, None, &detect_ecosystem(dir.path()));
 
         // The custom "cli" scope should be preserved, not replaced
         let cli_scope = scopes.iter().find(|s| s.name == "cli").unwrap();
@@ -996,6 +1518,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn terraform_ecosystem_detected() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        std::fs::write(
+            dir.path().join("main.tf"),
+            "resource \"null_resource\" \"x\" {}",
+        )?;
+        assert!(matches!(detect_ecosystem(dir.path()), Ecosystem::Terraform));
+
+        let mut scopes = vec![];
+        merge_pack_scopes(&mut scopes, None, &detect_ecosystem(dir.path()));
+        let names: Vec<&str> = scopes.iter().map(|s| s.name.as_str()).collect();
+        assert!(names.contains(&"modules"), "missing 'modules' scope");
+        assert!(names.contains(&"providers"), "missing 'providers' scope");
+        Ok(())
+    }
+
     #[test]
     fn no_marker_files_produces_empty() {
         let dir = {
@@ -1003,7 +1545,7 @@ mod tests {
             TempDir::new_in("tmp").unwrap()
         };
         let mut scopes = vec![];
-        merge_ecosystem_scopes(&mut scopes, dir.path());
+        merge_pack_scopes(&mut scopes, None, &detect_ecosystem(dir.path()));
         assert!(scopes.is_empty());
     }
 
@@ -1039,6 +1581,27 @@ scopes:
         Ok(())
     }
 
+    #[test]
+    fn load_project_scopes_uses_selected_pack() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]")?;
+        std::fs::write(
+            dir.path().join("scopes.yaml"),
+            "pack: terraform\nscopes: []\n",
+        )?;
+
+        let scopes = load_project_scopes(dir.path(), dir.path());
+        assert!(scopes.iter().any(|s| s.name == "modules"));
+        assert!(!scopes.iter().any(|s| s.name == "cargo"));
+
+        std::fs::write(dir.path().join("scopes.yaml"), "pack: none\nscopes: []\n")?;
+        assert!(load_project_scopes(dir.path(), dir.path()).is_empty());
+        Ok(())
+    }
+
     #[test]
     fn load_project_scopes_no_file() -> anyhow::Result<()> {
         let dir = {
@@ -1053,6 +1616,217 @@ scopes:
         Ok(())
     }
 
+    #[test]
+    fn load_project_scopes_infers_from_codeowners_without_yaml() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]")?;
+        std::fs::write(dir.path().join("CODEOWNERS"), "/src/payments/ @org/pay\n")?;
+
+        let scopes = load_project_scopes(dir.path(), dir.path());
+        let payments = scopes
+            .iter()
+            .find(|s| s.name == "payments")
+            .expect("CODEOWNERS scope");
+        assert_eq!(payments.file_patterns, vec!["src/payments/**"]);
+        // Ecosystem defaults still merge in alongside.
+        assert!(scopes.iter().any(|s| s.name == "cargo"));
+        Ok(())
+    }
+
+    #[test]
+    fn load_project_scopes_prefers_workspace_packages_over_codeowners() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        let root = dir.path();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )?;
+        std::fs::create_dir_all(root.join("crates/core"))?;
+        std::fs::write(
+            root.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"omni-core\"\n",
+        )?;
+        std::fs::write(
+            root.join("CODEOWNERS"),
+            "/crates/core/ @org/core\n/docs/ @org/docs\n",
+        )?;
+
+        let scopes = load_project_scopes(root, root);
+        let core = scopes
+            .iter()
+            .find(|s| s.name == "omni-core")
+            .expect("workspace package scope");
+        assert_eq!(core.file_patterns, vec!["crates/core/**"]);
+        // The CODEOWNERS entry for the same directory is dropped; others stay.
+        assert!(!scopes
+            .iter()
+            .any(|s| s.name == "core" && s.file_patterns == core.file_patterns));
+        assert!(scopes.iter().any(|s| s.name == "docs"));
+        Ok(())
+    }
+
+    #[test]
+    fn load_project_scopes_ignores_codeowners_when_yaml_present() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        let config_dir = dir.path().join("config");
+        std::fs::create_dir_all(&config_dir)?;
+        std::fs::write(
+            config_dir.join("scopes.yaml"),
+            "scopes:\n  - name: custom\n    description: Custom\n    examples: []\n    file_patterns: [\"src/custom/**\"]\n",
+        )?;
+        std::fs::write(dir.path().join("CODEOWNERS"), "/src/payments/ @org/pay\n")?;
+
+        let scopes = load_project_scopes(&config_dir, dir.path());
+        assert!(scopes.iter().any(|s| s.name == "custom"));
+        assert!(!scopes.iter().any(|s| s.name == "payments"));
+        Ok(())
+    }
+
+    // ── types.yaml ───────────────────────────────────────────────────
+
+    #[test]
+    fn types_config_extends_builtin_types_by_default() -> anyhow::Result<()> {
+        let types = parse_types_config(
+            "types:\n  - name: infra\n    description: Infrastructure\n  - name: fix\n    description: Bug fixes only\n",
+        )?;
+        let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names.first(), Some(&"feat"));
+        assert_eq!(names.last(), Some(&"infra"));
+        assert_eq!(names.iter().filter(|n| **n == "fix").count(), 1);
+        let fix = types.iter().find(|t| t.name == "fix").unwrap();
+        assert_eq!(fix.description, "Bug fixes only");
+        Ok(())
+    }
+
+    #[test]
+    fn types_config_replace_mode_uses_only_listed_types() -> anyhow::Result<()> {
+        let types = parse_types_config("mode: replace\ntypes:\n  - name: sec\n  - name: feat\n")?;
+        let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["sec", "feat"]);
+        Ok(())
+    }
+
+    #[test]
+    fn load_project_types_without_file_is_empty() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        assert!(load_project_types(dir.path()).is_empty());
+        std::fs::write(dir.path().join("types.yaml"), "types: not-a-list\n")?;
+        assert!(load_project_types(dir.path()).is_empty());
+        Ok(())
+    }
+
+    // ── branch-conventions.yaml ──────────────────────────────────────
+
+    #[test]
+    fn load_branch_conventions_skips_invalid_patterns() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        assert!(load_branch_conventions(dir.path()).is_empty());
+        std::fs::write(
+            dir.path().join("branch-conventions.yaml"),
+            "conventions:\n  - pattern: '^users/[^/]+/(?P<description>.+)$'\n    type: feature\n  - pattern: '(unclosed'\n",
+        )?;
+        let conventions = load_branch_conventions(dir.path());
+        assert_eq!(conventions.len(), 1);
+        assert_eq!(conventions[0].work_type.as_deref(), Some("feature"));
+        std::fs::write(
+            dir.path().join("branch-conventions.yaml"),
+            "conventions: nope\n",
+        )?;
+        assert!(load_branch_conventions(dir.path()).is_empty());
+        Ok(())
+    }
+
+    // ── work-patterns.yaml ───────────────────────────────────────────
+
+    #[test]
+    fn load_work_pattern_rules_skips_unusable_rules() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        assert!(load_work_pattern_rules(dir.path()).is_empty());
+        std::fs::write(
+            dir.path().join("work-patterns.yaml"),
+            "patterns:
+  - name: database-migration
+    files: ['migrations/**']
+    guidance: Mention schema changes and rollback notes.
+  - name: anything
+    guidance: Matches nothing.
+  - name: broken
+    files: ['[unclosed']
+    guidance: Invalid glob.
+",
+        )?;
+        let rules = load_work_pattern_rules(dir.path());
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name, "database-migration");
+        Ok(())
+    }
+
+    // ── prompts/*.md.hbs ─────────────────────────────────────────────
+
+    #[test]
+    fn load_prompt_templates_skips_invalid_templates() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        assert!(load_prompt_templates(dir.path()).is_empty());
+        std::fs::create_dir_all(dir.path().join("prompts"))?;
+        std::fs::write(
+            dir.path().join("prompts/twiddle-system.md.hbs"),
+            "Team rules first.\n\n{{default_prompt}}\n",
+        )?;
+        std::fs::write(
+            dir.path().join("prompts/pr-system.md.hbs"),
+            "{{#if branch}}unclosed",
+        )?;
+        let templates = load_prompt_templates(dir.path());
+        assert_eq!(templates.len(), 1);
+        assert!(templates["twiddle-system"].starts_with("Team rules first."));
+        Ok(())
+    }
+
+    // ── labels.yaml ──────────────────────────────────────────────────
+
+    #[test]
+    fn load_pr_label_config_reads_file_or_defaults() -> anyhow::Result<()> {
+        let dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        assert_eq!(load_pr_label_config(dir.path()), PrLabelConfig::default());
+        std::fs::write(
+            dir.path().join("labels.yaml"),
+            "types:\n  feat: feature\nscope_prefix: \"area: \"\n",
+        )?;
+        let config = load_pr_label_config(dir.path());
+        assert_eq!(
+            config.labels_for("feat", "cli"),
+            vec!["feature", "area: cli"]
+        );
+        assert!(config.labels_for("fix", "").is_empty());
+        std::fs::write(dir.path().join("labels.yaml"), "types: [feat]\n")?;
+        assert_eq!(load_pr_label_config(dir.path()), PrLabelConfig::default());
+        Ok(())
+    }
+
     // ── Helper functions ─────────────────────────────────────────────
 
     #[test]
@@ -1652,4 +2426,106 @@ scopes:
         assert_eq!(result, None);
         Ok(())
     }
+
+    // ── discovery cache ──────────────────────────────────────────────────
+
+    #[test]
+    fn config_resolution_chain_covers_every_tier() {
+        let dir = Path::new("/repo/.omni-dev");
+        let chain = config_resolution_chain_with(
+            dir,
+            "scopes.yaml",
+            &xdg_env(Path::new("/xdg")),
+            Some(Path::new("/home/me")),
+        );
+        assert_eq!(
+            chain,
+            vec![
+                ConfigSourceLabel::LocalOverride(dir.join("local/scopes.yaml")),
+                ConfigSourceLabel::Project(dir.join("scopes.yaml")),
+                ConfigSourceLabel::Xdg(PathBuf::from("/xdg/omni-dev/scopes.yaml")),
+                ConfigSourceLabel::Global(PathBuf::from("/home/me/.omni-dev/scopes.yaml")),
+            ]
+        );
+        assert_eq!(ConfigSourceLabel::NotFound.path(), None);
+    }
+
+    #[test]
+    fn discover_caches_until_a_source_changes() -> anyhow::Result<()> {
+        let repo = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        let context_dir = repo.path().join(".omni-dev");
+        std::fs::create_dir_all(&context_dir)?;
+        std::fs::write(
+            context_dir.join("scopes.yaml"),
+            "scopes:\n  - name: api\n    description: API\n",
+        )?;
+        let discovery = ProjectDiscovery::new(repo.path().to_path_buf(), context_dir.clone());
+
+        let first = discovery.discover()?;
+        assert!(first.valid_scopes.iter().any(|s| s.name == "api"));
+        assert!(context_dir.join("cache/project-context.json").exists());
+        assert!(discovery
+            .discover()?
+            .valid_scopes
+            .iter()
+            .any(|s| s.name == "api"));
+
+        std::fs::write(
+            context_dir.join("scopes.yaml"),
+            "scopes:\n  - name: cli\n    description: Command line\n",
+        )?;
+        let changed = discovery.discover()?;
+        assert!(changed.valid_scopes.iter().any(|s| s.name == "cli"));
+        assert!(!changed.valid_scopes.iter().any(|s| s.name == "api"));
+        Ok(())
+    }
+
+    #[test]
+    fn discover_without_context_dir_writes_no_cache() -> anyhow::Result<()> {
+        let repo = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        let context_dir = repo.path().join(".omni-dev");
+        ProjectDiscovery::new(repo.path().to_path_buf(), context_dir.clone()).discover()?;
+        assert!(!context_dir.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn discover_loads_tracked_subtree_guidelines() -> anyhow::Result<()> {
+        let repo_dir = {
+            std::fs::create_dir_all("tmp")?;
+            TempDir::new_in("tmp")?
+        };
+        let root = repo_dir.path();
+        let repo = git2::Repository::init(root)?;
+        let nested = root.join("services/payments/.omni-dev");
+        std::fs::create_dir_all(nested.join("local"))?;
+        std::fs::write(nested.join("commit-guidelines.md"), "shared")?;
+        std::fs::write(nested.join("local/commit-guidelines.md"), "mine")?;
+        std::fs::write(nested.join("scopes.yaml"), "scopes: []\n")?;
+        std::fs::create_dir_all(root.join(".omni-dev"))?;
+        std::fs::write(root.join(".omni-dev/commit-guidelines.md"), "root")?;
+        let mut index = repo.index()?;
+        index.add_path(Path::new(
+            "services/payments/.omni-dev/commit-guidelines.md",
+        ))?;
+        index.add_path(Path::new("services/payments/.omni-dev/scopes.yaml"))?;
+        index.add_path(Path::new(".omni-dev/commit-guidelines.md"))?;
+        index.write()?;
+
+        let context =
+            ProjectDiscovery::new(root.to_path_buf(), root.join(".omni-dev")).discover()?;
+        assert_eq!(context.commit_guidelines.as_deref(), Some("root"));
+        assert_eq!(context.subtree_contexts.len(), 1);
+        let subtree = &context.subtree_contexts[0];
+        assert_eq!(subtree.path, PathBuf::from("services/payments"));
+        assert_eq!(subtree.commit_guidelines.as_deref(), Some("mine"));
+        assert_eq!(subtree.pr_guidelines, None);
+        Ok(())
+    }
 }
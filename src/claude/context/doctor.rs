@@ -0,0 +1,468 @@
+//! Health checks behind `omni-dev context doctor`.
+//!
+//! Discovery silently ignores a malformed `scopes.yaml` and never explains
+//! why a scope is not picked, so the checks here surface what it would
+//! otherwise swallow: schema errors, patterns that match nothing, scopes
+//! that tie or are always shadowed during scope resolution (see
+//! [`crate::git::commit::resolve_scope`]), and guidance files too large
+//! for the model's input context.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use globset::{Glob, GlobMatcher};
+
+use super::discovery::ScopesConfig;
+use super::scope_packs;
+use crate::claude::token_budget;
+use crate::git::commit::count_specificity;
+
+/// Guidance files whose resolution chain the doctor reports.
+pub const GUIDANCE_FILES: &[&str] = &[
+    "commit-guidelines.md",
+    "pr-guidelines.md",
+    "scopes.yaml",
+    "types.yaml",
+    "labels.yaml",
+    "release-notes.md",
+    "examples.md",
+];
+
+/// Share of the model's input context (in percent) one guidance file may
+/// take before it is flagged; the rest is needed for diffs.
+const MAX_GUIDANCE_PERCENT: usize = 10;
+
+/// Keys recognised at the top level of `scopes.yaml`.
+const SCOPES_FILE_KEYS: &[&str] = &["extends", "pack", "scopes"];
+
+/// Keys recognised in a `scopes.yaml` scope entry.
+const SCOPE_KEYS: &[&str] = &["name", "description", "examples", "file_patterns"];
+
+/// How serious a finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The file is ignored or misread by discovery.
+    Error,
+    /// The file loads, but probably not as intended.
+    Warning,
+}
+
+/// One problem found in the project context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// The guidance file the problem is in.
+    pub file: String,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl Finding {
+    fn error(file: &str, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            file: file.to_string(),
+            message,
+        }
+    }
+
+    fn warning(file: &str, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            file: file.to_string(),
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let icon = match self.severity {
+            Severity::Error => "❌",
+            Severity::Warning => "⚠️ ",
+        };
+        write!(f, "{icon} {}: {}", self.file, self.message)
+    }
+}
+
+/// Validates `scopes.yaml` content and checks its patterns against the
+/// repository's tracked files.
+///
+/// Returns early with a single error when the file does not parse, since
+/// discovery then ignores it entirely.
+pub fn check_scopes(content: &str, tracked_files: &[&str]) -> Vec<Finding> {
+    const FILE: &str = "scopes.yaml";
+    let config = match serde_yaml::from_str::<ScopesConfig>(content) {
+        Ok(config) => config,
+        Err(e) => {
+            return vec![Finding::error(
+                FILE,
+                format!("does not match the schema and is ignored: {e}"),
+            )]
+        }
+    };
+
+    let mut findings = unknown_key_findings(content);
+    if let Some(pack) = &config.pack {
+        if !pack.trim().eq_ignore_ascii_case(scope_packs::NO_PACK)
+            && scope_packs::find(pack).is_none()
+        {
+            findings.push(Finding::error(
+                FILE,
+                format!(
+                    "unknown scope pack `{pack}`; expected one of: {}, {}",
+                    scope_packs::names().join(", "),
+                    scope_packs::NO_PACK
+                ),
+            ));
+        }
+    }
+    let mut compiled = Vec::new();
+    for (index, scope) in config.scopes.iter().enumerate() {
+        if config.scopes[..index].iter().any(|s| s.name == scope.name) {
+            findings.push(Finding::error(
+                FILE,
+                format!("scope `{}` is defined more than once", scope.name),
+            ));
+        }
+        if scope.description.trim().is_empty() {
+            findings.push(Finding::warning(
+                FILE,
+                format!(
+                    "scope `{}` has no description; the AI cannot tell when to use it",
+                    scope.name
+                ),
+            ));
+        }
+        if scope.file_patterns.is_empty() {
+            findings.push(Finding::warning(
+                FILE,
+                format!(
+                    "scope `{}` has no file_patterns; it is never detected from files",
+                    scope.name
+                ),
+            ));
+        }
+        match CompiledScope::new(&scope.name, &scope.file_patterns) {
+            Ok(scope) => compiled.push(scope),
+            Err(message) => findings.push(Finding::error(FILE, message)),
+        }
+    }
+
+    findings.extend(resolution_findings(&compiled, tracked_files));
+    findings
+}
+
+/// Flags a guidance file estimated to use more than
+/// [`MAX_GUIDANCE_PERCENT`] of `model`'s input context.
+pub fn check_guidance_size(
+    file: &str,
+    content: &str,
+    model: &str,
+    input_context: usize,
+) -> Option<Finding> {
+    let tokens = token_budget::estimate_tokens(content);
+    if input_context == 0 || tokens * 100 <= input_context * MAX_GUIDANCE_PERCENT {
+        return None;
+    }
+    Some(Finding::warning(
+        file,
+        format!(
+            "~{tokens} tokens is {}% of {model}'s {input_context}-token input context \
+             (limit {MAX_GUIDANCE_PERCENT}%); large diffs will be split or fail the token budget",
+            tokens * 100 / input_context
+        ),
+    ))
+}
+
+/// Warns about keys discovery does not read (usually typos such as
+/// `file_pattern`).
+fn unknown_key_findings(content: &str) -> Vec<Finding> {
+    let Ok(serde_yaml::Value::Mapping(root)) = serde_yaml::from_str(content) else {
+        return vec![];
+    };
+    let mut findings = Vec::new();
+    for key in root.keys().filter_map(serde_yaml::Value::as_str) {
+        if !SCOPES_FILE_KEYS.contains(&key) {
+            findings.push(Finding::warning(
+                "scopes.yaml",
+                format!("unknown top-level key `{key}` is ignored"),
+            ));
+        }
+    }
+    let scopes = root
+        .get("scopes")
+        .and_then(serde_yaml::Value::as_sequence)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for scope in scopes.iter().filter_map(serde_yaml::Value::as_mapping) {
+        let name = scope
+            .get("name")
+            .and_then(serde_yaml::Value::as_str)
+            .unwrap_or("?");
+        for key in scope.keys().filter_map(serde_yaml::Value::as_str) {
+            if !SCOPE_KEYS.contains(&key) {
+                findings.push(Finding::warning(
+                    "scopes.yaml",
+                    format!("unknown key `{key}` in scope `{name}` is ignored"),
+                ));
+            }
+        }
+    }
+    findings
+}
+
+/// A scope's patterns compiled once for matching many files.
+struct CompiledScope<'a> {
+    name: &'a str,
+    positive: Vec<(&'a str, GlobMatcher, usize)>,
+    negative: Vec<GlobMatcher>,
+}
+
+impl<'a> CompiledScope<'a> {
+    fn new(name: &'a str, patterns: &'a [String]) -> Result<Self, String> {
+        let mut positive = Vec::new();
+        let mut negative = Vec::new();
+        for pattern in patterns {
+            let (raw, negated) = match pattern.strip_prefix('!') {
+                Some(stripped) => (stripped, true),
+                None => (pattern.as_str(), false),
+            };
+            let matcher = Glob::new(raw)
+                .map_err(|e| {
+                    format!("pattern `{pattern}` of scope `{name}` is not a valid glob: {e}")
+                })?
+                .compile_matcher();
+            if negated {
+                negative.push(matcher);
+            } else {
+                positive.push((pattern.as_str(), matcher, count_specificity(raw)));
+            }
+        }
+        Ok(Self {
+            name,
+            positive,
+            negative,
+        })
+    }
+
+    /// Returns the indices of the positive patterns matching `file` and the
+    /// best specificity among them, as scope resolution computes it.
+    fn matches(&self, file: &str) -> (Vec<usize>, Option<usize>) {
+        if self.negative.iter().any(|m| m.is_match(file)) {
+            return (vec![], None);
+        }
+        let hits: Vec<usize> = self
+            .positive
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, matcher, _))| matcher.is_match(file))
+            .map(|(index, _)| index)
+            .collect();
+        let best = hits.iter().map(|&index| self.positive[index].2).max();
+        (hits, best)
+    }
+}
+
+/// Replays scope resolution over every tracked file and reports patterns
+/// that match nothing, scopes that never win, and scopes that tie.
+fn resolution_findings(scopes: &[CompiledScope<'_>], tracked_files: &[&str]) -> Vec<Finding> {
+    const FILE: &str = "scopes.yaml";
+    let mut pattern_hits: Vec<Vec<bool>> = scopes
+        .iter()
+        .map(|s| vec![false; s.positive.len()])
+        .collect();
+    let mut matched = vec![false; scopes.len()];
+    let mut wins = vec![0usize; scopes.len()];
+    let mut shadowed_by: Vec<Option<usize>> = vec![None; scopes.len()];
+    let mut ties: BTreeMap<(usize, usize), (usize, &str)> = BTreeMap::new();
+
+    for &file in tracked_files {
+        let mut candidates = Vec::new();
+        for (index, scope) in scopes.iter().enumerate() {
+            let (hits, best) = scope.matches(file);
+            for hit in hits {
+                pattern_hits[index][hit] = true;
+            }
+            if let Some(specificity) = best {
+                matched[index] = true;
+                candidates.push((index, specificity));
+            }
+        }
+        let Some(max) = candidates.iter().map(|&(_, s)| s).max() else {
+            continue;
+        };
+        let winners: Vec<usize> = candidates
+            .iter()
+            .filter(|&&(_, s)| s == max)
+            .map(|&(index, _)| index)
+            .collect();
+        for &(index, specificity) in &candidates {
+            if specificity == max {
+                wins[index] += 1;
+            } else if shadowed_by[index].is_none() {
+                shadowed_by[index] = Some(winners[0]);
+            }
+        }
+        for (i, &a) in winners.iter().enumerate() {
+            for &b in &winners[i + 1..] {
+                let entry = ties.entry((a, b)).or_insert((0, file));
+                entry.0 += 1;
+            }
+        }
+    }
+
+    let mut findings = Vec::new();
+    if tracked_files.is_empty() {
+        return findings;
+    }
+    for (index, scope) in scopes.iter().enumerate() {
+        for (hit, (pattern, _, _)) in pattern_hits[index].iter().zip(&scope.positive) {
+            if !hit {
+                findings.push(Finding::warning(
+                    FILE,
+                    format!(
+                        "pattern `{pattern}` of scope `{}` matches no tracked file",
+                        scope.name
+                    ),
+                ));
+            }
+        }
+        if matched[index] && wins[index] == 0 {
+            let winner = shadowed_by[index].map_or("another scope", |w| scopes[w].name);
+            findings.push(Finding::warning(
+                FILE,
+                format!(
+                    "scope `{}` is unreachable: every file it matches resolves to the more \
+                     specific `{winner}`",
+                    scope.name
+                ),
+            ));
+        }
+    }
+    for ((a, b), (count, example)) in ties {
+        findings.push(Finding::warning(
+            FILE,
+            format!(
+                "scopes `{}` and `{}` overlap with equal specificity on {count} file(s) \
+                 (e.g. {example}); commits there get the joined scope `{}, {}`",
+                scopes[a].name, scopes[b].name, scopes[a].name, scopes[b].name
+            ),
+        ));
+    }
+    findings
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const FILES: &[&str] = &[
+        "src/cli/git.rs",
+        "src/cli/ai.rs",
+        "src/git/commit.rs",
+        "docs/user-guide.md",
+    ];
+
+    fn messages(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.message.as_str()).collect()
+    }
+
+    #[test]
+    fn malformed_scopes_is_a_single_error() {
+        let findings = check_scopes("scopes:\n  - name: cli\n", FILES);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert!(findings[0].message.contains("ignored"));
+    }
+
+    #[test]
+    fn clean_scopes_have_no_findings() {
+        let yaml = "scopes:
+  - name: cli
+    description: CLI
+    examples: []
+    file_patterns: ['src/cli/**']
+  - name: git
+    description: Git
+    examples: []
+    file_patterns: ['src/git/**']
+  - name: docs
+    description: Docs
+    examples: []
+    file_patterns: ['docs/**']
+";
+        assert!(check_scopes(yaml, FILES).is_empty());
+    }
+
+    #[test]
+    fn flags_unknown_keys_duplicates_and_bad_globs() {
+        let yaml = "scoeps: []
+scopes:
+  - name: cli
+    description: ''
+    examples: []
+    file_pattern: ['src/cli/**']
+    file_patterns: ['src/cli/**']
+  - name: cli
+    description: CLI again
+    examples: []
+    file_patterns: ['src/[cli/**']
+";
+        let findings = check_scopes(yaml, FILES);
+        let messages = messages(&findings);
+        assert!(messages.contains(&"unknown top-level key `scoeps` is ignored"));
+        assert!(messages.contains(&"unknown key `file_pattern` in scope `cli` is ignored"));
+        assert!(messages.contains(&"scope `cli` is defined more than once"));
+        assert!(check_scopes("pack: rust\nscopes: []\n", FILES).is_empty());
+        assert!(messages(&check_scopes("pack: cobol\n", FILES))[0]
+            .starts_with("unknown scope pack `cobol`"));
+        assert!(messages.iter().any(|m| m.contains("no description")));
+        assert!(findings
+            .iter()
+            .any(|f| f.severity == Severity::Error && f.message.contains("not a valid glob")));
+    }
+
+    #[test]
+    fn flags_dead_patterns_shadowed_scopes_and_ties() {
+        let yaml = "scopes:
+  - name: src
+    description: Everything in src
+    examples: []
+    file_patterns: ['src/**']
+  - name: cli
+    description: CLI
+    examples: []
+    file_patterns: ['src/cli/**', 'src/tui/**']
+  - name: git
+    description: Git
+    examples: []
+    file_patterns: ['src/git/**']
+  - name: vcs
+    description: Version control
+    examples: []
+    file_patterns: ['src/git/**']
+";
+        let findings = check_scopes(yaml, FILES);
+        let messages = messages(&findings);
+        assert!(messages.contains(&"pattern `src/tui/**` of scope `cli` matches no tracked file"));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("scope `src` is unreachable")));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("scopes `git` and `vcs` overlap") && m.contains("1 file(s)")));
+    }
+
+    #[test]
+    fn flags_guidance_over_budget_share() {
+        let small = "a".repeat(1_000);
+        assert!(check_guidance_size("commit-guidelines.md", &small, "m", 200_000).is_none());
+        let large = "a".repeat(100_000);
+        let finding = check_guidance_size("commit-guidelines.md", &large, "m", 200_000).unwrap();
+        assert_eq!(finding.severity, Severity::Warning);
+        assert!(finding.message.contains("m's 200000-token input context"));
+    }
+}
@@ -0,0 +1,231 @@
+//! Few-shot commit message examples for the twiddle prompt.
+//!
+//! Showing the model a handful of the project's own messages is the most
+//! direct way to get output in the house style. Examples come from one of two
+//! places:
+//!
+//! - `examples.md` in the context directory, hand-curated. Messages are
+//!   separated by lines containing only `---`; a leading `# Heading` line in
+//!   a block is ignored. When present, it is always used.
+//! - Otherwise, with `--examples <N>` or `OMNI_DEV_COMMIT_EXAMPLES` set, the
+//!   `N` best-scoring messages among recent history (see [`score_message`]).
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+
+use crate::git::release::parse_conventional_subject;
+
+/// File in the context directory holding curated examples.
+pub const EXAMPLES_FILE: &str = "examples.md";
+
+/// Setting holding the number of history examples to include.
+pub const COMMIT_EXAMPLES_ENV: &str = "OMNI_DEV_COMMIT_EXAMPLES";
+
+/// Recent commits considered when picking examples from history.
+const HISTORY_WINDOW: usize = 200;
+
+/// Longest message worth showing as an example.
+const MAX_EXAMPLE_CHARS: usize = 1500;
+
+/// Subject prefixes of messages that never make good examples.
+const SKIPPED_PREFIXES: &[&str] = &[
+    "fixup!", "squash!", "amend!", "Merge ", "Revert ", "WIP", "wip",
+];
+
+/// Splits curated `examples.md` content into messages.
+pub fn parse_examples(content: &str) -> Vec<String> {
+    let mut examples = Vec::new();
+    let mut current = Vec::new();
+    for line in content.lines().chain(std::iter::once("---")) {
+        if line.trim() != "---" {
+            current.push(line);
+            continue;
+        }
+        let lines: Vec<&str> = current
+            .drain(..)
+            .skip_while(|l| l.trim().is_empty() || l.starts_with("# "))
+            .collect();
+        let example = lines.join("\n").trim().to_string();
+        if !example.is_empty() {
+            examples.push(example);
+        }
+    }
+    examples
+}
+
+/// Reads the number of history examples from settings; unset, empty, or
+/// invalid values mean none.
+pub fn count_from_settings() -> usize {
+    let Ok(raw) = crate::utils::settings::get_env_var(COMMIT_EXAMPLES_ENV) else {
+        return 0;
+    };
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return 0;
+    }
+    raw.parse().unwrap_or_else(|_| {
+        tracing::warn!("Ignoring invalid {COMMIT_EXAMPLES_ENV} '{raw}' (expected a count)");
+        0
+    })
+}
+
+/// Scores how good an example `message` is, or `None` when it should not be
+/// used at all (merges, reverts, fixups, work in progress, very long).
+///
+/// Conventional subjects of a sensible length without a trailing period
+/// score higher, as do bodies separated by a blank line and wrapped at 72
+/// columns.
+pub fn score_message(message: &str) -> Option<i32> {
+    let message = message.trim();
+    let subject = message.lines().next()?.trim();
+    if subject.is_empty()
+        || message.len() > MAX_EXAMPLE_CHARS
+        || SKIPPED_PREFIXES.iter().any(|p| subject.starts_with(p))
+    {
+        return None;
+    }
+
+    let mut score = 0;
+    if parse_conventional_subject(subject).is_some() {
+        score += 3;
+    }
+    score += if (15..=72).contains(&subject.chars().count()) {
+        2
+    } else {
+        -2
+    };
+    if subject.ends_with('.') {
+        score -= 1;
+    }
+
+    let mut rest = message.lines().skip(1);
+    match rest.next() {
+        Some(separator) if !separator.trim().is_empty() => score -= 2,
+        Some(_) => {
+            let body: Vec<&str> = rest.collect();
+            if body.iter().any(|line| !line.trim().is_empty()) {
+                score += 2;
+                if body.iter().all(|line| line.chars().count() <= 72) {
+                    score += 1;
+                }
+            }
+        }
+        None => {}
+    }
+    Some(score)
+}
+
+/// Picks the `count` best-scoring messages among the recent non-merge
+/// commits reachable from HEAD, skipping those in `exclude` (full hashes of
+/// the commits being rewritten). Ties favour the more recent commit.
+pub fn best_recent_messages(
+    repo: &git2::Repository,
+    count: usize,
+    exclude: &HashSet<String>,
+) -> Result<Vec<String>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    let mut revwalk = repo.revwalk().context("Failed to walk commit history")?;
+    revwalk.push_head().context("Repository has no commits")?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    let mut scored = Vec::new();
+    for oid in revwalk.take(HISTORY_WINDOW) {
+        let oid = oid?;
+        if exclude.contains(&oid.to_string()) {
+            continue;
+        }
+        let commit = repo.find_commit(oid)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let message = commit.message().unwrap_or_default().trim().to_string();
+        if let Some(score) = score_message(&message) {
+            scored.push((score, message));
+        }
+    }
+    // A stable sort keeps history order among equal scores.
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    let mut seen = HashSet::new();
+    Ok(scored
+        .into_iter()
+        .map(|(_, message)| message)
+        .filter(|message| seen.insert(message.clone()))
+        .take(count)
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_curated_examples() {
+        let content = "# Commit examples\n\nfeat(cli): add --json\n\nExplains why.\n---\n\
+                       fix(git): handle detached HEAD\n---\n\n---\n";
+        assert_eq!(
+            parse_examples(content),
+            [
+                "feat(cli): add --json\n\nExplains why.",
+                "fix(git): handle detached HEAD"
+            ]
+        );
+    }
+
+    #[test]
+    fn scores_house_style_above_sloppy_messages() {
+        let good = score_message(
+            "feat(cli): add context export\n\nBundles guidance files so teams can share them.",
+        )
+        .unwrap();
+        let plain = score_message("feat(cli): add context export").unwrap();
+        let sloppy = score_message("updated stuff.").unwrap();
+        assert!(good > plain);
+        assert!(plain > sloppy);
+        assert_eq!(score_message("Merge branch 'main' into topic"), None);
+        assert_eq!(score_message("fixup! feat: thing"), None);
+        assert_eq!(score_message(&"x".repeat(MAX_EXAMPLE_CHARS + 1)), None);
+    }
+
+    #[test]
+    fn picks_best_recent_messages_excluding_the_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let mut parent: Option<git2::Oid> = None;
+        let mut oids = Vec::new();
+        for message in [
+            "fix(core): handle empty input\n\nEmpty ranges used to panic.",
+            "wip",
+            "docs: explain scope packs",
+            "feat(cli): add use-pack\n\nLets users pick a pack explicitly.",
+        ] {
+            let parents: Vec<git2::Commit> = parent
+                .map(|oid| repo.find_commit(oid).unwrap())
+                .into_iter()
+                .collect();
+            let refs: Vec<&git2::Commit> = parents.iter().collect();
+            let oid = repo
+                .commit(Some("HEAD"), &sig, &sig, message, &tree, &refs)
+                .unwrap();
+            oids.push(oid);
+            parent = Some(oid);
+        }
+
+        let exclude: HashSet<String> = [oids[3].to_string()].into_iter().collect();
+        let picked = best_recent_messages(&repo, 2, &exclude).unwrap();
+        assert_eq!(
+            picked,
+            [
+                "fix(core): handle empty input\n\nEmpty ranges used to panic.",
+                "docs: explain scope packs"
+            ]
+        );
+        assert!(best_recent_messages(&repo, 0, &exclude).unwrap().is_empty());
+    }
+}
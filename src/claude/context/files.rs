@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use crate::data::context::{
     ArchitecturalLayer, ChangeImpact, FileContext, FilePurpose, ProjectSignificance,
 };
-use crate::git::CommitInfo;
+use crate::git::{CommitInfo, GeneratedFiles};
 
 /// File context analyzer.
 pub struct FileAnalyzer;
@@ -110,6 +110,11 @@ fn determine_file_purpose(path: &Path) -> FilePurpose {
         .unwrap_or("")
         .to_lowercase();
 
+    // Lockfiles, generated code, and vendored dependencies
+    if GeneratedFiles::is_builtin_match(&path.to_string_lossy().replace('\\', "/")) {
+        return FilePurpose::Generated;
+    }
+
     // Configuration files
     if is_config_file(&path_str, &file_name) {
         return FilePurpose::Config;
@@ -149,9 +154,10 @@ fn determine_architectural_layer(path: &Path, file_purpose: &FilePurpose) -> Arc
     let path_str = path.to_string_lossy().to_lowercase();
 
     match file_purpose {
-        FilePurpose::Config | FilePurpose::Build | FilePurpose::Tooling => {
-            ArchitecturalLayer::Infrastructure
-        }
+        FilePurpose::Config
+        | FilePurpose::Build
+        | FilePurpose::Tooling
+        | FilePurpose::Generated => ArchitecturalLayer::Infrastructure,
         FilePurpose::Test | FilePurpose::Documentation => ArchitecturalLayer::Cross,
         FilePurpose::Interface => ArchitecturalLayer::Presentation,
         FilePurpose::CoreLogic => {
@@ -194,7 +200,9 @@ fn determine_change_impact(change_type: &str, file_purpose: &FilePurpose) -> Cha
         "M" => {
             // Modified file - depends on purpose
             match file_purpose {
-                FilePurpose::Test | FilePurpose::Documentation => ChangeImpact::Style,
+                FilePurpose::Test | FilePurpose::Documentation | FilePurpose::Generated => {
+                    ChangeImpact::Style
+                }
                 FilePurpose::Interface => ChangeImpact::Breaking, // Potentially breaking
                 _ => ChangeImpact::Modification,
             }
@@ -229,9 +237,10 @@ fn determine_project_significance(path: &Path, file_purpose: &FilePurpose) -> Pr
                 ProjectSignificance::Important
             }
         }
-        FilePurpose::Test | FilePurpose::Documentation | FilePurpose::Tooling => {
-            ProjectSignificance::Routine
-        }
+        FilePurpose::Test
+        | FilePurpose::Documentation
+        | FilePurpose::Tooling
+        | FilePurpose::Generated => ProjectSignificance::Routine,
     }
 }
 
@@ -417,6 +426,16 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn purpose_generated_and_vendored() {
+        for path in ["Cargo.lock", "web/pnpm-lock.yaml", "vendor/lib/mod.go"] {
+            assert!(matches!(
+                determine_file_purpose(Path::new(path)),
+                FilePurpose::Generated
+            ));
+        }
+    }
+
     #[test]
     fn purpose_core_logic_default() {
         assert!(matches!(
@@ -688,6 +707,8 @@ mod tests {
                     diff_summary: String::new(),
                     diff_file: String::new(),
                     file_diffs: Vec::new(),
+                    submodule_changes: Vec::new(),
+                    dependency_updates: Vec::new(),
                 },
             }
         }
@@ -747,6 +768,7 @@ mod tests {
                 Just(FilePurpose::Tooling),
                 Just(FilePurpose::Interface),
                 Just(FilePurpose::CoreLogic),
+                Just(FilePurpose::Generated),
             ]
         }
 
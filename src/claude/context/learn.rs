@@ -0,0 +1,562 @@
+//! Convention inference from git history.
+//!
+//! Projects without `.omni-dev/` configuration usually still have a
+//! consistent commit style. `omni-dev context learn` samples recent non-merge
+//! commits and measures it: which conventional types and scopes appear, how
+//! subjects are written, and which trailers recur. The result renders as
+//! drafts of `commit-guidelines.md` and `scopes.yaml` that
+//! [`super::ProjectDiscovery`] loads like hand-written ones.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+
+use crate::data::context::ScopeDefinition;
+use crate::git::commit::DEFAULT_COMMIT_TYPES;
+use crate::git::release::parse_conventional_subject;
+
+/// Minimum commits a type or scope needs before it is listed, so one-off
+/// typos do not become conventions.
+const MIN_USES: usize = 2;
+
+/// Share of a scope's file changes a directory needs to become one of its
+/// `file_patterns`.
+const DIRECTORY_SHARE: f64 = 0.3;
+
+/// Maximum `file_patterns` inferred per scope.
+const MAX_SCOPE_DIRECTORIES: usize = 3;
+
+/// Maximum example subjects kept per scope.
+const MAX_SCOPE_EXAMPLES: usize = 2;
+
+/// One sampled commit: its message and the paths it changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryCommit {
+    /// Full commit message.
+    pub message: String,
+    /// Repository-relative paths changed relative to the first parent.
+    pub files: Vec<String>,
+}
+
+/// Collects up to `limit` non-merge commits reachable from `HEAD`, newest
+/// first.
+pub fn sample_history(repo: &git2::Repository, limit: usize) -> Result<Vec<HistoryCommit>> {
+    let mut revwalk = repo.revwalk().context("Failed to walk commit history")?;
+    revwalk.push_head().context("Repository has no commits")?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    let mut commits = Vec::new();
+    for oid in revwalk {
+        if commits.len() >= limit {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => Some(parent.tree()?),
+            Err(_) => None,
+        };
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        let files = diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+        commits.push(HistoryCommit {
+            message: commit.message().unwrap_or_default().to_string(),
+            files,
+        });
+    }
+    Ok(commits)
+}
+
+/// A scope observed in the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LearnedScope {
+    /// Scope name as written in subjects.
+    pub name: String,
+    /// Commits that used the scope.
+    pub commits: usize,
+    /// Directories most of the scope's changes fall under.
+    pub directories: Vec<String>,
+    /// Example subjects using the scope.
+    pub examples: Vec<String>,
+}
+
+/// Conventions measured over a sample of commits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LearnedConventions {
+    /// Commits sampled.
+    pub sampled: usize,
+    /// Commits whose subject parses as a conventional commit.
+    pub conventional: usize,
+    /// Types with their commit counts, most used first.
+    pub types: Vec<(String, usize)>,
+    /// Scopes, most used first.
+    pub scopes: Vec<LearnedScope>,
+    /// Conventional commits marked breaking (`!` or a `BREAKING CHANGE` footer).
+    pub breaking: usize,
+    /// Conventional descriptions starting with a lowercase letter.
+    pub lowercase_descriptions: usize,
+    /// Subjects ending with a period.
+    pub trailing_periods: usize,
+    /// Median subject length in characters.
+    pub median_subject_length: usize,
+    /// Subject length 90% of commits stay within.
+    pub p90_subject_length: usize,
+    /// Trailer keys with the number of commits carrying them, most used first.
+    pub trailers: Vec<(String, usize)>,
+}
+
+impl LearnedConventions {
+    /// Measures the conventions of `commits`.
+    pub fn from_history(commits: &[HistoryCommit]) -> Self {
+        let mut types: BTreeMap<String, usize> = BTreeMap::new();
+        let mut scopes: BTreeMap<String, ScopeTally> = BTreeMap::new();
+        let mut trailers: BTreeMap<String, usize> = BTreeMap::new();
+        let mut lengths = Vec::new();
+        let mut conventional = 0;
+        let mut breaking = 0;
+        let mut lowercase_descriptions = 0;
+        let mut trailing_periods = 0;
+
+        for commit in commits {
+            let subject = commit.message.lines().next().unwrap_or_default().trim();
+            if subject.is_empty() {
+                continue;
+            }
+            lengths.push(subject.chars().count());
+            if subject.ends_with('.') {
+                trailing_periods += 1;
+            }
+            let commit_trailers = parse_trailers(&commit.message);
+            for key in &commit_trailers {
+                *trailers.entry(key.clone()).or_default() += 1;
+            }
+
+            let Some(parsed) = parse_conventional_subject(subject) else {
+                continue;
+            };
+            conventional += 1;
+            *types.entry(parsed.kind.clone()).or_default() += 1;
+            if parsed.breaking || commit.message.contains("BREAKING CHANGE:") {
+                breaking += 1;
+            }
+            if parsed
+                .description
+                .chars()
+                .next()
+                .is_some_and(char::is_lowercase)
+            {
+                lowercase_descriptions += 1;
+            }
+            for scope in parsed.scope.iter().flat_map(|s| s.split(',')) {
+                let scope = scope.trim();
+                if scope.is_empty() {
+                    continue;
+                }
+                let tally = scopes.entry(scope.to_string()).or_default();
+                tally.commits += 1;
+                if tally.examples.len() < MAX_SCOPE_EXAMPLES {
+                    tally.examples.push(subject.to_string());
+                }
+                for file in &commit.files {
+                    if let Some(dir) = scope_directory(file) {
+                        *tally.directories.entry(dir).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        lengths.sort_unstable();
+        Self {
+            sampled: commits.len(),
+            conventional,
+            types: most_used(types),
+            scopes: learned_scopes(scopes),
+            breaking,
+            lowercase_descriptions,
+            trailing_periods,
+            median_subject_length: percentile(&lengths, 50),
+            p90_subject_length: percentile(&lengths, 90),
+            trailers: most_used(trailers),
+        }
+    }
+
+    /// Returns the learned scopes as [`ScopeDefinition`]s.
+    pub fn scope_definitions(&self) -> Vec<ScopeDefinition> {
+        self.scopes
+            .iter()
+            .map(|scope| ScopeDefinition {
+                name: scope.name.clone(),
+                description: if scope.directories.is_empty() {
+                    format!("Used by {} sampled commits", scope.commits)
+                } else {
+                    format!("Changes under {}", scope.directories.join(", "))
+                },
+                examples: scope.examples.clone(),
+                file_patterns: scope
+                    .directories
+                    .iter()
+                    .map(|dir| format!("{dir}/**"))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Renders a `scopes.yaml` draft.
+    pub fn scopes_yaml(&self) -> Result<String> {
+        #[derive(serde::Serialize)]
+        struct ScopesFile {
+            scopes: Vec<ScopeDefinition>,
+        }
+        let yaml = serde_yaml::to_string(&ScopesFile {
+            scopes: self.scope_definitions(),
+        })
+        .context("Failed to serialize learned scopes")?;
+        Ok(format!(
+            "# Drafted by `omni-dev context learn` from {} commits; review before committing.\n{yaml}",
+            self.sampled
+        ))
+    }
+
+    /// Renders a `commit-guidelines.md` draft.
+    pub fn guidelines_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Commit Guidelines\n");
+        let _ = writeln!(
+            out,
+            "<!-- Drafted by `omni-dev context learn` from the last {} commits; review before committing. -->\n",
+            self.sampled
+        );
+
+        let _ = writeln!(out, "## Commit Format\n");
+        if self.is_conventional() {
+            let _ = writeln!(
+                out,
+                "Subjects follow Conventional Commits ({}% of sampled commits):\n",
+                share(self.conventional, self.sampled)
+            );
+            let _ = writeln!(
+                out,
+                "```\n<type>(<scope>): <description>\n\n[optional body]\n\n[optional footer(s)]\n```\n"
+            );
+        } else {
+            let _ = writeln!(
+                out,
+                "Only {}% of sampled commits use Conventional Commits, so no format is \
+                 required. Write a short summary line, a blank line, then the body.\n",
+                share(self.conventional, self.sampled)
+            );
+        }
+
+        let types: Vec<&(String, usize)> = self
+            .types
+            .iter()
+            .filter(|(_, count)| *count >= MIN_USES)
+            .collect();
+        if !types.is_empty() {
+            let _ = writeln!(out, "## Types\n");
+            let _ = writeln!(out, "| Type | Use for | Commits |");
+            let _ = writeln!(out, "|------|---------|---------|");
+            for (name, count) in types {
+                let use_for = DEFAULT_COMMIT_TYPES
+                    .iter()
+                    .find(|(known, _)| known == name)
+                    .map_or("Project-specific type", |(_, description)| description);
+                let _ = writeln!(out, "| `{name}` | {use_for} | {count} |");
+            }
+            let _ = writeln!(out);
+        }
+
+        if !self.scopes.is_empty() {
+            let _ = writeln!(out, "## Scopes\n");
+            let _ = writeln!(out, "Use scopes defined in `.omni-dev/scopes.yaml`:\n");
+            for scope in &self.scopes {
+                let _ = writeln!(out, "- `{}` ({} commits)", scope.name, scope.commits);
+            }
+            let _ = writeln!(out);
+        }
+
+        let _ = writeln!(out, "## Subject Line\n");
+        let _ = writeln!(
+            out,
+            "- Keep the subject within {} characters (median {})",
+            self.p90_subject_length, self.median_subject_length
+        );
+        if self.conventional > 0 {
+            if share(self.lowercase_descriptions, self.conventional) >= 50 {
+                let _ = writeln!(out, "- Start the description with a lowercase letter");
+            } else {
+                let _ = writeln!(out, "- Start the description with a capital letter");
+            }
+        }
+        if share(self.trailing_periods, self.sampled) < 50 {
+            let _ = writeln!(out, "- Do not end the subject with a period");
+        }
+        let _ = writeln!(out);
+
+        if self.breaking > 0 {
+            let _ = writeln!(out, "## Breaking Changes\n");
+            let _ = writeln!(
+                out,
+                "Mark breaking changes with `!` after the type or scope, or a \
+                 `BREAKING CHANGE:` footer ({} sampled commits).\n",
+                self.breaking
+            );
+        }
+
+        let trailers: Vec<&(String, usize)> = self
+            .trailers
+            .iter()
+            .filter(|(_, count)| *count >= MIN_USES)
+            .collect();
+        if !trailers.is_empty() {
+            let _ = writeln!(out, "## Trailers\n");
+            for (key, count) in trailers {
+                let _ = writeln!(
+                    out,
+                    "- `{key}:` ({}% of sampled commits)",
+                    share(*count, self.sampled)
+                );
+            }
+            let _ = writeln!(out);
+        }
+
+        out.truncate(out.trim_end().len());
+        out.push('\n');
+        out
+    }
+
+    /// Returns whether most sampled commits are conventional.
+    fn is_conventional(&self) -> bool {
+        share(self.conventional, self.sampled) >= 50
+    }
+}
+
+/// Per-scope counters gathered while sampling.
+#[derive(Debug, Default)]
+struct ScopeTally {
+    commits: usize,
+    directories: BTreeMap<String, usize>,
+    examples: Vec<String>,
+}
+
+/// Orders scopes by use, keeping those used at least [`MIN_USES`] times,
+/// with the directories that hold a [`DIRECTORY_SHARE`] of their changes.
+fn learned_scopes(scopes: BTreeMap<String, ScopeTally>) -> Vec<LearnedScope> {
+    let mut learned: Vec<LearnedScope> = scopes
+        .into_iter()
+        .filter(|(_, tally)| tally.commits >= MIN_USES)
+        .map(|(name, tally)| {
+            let total: usize = tally.directories.values().sum();
+            #[allow(clippy::cast_precision_loss)]
+            let threshold = total as f64 * DIRECTORY_SHARE;
+            let directories = most_used(tally.directories)
+                .into_iter()
+                .take_while(|(_, count)| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let count = *count as f64;
+                    count >= threshold
+                })
+                .take(MAX_SCOPE_DIRECTORIES)
+                .map(|(dir, _)| dir)
+                .collect();
+            LearnedScope {
+                name,
+                commits: tally.commits,
+                directories,
+                examples: tally.examples,
+            }
+        })
+        .collect();
+    learned.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    learned
+}
+
+/// Returns the directory a changed file counts towards: its first two path
+/// components (`src/cli` for `src/cli/git.rs`), or its parent when shallower.
+/// Files at the repository root have none.
+fn scope_directory(file: &str) -> Option<String> {
+    let (dir, _) = file.rsplit_once('/')?;
+    let components: Vec<&str> = dir.split('/').take(2).collect();
+    Some(components.join("/"))
+}
+
+/// Returns the trailer keys (`Signed-off-by`, `Refs`, …) in the last
+/// paragraph of `message`, which must not be the subject itself.
+fn parse_trailers(message: &str) -> Vec<String> {
+    let trimmed = message.trim_end();
+    let Some((_, last)) = trimmed.rsplit_once("\n\n") else {
+        return Vec::new();
+    };
+    let keys: Vec<String> = last
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(": ")?;
+            let valid = !value.trim().is_empty()
+                && key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+            valid.then(|| key.to_string())
+        })
+        .collect();
+    // A paragraph that is only partly `Key: value` lines is prose, not trailers.
+    if keys.len() == last.lines().count() {
+        keys
+    } else {
+        Vec::new()
+    }
+}
+
+/// Sorts counts descending, breaking ties by name.
+fn most_used(counts: BTreeMap<String, usize>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Returns `part` as a whole-number percentage of `total`.
+fn share(part: usize, total: usize) -> usize {
+    if total == 0 {
+        0
+    } else {
+        part * 100 / total
+    }
+}
+
+/// Returns the value at percentile `pct` of sorted `values` (0 when empty).
+fn percentile(values: &[usize], pct: usize) -> usize {
+    if values.is_empty() {
+        return 0;
+    }
+    let index = (values.len() * pct).div_ceil(100).saturating_sub(1);
+    values[index.min(values.len() - 1)]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit(message: &str, files: &[&str]) -> HistoryCommit {
+        HistoryCommit {
+            message: message.to_string(),
+            files: files.iter().map(|f| (*f).to_string()).collect(),
+        }
+    }
+
+    fn history() -> Vec<HistoryCommit> {
+        vec![
+            commit(
+                "feat(cli): add learn command\n\nSigned-off-by: Dev <dev@example.com>",
+                &["src/cli/context.rs", "src/cli.rs"],
+            ),
+            commit(
+                "fix(cli): handle empty history\n\nSigned-off-by: Dev <dev@example.com>",
+                &["src/cli/context.rs"],
+            ),
+            commit("feat(git,cli)!: rework revwalk", &["src/git/repository.rs"]),
+            commit("docs: explain learn", &["docs/user-guide.md"]),
+            commit("fix(git): skip merges", &["src/git/repository.rs"]),
+            commit("Update README.", &["README.md"]),
+        ]
+    }
+
+    #[test]
+    fn measures_types_scopes_and_style() {
+        let learned = LearnedConventions::from_history(&history());
+        assert_eq!(learned.sampled, 6);
+        assert_eq!(learned.conventional, 5);
+        assert_eq!(
+            learned.types,
+            vec![
+                ("feat".to_string(), 2),
+                ("fix".to_string(), 2),
+                ("docs".to_string(), 1)
+            ]
+        );
+        assert_eq!(learned.breaking, 1);
+        assert_eq!(learned.lowercase_descriptions, 5);
+        assert_eq!(learned.trailing_periods, 1);
+        assert_eq!(learned.trailers, vec![("Signed-off-by".to_string(), 2)]);
+
+        let names: Vec<&str> = learned.scopes.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["cli", "git"]);
+        assert_eq!(learned.scopes[0].commits, 3);
+        assert_eq!(learned.scopes[1].directories, vec!["src/git"]);
+    }
+
+    #[test]
+    fn renders_drafts_that_discovery_can_load() {
+        let learned = LearnedConventions::from_history(&history());
+        let guidelines = learned.guidelines_markdown();
+        assert!(guidelines.contains("Conventional Commits (83% of sampled commits)"));
+        assert!(guidelines.contains("| `feat` | A new feature | 2 |"));
+        assert!(!guidelines.contains("`docs`"));
+        assert!(guidelines.contains("- Start the description with a lowercase letter"));
+        assert!(guidelines.contains("- `Signed-off-by:` (33% of sampled commits)"));
+
+        let yaml = learned.scopes_yaml().unwrap();
+        #[derive(serde::Deserialize)]
+        struct ScopesFile {
+            scopes: Vec<ScopeDefinition>,
+        }
+        let parsed: ScopesFile = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.scopes[1].name, "git");
+        assert_eq!(parsed.scopes[1].file_patterns, vec!["src/git/**"]);
+    }
+
+    #[test]
+    fn trailers_need_a_trailer_only_paragraph() {
+        assert_eq!(
+            parse_trailers("fix: x\n\nbody\n\nRefs: #12\nCo-authored-by: A <a@b>"),
+            vec!["Refs", "Co-authored-by"]
+        );
+        assert!(parse_trailers("fix: x\n\nNote: this is prose\nthat continues").is_empty());
+        assert!(parse_trailers("Refs: #12").is_empty());
+    }
+
+    #[test]
+    fn samples_non_merge_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let signature = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let mut parent: Option<git2::Oid> = None;
+        for (n, message) in ["feat(core): first", "fix(core): second"]
+            .iter()
+            .enumerate()
+        {
+            std::fs::create_dir_all(dir.path().join("core")).unwrap();
+            std::fs::write(dir.path().join("core/lib.rs"), format!("{n}\n")).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(std::path::Path::new("core/lib.rs")).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<git2::Commit> = parent
+                .map(|oid| repo.find_commit(oid).unwrap())
+                .into_iter()
+                .collect();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            parent = Some(
+                repo.commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    message,
+                    &tree,
+                    &parent_refs,
+                )
+                .unwrap(),
+            );
+        }
+
+        let sampled = sample_history(&repo, 10).unwrap();
+        assert_eq!(sampled.len(), 2);
+        assert_eq!(sampled[0].message, "fix(core): second");
+        assert_eq!(sampled[0].files, vec!["core/lib.rs"]);
+        assert_eq!(sample_history(&repo, 1).unwrap().len(), 1);
+    }
+}
@@ -3,8 +3,11 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use globset::{Glob, GlobSetBuilder};
+
 use crate::data::context::{
     ArchitecturalImpact, ChangeSignificance, CommitRangeContext, ScopeAnalysis, WorkPattern,
+    WorkPatternRule,
 };
 use crate::git::CommitInfo;
 
@@ -14,6 +17,15 @@ pub struct WorkPatternAnalyzer;
 impl WorkPatternAnalyzer {
     /// Analyzes a range of commits to detect work patterns.
     pub fn analyze_commit_range(commits: &[CommitInfo]) -> CommitRangeContext {
+        Self::analyze_commit_range_with(commits, &[])
+    }
+
+    /// Analyzes a range of commits, also matching the project's work
+    /// pattern `rules`.
+    pub fn analyze_commit_range_with(
+        commits: &[CommitInfo],
+        rules: &[WorkPatternRule],
+    ) -> CommitRangeContext {
         let mut context = CommitRangeContext::default();
 
         if commits.is_empty() {
@@ -36,6 +48,13 @@ impl WorkPatternAnalyzer {
         // Determine change significance
         context.change_significance = Self::determine_change_significance(commits);
 
+        // Match project-defined patterns
+        context.project_patterns = rules
+            .iter()
+            .filter(|rule| rule_matches(rule, commits))
+            .cloned()
+            .collect();
+
         context
     }
 
@@ -366,6 +385,46 @@ fn estimate_lines_changed(diff_summary: &str) -> i32 {
     total
 }
 
+/// Returns whether a project work pattern rule matches the commits: some
+/// changed file matches one of its globs and some commit has one of its
+/// conventional types. An empty criterion always holds; a rule with an
+/// invalid glob never matches.
+fn rule_matches(rule: &WorkPatternRule, commits: &[CommitInfo]) -> bool {
+    let files_match = rule.files.is_empty() || {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &rule.files {
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                }
+                Err(_) => return false,
+            }
+        }
+        let Ok(globs) = builder.build() else {
+            return false;
+        };
+        commits.iter().any(|commit| {
+            commit
+                .analysis
+                .file_changes
+                .file_list
+                .iter()
+                .any(|f| globs.is_match(&f.file))
+        })
+    };
+    let types_match = rule.types.is_empty()
+        || commits.iter().any(|commit| {
+            crate::git::release::parse_conventional_subject(&commit.original_message).is_some_and(
+                |subject| {
+                    rule.types
+                        .iter()
+                        .any(|t| t.eq_ignore_ascii_case(&subject.kind))
+                },
+            )
+        });
+    files_match && types_match
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,10 +456,44 @@ mod tests {
                 diff_summary: String::new(),
                 diff_file: String::new(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         }
     }
 
+    fn migration_rule(types: &[&str]) -> WorkPatternRule {
+        WorkPatternRule {
+            name: "database-migration".to_string(),
+            files: vec!["migrations/**".to_string()],
+            types: types.iter().map(|t| (*t).to_string()).collect(),
+            guidance: "Mention schema changes and rollback notes.".to_string(),
+        }
+    }
+
+    #[test]
+    fn project_patterns_match_files_and_types() {
+        let commits = vec![make_commit(
+            "feat(db): add orders table",
+            vec![("A", "migrations/0042_orders.sql")],
+        )];
+        let context =
+            WorkPatternAnalyzer::analyze_commit_range_with(&commits, &[migration_rule(&[])]);
+        assert_eq!(context.project_patterns.len(), 1);
+
+        let context =
+            WorkPatternAnalyzer::analyze_commit_range_with(&commits, &[migration_rule(&["fix"])]);
+        assert!(context.project_patterns.is_empty());
+
+        let commits = vec![make_commit(
+            "feat: add orders",
+            vec![("M", "src/orders.rs")],
+        )];
+        let context =
+            WorkPatternAnalyzer::analyze_commit_range_with(&commits, &[migration_rule(&["feat"])]);
+        assert!(context.project_patterns.is_empty());
+    }
+
     fn make_commit_with_scope(message: &str, scope: &str) -> CommitInfo {
         let mut commit = make_commit(message, vec![]);
         commit.analysis.detected_scope = scope.to_string();
@@ -0,0 +1,419 @@
+//! Remote (shared) configuration sources.
+//!
+//! A config file may declare that it extends a ruleset published elsewhere,
+//! so a platform team can maintain one set of guidelines and scopes consumed
+//! by many repositories:
+//!
+//! - Markdown files (`commit-guidelines.md`, `pr-guidelines.md`) use YAML
+//!   front matter: `---\nextends: github:org/conventions@v2\n---`. The remote
+//!   content is placed before the local body.
+//! - `scopes.yaml` uses a top-level `extends:` key. Remote scopes are merged
+//!   in after the local ones, skipping names the local file already defines.
+//!
+//! There is no separate check rules file: `git commit message check` checks
+//! against the commit guidelines, so extending `commit-guidelines.md` shares
+//! the check rules too.
+//!
+//! Sources are either `github:OWNER/REPO[/DIR]@REF` (the same file name under
+//! `DIR`, default `.omni-dev`, at `REF`) or a literal `https://` URL. Fetched
+//! content is cached under the user cache directory; a fresh cache entry is
+//! used without touching the network, and a stale one is the fallback when a
+//! fetch fails or times out.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+
+use crate::utils::digest::sha256_hex;
+use crate::utils::network::NetworkSettings;
+
+/// How long a fetched remote source is served from cache before refetching.
+pub(crate) const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Maximum `extends` chain length (guards against cycles).
+const MAX_EXTENDS_DEPTH: usize = 4;
+
+/// Environment variable overriding the remote-source cache directory.
+pub const REMOTE_CACHE_DIR_ENV: &str = "OMNI_DEV_REMOTE_CACHE_DIR";
+
+/// A parsed `extends:` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSource {
+    /// A file in a GitHub repository at a given ref.
+    GitHub {
+        /// Repository owner (user or organization).
+        owner: String,
+        /// Repository name.
+        repo: String,
+        /// Directory within the repository holding the config files.
+        dir: String,
+        /// Branch, tag, or commit.
+        git_ref: String,
+    },
+    /// A literal HTTPS URL of the file itself.
+    Url(String),
+}
+
+impl RemoteSource {
+    /// Parses `github:OWNER/REPO[/DIR]@REF` or an `https://` URL.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if spec.starts_with("https://") {
+            return Ok(Self::Url(spec.to_string()));
+        }
+        let Some(rest) = spec.strip_prefix("github:") else {
+            bail!(
+                "Unsupported extends source '{spec}' (expected github:OWNER/REPO@REF or https://…)"
+            );
+        };
+        let Some((path, git_ref)) = rest.rsplit_once('@') else {
+            bail!("extends source '{spec}' is missing an @REF (e.g. github:org/conventions@v2)");
+        };
+        let mut parts = path.splitn(3, '/');
+        let (Some(owner), Some(repo)) = (parts.next(), parts.next()) else {
+            bail!("extends source '{spec}' must name OWNER/REPO");
+        };
+        if owner.is_empty() || repo.is_empty() || git_ref.is_empty() {
+            bail!("extends source '{spec}' must name OWNER/REPO@REF");
+        }
+        let dir = parts
+            .next()
+            .map_or(".omni-dev", |d| d.trim_matches('/'))
+            .to_string();
+        Ok(Self::GitHub {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            dir,
+            git_ref: git_ref.to_string(),
+        })
+    }
+
+    /// Returns the URL of `filename` within this source.
+    pub fn file_url(&self, filename: &str) -> String {
+        match self {
+            Self::GitHub {
+                owner,
+                repo,
+                dir,
+                git_ref,
+            } => {
+                let dir = if dir.is_empty() {
+                    String::new()
+                } else {
+                    format!("{dir}/")
+                };
+                format!(
+                    "https://raw.githubusercontent.com/{owner}/{repo}/{git_ref}/{dir}{filename}"
+                )
+            }
+            Self::Url(url) => url.clone(),
+        }
+    }
+}
+
+/// Splits `extends:` front matter off a markdown file.
+///
+/// Returns the `extends` value (if the file opens with front matter carrying
+/// one) and the body after the front matter.
+pub fn split_markdown_extends(content: &str) -> (Option<String>, &str) {
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (None, content);
+    };
+    let Some(end) = rest.find("\n---") else {
+        return (None, content);
+    };
+    let front_matter = &rest[..end];
+    let body = rest[end + 4..].trim_start_matches(['\r', '\n']);
+
+    let extends = front_matter.lines().find_map(|line| {
+        line.trim()
+            .strip_prefix("extends:")
+            .map(|v| v.trim().trim_matches(['"', '\'']).to_string())
+    });
+    match extends {
+        Some(value) if !value.is_empty() => (Some(value), body),
+        _ => (None, content),
+    }
+}
+
+/// Expands `extends:` front matter in a markdown config file.
+///
+/// Content without front matter is returned unchanged.
+pub fn resolve_markdown_extends(content: &str, filename: &str) -> Result<String> {
+    resolve_markdown_extends_with(content, filename, 0, &fetch_cached)
+}
+
+/// Like [`resolve_markdown_extends`], but an `extends:` source that cannot
+/// be loaded is skipped with a warning and the file's own body is used, as
+/// for scopes, so an unreachable remote does not stop every AI command.
+pub fn resolve_markdown_extends_or_local(content: &str, filename: &str) -> String {
+    resolve_markdown_extends_or_local_with(content, filename, &fetch_cached)
+}
+
+fn resolve_markdown_extends_or_local_with(
+    content: &str,
+    filename: &str,
+    fetch: &dyn Fn(&str) -> Result<String>,
+) -> String {
+    resolve_markdown_extends_with(content, filename, 0, fetch).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring the extends source of {filename}: {e:#}");
+        split_markdown_extends(content).1.to_string()
+    })
+}
+
+fn resolve_markdown_extends_with(
+    content: &str,
+    filename: &str,
+    depth: usize,
+    fetch: &dyn Fn(&str) -> Result<String>,
+) -> Result<String> {
+    let (Some(spec), body) = split_markdown_extends(content) else {
+        return Ok(content.to_string());
+    };
+    if depth >= MAX_EXTENDS_DEPTH {
+        bail!("extends chain for {filename} is deeper than {MAX_EXTENDS_DEPTH} levels");
+    }
+    let url = RemoteSource::parse(&spec)?.file_url(filename);
+    let remote = fetch(&url).with_context(|| format!("Failed to load extends source {spec}"))?;
+    let remote = resolve_markdown_extends_with(&remote, filename, depth + 1, fetch)?;
+
+    if body.trim().is_empty() {
+        Ok(remote)
+    } else {
+        Ok(format!("{}\n\n{body}", remote.trim_end()))
+    }
+}
+
+/// Fetches the content of `filename` from an `extends:` spec.
+pub fn fetch_source_file(spec: &str, filename: &str) -> Result<String> {
+    fetch_cached(&RemoteSource::parse(spec)?.file_url(filename))
+}
+
+/// Fetches `url`, serving a fresh cache entry when one exists and falling
+/// back to a stale one when the network fetch fails.
+fn fetch_cached(url: &str) -> Result<String> {
+    fetch_cached_with(
+        url,
+        cache_dir().map(|dir| dir.join(sha256_hex(url))),
+        &fetch_url,
+    )
+}
+
+fn fetch_cached_with(
+    url: &str,
+    cache_path: Option<PathBuf>,
+    fetch: &dyn Fn(&str) -> Result<String>,
+) -> Result<String> {
+    if let Some(path) = &cache_path {
+        let fresh = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .is_some_and(|age| age < CACHE_TTL);
+        if fresh {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                return Ok(content);
+            }
+        }
+    }
+
+    match fetch(url) {
+        Ok(content) => {
+            if let Some(path) = &cache_path {
+                if let Err(e) = write_cache(path, &content) {
+                    tracing::debug!("remote config: failed to cache {url}: {e}");
+                }
+            }
+            Ok(content)
+        }
+        Err(e) => match cache_path.and_then(|p| std::fs::read_to_string(p).ok()) {
+            Some(stale) => {
+                tracing::warn!("Using cached copy of {url}; refetch failed: {e}");
+                Ok(stale)
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Fetches `url` over the network. The agent's timeout bounds the call, so an
+/// unresponsive host falls back to the cache instead of hanging the command.
+fn fetch_url(url: &str) -> Result<String> {
+    let network = NetworkSettings::load();
+    let mut request = network.ureq_agent(url)?.get(url);
+    if url.starts_with("https://raw.githubusercontent.com/") {
+        if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+            if !token.is_empty() {
+                request = request.header("Authorization", &format!("Bearer {token}"));
+            }
+        }
+    }
+    let response = request
+        .call()
+        .with_context(|| format!("Failed to fetch {url}{}", network.route_to(url)))?;
+    response
+        .into_body()
+        .read_to_string()
+        .with_context(|| format!("Failed to read response body from {url}"))
+}
+
+fn write_cache(path: &std::path::Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+pub(crate) fn cache_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(REMOTE_CACHE_DIR_ENV) {
+        if !dir.is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    dirs::cache_dir().map(|d| d.join("omni-dev").join("remote"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_github_source_defaults_dir() {
+        let source = RemoteSource::parse("github:org/conventions@v2").unwrap();
+        assert_eq!(
+            source.file_url("commit-guidelines.md"),
+            "https://raw.githubusercontent.com/org/conventions/v2/.omni-dev/commit-guidelines.md"
+        );
+    }
+
+    #[test]
+    fn parse_github_source_with_dir() {
+        let source = RemoteSource::parse("github:org/platform/rules/commits@main").unwrap();
+        assert_eq!(
+            source.file_url("scopes.yaml"),
+            "https://raw.githubusercontent.com/org/platform/main/rules/commits/scopes.yaml"
+        );
+    }
+
+    #[test]
+    fn parse_https_source_is_verbatim() {
+        let source = RemoteSource::parse("https://example.com/g.md").unwrap();
+        assert_eq!(source.file_url("ignored.md"), "https://example.com/g.md");
+    }
+
+    #[test]
+    fn parse_rejects_malformed_sources() {
+        assert!(RemoteSource::parse("github:org/repo").is_err());
+        assert!(RemoteSource::parse("github:org@v1").is_err());
+        assert!(RemoteSource::parse("gitlab:org/repo@v1").is_err());
+        assert!(RemoteSource::parse("http://insecure.example.com/x").is_err());
+    }
+
+    #[test]
+    fn split_front_matter() {
+        let (extends, body) =
+            split_markdown_extends("---\nextends: \"github:o/r@v1\"\n---\n\n# Local\n");
+        assert_eq!(extends.as_deref(), Some("github:o/r@v1"));
+        assert_eq!(body, "# Local\n");
+    }
+
+    #[test]
+    fn content_without_front_matter_is_untouched() {
+        let (extends, body) = split_markdown_extends("# Guidelines\n---\n");
+        assert!(extends.is_none());
+        assert_eq!(body, "# Guidelines\n---\n");
+    }
+
+    #[test]
+    fn front_matter_without_extends_is_untouched() {
+        let content = "---\ntitle: x\n---\nbody";
+        let (extends, body) = split_markdown_extends(content);
+        assert!(extends.is_none());
+        assert_eq!(body, content);
+    }
+
+    #[test]
+    fn resolve_places_remote_before_local_body() {
+        let fetch = |url: &str| -> Result<String> {
+            assert!(url.ends_with("/o/r/v1/.omni-dev/commit-guidelines.md"));
+            Ok("# Shared\nRule A\n".to_string())
+        };
+        let resolved = resolve_markdown_extends_with(
+            "---\nextends: github:o/r@v1\n---\n# Local\nRule B\n",
+            "commit-guidelines.md",
+            0,
+            &fetch,
+        )
+        .unwrap();
+        assert_eq!(resolved, "# Shared\nRule A\n\n# Local\nRule B\n");
+    }
+
+    #[test]
+    fn resolve_rejects_cycles() {
+        let fetch =
+            |_: &str| -> Result<String> { Ok("---\nextends: github:o/r@v1\n---\n".to_string()) };
+        let result = resolve_markdown_extends_with(
+            "---\nextends: github:o/r@v1\n---\n",
+            "commit-guidelines.md",
+            0,
+            &fetch,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn resolve_propagates_fetch_errors() {
+        let fetch = |_: &str| -> Result<String> { bail!("offline") };
+        let result = resolve_markdown_extends_with(
+            "---\nextends: github:o/r@v1\n---\nbody",
+            "commit-guidelines.md",
+            0,
+            &fetch,
+        );
+        assert!(result.is_err());
+
+        assert_eq!(
+            resolve_markdown_extends_or_local_with(
+                "---\nextends: github:o/r@v1\n---\nbody",
+                "commit-guidelines.md",
+                &fetch,
+            ),
+            "body"
+        );
+    }
+
+    #[test]
+    fn failed_fetch_serves_stale_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entry");
+        std::fs::write(&path, "cached guidelines").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now() - 2 * CACHE_TTL)
+            .unwrap();
+
+        let fetch = |_: &str| -> Result<String> { bail!("timed out") };
+        let content = fetch_cached_with("https://example.com/g.md", Some(path), &fetch).unwrap();
+        assert_eq!(content, "cached guidelines");
+    }
+
+    #[test]
+    fn failed_fetch_without_cache_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let fetch = |_: &str| -> Result<String> { bail!("timed out") };
+        let result = fetch_cached_with(
+            "https://example.com/g.md",
+            Some(dir.path().join("none")),
+            &fetch,
+        );
+        assert!(result.is_err());
+    }
+}
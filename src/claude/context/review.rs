@@ -0,0 +1,229 @@
+//! Review discussion for branch context.
+//!
+//! When the branch already has an open pull request, reviewers' comments
+//! often explain why the latest commits exist ("split this into two
+//! functions", "handle the empty case"). The most recent human comments are
+//! fetched and handed to the commit and PR prompts so amended messages and
+//! refreshed descriptions can say which feedback a change addresses.
+//!
+//! Comments come from the forge API (GitHub, Bitbucket, Gitea) or, without a
+//! token, from `gh pr view`. Bot comments and omni-dev's own marker comments
+//! are dropped. Set `OMNI_DEV_REVIEW_CONTEXT=off` (environment or
+//! `settings.json`) to skip the lookup.
+//!
+//! Fetching is best effort: any failure is logged and the prompts simply go
+//! without review feedback.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use crate::data::context::ReviewComment;
+use crate::forge::review_comments_at;
+
+/// Setting that disables the review discussion lookup.
+pub const REVIEW_CONTEXT_ENV: &str = "OMNI_DEV_REVIEW_CONTEXT";
+
+/// Most comments passed to the prompts; older ones are dropped.
+const MAX_COMMENTS: usize = 15;
+
+/// Longest single comment passed to the prompts, in characters.
+const MAX_COMMENT_CHARS: usize = 600;
+
+/// Start of the hidden markers on comments omni-dev posts itself.
+const OMNI_DEV_MARKER_PREFIX: &str = "<!-- omni-dev";
+
+/// Returns whether the review lookup is enabled (the default).
+pub fn review_context_enabled() -> bool {
+    !crate::utils::settings::get_env_var(REVIEW_CONTEXT_ENV).is_ok_and(|value| {
+        matches!(
+            value.trim().to_lowercase().as_str(),
+            "off" | "false" | "0" | "no"
+        )
+    })
+}
+
+/// Fetches the review discussion of the open pull request for `branch`.
+///
+/// Returns an empty list when the lookup is disabled, the branch has no open
+/// pull request, or anything fails.
+pub async fn fetch_branch_review_comments(branch: &str, repo_root: &Path) -> Vec<ReviewComment> {
+    if !review_context_enabled() {
+        return Vec::new();
+    }
+    match crate::cli::git::InfoCommand::get_branch_prs(branch, repo_root).await {
+        Ok(prs) => match prs.iter().find(|pr| pr.state.eq_ignore_ascii_case("open")) {
+            Some(pr) => fetch_review_comments(pr.number, repo_root).await,
+            None => {
+                debug!("No open pull request for branch {branch}");
+                Vec::new()
+            }
+        },
+        Err(e) => {
+            warn!("Could not look up the pull request for {branch}: {e:#}");
+            Vec::new()
+        }
+    }
+}
+
+/// Fetches and distills the review discussion of pull request `number`.
+///
+/// Returns an empty list when the lookup is disabled or fails.
+pub async fn fetch_review_comments(number: u64, repo_root: &Path) -> Vec<ReviewComment> {
+    if !review_context_enabled() {
+        return Vec::new();
+    }
+    match read_review_comments(number, repo_root).await {
+        Ok(comments) => distill(comments),
+        Err(e) => {
+            warn!("Could not read review comments for PR #{number}: {e:#}");
+            Vec::new()
+        }
+    }
+}
+
+async fn read_review_comments(number: u64, repo_root: &Path) -> Result<Vec<ReviewComment>> {
+    if let Some(api) = crate::forge::Forge::detect(repo_root).api_client()? {
+        return api.review_comments(number).await;
+    }
+
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        [
+            "pr",
+            "view",
+            &number.to_string(),
+            "--json",
+            "reviews,comments",
+        ],
+        "pr view",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh pr view failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    parse_gh_review_json(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses `gh pr view --json reviews,comments` output, oldest first.
+fn parse_gh_review_json(json: &str) -> Result<Vec<ReviewComment>> {
+    let view: Value = serde_json::from_str(json).context("Failed to parse gh pr view output")?;
+    let list = |key: &str| {
+        view.get(key)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut comments = review_comments_at(
+        &list("reviews"),
+        "/author/login",
+        "/body",
+        None,
+        "/submittedAt",
+    );
+    comments.extend(review_comments_at(
+        &list("comments"),
+        "/author/login",
+        "/body",
+        None,
+        "/createdAt",
+    ));
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(comments)
+}
+
+/// Drops bot and omni-dev comments, keeps the [`MAX_COMMENTS`] most recent
+/// in chronological order, and shortens long ones.
+fn distill(comments: Vec<ReviewComment>) -> Vec<ReviewComment> {
+    let mut kept: Vec<ReviewComment> = comments
+        .into_iter()
+        .filter(|c| !c.author.ends_with("[bot]"))
+        .filter(|c| {
+            let body = c.body.trim();
+            !body.is_empty() && !body.contains(OMNI_DEV_MARKER_PREFIX)
+        })
+        .map(|mut c| {
+            c.body = truncate_comment(c.body.trim());
+            c
+        })
+        .collect();
+    if kept.len() > MAX_COMMENTS {
+        kept.drain(..kept.len() - MAX_COMMENTS);
+    }
+    kept
+}
+
+/// Cuts `body` at [`MAX_COMMENT_CHARS`].
+fn truncate_comment(body: &str) -> String {
+    match body.char_indices().nth(MAX_COMMENT_CHARS) {
+        Some((end, _)) => format!("{} […]", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn comment(author: &str, body: &str, created_at: &str) -> ReviewComment {
+        ReviewComment {
+            author: author.to_string(),
+            body: body.to_string(),
+            path: None,
+            created_at: created_at.to_string(),
+        }
+    }
+
+    #[test]
+    fn distill_drops_bots_and_markers_and_keeps_recent() {
+        let mut comments = vec![
+            comment("dependabot[bot]", "Bumps serde", "2024-01-01T00:00:00Z"),
+            comment(
+                "alice",
+                "<!-- omni-dev-check-report -->\nAll good",
+                "2024-01-01T00:00:01Z",
+            ),
+            comment("bob", "   ", "2024-01-01T00:00:02Z"),
+        ];
+        for i in 0..20 {
+            comments.push(comment(
+                "carol",
+                &format!("note {i}"),
+                &format!("2024-01-02T00:00:{i:02}Z"),
+            ));
+        }
+        let distilled = distill(comments);
+        assert_eq!(distilled.len(), MAX_COMMENTS);
+        assert_eq!(distilled[0].body, "note 5");
+        assert_eq!(distilled[MAX_COMMENTS - 1].body, "note 19");
+        assert!(distilled.iter().all(|c| c.author == "carol"));
+
+        let long = distill(vec![comment("dave", &"x".repeat(2000), "")]);
+        assert!(long[0].body.ends_with(" […]"));
+        assert_eq!(
+            long[0].body.chars().filter(|c| *c == 'x').count(),
+            MAX_COMMENT_CHARS
+        );
+    }
+
+    #[test]
+    fn parses_gh_review_json_in_order() {
+        let json = r#"{
+            "reviews": [{"author": {"login": "alice"}, "body": "Please add a test", "submittedAt": "2024-03-02T10:00:00Z"},
+                        {"author": {"login": "bob"}, "body": "", "submittedAt": "2024-03-02T11:00:00Z"}],
+            "comments": [{"author": {"login": "carol"}, "body": "Why not reuse the parser?", "createdAt": "2024-03-01T09:00:00Z"}]
+        }"#;
+        let comments = parse_gh_review_json(json).unwrap();
+        assert_eq!(comments.len(), 2);
+        assert_eq!(comments[0].author, "carol");
+        assert_eq!(comments[1].body, "Please add a test");
+        assert!(parse_gh_review_json("not json").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! Project scaffolding for `omni-dev init`.
+//!
+//! Builds the initial contents of a context directory from what can be
+//! detected in the repository — workspace packages, the ecosystem, and the
+//! conventions already visible in git history — and installs the optional
+//! pre-push hook. Nothing here prompts; the CLI decides which pieces to
+//! write.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::learn::LearnedConventions;
+use crate::data::context::ScopeDefinition;
+
+/// Minimum sampled commits before history-derived guidelines replace the
+/// default template.
+pub const MIN_HISTORY: usize = 20;
+
+/// Comment identifying hooks written by `omni-dev init`, so re-running it
+/// may replace them while a hand-written hook is left alone.
+const HOOK_MARKER: &str = "# Installed by `omni-dev init`";
+
+/// The pre-push hook: checks the commits ahead of the default base branch.
+const PRE_PUSH_HOOK: &str = "#!/bin/sh
+# Installed by `omni-dev init`: checks the messages of the commits being pushed.
+# Skip once with `git push --no-verify`; delete this file to disable.
+exec omni-dev git commit message check --strict --quiet
+";
+
+/// The default commit guidelines shipped with omni-dev.
+const DEFAULT_GUIDELINES: &str = include_str!("../../templates/default-commit-guidelines.md");
+
+/// Returns the `commit-guidelines.md` to scaffold: drafted from history when
+/// at least [`MIN_HISTORY`] commits were sampled, otherwise the default
+/// template (whose severity table drives `check`).
+pub fn commit_guidelines(learned: Option<&LearnedConventions>) -> String {
+    match learned {
+        Some(learned) if learned.sampled >= MIN_HISTORY => learned.guidelines_markdown(),
+        _ => DEFAULT_GUIDELINES.to_string(),
+    }
+}
+
+/// Returns the scopes to scaffold: one per workspace package, followed by
+/// the scopes learned from history that no package already provides.
+pub fn scopes(
+    workspace: Vec<ScopeDefinition>,
+    learned: Option<&LearnedConventions>,
+) -> Vec<ScopeDefinition> {
+    let mut scopes = workspace;
+    for scope in learned
+        .map(LearnedConventions::scope_definitions)
+        .unwrap_or_default()
+    {
+        if !scopes.iter().any(|s| s.name == scope.name) {
+            scopes.push(scope);
+        }
+    }
+    scopes
+}
+
+/// Renders `scopes` as a `scopes.yaml` file.
+pub fn scopes_yaml(scopes: &[ScopeDefinition]) -> Result<String> {
+    #[derive(serde::Serialize)]
+    struct ScopesFile<'a> {
+        scopes: &'a [ScopeDefinition],
+    }
+    let yaml =
+        serde_yaml::to_string(&ScopesFile { scopes }).context("Failed to serialize scopes")?;
+    Ok(format!(
+        "# Generated by `omni-dev init` from workspace packages and git history; review before committing.\n{yaml}"
+    ))
+}
+
+/// Returns `gitignore` with `entry` appended, or `None` when a line already
+/// ignores it.
+pub fn gitignore_with_entry(gitignore: &str, entry: &str) -> Option<String> {
+    let bare = entry.trim_matches('/');
+    let present = gitignore
+        .lines()
+        .map(str::trim)
+        .any(|line| line.trim_matches('/') == bare);
+    if present {
+        return None;
+    }
+    let mut updated = gitignore.to_string();
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(entry);
+    updated.push('\n');
+    Some(updated)
+}
+
+/// Returns the directory git runs hooks from: `core.hooksPath` when set
+/// (relative to the working tree), otherwise `hooks/` in the common git
+/// directory so linked worktrees share it.
+pub fn hooks_dir(repo: &git2::Repository) -> PathBuf {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_path("core.hooksPath").ok());
+    match (configured, repo.workdir()) {
+        (Some(path), Some(workdir)) if path.is_relative() => workdir.join(path),
+        (Some(path), _) => path,
+        (None, _) => repo.commondir().join("hooks"),
+    }
+}
+
+/// Outcome of [`install_pre_push_hook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookInstall {
+    /// The hook was written.
+    Installed(PathBuf),
+    /// A hook not written by omni-dev already exists and was left alone.
+    Kept(PathBuf),
+}
+
+/// Writes the pre-push hook into `hooks_dir`. An existing hook is replaced
+/// only when omni-dev wrote it or `force` is set.
+pub fn install_pre_push_hook(hooks_dir: &Path, force: bool) -> Result<HookInstall> {
+    let path = hooks_dir.join("pre-push");
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if !force && !existing.contains(HOOK_MARKER) {
+            return Ok(HookInstall::Kept(path));
+        }
+    }
+    std::fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create hooks directory {}", hooks_dir.display()))?;
+    std::fs::write(&path, PRE_PUSH_HOOK)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .with_context(|| format!("Failed to make {} executable", path.display()))?;
+    }
+    Ok(HookInstall::Installed(path))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::context::learn::HistoryCommit;
+
+    fn scope(name: &str, pattern: &str) -> ScopeDefinition {
+        ScopeDefinition {
+            name: name.to_string(),
+            description: String::new(),
+            examples: vec![],
+            file_patterns: vec![pattern.to_string()],
+        }
+    }
+
+    fn history(count: usize) -> LearnedConventions {
+        let commits: Vec<HistoryCommit> = (0..count)
+            .map(|i| HistoryCommit {
+                message: format!("feat(api): add endpoint {i}"),
+                files: vec!["api/src/lib.rs".to_string()],
+            })
+            .collect();
+        LearnedConventions::from_history(&commits)
+    }
+
+    #[test]
+    fn short_history_falls_back_to_default_guidelines() {
+        assert_eq!(commit_guidelines(None), DEFAULT_GUIDELINES);
+        assert_eq!(commit_guidelines(Some(&history(3))), DEFAULT_GUIDELINES);
+        assert!(commit_guidelines(Some(&history(MIN_HISTORY))).contains("omni-dev context learn"));
+    }
+
+    #[test]
+    fn workspace_scopes_take_precedence_over_learned_ones() {
+        let scopes = scopes(vec![scope("api", "crates/api/**")], Some(&history(5)));
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].file_patterns, ["crates/api/**"]);
+
+        let yaml = scopes_yaml(&scopes).unwrap();
+        assert!(yaml.starts_with("# Generated by `omni-dev init`"));
+        assert!(yaml.contains("- name: api"));
+    }
+
+    #[test]
+    fn gitignore_entry_is_added_once() {
+        assert_eq!(
+            gitignore_with_entry("target", ".omni-dev/local/").as_deref(),
+            Some("target\n.omni-dev/local/\n")
+        );
+        assert_eq!(
+            gitignore_with_entry("/.omni-dev/local\n", ".omni-dev/local/"),
+            None
+        );
+    }
+
+    #[test]
+    fn keeps_foreign_hooks_unless_forced() {
+        let dir = tempfile::tempdir().unwrap();
+        let hook = dir.path().join("pre-push");
+        std::fs::write(&hook, "#!/bin/sh\nmake lint\n").unwrap();
+
+        assert_eq!(
+            install_pre_push_hook(dir.path(), false).unwrap(),
+            HookInstall::Kept(hook.clone())
+        );
+        assert_eq!(
+            install_pre_push_hook(dir.path(), true).unwrap(),
+            HookInstall::Installed(hook.clone())
+        );
+        // Our own hook is refreshed without --force.
+        assert_eq!(
+            install_pre_push_hook(dir.path(), false).unwrap(),
+            HookInstall::Installed(hook.clone())
+        );
+        assert!(std::fs::read_to_string(&hook)
+            .unwrap()
+            .contains("omni-dev git commit message check"));
+    }
+}
@@ -0,0 +1,421 @@
+//! Curated default scope sets per ecosystem.
+//!
+//! When a repository's ecosystem is detected (see
+//! [`super::discovery::detect_ecosystem`]), the matching pack's scopes are
+//! merged into the project scopes, skipping names the project already
+//! defines. A project can pick a pack explicitly with a top-level `pack:` key
+//! in `scopes.yaml` (written by `omni-dev context use-pack <name>`), or turn
+//! the defaults off with `pack: none`.
+
+use crate::data::context::{Ecosystem, ScopeDefinition};
+
+/// `pack:` value that disables ecosystem default scopes.
+pub const NO_PACK: &str = "none";
+
+/// A named set of default scopes.
+#[derive(Debug)]
+pub struct ScopePack {
+    /// Name used in `pack:` and `context use-pack`.
+    pub name: &'static str,
+    /// One-line summary shown when listing packs.
+    pub description: &'static str,
+    /// Scope name, description, and file patterns.
+    scopes: &'static [(&'static str, &'static str, &'static [&'static str])],
+}
+
+impl ScopePack {
+    /// The pack's scopes as definitions.
+    pub fn scopes(&self) -> Vec<ScopeDefinition> {
+        self.scopes
+            .iter()
+            .map(|(name, description, patterns)| ScopeDefinition {
+                name: (*name).to_string(),
+                description: (*description).to_string(),
+                examples: vec![],
+                file_patterns: patterns.iter().map(|p| (*p).to_string()).collect(),
+            })
+            .collect()
+    }
+}
+
+/// Every shipped pack.
+pub const PACKS: &[ScopePack] = &[
+    ScopePack {
+        name: "rust",
+        description: "Rust crate layout (src/, tests/, benches/, examples/)",
+        scopes: &[
+            (
+                "cargo",
+                "Cargo.toml and dependency management",
+                &["Cargo.toml", "Cargo.lock"],
+            ),
+            (
+                "lib",
+                "Library code and public API",
+                &["src/lib.rs", "src/**"],
+            ),
+            (
+                "cli",
+                "Command-line interface",
+                &["src/main.rs", "src/cli/**", "src/bin/**"],
+            ),
+            (
+                "core",
+                "Core application logic",
+                &["src/core/**", "src/lib/**"],
+            ),
+            ("build", "Build script", &["build.rs"]),
+            ("test", "Test code", &["tests/**", "src/**/test*"]),
+            ("bench", "Benchmarks", &["benches/**"]),
+            ("examples", "Example programs", &["examples/**"]),
+            (
+                "docs",
+                "Documentation",
+                &["docs/**", "README.md", "**/*.md"],
+            ),
+            (
+                "ci",
+                "Continuous integration",
+                &[".github/**", ".gitlab-ci.yml"],
+            ),
+        ],
+    },
+    ScopePack {
+        name: "node",
+        description: "Node application (package.json, src/, bundler config)",
+        scopes: &[
+            (
+                "deps",
+                "Dependencies and package.json",
+                &[
+                    "package.json",
+                    "package-lock.json",
+                    "yarn.lock",
+                    "pnpm-lock.yaml",
+                ],
+            ),
+            (
+                "config",
+                "Configuration files",
+                &["*.config.js", "*.config.json", "*.config.ts", ".env*"],
+            ),
+            (
+                "build",
+                "Build system and tooling",
+                &[
+                    "webpack.config.js",
+                    "rollup.config.js",
+                    "vite.config.*",
+                    "tsconfig*.json",
+                ],
+            ),
+            (
+                "api",
+                "Server routes and API handlers",
+                &["src/api/**", "src/routes/**", "pages/api/**"],
+            ),
+            (
+                "ui",
+                "Components and styles",
+                &["src/components/**", "src/styles/**", "**/*.css"],
+            ),
+            (
+                "test",
+                "Test files",
+                &[
+                    "test/**",
+                    "tests/**",
+                    "**/*.test.js",
+                    "**/*.test.ts",
+                    "**/*.spec.ts",
+                ],
+            ),
+            (
+                "docs",
+                "Documentation",
+                &["docs/**", "README.md", "**/*.md"],
+            ),
+            (
+                "ci",
+                "Continuous integration",
+                &[".github/**", ".gitlab-ci.yml"],
+            ),
+        ],
+    },
+    ScopePack {
+        name: "python",
+        description: "Python package (pyproject.toml, src/ or package dir, tests/)",
+        scopes: &[
+            (
+                "deps",
+                "Dependencies and requirements",
+                &[
+                    "requirements*.txt",
+                    "pyproject.toml",
+                    "setup.py",
+                    "poetry.lock",
+                ],
+            ),
+            (
+                "config",
+                "Configuration files",
+                &["*.ini", "*.cfg", "*.toml"],
+            ),
+            (
+                "cli",
+                "Command-line entry points",
+                &["**/cli.py", "**/__main__.py"],
+            ),
+            (
+                "typing",
+                "Type stubs and checker config",
+                &["**/*.pyi", "mypy.ini", "py.typed"],
+            ),
+            (
+                "test",
+                "Test files",
+                &[
+                    "test/**",
+                    "tests/**",
+                    "**/*_test.py",
+                    "**/test_*.py",
+                    "conftest.py",
+                ],
+            ),
+            (
+                "docs",
+                "Documentation",
+                &["docs/**", "README.md", "**/*.md", "**/*.rst"],
+            ),
+            (
+                "ci",
+                "Continuous integration",
+                &[".github/**", ".gitlab-ci.yml", "tox.ini"],
+            ),
+        ],
+    },
+    ScopePack {
+        name: "go",
+        description: "Go module (cmd/, pkg/, internal/)",
+        scopes: &[
+            ("mod", "Go modules and dependencies", &["go.mod", "go.sum"]),
+            ("cmd", "Command-line applications", &["cmd/**"]),
+            ("pkg", "Library packages", &["pkg/**"]),
+            ("internal", "Internal packages", &["internal/**"]),
+            ("test", "Test files", &["**/*_test.go"]),
+            (
+                "docs",
+                "Documentation",
+                &["docs/**", "README.md", "**/*.md"],
+            ),
+        ],
+    },
+    ScopePack {
+        name: "java",
+        description: "Java project built with Maven or Gradle",
+        scopes: &[
+            (
+                "build",
+                "Build system",
+                &["pom.xml", "build.gradle", "build.gradle.kts"],
+            ),
+            (
+                "config",
+                "Configuration",
+                &["src/main/resources/**", "application.properties"],
+            ),
+            ("test", "Test files", &["src/test/**"]),
+            (
+                "docs",
+                "Documentation",
+                &["docs/**", "README.md", "**/*.md"],
+            ),
+        ],
+    },
+    ScopePack {
+        name: "terraform",
+        description: "Terraform repository (root module, modules/, environments/)",
+        scopes: &[
+            (
+                "providers",
+                "Provider and Terraform version constraints",
+                &[
+                    "versions.tf",
+                    "providers.tf",
+                    "terraform.tf",
+                    ".terraform.lock.hcl",
+                ],
+            ),
+            ("modules", "Reusable modules", &["modules/**"]),
+            (
+                "env",
+                "Environment configuration and variable values",
+                &["environments/**", "envs/**", "**/*.tfvars"],
+            ),
+            (
+                "vars",
+                "Input variables and outputs",
+                &["**/variables.tf", "**/outputs.tf"],
+            ),
+            (
+                "state",
+                "Backend and state configuration",
+                &["**/backend.tf"],
+            ),
+            ("infra", "Resource definitions", &["*.tf", "**/*.tf"]),
+            (
+                "docs",
+                "Documentation",
+                &["docs/**", "README.md", "**/*.md"],
+            ),
+            (
+                "ci",
+                "Continuous integration",
+                &[".github/**", ".gitlab-ci.yml", "atlantis.yaml"],
+            ),
+        ],
+    },
+];
+
+/// Looks up a pack by name, ignoring case.
+pub fn find(name: &str) -> Option<&'static ScopePack> {
+    PACKS
+        .iter()
+        .find(|pack| pack.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// The pack shipped for `ecosystem`, if any.
+pub fn for_ecosystem(ecosystem: &Ecosystem) -> Option<&'static ScopePack> {
+    let name = match ecosystem {
+        Ecosystem::Rust => "rust",
+        Ecosystem::Node => "node",
+        Ecosystem::Python => "python",
+        Ecosystem::Go => "go",
+        Ecosystem::Java => "java",
+        Ecosystem::Terraform => "terraform",
+        Ecosystem::Unknown | Ecosystem::Generic => return None,
+    };
+    find(name)
+}
+
+/// Resolves the pack to merge: the one named by `selected` (`pack:` in
+/// `scopes.yaml`), otherwise the detected ecosystem's. An unknown name is
+/// warned about and falls back to detection.
+pub fn select(selected: Option<&str>, ecosystem: &Ecosystem) -> Option<&'static ScopePack> {
+    match selected.map(str::trim).filter(|name| !name.is_empty()) {
+        Some(name) if name.eq_ignore_ascii_case(NO_PACK) => None,
+        Some(name) => find(name).or_else(|| {
+            tracing::warn!(
+                "Ignoring unknown scope pack '{name}' (expected one of: {}, {NO_PACK})",
+                names().join(", ")
+            );
+            for_ecosystem(ecosystem)
+        }),
+        None => for_ecosystem(ecosystem),
+    }
+}
+
+/// Names of every shipped pack.
+pub fn names() -> Vec<&'static str> {
+    PACKS.iter().map(|pack| pack.name).collect()
+}
+
+/// Returns `scopes_yaml` with its top-level `pack:` set to `name`, replacing
+/// an existing key in place or adding one at the top. Comments and the rest
+/// of the file are left as they are.
+pub fn set_pack(scopes_yaml: Option<&str>, name: &str) -> String {
+    let line = format!("pack: {name}");
+    let Some(content) = scopes_yaml else {
+        return format!(
+            "# Scopes listed here take precedence over the scope pack's.\n{line}\nscopes: []\n"
+        );
+    };
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|existing| {
+            if !replaced && existing.starts_with("pack:") {
+                replaced = true;
+                line.clone()
+            } else {
+                existing.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        let first_content = lines
+            .iter()
+            .position(|l| !l.trim().is_empty() && !l.starts_with('#') && l.trim() != "---")
+            .unwrap_or(lines.len());
+        lines.insert(first_content, line);
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_ecosystem_with_defaults_has_a_pack() {
+        for ecosystem in [
+            Ecosystem::Rust,
+            Ecosystem::Node,
+            Ecosystem::Python,
+            Ecosystem::Go,
+            Ecosystem::Java,
+            Ecosystem::Terraform,
+        ] {
+            let pack = for_ecosystem(&ecosystem).expect("pack");
+            assert!(!pack.scopes().is_empty(), "{} is empty", pack.name);
+        }
+        assert!(for_ecosystem(&Ecosystem::Generic).is_none());
+    }
+
+    #[test]
+    fn pack_scope_names_are_unique_and_patterns_valid() {
+        for pack in PACKS {
+            let scopes = pack.scopes();
+            for (index, scope) in scopes.iter().enumerate() {
+                assert!(
+                    !scopes[..index].iter().any(|s| s.name == scope.name),
+                    "{} repeats {}",
+                    pack.name,
+                    scope.name
+                );
+                for pattern in &scope.file_patterns {
+                    globset::Glob::new(pattern).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn explicit_selection_overrides_detection() {
+        assert_eq!(
+            select(Some("Terraform"), &Ecosystem::Rust).unwrap().name,
+            "terraform"
+        );
+        assert!(select(Some("none"), &Ecosystem::Rust).is_none());
+        assert_eq!(select(Some("bogus"), &Ecosystem::Go).unwrap().name, "go");
+        assert_eq!(select(None, &Ecosystem::Python).unwrap().name, "python");
+    }
+
+    #[test]
+    fn set_pack_preserves_the_rest_of_the_file() {
+        let existing = "# Team scopes\nscopes:\n  - name: api\n";
+        assert_eq!(
+            set_pack(Some(existing), "node"),
+            "# Team scopes\npack: node\nscopes:\n  - name: api\n"
+        );
+        assert_eq!(
+            set_pack(Some("pack: rust\nscopes: []\n"), "python"),
+            "pack: python\nscopes: []\n"
+        );
+        let created = set_pack(None, "terraform");
+        assert!(created.contains("pack: terraform\nscopes: []\n"));
+    }
+}
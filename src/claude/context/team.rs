@@ -0,0 +1,315 @@
+//! Shared team context from a git repository.
+//!
+//! `.omni-dev/context.yaml` points the whole context directory at a central
+//! repository instead of extending one file at a time:
+//!
+//! ```yaml
+//! extends: git@github.com:org/omni-conventions.git
+//! ref: v2          # branch, tag, or commit (default: the remote HEAD)
+//! dir: .omni-dev   # directory inside the repository (default: .omni-dev)
+//! ```
+//!
+//! The repository is cloned with the `git` CLI (so the user's SSH and
+//! credential setup applies) into the remote-source cache directory, and the
+//! pinned ref is checked out. Its files become a resolution tier between the
+//! project and the global config (see
+//! [`resolve_config_file`](super::discovery::resolve_config_file)), so a
+//! project file of the same name still wins. Branches and tags are refetched
+//! once the checkout is older than the remote-source TTL; a ref that is a
+//! full commit hash is immutable and never refetched once present. A failed
+//! refetch falls back to the existing checkout.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{LazyLock, Mutex};
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::utils::digest::sha256_hex;
+
+/// Name of the team context declaration inside the context directory.
+pub const CONTEXT_FILE: &str = "context.yaml";
+
+/// File touched after every successful fetch; its age drives refetching.
+const FETCHED_MARKER: &str = "omni-dev-fetched";
+
+/// Team checkouts already resolved by this process, keyed by context dir.
+static RESOLVED: LazyLock<Mutex<HashMap<PathBuf, Option<PathBuf>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The contents of `context.yaml`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TeamContextConfig {
+    /// Git URL of the shared repository (SSH, HTTPS, or a local path).
+    pub extends: String,
+    /// Branch, tag, or commit to use; the remote HEAD when unset.
+    #[serde(default, rename = "ref")]
+    pub git_ref: Option<String>,
+    /// Directory inside the repository holding the config files.
+    #[serde(default = "default_dir")]
+    pub dir: String,
+}
+
+fn default_dir() -> String {
+    ".omni-dev".to_string()
+}
+
+/// Returns the team config directory declared by `{context_dir}/context.yaml`,
+/// syncing the checkout on first use in this process.
+///
+/// Returns `None` when there is no `context.yaml`, and also when it is
+/// malformed or the repository cannot be fetched (logged as a warning), so a
+/// broken team source never blocks local work.
+pub fn team_config_dir(context_dir: &Path) -> Option<PathBuf> {
+    let mut resolved = RESOLVED
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(dir) = resolved.get(context_dir) {
+        return dir.clone();
+    }
+    let dir = if context_dir.join(CONTEXT_FILE).is_file() {
+        match super::remote::cache_dir() {
+            Some(cache_root) => resolve_team_dir(context_dir, &cache_root.join("git"))
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Ignoring team context in {}: {e:#}", context_dir.display());
+                    None
+                }),
+            None => None,
+        }
+    } else {
+        None
+    };
+    resolved.insert(context_dir.to_path_buf(), dir.clone());
+    dir
+}
+
+/// Reads `context.yaml` in `context_dir` and syncs its repository under
+/// `cache_root`, returning the config directory inside the checkout.
+fn resolve_team_dir(context_dir: &Path, cache_root: &Path) -> Result<Option<PathBuf>> {
+    let path = context_dir.join(CONTEXT_FILE);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let config: TeamContextConfig = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let checkout = sync_checkout(&config.extends, config.git_ref.as_deref(), cache_root)?;
+    let dir = checkout.join(config.dir.trim_matches('/'));
+    if !dir.is_dir() {
+        bail!(
+            "{} has no directory '{}' at {}",
+            config.extends,
+            config.dir,
+            config.git_ref.as_deref().unwrap_or("HEAD")
+        );
+    }
+    Ok(Some(dir))
+}
+
+/// Clones or refreshes `url` under `cache_root` and checks out `git_ref`.
+fn sync_checkout(url: &str, git_ref: Option<&str>, cache_root: &Path) -> Result<PathBuf> {
+    let checkout = cache_root.join(sha256_hex(url));
+    let marker = checkout.join(".git").join(FETCHED_MARKER);
+
+    if !checkout.join(".git").is_dir() {
+        std::fs::create_dir_all(cache_root).with_context(|| {
+            format!("Failed to create cache directory {}", cache_root.display())
+        })?;
+        let target = checkout.to_string_lossy();
+        run_git(
+            None,
+            &["clone", "--quiet", "--no-checkout", url, target.as_ref()],
+        )
+        .with_context(|| format!("Failed to clone {url}"))?;
+        touch(&marker);
+    } else if needs_fetch(&checkout, &marker, git_ref) {
+        match run_git(
+            Some(&checkout),
+            &["fetch", "--quiet", "--tags", "--force", "--prune", "origin"],
+        ) {
+            Ok(_) => touch(&marker),
+            Err(e) => tracing::warn!("Using cached checkout of {url}; fetch failed: {e:#}"),
+        }
+    }
+
+    let target = resolve_target(&checkout, git_ref)
+        .with_context(|| format!("{url} has no ref '{}'", git_ref.unwrap_or("HEAD")))?;
+    check_out_detached(&checkout, target)?;
+    Ok(checkout)
+}
+
+/// Detaches the checkout's `HEAD` at `target` and forces the working tree to
+/// match, like `git checkout --force --detach`.
+fn check_out_detached(checkout: &Path, target: git2::Oid) -> Result<()> {
+    let repo = git2::Repository::open(checkout)
+        .with_context(|| format!("Failed to open {}", checkout.display()))?;
+    repo.set_head_detached(target)
+        .with_context(|| format!("Failed to detach HEAD at {target}"))?;
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .with_context(|| format!("Failed to check out {target}"))?;
+    Ok(())
+}
+
+/// Whether the checkout should be refetched: never for a commit hash that is
+/// already present, otherwise once the last fetch is older than the TTL.
+fn needs_fetch(checkout: &Path, marker: &Path, git_ref: Option<&str>) -> bool {
+    if let Some(git_ref) = git_ref.filter(|r| is_commit_hash(r)) {
+        if rev_parse(checkout, &format!("{git_ref}^{{commit}}")).is_some() {
+            return false;
+        }
+    }
+    std::fs::metadata(marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .map_or(true, |age| age >= super::remote::CACHE_TTL)
+}
+
+/// Resolves `git_ref` to a commit, preferring the remote-tracking branch so
+/// a branch ref follows fetches.
+fn resolve_target(checkout: &Path, git_ref: Option<&str>) -> Result<git2::Oid> {
+    let candidates = match git_ref {
+        Some(git_ref) => vec![
+            format!("refs/remotes/origin/{git_ref}^{{commit}}"),
+            format!("{git_ref}^{{commit}}"),
+        ],
+        None => vec!["refs/remotes/origin/HEAD^{commit}".to_string()],
+    };
+    candidates
+        .iter()
+        .find_map(|candidate| rev_parse(checkout, candidate))
+        .context("ref not found")
+}
+
+fn rev_parse(checkout: &Path, spec: &str) -> Option<git2::Oid> {
+    let repo = git2::Repository::open(checkout).ok()?;
+    let object = repo.revparse_single(spec).ok()?;
+    Some(object.id())
+}
+
+/// Whether `git_ref` is a full SHA-1 or SHA-256 commit hash.
+fn is_commit_hash(git_ref: &str) -> bool {
+    matches!(git_ref.len(), 40 | 64) && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Runs a `git` clone or fetch (in `dir`, when given) without prompting for
+/// credentials. Transport stays on the CLI for the reasons given on
+/// [`run_git_cli`](crate::git::backend::run_git_cli); local reads and the
+/// checkout go through `git2`.
+fn run_git(dir: Option<&Path>, args: &[&str]) -> Result<String> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.arg("-C").arg(dir);
+    }
+    let output = cmd
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn touch(path: &Path) {
+    if let Err(e) = std::fs::write(path, "") {
+        tracing::debug!("team context: failed to write {}: {e}", path.display());
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    /// Creates a repository with one commit holding `.omni-dev/scopes.yaml`
+    /// set to `content`, and returns the commit hash.
+    fn commit_scopes(repo: &git2::Repository, content: &str) -> String {
+        let root = repo.workdir().unwrap();
+        std::fs::create_dir_all(root.join(".omni-dev")).unwrap();
+        std::fs::write(root.join(".omni-dev/scopes.yaml"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(".omni-dev/scopes.yaml")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, content, &tree, &parents)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn parses_context_yaml_defaults() {
+        let config: TeamContextConfig =
+            serde_yaml::from_str("extends: git@github.com:org/conventions.git\n").unwrap();
+        assert_eq!(config.extends, "git@github.com:org/conventions.git");
+        assert_eq!(config.git_ref, None);
+        assert_eq!(config.dir, ".omni-dev");
+    }
+
+    #[test]
+    fn detects_commit_hashes() {
+        assert!(is_commit_hash(&"a".repeat(40)));
+        assert!(!is_commit_hash("v2"));
+        assert!(!is_commit_hash(&"g".repeat(40)));
+    }
+
+    #[test]
+    fn checks_out_pinned_ref_from_shared_repository() {
+        let origin_dir = tempfile::tempdir().unwrap();
+        let origin = git2::Repository::init(origin_dir.path()).unwrap();
+        let first = commit_scopes(&origin, "scopes: [] # v1\n");
+        commit_scopes(&origin, "scopes: [] # v2\n");
+
+        let project = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        std::fs::write(
+            project.path().join(CONTEXT_FILE),
+            format!("extends: {}\nref: {first}\n", origin_dir.path().display()),
+        )
+        .unwrap();
+
+        let dir = resolve_team_dir(project.path(), cache.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("scopes.yaml")).unwrap(),
+            "scopes: [] # v1\n"
+        );
+
+        // Without a pin the remote HEAD is used.
+        std::fs::write(
+            project.path().join(CONTEXT_FILE),
+            format!("extends: {}\n", origin_dir.path().display()),
+        )
+        .unwrap();
+        let dir = resolve_team_dir(project.path(), cache.path())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("scopes.yaml")).unwrap(),
+            "scopes: [] # v2\n"
+        );
+    }
+
+    #[test]
+    fn missing_context_yaml_is_none() {
+        let project = tempfile::tempdir().unwrap();
+        let cache = tempfile::tempdir().unwrap();
+        assert_eq!(
+            resolve_team_dir(project.path(), cache.path()).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Ticket enrichment for branch context.
+//!
+//! When the branch names a ticket (`PROJ-123-add-login`), its title and
+//! description can be fetched from Jira or Linear and handed to the commit
+//! and PR prompts, so the generated text can explain why a change was made
+//! rather than only restating the diff.
+//!
+//! Enrichment is opt-in through `OMNI_DEV_TICKET_CONTEXT` (environment or
+//! `settings.json`):
+//!
+//! | Value | Tracker |
+//! |---|---|
+//! | unset, `off` | none |
+//! | `jira` | Jira, with the `ATLASSIAN_*` credentials |
+//! | `linear` | Linear, with `LINEAR_API_KEY` |
+//! | `auto`, `on`, `true` | Jira when Atlassian credentials are configured, else Linear when `LINEAR_API_KEY` is set |
+//!
+//! Fetching is best effort: a missing token, an unknown ticket, or a network
+//! failure is logged as a warning and the prompts fall back to the ticket ID.
+
+use std::sync::LazyLock;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use tracing::{debug, warn};
+
+use crate::atlassian::adf::AdfDocument;
+use crate::atlassian::client::AtlassianClient;
+use crate::data::context::TicketContext;
+
+/// Setting that enables ticket enrichment and selects the tracker.
+pub const TICKET_CONTEXT_ENV: &str = "OMNI_DEV_TICKET_CONTEXT";
+
+/// Setting holding the Linear personal API key.
+pub const LINEAR_API_KEY: &str = "LINEAR_API_KEY";
+
+/// Linear's GraphQL endpoint.
+const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
+
+/// Longest description passed to the prompts, in characters.
+const MAX_DESCRIPTION_CHARS: usize = 4000;
+
+#[allow(clippy::unwrap_used)] // Compile-time constant regex pattern
+static TICKET_KEY_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z][A-Za-z0-9]*-\d+$").unwrap());
+
+/// Issue tracker a ticket is fetched from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TicketProvider {
+    /// Atlassian Jira.
+    Jira,
+    /// Linear.
+    Linear,
+}
+
+/// Selects the tracker from the `OMNI_DEV_TICKET_CONTEXT` value and which
+/// credentials are configured. `None` disables enrichment.
+pub fn select_provider(
+    setting: Option<&str>,
+    has_jira: bool,
+    has_linear: bool,
+) -> Result<Option<TicketProvider>> {
+    let Some(setting) = setting.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(None);
+    };
+    match setting.to_lowercase().as_str() {
+        "off" | "false" | "0" => Ok(None),
+        "jira" => Ok(Some(TicketProvider::Jira)),
+        "linear" => Ok(Some(TicketProvider::Linear)),
+        "auto" | "on" | "true" | "1" => Ok(if has_jira {
+            Some(TicketProvider::Jira)
+        } else if has_linear {
+            Some(TicketProvider::Linear)
+        } else {
+            None
+        }),
+        other => bail!(
+            "Invalid {TICKET_CONTEXT_ENV} value '{other}' (expected off, auto, jira, or linear)"
+        ),
+    }
+}
+
+/// Fetches the ticket named by `ticket_id` when enrichment is enabled.
+///
+/// Returns `None` when enrichment is off, the ID is not a tracker key (e.g.
+/// `#42`), or the fetch fails.
+pub async fn fetch_ticket_context(ticket_id: &str) -> Option<TicketContext> {
+    if !TICKET_KEY_PATTERN.is_match(ticket_id) {
+        return None;
+    }
+    let setting = crate::utils::settings::get_env_var(TICKET_CONTEXT_ENV).ok()?;
+    let linear_key = crate::utils::settings::get_env_var(LINEAR_API_KEY).ok();
+    let jira = crate::atlassian::auth::load_credentials().ok();
+    let provider = match select_provider(Some(&setting), jira.is_some(), linear_key.is_some()) {
+        Ok(Some(provider)) => provider,
+        Ok(None) => {
+            debug!("Ticket enrichment disabled or no tracker credentials configured");
+            return None;
+        }
+        Err(e) => {
+            warn!("{e:#}");
+            return None;
+        }
+    };
+
+    let key = ticket_id.to_uppercase();
+    let result = match provider {
+        TicketProvider::Jira => match jira {
+            Some(credentials) => match AtlassianClient::from_credentials(&credentials) {
+                Ok(client) => fetch_jira(&client, &key).await,
+                Err(e) => Err(e),
+            },
+            None => Err(anyhow::anyhow!("Atlassian credentials are not configured")),
+        },
+        TicketProvider::Linear => match linear_key {
+            Some(api_key) => fetch_linear(LINEAR_API_URL, &api_key, &key).await,
+            None => Err(anyhow::anyhow!("{LINEAR_API_KEY} is not configured")),
+        },
+    };
+    match result {
+        Ok(ticket) => Some(ticket),
+        Err(e) => {
+            warn!("Could not fetch ticket {key}: {e:#}");
+            None
+        }
+    }
+}
+
+/// Fetches a Jira issue and renders its description as Markdown.
+async fn fetch_jira(client: &AtlassianClient, key: &str) -> Result<TicketContext> {
+    let issue = client.get_issue(key).await?;
+    let description = match issue.description_adf {
+        Some(adf) => {
+            let doc: AdfDocument =
+                serde_json::from_value(adf).context("Failed to parse issue description")?;
+            Some(crate::atlassian::convert::adf_to_markdown(&doc)?)
+        }
+        None => None,
+    };
+    Ok(TicketContext {
+        id: issue.key.clone(),
+        title: issue.summary,
+        description: description.and_then(|d| truncate_description(&d)),
+        url: Some(format!("{}/browse/{}", client.instance_url(), issue.key)),
+    })
+}
+
+/// Fetches a Linear issue by its identifier through the GraphQL API.
+async fn fetch_linear(endpoint: &str, api_key: &str, key: &str) -> Result<TicketContext> {
+    #[derive(serde::Deserialize)]
+    struct Issue {
+        identifier: String,
+        title: String,
+        description: Option<String>,
+        url: Option<String>,
+    }
+
+    let query = "query($id: String!) { issue(id: $id) { identifier title description url } }";
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(endpoint)
+        .header("Authorization", api_key)
+        .json(&serde_json::json!({ "query": query, "variables": { "id": key } }))
+        .send()
+        .await
+        .context("Failed to reach the Linear API")?
+        .error_for_status()
+        .context("Linear API request failed")?
+        .json()
+        .await
+        .context("Failed to parse Linear API response")?;
+
+    if let Some(message) = response
+        .pointer("/errors/0/message")
+        .and_then(serde_json::Value::as_str)
+    {
+        bail!("Linear API error: {message}");
+    }
+    let issue = response
+        .pointer("/data/issue")
+        .filter(|issue| !issue.is_null())
+        .context("Linear issue not found")?;
+    let issue: Issue =
+        serde_json::from_value(issue.clone()).context("Unexpected Linear issue shape")?;
+    Ok(TicketContext {
+        id: issue.identifier,
+        title: issue.title,
+        description: issue.description.and_then(|d| truncate_description(&d)),
+        url: issue.url,
+    })
+}
+
+/// Trims `description`, dropping it when empty and cutting it at
+/// [`MAX_DESCRIPTION_CHARS`].
+fn truncate_description(description: &str) -> Option<String> {
+    let trimmed = description.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    match trimmed.char_indices().nth(MAX_DESCRIPTION_CHARS) {
+        Some((end, _)) => Some(format!("{}\n[…truncated]", &trimmed[..end])),
+        None => Some(trimmed.to_string()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_provider_from_setting_and_credentials() {
+        assert_eq!(select_provider(None, true, true).unwrap(), None);
+        assert_eq!(select_provider(Some("off"), true, true).unwrap(), None);
+        assert_eq!(
+            select_provider(Some("Linear"), true, false).unwrap(),
+            Some(TicketProvider::Linear)
+        );
+        assert_eq!(
+            select_provider(Some("auto"), true, true).unwrap(),
+            Some(TicketProvider::Jira)
+        );
+        assert_eq!(
+            select_provider(Some("auto"), false, true).unwrap(),
+            Some(TicketProvider::Linear)
+        );
+        assert_eq!(select_provider(Some("auto"), false, false).unwrap(), None);
+        assert!(select_provider(Some("github"), true, true).is_err());
+    }
+
+    #[test]
+    fn truncates_long_descriptions() {
+        assert_eq!(truncate_description("  \n"), None);
+        assert_eq!(truncate_description(" why \n").as_deref(), Some("why"));
+        let long = "é".repeat(MAX_DESCRIPTION_CHARS + 10);
+        let truncated = truncate_description(&long).unwrap();
+        assert!(truncated.ends_with("[…truncated]"));
+        assert_eq!(
+            truncated.chars().filter(|c| *c == 'é').count(),
+            MAX_DESCRIPTION_CHARS
+        );
+    }
+
+    #[tokio::test]
+    async fn fetches_linear_issue() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .and(wiremock::matchers::header("Authorization", "lin_api_key"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"data": {"issue": {
+                    "identifier": "ENG-12",
+                    "title": "Customers cannot reset passwords",
+                    "description": "Reset emails bounce for SSO users.",
+                    "url": "https://linear.app/org/issue/ENG-12"
+                }}}),
+            ))
+            .mount(&server)
+            .await;
+
+        let ticket = fetch_linear(&server.uri(), "lin_api_key", "ENG-12")
+            .await
+            .unwrap();
+        assert_eq!(ticket.title, "Customers cannot reset passwords");
+        assert_eq!(
+            ticket.description.as_deref(),
+            Some("Reset emails bounce for SSO users.")
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_missing_linear_issue() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(
+                wiremock::ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"data": {"issue": null}})),
+            )
+            .mount(&server)
+            .await;
+
+        let err = fetch_linear(&server.uri(), "key", "ENG-404")
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn fetches_jira_issue_as_markdown() {
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("GET"))
+            .and(wiremock::matchers::path("/rest/api/3/issue/PROJ-7"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({"key": "PROJ-7", "fields": {
+                    "summary": "Export invoices as CSV",
+                    "description": {"type": "doc", "version": 1, "content": [
+                        {"type": "paragraph", "content": [
+                            {"type": "text", "text": "Finance needs monthly exports."}
+                        ]}
+                    ]}
+                }}),
+            ))
+            .mount(&server)
+            .await;
+
+        let client = AtlassianClient::new(&server.uri(), "user@test.com", "token").unwrap();
+        let ticket = fetch_jira(&client, "PROJ-7").await.unwrap();
+        assert_eq!(ticket.title, "Export invoices as CSV");
+        assert!(ticket
+            .description
+            .unwrap()
+            .contains("Finance needs monthly exports."));
+        assert_eq!(ticket.url, Some(format!("{}/browse/PROJ-7", server.uri())));
+    }
+}
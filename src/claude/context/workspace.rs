@@ -0,0 +1,592 @@
+//! Package detection for monorepo workspaces.
+//!
+//! Cargo workspaces, pnpm/yarn/npm workspaces, and Go workspaces all list
+//! their member packages in a root manifest. Each member becomes a scope
+//! named after the package (not its directory), with a `<dir>/**` file
+//! pattern, so [`crate::git::CommitAnalysis::refine_scope`] maps a change
+//! under `crates/omni-core/` to `omni-core` rather than a generic `crates`
+//! bucket.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::Regex;
+
+use crate::data::context::ScopeDefinition;
+
+/// Root manifests that declare workspace members.
+pub const WORKSPACE_MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pnpm-workspace.yaml",
+    "go.work",
+];
+
+/// Directories never descended into while expanding `**` member globs.
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "vendor", "dist", "build"];
+
+/// How deep a `**` member glob may descend.
+const MAX_GLOB_DEPTH: usize = 6;
+
+/// The tool whose workspace a package belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceKind {
+    /// A Cargo workspace member.
+    Cargo,
+    /// A pnpm, yarn, or npm workspace package.
+    Node,
+    /// A Go module listed in `go.work`.
+    Go,
+}
+
+/// A workspace member package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspacePackage {
+    /// Package name as declared in its manifest.
+    pub name: String,
+    /// Package directory, relative to the repository root.
+    pub dir: PathBuf,
+    /// Workspace tool that declared the package.
+    pub kind: WorkspaceKind,
+}
+
+impl WorkspacePackage {
+    /// Returns the scope name for the package: its name without an npm
+    /// `@org/` prefix, lowercased.
+    pub fn scope_name(&self) -> String {
+        let name = match self.name.strip_prefix('@') {
+            Some(scoped) => scoped.split_once('/').map_or(scoped, |(_, name)| name),
+            None => &self.name,
+        };
+        name.to_lowercase()
+    }
+}
+
+/// Detects the member packages of every workspace rooted at `repo_path`.
+///
+/// Members without a readable manifest or package name are skipped, as is a
+/// package living at the repository root (its scope would match every file).
+pub fn detect_packages(repo_path: &Path) -> Vec<WorkspacePackage> {
+    let mut packages = Vec::new();
+    packages.extend(cargo_packages(repo_path));
+    packages.extend(node_packages(repo_path));
+    packages.extend(go_packages(repo_path));
+    packages.retain(|p| !p.dir.as_os_str().is_empty());
+    packages
+}
+
+/// Loads one scope definition per detected workspace package.
+///
+/// Packages sharing a scope name are merged into one scope.
+pub fn load_workspace_scopes(repo_path: &Path) -> Vec<ScopeDefinition> {
+    let mut scopes: Vec<ScopeDefinition> = Vec::new();
+    for package in detect_packages(repo_path) {
+        let dir = package.dir.to_string_lossy().replace('\\', "/");
+        let pattern = format!("{dir}/**");
+        let name = package.scope_name();
+        if let Some(existing) = scopes.iter_mut().find(|s| s.name == name) {
+            if !existing.file_patterns.contains(&pattern) {
+                existing.file_patterns.push(pattern);
+            }
+        } else {
+            scopes.push(ScopeDefinition {
+                name,
+                description: format!("The {} package ({dir})", package.name),
+                examples: vec![],
+                file_patterns: vec![pattern],
+            });
+        }
+    }
+    scopes
+}
+
+/// Returns the package containing `file` (a repository-relative path),
+/// preferring the most deeply nested package directory.
+pub fn package_for_file<'a>(
+    file: &Path,
+    packages: &'a [WorkspacePackage],
+) -> Option<&'a WorkspacePackage> {
+    packages
+        .iter()
+        .filter(|p| file.starts_with(&p.dir))
+        .max_by_key(|p| p.dir.components().count())
+}
+
+/// Reads the Cargo workspace members declared in the root `Cargo.toml`.
+fn cargo_packages(repo_path: &Path) -> Vec<WorkspacePackage> {
+    let Ok(content) = fs::read_to_string(repo_path.join("Cargo.toml")) else {
+        return vec![];
+    };
+    let members = toml_string_array(&content, "workspace", "members");
+    let excludes = toml_string_array(&content, "workspace", "exclude");
+    expand_members(repo_path, &members, &excludes)
+        .into_iter()
+        .filter_map(|dir| {
+            let manifest = fs::read_to_string(repo_path.join(&dir).join("Cargo.toml")).ok()?;
+            Some(WorkspacePackage {
+                name: toml_string(&manifest, "package", "name")?,
+                dir,
+                kind: WorkspaceKind::Cargo,
+            })
+        })
+        .collect()
+}
+
+/// Reads the Node workspace packages from `pnpm-workspace.yaml`, falling
+/// back to the `workspaces` field of the root `package.json` (yarn, npm).
+fn node_packages(repo_path: &Path) -> Vec<WorkspacePackage> {
+    let patterns = match fs::read_to_string(repo_path.join("pnpm-workspace.yaml")) {
+        Ok(content) => pnpm_workspace_patterns(&content),
+        Err(_) => fs::read_to_string(repo_path.join("package.json"))
+            .map(|content| package_json_workspaces(&content))
+            .unwrap_or_default(),
+    };
+    let (excludes, includes): (Vec<String>, Vec<String>) =
+        patterns.into_iter().partition(|p| p.starts_with('!'));
+    let excludes: Vec<String> = excludes
+        .into_iter()
+        .map(|p| p.trim_start_matches('!').to_string())
+        .collect();
+    expand_members(repo_path, &includes, &excludes)
+        .into_iter()
+        .filter_map(|dir| {
+            let manifest = fs::read_to_string(repo_path.join(&dir).join("package.json")).ok()?;
+            let value: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+            Some(WorkspacePackage {
+                name: value.get("name")?.as_str()?.to_string(),
+                dir,
+                kind: WorkspaceKind::Node,
+            })
+        })
+        .collect()
+}
+
+/// Reads the Go modules listed by `use` directives in `go.work`.
+fn go_packages(repo_path: &Path) -> Vec<WorkspacePackage> {
+    let Ok(content) = fs::read_to_string(repo_path.join("go.work")) else {
+        return vec![];
+    };
+    go_work_uses(&content)
+        .into_iter()
+        .filter_map(|dir| {
+            let dir = normalize_member(&dir)?;
+            let go_mod = fs::read_to_string(repo_path.join(&dir).join("go.mod")).ok()?;
+            Some(WorkspacePackage {
+                name: go_module_name(&go_mod)?,
+                dir,
+                kind: WorkspaceKind::Go,
+            })
+        })
+        .collect()
+}
+
+/// Parses the `packages` list of a `pnpm-workspace.yaml`.
+fn pnpm_workspace_patterns(content: &str) -> Vec<String> {
+    #[derive(serde::Deserialize)]
+    struct PnpmWorkspace {
+        #[serde(default)]
+        packages: Vec<String>,
+    }
+    match serde_yaml::from_str::<PnpmWorkspace>(content) {
+        Ok(workspace) => workspace.packages,
+        Err(e) => {
+            tracing::warn!("Ignoring malformed pnpm-workspace.yaml: {e}");
+            vec![]
+        }
+    }
+}
+
+/// Parses the `workspaces` field of a `package.json`, in either its array
+/// form or yarn's `{ "packages": [...] }` form.
+fn package_json_workspaces(content: &str) -> Vec<String> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return vec![];
+    };
+    let workspaces = match value.get("workspaces") {
+        Some(serde_json::Value::Object(map)) => map.get("packages"),
+        other => other,
+    };
+    workspaces
+        .and_then(serde_json::Value::as_array)
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|p| p.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the directories named by `use` directives, in both the single
+/// (`use ./a`) and block (`use ( ... )`) forms.
+fn go_work_uses(content: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        if in_block {
+            if line == ")" {
+                in_block = false;
+            } else if !line.is_empty() {
+                uses.push(line.trim_matches('"').to_string());
+            }
+        } else if let Some(rest) = line
+            .strip_prefix("use")
+            .filter(|rest| rest.starts_with([' ', '\t', '(']))
+        {
+            let rest = rest.trim();
+            if rest == "(" {
+                in_block = true;
+            } else if !rest.is_empty() {
+                uses.push(rest.trim_matches('"').to_string());
+            }
+        }
+    }
+    uses
+}
+
+/// Derives a package name from the `module` path in a `go.mod`: its last
+/// segment, skipping a major-version suffix (`example.com/api/v2` → `api`).
+fn go_module_name(content: &str) -> Option<String> {
+    let path = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?
+        .trim()
+        .trim_matches('"');
+    let mut segments = path.rsplit('/');
+    let last = segments.next()?;
+    let is_major_version =
+        last.len() > 1 && last.starts_with('v') && last[1..].chars().all(|c| c.is_ascii_digit());
+    let name = if is_major_version {
+        segments.next()?
+    } else {
+        last
+    };
+    Some(name.to_string())
+}
+
+#[allow(clippy::unwrap_used)] // Compile-time constant regex pattern
+static TOML_STRING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([^"]*)"|'([^']*)'"#).unwrap());
+
+/// Reads a string-array value from a TOML table with a line-based scan,
+/// enough for the `members`/`exclude` arrays of a Cargo workspace.
+fn toml_string_array(content: &str, table: &str, key: &str) -> Vec<String> {
+    let Some(value) = toml_raw_value(content, table, key) else {
+        return vec![];
+    };
+    TOML_STRING
+        .captures_iter(&value)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+/// Reads a string value from a TOML table.
+fn toml_string(content: &str, table: &str, key: &str) -> Option<String> {
+    let value = toml_raw_value(content, table, key)?;
+    let captures = TOML_STRING.captures(&value)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(2))
+        .map(|m| m.as_str().to_string())
+}
+
+/// Returns the raw text of `key` in `[table]`, spanning lines until a
+/// multi-line array closes.
+fn toml_raw_value(content: &str, table: &str, key: &str) -> Option<String> {
+    let mut current_table = String::new();
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            current_table = line.trim_matches(['[', ']']).trim().to_string();
+            continue;
+        }
+        if current_table != table {
+            continue;
+        }
+        let Some((line_key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if line_key.trim() != key {
+            continue;
+        }
+        let mut value = value.trim().to_string();
+        if value.starts_with('[') {
+            while !value.contains(']') {
+                let Some(next) = lines.next() else {
+                    break;
+                };
+                let next = next.trim();
+                if !next.starts_with('#') {
+                    value.push(' ');
+                    value.push_str(next);
+                }
+            }
+        }
+        return Some(value);
+    }
+    None
+}
+
+/// Expands member patterns into existing member directories (relative to
+/// `repo_path`), dropping any matched by `excludes`.
+fn expand_members(repo_path: &Path, members: &[String], excludes: &[String]) -> Vec<PathBuf> {
+    let excluded = build_globset(excludes);
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for member in members {
+        for dir in expand_pattern(repo_path, member) {
+            if excluded.is_match(&dir) || dirs.contains(&dir) {
+                continue;
+            }
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Compiles member exclusion patterns, skipping invalid ones.
+fn build_globset(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let Some(pattern) = normalize_member(pattern) else {
+            continue;
+        };
+        if let Ok(glob) = Glob::new(&pattern.to_string_lossy()) {
+            builder.add(glob);
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// Expands one member pattern component by component: literal components
+/// must exist, `*`-style components match directory names, and `**` matches
+/// any depth.
+fn expand_pattern(repo_path: &Path, pattern: &str) -> Vec<PathBuf> {
+    let Some(pattern) = normalize_member(pattern) else {
+        return vec![];
+    };
+    let mut current = vec![PathBuf::new()];
+    for component in pattern.iter() {
+        let component = component.to_string_lossy();
+        current = if component == "**" {
+            current
+                .into_iter()
+                .flat_map(|dir| descendant_dirs(repo_path, dir, MAX_GLOB_DEPTH))
+                .collect()
+        } else if component.contains(['*', '?', '[']) {
+            let Ok(glob) = Glob::new(&component) else {
+                return vec![];
+            };
+            let matcher = glob.compile_matcher();
+            current
+                .into_iter()
+                .flat_map(|dir| child_dirs(repo_path, &dir))
+                .filter(|dir| dir.file_name().is_some_and(|name| matcher.is_match(name)))
+                .collect()
+        } else {
+            current
+                .into_iter()
+                .map(|dir| dir.join(&*component))
+                .filter(|dir| repo_path.join(dir).is_dir())
+                .collect()
+        };
+    }
+    current
+}
+
+/// Strips `./` and trailing slashes from a member path; `None` for the
+/// repository root itself.
+fn normalize_member(pattern: &str) -> Option<PathBuf> {
+    let path: PathBuf = Path::new(pattern.trim())
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect();
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+/// Lists the visible, non-build subdirectories of `dir`.
+fn child_dirs(repo_path: &Path, dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(repo_path.join(dir)) else {
+        return vec![];
+    };
+    let mut children: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            (!name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()))
+                .then(|| dir.join(name))
+        })
+        .collect();
+    children.sort();
+    children
+}
+
+/// Returns `dir` and every subdirectory below it, up to `depth` levels.
+fn descendant_dirs(repo_path: &Path, dir: PathBuf, depth: usize) -> Vec<PathBuf> {
+    let children = if depth > 0 {
+        child_dirs(repo_path, &dir)
+    } else {
+        Vec::new()
+    };
+    let mut dirs = vec![dir];
+    for child in children {
+        dirs.extend(descendant_dirs(repo_path, child, depth - 1));
+    }
+    dirs
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn reads_cargo_workspace_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n\n[workspace]\nmembers = [\n    \"crates/*\",\n    # \"old\",\n    \"tools/xtask\",\n]\nexclude = [\"crates/scratch\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"omni-core\"\n",
+        );
+        write(
+            root,
+            "crates/scratch/Cargo.toml",
+            "[package]\nname = \"scratch\"\n",
+        );
+        write(
+            root,
+            "tools/xtask/Cargo.toml",
+            "[package]\nname = 'xtask'\n",
+        );
+
+        let packages = detect_packages(root);
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["omni-core", "xtask"]);
+        assert_eq!(packages[0].dir, PathBuf::from("crates/core"));
+        assert_eq!(packages[0].kind, WorkspaceKind::Cargo);
+    }
+
+    #[test]
+    fn reads_pnpm_workspace_with_negation() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'packages/**'\n  - '!packages/internal'\n",
+        );
+        write(
+            root,
+            "packages/web/package.json",
+            r#"{"name": "@acme/web"}"#,
+        );
+        write(
+            root,
+            "packages/internal/package.json",
+            r#"{"name": "internal"}"#,
+        );
+        write(
+            root,
+            "packages/web/node_modules/dep/package.json",
+            r#"{"name": "dep"}"#,
+        );
+
+        let scopes = load_workspace_scopes(root);
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(scopes[0].name, "web");
+        assert_eq!(scopes[0].file_patterns, vec!["packages/web/**"]);
+        assert!(scopes[0].description.contains("@acme/web"));
+    }
+
+    #[test]
+    fn reads_yarn_workspaces_object_form() {
+        assert_eq!(
+            package_json_workspaces(r#"{"workspaces": {"packages": ["apps/*"]}}"#),
+            vec!["apps/*"]
+        );
+        assert_eq!(
+            package_json_workspaces(r#"{"workspaces": ["libs/*"]}"#),
+            vec!["libs/*"]
+        );
+        assert!(package_json_workspaces(r#"{"name": "solo"}"#).is_empty());
+    }
+
+    #[test]
+    fn reads_go_work_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "go.work",
+            "go 1.22\n\nuse (\n\t./services/api // the API\n\t./tools\n)\nuse ./lib\n",
+        );
+        write(
+            root,
+            "services/api/go.mod",
+            "module example.com/acme/api/v2\n",
+        );
+        write(root, "tools/go.mod", "module example.com/acme/tools\n");
+        write(root, "lib/go.mod", "module \"example.com/acme/lib\"\n");
+
+        let names: Vec<String> = detect_packages(root).into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["api", "tools", "lib"]);
+    }
+
+    #[test]
+    fn skips_root_package_and_missing_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\n[workspace]\nmembers = [\".\", \"missing\"]\n",
+        );
+        assert!(detect_packages(root).is_empty());
+    }
+
+    #[test]
+    fn maps_files_to_the_innermost_package() {
+        let packages = vec![
+            WorkspacePackage {
+                name: "outer".to_string(),
+                dir: PathBuf::from("packages/outer"),
+                kind: WorkspaceKind::Node,
+            },
+            WorkspacePackage {
+                name: "inner".to_string(),
+                dir: PathBuf::from("packages/outer/inner"),
+                kind: WorkspaceKind::Node,
+            },
+        ];
+        let find = |file: &str| package_for_file(Path::new(file), &packages).map(|p| &p.name);
+        assert_eq!(
+            find("packages/outer/inner/src/a.ts").map(String::as_str),
+            Some("inner")
+        );
+        assert_eq!(
+            find("packages/outer/index.ts").map(String::as_str),
+            Some("outer")
+        );
+        assert_eq!(find("README.md"), None);
+    }
+}
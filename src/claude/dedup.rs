@@ -0,0 +1,275 @@
+//! Reuse of AI answers for content that was already sent.
+//!
+//! A run can send the same content twice. A range may hold commits with
+//! identical diffs — a fix cherry-picked between branches and merged back,
+//! or the same change landed twice — and a repeated request (an interactive
+//! retry, the split-retry of a failed batch) can rebuild a prompt that was
+//! already answered. [`Answered`] remembers, for the life of a
+//! [`ClaudeClient`](super::client::ClaudeClient), the amendment written for
+//! each diff and each response that parsed, keyed by content hash, so the
+//! duplicate costs no request.
+//!
+//! Only responses that parsed are remembered: a retry after a malformed
+//! answer still goes to the AI.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::data::amendments::Amendment;
+use crate::data::RepositoryView;
+use crate::git::CommitInfo;
+use crate::utils::digest::hex_encode;
+
+/// Answers already received, by content hash.
+#[derive(Debug, Default)]
+pub(crate) struct Answered {
+    /// Response text by prompt digest.
+    responses: Mutex<HashMap<String, String>>,
+    /// Amendment by diff digest.
+    amendments: Mutex<HashMap<String, Amendment>>,
+}
+
+/// A repository view with its repeated diffs taken out.
+#[derive(Debug)]
+pub(crate) struct Deduplicated {
+    /// The commits still to send.
+    pub(crate) view: RepositoryView,
+    /// Hash and diff digest of each commit in `view` that has a digest.
+    sent: Vec<(String, String)>,
+    /// Hash and diff digest of each commit left out.
+    repeats: Vec<(String, String)>,
+}
+
+impl Answered {
+    /// Returns the remembered response to this exact prompt, if any.
+    pub(crate) fn response(&self, system_prompt: &str, user_prompt: &str) -> Option<String> {
+        let response = lock(&self.responses)
+            .get(&prompt_digest(system_prompt, user_prompt))
+            .cloned();
+        if response.is_some() {
+            debug!("Reusing the response to an identical prompt");
+        }
+        response
+    }
+
+    /// Remembers `content` as the answer to this prompt, once it has parsed.
+    pub(crate) fn remember_response(&self, system_prompt: &str, user_prompt: &str, content: &str) {
+        lock(&self.responses).insert(
+            prompt_digest(system_prompt, user_prompt),
+            content.to_string(),
+        );
+    }
+
+    /// Takes out of `repo_view` each commit whose diff was already answered,
+    /// or repeats the diff of an earlier commit in the view.
+    ///
+    /// Unless `fresh`, the original message is part of the request, so it
+    /// must match too.
+    pub(crate) fn deduplicate(&self, mut repo_view: RepositoryView, fresh: bool) -> Deduplicated {
+        let known = lock(&self.amendments);
+        let mut seen = HashSet::new();
+        let mut sent = Vec::new();
+        let mut repeats = Vec::new();
+        repo_view.commits.retain(|commit| {
+            let Some(digest) = diff_digest(commit, fresh) else {
+                return true;
+            };
+            if known.contains_key(&digest) || !seen.insert(digest.clone()) {
+                debug!(commit = %commit.hash, "Reusing the amendment of an identical diff");
+                repeats.push((commit.hash.clone(), digest));
+                false
+            } else {
+                sent.push((commit.hash.clone(), digest));
+                true
+            }
+        });
+        Deduplicated {
+            view: repo_view,
+            sent,
+            repeats,
+        }
+    }
+
+    /// Remembers the amendments received for the commits of `dedup`.
+    pub(crate) fn remember_amendments(&self, dedup: &Deduplicated, amendments: &[Amendment]) {
+        let mut known = lock(&self.amendments);
+        for amendment in amendments {
+            if let Some((_, digest)) = dedup
+                .sent
+                .iter()
+                .find(|(hash, _)| *hash == amendment.commit)
+            {
+                known.insert(digest.clone(), amendment.clone());
+            }
+        }
+    }
+
+    /// Returns the amendments of the commits left out of `dedup`, copied
+    /// from the commits with the same diff. A commit whose twin got no
+    /// amendment gets none either.
+    pub(crate) fn repeated_amendments(&self, dedup: &Deduplicated) -> Vec<Amendment> {
+        let known = lock(&self.amendments);
+        dedup
+            .repeats
+            .iter()
+            .filter_map(|(hash, digest)| {
+                known.get(digest).map(|twin| Amendment {
+                    commit: hash.clone(),
+                    ..twin.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn prompt_digest(system_prompt: &str, user_prompt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(system_prompt.as_bytes());
+    hasher.update([0]);
+    hasher.update(user_prompt.as_bytes());
+    hex_encode(&hasher.finalize())
+}
+
+/// Digest of the content a commit's amendment is written from: its diff,
+/// less the `index` lines that name blobs, and its message unless `fresh`.
+/// `None` when the diff cannot be read or changes nothing, since commits
+/// with empty diffs have nothing in common to share.
+fn diff_digest(commit: &CommitInfo, fresh: bool) -> Option<String> {
+    let diff = std::fs::read_to_string(&commit.analysis.diff_file).ok()?;
+    if diff.trim().is_empty() {
+        return None;
+    }
+    let mut hasher = Sha256::new();
+    for line in diff.lines().filter(|line| !line.starts_with("index ")) {
+        hasher.update(line.as_bytes());
+        hasher.update([b'\n']);
+    }
+    if !fresh {
+        hasher.update([0]);
+        hasher.update(commit.original_message.as_bytes());
+    }
+    Some(hex_encode(&hasher.finalize()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::data::{AiInfo, FieldExplanation, WorkingDirectoryInfo};
+    use crate::test_support::commits;
+
+    fn commit(dir: &std::path::Path, hash: char, diff: &str) -> CommitInfo {
+        let diff_file = dir.join(format!("{hash}.diff"));
+        std::fs::write(&diff_file, diff).unwrap();
+        let mut commit = commits::commit(&hash.to_string().repeat(40), &format!("wip {hash}"), &[]);
+        commit.analysis.diff_file = diff_file.to_string_lossy().to_string();
+        commit
+    }
+
+    fn view(commits: Vec<CommitInfo>) -> RepositoryView {
+        RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
+            versions: None,
+            explanation: FieldExplanation::default(),
+            working_directory: WorkingDirectoryInfo {
+                clean: true,
+                untracked_changes: Vec::new(),
+            },
+            remotes: Vec::new(),
+            ai: AiInfo {
+                scratch: String::new(),
+            },
+            branch_info: None,
+            pr_template: None,
+            pr_template_location: None,
+            branch_prs: None,
+            commits,
+        }
+    }
+
+    fn amendment(hash: char) -> Amendment {
+        Amendment {
+            commit: hash.to_string().repeat(40),
+            message: "fix: handle the empty case".to_string(),
+            summary: "Handles the empty case".to_string(),
+        }
+    }
+
+    #[test]
+    fn identical_diffs_are_sent_once_and_share_the_amendment() {
+        let dir = tempfile::tempdir().unwrap();
+        let fix = "diff --git a/lib.rs b/lib.rs\nindex 111..222\n+fix\n";
+        let picked = "diff --git a/lib.rs b/lib.rs\nindex 333..444\n+fix\n";
+        let answered = Answered::default();
+
+        let dedup = answered.deduplicate(
+            view(vec![
+                commit(dir.path(), 'a', fix),
+                commit(dir.path(), 'b', "+other\n"),
+                commit(dir.path(), 'c', picked),
+            ]),
+            true,
+        );
+        let sent: Vec<_> = dedup.view.commits.iter().map(|c| &c.hash[..1]).collect();
+        assert_eq!(sent, ["a", "b"]);
+
+        answered.remember_amendments(&dedup, &[amendment('a'), amendment('b')]);
+        let repeated = answered.repeated_amendments(&dedup);
+        assert_eq!(repeated.len(), 1);
+        assert_eq!(repeated[0].commit, "c".repeat(40));
+        assert_eq!(repeated[0].message, "fix: handle the empty case");
+
+        // A later request repeating the diff is answered without sending it.
+        let later = answered.deduplicate(view(vec![commit(dir.path(), 'd', fix)]), true);
+        assert!(later.view.commits.is_empty());
+        assert_eq!(
+            answered.repeated_amendments(&later)[0].commit,
+            "d".repeat(40)
+        );
+    }
+
+    #[test]
+    fn messages_count_unless_fresh_and_empty_diffs_never_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let answered = Answered::default();
+        let commits = || {
+            vec![
+                commit(dir.path(), 'a', "+same\n"),
+                commit(dir.path(), 'b', "+same\n"),
+                commit(dir.path(), 'c', ""),
+                commit(dir.path(), 'd', ""),
+            ]
+        };
+        assert_eq!(
+            answered
+                .deduplicate(view(commits()), true)
+                .view
+                .commits
+                .len(),
+            3
+        );
+        assert_eq!(
+            answered
+                .deduplicate(view(commits()), false)
+                .view
+                .commits
+                .len(),
+            4
+        );
+
+        assert!(answered.response("system", "user").is_none());
+        answered.remember_response("system", "user", "amendments: []");
+        assert_eq!(
+            answered.response("system", "user").as_deref(),
+            Some("amendments: []")
+        );
+        assert!(answered.response("system", "user 2").is_none());
+    }
+}
@@ -126,6 +126,25 @@ pub fn is_transient_ai_error(error: &anyhow::Error) -> bool {
         .map_or(true, ClaudeError::is_transient)
 }
 
+/// Reports whether an AI error says the provider is shedding load: a 429, or
+/// the 529 Anthropic returns when it is overloaded.
+///
+/// Callers use this to slow down rather than to decide whether to retry; see
+/// [`is_transient_ai_error`] for that.
+#[must_use]
+pub fn is_rate_limit_ai_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<ClaudeError>(),
+        Some(
+            ClaudeError::RateLimitExceeded
+                | ClaudeError::ApiHttpError {
+                    status: 429 | 529,
+                    ..
+                }
+        )
+    )
+}
+
 // Note: anyhow already has a blanket impl for thiserror::Error types
 
 #[cfg(test)]
@@ -159,6 +178,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rate_limit_errors_are_recognised() {
+        assert!(is_rate_limit_ai_error(
+            &ClaudeError::RateLimitExceeded.into()
+        ));
+        assert!(is_rate_limit_ai_error(&http(429).into()));
+        assert!(is_rate_limit_ai_error(&http(529).into()));
+        assert!(!is_rate_limit_ai_error(&http(500).into()));
+        assert!(!is_rate_limit_ai_error(&anyhow::anyhow!("rate limit")));
+    }
+
     #[test]
     fn unclassified_errors_default_to_transient() {
         assert!(ClaudeError::RateLimitExceeded.is_transient());
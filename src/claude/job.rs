@@ -0,0 +1,266 @@
+//! Saved progress of a map phase, so an interrupted run can resume.
+//!
+//! A `twiddle` over a long range sends many batched AI requests before it
+//! asks anything of the user. A crash, a lost connection, or Ctrl-C in that
+//! window used to throw away every answer already paid for. A [`Job`]
+//! records the batch plan when the map phase starts and each commit's
+//! amendment or failure as its batch finishes; `--resume-job <ID>` reloads
+//! the plan and sends only the commits that have no amendment yet.
+//!
+//! Jobs live in `<git dir>/omni-dev/jobs/<id>.json`, out of the working tree
+//! and private to the clone. A job is removed once its run reaches the end,
+//! and refuses to resume against a range whose commits have changed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::data::amendments::Amendment;
+use crate::utils::json_store;
+
+/// Directory of job files inside the repository's git directory.
+const JOBS_DIR: &str = "omni-dev/jobs";
+
+/// What a job file holds.
+#[derive(Debug, Serialize, Deserialize)]
+struct JobState {
+    /// Command that started the job.
+    command: String,
+    /// Hashes of the commits in the run, in view order.
+    commits: Vec<String>,
+    /// The batch plan, as indices into `commits`.
+    batches: Vec<Vec<usize>>,
+    /// Amendments received so far, by commit hash.
+    completed: BTreeMap<String, Amendment>,
+    /// Last error of each commit that failed, by commit hash. Failed commits
+    /// are sent again on resume.
+    failed: BTreeMap<String, String>,
+}
+
+/// A map phase whose progress is saved to disk as it goes.
+#[derive(Debug)]
+pub(crate) struct Job {
+    id: String,
+    path: PathBuf,
+    state: Mutex<JobState>,
+}
+
+impl Job {
+    /// Starts a job for `command` over `commits`, batched as `batches`, in
+    /// the repository at `repo_root`, and saves it.
+    pub(crate) fn start(
+        repo_root: &Path,
+        command: &str,
+        commits: Vec<String>,
+        batches: Vec<Vec<usize>>,
+    ) -> Result<Self> {
+        let id = format!(
+            "{}-{:04x}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S"),
+            rand::random::<u16>()
+        );
+        let job = Self {
+            path: jobs_dir(repo_root)?.join(format!("{id}.json")),
+            id,
+            state: Mutex::new(JobState {
+                command: command.to_string(),
+                commits,
+                batches,
+                completed: BTreeMap::new(),
+                failed: BTreeMap::new(),
+            }),
+        };
+        job.try_save()
+            .with_context(|| format!("Failed to save job {}", job.id))?;
+        Ok(job)
+    }
+
+    /// Reopens job `id` of `command` in the repository at `repo_root`.
+    /// Fails unless the job covers exactly `commits`.
+    pub(crate) fn resume(
+        repo_root: &Path,
+        id: &str,
+        command: &str,
+        commits: &[String],
+    ) -> Result<Self> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            bail!("Invalid job ID: {id}");
+        }
+        let path = jobs_dir(repo_root)?.join(format!("{id}.json"));
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("No job {id} to resume (looked for {})", path.display()))?;
+        let state: JobState = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse job file: {}", path.display()))?;
+        if state.command != command {
+            bail!("Job {id} belongs to `{}`, not `{command}`", state.command);
+        }
+        if state.commits != commits {
+            bail!(
+                "Job {id} was started on a different set of commits; \
+                 rerun with the same range, or without --resume-job"
+            );
+        }
+        Ok(Self {
+            id: id.to_string(),
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Returns the job's ID, for `--resume-job`.
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the number of batches in the plan.
+    pub(crate) fn batch_count(&self) -> usize {
+        self.state().batches.len()
+    }
+
+    /// Returns the batches still to send: each planned batch narrowed to the
+    /// commits without an amendment, empty ones dropped.
+    pub(crate) fn pending_batches(&self) -> Vec<Vec<usize>> {
+        let state = self.state();
+        state
+            .batches
+            .iter()
+            .map(|batch| {
+                batch
+                    .iter()
+                    .copied()
+                    .filter(|&i| !state.completed.contains_key(&state.commits[i]))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|batch| !batch.is_empty())
+            .collect()
+    }
+
+    /// Returns the amendments already received, in commit order.
+    pub(crate) fn completed(&self) -> Vec<Amendment> {
+        let state = self.state();
+        state
+            .commits
+            .iter()
+            .filter_map(|hash| state.completed.get(hash).cloned())
+            .collect()
+    }
+
+    /// Records received amendments and saves the job.
+    pub(crate) fn record_amendments<'a>(
+        &self,
+        amendments: impl IntoIterator<Item = &'a Amendment>,
+    ) {
+        let mut state = self.state();
+        for amendment in amendments {
+            state.failed.remove(&amendment.commit);
+            state
+                .completed
+                .insert(amendment.commit.clone(), amendment.clone());
+        }
+        self.save(&state);
+    }
+
+    /// Records that the commit `hash` failed with `error` and saves the job.
+    pub(crate) fn record_failure(&self, hash: &str, error: &anyhow::Error) {
+        let mut state = self.state();
+        state.failed.insert(hash.to_string(), format!("{error:#}"));
+        self.save(&state);
+    }
+
+    /// Deletes the job file once its run has finished.
+    pub(crate) fn discard(self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::debug!("Failed to remove job file {}: {e}", self.path.display());
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, JobState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Saves `state`. Best effort: a failed save costs only resumability,
+    /// so it is reported and the run carries on.
+    fn save(&self, state: &JobState) {
+        if let Err(e) = write_state(&self.path, state) {
+            tracing::warn!("Failed to save job {}: {e:#}", self.id);
+        }
+    }
+
+    fn try_save(&self) -> Result<()> {
+        write_state(&self.path, &self.state())
+    }
+}
+
+/// Returns the jobs directory of the repository at `repo_root`.
+fn jobs_dir(repo_root: &Path) -> Result<PathBuf> {
+    let repo = git2::Repository::discover(repo_root).context("Failed to open git repository")?;
+    Ok(repo.path().join(JOBS_DIR))
+}
+
+fn write_state(path: &Path, state: &JobState) -> Result<()> {
+    json_store::write_atomic(path, &serde_json::to_string_pretty(state)?)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn hashes(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("{i:040x}")).collect()
+    }
+
+    fn amendment(hash: &str) -> Amendment {
+        Amendment {
+            commit: hash.to_string(),
+            message: "fix: something".to_string(),
+            summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn resume_sends_only_commits_without_an_amendment() {
+        let dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        let commits = hashes(5);
+        let job = Job::start(
+            dir.path(),
+            "twiddle",
+            commits.clone(),
+            vec![vec![0, 1], vec![2, 3], vec![4]],
+        )
+        .unwrap();
+        job.record_amendments(&[amendment(&commits[0]), amendment(&commits[1])]);
+        job.record_amendments(&[amendment(&commits[2])]);
+        job.record_failure(&commits[4], &anyhow::anyhow!("rate limited"));
+        let id = job.id().to_string();
+        drop(job);
+
+        let resumed = Job::resume(dir.path(), &id, "twiddle", &commits).unwrap();
+        assert_eq!(resumed.batch_count(), 3);
+        assert_eq!(resumed.pending_batches(), vec![vec![3], vec![4]]);
+        let completed: Vec<_> = resumed.completed().into_iter().map(|a| a.commit).collect();
+        assert_eq!(completed, commits[..3]);
+
+        resumed.discard();
+        assert!(Job::resume(dir.path(), &id, "twiddle", &commits).is_err());
+    }
+
+    #[test]
+    fn resume_refuses_a_changed_range_or_a_bad_id() {
+        let dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        let job = Job::start(dir.path(), "twiddle", hashes(2), vec![vec![0, 1]]).unwrap();
+
+        let err = Job::resume(dir.path(), job.id(), "twiddle", &hashes(3)).unwrap_err();
+        assert!(
+            err.to_string().contains("different set of commits"),
+            "{err}"
+        );
+        assert!(Job::resume(dir.path(), job.id(), "check", &hashes(2)).is_err());
+        assert!(Job::resume(dir.path(), "../../config", "twiddle", &hashes(2)).is_err());
+    }
+}
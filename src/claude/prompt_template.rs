@@ -0,0 +1,377 @@
+//! Project overrides for the built-in system prompts.
+//!
+//! A project replaces a system prompt by adding a template to the `prompts/`
+//! directory of its context directory, e.g.
+//! `.omni-dev/prompts/twiddle-system.md.hbs` (resolved like the other context
+//! files, so `local/`, team, and global copies work too). Templates use a
+//! small Handlebars subset:
+//!
+//! - `{{name}}` (or `{{{name}}}`) inserts a variable; nothing is escaped.
+//! - `{{#if name}}…{{else}}…{{/if}}` renders a branch depending on whether
+//!   the variable is non-empty. Blocks nest.
+//! - `{{! comment }}` and `{{!-- comment --}}` are dropped.
+//!
+//! The variables are listed in [`VARIABLES`]. `{{default_prompt}}` is the
+//! prompt omni-dev would otherwise send, so a template can wrap it rather
+//! than start from scratch.
+
+use anyhow::{bail, Result};
+
+/// Directory within the context directory holding prompt templates.
+pub const PROMPTS_DIR: &str = "prompts";
+
+/// File extension of prompt templates.
+pub const TEMPLATE_EXTENSION: &str = "md.hbs";
+
+/// Variables available to every template, with their descriptions.
+pub const VARIABLES: &[(&str, &str)] = &[
+    (
+        "default_prompt",
+        "The complete built-in prompt, including all project context",
+    ),
+    (
+        "base_instructions",
+        "The built-in instructions without any project context",
+    ),
+    (
+        "provider",
+        "Prompt style of the model: `claude` or `openai`",
+    ),
+    ("commit_guidelines", "Contents of commit-guidelines.md"),
+    ("pr_guidelines", "Contents of pr-guidelines.md"),
+    ("scopes", "Valid scopes, one `- name: description` per line"),
+    (
+        "commit_types",
+        "Project commit types section, if types.yaml exists",
+    ),
+    ("branch", "Branch description, on feature branches"),
+    (
+        "work_type",
+        "Work type inferred from the branch (e.g. `bug fix`), on feature branches",
+    ),
+    (
+        "ticket",
+        "Ticket context section, when a ticket was fetched",
+    ),
+    (
+        "review_feedback",
+        "Review feedback section, when the branch has a PR",
+    ),
+    ("project_patterns", "Matched project work patterns section"),
+    (
+        "commit_examples",
+        "Example commit messages section, from examples.md or history",
+    ),
+];
+
+/// A system prompt that can be overridden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptTemplate {
+    /// System prompt for `twiddle` commit message amendments.
+    TwiddleSystem,
+    /// System prompt for PR descriptions generated from the diff.
+    PrSystem,
+    /// System prompt for PR descriptions generated with `--from-commits`.
+    PrFromCommitsSystem,
+}
+
+impl PromptTemplate {
+    /// Every overridable prompt.
+    pub const ALL: &'static [Self] = &[
+        Self::TwiddleSystem,
+        Self::PrSystem,
+        Self::PrFromCommitsSystem,
+    ];
+
+    /// Template name, used as the file stem.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::TwiddleSystem => "twiddle-system",
+            Self::PrSystem => "pr-system",
+            Self::PrFromCommitsSystem => "pr-from-commits-system",
+        }
+    }
+
+    /// Template path relative to the context directory.
+    pub fn relative_path(self) -> String {
+        format!("{PROMPTS_DIR}/{}.{TEMPLATE_EXTENSION}", self.name())
+    }
+}
+
+/// One piece of a parsed template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Variable(String),
+    If {
+        name: String,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// Tag that ended a run of nodes.
+enum Terminator {
+    Else,
+    EndIf,
+    Eof,
+}
+
+/// Which variable names a template may use.
+#[derive(Debug, Clone, Copy)]
+enum Names {
+    /// Only those in [`VARIABLES`].
+    Known,
+    /// Any name, for templates whose variables their author defines.
+    Open,
+}
+
+/// Checks that `template` parses and uses only known variables.
+pub fn validate(template: &str) -> Result<()> {
+    parse(template, Names::Known).map(|_| ())
+}
+
+/// Renders `template`, looking variables up with `lookup`. A variable the
+/// lookup does not know renders as empty.
+pub fn render<'a>(template: &str, lookup: impl Fn(&str) -> Option<&'a str>) -> Result<String> {
+    Ok(render_parsed(
+        &parse(template, Names::Known)?,
+        template.len(),
+        &lookup,
+    ))
+}
+
+/// Renders `template` like [`render`], but accepts any variable name rather
+/// than only those in [`VARIABLES`].
+pub fn render_open<'a>(template: &str, lookup: impl Fn(&str) -> Option<&'a str>) -> Result<String> {
+    Ok(render_parsed(
+        &parse(template, Names::Open)?,
+        template.len(),
+        &lookup,
+    ))
+}
+
+/// Names of the variables `template` inserts or tests, in order of first
+/// use, accepting any name.
+pub fn variables(template: &str) -> Result<Vec<String>> {
+    fn collect(nodes: &[Node], names: &mut Vec<String>) {
+        for node in nodes {
+            match node {
+                Node::Text(_) => {}
+                Node::Variable(name) => push_unique(names, name),
+                Node::If {
+                    name,
+                    then,
+                    otherwise,
+                } => {
+                    push_unique(names, name);
+                    collect(then, names);
+                    collect(otherwise, names);
+                }
+            }
+        }
+    }
+    fn push_unique(names: &mut Vec<String>, name: &str) {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    let mut names = Vec::new();
+    collect(&parse(template, Names::Open)?, &mut names);
+    Ok(names)
+}
+
+fn render_parsed<'a>(
+    nodes: &[Node],
+    capacity: usize,
+    lookup: &impl Fn(&str) -> Option<&'a str>,
+) -> String {
+    let mut out = String::with_capacity(capacity);
+    render_nodes(nodes, lookup, &mut out);
+    out
+}
+
+fn render_nodes<'a>(nodes: &[Node], lookup: &impl Fn(&str) -> Option<&'a str>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Variable(name) => out.push_str(lookup(name).unwrap_or_default()),
+            Node::If {
+                name,
+                then,
+                otherwise,
+            } => {
+                let set = lookup(name).is_some_and(|value| !value.trim().is_empty());
+                render_nodes(if set { then } else { otherwise }, lookup, out);
+            }
+        }
+    }
+}
+
+fn parse(template: &str, names: Names) -> Result<Vec<Node>> {
+    let mut rest = template;
+    let (nodes, terminator) = parse_nodes(&mut rest, names)?;
+    match terminator {
+        Terminator::Eof => Ok(nodes),
+        Terminator::Else => bail!("{{{{else}}}} outside an {{{{#if}}}} block"),
+        Terminator::EndIf => bail!("{{{{/if}}}} without a matching {{{{#if}}}}"),
+    }
+}
+
+/// Parses nodes from `rest` up to the next `{{else}}`, `{{/if}}`, or the end.
+fn parse_nodes(rest: &mut &str, names: Names) -> Result<(Vec<Node>, Terminator)> {
+    let mut nodes = Vec::new();
+    loop {
+        let Some(start) = rest.find("{{") else {
+            if !rest.is_empty() {
+                nodes.push(Node::Text((*rest).to_string()));
+            }
+            *rest = "";
+            return Ok((nodes, Terminator::Eof));
+        };
+        if start > 0 {
+            nodes.push(Node::Text(rest[..start].to_string()));
+        }
+        let tag_source = &rest[start..];
+        let (tag, consumed) = if tag_source.starts_with("{{!--") {
+            match tag_source.find("--}}") {
+                Some(end) => ("!", end + 4),
+                None => bail!("Unclosed comment"),
+            }
+        } else if tag_source.starts_with("{{{") {
+            match tag_source.find("}}}") {
+                Some(end) => (&tag_source[3..end], end + 3),
+                None => bail!("Unclosed tag starting '{}'", first_line(tag_source)),
+            }
+        } else {
+            match tag_source.find("}}") {
+                Some(end) => (&tag_source[2..end], end + 2),
+                None => bail!("Unclosed tag starting '{}'", first_line(tag_source)),
+            }
+        };
+        *rest = &tag_source[consumed..];
+
+        let tag = tag.trim();
+        if tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix("#if") {
+            let name = variable_name(name.trim(), names)?;
+            let (then, terminator) = parse_nodes(rest, names)?;
+            let otherwise = match terminator {
+                Terminator::EndIf => Vec::new(),
+                Terminator::Else => match parse_nodes(rest, names)? {
+                    (otherwise, Terminator::EndIf) => otherwise,
+                    _ => bail!("{{{{#if {name}}}}} is missing its {{{{/if}}}}"),
+                },
+                Terminator::Eof => bail!("{{{{#if {name}}}}} is missing its {{{{/if}}}}"),
+            };
+            nodes.push(Node::If {
+                name,
+                then,
+                otherwise,
+            });
+        } else if tag == "else" {
+            return Ok((nodes, Terminator::Else));
+        } else if tag == "/if" {
+            return Ok((nodes, Terminator::EndIf));
+        } else if tag.starts_with('#') || tag.starts_with('/') {
+            bail!("Unsupported block '{{{{{tag}}}}}' (only #if is supported)");
+        } else {
+            nodes.push(Node::Variable(variable_name(tag, names)?));
+        }
+    }
+}
+
+/// Checks `name` against [`VARIABLES`], or with [`Names::Open`] only that it
+/// is an identifier.
+fn variable_name(name: &str, names: Names) -> Result<String> {
+    if let Names::Open = names {
+        if !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Ok(name.to_string());
+        }
+        bail!("Invalid template variable name '{name}'");
+    }
+    if VARIABLES.iter().any(|(known, _)| *known == name) {
+        return Ok(name.to_string());
+    }
+    let known: Vec<&str> = VARIABLES.iter().map(|(known, _)| *known).collect();
+    bail!(
+        "Unknown template variable '{name}' (expected one of: {})",
+        known.join(", ")
+    )
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<&'static str> {
+        match name {
+            "default_prompt" => Some("BUILT-IN"),
+            "branch" => Some("add login"),
+            "ticket" => Some(""),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn renders_variables_conditionals_and_comments() {
+        let template = "{{! team prompt }}Prefix\n{{{default_prompt}}}\n\
+                        {{#if branch}}Branch: {{ branch }}{{else}}No branch{{/if}}\n\
+                        {{#if ticket}}Ticket{{else}}{{#if branch}}nested{{/if}}{{/if}}\
+                        {{!-- {{ignored}} --}}";
+        assert_eq!(
+            render(template, lookup).unwrap(),
+            "Prefix\nBUILT-IN\nBranch: add login\nnested"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_variables_and_unbalanced_blocks() {
+        let err = validate("{{defualt_prompt}}").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown template variable 'defualt_prompt'"));
+        assert!(validate("{{#if branch}}open").is_err());
+        assert!(validate("{{/if}}").is_err());
+        assert!(validate("{{#each scopes}}{{/each}}").is_err());
+        assert!(validate("{{branch").is_err());
+        assert!(validate("plain text").is_ok());
+    }
+
+    #[test]
+    fn open_templates_accept_any_variable_name() {
+        let template = "{{#if ticket}}[{{ticket}}] {{/if}}{{summary}} on {{branch}}";
+        assert_eq!(
+            variables(template).unwrap(),
+            ["ticket", "summary", "branch"]
+        );
+        let rendered = render_open(template, |name| match name {
+            "summary" => Some("tidy up"),
+            "branch" => Some("main"),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(rendered, "tidy up on main");
+        assert!(render_open("{{two words}}", |_| None).is_err());
+    }
+
+    #[test]
+    fn template_paths() {
+        assert_eq!(
+            PromptTemplate::TwiddleSystem.relative_path(),
+            "prompts/twiddle-system.md.hbs"
+        );
+        assert_eq!(PromptTemplate::ALL.len(), 3);
+    }
+}
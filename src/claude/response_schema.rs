@@ -34,9 +34,18 @@ fn schema_value<T: JsonSchema>(slot: &'static OnceLock<Value>) -> &'static Value
 }
 
 /// JSON Schema for [`AmendmentFile`] responses (twiddle, check-with-suggestions).
+///
+/// Leaves out `schema_version`: the model never writes it (loading fills it
+/// in), and the strict subset would otherwise force every response to.
 pub fn amendment_file_schema() -> &'static Value {
     static SLOT: OnceLock<Value> = OnceLock::new();
-    schema_value::<AmendmentFile>(&SLOT)
+    SLOT.get_or_init(|| {
+        let mut schema = serde_json::to_value(schema_for!(AmendmentFile)).unwrap_or(Value::Null);
+        if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+            properties.remove("schema_version");
+        }
+        schema
+    })
 }
 
 /// JSON Schema for [`PrContent`] responses (PR title + description).
@@ -44,6 +44,14 @@ pub(crate) fn estimate_tokens_from_char_count(char_count: usize) -> usize {
     (raw_estimate * SAFETY_MARGIN).ceil() as usize
 }
 
+/// Returns the most bytes of text whose estimate stays within `tokens`.
+///
+/// Inverse of [`estimate_tokens_from_char_count`], rounding down.
+#[must_use]
+pub(crate) fn max_chars_for_tokens(tokens: usize) -> usize {
+    (tokens as f64 / SAFETY_MARGIN * CHARS_PER_TOKEN).floor() as usize
+}
+
 /// Result of a token budget validation.
 #[derive(Debug, Clone)]
 pub(crate) struct TokenEstimate {
@@ -220,4 +228,13 @@ mod tests {
     fn estimate_tokens_from_char_count_zero() {
         assert_eq!(estimate_tokens_from_char_count(0), 0);
     }
+
+    #[test]
+    fn max_chars_for_tokens_stays_within_estimate() {
+        for tokens in [0, 1, 7, 100, 4096] {
+            let chars = max_chars_for_tokens(tokens);
+            assert!(estimate_tokens_from_char_count(chars) <= tokens);
+            assert!(estimate_tokens_from_char_count(chars + 3) > tokens);
+        }
+    }
 }
@@ -5,10 +5,13 @@ use clap::{Parser, Subcommand};
 
 pub mod ai;
 pub mod atlassian;
+pub mod bench;
 pub mod browser;
+pub mod check;
 pub mod commands;
 pub mod completions;
 pub mod config;
+pub mod context;
 pub mod coverage;
 // The daemon and the Snowflake client (which talks to the daemon over its
 // Unix-domain control socket) are Unix-only; on Windows they run only under WSL2,
@@ -16,11 +19,19 @@ pub mod coverage;
 #[cfg(unix)]
 pub mod daemon;
 pub mod datadog;
+pub mod explain;
 pub mod format;
 pub mod git;
 pub mod help;
+pub mod init;
 pub mod log;
+// `omni-dev mcp serve` links the MCP server, so it exists only in `mcp` builds.
+#[cfg(feature = "mcp")]
+pub mod mcp;
+pub mod repo;
 pub mod resources;
+pub mod schema;
+pub mod serve;
 #[cfg(unix)]
 pub mod sessions;
 #[cfg(unix)]
@@ -38,7 +49,8 @@ pub use crate::claude::backend::AiBackend;
 ///
 /// Global flags (`--ai-backend`, `--model`, `--beta-header`,
 /// `--claude-cli-allow-tools`, `--claude-cli-allow-mcp`,
-/// `--claude-cli-max-budget-usd`, `--models-yaml`) are propagated to
+/// `--claude-cli-max-budget-usd`, `--models-yaml`) and the top-level
+/// `--output` are propagated to
 /// environment variables read by downstream factories before dispatching to a
 /// [`Commands`] variant.
 #[derive(Parser)]
@@ -155,6 +167,69 @@ pub struct Cli {
     #[arg(long = "repo", short = 'C', global = true, value_name = "PATH")]
     pub repo: Option<std::path::PathBuf>,
 
+    /// Strips emoji and ANSI color codes from all output, for CI logs.
+    ///
+    /// Status glyphs become `[ok]`, `[error]`, and `[warn]`; other emoji are
+    /// dropped. Equivalent to setting `OMNI_DEV_PLAIN=true`. `NO_COLOR` strips
+    /// only the color codes. Applied by the `omni-dev` binary before dispatch.
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    /// Output format for commands that produce a report: `git commit message
+    /// view` and `check`, `git branch info`, the `twiddle` summary, and
+    /// `git release notes`.
+    ///
+    /// Given before the subcommand (`omni-dev --output json git ...`); a
+    /// command's own `-o/--output` still wins. Each command keeps its usual
+    /// default when neither is given. Equivalent to setting
+    /// `OMNI_DEV_OUTPUT`.
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub output: Option<crate::data::RenderFormat>,
+
+    /// Diagnostics level: `error`, `warn`, `info`, `debug`, `trace`, or a
+    /// `RUST_LOG`-style filter such as `omni_dev::claude=debug`.
+    ///
+    /// Overrides `RUST_LOG`. Diagnostics go to stderr (or `--log-file`) and
+    /// never mix with command output on stdout. Applied by the `omni-dev`
+    /// binary before dispatch.
+    #[arg(long, global = true, value_name = "LEVEL")]
+    pub log_level: Option<String>,
+
+    /// Appends diagnostics to this file instead of writing them to stderr.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<std::path::PathBuf>,
+
+    /// Format of diagnostics: human-readable lines, or one JSON object per
+    /// event.
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        default_value_t = crate::utils::logging::LogFormat::Text
+    )]
+    pub log_format: crate::utils::logging::LogFormat,
+
+    /// Prints long output (repository views, check reports, amendment
+    /// previews) directly instead of through a pager.
+    ///
+    /// Without it, such output goes through `OMNI_DEV_PAGER`, `PAGER`, or
+    /// `less -FRX` when stdout is a terminal. Equivalent to setting
+    /// `OMNI_DEV_NO_PAGER=true`.
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Never waits on a prompt: confirmations take their "yes" answer.
+    ///
+    /// Amendments and PRs are applied as with `--auto-apply`, retry prompts
+    /// for failed commits are skipped, and prompts that need real input
+    /// (`--tui`, editing, credential entry) fail instead of blocking.
+    /// Destructive actions (deletes, merges, releases, remote overwrites)
+    /// are refused unless the command's own `--force`/`--auto-apply` is
+    /// given. Equivalent to setting `OMNI_DEV_NON_INTERACTIVE=true`.
+    #[arg(long, visible_alias = "non-interactive", global = true)]
+    pub yes: bool,
+
     /// The main command to execute.
     #[command(subcommand)]
     pub command: Commands,
@@ -175,6 +250,16 @@ pub enum Commands {
     Commands(commands::CommandsCommand),
     /// Configuration and model information.
     Config(config::ConfigCommand),
+    /// Project context: learn commit conventions, diagnose guidance files, and share them as bundles.
+    Context(context::ContextCommand),
+    /// Sets up omni-dev in a repository: guidelines, scopes, and an optional pre-push hook.
+    Init(init::InitCommand),
+    /// Explains a repository to new contributors.
+    Explain(explain::ExplainCommand),
+    /// Repository policy checks that run without AI.
+    Check(check::CheckCommand),
+    /// Repository upkeep: flag committed artifacts and tidy the .gitignore.
+    Repo(repo::RepoCommand),
     /// Atlassian: JIRA and Confluence operations.
     Atlassian(atlassian::AtlassianCommand),
     /// Browser bridge: drive authenticated requests through a browser tab.
@@ -199,8 +284,17 @@ pub enum Commands {
     Transcript(transcript::TranscriptCommand),
     /// Search the local invocation + HTTP request log.
     Log(log::LogCommand),
+    /// MCP server: expose omni-dev's operations as tools to AI agents over stdio.
+    #[cfg(feature = "mcp")]
+    Mcp(mcp::McpCommand),
     /// Embedded reference resources (specs, etc.).
     Resources(resources::ResourcesCommand),
+    /// JSON Schemas of the versioned output formats.
+    Schema(schema::SchemaCommand),
+    /// Measures analysis, git backend reads, token estimation, and map-phase scheduling speed on this repository.
+    Bench(bench::BenchCommand),
+    /// HTTP API: view, check, generate, and apply amendments as JSON endpoints.
+    Serve(serve::ServeCommand),
     /// Generates shell completion scripts.
     #[command(hide = true)]
     Completions(completions::CompletionsCommand),
@@ -247,6 +341,18 @@ impl Cli {
             std::env::set_var("OMNI_DEV_CLAUDE_CLI_MAX_BUDGET_USD", format!("{budget}"));
         }
 
+        if let Some(output) = self.output {
+            std::env::set_var(crate::data::output::OUTPUT_ENV_VAR, output.as_str());
+        }
+
+        if self.no_pager {
+            std::env::set_var(crate::utils::pager::NO_PAGER_ENV_VAR, "true");
+        }
+
+        if self.yes {
+            std::env::set_var(crate::utils::interactive::NON_INTERACTIVE_ENV_VAR, "true");
+        }
+
         if let Some(path) = &self.models_yaml {
             std::env::set_var("OMNI_DEV_MODELS_YAML", path);
         }
@@ -317,7 +423,7 @@ impl Cli {
         match command {
             Commands::Ai(ai_cmd) => ai_cmd.execute().await,
             Commands::Git(git_cmd) => git_cmd.execute(repo).await,
-            Commands::Commands(commands_cmd) => commands_cmd.execute(),
+            Commands::Commands(commands_cmd) => commands_cmd.execute(repo).await,
             Commands::Atlassian(cmd) => cmd.execute().await,
             Commands::Browser(cmd) => cmd.execute().await,
             #[cfg(unix)]
@@ -332,8 +438,18 @@ impl Cli {
             Commands::Coverage(cmd) => cmd.execute(repo).await,
             Commands::Transcript(cmd) => cmd.execute().await,
             Commands::Log(log_cmd) => log_cmd.execute(),
+            #[cfg(feature = "mcp")]
+            Commands::Mcp(mcp_cmd) => mcp_cmd.execute().await,
             Commands::Config(config_cmd) => config_cmd.execute(),
+            Commands::Context(context_cmd) => context_cmd.execute(repo),
+            Commands::Init(init_cmd) => init_cmd.execute(repo),
+            Commands::Explain(explain_cmd) => explain_cmd.execute(repo).await,
+            Commands::Check(check_cmd) => check_cmd.execute(repo),
+            Commands::Repo(repo_cmd) => repo_cmd.execute(repo).await,
             Commands::Resources(resources_cmd) => resources_cmd.execute(),
+            Commands::Schema(schema_cmd) => schema_cmd.execute(),
+            Commands::Bench(bench_cmd) => bench_cmd.execute(repo).await,
+            Commands::Serve(serve_cmd) => serve_cmd.execute(repo).await,
             Commands::Completions(completions_cmd) => completions_cmd.execute(),
             Commands::HelpAll(help_cmd) => help_cmd.execute(),
         }
@@ -452,6 +568,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_context_doctor() {
+        let cli = Cli::try_parse_from(["omni-dev", "context", "doctor", "--model", "m"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Context(context::ContextCommand {
+                command: context::ContextSubcommands::Doctor(context::DoctorCommand {
+                    model: Some(ref model),
+                    ..
+                }),
+            }) if model == "m"
+        ));
+    }
+
+    #[test]
+    fn parses_init() {
+        let cli = Cli::try_parse_from(["omni-dev", "init", "-y", "--hooks"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Init(init::InitCommand {
+                yes: true,
+                hooks: true,
+                force: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_context_learn() {
+        let cli =
+            Cli::try_parse_from(["omni-dev", "context", "learn", "--limit", "200", "--force"])
+                .unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Context(context::ContextCommand {
+                command: context::ContextSubcommands::Learn(context::LearnCommand {
+                    limit: 200,
+                    force: true,
+                    print: false,
+                    ..
+                }),
+            })
+        ));
+    }
+
     #[test]
     fn parses_ai_backend_absent() {
         let cli = Cli::try_parse_from(["omni-dev", "help-all"]).unwrap();
@@ -559,12 +721,15 @@ mod tests {
     const MODELS_YAML_VAR: &str = "OMNI_DEV_MODELS_YAML";
     const PROFILE_VAR: &str = "OMNI_DEV_PROFILE";
     const INSTANCE_VAR: &str = "OMNI_DEV_ATLASSIAN_INSTANCE";
+    const OUTPUT_VAR: &str = "OMNI_DEV_OUTPUT";
+    const NO_PAGER_VAR: &str = "OMNI_DEV_NO_PAGER";
+    const NON_INTERACTIVE_VAR: &str = "OMNI_DEV_NON_INTERACTIVE";
 
     /// Locks the shared mutex and snapshots/restores every env var
     /// `propagate_global_flags` may touch.
     struct GlobalFlagsEnvGuard {
         _lock: std::sync::MutexGuard<'static, ()>,
-        saved: [(&'static str, Option<String>); 9],
+        saved: [(&'static str, Option<String>); 12],
     }
 
     impl GlobalFlagsEnvGuard {
@@ -582,6 +747,9 @@ mod tests {
                 MODELS_YAML_VAR,
                 PROFILE_VAR,
                 INSTANCE_VAR,
+                OUTPUT_VAR,
+                NO_PAGER_VAR,
+                NON_INTERACTIVE_VAR,
             ];
             let saved = names.map(|n| (n, std::env::var(n).ok()));
             for (n, _) in &saved {
@@ -619,6 +787,49 @@ mod tests {
         assert!(std::env::var(MODELS_YAML_VAR).is_err());
         assert!(std::env::var(PROFILE_VAR).is_err());
         assert!(std::env::var(INSTANCE_VAR).is_err());
+        assert!(std::env::var(OUTPUT_VAR).is_err());
+        assert!(std::env::var(NO_PAGER_VAR).is_err());
+        assert!(std::env::var(NON_INTERACTIVE_VAR).is_err());
+    }
+
+    #[test]
+    fn propagate_global_flags_sets_output() {
+        let _g = GlobalFlagsEnvGuard::new();
+        let cli = Cli::try_parse_from(["omni-dev", "--output", "json", "help-all"]).unwrap();
+        cli.propagate_global_flags();
+        assert_eq!(std::env::var(OUTPUT_VAR).ok().as_deref(), Some("json"));
+    }
+
+    #[test]
+    fn propagate_global_flags_sets_no_pager() {
+        let _g = GlobalFlagsEnvGuard::new();
+        let cli = Cli::try_parse_from(["omni-dev", "--no-pager", "help-all"]).unwrap();
+        cli.propagate_global_flags();
+        assert_eq!(std::env::var(NO_PAGER_VAR).ok().as_deref(), Some("true"));
+    }
+
+    #[test]
+    fn propagate_global_flags_sets_non_interactive() {
+        for flag in ["--yes", "--non-interactive"] {
+            let _g = GlobalFlagsEnvGuard::new();
+            let cli = Cli::try_parse_from(["omni-dev", flag, "help-all"]).unwrap();
+            cli.propagate_global_flags();
+            assert_eq!(
+                std::env::var(NON_INTERACTIVE_VAR).ok().as_deref(),
+                Some("true"),
+                "{flag}"
+            );
+        }
+    }
+
+    #[test]
+    fn global_yes_coexists_with_init_yes() {
+        let cli = Cli::try_parse_from(["omni-dev", "--yes", "init", "-y"]).unwrap();
+        assert!(cli.yes);
+        let Commands::Init(init) = cli.command else {
+            panic!("expected init");
+        };
+        assert!(init.yes);
     }
 
     #[test]
@@ -171,6 +171,7 @@ async fn run_auth_status(client: &AtlassianClient, instance_url: &str) -> Result
 
 /// Prompts the user for input on a single line.
 fn prompt(message: &str) -> Result<String> {
+    crate::utils::interactive::ensure_interactive("auth login")?;
     print!("{message}");
     io::stdout().flush().context("Failed to flush stdout")?;
 
@@ -24,7 +24,8 @@ pub struct GuardOptions<'a> {
     /// The preview message printed when `--dry-run` is set
     /// (e.g. "Would delete PROJ-123 (Fix login).").
     pub dry_run_message: &'a str,
-    /// Skip the interactive prompt.
+    /// Skip the interactive prompt. Non-interactive mode refuses the action
+    /// unless this is set.
     pub force: bool,
     /// Print `dry_run_message` and return without prompting or calling the API.
     pub dry_run: bool,
@@ -48,7 +49,7 @@ pub fn guard_destructive_with_io(
         return Ok(GuardOutcome::DryRun);
     }
 
-    if opts.force {
+    if crate::utils::interactive::destructive_preapproved(opts.force, "--force")? {
         return Ok(GuardOutcome::Proceed);
     }
 
@@ -204,7 +204,7 @@ pub async fn run_write(
     force: bool,
     api: &dyn AtlassianApi,
 ) -> Result<()> {
-    if !force {
+    if !crate::utils::interactive::destructive_preapproved(force, "--force")? {
         println!("About to update {id}:");
         if !title.is_empty() {
             println!("  Title: {title}");
@@ -243,7 +243,7 @@ pub async fn run_write_jira_with_resolved_fields(
     custom_fields: &std::collections::BTreeMap<String, serde_json::Value>,
     client: &AtlassianClient,
 ) -> Result<()> {
-    if !force {
+    if !crate::utils::interactive::destructive_preapproved(force, "--force")? {
         println!("About to update {key}:");
         if !title.is_empty() {
             println!("  Title: {title}");
@@ -336,6 +336,8 @@ pub fn print_jira_dry_run_with_custom_fields(
 pub async fn run_edit(id: &str, api: &dyn AtlassianApi, instance_url: &str) -> Result<()> {
     use tracing::debug;
 
+    crate::utils::interactive::ensure_interactive("Editing")?;
+
     // 1. Fetch the content
     println!("Fetching {id}...");
     let item = api.get_content(id).await?;
@@ -0,0 +1,591 @@
+//! `omni-dev bench` — times the local stages of the commit pipeline, so a
+//! release can be compared with the previous one on the same repository.
+//!
+//! Four stages are measured, each run `--iterations` times with the fastest
+//! run reported:
+//!
+//! - **analysis**: diffing and analyzing each commit from scratch, bypassing
+//!   the analysis cache, in commits and diff bytes per second;
+//! - **backend**: the local reads behind [`GitBackend`] — status, the
+//!   working-tree diff, and the diff and changed files of the measured
+//!   range — so another backend can be timed against `git2` on the same
+//!   repository;
+//! - **estimation**: loading each commit's diff into its AI view,
+//!   serializing it, and estimating its tokens, as the budget checks do;
+//! - **map phase** (with `--mock-ai`): batching the commits and sending the
+//!   batches through the real client and adaptive limiter to a mock backend
+//!   that answers after a fixed delay, so what remains is prompt building,
+//!   response parsing, and scheduling overhead.
+
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use git2::{Commit, Repository};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::claude::ai::{AiClient, AiClientMetadata};
+use crate::claude::concurrency::AdaptiveLimiter;
+use crate::claude::{batch, token_budget, ClaudeClient};
+use crate::data::output::Render;
+use crate::data::{
+    AiInfo, FieldExplanation, RenderFormat, RepositoryView, WorkingDirectoryInfo, SCHEMA_VERSION,
+};
+use crate::git::commit::{CommitAnalysis, CommitInfoForAI};
+use crate::git::{CommitInfo, Git2Backend, GitBackend, GitRepository};
+use crate::utils::env::SystemEnv;
+
+/// Bench command options.
+#[derive(Parser)]
+pub struct BenchCommand {
+    /// Commit range to measure (e.g. HEAD~100..HEAD); defaults to HEAD's history.
+    #[arg(value_name = "COMMIT_RANGE")]
+    pub commit_range: Option<String>,
+
+    /// Measures at most N commits, the newest ones; merge commits are skipped.
+    #[arg(long, value_name = "N", default_value_t = 50)]
+    pub commits: usize,
+
+    /// Runs each stage N times and reports the fastest run.
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub iterations: usize,
+
+    /// Also times the map phase against a mock AI backend; nothing is sent over the network.
+    #[arg(long)]
+    pub mock_ai: bool,
+
+    /// Delay of each mock AI response, in milliseconds.
+    #[arg(long, value_name = "MS", default_value_t = 50)]
+    pub mock_latency_ms: u64,
+
+    /// Starting concurrency of the mocked map phase.
+    #[arg(long, value_name = "N", default_value_t = 4)]
+    pub concurrency: usize,
+
+    /// Output format (default: text, or the top-level `--output`).
+    #[arg(short = 'o', long, value_enum)]
+    pub output: Option<RenderFormat>,
+}
+
+/// Timings of one `omni-dev bench` run.
+#[derive(Debug, Serialize)]
+pub struct BenchReport {
+    /// omni-dev version that was measured.
+    pub version: String,
+    /// Commits measured.
+    pub commits: usize,
+    /// Runs per stage; each stage reports its fastest.
+    pub iterations: usize,
+    /// Commit analysis without the cache.
+    pub analysis: AnalysisTiming,
+    /// Local reads through the git backend.
+    pub backend: BackendTiming,
+    /// AI view serialization and token estimation.
+    pub estimation: EstimationTiming,
+    /// Map phase against the mock backend, with `--mock-ai`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub map_phase: Option<MapPhaseTiming>,
+}
+
+/// Timing of the analysis stage.
+#[derive(Debug, Serialize)]
+pub struct AnalysisTiming {
+    /// Seconds taken by the fastest run.
+    pub seconds: f64,
+    /// Commits analyzed per second.
+    pub commits_per_sec: f64,
+    /// Bytes of diff written for the measured commits.
+    pub diff_bytes: u64,
+    /// Diff bytes written per second.
+    pub diff_bytes_per_sec: f64,
+}
+
+/// Timing of the backend stage.
+#[derive(Debug, Serialize)]
+pub struct BackendTiming {
+    /// Backend that was measured.
+    pub backend: String,
+    /// Seconds taken by the fastest run.
+    pub seconds: f64,
+    /// Bytes of diff read per run.
+    pub diff_bytes: usize,
+    /// Diff bytes read per second.
+    pub diff_bytes_per_sec: f64,
+}
+
+/// Timing of the estimation stage.
+#[derive(Debug, Serialize)]
+pub struct EstimationTiming {
+    /// Seconds taken by the fastest run.
+    pub seconds: f64,
+    /// Tokens estimated across the measured commits.
+    pub tokens: usize,
+    /// Tokens estimated per second.
+    pub tokens_per_sec: f64,
+}
+
+/// Timing of the mocked map phase.
+#[derive(Debug, Serialize)]
+pub struct MapPhaseTiming {
+    /// Batched requests sent.
+    pub requests: usize,
+    /// Starting concurrency.
+    pub concurrency: usize,
+    /// Delay of each mock response, in milliseconds.
+    pub mock_latency_ms: u64,
+    /// Seconds taken by the fastest run.
+    pub seconds: f64,
+    /// Requests completed per second.
+    pub requests_per_sec: f64,
+    /// Mean time per request beyond the mock delay, in milliseconds.
+    pub overhead_ms_per_request: f64,
+}
+
+impl Render for BenchReport {
+    fn render_text(&self) -> Result<String> {
+        let mut text = format!(
+            "omni-dev {} bench: {} commits, fastest of {}\n\n",
+            self.version, self.commits, self.iterations
+        );
+        let analysis = &self.analysis;
+        text.push_str(&format!(
+            "analysis    {:>8.3}s  {:>10.1} commits/s  {:>8.2} MB/s diffed ({:.2} MB)\n",
+            analysis.seconds,
+            analysis.commits_per_sec,
+            analysis.diff_bytes_per_sec / 1e6,
+            analysis.diff_bytes as f64 / 1e6,
+        ));
+        let backend = &self.backend;
+        text.push_str(&format!(
+            "backend     {:>8.3}s  {:>10} backend    {:>8.2} MB/s read   ({:.2} MB)\n",
+            backend.seconds,
+            backend.backend,
+            backend.diff_bytes_per_sec / 1e6,
+            backend.diff_bytes as f64 / 1e6,
+        ));
+        let estimation = &self.estimation;
+        text.push_str(&format!(
+            "estimation  {:>8.3}s  {:>10.0} tokens/s   ({} tokens)\n",
+            estimation.seconds, estimation.tokens_per_sec, estimation.tokens,
+        ));
+        if let Some(map) = &self.map_phase {
+            text.push_str(&format!(
+                "map phase   {:>8.3}s  {:>10.1} requests/s {:>8.2} ms overhead per request \
+                 ({} requests, {} ms mock latency, concurrency {})\n",
+                map.seconds,
+                map.requests_per_sec,
+                map.overhead_ms_per_request,
+                map.requests,
+                map.mock_latency_ms,
+                map.concurrency,
+            ));
+        }
+        Ok(text)
+    }
+}
+
+impl BenchCommand {
+    /// Executes the bench command.
+    ///
+    /// `repo` is the repository location resolved at the CLI boundary
+    /// (`None` = current working directory).
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let iterations = self.iterations.max(1);
+        let (analysis, backend, commits) = self.measure_repository(repo, iterations)?;
+        let estimation = time_estimation(&commits, iterations)?;
+        let commit_count = commits.len();
+
+        let map_phase = if self.mock_ai {
+            let latency = Duration::from_millis(self.mock_latency_ms);
+            Some(time_map_phase(commits, latency, self.concurrency.max(1), iterations).await?)
+        } else {
+            None
+        };
+
+        let report = BenchReport {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            commits: commit_count,
+            iterations,
+            analysis,
+            backend,
+            estimation,
+            map_phase,
+        };
+        let format = crate::data::output::resolve(self.output, &SystemEnv, RenderFormat::Text);
+        let rendered = crate::data::output::render(&report, format)?;
+        if rendered.ends_with('\n') {
+            print!("{rendered}");
+        } else {
+            println!("{rendered}");
+        }
+        Ok(())
+    }
+
+    /// Times the analysis and backend stages, then returns the measured
+    /// commits' info for the later stages. Kept apart from the async part so
+    /// no `git2` handle is held across an await.
+    fn measure_repository(
+        &self,
+        repo: Option<&Path>,
+        iterations: usize,
+    ) -> Result<(AnalysisTiming, BackendTiming, Vec<CommitInfo>)> {
+        let git_repo = if let Some(path) = repo {
+            GitRepository::open_at(path)
+                .context("Failed to open git repository at the given path")?
+        } else {
+            let cwd = std::env::current_dir().context("Failed to determine current directory")?;
+            GitRepository::open_at(cwd)
+                .context("Failed to open git repository. Make sure you're in a git repository.")?
+        };
+        let repo = git_repo.repository();
+        let commits = select_commits(repo, self.commit_range.as_deref(), self.commits)?;
+        if commits.is_empty() {
+            anyhow::bail!("No commits to measure");
+        }
+
+        let analysis = time_analysis(repo, &commits, iterations)?;
+        let range = measured_range(&commits)?;
+        let backend = time_backend(&Git2Backend::new(repo), "git2", &range, iterations)?;
+
+        let main_tips = crate::git::main_branches::detect_main_branch_tips(repo)?;
+        let infos = commits
+            .iter()
+            .map(|commit| CommitInfo::from_git_commit(repo, commit, &main_tips))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((analysis, backend, infos))
+    }
+}
+
+/// The newest `limit` non-merge commits of `range`, or of `HEAD`'s history.
+fn select_commits<'r>(
+    repo: &'r Repository,
+    range: Option<&str>,
+    limit: usize,
+) -> Result<Vec<Commit<'r>>> {
+    let mut walker = repo.revwalk().context("Failed to create revwalk")?;
+    match range {
+        Some(range) if range.contains("..") => walker
+            .push_range(range)
+            .with_context(|| format!("Invalid commit range: {range}"))?,
+        Some(rev) => {
+            let commit = repo
+                .revparse_single(rev)
+                .and_then(|object| object.peel_to_commit())
+                .with_context(|| format!("Failed to resolve commit: {rev}"))?;
+            walker.push(commit.id()).context("Failed to walk commit")?;
+        }
+        None => walker.push_head().context("Failed to walk HEAD")?,
+    }
+
+    let mut commits = Vec::new();
+    for oid in walker {
+        if commits.len() == limit {
+            break;
+        }
+        let commit = repo
+            .find_commit(oid.context("Failed to walk commits")?)
+            .context("Failed to find commit")?;
+        if commit.parent_count() <= 1 {
+            commits.push(commit);
+        }
+    }
+    Ok(commits)
+}
+
+/// The range from the oldest to the newest of `commits` (newest first).
+fn measured_range(commits: &[Commit<'_>]) -> Result<String> {
+    let (Some(newest), Some(oldest)) = (commits.first(), commits.last()) else {
+        anyhow::bail!("No commits to measure");
+    };
+    Ok(format!("{}..{}", oldest.id(), newest.id()))
+}
+
+/// Runs `stage` `iterations` times and returns the fastest duration with
+/// that run's result.
+fn fastest<T>(iterations: usize, mut stage: impl FnMut() -> Result<T>) -> Result<(Duration, T)> {
+    let mut best: Option<(Duration, T)> = None;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let result = stage()?;
+        let elapsed = start.elapsed();
+        if best
+            .as_ref()
+            .map_or(true, |(fastest, _)| elapsed < *fastest)
+        {
+            best = Some((elapsed, result));
+        }
+    }
+    best.context("No iterations were run")
+}
+
+/// Rate of `amount` over `elapsed`, guarding against a zero duration.
+fn per_sec(amount: f64, elapsed: Duration) -> f64 {
+    amount / elapsed.as_secs_f64().max(f64::EPSILON)
+}
+
+fn time_analysis(
+    repo: &Repository,
+    commits: &[Commit<'_>],
+    iterations: usize,
+) -> Result<AnalysisTiming> {
+    let (elapsed, diff_bytes) = fastest(iterations, || {
+        let mut diff_bytes = 0;
+        for commit in commits {
+            let analysis = CommitAnalysis::analyze_uncached(repo, commit)?;
+            diff_bytes += std::fs::metadata(&analysis.diff_file).map_or(0, |m| m.len());
+        }
+        Ok(diff_bytes)
+    })?;
+    Ok(AnalysisTiming {
+        seconds: elapsed.as_secs_f64(),
+        commits_per_sec: per_sec(commits.len() as f64, elapsed),
+        diff_bytes,
+        diff_bytes_per_sec: per_sec(diff_bytes as f64, elapsed),
+    })
+}
+
+fn time_backend(
+    backend: &dyn GitBackend,
+    name: &str,
+    range: &str,
+    iterations: usize,
+) -> Result<BackendTiming> {
+    let (elapsed, diff_bytes) = fastest(iterations, || {
+        backend.working_directory_status()?;
+        backend.changed_files(Some(range))?;
+        Ok(backend.working_diff()?.len() + backend.range_diff(range)?.len())
+    })?;
+    Ok(BackendTiming {
+        backend: name.to_string(),
+        seconds: elapsed.as_secs_f64(),
+        diff_bytes,
+        diff_bytes_per_sec: per_sec(diff_bytes as f64, elapsed),
+    })
+}
+
+fn time_estimation(commits: &[CommitInfo], iterations: usize) -> Result<EstimationTiming> {
+    let (elapsed, tokens) = fastest(iterations, || {
+        let mut tokens = 0;
+        for commit in commits {
+            let ai_commit = CommitInfoForAI::from_commit_info(commit.clone())?;
+            tokens += token_budget::estimate_tokens(&crate::data::to_yaml(&ai_commit)?);
+        }
+        Ok(tokens)
+    })?;
+    Ok(EstimationTiming {
+        seconds: elapsed.as_secs_f64(),
+        tokens,
+        tokens_per_sec: per_sec(tokens as f64, elapsed),
+    })
+}
+
+async fn time_map_phase(
+    commits: Vec<CommitInfo>,
+    latency: Duration,
+    concurrency: usize,
+    iterations: usize,
+) -> Result<MapPhaseTiming> {
+    let mock = MockAiClient { latency };
+    let plan = batch::plan_batches(
+        &commits,
+        &mock.get_metadata(),
+        token_budget::estimate_tokens(crate::claude::prompts::SYSTEM_PROMPT),
+    );
+    let client = ClaudeClient::new(Box::new(mock));
+    let view = bench_view(commits);
+
+    let mut best: Option<(Duration, Duration)> = None;
+    for _ in 0..iterations {
+        let limiter = AdaptiveLimiter::new(concurrency);
+        let start = Instant::now();
+        let calls = plan.batches.iter().map(|batch| {
+            let limiter = limiter.clone();
+            let commits: Vec<_> = batch
+                .commit_indices
+                .iter()
+                .map(|&i| &view.commits[i])
+                .collect();
+            let batch_view = view.multi_commit_view(&commits);
+            let client = &client;
+            async move {
+                let mut permit = limiter.acquire().await;
+                let sent = Instant::now();
+                let result = client
+                    .generate_amendments_with_options(&batch_view, false)
+                    .await;
+                let took = sent.elapsed();
+                permit.record(&result, batch_view.commits.len());
+                result.map(|_| took)
+            }
+        });
+        let calls = futures::future::join_all(calls)
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>>>()?;
+        let elapsed = start.elapsed();
+        let beyond_latency: Duration = calls.iter().map(|took| took.saturating_sub(latency)).sum();
+        if best.map_or(true, |(fastest, _)| elapsed < fastest) {
+            best = Some((elapsed, beyond_latency));
+        }
+    }
+    let (elapsed, beyond_latency) = best.context("No iterations were run")?;
+    let requests = plan.batches.len();
+    Ok(MapPhaseTiming {
+        requests,
+        concurrency,
+        mock_latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+        seconds: elapsed.as_secs_f64(),
+        requests_per_sec: per_sec(requests as f64, elapsed),
+        overhead_ms_per_request: beyond_latency.as_secs_f64() * 1e3 / requests.max(1) as f64,
+    })
+}
+
+/// A view of `commits` with nothing else in it, as the map phase batches.
+fn bench_view(commits: Vec<CommitInfo>) -> RepositoryView {
+    RepositoryView {
+        schema_version: SCHEMA_VERSION,
+        versions: None,
+        explanation: FieldExplanation {
+            text: String::new(),
+            fields: Vec::new(),
+        },
+        working_directory: WorkingDirectoryInfo {
+            clean: true,
+            untracked_changes: Vec::new(),
+        },
+        remotes: Vec::new(),
+        ai: AiInfo {
+            scratch: String::new(),
+        },
+        branch_info: None,
+        pr_template: None,
+        pr_template_location: None,
+        branch_prs: None,
+        commits,
+    }
+}
+
+/// Commit hashes in a prompt's repository view.
+static HASH_FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    #[allow(clippy::unwrap_used)] // Static pattern
+    Regex::new(r#"(?m)^\s*-?\s*hash: "?([0-9a-f]{40})"#).unwrap()
+});
+
+/// AI backend that answers every amendment request with a fixed message per
+/// commit after `latency`, standing in for the network round trip.
+struct MockAiClient {
+    latency: Duration,
+}
+
+impl AiClient for MockAiClient {
+    fn send_request<'a>(
+        &'a self,
+        _system_prompt: &'a str,
+        user_prompt: &'a str,
+    ) -> Pin<Box<dyn Future<Output = Result<String>> + Send + 'a>> {
+        Box::pin(async move {
+            tokio::time::sleep(self.latency).await;
+            let mut response = String::from("amendments:\n");
+            for hash in HASH_FIELD.captures_iter(user_prompt) {
+                response.push_str(&format!(
+                    "  - commit: \"{}\"\n    message: \"chore: bench\"\n",
+                    &hash[1]
+                ));
+            }
+            Ok(response)
+        })
+    }
+
+    fn get_metadata(&self) -> AiClientMetadata {
+        AiClientMetadata {
+            provider: "Mock".to_string(),
+            model: "bench".to_string(),
+            max_context_length: 200_000,
+            max_response_length: 8_192,
+            active_beta: None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn repo_with_commits(count: usize) -> (tempfile::TempDir, Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        for i in 0..count {
+            std::fs::write(dir.path().join("lib.rs"), format!("fn f{i}() {{}}\n")).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("lib.rs")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parents: Vec<_> = repo
+                .head()
+                .ok()
+                .map(|head| head.peel_to_commit().unwrap())
+                .into_iter()
+                .collect();
+            let parents: Vec<_> = parents.iter().collect();
+            repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &format!("feat: step {i}"),
+                &tree,
+                &parents,
+            )
+            .unwrap();
+        }
+        (dir, repo)
+    }
+
+    #[test]
+    fn selects_the_newest_commits_of_a_range() {
+        let (_dir, repo) = repo_with_commits(4);
+        let newest = select_commits(&repo, None, 2).unwrap();
+        let messages: Vec<_> = newest.iter().map(|c| c.summary().unwrap()).collect();
+        assert_eq!(messages, ["feat: step 3", "feat: step 2"]);
+        assert_eq!(
+            select_commits(&repo, Some("HEAD~3..HEAD"), 50)
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn times_backend_reads_over_the_measured_range() {
+        let (_dir, repo) = repo_with_commits(3);
+        let commits = select_commits(&repo, None, 3).unwrap();
+        let range = measured_range(&commits).unwrap();
+        assert_eq!(range, format!("{}..{}", commits[2].id(), commits[0].id()));
+
+        let timing = time_backend(&Git2Backend::new(&repo), "git2", &range, 2).unwrap();
+        assert_eq!(timing.backend, "git2");
+        assert!(timing.diff_bytes > 0);
+    }
+
+    #[tokio::test]
+    async fn mock_backend_answers_for_each_commit_in_the_prompt() {
+        let mock = MockAiClient {
+            latency: Duration::ZERO,
+        };
+        let prompt = format!(
+            "commits:\n  - hash: {}\n  - hash: {}\n",
+            "a".repeat(40),
+            "b".repeat(40)
+        );
+        let response = mock.send_request("", &prompt).await.unwrap();
+        let amendments = crate::data::amendments::AmendmentFile::from_yaml_str(&response).unwrap();
+        assert_eq!(amendments.amendments.len(), 2);
+        assert_eq!(amendments.amendments[1].commit, "b".repeat(40));
+    }
+}
@@ -0,0 +1,33 @@
+//! Check CLI commands.
+
+pub(crate) mod headers;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+/// Check: repository policies verified without AI.
+#[derive(Parser)]
+pub struct CheckCommand {
+    /// The check subcommand to execute.
+    #[command(subcommand)]
+    pub command: CheckSubcommands,
+}
+
+/// Check subcommands.
+#[derive(Subcommand)]
+pub enum CheckSubcommands {
+    /// Verifies that changed source files carry the license header from .omni-dev/license-header.txt.
+    Headers(headers::CheckHeadersCommand),
+}
+
+impl CheckCommand {
+    /// Executes the check command.
+    ///
+    /// `repo` is the repository location resolved at the CLI boundary
+    /// (`None` = current working directory).
+    pub fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        match self.command {
+            CheckSubcommands::Headers(cmd) => cmd.execute(repo),
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Check headers command — verifies that the source files a commit range or
+//! the staged changes touch carry the project's license header, and inserts
+//! it where it is missing: in place with `--fix`, or with `--patch` as a patch
+//! for `git apply`.
+
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::Datelike;
+use clap::Parser;
+use similar::TextDiff;
+
+use crate::git::generated::GeneratedFiles;
+use crate::git::license_header::{comment_prefix, LicenseHeader, LICENSE_HEADER_FILE};
+use crate::git::GitBackend;
+
+/// Target naming the staged changes instead of a commit range.
+const STAGED_TARGET: &str = "staged";
+
+/// `omni-dev check headers` CLI command.
+#[derive(Parser)]
+pub struct CheckHeadersCommand {
+    /// Commit range whose changed files to check (e.g. origin/main..HEAD; a single ref means <ref>..HEAD), or `staged` for the staged files (defaults to the branch against its base).
+    #[arg(value_name = "RANGE|staged")]
+    pub target: Option<String>,
+
+    /// Inserts the missing headers into the files.
+    #[arg(long, conflicts_with = "patch")]
+    pub fix: bool,
+
+    /// Prints a patch inserting the missing headers, for `git apply`, instead of failing.
+    #[arg(long)]
+    pub patch: bool,
+}
+
+/// A changed source file without the header.
+struct MissingHeader {
+    /// Repository-relative path.
+    file: String,
+    /// Current content.
+    content: String,
+    /// Content with the header inserted.
+    fixed: String,
+}
+
+impl CheckHeadersCommand {
+    /// Executes the check headers command, exiting with status 1 when a file
+    /// lacks the header and neither `--fix` nor `--patch` was given.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let header = LicenseHeader::load(repo_root)?.ok_or_else(|| {
+            anyhow!("No license header configured; write the required header text to .omni-dev/{LICENSE_HEADER_FILE}")
+        })?;
+
+        let files = changed_files(repo_root, self.target.as_deref())?;
+        let generated = GeneratedFiles::for_repo(repo_root);
+        let year = chrono::Local::now().year();
+        let mut checked = 0usize;
+        let mut missing = Vec::new();
+        for file in files {
+            let Some(prefix) = comment_prefix(&file) else {
+                continue;
+            };
+            if generated.is_generated(&file) {
+                continue;
+            }
+            // Deleted and non-UTF-8 files have nothing to check.
+            let Ok(content) = std::fs::read_to_string(repo_root.join(&file)) else {
+                continue;
+            };
+            checked += 1;
+            if !header.is_present(&content, prefix) {
+                let fixed = header.insert(&content, prefix, year);
+                missing.push(MissingHeader {
+                    file,
+                    content,
+                    fixed,
+                });
+            }
+        }
+
+        if self.patch {
+            print!("{}", header_patch(&missing));
+            return Ok(());
+        }
+        if missing.is_empty() {
+            println!("✅ {checked} changed source file(s) carry the license header");
+            return Ok(());
+        }
+        if self.fix {
+            for entry in &missing {
+                let path = repo_root.join(&entry.file);
+                std::fs::write(&path, &entry.fixed)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+            println!("📝 Added the license header to {} file(s):", missing.len());
+            for entry in &missing {
+                println!("  {}", entry.file);
+            }
+            return Ok(());
+        }
+
+        println!(
+            "❌ {} of {checked} changed source file(s) lack the license header:",
+            missing.len()
+        );
+        for entry in &missing {
+            println!("  {}", entry.file);
+        }
+        println!("💡 Run with --fix to insert it, or --patch to print a patch for `git apply`");
+        crate::utils::plain::exit(1);
+    }
+}
+
+/// Lists the files changed by `target` that still exist: the staged files
+/// for `staged`, `<ref>..HEAD` for a single ref, and the branch against its
+/// base when `target` is `None`.
+fn changed_files(repo_root: &Path, target: Option<&str>) -> Result<Vec<String>> {
+    let git_repo = crate::git::GitRepository::open_at(repo_root)
+        .context("Failed to open git repository at the given path")?;
+    let range = match target {
+        Some(STAGED_TARGET) => None,
+        Some(target) if target.contains("..") => Some(target.to_string()),
+        Some(target) => Some(format!("{target}..HEAD")),
+        None => Some(crate::cli::git::default_commit_range(&git_repo)?),
+    };
+    git_repo.backend().changed_files(range.as_deref())
+}
+
+/// A patch inserting the header into each file, for `git apply`.
+fn header_patch(missing: &[MissingHeader]) -> String {
+    let mut patch = String::new();
+    for entry in missing {
+        patch.push_str(&format!("diff --git a/{0} b/{0}\n", entry.file));
+        patch.push_str(
+            &TextDiff::from_lines(&entry.content, &entry.fixed)
+                .unified_diff()
+                .context_radius(3)
+                .header(&format!("a/{}", entry.file), &format!("b/{}", entry.file))
+                .to_string(),
+        );
+    }
+    patch
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_inserts_the_header_at_the_top() {
+        let header = LicenseHeader::parse("Copyright {year} Example Corp.").unwrap();
+        let content = "use std::io;\n\nfn main() {}\n".to_string();
+        let fixed = header.insert(&content, "//", 2026);
+        let patch = header_patch(&[MissingHeader {
+            file: "src/main.rs".to_string(),
+            content,
+            fixed,
+        }]);
+        assert_eq!(
+            patch,
+            "diff --git a/src/main.rs b/src/main.rs\n\
+             --- a/src/main.rs\n\
+             +++ b/src/main.rs\n\
+             @@ -1,3 +1,5 @@\n\
+             +// Copyright 2026 Example Corp.\n\
+             +\n \
+             use std::io;\n \
+             \n \
+             fn main() {}\n"
+        );
+    }
+}
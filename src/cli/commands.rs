@@ -1,11 +1,17 @@
-//! Command template management.
+//! Command template management: the built-in Claude Code slash-commands and
+//! the project's own templates in `.omni-dev/commands/`.
 
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 
+use crate::claude::command_template::{self, CommandTemplate, COMMANDS_DIR};
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::utils::env::SystemEnv;
+
 // Embed the template files as strings
 const COMMIT_TWIDDLE_TEMPLATE: &str = include_str!("../templates/commit-twiddle.md");
 const PR_CREATE_TEMPLATE: &str = include_str!("../templates/pr-create.md");
@@ -24,6 +30,66 @@ pub struct CommandsCommand {
 pub enum CommandsSubcommands {
     /// Generates command templates.
     Generate(GenerateCommand),
+    /// Adds a project command template to .omni-dev/commands/.
+    Add(AddCommand),
+    /// Lists the command templates from every context tier.
+    List(ListCommand),
+    /// Renders a command template and sends it to the AI.
+    Run(RunCommand),
+}
+
+/// Add command options.
+#[derive(Parser)]
+pub struct AddCommand {
+    /// Command name: lowercase letters, digits, '-', and '_'.
+    pub name: String,
+
+    /// One-line description stored in the template's front matter.
+    #[arg(long, default_value = "Team workflow")]
+    pub description: String,
+
+    /// Copies the template from this file instead of writing a starting point.
+    #[arg(long, value_name = "FILE")]
+    pub from: Option<PathBuf>,
+
+    /// Adds the template to the global config directory instead of the project.
+    #[arg(long)]
+    pub global: bool,
+
+    /// Overwrites an existing template with the same name.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// List command options.
+#[derive(Parser)]
+pub struct ListCommand {
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Run command options.
+#[derive(Parser)]
+pub struct RunCommand {
+    /// Command name.
+    pub name: String,
+
+    /// Sets a template variable, overriding the built-in branch, ticket, and scope (repeatable).
+    #[arg(long = "var", value_name = "NAME=VALUE")]
+    pub vars: Vec<String>,
+
+    /// Prints the rendered prompt instead of sending it to the AI.
+    #[arg(long)]
+    pub print: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
 }
 
 /// Generate command options.
@@ -52,11 +118,196 @@ pub enum GenerateSubcommands {
 
 impl CommandsCommand {
     /// Executes the commands command.
-    pub fn execute(self) -> Result<()> {
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
         match self.command {
             CommandsSubcommands::Generate(generate_cmd) => generate_cmd.execute(),
+            CommandsSubcommands::Add(add_cmd) => add_cmd.execute(repo),
+            CommandsSubcommands::List(list_cmd) => list_cmd.execute(repo),
+            CommandsSubcommands::Run(run_cmd) => run_cmd.execute(repo).await,
+        }
+    }
+}
+
+/// The repository root: `repo`, or the current directory.
+fn repo_root(repo: Option<&Path>) -> Result<PathBuf> {
+    match repo {
+        Some(p) => Ok(p.to_path_buf()),
+        None => std::env::current_dir().context("Failed to determine current directory"),
+    }
+}
+
+impl AddCommand {
+    /// Executes the add command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        command_template::validate_name(&self.name)?;
+        let base = if self.global {
+            crate::claude::context::global_config_dir()
+                .context("Cannot determine the global config directory")?
+        } else {
+            crate::claude::context::resolve_context_dir_at(
+                self.context_dir.as_deref(),
+                &repo_root(repo)?,
+            )
+        };
+        let path = base.join(command_template::relative_path(&self.name));
+        if path.exists() && !self.force {
+            bail!(
+                "{} already exists; pass --force to overwrite it",
+                path.display()
+            );
+        }
+
+        let content = match &self.from {
+            Some(from) => fs::read_to_string(from)
+                .with_context(|| format!("Failed to read {}", from.display()))?,
+            None => command_template::scaffold(&self.description),
+        };
+        // Refuse a template that would fail at `run` time.
+        CommandTemplate::parse(&self.name, &content, "project", path.clone())?;
+
+        let dir = base.join(COMMANDS_DIR);
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("✅ Added {}", path.display());
+        println!("   Run it with: omni-dev commands run {}", self.name);
+        Ok(())
+    }
+}
+
+/// The command templates, as listed.
+#[derive(serde::Serialize)]
+struct CommandList {
+    commands: Vec<CommandTemplate>,
+}
+
+impl Render for CommandList {
+    fn render_text(&self) -> Result<String> {
+        if self.commands.is_empty() {
+            return Ok(format!(
+                "No command templates found. Add one with: omni-dev commands add <NAME>\n\
+                 Templates live in {COMMANDS_DIR}/ of the context directory (.omni-dev/)."
+            ));
+        }
+        let width = self
+            .commands
+            .iter()
+            .map(|c| c.name.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::new();
+        for command in &self.commands {
+            out.push_str(&format!(
+                "{:<width$}  [{}] {}\n",
+                command.name,
+                command.source,
+                command.description.as_deref().unwrap_or("")
+            ));
+            let user_vars: Vec<&str> = command
+                .variables
+                .iter()
+                .map(String::as_str)
+                .filter(|v| !command_template::VARIABLES.iter().any(|(b, _)| b == v))
+                .collect();
+            if !user_vars.is_empty() {
+                out.push_str(&format!(
+                    "{:<width$}  needs: {}\n",
+                    "",
+                    user_vars
+                        .iter()
+                        .map(|v| format!("--var {v}=…"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                ));
+            }
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+impl ListCommand {
+    /// Executes the list command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let context_dir = crate::claude::context::resolve_context_dir_at(
+            self.context_dir.as_deref(),
+            &repo_root(repo)?,
+        );
+        let list = CommandList {
+            commands: command_template::list(&context_dir)?,
+        };
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&list, format)?);
+        Ok(())
+    }
+}
+
+impl RunCommand {
+    /// Executes the run command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = repo_root(repo)?;
+        let context_dir =
+            crate::claude::context::resolve_context_dir_at(self.context_dir.as_deref(), &repo_root);
+        let Some(template) = command_template::find(&context_dir, &self.name)? else {
+            bail!(
+                "No command template '{}'; see `omni-dev commands list`",
+                self.name
+            );
+        };
+
+        let mut values = builtin_values(&repo_root, &context_dir);
+        values.extend(parse_vars(&self.vars)?);
+        let prompt = template.render(&values)?;
+        if self.print {
+            print!("{prompt}");
+            return Ok(());
+        }
+
+        crate::utils::check_ai_command_prerequisites(None, &repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        eprintln!("🤖 Running {}...", template.name);
+        let response = claude_client
+            .send_message(
+                crate::claude::prompts::COMMAND_TEMPLATE_SYSTEM_PROMPT,
+                &prompt,
+            )
+            .await?;
+        println!("{}", response.trim_end());
+        Ok(())
+    }
+}
+
+/// The built-in variables: the current branch, and the ticket and scope the
+/// project's branch conventions (or the built-in patterns) find in it.
+fn builtin_values(repo_root: &Path, context_dir: &Path) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let Ok(branch) =
+        crate::git::GitRepository::open_at(repo_root).and_then(|repo| repo.get_current_branch())
+    else {
+        return values;
+    };
+    let conventions = crate::claude::context::load_branch_conventions(context_dir);
+    if let Ok(context) = crate::claude::context::BranchAnalyzer::analyze_with(&branch, &conventions)
+    {
+        if let Some(ticket) = context.ticket_id {
+            values.insert("ticket".to_string(), ticket);
+        }
+        if let Some(scope) = context.scope {
+            values.insert("scope".to_string(), scope);
         }
     }
+    values.insert("branch".to_string(), branch);
+    values
+}
+
+/// Parses `--var NAME=VALUE` arguments.
+fn parse_vars(vars: &[String]) -> Result<HashMap<String, String>> {
+    vars.iter()
+        .map(|var| match var.split_once('=') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                Ok((name.trim().to_string(), value.to_string()))
+            }
+            _ => bail!("Invalid --var '{var}'; expected NAME=VALUE"),
+        })
+        .collect()
 }
 
 impl GenerateCommand {
@@ -126,9 +377,42 @@ fn ensure_claude_commands_dir() -> Result<()> {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_vars_splits_on_the_first_equals_sign() {
+        let vars = parse_vars(&["audience=the team".to_string(), "q=a=b".to_string()]).unwrap();
+        assert_eq!(vars["audience"], "the team");
+        assert_eq!(vars["q"], "a=b");
+        assert!(parse_vars(&["novalue".to_string()]).is_err());
+        assert!(parse_vars(&["=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn list_text_shows_source_description_and_needed_vars() {
+        let template = CommandTemplate::parse(
+            "standup",
+            "---\ndescription: Standup update\n---\n{{branch}} for {{audience}}\n",
+            "project",
+            PathBuf::from(".omni-dev/commands/standup.md"),
+        )
+        .unwrap();
+        let list = CommandList {
+            commands: vec![template],
+        };
+        assert_eq!(
+            list.render_text().unwrap(),
+            "standup  [project] Standup update\n         needs: --var audience=…"
+        );
+        let empty = CommandList { commands: vec![] };
+        assert!(empty
+            .render_text()
+            .unwrap()
+            .starts_with("No command templates found."));
+    }
+
     #[test]
     fn commit_twiddle_template_has_content() {
         assert!(COMMIT_TWIDDLE_TEMPLATE.len() > 10);
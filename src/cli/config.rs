@@ -1,9 +1,23 @@
 //! Configuration-related CLI commands.
 
-use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
 use clap::{Parser, Subcommand};
 
+use crate::claude::context::discovery::context_file_schemas;
+use crate::claude::context::doctor::{Finding, Severity};
+use crate::claude::context::{config_resolution_chain, resolve_context_dir};
 use crate::claude::model_config::{get_model_registry, ModelSource, MODELS_YAML};
+use crate::utils::config::{self, ConfigLayer, ENV_ONLY, KEYS};
+use crate::utils::env::{EnvSource, SystemEnv};
+use crate::utils::schema::{self, PathSegment, SourceFormat};
+use crate::utils::secret_helper::PROVIDER_KEYS;
+use crate::utils::settings::{active_profile_from, profile_suffix, write_settings, Settings};
+use crate::utils::settings_migration;
 
 /// Configuration operations.
 #[derive(Parser)]
@@ -18,6 +32,25 @@ pub struct ConfigCommand {
 pub enum ConfigSubcommands {
     /// AI model configuration and information.
     Models(ModelsCommand),
+    /// Prints the effective value of a configuration key.
+    Get(GetCommand),
+    /// Sets a configuration key globally or for the project.
+    Set(SetCommand),
+    /// Removes a configuration key from the global or project layer.
+    Unset(UnsetCommand),
+    /// Lists configuration keys with their values and layers.
+    List(ListCommand),
+    /// Lists every environment variable omni-dev reads, with its value and
+    /// where the value comes from.
+    Env(EnvCommand),
+    /// Shows where each configuration layer is stored.
+    Path(PathCommand),
+    /// Checks settings.json and the context directory's YAML files for
+    /// unknown keys and wrongly typed values.
+    Validate(ValidateCommand),
+    /// Upgrades settings.json to the current layout, keeping a backup of the
+    /// original.
+    Migrate(MigrateCommand),
 }
 
 /// Models operations.
@@ -46,15 +79,798 @@ pub struct ShowCommand {
     pub embedded_only: bool,
 }
 
+/// Get command options.
+#[derive(Parser)]
+pub struct GetCommand {
+    /// Configuration key, e.g. OMNI_DEV_MODEL.
+    pub key: String,
+
+    /// Also print the layer the value comes from.
+    #[arg(long)]
+    pub show_origin: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Set command options.
+#[derive(Parser)]
+pub struct SetCommand {
+    /// Configuration key, e.g. OMNI_DEV_MODEL.
+    pub key: String,
+
+    /// Value to store; read from stdin when omitted, which keeps secrets out
+    /// of shell history.
+    pub value: Option<String>,
+
+    /// Write to the project's .omni-dev/config.yaml instead of
+    /// ~/.omni-dev/settings.json.
+    #[arg(long)]
+    pub project: bool,
+
+    /// Accept a key omni-dev does not document.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Unset command options.
+#[derive(Parser)]
+pub struct UnsetCommand {
+    /// Configuration key to remove.
+    pub key: String,
+
+    /// Remove from the project's .omni-dev/config.yaml instead of
+    /// ~/.omni-dev/settings.json.
+    #[arg(long)]
+    pub project: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// List command options.
+#[derive(Parser)]
+pub struct ListCommand {
+    /// Include every documented key, set or not, with its description.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Print credentials instead of masking them.
+    #[arg(long)]
+    pub show_secrets: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Env command options.
+#[derive(Parser)]
+pub struct EnvCommand {
+    /// Show only variables that have a value.
+    #[arg(long)]
+    pub set: bool,
+
+    /// Print credentials instead of masking them.
+    #[arg(long)]
+    pub show_secrets: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Path command options.
+#[derive(Parser)]
+pub struct PathCommand {
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Validate command options.
+#[derive(Parser)]
+pub struct ValidateCommand {
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Migrate command options.
+#[derive(Parser)]
+pub struct MigrateCommand {
+    /// Lists the changes without writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
 impl ConfigCommand {
     /// Executes the config command.
     pub fn execute(self) -> Result<()> {
         match self.command {
             ConfigSubcommands::Models(models_cmd) => models_cmd.execute(),
+            ConfigSubcommands::Get(cmd) => cmd.execute(),
+            ConfigSubcommands::Set(cmd) => cmd.execute(),
+            ConfigSubcommands::Unset(cmd) => cmd.execute(),
+            ConfigSubcommands::List(cmd) => cmd.execute(),
+            ConfigSubcommands::Env(cmd) => cmd.execute(),
+            ConfigSubcommands::Path(cmd) => cmd.execute(),
+            ConfigSubcommands::Validate(cmd) => cmd.execute(),
+            ConfigSubcommands::Migrate(cmd) => cmd.execute(),
         }
     }
 }
 
+/// The configuration layers as read for one invocation.
+struct Layers<'a, E: EnvSource> {
+    env: &'a E,
+    project: BTreeMap<String, String>,
+    settings: Settings,
+    profile: Option<String>,
+}
+
+impl Layers<'static, SystemEnv> {
+    /// Reads every layer from disk and the process environment.
+    fn load(context_dir: Option<&Path>) -> Result<Self> {
+        let project_path = config::project_config_path(&resolve_context_dir(context_dir));
+        Ok(Self {
+            env: &SystemEnv,
+            project: config::load_project_config(&project_path),
+            settings: Settings::load_from_path(Settings::get_settings_path()?)?,
+            profile: active_profile_from(&SystemEnv),
+        })
+    }
+}
+
+impl<E: EnvSource> Layers<'_, E> {
+    /// The global value of `key`, from the active profile's `env` map when a
+    /// profile is selected.
+    fn global(&self, key: &str) -> Option<&String> {
+        match &self.profile {
+            Some(name) => self.settings.profiles.get(name)?.env.get(key),
+            None => self.settings.env.get(key),
+        }
+    }
+
+    /// The effective value of `key` and the layer supplying it.
+    fn resolve(&self, key: &str) -> Option<(String, ConfigLayer)> {
+        self.env
+            .var(key)
+            .map(|value| (value, ConfigLayer::Env))
+            .or_else(|| {
+                self.project
+                    .get(key)
+                    .map(|value| (value.clone(), ConfigLayer::Project))
+            })
+            .or_else(|| {
+                self.global(key)
+                    .map(|value| (value.clone(), ConfigLayer::Global))
+            })
+    }
+
+    /// Keys to list: every documented key when `all` is set, otherwise the
+    /// documented keys with a value plus any undocumented key stored in the
+    /// project or global layer.
+    fn keys(&self, all: bool) -> Vec<String> {
+        let mut keys: Vec<String> = KEYS
+            .iter()
+            .filter(|key| all || self.resolve(key.name).is_some())
+            .map(|key| key.name.to_string())
+            .collect();
+        let global = match &self.profile {
+            Some(name) => self.settings.profiles.get(name).map(|p| &p.env),
+            None => Some(&self.settings.env),
+        };
+        let mut extra: Vec<String> = self
+            .project
+            .keys()
+            .chain(global.into_iter().flat_map(|env| env.keys()))
+            .filter(|key| config::find_key(key).is_none())
+            .cloned()
+            .collect();
+        extra.sort();
+        extra.dedup();
+        keys.extend(extra);
+        keys
+    }
+}
+
+impl GetCommand {
+    /// Executes the get command.
+    pub fn execute(self) -> Result<()> {
+        let layers = Layers::load(self.context_dir.as_deref())?;
+        let Some((value, layer)) = layers.resolve(&self.key) else {
+            bail!("'{}' is not set", self.key);
+        };
+        if self.show_origin {
+            println!("{value}\t({layer})");
+        } else {
+            println!("{value}");
+        }
+        Ok(())
+    }
+}
+
+impl SetCommand {
+    /// Executes the set command.
+    pub fn execute(self) -> Result<()> {
+        let value = match self.value {
+            Some(value) => value,
+            None => read_value_from_stdin(&self.key)?,
+        };
+        if self.project {
+            let context_dir = resolve_context_dir(self.context_dir.as_deref());
+            run_set_project(&context_dir, &self.key, &value, self.force)?;
+        } else {
+            run_set_global(
+                &Settings::get_settings_path()?,
+                active_profile_from(&SystemEnv).as_deref(),
+                &self.key,
+                &value,
+                self.force,
+            )?;
+        }
+        let layers = Layers::load(self.context_dir.as_deref())?;
+        if let Some((_, layer)) = layers.resolve(&self.key) {
+            let written = if self.project {
+                ConfigLayer::Project
+            } else {
+                ConfigLayer::Global
+            };
+            if layer < written {
+                println!(
+                    "Note: {} is also set in the {layer} layer, which takes precedence.",
+                    self.key
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads a value for `key` from the first line of stdin.
+fn read_value_from_stdin(key: &str) -> Result<String> {
+    if std::io::IsTerminal::is_terminal(&io::stdin()) {
+        eprint!("Value for {key}: ");
+    }
+    let mut input = String::new();
+    io::stdin()
+        .lock()
+        .read_line(&mut input)
+        .context("Failed to read value from stdin")?;
+    let value = input.trim_end_matches(['\n', '\r']).to_string();
+    if value.is_empty() {
+        bail!("No value given for {key}");
+    }
+    Ok(value)
+}
+
+/// Stores `key` in the settings file at `settings_path`, in `profile`'s `env`
+/// map when one is given.
+fn run_set_global(
+    settings_path: &Path,
+    profile: Option<&str>,
+    key: &str,
+    value: &str,
+    force: bool,
+) -> Result<()> {
+    config::validate_key(key, ConfigLayer::Global, force)?;
+    Settings::upsert_env_vars_in(settings_path, profile, &[(key, value)])?;
+    println!(
+        "Set {key} in {}{}",
+        settings_path.display(),
+        profile_suffix(profile)
+    );
+    Ok(())
+}
+
+/// Stores `key` in the project configuration of `context_dir`.
+fn run_set_project(context_dir: &Path, key: &str, value: &str, force: bool) -> Result<()> {
+    config::validate_key(key, ConfigLayer::Project, force)?;
+    let path = config::project_config_path(context_dir);
+    let existing = std::fs::read_to_string(&path).ok();
+    let updated = config::set_project_value(existing.as_deref(), key, value);
+    config::write_project_config(&path, &updated)?;
+    println!("Set {key} in {}", path.display());
+    Ok(())
+}
+
+impl UnsetCommand {
+    /// Executes the unset command.
+    pub fn execute(self) -> Result<()> {
+        if self.project {
+            let context_dir = resolve_context_dir(self.context_dir.as_deref());
+            run_unset_project(&context_dir, &self.key)
+        } else {
+            run_unset_global(
+                &Settings::get_settings_path()?,
+                active_profile_from(&SystemEnv).as_deref(),
+                &self.key,
+            )
+        }
+    }
+}
+
+/// Removes `key` from the settings file at `settings_path`.
+fn run_unset_global(settings_path: &Path, profile: Option<&str>, key: &str) -> Result<()> {
+    if Settings::remove_env_vars_in(settings_path, profile, &[key])? {
+        println!(
+            "Removed {key} from {}{}",
+            settings_path.display(),
+            profile_suffix(profile)
+        );
+    } else {
+        println!(
+            "{key} was not set in {}{}",
+            settings_path.display(),
+            profile_suffix(profile)
+        );
+    }
+    Ok(())
+}
+
+/// Removes `key` from the project configuration of `context_dir`.
+fn run_unset_project(context_dir: &Path, key: &str) -> Result<()> {
+    let path = config::project_config_path(context_dir);
+    let updated = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| config::unset_project_value(&content, key));
+    match updated {
+        Some(content) => {
+            config::write_project_config(&path, &content)?;
+            println!("Removed {key} from {}", path.display());
+        }
+        None => println!("{key} was not set in {}", path.display()),
+    }
+    Ok(())
+}
+
+impl ListCommand {
+    /// Executes the list command.
+    pub fn execute(self) -> Result<()> {
+        let layers = Layers::load(self.context_dir.as_deref())?;
+        print!("{}", render_list(&layers, self.all, self.show_secrets));
+        Ok(())
+    }
+}
+
+/// Renders `config list`: one `KEY  value  (layer)` line per key, with the
+/// description underneath when `all` is set.
+fn render_list<E: EnvSource>(layers: &Layers<'_, E>, all: bool, show_secrets: bool) -> String {
+    let keys = layers.keys(all);
+    if keys.is_empty() {
+        return "No configuration set. Run `omni-dev config list --all` to see known keys.\n"
+            .to_string();
+    }
+    let width = keys.iter().map(String::len).max().unwrap_or(0);
+    let mut out = String::new();
+    for key in &keys {
+        let line = match layers.resolve(key) {
+            Some((value, layer)) => {
+                let value = if config::is_secret(key) && !show_secrets {
+                    config::mask(&value)
+                } else {
+                    value
+                };
+                format!("{key:<width$}  {value}  ({layer})")
+            }
+            None => format!("{key:<width$}  (unset)"),
+        };
+        out.push_str(line.trim_end());
+        out.push('\n');
+        if all {
+            if let Some(known) = config::find_key(key) {
+                out.push_str(&format!("{:width$}  {}\n", "", known.description));
+            }
+        }
+    }
+    out
+}
+
+impl EnvCommand {
+    /// Executes the env command.
+    pub fn execute(self) -> Result<()> {
+        let layers = Layers::load(self.context_dir.as_deref())?;
+        print!("{}", render_env(&layers, self.set, self.show_secrets));
+        Ok(())
+    }
+}
+
+/// Renders `config env`: the settings-backed [`KEYS`] with their value and
+/// layer, then the [`ENV_ONLY`] variables, each followed by its description.
+fn render_env<E: EnvSource>(layers: &Layers<'_, E>, set_only: bool, show_secrets: bool) -> String {
+    let shown = |name: &str, value: String| {
+        if config::is_secret(name) && !show_secrets {
+            config::mask(&value)
+        } else {
+            value
+        }
+    };
+    let settings_rows: Vec<(&str, &str, Option<String>)> = KEYS
+        .iter()
+        .map(|key| {
+            let value = layers
+                .resolve(key.name)
+                .map(|(value, layer)| format!("{}  ({layer})", shown(key.name, value)));
+            (key.name, key.description, value)
+        })
+        .collect();
+    let env_rows: Vec<(&str, &str, Option<String>)> = ENV_ONLY
+        .iter()
+        .map(|var| {
+            let value = layers.env.var(var.name).map(|value| shown(var.name, value));
+            (var.name, var.description, value)
+        })
+        .collect();
+    let width = settings_rows
+        .iter()
+        .chain(&env_rows)
+        .map(|(name, _, _)| name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    for (heading, rows) in [
+        (
+            "Settings (environment, then .omni-dev/config.yaml, then settings.json):",
+            settings_rows,
+        ),
+        ("Environment only:", env_rows),
+    ] {
+        let rows: Vec<_> = rows
+            .into_iter()
+            .filter(|(_, _, value)| !set_only || value.is_some())
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(heading);
+        out.push('\n');
+        for (name, description, value) in rows {
+            let value = value.unwrap_or_else(|| "(unset)".to_string());
+            out.push_str(&format!("  {name:<width$}  {value}\n"));
+            out.push_str(&format!("  {:width$}  {description}\n", ""));
+        }
+    }
+    if out.is_empty() {
+        return "No variables set. Run `omni-dev config env` to see every variable.\n".to_string();
+    }
+    out
+}
+
+impl PathCommand {
+    /// Executes the path command.
+    pub fn execute(self) -> Result<()> {
+        let project =
+            config::project_config_path(&resolve_context_dir(self.context_dir.as_deref()));
+        let exists = |path: &Path| {
+            if path.exists() {
+                ""
+            } else {
+                " (not created yet)"
+            }
+        };
+        let global = Settings::get_settings_path()?;
+        let profile = active_profile_from(&SystemEnv);
+        println!("env:     process environment (highest precedence)");
+        println!("project: {}{}", project.display(), exists(&project));
+        println!(
+            "global:  {}{}{}",
+            global.display(),
+            profile_suffix(profile.as_deref()),
+            exists(&global)
+        );
+        Ok(())
+    }
+}
+
+impl ValidateCommand {
+    /// Executes the validate command.
+    pub fn execute(self) -> Result<()> {
+        let context_dir = resolve_context_dir(self.context_dir.as_deref());
+        // Files without a schema are the project config.yaml, checked
+        // against the key registry instead.
+        let mut files: Vec<(PathBuf, Option<serde_json::Value>)> = vec![
+            (Settings::get_settings_path()?, Some(Settings::schema())),
+            (config::project_config_path(&context_dir), None),
+        ];
+        for (name, file_schema) in context_file_schemas() {
+            for tier in config_resolution_chain(&context_dir, name) {
+                if let Some(path) = tier.path() {
+                    files.push((path.to_path_buf(), Some(file_schema.clone())));
+                }
+            }
+        }
+        files.retain(|(path, _)| path.is_file());
+
+        println!("🔍 Validating {} file(s)", files.len());
+        let mut findings = Vec::new();
+        for (path, file_schema) in &files {
+            println!("   {}", path.display());
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let file = path.display().to_string();
+            findings.extend(match file_schema {
+                Some(file_schema) if path.extension().is_some_and(|e| e == "json") => {
+                    validate_settings(&file, &content, file_schema)
+                }
+                Some(file_schema) => {
+                    validate_document(&file, &content, file_schema, SourceFormat::Yaml)
+                }
+                None => validate_project_config(&file, &content),
+            });
+        }
+        crate::cli::context::report(&findings, "Configuration")
+    }
+}
+
+impl MigrateCommand {
+    /// Executes the migrate command.
+    pub fn execute(self) -> Result<()> {
+        migrate_settings_file(&Settings::get_settings_path()?, self.dry_run)
+    }
+}
+
+/// Upgrades the settings file at `path` to the current layout, first copying
+/// the original next to it. With `dry_run`, only lists the changes.
+fn migrate_settings_file(path: &Path, dry_run: bool) -> Result<()> {
+    if !path.is_file() {
+        println!("ℹ️  {} does not exist; nothing to migrate", path.display());
+        return Ok(());
+    }
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let original: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let from = settings_migration::version_of(&original);
+    let mut migrated = original.clone();
+    let changes = settings_migration::migrate(&mut migrated)
+        .with_context(|| format!("Cannot migrate {}", path.display()))?;
+    if migrated == original {
+        println!(
+            "✅ {} is up to date (version {})",
+            path.display(),
+            settings_migration::CURRENT_VERSION
+        );
+        return Ok(());
+    }
+
+    println!(
+        "🔄 Migrating {} from version {from} to {}",
+        path.display(),
+        settings_migration::CURRENT_VERSION
+    );
+    for change in &changes {
+        println!("   - {change}");
+    }
+    if dry_run {
+        println!("ℹ️  Dry run: nothing written");
+        return Ok(());
+    }
+
+    let backup = backup_path(path);
+    fs::copy(path, &backup).with_context(|| {
+        format!(
+            "Failed to back up {} to {}",
+            path.display(),
+            backup.display()
+        )
+    })?;
+    crate::daemon::paths::set_file_0600(&backup)?;
+    write_settings(path, &migrated)?;
+    println!("✅ Migrated; the original is saved as {}", backup.display());
+    Ok(())
+}
+
+/// `<path>.<UTC timestamp>.bak`, so repeated migrations never overwrite an
+/// earlier backup.
+fn backup_path(path: &Path) -> PathBuf {
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{stamp}.bak"));
+    path.with_file_name(name)
+}
+
+/// A finding pointing at `line` of `file` when it is known.
+fn finding_at(severity: Severity, file: &str, line: Option<usize>, message: String) -> Finding {
+    Finding {
+        severity,
+        file: line.map_or_else(|| file.to_string(), |line| format!("{file}:{line}")),
+        message,
+    }
+}
+
+/// Parses `content` and checks it against `file_schema`. An empty YAML file
+/// is treated as having no settings.
+fn validate_document(
+    file: &str,
+    content: &str,
+    file_schema: &serde_json::Value,
+    format: SourceFormat,
+) -> Vec<Finding> {
+    if format == SourceFormat::Yaml && content.trim().is_empty() {
+        return Vec::new();
+    }
+    let value = match parse_document(content, format) {
+        Ok(value) => value,
+        Err((line, message)) => return vec![finding_at(Severity::Error, file, line, message)],
+    };
+    schema::validate(file_schema, &value)
+        .into_iter()
+        .map(|error| {
+            let line = schema::locate_line(content, &error.path, format);
+            finding_at(Severity::Error, file, line, error.to_string())
+        })
+        .collect()
+}
+
+/// Parses YAML or JSON into a JSON value, or returns the syntax error with
+/// its line.
+fn parse_document(
+    content: &str,
+    format: SourceFormat,
+) -> std::result::Result<serde_json::Value, (Option<usize>, String)> {
+    match format {
+        SourceFormat::Yaml => serde_yaml::from_str(content)
+            .map_err(|e| (e.location().map(|l| l.line()), format!("invalid YAML: {e}"))),
+        SourceFormat::Json => serde_json::from_str(content)
+            .map_err(|e| (Some(e.line()), format!("invalid JSON: {e}"))),
+    }
+}
+
+/// Checks `settings.json` against its schema, then warns about `env` keys
+/// that look like misspelt documented keys and about secret helpers for
+/// providers that take none. Neither is an error: `env` may carry variables
+/// for other tools, and an unused helper is harmless.
+fn validate_settings(file: &str, content: &str, file_schema: &serde_json::Value) -> Vec<Finding> {
+    let mut findings = validate_document(file, content, file_schema, SourceFormat::Json);
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(content) else {
+        return findings;
+    };
+    let mut scopes = vec![(Vec::new(), &value)];
+    if let Some(profiles) = value.get("profiles").and_then(serde_json::Value::as_object) {
+        for (name, profile) in profiles {
+            scopes.push((
+                vec![
+                    PathSegment::Key("profiles".to_string()),
+                    PathSegment::Key(name.clone()),
+                ],
+                profile,
+            ));
+        }
+    }
+    let mut migrated = value.clone();
+    if let Ok(changes) = settings_migration::migrate(&mut migrated) {
+        if !changes.is_empty() {
+            findings.push(finding_at(
+                Severity::Warning,
+                file,
+                None,
+                format!(
+                    "outdated layout (version {}, {} change(s) pending); run `omni-dev config migrate`",
+                    settings_migration::version_of(&value),
+                    changes.len()
+                ),
+            ));
+        }
+    }
+    let known_keys = KEYS.iter().map(|key| key.name);
+    let providers = PROVIDER_KEYS.iter().map(|(provider, _)| *provider);
+    for (base, scope) in scopes {
+        let mut warn = |section: &str, key: &str, message: String| {
+            let mut path = base.clone();
+            path.push(PathSegment::Key(section.to_string()));
+            path.push(PathSegment::Key(key.to_string()));
+            let line = schema::locate_line(content, &path, SourceFormat::Json);
+            findings.push(finding_at(Severity::Warning, file, line, message));
+        };
+        for key in object_keys(scope, "env") {
+            if config::find_key(key).is_none() {
+                if let Some(suggestion) = schema::closest(key, known_keys.clone()) {
+                    warn(
+                        "env",
+                        key,
+                        format!("'{key}' is not a known key (did you mean '{suggestion}'?)"),
+                    );
+                }
+            }
+        }
+        for provider in object_keys(scope, "secret_helpers") {
+            if !providers.clone().any(|p| p == provider) {
+                let accepted: Vec<&str> = providers.clone().collect();
+                warn(
+                    "secret_helpers",
+                    provider,
+                    format!(
+                        "no provider named '{provider}' takes a secret helper (expected one of {})",
+                        accepted.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+    findings
+}
+
+/// The keys of the object at `value[section]`, if it is one.
+fn object_keys<'a>(value: &'a serde_json::Value, section: &str) -> Vec<&'a str> {
+    value
+        .get(section)
+        .and_then(serde_json::Value::as_object)
+        .map(|map| map.keys().map(String::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Checks the project `config.yaml` against the key registry: every entry
+/// must be a key the project layer may set (or its flag-style alias), with a
+/// scalar value, and set at most once.
+fn validate_project_config(file: &str, content: &str) -> Vec<Finding> {
+    if content.trim().is_empty() {
+        return Vec::new();
+    }
+    let mapping: BTreeMap<String, serde_yaml::Value> = match serde_yaml::from_str(content) {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            let line = e.location().map(|l| l.line());
+            return vec![finding_at(
+                Severity::Error,
+                file,
+                line,
+                format!("invalid YAML: {e}"),
+            )];
+        }
+    };
+    let project_keys = || KEYS.iter().filter(|key| key.project).map(|key| key.name);
+    mapping
+        .iter()
+        .filter_map(|(name, value)| {
+            let key = config::canonical_key(name);
+            let message = match config::find_key(key) {
+                None => {
+                    let mut message = format!("unknown key '{name}'");
+                    let aliases = config::FLAG_ALIASES.iter().map(|(alias, _)| *alias);
+                    if let Some(suggestion) = schema::closest(name, project_keys().chain(aliases)) {
+                        message.push_str(&format!(" (did you mean '{suggestion}'?)"));
+                    }
+                    message
+                }
+                Some(key) if !key.project => format!(
+                    "'{name}' cannot be set per project; set it with `omni-dev config set {name}`"
+                ),
+                Some(_)
+                    if !matches!(
+                        value,
+                        serde_yaml::Value::String(_)
+                            | serde_yaml::Value::Bool(_)
+                            | serde_yaml::Value::Number(_)
+                    ) =>
+                {
+                    format!("{name}: expected a string, number, or boolean")
+                }
+                Some(_) if key != name && mapping.contains_key(key) => {
+                    format!("'{name}' sets {key}, which is already set")
+                }
+                Some(_) => return None,
+            };
+            let line = schema::locate_line(
+                content,
+                &[PathSegment::Key(name.clone())],
+                SourceFormat::Yaml,
+            );
+            Some(finding_at(Severity::Error, file, line, message))
+        })
+        .collect()
+}
+
 impl ModelsCommand {
     /// Executes the models command.
     pub fn execute(self) -> Result<()> {
@@ -186,4 +1002,249 @@ models:
         let summary = prepend_layer_summary("", &config);
         assert!(summary.contains("Models by source: (none)"));
     }
+
+    #[test]
+    fn list_shows_effective_layer_and_masks_secrets() {
+        use crate::test_support::env::MapEnv;
+        let env = MapEnv::new().with("OMNI_DEV_AI_BACKEND", "claude-cli");
+        let mut settings = Settings::default();
+        settings
+            .env
+            .insert("OMNI_DEV_MODEL".to_string(), "global-model".to_string());
+        settings
+            .env
+            .insert("ANTHROPIC_API_KEY".to_string(), "sk-secret".to_string());
+        settings
+            .env
+            .insert("MY_CUSTOM_FLAG".to_string(), "1".to_string());
+        let layers = Layers {
+            env: &env,
+            project: std::iter::once(("OMNI_DEV_MODEL".to_string(), "project-model".to_string()))
+                .collect(),
+            settings,
+            profile: None,
+        };
+
+        let list = render_list(&layers, false, false);
+        assert!(list.contains("OMNI_DEV_AI_BACKEND"));
+        assert!(list.contains("claude-cli  (env)"));
+        assert!(list.contains("project-model  (project)"));
+        assert!(!list.contains("global-model"));
+        assert!(list.contains("******** (9 chars)  (global)"));
+        assert!(!list.contains("sk-secret"));
+        assert!(list.contains("MY_CUSTOM_FLAG"));
+        assert!(!list.contains("OMNI_DEV_MERGE_STRATEGY"));
+
+        let all = render_list(&layers, true, true);
+        assert!(all.contains("sk-secret  (global)"));
+        assert!(all.lines().any(
+            |line| line.starts_with("OMNI_DEV_MERGE_STRATEGY ") && line.ends_with("  (unset)")
+        ));
+        assert!(all.contains("Default merge strategy for merge-pr"));
+    }
+
+    #[test]
+    fn env_lists_settings_keys_and_env_only_variables() {
+        use crate::test_support::env::MapEnv;
+        let env = MapEnv::new()
+            .with("OMNI_DEV_CONFIG_DIR", "/work/.omni-dev")
+            .with("GH_TOKEN", "ghp-secret");
+        let mut settings = Settings::default();
+        settings
+            .env
+            .insert("OMNI_DEV_MODEL".to_string(), "global-model".to_string());
+        let layers = Layers {
+            env: &env,
+            project: BTreeMap::new(),
+            settings,
+            profile: None,
+        };
+
+        let all = render_env(&layers, false, false);
+        assert!(all.starts_with("Settings ("));
+        assert!(all.contains("\nEnvironment only:\n"));
+        assert!(all.contains("global-model  (global)"));
+        assert!(all.contains("******** (10 chars)  (env)"));
+        assert!(all.contains("  OMNI_DEV_CONFIG_DIR "));
+        assert!(all.contains("/work/.omni-dev\n"));
+        assert!(all
+            .lines()
+            .any(|line| line.starts_with("  RUST_LOG ") && line.ends_with("  (unset)")));
+        let (_, env_only) = all.split_once("\nEnvironment only:\n").unwrap();
+        assert!(env_only.contains("  OMNI_DEV_PLAIN "));
+        assert!(env_only.contains("  NO_COLOR "));
+
+        let set = render_env(&layers, true, true);
+        assert!(set.contains("ghp-secret  (env)"));
+        assert!(!set.contains("RUST_LOG"));
+        assert!(!set.contains("(unset)"));
+
+        let empty = Layers {
+            env: &MapEnv::new(),
+            project: BTreeMap::new(),
+            settings: Settings::default(),
+            profile: None,
+        };
+        assert!(render_env(&empty, true, false).starts_with("No variables set"));
+    }
+
+    #[test]
+    fn set_and_unset_round_trip_both_layers() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings_path = dir.path().join("settings.json");
+        let context_dir = dir.path().join(".omni-dev");
+
+        run_set_global(&settings_path, Some("work"), "OMNI_DEV_MODEL", "m1", false).unwrap();
+        let settings = Settings::load_from_path(&settings_path).unwrap();
+        assert_eq!(settings.profiles["work"].env["OMNI_DEV_MODEL"], "m1");
+        run_unset_global(&settings_path, Some("work"), "OMNI_DEV_MODEL").unwrap();
+        let settings = Settings::load_from_path(&settings_path).unwrap();
+        assert!(settings.profiles["work"].env.is_empty());
+        assert!(run_set_global(&settings_path, None, "OMNI_DEV_MODLE", "x", false).is_err());
+
+        run_set_project(&context_dir, "OMNI_DEV_COMMIT_EXAMPLES", "3", false).unwrap();
+        let path = config::project_config_path(&context_dir);
+        assert_eq!(
+            config::load_project_config(&path)["OMNI_DEV_COMMIT_EXAMPLES"],
+            "3"
+        );
+        assert!(run_set_project(&context_dir, "ANTHROPIC_API_KEY", "sk", true).is_err());
+        run_unset_project(&context_dir, "OMNI_DEV_COMMIT_EXAMPLES").unwrap();
+        assert!(config::load_project_config(&path).is_empty());
+    }
+
+    fn messages(findings: &[Finding]) -> Vec<String> {
+        findings.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn validate_document_points_at_typos() {
+        let (_, scopes_schema) = context_file_schemas()
+            .into_iter()
+            .find(|(name, _)| *name == "scopes.yaml")
+            .unwrap();
+        let content = "scopes:\n  - name: cli\n    description: CLI\n    examples: []\n    file_pattern:\n      - src/cli/**\n";
+        let findings = messages(&validate_document(
+            "scopes.yaml",
+            content,
+            &scopes_schema,
+            SourceFormat::Yaml,
+        ));
+        assert!(findings.contains(
+            &"❌ scopes.yaml:5: scopes[0].file_pattern: unknown key 'file_pattern' (did you mean 'file_patterns'?)"
+                .to_string()
+        ));
+        assert!(findings.contains(
+            &"❌ scopes.yaml:2: scopes[0]: missing required key 'file_patterns'".to_string()
+        ));
+
+        let findings = validate_document(
+            "scopes.yaml",
+            "scopes: [",
+            &scopes_schema,
+            SourceFormat::Yaml,
+        );
+        assert_eq!(findings.len(), 1);
+        assert!(findings[0].message.starts_with("invalid YAML"));
+    }
+
+    #[test]
+    fn validate_settings_flags_unknown_keys() {
+        let content = r#"{
+  "env": { "OMNI_DEV_MODLE": "x", "MY_TOOL_HOME": "/opt" },
+  "mcp": { "log_levl": "info" },
+  "secret_helpers": { "antropic": { "command": "op read x" } }
+}
+"#;
+        let findings = validate_settings("settings.json", content, &Settings::schema());
+        let errors: Vec<&Finding> = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].file, "settings.json:3");
+        assert!(errors[0].message.contains("did you mean 'log_level'?"));
+
+        let warnings: Vec<&str> = findings
+            .iter()
+            .filter(|f| f.severity == Severity::Warning)
+            .map(|f| f.file.as_str())
+            .collect();
+        assert_eq!(warnings, ["settings.json:2", "settings.json:4"]);
+    }
+
+    #[test]
+    fn migrate_settings_file_backs_up_and_upgrades() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        let original = r#"{ "env": { "USE_OLLAMA": "true", "OLLAMA_MODEL": "llama3" } }"#;
+        fs::write(&path, original).unwrap();
+
+        let findings = validate_settings("settings.json", original, &Settings::schema());
+        assert!(messages(&findings)
+            .iter()
+            .any(|f| f.contains("run `omni-dev config migrate`")));
+
+        migrate_settings_file(&path, true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+
+        migrate_settings_file(&path, false).unwrap();
+        let migrated: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            migrated,
+            serde_json::json!({
+                "version": settings_migration::CURRENT_VERSION,
+                "env": { "OMNI_DEV_AI_BACKEND": "ollama", "OLLAMA_MODEL": "llama3" }
+            })
+        );
+        let backups: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".bak"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.path().join(&backups[0])).unwrap(),
+            original
+        );
+        assert!(validate_settings(
+            "settings.json",
+            &fs::read_to_string(&path).unwrap(),
+            &Settings::schema()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn validate_project_config_checks_the_registry() {
+        let content = "OMNI_DEV_MODEL: claude\nOMNI_DEV_MODLE: x\nANTHROPIC_API_KEY: sk\nOMNI_DEV_COMMIT_EXAMPLES: [1]\n";
+        let findings = messages(&validate_project_config("config.yaml", content));
+        assert_eq!(findings.len(), 3);
+        assert!(findings
+            .iter()
+            .any(|f| f.starts_with("❌ config.yaml:2: unknown key")
+                && f.ends_with("(did you mean 'OMNI_DEV_MODEL'?)")));
+        assert!(findings
+            .iter()
+            .any(|f| f
+                .starts_with("❌ config.yaml:3: 'ANTHROPIC_API_KEY' cannot be set per project")));
+        assert!(findings.iter().any(|f| {
+            f == "❌ config.yaml:4: OMNI_DEV_COMMIT_EXAMPLES: expected a string, number, or boolean"
+        }));
+    }
+
+    #[test]
+    fn validate_project_config_accepts_flag_aliases() {
+        let content =
+            "concurrency: 8\nauto_apply: true\nOMNI_DEV_MODEL: claude\nmodel: x\nconcurency: 2\n";
+        let findings = messages(&validate_project_config("config.yaml", content));
+        assert_eq!(
+            findings,
+            [
+                "❌ config.yaml:5: unknown key 'concurency' (did you mean 'concurrency'?)",
+                "❌ config.yaml:4: 'model' sets OMNI_DEV_MODEL, which is already set",
+            ]
+        );
+    }
 }
@@ -0,0 +1,493 @@
+//! Project context management.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+
+use crate::claude::context::bundle;
+use crate::claude::context::doctor::{self, Finding, Severity, GUIDANCE_FILES};
+use crate::claude::context::scope_packs;
+use crate::claude::context::{
+    config_resolution_chain, detect_ecosystem, resolve_context_dir_at,
+    resolve_context_dir_with_source_at, sample_history, LearnedConventions,
+};
+use crate::git::GitRepository;
+
+/// Project context operations.
+#[derive(Parser)]
+pub struct ContextCommand {
+    /// Context subcommand to execute.
+    #[command(subcommand)]
+    pub command: ContextSubcommands,
+}
+
+/// Context subcommands.
+#[derive(Subcommand)]
+pub enum ContextSubcommands {
+    /// Infers commit conventions from git history and drafts commit-guidelines.md and scopes.yaml.
+    Learn(LearnCommand),
+    /// Validates scopes.yaml, flags overlapping or unreachable patterns and oversized guidelines, and shows where each guidance file resolves from.
+    Doctor(DoctorCommand),
+    /// Packages the shared context files (guidelines, scopes, types, prompts, ...) into a .tar.gz bundle.
+    Export(ExportCommand),
+    /// Unpacks a context bundle into the context directory after verifying its manifest.
+    Import(ImportCommand),
+    /// Selects the default scope pack (rust, node, python, terraform, ...) in scopes.yaml, or lists the packs.
+    UsePack(UsePackCommand),
+}
+
+/// Learn command options.
+#[derive(Parser)]
+pub struct LearnCommand {
+    /// Number of recent non-merge commits to analyze.
+    #[arg(long, value_name = "N", default_value_t = 500)]
+    pub limit: usize,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+
+    /// Overwrites existing commit-guidelines.md and scopes.yaml.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Prints the drafts instead of writing them.
+    #[arg(long)]
+    pub print: bool,
+}
+
+/// Doctor command options.
+#[derive(Parser)]
+pub struct DoctorCommand {
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+
+    /// Model whose input context sizes the guideline budget (defaults to the configured model).
+    #[arg(long)]
+    pub model: Option<String>,
+}
+
+/// Export command options.
+#[derive(Parser)]
+pub struct ExportCommand {
+    /// Bundle file to write (e.g. context-bundle.tar.gz).
+    pub output: PathBuf,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+/// Import command options.
+#[derive(Parser)]
+pub struct ImportCommand {
+    /// Bundle file written by `omni-dev context export`.
+    pub bundle: PathBuf,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+
+    /// Overwrites existing files that differ from the bundle.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Lists what would change without writing anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Use-pack command options.
+#[derive(Parser)]
+pub struct UsePackCommand {
+    /// Pack to use, or `none` to turn ecosystem defaults off; lists the packs when omitted.
+    pub name: Option<String>,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<PathBuf>,
+}
+
+impl ContextCommand {
+    /// Executes the context command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            ContextSubcommands::Learn(learn_cmd) => learn_cmd.execute(repo),
+            ContextSubcommands::Doctor(doctor_cmd) => doctor_cmd.execute(repo),
+            ContextSubcommands::Export(export_cmd) => export_cmd.execute(repo),
+            ContextSubcommands::Import(import_cmd) => import_cmd.execute(repo),
+            ContextSubcommands::UsePack(use_pack_cmd) => use_pack_cmd.execute(repo),
+        }
+    }
+}
+
+impl LearnCommand {
+    /// Executes the learn command.
+    ///
+    /// Refuses to replace existing files unless `--force` is given, since
+    /// hand-written guidelines are usually better than inferred ones.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+
+        let history = sample_history(git_repo.repository(), self.limit)?;
+        if history.is_empty() {
+            bail!("No non-merge commits found to learn from");
+        }
+        let learned = LearnedConventions::from_history(&history);
+        let drafts = [
+            ("commit-guidelines.md", learned.guidelines_markdown()),
+            ("scopes.yaml", learned.scopes_yaml()?),
+        ];
+
+        if self.print {
+            for (name, content) in &drafts {
+                println!("# ── {name} ──\n{content}");
+            }
+            return Ok(());
+        }
+
+        println!(
+            "✓ Analyzed {} commits: {} types, {} scopes, {}% conventional",
+            learned.sampled,
+            learned.types.len(),
+            learned.scopes.len(),
+            learned.conventional * 100 / learned.sampled
+        );
+
+        let context_dir = resolve_context_dir_at(self.context_dir.as_deref(), &repo_root);
+        let existing: Vec<&str> = drafts
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| context_dir.join(name).exists())
+            .collect();
+        if !existing.is_empty() && !self.force {
+            bail!(
+                "{} already exist(s) in {}; pass --force to overwrite or --print to review the drafts",
+                existing.join(" and "),
+                context_dir.display()
+            );
+        }
+
+        std::fs::create_dir_all(&context_dir).with_context(|| {
+            format!(
+                "Failed to create context directory: {}",
+                context_dir.display()
+            )
+        })?;
+        for (name, content) in &drafts {
+            let path = context_dir.join(name);
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("✅ Wrote {}", path.display());
+        }
+        println!("📝 Review the drafts; twiddle, check, and create pr pick them up automatically.");
+        Ok(())
+    }
+}
+
+impl DoctorCommand {
+    /// Executes the doctor command.
+    ///
+    /// Fails when any error is found, so it can gate CI; warnings are only
+    /// reported.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let (context_dir, dir_source) =
+            resolve_context_dir_with_source_at(self.context_dir.as_deref(), &repo_root);
+
+        println!("🩺 Context doctor");
+        println!("📂 Config dir: {} ({dir_source})", context_dir.display());
+
+        println!("\n🔗 Resolution chain (first existing file wins):");
+        let mut active = Vec::new();
+        for filename in GUIDANCE_FILES {
+            println!("   {filename}");
+            let mut found = false;
+            for tier in config_resolution_chain(&context_dir, filename) {
+                let Some(path) = tier.path() else {
+                    continue;
+                };
+                let status = if !path.exists() {
+                    "·  "
+                } else if found {
+                    "⏭️ "
+                } else {
+                    found = true;
+                    active.push((*filename, path.to_path_buf()));
+                    "✅"
+                };
+                println!("     {status} {tier}");
+            }
+        }
+
+        let mut findings = Vec::new();
+        let active_file = |name: &str| {
+            active
+                .iter()
+                .find(|(filename, _)| *filename == name)
+                .map(|(_, path)| path)
+        };
+        match active_file("scopes.yaml") {
+            Some(path) => {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let tracked = tracked_files(git_repo.repository())?;
+                let tracked: Vec<&str> = tracked.iter().map(String::as_str).collect();
+                findings.extend(doctor::check_scopes(&content, &tracked));
+            }
+            None => println!(
+                "\nℹ️  No scopes.yaml; scopes are inferred from workspace packages, CODEOWNERS, and ecosystem defaults."
+            ),
+        }
+
+        let env = crate::utils::settings::SettingsEnv::load();
+        let registry = crate::claude::model_config::get_model_registry();
+        let backend = crate::claude::backend::resolve_backend(&env)?;
+        let model =
+            crate::claude::backend::resolve_model(backend, self.model.as_deref(), &env, registry);
+        let input_context = registry.get_input_context(&model);
+        for filename in ["commit-guidelines.md", "pr-guidelines.md"] {
+            if let Some(path) = active_file(filename) {
+                let content = std::fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                findings.extend(doctor::check_guidance_size(
+                    filename,
+                    &content,
+                    &model,
+                    input_context,
+                ));
+            }
+        }
+
+        report(&findings, "Project context")
+    }
+}
+
+impl ExportCommand {
+    /// Executes the export command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let context_dir = resolve_context_dir_at(self.context_dir.as_deref(), &repo_root);
+        let files = bundle::collect_files(&context_dir)?;
+        if files.is_empty() {
+            bail!(
+                "No shareable context files found in {}",
+                context_dir.display()
+            );
+        }
+
+        let file = std::fs::File::create(&self.output)
+            .with_context(|| format!("Failed to create {}", self.output.display()))?;
+        let manifest = bundle::write_bundle(&files, std::io::BufWriter::new(file))?;
+        for entry in &manifest.files {
+            println!("   📄 {}", entry.path);
+        }
+        println!(
+            "✅ Exported {} file(s) from {} to {}",
+            manifest.files.len(),
+            context_dir.display(),
+            self.output.display()
+        );
+        Ok(())
+    }
+}
+
+impl ImportCommand {
+    /// Executes the import command.
+    ///
+    /// Nothing is written when any existing file differs from the bundle,
+    /// unless `--force` is given.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let context_dir = resolve_context_dir_at(self.context_dir.as_deref(), &repo_root);
+        let file = std::fs::File::open(&self.bundle)
+            .with_context(|| format!("Failed to open {}", self.bundle.display()))?;
+        let (manifest, files) = bundle::read_bundle(std::io::BufReader::new(file))
+            .with_context(|| format!("Invalid context bundle {}", self.bundle.display()))?;
+        println!(
+            "📦 Bundle written by omni-dev {} at {} ({} file(s))",
+            manifest.omni_dev_version,
+            manifest.created_at,
+            manifest.files.len()
+        );
+
+        let mut conflicts = Vec::new();
+        let mut changes = Vec::new();
+        for (path, content) in &files {
+            let target = context_dir.join(path);
+            match std::fs::read(&target) {
+                Ok(existing) if existing == *content => println!("   ·  {path} (unchanged)"),
+                Ok(_) => {
+                    conflicts.push(path.as_str());
+                    changes.push((path, target, "overwrite"));
+                }
+                Err(_) => changes.push((path, target, "create")),
+            }
+        }
+        if !conflicts.is_empty() && !self.force {
+            bail!(
+                "{} already exist(s) in {} with different content; pass --force to overwrite",
+                conflicts.join(", "),
+                context_dir.display()
+            );
+        }
+
+        for (path, target, action) in &changes {
+            if self.dry_run {
+                println!("   📝 would {action} {path}");
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            std::fs::write(target, &files[path.as_str()])
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            let done = if *action == "create" {
+                "created"
+            } else {
+                "overwrote"
+            };
+            println!("   ✅ {done} {path}");
+        }
+        if self.dry_run {
+            println!("🔍 Dry run: nothing written");
+        } else {
+            println!(
+                "✅ Imported {} file(s) into {}",
+                changes.len(),
+                context_dir.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl UsePackCommand {
+    /// Executes the use-pack command.
+    ///
+    /// Sets `pack:` in the project's `scopes.yaml`, creating the file when
+    /// there is none, and leaves any scopes already listed there untouched.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let detected = detect_ecosystem(&repo_root);
+
+        let Some(name) = self.name else {
+            let detected_pack = scope_packs::for_ecosystem(&detected).map(|pack| pack.name);
+            for pack in scope_packs::PACKS {
+                let marker = if Some(pack.name) == detected_pack {
+                    " (detected)"
+                } else {
+                    ""
+                };
+                println!("   📦 {:<10} {}{marker}", pack.name, pack.description);
+            }
+            println!(
+                "   📦 {:<10} No ecosystem default scopes",
+                scope_packs::NO_PACK
+            );
+            return Ok(());
+        };
+
+        let name = name.trim().to_ascii_lowercase();
+        let pack = scope_packs::find(&name);
+        if pack.is_none() && name != scope_packs::NO_PACK {
+            bail!(
+                "Unknown scope pack '{name}' (expected one of: {}, {})",
+                scope_packs::names().join(", "),
+                scope_packs::NO_PACK
+            );
+        }
+
+        let context_dir = resolve_context_dir_at(self.context_dir.as_deref(), &repo_root);
+        let path = context_dir.join("scopes.yaml");
+        let existing = if path.exists() {
+            Some(
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?,
+            )
+        } else {
+            None
+        };
+        std::fs::create_dir_all(&context_dir).with_context(|| {
+            format!(
+                "Failed to create context directory: {}",
+                context_dir.display()
+            )
+        })?;
+        std::fs::write(&path, scope_packs::set_pack(existing.as_deref(), &name))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+
+        match pack {
+            Some(pack) => println!(
+                "✅ Using the '{}' scope pack ({} scopes) in {}",
+                pack.name,
+                pack.scopes().len(),
+                path.display()
+            ),
+            None => println!(
+                "✅ Turned off ecosystem default scopes in {}",
+                path.display()
+            ),
+        }
+        if existing.is_none() {
+            println!("📝 Scopes you add to scopes.yaml take precedence over the pack's.");
+        }
+        Ok(())
+    }
+}
+
+/// Prints the findings, errors first, and fails when there are errors,
+/// naming `subject` in the failure.
+pub(crate) fn report(findings: &[Finding], subject: &str) -> Result<()> {
+    println!();
+    if findings.is_empty() {
+        println!("✅ No problems found");
+        return Ok(());
+    }
+    let mut sorted: Vec<&Finding> = findings.iter().collect();
+    sorted.sort_by_key(|f| f.severity);
+    for finding in &sorted {
+        println!("{finding}");
+    }
+    let errors = findings
+        .iter()
+        .filter(|f| f.severity == Severity::Error)
+        .count();
+    let warnings = findings.len() - errors;
+    println!("\n{errors} error(s), {warnings} warning(s)");
+    if errors > 0 {
+        bail!("{subject} has {errors} error(s)");
+    }
+    Ok(())
+}
+
+/// Lists the paths in the git index.
+fn tracked_files(repo: &git2::Repository) -> Result<Vec<String>> {
+    let index = repo.index().context("Failed to read git index")?;
+    Ok(index
+        .iter()
+        .filter_map(|entry| String::from_utf8(entry.path).ok())
+        .collect())
+}
@@ -0,0 +1,33 @@
+//! Explain CLI commands.
+
+pub(crate) mod repo;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+/// Explain: onboarding overviews generated from the repository itself.
+#[derive(Parser)]
+pub struct ExplainCommand {
+    /// The explain subcommand to execute.
+    #[command(subcommand)]
+    pub command: ExplainSubcommands,
+}
+
+/// Explain subcommands.
+#[derive(Subcommand)]
+pub enum ExplainSubcommands {
+    /// Writes an onboarding document from a map of the repository's layout, entry points, and recent hot spots.
+    Repo(repo::ExplainRepoCommand),
+}
+
+impl ExplainCommand {
+    /// Executes the explain command.
+    ///
+    /// `repo` is the repository location resolved at the CLI boundary
+    /// (`None` = current working directory).
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        match self.command {
+            ExplainSubcommands::Repo(cmd) => cmd.execute(repo).await,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+//! Explain repo command — builds a condensed map of the repository (layout,
+//! entry points, recent hot spots) and has the AI turn it into an onboarding
+//! document for new contributors.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Serialize;
+
+use crate::cli::git::release_notes::strip_markdown_fence;
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::repo_map::{RepoMap, RepoMapOptions};
+use crate::git::{GeneratedFiles, GitRepository};
+use crate::utils::env::SystemEnv;
+
+/// Explain repo command options.
+#[derive(Parser)]
+pub struct ExplainRepoCommand {
+    /// Directory depth the layout is grouped at.
+    #[arg(long, value_name = "N", default_value_t = 2)]
+    pub depth: usize,
+
+    /// Counts hot spots from commits made since this date (`YYYY-MM-DD`,
+    /// RFC 3339, or a duration back from now such as `26w`).
+    #[arg(long, value_name = "WHEN", default_value = "26w")]
+    pub since: String,
+
+    /// Hot spots listed in the map.
+    #[arg(long, value_name = "N", default_value_t = 15)]
+    pub hot_spots: usize,
+
+    /// Prints the repository map without asking the AI for a document.
+    #[arg(long)]
+    pub no_ai: bool,
+}
+
+impl ExplainRepoCommand {
+    /// Executes the explain repo command.
+    ///
+    /// The document goes to stdout and progress to stderr, so it can be
+    /// redirected straight into e.g. `ONBOARDING.md`.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        if self.depth == 0 {
+            bail!("--depth must be at least 1");
+        }
+        let options = RepoMapOptions {
+            depth: self.depth,
+            since: Some(crate::cli::log::parse_time_bound(&self.since)?),
+            hot_spots: self.hot_spots,
+        };
+
+        eprintln!("🗺️  Mapping the repository...");
+        let map = RepoMap::build(
+            git_repo.repository(),
+            &GeneratedFiles::for_repo(&repo_root),
+            &options,
+        )?;
+        if map.files == 0 {
+            bail!("The repository has no tracked files to explain");
+        }
+
+        let document = if self.no_ai {
+            None
+        } else {
+            crate::utils::check_ai_command_prerequisites(None, &repo_root)?;
+            let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+            eprintln!(
+                "🤖 Writing the onboarding document from {} files and {} commits...",
+                map.files, map.commits
+            );
+            Some(explain_with_client(&claude_client, &map).await?)
+        };
+
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&RepoExplanation { map, document }, format)?);
+        Ok(())
+    }
+}
+
+/// The map and, unless `--no-ai`, the document written from it.
+#[derive(Serialize)]
+struct RepoExplanation {
+    map: RepoMap,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    document: Option<String>,
+}
+
+impl Render for RepoExplanation {
+    fn render_text(&self) -> Result<String> {
+        Ok(match &self.document {
+            Some(document) => document.clone(),
+            None => self.map.to_markdown().trim_end().to_string(),
+        })
+    }
+}
+
+/// Asks the AI to write the onboarding document from the repository map.
+pub(crate) async fn explain_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    map: &RepoMap,
+) -> Result<String> {
+    let user = crate::claude::prompts::generate_explain_repo_user_prompt(&map.to_markdown());
+    let response = claude_client
+        .send_message(crate::claude::prompts::EXPLAIN_REPO_SYSTEM_PROMPT, &user)
+        .await?;
+    let document = strip_markdown_fence(response.trim());
+    if document.is_empty() {
+        bail!("AI returned an empty onboarding document");
+    }
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::git::repo_map::{HotSpot, LayoutEntry};
+
+    fn map() -> RepoMap {
+        RepoMap {
+            name: "demo".to_string(),
+            ecosystem: "Rust".to_string(),
+            files: 3,
+            layout: vec![LayoutEntry {
+                dir: "src".to_string(),
+                files: 3,
+                extensions: vec![".rs".to_string()],
+            }],
+            entry_points: vec!["src/main.rs".to_string()],
+            readme: None,
+            since: Some("2026-04-01".to_string()),
+            commits: 4,
+            hot_spots: vec![HotSpot {
+                path: "src/cli.rs".to_string(),
+                commits: 4,
+                authors: 2,
+                last_changed: "2026-10-01".to_string(),
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn document_comes_from_ai_response() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "```markdown\n# demo\n\n## Overview\n\nA demo.\n```".to_string(),
+        )]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let document = explain_with_client(&client, &map()).await.unwrap();
+        assert_eq!(document, "# demo\n\n## Overview\n\nA demo.");
+
+        let sent = prompts.prompts();
+        let (system, user) = &sent[0];
+        assert_eq!(system, crate::claude::prompts::EXPLAIN_REPO_SYSTEM_PROMPT);
+        assert!(user.contains("- `src/main.rs`"));
+        assert!(user.contains("| `src/cli.rs` | 4 | 2 | 2026-10-01 |"));
+    }
+
+    #[test]
+    fn text_without_a_document_is_the_map() {
+        let explanation = RepoExplanation {
+            map: map(),
+            document: None,
+        };
+        let text = explanation.render_text().unwrap();
+        assert!(text.starts_with("# Repository map: demo"));
+        assert!(text.contains("## Hot spots since 2026-04-01 (4 commits)"));
+    }
+}
@@ -49,6 +49,27 @@ pub enum TableOrJson {
     Json,
 }
 
+/// A two-way `-o/--output` selector for commands whose default output is a
+/// YAML document, with JSON for consumers without a YAML parser.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum YamlOrJson {
+    /// YAML document.
+    #[default]
+    Yaml,
+    /// Pretty-printed JSON.
+    Json,
+}
+
+impl From<YamlOrJson> for crate::data::RenderFormat {
+    fn from(format: YamlOrJson) -> Self {
+        match format {
+            YamlOrJson::Yaml => Self::Yaml,
+            YamlOrJson::Json => Self::Json,
+        }
+    }
+}
+
 /// Writes a value as newline-terminated JSON Lines.
 ///
 /// For collection-like types, implementations emit one JSON object per
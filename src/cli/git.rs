@@ -1,27 +1,75 @@
 //! Git-related CLI commands.
 
 mod amend;
+mod backport;
+mod bisect_assist;
+mod branch_name;
+mod changelog;
 mod check;
+mod cleanup;
 mod create_pr;
+mod defaults;
+mod edit_pr;
 pub(crate) mod formatting;
 mod info;
+mod log_search;
+mod merge_assist;
+mod merge_pr;
+mod owners;
+mod ready;
+mod rebase_plan;
+mod release_create;
+pub(crate) mod release_notes;
+mod review;
+mod review_docs;
 mod staged;
+mod standup;
+mod stats;
+mod suggest_tests;
+mod summarize;
+mod tui;
 mod twiddle;
+mod update_pr;
 mod view;
+mod worklog;
 mod worktree;
 
 pub use amend::{run_amend, AmendCommand, AmendOutcome};
+pub use backport::BackportCommand;
+pub use bisect_assist::BisectAssistCommand;
+pub use branch_name::BranchNameCommand;
+pub use changelog::{ChangelogCheckCommand, ChangelogUpdateCommand};
 pub use check::{run_check, CheckCommand, CheckOutcome};
+pub use cleanup::CleanupCommand;
 pub use create_pr::{run_create_pr, CreatePrCommand, CreatePrOutcome, PrContent};
+pub use defaults::AutoApply;
+pub use edit_pr::EditPrCommand;
 pub use info::{run_info, InfoCommand};
+pub use log_search::LogSearchCommand;
+pub use merge_assist::MergeAssistCommand;
+pub use merge_pr::MergePrCommand;
+pub use owners::StatsOwnersCommand;
+pub use ready::ReadyCommand;
+pub use rebase_plan::RebasePlanCommand;
+pub use release_create::ReleaseCreateCommand;
+pub use release_notes::ReleaseNotesCommand;
+pub use review::ReviewStagedCommand;
+pub use review_docs::ReviewDocsCommand;
+pub(crate) use staged::{commit_with_message, has_staged_changes};
 pub use staged::{run_staged, StagedCommand, StagedOutcome};
+pub use standup::StandupCommand;
+pub use stats::StatsQualityCommand;
+pub use suggest_tests::SuggestTestsCommand;
+pub use summarize::SummarizeCommand;
 pub use twiddle::{run_twiddle, TwiddleCommand, TwiddleOutcome};
-pub use view::{run_view, ViewCommand};
+pub use update_pr::UpdatePrCommand;
+pub use view::{run_view, run_view_as, ViewCommand};
+pub use worklog::{WorklogExportCommand, WorklogFormat};
 pub use worktree::WorktreeCommand;
 
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 
 /// Reads one line of interactive input from `reader`.
@@ -54,6 +102,51 @@ pub(crate) fn default_commit_range(repo: &crate::git::GitRepository) -> Result<S
     }
 }
 
+/// Returns the commits whose analysis an `--update-view` run can take from
+/// the view saved at `path`; none when there is no saved view yet.
+pub(crate) fn saved_view_commits(
+    path: Option<&std::path::Path>,
+) -> Result<std::collections::HashMap<String, crate::git::CommitInfo>> {
+    match path {
+        Some(path) if path.exists() => {
+            Ok(crate::data::RepositoryView::load_from_file(path)?.into_reusable_commits())
+        }
+        _ => Ok(std::collections::HashMap::new()),
+    }
+}
+
+/// Starts scanning the working directory of the repository at `repo_root`
+/// on a blocking thread, so the scan overlaps with commit extraction. The
+/// returned future resolves to the repository view's working-directory
+/// section.
+pub(crate) fn spawn_working_directory_scan(
+    repo_root: &Path,
+) -> impl std::future::Future<Output = Result<crate::data::WorkingDirectoryInfo>> {
+    use crate::data::{FileStatusInfo, WorkingDirectoryInfo};
+
+    let root = repo_root.to_path_buf();
+    let task = tokio::task::spawn_blocking(move || {
+        let status = crate::git::GitRepository::open_at(&root)
+            .context("Failed to open git repository at the given path")?
+            .get_working_directory_status()?;
+        Ok(WorkingDirectoryInfo {
+            clean: status.clean,
+            untracked_changes: status
+                .untracked_changes
+                .into_iter()
+                .map(|fs| FileStatusInfo {
+                    status: fs.status,
+                    file: fs.file,
+                })
+                .collect(),
+        })
+    });
+    async move {
+        task.await
+            .unwrap_or_else(|err| Err(anyhow::anyhow!("blocking status task failed: {err}")))
+    }
+}
+
 /// Git operations.
 #[derive(Parser)]
 pub struct GitCommand {
@@ -71,6 +164,24 @@ pub enum GitSubcommands {
     Branch(BranchCommand),
     /// Worktree operations: logged wrappers over `git worktree`.
     Worktree(WorktreeCommand),
+    /// Release-related operations.
+    Release(ReleaseCommand),
+    /// Changelog operations.
+    Changelog(ChangelogCommand),
+    /// History operations.
+    Log(LogCommand),
+    /// Repository statistics.
+    Stats(StatsCommand),
+    /// Merge conflict operations.
+    Merge(GitMergeCommand),
+    /// Interactive rebase operations.
+    Rebase(RebaseCommand),
+    /// Bisect operations.
+    Bisect(BisectCommand),
+    /// Summarizes your recent commits by branch as a "what I did / what's next" standup update, in plain text or Slack format.
+    Standup(StandupCommand),
+    /// Time tracking from commit history.
+    Worklog(WorklogCommand),
 }
 
 /// Commit operations.
@@ -86,6 +197,42 @@ pub struct CommitCommand {
 pub enum CommitSubcommands {
     /// Commit message operations.
     Message(MessageCommand),
+    /// AI review of changes before they are committed.
+    Review(ReviewCommand),
+    /// AI suggestions for changes.
+    Suggest(SuggestCommand),
+}
+
+/// AI review of changes before they are committed.
+#[derive(Parser)]
+pub struct ReviewCommand {
+    /// Review subcommand to execute.
+    #[command(subcommand)]
+    pub command: ReviewSubcommands,
+}
+
+/// Review subcommands.
+#[derive(Subcommand)]
+pub enum ReviewSubcommands {
+    /// Checks whether changes to public APIs or CLI flags come with matching documentation changes.
+    Docs(ReviewDocsCommand),
+    /// Reviews the staged changes for likely bugs, missing tests, debug leftovers, and secrets before they are committed.
+    Staged(ReviewStagedCommand),
+}
+
+/// AI suggestions for changes.
+#[derive(Parser)]
+pub struct SuggestCommand {
+    /// Suggest subcommand to execute.
+    #[command(subcommand)]
+    pub command: SuggestSubcommands,
+}
+
+/// Suggest subcommands.
+#[derive(Subcommand)]
+pub enum SuggestSubcommands {
+    /// Proposes test cases for the functions a commit range or the staged changes add or change, as markdown or a patch of TODO comments.
+    Tests(SuggestTestsCommand),
 }
 
 /// Message operations.
@@ -126,6 +273,20 @@ pub enum BranchSubcommands {
     Info(InfoCommand),
     /// Create operations.
     Create(CreateCommand),
+    /// Update operations.
+    Update(UpdateCommand),
+    /// Edit operations.
+    Edit(EditCommand),
+    /// Merge operations.
+    Merge(MergeCommand),
+    /// Deletes local branches that are merged into main or whose pull requests were merged or closed.
+    Cleanup(CleanupCommand),
+    /// Summarizes what the current branch does — goal, major changes, and risky areas — without creating a PR.
+    Summarize(SummarizeCommand),
+    /// Marks the branch's draft pull request ready for review after re-checking commits, refreshing a stale description, and requesting reviewers.
+    Ready(ReadyCommand),
+    /// Cherry-picks a pull request's or commit range's commits onto a release branch and opens the backport pull request.
+    Backport(BackportCommand),
 }
 
 /// Create operations.
@@ -141,6 +302,180 @@ pub struct CreateCommand {
 pub enum CreateSubcommands {
     /// Creates a pull request with AI-generated description (mirrors the `git_create_pr` MCP tool).
     Pr(CreatePrCommand),
+    /// Suggests convention-compliant branch names from local changes or a description.
+    Name(BranchNameCommand),
+}
+
+/// Update operations.
+#[derive(Parser)]
+pub struct UpdateCommand {
+    /// Update subcommand to execute.
+    #[command(subcommand)]
+    pub command: UpdateSubcommands,
+}
+
+/// Update subcommands.
+#[derive(Subcommand)]
+pub enum UpdateSubcommands {
+    /// Regenerates the title and description of an existing pull request from the current branch.
+    Pr(UpdatePrCommand),
+}
+
+/// Edit operations.
+#[derive(Parser)]
+pub struct EditCommand {
+    /// Edit subcommand to execute.
+    #[command(subcommand)]
+    pub command: EditSubcommands,
+}
+
+/// Edit subcommands.
+#[derive(Subcommand)]
+pub enum EditSubcommands {
+    /// Opens an existing pull request's title and description in your editor and writes the result back.
+    Pr(EditPrCommand),
+}
+
+/// Merge operations.
+#[derive(Parser)]
+pub struct MergeCommand {
+    /// Merge subcommand to execute.
+    #[command(subcommand)]
+    pub command: MergeSubcommands,
+}
+
+/// Merge subcommands.
+#[derive(Subcommand)]
+pub enum MergeSubcommands {
+    /// Merges the current branch's pull request once CI checks and commit policy checks pass.
+    Pr(MergePrCommand),
+}
+
+/// Release operations.
+#[derive(Parser)]
+pub struct ReleaseCommand {
+    /// Release subcommand to execute.
+    #[command(subcommand)]
+    pub command: ReleaseSubcommands,
+}
+
+/// Release subcommands.
+#[derive(Subcommand)]
+pub enum ReleaseSubcommands {
+    /// Generates release notes in markdown from the commits in a range, grouped by type and scope.
+    Notes(ReleaseNotesCommand),
+    /// Tags the next version, pushes the tag, and publishes a release with generated notes.
+    Create(ReleaseCreateCommand),
+}
+
+/// Changelog operations.
+#[derive(Parser)]
+pub struct ChangelogCommand {
+    /// Changelog subcommand to execute.
+    #[command(subcommand)]
+    pub command: ChangelogSubcommands,
+}
+
+/// Changelog subcommands.
+#[derive(Subcommand)]
+pub enum ChangelogSubcommands {
+    /// Adds conventional commits since the last released version to the Unreleased section of CHANGELOG.md.
+    Update(ChangelogUpdateCommand),
+    /// Flags feature and fix commits that do not touch the changelog, and with --fix drafts their entries.
+    Check(ChangelogCheckCommand),
+}
+
+/// History operations.
+#[derive(Parser)]
+pub struct LogCommand {
+    /// Log subcommand to execute.
+    #[command(subcommand)]
+    pub command: LogSubcommands,
+}
+
+/// Log subcommands.
+#[derive(Subcommand)]
+pub enum LogSubcommands {
+    /// Searches commit messages, and optionally diffs, across history, or answers a question about it with AI.
+    Search(LogSearchCommand),
+}
+
+/// Merge operations on the repository (`git merge assist`), as opposed to
+/// merging pull requests (`git branch merge`).
+#[derive(Parser)]
+pub struct GitMergeCommand {
+    /// Merge subcommand to execute.
+    #[command(subcommand)]
+    pub command: GitMergeSubcommands,
+}
+
+/// Git merge subcommands.
+#[derive(Subcommand)]
+pub enum GitMergeSubcommands {
+    /// Proposes an AI resolution, with its rationale, for each conflict of a merge or rebase in progress and applies the ones you accept.
+    Assist(MergeAssistCommand),
+}
+
+/// Interactive rebase operations.
+#[derive(Parser)]
+pub struct RebaseCommand {
+    /// Rebase subcommand to execute.
+    #[command(subcommand)]
+    pub command: RebaseSubcommands,
+}
+
+/// Rebase subcommands.
+#[derive(Subcommand)]
+pub enum RebaseSubcommands {
+    /// Writes a `git rebase -i` todo list that reorders, squashes, fixes up, and rewords a commit range, and can start the rebase with it.
+    Plan(RebasePlanCommand),
+}
+
+/// Bisect operations.
+#[derive(Parser)]
+pub struct BisectCommand {
+    /// Bisect subcommand to execute.
+    #[command(subcommand)]
+    pub command: BisectSubcommands,
+}
+
+/// Bisect subcommands.
+#[derive(Subcommand)]
+pub enum BisectSubcommands {
+    /// Ranks the commits a bisect session has not ruled out by how plausibly each introduced the described bug, so the likeliest are tested first.
+    Assist(BisectAssistCommand),
+}
+
+/// Time tracking from commit history.
+#[derive(Parser)]
+pub struct WorklogCommand {
+    /// Worklog subcommand to execute.
+    #[command(subcommand)]
+    pub command: WorklogSubcommands,
+}
+
+/// Worklog subcommands.
+#[derive(Subcommand)]
+pub enum WorklogSubcommands {
+    /// Estimates the time spent per ticket and day from your commits and exports it as CSV or JSON for reconciling against time tracking.
+    Export(WorklogExportCommand),
+}
+
+/// Repository statistics.
+#[derive(Parser)]
+pub struct StatsCommand {
+    /// Stats subcommand to execute.
+    #[command(subcommand)]
+    pub command: StatsSubcommands,
+}
+
+/// Stats subcommands.
+#[derive(Subcommand)]
+pub enum StatsSubcommands {
+    /// Scores historical commit messages against the offline rules and shows the trend per period and author or scope.
+    Quality(StatsQualityCommand),
+    /// Reports who owns each area of the repository from blame, commit frequency, and recency, as markdown, YAML, or a CODEOWNERS file.
+    Owners(StatsOwnersCommand),
 }
 
 impl GitCommand {
@@ -154,6 +489,89 @@ impl GitCommand {
             GitSubcommands::Commit(commit_cmd) => commit_cmd.execute(repo).await,
             GitSubcommands::Branch(branch_cmd) => branch_cmd.execute(repo).await,
             GitSubcommands::Worktree(worktree_cmd) => worktree_cmd.execute(repo),
+            GitSubcommands::Release(release_cmd) => release_cmd.execute(repo).await,
+            GitSubcommands::Changelog(changelog_cmd) => changelog_cmd.execute(repo),
+            GitSubcommands::Log(log_cmd) => log_cmd.execute(repo).await,
+            GitSubcommands::Stats(stats_cmd) => stats_cmd.execute(repo),
+            GitSubcommands::Merge(merge_cmd) => merge_cmd.execute(repo).await,
+            GitSubcommands::Rebase(rebase_cmd) => rebase_cmd.execute(repo).await,
+            GitSubcommands::Bisect(bisect_cmd) => bisect_cmd.execute(repo).await,
+            GitSubcommands::Standup(standup_cmd) => standup_cmd.execute(repo).await,
+            GitSubcommands::Worklog(worklog_cmd) => worklog_cmd.execute(repo),
+        }
+    }
+}
+
+impl LogCommand {
+    /// Executes the log command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            LogSubcommands::Search(search_cmd) => search_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl GitMergeCommand {
+    /// Executes the merge command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            GitMergeSubcommands::Assist(assist_cmd) => assist_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl RebaseCommand {
+    /// Executes the rebase command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            RebaseSubcommands::Plan(plan_cmd) => plan_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl BisectCommand {
+    /// Executes the bisect command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            BisectSubcommands::Assist(assist_cmd) => assist_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl WorklogCommand {
+    /// Executes the worklog command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            WorklogSubcommands::Export(export_cmd) => export_cmd.execute(repo),
+        }
+    }
+}
+
+impl StatsCommand {
+    /// Executes the stats command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            StatsSubcommands::Quality(quality_cmd) => quality_cmd.execute(repo),
+            StatsSubcommands::Owners(owners_cmd) => owners_cmd.execute(repo),
+        }
+    }
+}
+
+impl ReviewCommand {
+    /// Executes the review command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            ReviewSubcommands::Docs(docs_cmd) => docs_cmd.execute(repo).await,
+            ReviewSubcommands::Staged(staged_cmd) => staged_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl SuggestCommand {
+    /// Executes the suggest command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            SuggestSubcommands::Tests(tests_cmd) => tests_cmd.execute(repo).await,
         }
     }
 }
@@ -163,6 +581,8 @@ impl CommitCommand {
     pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
         match self.command {
             CommitSubcommands::Message(message_cmd) => message_cmd.execute(repo).await,
+            CommitSubcommands::Review(review_cmd) => review_cmd.execute(repo).await,
+            CommitSubcommands::Suggest(suggest_cmd) => suggest_cmd.execute(repo).await,
         }
     }
 }
@@ -184,8 +604,15 @@ impl BranchCommand {
     /// Executes the branch command.
     pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
         match self.command {
-            BranchSubcommands::Info(info_cmd) => info_cmd.execute(repo),
+            BranchSubcommands::Info(info_cmd) => info_cmd.execute(repo).await,
             BranchSubcommands::Create(create_cmd) => create_cmd.execute(repo).await,
+            BranchSubcommands::Update(update_cmd) => update_cmd.execute(repo).await,
+            BranchSubcommands::Edit(edit_cmd) => edit_cmd.execute(repo).await,
+            BranchSubcommands::Merge(merge_cmd) => merge_cmd.execute(repo).await,
+            BranchSubcommands::Cleanup(cleanup_cmd) => cleanup_cmd.execute(repo).await,
+            BranchSubcommands::Summarize(summarize_cmd) => summarize_cmd.execute(repo).await,
+            BranchSubcommands::Ready(ready_cmd) => ready_cmd.execute(repo).await,
+            BranchSubcommands::Backport(backport_cmd) => backport_cmd.execute(repo).await,
         }
     }
 }
@@ -195,6 +622,54 @@ impl CreateCommand {
     pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
         match self.command {
             CreateSubcommands::Pr(pr_cmd) => pr_cmd.execute(repo).await,
+            CreateSubcommands::Name(name_cmd) => name_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl UpdateCommand {
+    /// Executes the update command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            UpdateSubcommands::Pr(pr_cmd) => pr_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl EditCommand {
+    /// Executes the edit command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            EditSubcommands::Pr(pr_cmd) => pr_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl MergeCommand {
+    /// Executes the merge command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            MergeSubcommands::Pr(pr_cmd) => pr_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl ReleaseCommand {
+    /// Executes the release command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            ReleaseSubcommands::Notes(notes_cmd) => notes_cmd.execute(repo).await,
+            ReleaseSubcommands::Create(create_cmd) => create_cmd.execute(repo).await,
+        }
+    }
+}
+
+impl ChangelogCommand {
+    /// Executes the changelog command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        match self.command {
+            ChangelogSubcommands::Update(update_cmd) => update_cmd.execute(repo),
+            ChangelogSubcommands::Check(check_cmd) => check_cmd.execute(repo),
         }
     }
 }
@@ -233,6 +708,30 @@ mod tests {
         assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
     }
 
+    #[test]
+    fn cli_parses_git_commit_message_check_comment_pr() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "commit",
+            "message",
+            "check",
+            "--comment-pr",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "commit",
+            "message",
+            "check",
+            "--comment-pr",
+            "12",
+            "HEAD~2..HEAD",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
     #[test]
     fn cli_parses_git_branch_info() {
         let cli = Cli::try_parse_from(["omni-dev", "git", "branch", "info"]);
@@ -245,6 +744,178 @@ mod tests {
         assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
     }
 
+    #[test]
+    fn cli_parses_git_branch_update_pr_with_number() {
+        let cli = Cli::try_parse_from([
+            "omni-dev", "git", "branch", "update", "pr", "--number", "42",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_edit_pr_with_number() {
+        let cli =
+            Cli::try_parse_from(["omni-dev", "git", "branch", "edit", "pr", "--number", "42"]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_create_pr_with_repeated_fixes() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "create",
+            "pr",
+            "--fixes",
+            "123",
+            "--fixes",
+            "acme/api#45",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_create_name() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "create",
+            "name",
+            "add retry to uploads",
+            "--ticket",
+            "PROJ-12",
+            "--create",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_summarize_with_base() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "summarize",
+            "develop",
+            "--from-commits",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_ready() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "ready",
+            "--number",
+            "42",
+            "--strict",
+            "--no-reviewers",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_backport() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "backport",
+            "#123",
+            "--to",
+            "release/1.2",
+            "--no-pr",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+        assert!(Cli::try_parse_from(["omni-dev", "git", "branch", "backport", "123"]).is_err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_cleanup() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "cleanup",
+            "--dry-run",
+            "--remote",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_branch_merge_pr_with_strategy() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "merge",
+            "pr",
+            "--strategy",
+            "rebase",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "branch",
+            "merge",
+            "pr",
+            "--strategy",
+            "octopus",
+        ]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn cli_parses_git_release_notes() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "release",
+            "notes",
+            "v1.2.0..v1.3.0",
+            "--no-ai",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+        let cli = Cli::try_parse_from(["omni-dev", "git", "release", "notes"]);
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn cli_parses_git_release_create() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "release",
+            "create",
+            "--version",
+            "2.0.0",
+            "--prerelease",
+            "--auto-apply",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
+    #[test]
+    fn cli_parses_git_changelog_update() {
+        let cli = Cli::try_parse_from([
+            "omni-dev",
+            "git",
+            "changelog",
+            "update",
+            "--from",
+            "v1.2.0",
+            "--dry-run",
+        ]);
+        assert!(cli.is_ok(), "Failed to parse: {:?}", cli.err());
+    }
+
     #[test]
     fn cli_parses_config_models_show() {
         let cli = Cli::try_parse_from(["omni-dev", "config", "models", "show"]);
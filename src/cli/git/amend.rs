@@ -1,4 +1,5 @@
-//! Amend command — applies commit message amendments from a YAML file.
+//! Amend command — applies commit message amendments from a YAML file or
+//! stdin.
 
 use std::path::Path;
 
@@ -8,7 +9,7 @@ use clap::Parser;
 /// Amend command options.
 #[derive(Parser)]
 pub struct AmendCommand {
-    /// YAML file containing commit amendments.
+    /// YAML file containing commit amendments (`-` reads it from stdin).
     #[arg(value_name = "YAML_FILE")]
     pub yaml_file: String,
 
@@ -37,7 +38,12 @@ impl AmendCommand {
         crate::utils::check_working_directory_clean_at(&repo_root)?;
 
         println!("🔄 Starting commit amendment process...");
-        println!("📄 Loading amendments from: {}", self.yaml_file);
+        let source = if self.yaml_file == "-" {
+            "stdin"
+        } else {
+            &self.yaml_file
+        };
+        println!("📄 Loading amendments from: {source}");
 
         // Create amendment handler and apply amendments
         let handler = AmendmentHandler::new(&repo_root)
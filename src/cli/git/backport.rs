@@ -0,0 +1,488 @@
+//! Backport command — cherry-picks a pull request's (or a commit range's)
+//! commits onto a new branch off a release branch, annotates their messages
+//! with the backport source, and opens the backport pull request.
+
+use std::fmt;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde_json::Value;
+
+use super::defaults::AutoApply;
+use super::formatting::truncate_hash;
+use crate::data::amendments::{Amendment, AmendmentFile};
+use crate::forge::Forge;
+use crate::git::{AmendmentHandler, CommitInfo, GitRepository};
+
+/// Remote the release branch is read from and the backport branch pushed to.
+const REMOTE: &str = "origin";
+
+/// Branch backport command options.
+#[derive(Parser)]
+pub struct BackportCommand {
+    /// Pull request number (123 or #123) or commit range (A..B) to backport.
+    #[arg(value_name = "PR_OR_RANGE")]
+    pub source: String,
+
+    /// Release branch to backport onto.
+    #[arg(long, value_name = "BRANCH")]
+    pub to: String,
+
+    /// Name of the backport branch (defaults to backport/<PR or hash>-to-<BRANCH>).
+    #[arg(long, value_name = "NAME")]
+    pub branch: Option<String>,
+
+    /// Creates the backport branch locally without pushing it or opening a PR.
+    #[arg(long)]
+    pub no_pr: bool,
+
+    #[command(flatten)]
+    pub apply: AutoApply,
+}
+
+/// What to backport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BackportSource {
+    /// The commits of a pull request.
+    Pr(u64),
+    /// The commits in a range such as `v1.2.0..main`.
+    Range(String),
+}
+
+impl BackportSource {
+    /// Parses `123`, `#123`, or a commit range `A..B`.
+    fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if let Ok(number) = value.trim_start_matches('#').parse() {
+            return Ok(Self::Pr(number));
+        }
+        if value.contains("..") {
+            return Ok(Self::Range(value.to_string()));
+        }
+        bail!("'{value}' is neither a pull request number (123, #123) nor a commit range (A..B)")
+    }
+
+    /// The annotation appended to the subject of a backported commit.
+    fn annotation(&self, original_hash: &str) -> String {
+        match self {
+            Self::Pr(number) => format!("(backport #{number})"),
+            Self::Range(_) => format!("(backport of {})", truncate_hash(original_hash)),
+        }
+    }
+}
+
+impl fmt::Display for BackportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pr(number) => write!(f, "#{number}"),
+            Self::Range(range) => write!(f, "{range}"),
+        }
+    }
+}
+
+impl BackportCommand {
+    /// Executes the backport command.
+    ///
+    /// Nothing is changed until the commits are resolved and the backport is
+    /// confirmed. A cherry-pick conflict leaves the repository as it was.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let source = BackportSource::parse(&self.source)?;
+        crate::utils::preflight::check_working_directory_clean_at(repo_root)
+            .context("Cannot backport with uncommitted changes")?;
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let start = release_start(&git_repo, &self.to)?;
+
+        let needs_forge = !self.no_pr || matches!(source, BackportSource::Pr(_));
+        let forge = Forge::detect(repo_root);
+        let forge_api = if needs_forge {
+            forge.api_client()?
+        } else {
+            None
+        };
+
+        let originals = match &source {
+            BackportSource::Pr(number) => {
+                let hashes = match forge_api.as_deref() {
+                    Some(api) => api
+                        .pr_commits(*number)
+                        .await
+                        .with_context(|| format!("Failed to list the commits of PR #{number}"))?,
+                    None => gh_pr_commits(repo_root, *number)?,
+                };
+                ensure_local_commits(&git_repo, *number, &hashes)?;
+                git_repo.get_commits_by_hashes(&history_order(&git_repo, &hashes)?)?
+            }
+            BackportSource::Range(range) => git_repo
+                .get_commits_in_range(range)
+                .with_context(|| format!("Failed to read commits in {range}"))?,
+        };
+        if originals.is_empty() {
+            bail!("No commits to backport in {source}");
+        }
+
+        let last = originals.last().map_or("", |c| c.hash.as_str());
+        let branch = self
+            .branch
+            .clone()
+            .unwrap_or_else(|| default_branch_name(&source, last, &self.to));
+        println!(
+            "🍒 Backporting {} commit(s) from {source} onto {start} as {branch}:",
+            originals.len()
+        );
+        for commit in &originals {
+            println!(
+                "   {} {}",
+                truncate_hash(&commit.hash),
+                commit.original_message.lines().next().unwrap_or("").trim()
+            );
+        }
+        if !self.apply.assumed() && !confirm_backport(&branch)? {
+            println!("❌ Backport cancelled by user");
+            return Ok(());
+        }
+
+        let hashes: Vec<String> = originals.iter().map(|c| c.hash.clone()).collect();
+        git_repo.cherry_pick_onto(&branch, &start, &hashes)?;
+        println!("✓ Cherry-picked onto {branch}");
+
+        let picked = git_repo.get_commits_in_range(&format!("{start}..HEAD"))?;
+        if picked.len() != originals.len() {
+            bail!(
+                "Expected {} backported commit(s) on {branch} but found {}; messages were not annotated.",
+                originals.len(),
+                picked.len()
+            );
+        }
+        let amendments = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
+            amendments: originals
+                .iter()
+                .zip(&picked)
+                .map(|(original, new)| Amendment {
+                    commit: new.hash.clone(),
+                    message: annotate_message(
+                        &original.original_message,
+                        &source.annotation(&original.hash),
+                    ),
+                    summary: String::new(),
+                })
+                .collect(),
+        };
+        AmendmentHandler::new(repo_root)?.apply_amendment_file(&amendments)?;
+
+        if self.no_pr {
+            println!(
+                "✅ Backport branch {branch} is ready; push it and open a PR against {}",
+                self.to
+            );
+            return Ok(());
+        }
+
+        git_repo.push_branch(&branch, REMOTE)?;
+        let title = backport_title(&source, &amendments.amendments, &self.to);
+        let description = backport_description(&source, &originals, &self.to);
+        let url = match forge_api.as_deref() {
+            Some(api) => {
+                println!("🚀 Opening {} backport pull request...", api.forge_name());
+                api.create_pr(&branch, Some(&self.to), &title, &description, false)
+                    .await
+                    .context("Failed to create backport pull request")?
+                    .url
+            }
+            None => gh_create_pr(repo_root, &branch, &self.to, &title, &description)?,
+        };
+        println!("🎉 Backport pull request created: {url}");
+        Ok(())
+    }
+}
+
+/// Returns the ref the backport branch starts from: `origin/<branch>` when
+/// fetched, else the local branch.
+fn release_start(repo: &GitRepository, branch: &str) -> Result<String> {
+    let remote = format!("{REMOTE}/{branch}");
+    if repo
+        .repository()
+        .find_reference(&format!("refs/remotes/{remote}"))
+        .is_ok()
+    {
+        return Ok(remote);
+    }
+    if repo.branch_exists(branch)? {
+        return Ok(branch.to_string());
+    }
+    bail!("Release branch '{branch}' does not exist (also tried '{remote}'); fetch it first")
+}
+
+/// Makes sure every commit of PR `number` is in the local object database,
+/// fetching the forge's `refs/pull/<number>/head` ref when some are missing.
+fn ensure_local_commits(repo: &GitRepository, number: u64, hashes: &[String]) -> Result<()> {
+    let missing = |repo: &GitRepository| {
+        hashes
+            .iter()
+            .find(|hash| {
+                git2::Oid::from_str(hash)
+                    .and_then(|oid| repo.repository().find_commit(oid))
+                    .is_err()
+            })
+            .cloned()
+    };
+    if missing(repo).is_none() {
+        return Ok(());
+    }
+    let refspec = format!("refs/pull/{number}/head");
+    if let Err(e) = repo.fetch_ref(&refspec, REMOTE) {
+        tracing::debug!("Could not fetch {refspec}: {e:#}");
+    }
+    match missing(repo) {
+        None => Ok(()),
+        Some(hash) => bail!(
+            "Commit {} of PR #{number} is not available locally; fetch the PR's branch first",
+            truncate_hash(&hash)
+        ),
+    }
+}
+
+/// Orders `hashes` as they appear in history, oldest first.
+fn history_order(repo: &GitRepository, hashes: &[String]) -> Result<Vec<String>> {
+    let git = repo.repository();
+    let mut revwalk = git.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+    for hash in hashes {
+        revwalk.push(git2::Oid::from_str(hash)?)?;
+    }
+    let mut ordered = Vec::new();
+    for oid in revwalk {
+        let hash = oid?.to_string();
+        if hashes.contains(&hash) {
+            ordered.push(hash);
+            if ordered.len() == hashes.len() {
+                break;
+            }
+        }
+    }
+    ordered.reverse();
+    Ok(ordered)
+}
+
+/// Appends `annotation` to the subject line of `message`, keeping the body.
+fn annotate_message(message: &str, annotation: &str) -> String {
+    let (subject, body) = crate::forge::split_commit_message(message);
+    let subject = if subject.ends_with(annotation) {
+        subject.to_string()
+    } else {
+        format!("{subject} {annotation}")
+    };
+    if body.is_empty() {
+        subject
+    } else {
+        format!("{subject}\n\n{body}")
+    }
+}
+
+/// Default backport branch name, e.g. `backport/123-to-release-1.2`.
+fn default_branch_name(source: &BackportSource, last_hash: &str, to: &str) -> String {
+    let id = match source {
+        BackportSource::Pr(number) => number.to_string(),
+        BackportSource::Range(_) => truncate_hash(last_hash).to_string(),
+    };
+    format!("backport/{id}-to-{}", to.replace('/', "-"))
+}
+
+/// Title of the backport PR: the annotated subject for a single commit, else
+/// a summary naming the source.
+fn backport_title(source: &BackportSource, amendments: &[Amendment], to: &str) -> String {
+    match (amendments, source) {
+        ([only], _) => crate::forge::split_commit_message(&only.message)
+            .0
+            .to_string(),
+        (_, BackportSource::Pr(number)) => format!("Backport #{number} to {to}"),
+        (_, BackportSource::Range(_)) => {
+            format!("Backport {} commits to {to}", amendments.len())
+        }
+    }
+}
+
+/// Description of the backport PR, listing the original commits.
+fn backport_description(source: &BackportSource, originals: &[CommitInfo], to: &str) -> String {
+    let source = match source {
+        BackportSource::Pr(number) => format!("#{number}"),
+        BackportSource::Range(range) => format!("`{range}`"),
+    };
+    let mut description = format!("Backport of {source} to `{to}`.\n\n### Commits\n");
+    for commit in originals {
+        description.push_str(&format!(
+            "- `{}` {}\n",
+            truncate_hash(&commit.hash),
+            commit.original_message.lines().next().unwrap_or("").trim()
+        ));
+    }
+    description
+}
+
+/// Lists the commits of PR `number` with `gh pr view` (the fallback when no
+/// GitHub token resolves for the native API client).
+fn gh_pr_commits(repo_root: &std::path::Path, number: u64) -> Result<Vec<String>> {
+    let number = number.to_string();
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        ["pr", "view", number.as_str(), "--json", "commits"],
+        "pr view",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "Failed to list the commits of PR #{number}: {}",
+            error_msg.trim()
+        );
+    }
+    let response: Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh pr view output")?;
+    let commits = response
+        .get("commits")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    Ok(crate::forge::commit_hashes(commits, "/oid"))
+}
+
+/// Opens the backport PR with `gh pr create` and returns its URL.
+fn gh_create_pr(
+    repo_root: &std::path::Path,
+    branch: &str,
+    base: &str,
+    title: &str,
+    description: &str,
+) -> Result<String> {
+    println!("🚀 Opening backport pull request via gh...");
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        [
+            "pr",
+            "create",
+            "--head",
+            branch,
+            "--base",
+            base,
+            "--title",
+            title,
+            "--body",
+            description,
+        ],
+        "pr create",
+        Some(repo_root),
+    )
+    .context("Failed to create backport pull request")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "Failed to create backport pull request: {}",
+            error_msg.trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Asks whether to create backport branch `branch`.
+fn confirm_backport(branch: &str) -> Result<bool> {
+    use std::io::{self, Write};
+
+    print!(
+        "❓ {} ",
+        crate::utils::i18n::tr("confirm-backport", &[("branch", &branch)])
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(crate::utils::i18n::is_yes(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn amendment(message: &str) -> Amendment {
+        Amendment {
+            commit: "a".repeat(40),
+            message: message.to_string(),
+            summary: String::new(),
+        }
+    }
+
+    #[test]
+    fn parses_backport_sources() {
+        assert_eq!(
+            BackportSource::parse("123").unwrap(),
+            BackportSource::Pr(123)
+        );
+        assert_eq!(BackportSource::parse("#7").unwrap(), BackportSource::Pr(7));
+        assert_eq!(
+            BackportSource::parse("v1.2.0..main").unwrap(),
+            BackportSource::Range("v1.2.0..main".to_string())
+        );
+        assert!(BackportSource::parse("main").is_err());
+    }
+
+    #[test]
+    fn annotates_subject_once_and_keeps_body() {
+        assert_eq!(
+            annotate_message("fix(api): handle nulls\n\nDetails.\n", "(backport #12)"),
+            "fix(api): handle nulls (backport #12)\n\nDetails."
+        );
+        assert_eq!(
+            annotate_message("fix: x (backport #12)", "(backport #12)"),
+            "fix: x (backport #12)"
+        );
+        assert_eq!(
+            BackportSource::Range("a..b".to_string()).annotation("0123456789abcdef"),
+            "(backport of 01234567)"
+        );
+    }
+
+    #[test]
+    fn names_backport_branch_after_source() {
+        assert_eq!(
+            default_branch_name(&BackportSource::Pr(12), "", "release/1.2"),
+            "backport/12-to-release-1.2"
+        );
+        assert_eq!(
+            default_branch_name(
+                &BackportSource::Range("a..b".to_string()),
+                "0123456789abcdef",
+                "stable"
+            ),
+            "backport/01234567-to-stable"
+        );
+    }
+
+    #[test]
+    fn titles_backport_pr() {
+        let pr = BackportSource::Pr(12);
+        assert_eq!(
+            backport_title(&pr, &[amendment("fix: x (backport #12)\n\nBody")], "stable"),
+            "fix: x (backport #12)"
+        );
+        assert_eq!(
+            backport_title(&pr, &[amendment("a"), amendment("b")], "stable"),
+            "Backport #12 to stable"
+        );
+        assert_eq!(
+            backport_title(
+                &BackportSource::Range("a..b".to_string()),
+                &[amendment("a"), amendment("b")],
+                "stable"
+            ),
+            "Backport 2 commits to stable"
+        );
+    }
+}
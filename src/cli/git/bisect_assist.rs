@@ -0,0 +1,442 @@
+//! `omni-dev git bisect assist` — ranks the commits a bisect session has not
+//! ruled out yet by how plausibly each introduced the described bug.
+//!
+//! The session itself is left alone: the ranking only suggests which commit
+//! to check out and test next, and the user still marks it with
+//! `git bisect good` or `git bisect bad`.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use super::formatting::truncate_hash;
+use super::release_notes::strip_markdown_fence;
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::bisect::BisectSession;
+use crate::git::{CommitInfo, GitRepository};
+use crate::utils::env::SystemEnv;
+use crate::utils::redact::redact_secrets;
+
+/// Commits handed to the AI when `--limit` is not given.
+const DEFAULT_LIMIT: usize = 30;
+
+/// Maximum characters of diff sent to the AI, shared between the commits.
+const MAX_DIFF_CHARS: usize = 60_000;
+
+/// `omni-dev git bisect assist` CLI command.
+#[derive(Parser)]
+pub struct BisectAssistCommand {
+    /// What the bug looks like, e.g. "login fails with a 500 after signing in".
+    #[arg(value_name = "BUG")]
+    pub bug: String,
+
+    /// Maximum number of remaining commits handed to the AI, newest first.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_LIMIT)]
+    pub limit: usize,
+
+    /// Hands the AI commit messages and file lists only, without diffs.
+    #[arg(long)]
+    pub no_diff: bool,
+}
+
+/// How plausibly a commit introduced the bug.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Likelihood {
+    /// The change touches what the symptom points to.
+    High,
+    /// The change could plausibly cause the symptom.
+    Medium,
+    /// Little or nothing ties the change to the symptom.
+    Low,
+}
+
+impl Likelihood {
+    /// Lowercase name, as printed.
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+        }
+    }
+}
+
+/// The AI's assessment of one commit.
+#[derive(Debug, Clone, Deserialize)]
+struct Assessment {
+    commit: String,
+    #[serde(default)]
+    summary: String,
+    likelihood: Likelihood,
+    #[serde(default)]
+    reason: String,
+}
+
+/// The AI's response.
+#[derive(Deserialize)]
+struct AssessmentResponse {
+    #[serde(default)]
+    candidates: Vec<Assessment>,
+}
+
+/// A remaining commit, ranked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RankedCommit {
+    /// Full hash of the commit.
+    pub hash: String,
+    /// First line of the commit message.
+    pub subject: String,
+    /// What the commit changes.
+    pub summary: String,
+    /// How plausibly it introduced the bug.
+    pub likelihood: Likelihood,
+    /// Why.
+    pub reason: String,
+    /// Whether the commit was skipped as untestable in this session.
+    pub skipped: bool,
+}
+
+/// The ranking, as printed.
+#[derive(Serialize)]
+struct BisectRanking {
+    bug: String,
+    /// Commits the session has not ruled out, including those not handed to
+    /// the AI.
+    remaining: usize,
+    ranked: Vec<RankedCommit>,
+    /// Commits handed to the AI that it did not rank.
+    unranked: Vec<String>,
+    #[serde(skip)]
+    bad_term: String,
+    #[serde(skip)]
+    good_term: String,
+}
+
+impl Render for BisectRanking {
+    fn render_text(&self) -> Result<String> {
+        let mut out = format!(
+            "🔎 {} commit(s) not ruled out; most plausible first:\n",
+            self.remaining
+        );
+        for (index, commit) in self.ranked.iter().enumerate() {
+            out.push_str(&format!(
+                "\n{}. {} [{}] {}{}\n",
+                index + 1,
+                truncate_hash(&commit.hash),
+                commit.likelihood.as_str(),
+                commit.subject,
+                if commit.skipped { " (skipped)" } else { "" }
+            ));
+            if !commit.summary.is_empty() {
+                out.push_str(&format!("   {}\n", commit.summary));
+            }
+            if !commit.reason.is_empty() {
+                out.push_str(&format!("   Why: {}\n", commit.reason));
+            }
+        }
+        if !self.unranked.is_empty() {
+            let unranked: Vec<&str> = self.unranked.iter().map(|h| truncate_hash(h)).collect();
+            out.push_str(&format!("\nNot ranked: {}\n", unranked.join(", ")));
+        }
+        if let Some(top) = self.ranked.iter().find(|c| !c.skipped) {
+            out.push_str(&format!(
+                "\n💡 Test the top commit with `git checkout {}`, then mark it with \
+                 `git bisect {}` or `git bisect {}`.",
+                truncate_hash(&top.hash),
+                self.bad_term,
+                self.good_term
+            ));
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+impl BisectAssistCommand {
+    /// Executes the bisect assist command.
+    ///
+    /// Results go to stdout and progress to stderr; `--output yaml|json`
+    /// prints the ranking as structured data.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+
+        let Some(session) = BisectSession::read(git_repo.repository())? else {
+            bail!("No bisect in progress. Start one with `git bisect start <bad> <good>`.");
+        };
+        if session.bad.is_none() || session.good.is_empty() {
+            bail!(
+                "Mark a {} and a {} commit first, with `git bisect {} <rev>` and `git bisect {} <rev>`",
+                session.bad_term,
+                session.good_term,
+                session.bad_term,
+                session.good_term
+            );
+        }
+        let suspects = session.suspects(git_repo.repository())?;
+        if suspects.is_empty() {
+            bail!(
+                "No commits are left between the {} and {} commits",
+                session.good_term,
+                session.bad_term
+            );
+        }
+        if let [only] = suspects.as_slice() {
+            println!(
+                "🎯 Only {} is left: it is the first {} commit.",
+                truncate_hash(only),
+                session.bad_term
+            );
+            return Ok(());
+        }
+        let limit = self.limit.max(1);
+        if suspects.len() > limit {
+            eprintln!(
+                "⚠️  {} commits are left; ranking the newest {limit} (raise --limit for more)",
+                suspects.len()
+            );
+        }
+        let hashes: Vec<String> = suspects.iter().take(limit).cloned().collect();
+        let commits = git_repo.get_commits_by_hashes(&hashes)?;
+        let diffs = if self.no_diff {
+            None
+        } else {
+            Some(read_diffs(&commits)?)
+        };
+
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        eprintln!("🤖 Ranking {} commit(s)...", commits.len());
+        let (ranked, unranked) = rank_with_client(
+            &claude_client,
+            &self.bug,
+            &commits,
+            diffs.as_deref(),
+            &session,
+        )
+        .await?;
+
+        let ranking = BisectRanking {
+            bug: self.bug,
+            remaining: suspects.len(),
+            ranked,
+            unranked,
+            bad_term: session.bad_term,
+            good_term: session.good_term,
+        };
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&ranking, format)?);
+        Ok(())
+    }
+}
+
+/// Asks the AI to rank `commits` and returns them most plausible first, with
+/// the hashes it left out.
+pub(crate) async fn rank_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    bug: &str,
+    commits: &[CommitInfo],
+    diffs: Option<&[String]>,
+    session: &BisectSession,
+) -> Result<(Vec<RankedCommit>, Vec<String>)> {
+    let user = crate::claude::prompts::generate_bisect_assist_user_prompt(
+        bug,
+        &format_commits(commits, diffs, session),
+    );
+    let response = claude_client
+        .send_message(crate::claude::prompts::BISECT_ASSIST_SYSTEM_PROMPT, &user)
+        .await?;
+    let response: AssessmentResponse = serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned a ranking that is not valid YAML")?;
+
+    let mut ranked: Vec<RankedCommit> = Vec::new();
+    for assessment in response.candidates {
+        let prefix = assessment.commit.trim();
+        let Some(commit) = commits
+            .iter()
+            .find(|c| prefix.len() >= 4 && c.hash.starts_with(prefix))
+        else {
+            continue;
+        };
+        if ranked.iter().any(|r| r.hash == commit.hash) {
+            continue;
+        }
+        ranked.push(RankedCommit {
+            hash: commit.hash.clone(),
+            subject: subject(commit).to_string(),
+            summary: assessment.summary.trim().to_string(),
+            likelihood: assessment.likelihood,
+            reason: assessment.reason.trim().to_string(),
+            skipped: session.is_skipped(&commit.hash),
+        });
+    }
+    if ranked.is_empty() {
+        bail!("AI returned a ranking that names none of the remaining commits");
+    }
+    let unranked = commits
+        .iter()
+        .filter(|c| !ranked.iter().any(|r| r.hash == c.hash))
+        .map(|c| c.hash.clone())
+        .collect();
+    Ok((ranked, unranked))
+}
+
+/// The first line of the commit's message.
+fn subject(commit: &CommitInfo) -> &str {
+    commit.original_message.lines().next().unwrap_or("").trim()
+}
+
+/// Lists the commits with their messages, files, and diffs when given.
+fn format_commits(
+    commits: &[CommitInfo],
+    diffs: Option<&[String]>,
+    session: &BisectSession,
+) -> String {
+    let mut out = String::new();
+    for (index, commit) in commits.iter().enumerate() {
+        let skipped = if session.is_skipped(&commit.hash) {
+            " (skipped: cannot be tested)"
+        } else {
+            ""
+        };
+        out.push_str(&format!(
+            "\n## {} {}{skipped}\n",
+            truncate_hash(&commit.hash),
+            subject(commit)
+        ));
+        let body = commit
+            .original_message
+            .split_once('\n')
+            .map_or("", |(_, body)| body.trim());
+        if !body.is_empty() {
+            out.push_str(&format!("{body}\n"));
+        }
+        let files: Vec<&str> = commit
+            .analysis
+            .file_changes
+            .file_list
+            .iter()
+            .map(|f| f.file.as_str())
+            .collect();
+        if !files.is_empty() {
+            out.push_str(&format!("files: {}\n", files.join(", ")));
+        }
+        if let Some(diff) = diffs.and_then(|d| d.get(index)) {
+            if !diff.is_empty() {
+                out.push_str(&format!("```diff\n{}\n```\n", diff.trim_end()));
+            }
+        }
+    }
+    out
+}
+
+/// Reads each commit's diff, secrets masked, with [`MAX_DIFF_CHARS`] split
+/// evenly between them.
+fn read_diffs(commits: &[CommitInfo]) -> Result<Vec<String>> {
+    let budget = MAX_DIFF_CHARS / commits.len().max(1);
+    commits
+        .iter()
+        .map(|commit| {
+            let path = &commit.analysis.diff_file;
+            let diff = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read diff file: {path}"))?;
+            let mut diff = redact_secrets(&diff).into_owned();
+            if diff.len() > budget {
+                let mut end = budget;
+                while !diff.is_char_boundary(end) {
+                    end -= 1;
+                }
+                diff.truncate(end);
+                diff.push_str("\n# (diff truncated)\n");
+            }
+            Ok(diff)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::test_support::commits::commit;
+
+    fn session() -> BisectSession {
+        BisectSession {
+            bad_term: "bad".to_string(),
+            good_term: "good".to_string(),
+            bad: Some("aaaaaaaa11111111".to_string()),
+            good: vec!["0000000000000000".to_string()],
+            skipped: vec!["bbbbbbbb22222222".to_string()],
+        }
+    }
+
+    fn commits() -> Vec<CommitInfo> {
+        vec![
+            commit(
+                "aaaaaaaa11111111",
+                "docs: fix typo\n\nIn the README.",
+                &["README.md"],
+            ),
+            commit("bbbbbbbb22222222", "feat: cache sessions", &["src/auth.rs"]),
+            commit("cccccccc33333333", "test: more cases", &["tests/auth.rs"]),
+        ]
+    }
+
+    #[test]
+    fn commit_list_marks_skipped_commits_and_includes_diffs() {
+        let diffs = vec!["+typo".to_string(), String::new()];
+        let text = format_commits(&commits()[..2], Some(&diffs), &session());
+        assert_eq!(
+            text,
+            "\n## aaaaaaaa docs: fix typo\nIn the README.\nfiles: README.md\n```diff\n+typo\n```\n\
+             \n## bbbbbbbb feat: cache sessions (skipped: cannot be tested)\nfiles: src/auth.rs\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn ranking_follows_the_ai_order() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok("candidates:\n\
+             - commit: bbbbbbbb\n  summary: Caches sessions.\n  likelihood: high\n  reason: Sessions.\n\
+             - commit: aaaaaaaa\n  likelihood: low\n\
+             - commit: dddddddd\n  likelihood: high\n"
+            .to_string())]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let (ranked, unranked) =
+            rank_with_client(&client, "login is stale", &commits(), None, &session())
+                .await
+                .unwrap();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0].hash, "bbbbbbbb22222222");
+        assert_eq!(ranked[0].likelihood, Likelihood::High);
+        assert!(ranked[0].skipped);
+        assert_eq!(ranked[1].likelihood, Likelihood::Low);
+        assert_eq!(unranked, vec!["cccccccc33333333".to_string()]);
+        let sent = prompts.prompts();
+        assert!(sent[0].1.starts_with("Bug: login is stale"));
+
+        let ranking = BisectRanking {
+            bug: "login is stale".to_string(),
+            remaining: 3,
+            ranked,
+            unranked,
+            bad_term: "bad".to_string(),
+            good_term: "good".to_string(),
+        };
+        let text = ranking.render_text().unwrap();
+        assert!(text.contains("1. bbbbbbbb [high] feat: cache sessions (skipped)"));
+        assert!(text.contains("Not ranked: cccccccc"));
+        assert!(text.ends_with(
+            "Test the top commit with `git checkout aaaaaaaa`, then mark it with \
+             `git bisect bad` or `git bisect good`."
+        ));
+    }
+}
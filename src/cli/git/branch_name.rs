@@ -0,0 +1,362 @@
+//! Branch name command — suggests convention-compliant branch names
+//! (`<type>/<ticket>/<slug>`) from the working tree or a description, and
+//! optionally creates and switches to the chosen one.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use crate::git::GitBackend;
+
+/// Branch types accepted in suggested names: the conventional commit types
+/// that [`crate::claude::context::BranchAnalyzer`] recognises as a prefix.
+const BRANCH_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "refactor", "chore", "test", "ci", "build", "perf",
+];
+
+/// Maximum slug length, keeping names readable in prompts and PR lists.
+const MAX_SLUG_LEN: usize = 50;
+
+/// Maximum characters of working-tree diff sent to the AI.
+const MAX_CHANGES_CHARS: usize = 20_000;
+
+/// Branch name command options.
+#[derive(Parser)]
+pub struct BranchNameCommand {
+    /// Description of the work (defaults to the staged and unstaged changes).
+    #[arg(value_name = "DESCRIPTION")]
+    pub description: Option<String>,
+
+    /// Ticket identifier to include in the name (e.g. PROJ-123).
+    #[arg(long, value_name = "ID")]
+    pub ticket: Option<String>,
+
+    /// Number of names to suggest.
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub count: usize,
+
+    /// Creates the chosen branch at HEAD and switches to it.
+    #[arg(long)]
+    pub create: bool,
+
+    /// With --create, picks the first suggestion without prompting.
+    #[arg(long, requires = "create")]
+    pub auto_apply: bool,
+}
+
+impl BranchNameCommand {
+    /// Executes the branch name command.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        if self.count == 0 {
+            bail!("--count must be at least 1");
+        }
+        let ticket = self.ticket.as_deref().map(clean_ticket).transpose()?;
+        let changes = if self.description.is_some() {
+            None
+        } else {
+            let changes = read_working_changes(repo_root)?;
+            if changes.trim().is_empty() {
+                bail!(
+                    "No staged or unstaged changes to name a branch after; \
+                     describe the work instead, e.g. omni-dev git branch create name \"add retry to uploads\""
+                );
+            }
+            Some(changes)
+        };
+
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+
+        println!("🤖 Suggesting branch names...");
+        let names = suggest_branch_names_with_client(
+            &claude_client,
+            self.description.as_deref(),
+            changes.as_deref(),
+            ticket.as_deref(),
+            self.count,
+        )
+        .await?;
+
+        println!("\n🌿 Suggested branch names:");
+        for (i, name) in names.iter().enumerate() {
+            println!("  {}. {name}", i + 1);
+        }
+
+        if !self.create {
+            return Ok(());
+        }
+        let chosen = if crate::utils::interactive::assume_yes(self.auto_apply) {
+            Some(&names[0])
+        } else {
+            choose_name(&names)?
+        };
+        let Some(name) = chosen else {
+            println!("❌ Branch creation cancelled by user");
+            return Ok(());
+        };
+        create_and_switch(repo_root, name)?;
+        println!("✅ Created and switched to branch '{name}'");
+        Ok(())
+    }
+}
+
+/// Asks the AI for up to `count` branch names and normalises them, dropping
+/// duplicates and lines that do not follow the naming convention.
+pub(crate) async fn suggest_branch_names_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    description: Option<&str>,
+    changes: Option<&str>,
+    ticket: Option<&str>,
+    count: usize,
+) -> Result<Vec<String>> {
+    let user = crate::claude::prompts::generate_branch_name_user_prompt(
+        description,
+        changes,
+        ticket,
+        count,
+    );
+    let response = claude_client
+        .send_message(crate::claude::prompts::BRANCH_NAME_SYSTEM_PROMPT, &user)
+        .await?;
+
+    let mut names: Vec<String> = Vec::new();
+    for name in response
+        .lines()
+        .filter_map(|line| normalize_branch_name(line, ticket))
+    {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names.truncate(count);
+    if names.is_empty() {
+        bail!("AI returned no usable branch names");
+    }
+    Ok(names)
+}
+
+/// Normalises one suggested line into `<type>/[<ticket>/]<slug>`, or `None`
+/// when it has no recognised type or no slug. A given `ticket` replaces any
+/// ticket segment the AI produced.
+fn normalize_branch_name(line: &str, ticket: Option<&str>) -> Option<String> {
+    let line = line
+        .trim()
+        .trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, '.' | ')' | '-' | '*'))
+        .trim()
+        .trim_matches('`');
+    let segments: Vec<&str> = line.split('/').collect();
+    let (kind, slug) = match segments.as_slice() {
+        [kind, slug] | [kind, _, slug] => (kind.to_ascii_lowercase(), slugify(slug)),
+        _ => return None,
+    };
+    if !BRANCH_TYPES.contains(&kind.as_str()) || slug.is_empty() {
+        return None;
+    }
+    let name = match ticket {
+        Some(ticket) => format!("{kind}/{ticket}/{slug}"),
+        None => format!("{kind}/{slug}"),
+    };
+    git2::Branch::name_is_valid(&name)
+        .unwrap_or(false)
+        .then_some(name)
+}
+
+/// Lowercases `text` and joins its alphanumeric runs with hyphens, cut to
+/// [`MAX_SLUG_LEN`] at a word boundary.
+fn slugify(text: &str) -> String {
+    let lowered = text.to_ascii_lowercase();
+    let mut slug = String::new();
+    for word in lowered
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let extra = usize::from(!slug.is_empty()) + word.len();
+        if !slug.is_empty() && slug.len() + extra > MAX_SLUG_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    slug
+}
+
+/// Validates a user-supplied ticket identifier. A leading `#` is dropped so
+/// `#123` becomes `123`.
+fn clean_ticket(ticket: &str) -> Result<String> {
+    let ticket = ticket.trim().trim_start_matches('#');
+    if ticket.is_empty()
+        || !ticket
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+    {
+        bail!("Invalid ticket '{ticket}': use letters, digits, '-' or '_' (e.g. PROJ-123)");
+    }
+    Ok(ticket.to_string())
+}
+
+/// Reads the staged and unstaged changes against `HEAD`, followed by the
+/// names of untracked files, truncated to [`MAX_CHANGES_CHARS`].
+fn read_working_changes(repo_root: &std::path::Path) -> Result<String> {
+    let repo = crate::git::GitRepository::open_at(repo_root)
+        .context("Failed to open git repository at the given path")?;
+    let backend = repo.backend();
+    let mut changes = backend.working_diff()?;
+    let untracked: Vec<String> = backend
+        .working_directory_status()?
+        .untracked_changes
+        .into_iter()
+        .filter(|change| change.status.ends_with('?'))
+        .map(|change| change.file)
+        .collect();
+    if !untracked.is_empty() {
+        changes.push_str("\n# New untracked files:\n");
+        for file in untracked {
+            changes.push_str(&file);
+            changes.push('\n');
+        }
+    }
+    if changes.len() > MAX_CHANGES_CHARS {
+        let mut end = MAX_CHANGES_CHARS;
+        while !changes.is_char_boundary(end) {
+            end -= 1;
+        }
+        changes.truncate(end);
+        changes.push_str("\n# (diff truncated)\n");
+    }
+    Ok(changes)
+}
+
+/// Prompts for one of `names`; `None` when the user cancels.
+fn choose_name(names: &[String]) -> Result<Option<&String>> {
+    use std::io::{self, Write};
+
+    loop {
+        print!(
+            "❓ Create which branch? [1-{}, Enter to cancel] ",
+            names.len()
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match input.parse::<usize>() {
+            Ok(n) if (1..=names.len()).contains(&n) => return Ok(Some(&names[n - 1])),
+            _ => println!("Please enter a number between 1 and {}.", names.len()),
+        }
+    }
+}
+
+/// Creates branch `name` at `HEAD` and checks it out. The working tree and
+/// index are untouched since the new branch points at the current commit.
+fn create_and_switch(repo_root: &std::path::Path, name: &str) -> Result<()> {
+    let repo = git2::Repository::discover(repo_root).context("Failed to open git repository")?;
+    if repo.find_branch(name, git2::BranchType::Local).is_ok() {
+        bail!("Branch '{name}' already exists");
+    }
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .context("Failed to resolve HEAD; commit at least once before creating a branch")?;
+    let branch = repo
+        .branch(name, &head, false)
+        .with_context(|| format!("Failed to create branch '{name}'"))?;
+    let refname = branch
+        .get()
+        .name()
+        .context("Branch reference name is not valid UTF-8")?;
+    repo.set_head(refname)
+        .with_context(|| format!("Failed to switch to branch '{name}'"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+
+    #[test]
+    fn auto_apply_requires_create() {
+        assert!(BranchNameCommand::try_parse_from(["name", "--auto-apply"]).is_err());
+        let cmd = BranchNameCommand::try_parse_from(["name", "--create", "--auto-apply"]).unwrap();
+        assert!(cmd.create && cmd.auto_apply);
+    }
+
+    #[test]
+    fn normalizes_suggested_names() {
+        assert_eq!(
+            normalize_branch_name("1. `feat/Add Merge Command`", None).as_deref(),
+            Some("feat/add-merge-command")
+        );
+        assert_eq!(
+            normalize_branch_name("fix/ABC-1/handle-empty-config", Some("PROJ-7")).as_deref(),
+            Some("fix/PROJ-7/handle-empty-config")
+        );
+        assert_eq!(
+            normalize_branch_name("- docs/readme", Some("PROJ-7")).as_deref(),
+            Some("docs/PROJ-7/readme")
+        );
+        assert!(normalize_branch_name("feature-add-merge", None).is_none());
+        assert!(normalize_branch_name("wip/add-merge", None).is_none());
+        assert!(normalize_branch_name("Here are some names:", None).is_none());
+    }
+
+    #[test]
+    fn slug_is_cut_at_a_word_boundary() {
+        let slug = slugify(&"word ".repeat(20));
+        assert!(slug.len() <= MAX_SLUG_LEN);
+        assert!(slug.ends_with("word"));
+    }
+
+    #[test]
+    fn validates_tickets() {
+        assert_eq!(clean_ticket("#123").unwrap(), "123");
+        assert_eq!(clean_ticket(" PROJ-9 ").unwrap(), "PROJ-9");
+        assert!(clean_ticket("PROJ 9").is_err());
+        assert!(clean_ticket("#").is_err());
+    }
+
+    #[tokio::test]
+    async fn suggestions_are_deduplicated_and_capped() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "feat/add-merge\nfeat/Add Merge\nnot a branch\nfix/merge-errors\nrefactor/merge-flow\n"
+                .to_string(),
+        )]);
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let names = suggest_branch_names_with_client(&client, Some("merge"), None, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(names, vec!["feat/add-merge", "fix/merge-errors"]);
+    }
+
+    #[test]
+    fn creates_and_switches_to_branch() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+
+        create_and_switch(dir.path(), "feat/add-merge").unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feat/add-merge"));
+        let err = create_and_switch(dir.path(), "feat/add-merge").unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+}
@@ -0,0 +1,294 @@
+//! Changelog commands — `update` adds the conventional commits made since the
+//! last released version to the `Unreleased` section of a Keep a Changelog
+//! file, leaving hand-written entries in place; `check` enforces the
+//! changelog rule offline and can draft the entries it finds missing.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use super::formatting::truncate_hash;
+use crate::git::changelog::Changelog;
+use crate::git::changelog_rule::ChangelogRule;
+use crate::git::release::release_entry;
+use crate::git::GitRepository;
+
+/// Header written when the changelog file does not exist yet.
+const NEW_CHANGELOG: &str = "# Changelog
+
+All notable changes to this project will be documented in this file.
+
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.0.0/),
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).
+";
+
+/// Changelog update command options.
+#[derive(Parser)]
+pub struct ChangelogUpdateCommand {
+    /// Changelog file, relative to the repository root.
+    #[arg(long, value_name = "PATH", default_value = "CHANGELOG.md")]
+    pub file: PathBuf,
+
+    /// Ref to collect commits after (defaults to the tag of the latest version in the changelog).
+    #[arg(long, value_name = "REF")]
+    pub from: Option<String>,
+
+    /// Prints the entries that would be added without writing the file.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl ChangelogUpdateCommand {
+    /// Executes the changelog update command.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let path = repo_root.join(&self.file);
+        let mut changelog = read_changelog(&path)?;
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let from = match &self.from {
+            Some(from) => from.clone(),
+            None => {
+                let Some(version) = changelog.latest_version() else {
+                    bail!(
+                        "{} has no released version to start from; pass --from <REF>",
+                        self.file.display()
+                    );
+                };
+                version_tag(git_repo.repository(), version)?
+            }
+        };
+
+        let range = format!("{from}..HEAD");
+        let commits = git_repo
+            .get_commits_in_range(&range)
+            .with_context(|| format!("Failed to read commits in {range}"))?;
+        let entries: Vec<_> = commits.iter().map(release_entry).collect();
+        let added = changelog.add_unreleased(&entries);
+
+        if added.is_empty() {
+            println!("✨ No new changelog entries since {from}");
+            return Ok(());
+        }
+        let verb = if self.dry_run { "Would add" } else { "Adding" };
+        println!("📝 {verb} {} entries since {from}:", added.len());
+        for line in &added {
+            println!("  {line}");
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        std::fs::write(&path, changelog.render())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("✅ Updated {}", self.file.display());
+        Ok(())
+    }
+}
+
+/// Changelog check command options.
+#[derive(Parser)]
+pub struct ChangelogCheckCommand {
+    /// Commit range to check (e.g., HEAD~3..HEAD). Defaults to commits ahead
+    /// of the default base branch.
+    #[arg(value_name = "COMMIT_RANGE")]
+    pub commit_range: Option<String>,
+
+    /// Drafts the missing entries into the Unreleased section of the
+    /// changelog (the first of the rule's `paths`) instead of failing.
+    #[arg(long)]
+    pub fix: bool,
+}
+
+impl ChangelogCheckCommand {
+    /// Executes the changelog check command, exiting with status 1 when
+    /// entries are missing and `--fix` was not given.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let rule = match ChangelogRule::load(repo_root)? {
+            Some(rule) => rule,
+            None => ChangelogRule::from_config("")?,
+        };
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let range = match &self.commit_range {
+            Some(range) => range.clone(),
+            None => super::default_commit_range(&git_repo)?,
+        };
+        let commits = git_repo
+            .get_commits_in_range(&range)
+            .with_context(|| format!("Failed to read commits in {range}"))?;
+        let missing = rule.missing_entries(&commits);
+        if missing.is_empty() {
+            println!(
+                "✅ Changelog rule satisfied for {} commit(s) in {range}",
+                commits.len()
+            );
+            return Ok(());
+        }
+
+        println!(
+            "⚠️  {} commit(s) need an entry in {}:",
+            missing.len(),
+            rule.paths.join(", ")
+        );
+        for commit in &missing {
+            println!(
+                "  {} {}",
+                truncate_hash(&commit.hash),
+                commit.original_message.lines().next().unwrap_or_default()
+            );
+        }
+        if !self.fix {
+            println!("💡 Run with --fix to draft the entries, or add [skip changelog] to a commit message to exempt it");
+            crate::utils::plain::exit(1);
+        }
+
+        let file = changelog_file(&rule)?;
+        let path = repo_root.join(file);
+        let mut changelog = read_changelog(&path)?;
+        let entries: Vec<_> = missing.into_iter().map(release_entry).collect();
+        let added = changelog.add_unreleased(&entries);
+        if added.is_empty() {
+            bail!(
+                "None of the commits map to a changelog category; add the entries to {file} by hand"
+            );
+        }
+        std::fs::write(&path, changelog.render())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("📝 Drafted {} entries in {file}:", added.len());
+        for line in &added {
+            println!("  {line}");
+        }
+        println!("💡 Review the wording, then commit {file}");
+        Ok(())
+    }
+}
+
+/// The changelog file `--fix` writes: the rule's first path, which must name
+/// a file rather than a pattern.
+fn changelog_file(rule: &ChangelogRule) -> Result<&str> {
+    let file = rule
+        .paths
+        .first()
+        .context("The changelog rule lists no paths")?;
+    if file.contains(['*', '?', '[', '{']) {
+        bail!("The first changelog rule path '{file}' is a pattern; list the file to draft into first");
+    }
+    Ok(file)
+}
+
+/// Reads and parses a changelog, starting from a fresh header when the file
+/// does not exist yet.
+fn read_changelog(path: &Path) -> Result<Changelog> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => NEW_CHANGELOG.to_string(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    Ok(Changelog::parse(&text))
+}
+
+/// Finds the tag for a changelog version, trying `v<version>` then
+/// `<version>`.
+fn version_tag(repo: &git2::Repository, version: &str) -> Result<String> {
+    let version = version.trim_start_matches('v');
+    [format!("v{version}"), version.to_string()]
+        .into_iter()
+        .find(|tag| repo.refname_to_id(&format!("refs/tags/{tag}")).is_ok())
+        .with_context(|| {
+            format!("No tag found for version {version} (tried v{version} and {version}); pass --from <REF>")
+        })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit(repo: &git2::Repository, message: &str) -> git2::Oid {
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolves_version_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let oid = commit(&repo, "chore: init");
+        let object = repo.find_object(oid, None).unwrap();
+        repo.tag_lightweight("v1.0.0", &object, false).unwrap();
+        repo.tag_lightweight("2.0.0", &object, false).unwrap();
+
+        assert_eq!(version_tag(&repo, "1.0.0").unwrap(), "v1.0.0");
+        assert_eq!(version_tag(&repo, "v2.0.0").unwrap(), "2.0.0");
+        assert!(version_tag(&repo, "3.0.0").is_err());
+    }
+
+    #[test]
+    fn updates_unreleased_with_commits_since_last_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let oid = commit(&repo, "chore: init");
+        repo.tag_lightweight("v1.0.0", &repo.find_object(oid, None).unwrap(), false)
+            .unwrap();
+        commit(&repo, "feat(git): add changelog update");
+        commit(&repo, "test: cover changelog");
+        std::fs::write(
+            dir.path().join("CHANGELOG.md"),
+            "# Changelog\n\n## [Unreleased]\n\n## [1.0.0] - 2026-01-01\n\n- First release\n",
+        )
+        .unwrap();
+
+        let cmd = ChangelogUpdateCommand {
+            file: PathBuf::from("CHANGELOG.md"),
+            from: None,
+            dry_run: false,
+        };
+        cmd.execute(Some(dir.path())).unwrap();
+
+        let text = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(text.contains("## [Unreleased]\n\n### Added\n- **git:** Add changelog update ("));
+        assert!(!text.contains("cover changelog"));
+        assert!(text.ends_with("## [1.0.0] - 2026-01-01\n\n- First release\n"));
+    }
+
+    #[test]
+    fn check_fix_drafts_the_missing_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        commit(&repo, "chore: init");
+        commit(&repo, "feat(git): add changelog check");
+        commit(&repo, "test: cover changelog check");
+
+        let cmd = ChangelogCheckCommand {
+            commit_range: Some("HEAD~2..HEAD".to_string()),
+            fix: true,
+        };
+        cmd.execute(Some(dir.path())).unwrap();
+
+        let text = std::fs::read_to_string(dir.path().join("CHANGELOG.md")).unwrap();
+        assert!(text.starts_with("# Changelog\n"));
+        assert!(text.contains("## [Unreleased]\n\n### Added\n- **git:** Add changelog check ("));
+        assert!(!text.contains("cover changelog check"));
+    }
+}
@@ -3,7 +3,8 @@
 use anyhow::{Context, Result};
 use clap::Parser;
 
-use crate::data::check::OutputFormat;
+use crate::claude::check_cache::{self, CheckCache, CheckCacheKeyer};
+use crate::data::check::{OutputFormat, ReportFormat};
 
 /// Check command options - validates commit messages against guidelines.
 #[derive(Parser)]
@@ -14,6 +15,11 @@ pub struct CheckCommand {
     #[arg(value_name = "COMMIT_RANGE")]
     pub commit_range: Option<String>,
 
+    /// Checks exactly the commits of this GitHub pull request (fetched via
+    /// `gh`) instead of a commit range.
+    #[arg(long, value_name = "NUMBER", conflicts_with = "commit_range")]
+    pub pr: Option<u64>,
+
     /// Path to custom context directory (defaults to .omni-dev/).
     #[arg(long)]
     pub context_dir: Option<std::path::PathBuf>,
@@ -22,14 +28,32 @@ pub struct CheckCommand {
     #[arg(long)]
     pub guidelines: Option<std::path::PathBuf>,
 
-    /// Output format.
-    #[arg(short = 'o', long, value_enum, default_value_t = OutputFormat::Text)]
-    pub output: OutputFormat,
+    /// Output format (default: text, or the top-level `--output`).
+    #[arg(short = 'o', long, value_enum)]
+    pub output: Option<OutputFormat>,
 
     /// Deprecated: use `-o`/`--output` instead.
     #[arg(long = "format", hide = true)]
     pub format: Option<OutputFormat>,
 
+    /// Produces a human-friendly report (e.g. for a PR comment or job
+    /// summary). Printed instead of the regular output unless
+    /// `--report-file` is given.
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub report: Option<ReportFormat>,
+
+    /// Writes the `--report` output to this file (e.g. `$GITHUB_STEP_SUMMARY`)
+    /// in addition to the regular output.
+    #[arg(long, value_name = "PATH", requires = "report")]
+    pub report_file: Option<std::path::PathBuf>,
+
+    /// Posts the Markdown report as a comment on this pull request, editing
+    /// the previous report comment on re-runs instead of adding another.
+    /// Without a number, uses `--pr` or the pull request of the GitHub
+    /// Actions run.
+    #[arg(long, value_name = "NUMBER", num_args = 0..=1)]
+    pub comment_pr: Option<Option<u64>>,
+
     /// Exits with error code if any issues found (including warnings).
     #[arg(long)]
     pub strict: bool,
@@ -46,9 +70,9 @@ pub struct CheckCommand {
     #[arg(long)]
     pub show_passing: bool,
 
-    /// Maximum number of concurrent AI requests (default: 4).
-    #[arg(long, default_value = "4")]
-    pub concurrency: usize,
+    /// Starting number of concurrent AI requests, adjusted for rate limits and latency (default: 4, or OMNI_DEV_CONCURRENCY).
+    #[arg(long)]
+    pub concurrency: Option<usize>,
 
     /// Deprecated: use --concurrency instead.
     #[arg(long, hide = true)]
@@ -65,9 +89,34 @@ pub struct CheckCommand {
     /// Offers to apply suggested messages when issues are found.
     #[arg(long)]
     pub twiddle: bool,
+
+    /// Shows a full-screen view with live per-commit progress, then reviews
+    /// the suggested fixes with accept/edit/skip keys (needs a terminal).
+    #[arg(long, conflicts_with_all = ["report", "quiet", "comment_pr"])]
+    pub tui: bool,
+
+    /// Re-checks every commit instead of reusing cached results for
+    /// unchanged commits.
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Saves the analyzed repository view to this file, for `--update-view`.
+    #[arg(long, value_name = "FILE", conflicts_with = "update_view")]
+    pub save_view: Option<std::path::PathBuf>,
+
+    /// Updates the view saved in this file, analyzing only commits added or rewritten since, and saves it back.
+    #[arg(long, value_name = "FILE")]
+    pub update_view: Option<std::path::PathBuf>,
 }
 
 impl CheckCommand {
+    /// Maximum concurrent AI requests, once `execute` has resolved the
+    /// configured default into `concurrency`.
+    fn concurrency(&self) -> usize {
+        self.concurrency
+            .unwrap_or(super::defaults::DEFAULT_CONCURRENCY)
+    }
+
     /// Executes the check command, validating commit messages against guidelines.
     pub async fn execute(mut self, repo: Option<&std::path::Path>) -> Result<()> {
         // Resolve the repo root once; every git, config, and scratch read below
@@ -81,29 +130,59 @@ impl CheckCommand {
         // Resolve deprecated --batch-size into --concurrency
         if let Some(bs) = self.batch_size {
             eprintln!("warning: --batch-size is deprecated; use --concurrency instead");
-            self.concurrency = bs;
+            self.concurrency = Some(bs);
+        }
+        // Fill omitted flags from the configured defaults (CLI > project > global)
+        let settings = crate::utils::settings::SettingsEnv::load();
+        self.concurrency = Some(super::defaults::concurrency(self.concurrency, &settings));
+        if self.pr.is_none() {
+            self.commit_range =
+                super::defaults::commit_range(self.commit_range.as_deref(), &settings);
         }
 
         // Resolve deprecated --format into -o/--output
         if let Some(format) = self.format.take() {
             eprintln!("warning: --format is deprecated; use -o/--output instead");
-            self.output = format;
+            self.output = Some(format);
+        }
+        let output_format = crate::data::output::resolve(
+            self.output,
+            &crate::utils::env::SystemEnv,
+            OutputFormat::Text,
+        );
+        // A report printed to stdout replaces the regular output, so progress
+        // lines are suppressed as for the machine-readable formats.
+        let report_to_stdout = self.report.is_some() && self.report_file.is_none();
+        let text_output = output_format == OutputFormat::Text && !report_to_stdout;
+        if self.tui {
+            if output_format != OutputFormat::Text {
+                anyhow::bail!("--tui needs the text output format");
+            }
+            super::tui::ensure_terminal()?;
         }
-        let output_format = self.output;
 
         // Preflight check: validate AI credentials before any processing.
         // Model/beta-header selection uses the global `--model`/`--beta-header`
         // flags (propagated as OMNI_DEV_MODEL/OMNI_DEV_BETA_HEADER) and the
         // per-backend env chain.
         let ai_info = crate::utils::check_ai_command_prerequisites(None, repo_root)?;
-        if !self.quiet && output_format == OutputFormat::Text {
+        if self.pr.is_some() {
+            crate::utils::check_github_cli(repo_root)?;
+        }
+        let comment_pr = self
+            .comment_pr
+            .map(|number| {
+                comment_pr_number(number, self.pr, std::env::var("GITHUB_REF").ok().as_deref())
+            })
+            .transpose()?;
+        if !self.quiet && text_output {
             println!(
                 "✓ {} credentials verified (model: {})",
                 ai_info.provider, ai_info.model
             );
         }
 
-        if !self.quiet && output_format == OutputFormat::Text {
+        if !self.quiet && text_output {
             println!("🔍 Checking commit messages against guidelines...");
         }
 
@@ -113,77 +192,188 @@ impl CheckCommand {
         // 2. Check for empty commit range (exit code 3)
         if repo_view.commits.is_empty() {
             eprintln!("error: no commits found in range");
-            std::process::exit(3);
+            crate::utils::plain::exit(3);
         }
 
-        if !self.quiet && output_format == OutputFormat::Text {
+        if !self.quiet && text_output {
             println!("📊 Found {} commits to check", repo_view.commits.len());
         }
 
-        // 3. Load commit guidelines and scopes
+        // 3. Load commit guidelines, scopes, and commit types
         let guidelines = self.load_guidelines(repo_root).await?;
         let valid_scopes = self.load_scopes(repo_root);
+        let commit_types = self.load_types(repo_root);
 
-        // Refine detected scopes using file_patterns from scope definitions
+        // Refine detected scopes using file_patterns from scope definitions,
+        // and detected types against the project's commit types
         for commit in &mut repo_view.commits {
             commit.analysis.refine_scope(&valid_scopes);
+            commit
+                .analysis
+                .refine_type(&commit.original_message, &commit_types);
         }
 
-        if !self.quiet && output_format == OutputFormat::Text {
+        if !self.quiet && text_output {
             self.show_guidance_files_status(repo_root, &guidelines, &valid_scopes);
         }
 
         // 4. Initialize Claude client
-        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        let claude_client = crate::claude::create_default_claude_client(None, None)
+            .await?
+            .with_commit_types(commit_types);
 
-        if self.verbose && output_format == OutputFormat::Text {
+        if self.verbose && text_output {
             self.show_model_info(&claude_client)?;
         }
 
-        // 5. Use parallel map-reduce for multiple commits, direct call for single
-        let report = if repo_view.commits.len() > 1 {
-            if !self.quiet && output_format == OutputFormat::Text {
+        // 5. Serve unchanged commits from the check cache
+        let cache = if self.no_cache {
+            None
+        } else {
+            CheckCache::open_default()
+        };
+        let keyer = CheckCacheKeyer::new(
+            guidelines.as_deref(),
+            &valid_scopes,
+            claude_client.commit_types(),
+            &claude_client.get_ai_client_metadata().model,
+            !self.no_suggestions,
+            !self.no_coherence,
+        );
+        let commit_order: Vec<String> = repo_view.commits.iter().map(|c| c.hash.clone()).collect();
+        let (cached_results, pending_hashes) =
+            check_cache::partition(cache.as_ref(), &keyer, &commit_order);
+        if !cached_results.is_empty() && !self.quiet && text_output {
+            println!(
+                "♻️  Reusing cached results for {} unchanged commit(s)",
+                cached_results.len()
+            );
+        }
+        let mut pending_view = repo_view.clone();
+        pending_view
+            .commits
+            .retain(|c| pending_hashes.contains(&c.hash));
+
+        // With --tui, progress goes to the full-screen view instead of lines
+        let screen = if self.tui {
+            let screen = super::tui::Tui::enter(super::tui::Screen::new(
+                "check",
+                repo_view
+                    .commits
+                    .iter()
+                    .map(|c| (c.hash.clone(), c.original_message.clone())),
+            ))?;
+            screen.update(|view| {
+                for result in &cached_results {
+                    view.set_state(&result.hash, check_row_state(result));
+                }
+            });
+            Some(screen)
+        } else {
+            None
+        };
+
+        // 6. Use parallel map-reduce for multiple commits, direct call for single
+        let fresh_report = if pending_view.commits.len() > 1 {
+            if !self.quiet && text_output && screen.is_none() {
                 println!(
                     "🔄 Processing {} commits in parallel (concurrency: {})...",
-                    repo_view.commits.len(),
-                    self.concurrency
+                    pending_view.commits.len(),
+                    self.concurrency()
                 );
             }
             self.check_with_map_reduce(
                 &claude_client,
-                &repo_view,
+                &pending_view,
                 guidelines.as_deref(),
                 &valid_scopes,
+                screen.as_ref(),
             )
             .await?
-        } else {
+        } else if pending_view.commits.len() == 1 {
             // Single commit — direct call
-            if !self.quiet && output_format == OutputFormat::Text {
+            if let Some(screen) = &screen {
+                screen.set_state(
+                    pending_view.commits.iter().map(|c| c.hash.as_str()),
+                    super::tui::RowState::Running,
+                );
+            } else if !self.quiet && text_output {
                 println!("🤖 Analyzing commits with AI...");
             }
             claude_client
                 .check_commits_with_scopes(
-                    &repo_view,
+                    &pending_view,
                     guidelines.as_deref(),
                     &valid_scopes,
                     !self.no_suggestions,
                 )
                 .await?
+        } else {
+            crate::data::check::CheckReport::new(Vec::new())
+        };
+        let mut report = check_cache::store_and_merge(
+            cache.as_ref(),
+            &keyer,
+            &commit_order,
+            cached_results,
+            fresh_report,
+        );
+
+        // The offline changelog rule, when the project enables it
+        if let Some(rule) = crate::git::changelog_rule::ChangelogRule::load(repo_root)? {
+            report = apply_changelog_rule(report, &rule, &repo_view.commits);
+        }
+
+        // With --tui, the results are reviewed on screen (accepting suggested
+        // fixes there) before the report is printed below
+        let reviewed = match screen {
+            Some(screen) => review_report(&screen, &report)?,
+            None => None,
         };
 
         // 7. Output results
-        self.output_report(&report, output_format)?;
+        match self.report {
+            Some(ReportFormat::Markdown) => {
+                let markdown = format_markdown_report(&report, self.show_passing);
+                if let Some(path) = &self.report_file {
+                    self.output_report(&report, output_format)?;
+                    std::fs::write(path, markdown).with_context(|| {
+                        format!("Failed to write report file: {}", path.display())
+                    })?;
+                } else {
+                    print!("{markdown}");
+                }
+            }
+            None => self.output_report(&report, output_format)?,
+        }
 
-        // 8. If --twiddle and there are errors with suggestions, offer to apply them
-        if should_offer_twiddle(self.twiddle, report.has_errors(), output_format) {
-            use std::io::IsTerminal;
+        if let Some(number) = comment_pr {
+            let markdown = format_markdown_report(&report, self.show_passing);
+            post_report_comment(repo_root, number, &markdown).await?;
+            if !self.quiet && text_output {
+                println!("💬 Posted the check report to PR #{number}");
+            }
+        }
+
+        // 8. Apply the fixes accepted on screen, or with --twiddle offer to
+        //    apply the suggestions when there are errors
+        if let Some(amendments) = reviewed.filter(|a| !a.is_empty()) {
+            self.apply_suggestions(repo_root, amendments)?;
+            return Ok(());
+        } else if !report_to_stdout
+            && should_offer_twiddle(self.twiddle, report.has_errors(), output_format)
+        {
             let amendments = self.build_amendments_from_suggestions(&report, &repo_view);
+            if !amendments.is_empty() && crate::utils::interactive::assume_yes(false) {
+                self.apply_suggestions(repo_root, amendments)?;
+                return Ok(());
+            }
             if !amendments.is_empty()
                 && self
                     .prompt_and_apply_suggestions(
                         repo_root,
                         amendments,
-                        std::io::stdin().is_terminal(),
+                        crate::utils::interactive::stdin_is_interactive(),
                         &mut std::io::BufReader::new(std::io::stdin()),
                     )
                     .await?
@@ -196,7 +386,7 @@ impl CheckCommand {
         // 9. Determine exit code
         let exit_code = report.exit_code(self.strict);
         if exit_code != 0 {
-            std::process::exit(exit_code);
+            crate::utils::plain::exit(exit_code);
         }
 
         Ok(())
@@ -207,10 +397,7 @@ impl CheckCommand {
         &self,
         repo_root: &std::path::Path,
     ) -> Result<crate::data::RepositoryView> {
-        use crate::data::{
-            AiInfo, BranchInfo, FieldExplanation, FileStatusInfo, RepositoryView, VersionInfo,
-            WorkingDirectoryInfo,
-        };
+        use crate::data::{AiInfo, BranchInfo, FieldExplanation, RepositoryView, VersionInfo};
         use crate::git::{GitRepository, RemoteInfo};
         use crate::utils::ai_scratch;
 
@@ -223,31 +410,29 @@ impl CheckCommand {
             .get_current_branch()
             .unwrap_or_else(|_| "HEAD".to_string());
 
-        // Determine commit range
-        let commit_range = match &self.commit_range {
-            Some(range) => range.clone(),
-            None => super::default_commit_range(&repo)?,
-        };
-
-        // Get working directory status
-        let wd_status = repo.get_working_directory_status()?;
-        let working_directory = WorkingDirectoryInfo {
-            clean: wd_status.clean,
-            untracked_changes: wd_status
-                .untracked_changes
-                .into_iter()
-                .map(|fs| FileStatusInfo {
-                    status: fs.status,
-                    file: fs.file,
-                })
-                .collect(),
-        };
+        // Scan the working directory while the commits are extracted
+        let working_directory = super::spawn_working_directory_scan(repo_root);
 
         // Get remote information
         let remotes = RemoteInfo::get_all_remotes(repo.repository())?;
 
-        // Parse commit range and get commits
-        let commits = repo.get_commits_in_range(&commit_range)?;
+        // Get commits: the PR's exact commit list, or the (default) range
+        let commits = if let Some(number) = self.pr {
+            let hashes = fetch_pr_commit_hashes(number, repo_root)?;
+            repo.get_commits_by_hashes(&hashes).with_context(|| {
+                format!(
+                    "Commits of PR #{number} are missing locally; \
+                     run `git fetch origin pull/{number}/head` and retry"
+                )
+            })?
+        } else {
+            let commit_range = match &self.commit_range {
+                Some(range) => range.clone(),
+                None => super::default_commit_range(&repo)?,
+            };
+            let known = super::saved_view_commits(self.update_view.as_deref())?;
+            repo.get_commits_in_range_reusing(&commit_range, &known)?
+        };
 
         // Create version information
         let versions = Some(VersionInfo {
@@ -263,13 +448,15 @@ impl CheckCommand {
 
         // Build repository view with branch info
         let mut repo_view = RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions,
             explanation: FieldExplanation::default(),
-            working_directory,
+            working_directory: working_directory.await?,
             remotes,
             ai: ai_info,
             branch_info: Some(BranchInfo {
                 branch: current_branch,
+                merge_conflicts: None,
             }),
             pr_template: None,
             pr_template_location: None,
@@ -280,6 +467,10 @@ impl CheckCommand {
         // Update field presence based on actual data
         repo_view.update_field_presence();
 
+        if let Some(path) = self.save_view.as_deref().or(self.update_view.as_deref()) {
+            repo_view.save_to_file(path)?;
+        }
+
         Ok(repo_view)
     }
 
@@ -293,6 +484,10 @@ impl CheckCommand {
                     guidelines_path.display()
                 )
             })?;
+            let content = crate::claude::context::remote::resolve_markdown_extends_or_local(
+                &content,
+                "commit-guidelines.md",
+            );
             return Ok(Some(content));
         }
 
@@ -312,6 +507,13 @@ impl CheckCommand {
         crate::claude::context::load_project_scopes(&context_dir, repo_root)
     }
 
+    /// Loads the project's commit types (empty when the built-in types apply).
+    fn load_types(&self, repo_root: &std::path::Path) -> Vec<crate::data::context::TypeDefinition> {
+        let context_dir =
+            crate::claude::context::resolve_context_dir_at(self.context_dir.as_deref(), repo_root);
+        crate::claude::context::load_project_types(&context_dir)
+    }
+
     /// Shows diagnostic information about loaded guidance files.
     fn show_guidance_files_status(
         &self,
@@ -368,12 +570,13 @@ impl CheckCommand {
         full_repo_view: &crate::data::RepositoryView,
         guidelines: Option<&str>,
         valid_scopes: &[crate::data::context::ScopeDefinition],
+        screen: Option<&super::tui::Tui>,
     ) -> Result<crate::data::check::CheckReport> {
-        use std::io::IsTerminal;
         use std::sync::atomic::{AtomicUsize, Ordering};
         use std::sync::Arc;
 
         use crate::claude::batch;
+        use crate::claude::concurrency::AdaptiveLimiter;
         use crate::claude::token_budget;
         use crate::data::check::{CheckReport, CommitCheckResult};
 
@@ -381,15 +584,19 @@ impl CheckCommand {
 
         // Plan batches based on token budget
         let metadata = claude_client.get_ai_client_metadata();
-        let system_prompt = crate::claude::prompts::generate_check_system_prompt_with_scopes(
+        let system_prompt = crate::claude::prompts::generate_check_system_prompt_with_types(
             guidelines,
             valid_scopes,
+            claude_client.commit_types(),
         );
         let system_prompt_tokens = token_budget::estimate_tokens(&system_prompt);
         let batch_plan =
             batch::plan_batches(&full_repo_view.commits, &metadata, system_prompt_tokens);
 
-        if !self.quiet && batch_plan.batches.len() < total_commits {
+        // Progress lines, unless quiet or drawn on the full-screen view
+        let progress_lines = !self.quiet && screen.is_none();
+
+        if progress_lines && batch_plan.batches.len() < total_commits {
             println!(
                 "   📦 Grouped {} commits into {} batches by token budget",
                 total_commits,
@@ -397,7 +604,7 @@ impl CheckCommand {
             );
         }
 
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.concurrency));
+        let limiter = AdaptiveLimiter::new(self.concurrency());
         let completed = Arc::new(AtomicUsize::new(0));
 
         // Map phase: check batches in parallel
@@ -405,17 +612,22 @@ impl CheckCommand {
             .batches
             .iter()
             .map(|batch| {
-                let sem = semaphore.clone();
+                let limiter = limiter.clone();
                 let completed = completed.clone();
                 let batch_indices = &batch.commit_indices;
 
                 async move {
-                    let _permit = sem
-                        .acquire()
-                        .await
-                        .map_err(|e| anyhow::anyhow!("semaphore closed: {e}"))?;
+                    let mut permit = limiter.acquire().await;
 
                     let batch_size = batch_indices.len();
+                    let batch_hashes = move || {
+                        batch_indices
+                            .iter()
+                            .map(move |&i| full_repo_view.commits[i].hash.as_str())
+                    };
+                    if let Some(screen) = screen {
+                        screen.set_state(batch_hashes(), super::tui::RowState::Running);
+                    }
 
                     // Create view for this batch
                     let batch_view = if batch_size == 1 {
@@ -437,11 +649,15 @@ impl CheckCommand {
                         )
                         .await;
 
+                    permit.record(&result, batch_size);
+
                     match result {
                         Ok(report) => {
                             let done =
                                 completed.fetch_add(batch_size, Ordering::Relaxed) + batch_size;
-                            if !self.quiet {
+                            if let Some(screen) = screen {
+                                screen.set_state(batch_hashes(), super::tui::RowState::Done);
+                            } else if !self.quiet {
                                 println!("   ✅ {done}/{total_commits} commits checked");
                             }
 
@@ -457,9 +673,15 @@ impl CheckCommand {
                         }
                         Err(e) if batch_size > 1 => {
                             // Split-and-retry: fall back to individual commits
-                            eprintln!(
-                                "warning: batch of {batch_size} failed, retrying individually: {e}"
-                            );
+                            if let Some(screen) = screen {
+                                screen.set_status(format!(
+                                    "Batch of {batch_size} failed, retrying individually: {e}"
+                                ));
+                            } else {
+                                eprintln!(
+                                    "warning: batch of {batch_size} failed, retrying individually: {e}"
+                                );
+                            }
                             let mut items = Vec::new();
                             let mut failed_indices = Vec::new();
                             for &idx in batch_indices {
@@ -473,6 +695,7 @@ impl CheckCommand {
                                         !self.no_suggestions,
                                     )
                                     .await;
+                                permit.record(&single_result, 1);
                                 match single_result {
                                     Ok(report) => {
                                         if let Some(r) = report.commits.into_iter().next() {
@@ -480,15 +703,24 @@ impl CheckCommand {
                                             items.push((r, summary));
                                         }
                                         let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
-                                        if !self.quiet {
+                                        if let Some(screen) = screen {
+                                            screen.set_state(
+                                                [full_repo_view.commits[idx].hash.as_str()],
+                                                super::tui::RowState::Done,
+                                            );
+                                        } else if !self.quiet {
                                             println!(
                                                 "   ✅ {done}/{total_commits} commits checked"
                                             );
                                         }
                                     }
                                     Err(e) => {
-                                        eprintln!("warning: failed to check commit: {e}");
                                         failed_indices.push(idx);
+                                        if let Some(screen) = screen {
+                                            screen.fail(&full_repo_view.commits[idx].hash, &e);
+                                            continue;
+                                        }
+                                        eprintln!("warning: failed to check commit: {e}");
                                         if !self.quiet {
                                             println!("   ❌ commit check failed");
                                         }
@@ -500,8 +732,12 @@ impl CheckCommand {
                         Err(e) => {
                             // Single-commit batch failed; record the index so the user can retry
                             let idx = batch_indices[0];
-                            eprintln!("warning: failed to check commit: {e}");
                             let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                            if let Some(screen) = screen {
+                                screen.fail(&full_repo_view.commits[idx].hash, &e);
+                                return Ok((vec![], vec![idx]));
+                            }
+                            eprintln!("warning: failed to check commit: {e}");
                             if !self.quiet {
                                 println!("   ❌ {done}/{total_commits} commits checked (failed)");
                             }
@@ -531,8 +767,19 @@ impl CheckCommand {
             }
         }
 
-        // Offer interactive retry for commits that failed
-        if !failed_indices.is_empty() && !self.quiet && std::io::stdin().is_terminal() {
+        // Offer interactive retry for commits that failed (the full-screen
+        // view lists them as failed instead)
+        if let Some(screen) = screen {
+            if !failed_indices.is_empty() {
+                screen.set_status(format!(
+                    "{} commit(s) failed to check",
+                    failed_indices.len()
+                ));
+            }
+        } else if !failed_indices.is_empty()
+            && !self.quiet
+            && crate::utils::interactive::stdin_is_interactive()
+        {
             self.run_interactive_retry_check(
                 &mut failed_indices,
                 full_repo_view,
@@ -550,7 +797,7 @@ impl CheckCommand {
             );
         }
 
-        if !failed_indices.is_empty() {
+        if !failed_indices.is_empty() && screen.is_none() {
             eprintln!(
                 "warning: {} commit(s) ultimately failed to check",
                 failed_indices.len()
@@ -565,7 +812,9 @@ impl CheckCommand {
         // Skip when all commits were in a single batch (AI already saw them together)
         let single_batch = batch_plan.batches.len() <= 1;
         if !self.no_coherence && !single_batch && successes.len() >= 2 {
-            if !self.quiet {
+            if let Some(screen) = screen {
+                screen.set_status("Running cross-commit coherence pass…");
+            } else if !self.quiet {
                 println!("🔗 Running cross-commit coherence pass...");
             }
             match claude_client
@@ -573,18 +822,20 @@ impl CheckCommand {
                 .await
             {
                 Ok(refined) => {
-                    if !self.quiet {
+                    if progress_lines {
                         println!("✅ All commits checked!");
                     }
                     return Ok(refined);
                 }
                 Err(e) => {
-                    eprintln!("warning: coherence pass failed, using individual results: {e}");
+                    if screen.is_none() {
+                        eprintln!("warning: coherence pass failed, using individual results: {e}");
+                    }
                 }
             }
         }
 
-        if !self.quiet {
+        if progress_lines {
             println!("✅ All commits checked!");
         }
 
@@ -602,27 +853,30 @@ impl CheckCommand {
         use crate::data::check::OutputFormat;
 
         match format {
-            OutputFormat::Text => self.output_text_report(report),
+            OutputFormat::Text => crate::utils::pager::page(&self.format_text_report(report)),
             OutputFormat::Json => {
                 let json = serde_json::to_string_pretty(report)
                     .context("Failed to serialize report to JSON")?;
-                println!("{json}");
-                Ok(())
+                crate::utils::pager::page(&json)
             }
             OutputFormat::Yaml => {
                 let yaml =
                     crate::data::to_yaml(report).context("Failed to serialize report to YAML")?;
-                println!("{yaml}");
+                crate::utils::pager::page(&yaml)
+            }
+            OutputFormat::Github => {
+                print!("{}", format_github_annotations(report));
                 Ok(())
             }
         }
     }
 
-    /// Outputs the text format report.
-    fn output_text_report(&self, report: &crate::data::check::CheckReport) -> Result<()> {
+    /// Formats the text report, as printed (and paged) by [`Self::output_report`].
+    fn format_text_report(&self, report: &crate::data::check::CheckReport) -> String {
         use crate::data::check::IssueSeverity;
+        use std::fmt::Write;
 
-        println!();
+        let mut out = String::from("\n");
 
         for result in &report.commits {
             if !should_display_commit(result.passes, self.show_passing) {
@@ -636,7 +890,11 @@ impl CheckCommand {
 
             let icon = super::formatting::determine_commit_icon(result.passes, &result.issues);
             let short_hash = super::formatting::truncate_hash(&result.hash);
-            println!("{}", format_commit_line(icon, short_hash, &result.message));
+            let _ = writeln!(
+                out,
+                "{}",
+                format_commit_line(icon, short_hash, &result.message)
+            );
 
             // Print issues
             for issue in &result.issues {
@@ -646,7 +904,8 @@ impl CheckCommand {
                 }
 
                 let severity_str = super::formatting::format_severity_label(issue.severity);
-                println!(
+                let _ = writeln!(
+                    out,
                     "   {} [{}] {}",
                     severity_str, issue.section, issue.explanation
                 );
@@ -655,18 +914,18 @@ impl CheckCommand {
             // Print suggestion if available and not in quiet mode
             if !self.quiet {
                 if let Some(suggestion) = &result.suggestion {
-                    println!();
-                    print!("{}", format_suggestion_text(suggestion, self.verbose));
+                    out.push('\n');
+                    out.push_str(&format_suggestion_text(suggestion, self.verbose));
                 }
             }
 
-            println!();
+            out.push('\n');
         }
 
         // Print summary
-        println!("{}", format_summary_text(&report.summary));
+        let _ = writeln!(out, "{}", format_summary_text(&report.summary));
 
-        Ok(())
+        out
     }
 
     /// Shows model information.
@@ -740,15 +999,13 @@ impl CheckCommand {
         is_terminal: bool,
         reader: &mut (dyn std::io::BufRead + Send),
     ) -> Result<bool> {
-        use crate::data::amendments::AmendmentFile;
-        use crate::git::AmendmentHandler;
         use std::io::{self, Write};
 
+        use crate::utils::i18n::tr;
+
         println!();
-        println!(
-            "🔧 {} commit(s) have issues with suggested fixes available.",
-            amendments.len()
-        );
+        let count = amendments.len();
+        println!("🔧 {}", tr("check-fixes-available", &[("count", &count)]));
 
         if !is_terminal {
             eprintln!("warning: stdin is not interactive, cannot prompt to apply suggested fixes");
@@ -756,7 +1013,7 @@ impl CheckCommand {
         }
 
         loop {
-            print!("❓ [A]pply suggested fixes, or [Q]uit? [A/q] ");
+            print!("❓ {} ", tr("check-menu", &[]));
             io::stdout().flush()?;
 
             let Some(input) = super::read_interactive_line(reader)? else {
@@ -766,31 +1023,49 @@ impl CheckCommand {
 
             match input.trim().to_lowercase().as_str() {
                 "a" | "apply" | "" => {
-                    let amendment_file = AmendmentFile { amendments };
-                    let temp_file = tempfile::NamedTempFile::new()
-                        .context("Failed to create temp file for amendments")?;
-                    amendment_file
-                        .save_to_file(temp_file.path())
-                        .context("Failed to save amendments")?;
-
-                    let handler = AmendmentHandler::new(repo_root)
-                        .context("Failed to initialize amendment handler")?;
-                    handler
-                        .apply_amendments(&temp_file.path().to_string_lossy())
-                        .context("Failed to apply amendments")?;
-
-                    println!("✅ Suggested fixes applied successfully!");
+                    self.apply_suggestions(repo_root, amendments)?;
                     return Ok(true);
                 }
                 "q" | "quit" => return Ok(false),
                 _ => {
-                    println!("Invalid choice. Please enter 'a' to apply or 'q' to quit.");
+                    println!("{}", tr("check-invalid-choice", &[]));
                 }
             }
         }
     }
 }
 
+impl CheckCommand {
+    /// Applies suggested messages as amendments.
+    fn apply_suggestions(
+        &self,
+        repo_root: &std::path::Path,
+        amendments: Vec<crate::data::amendments::Amendment>,
+    ) -> Result<()> {
+        use crate::data::amendments::AmendmentFile;
+        use crate::git::AmendmentHandler;
+
+        let amendment_file = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
+            amendments,
+        };
+        let temp_file =
+            tempfile::NamedTempFile::new().context("Failed to create temp file for amendments")?;
+        amendment_file
+            .save_to_file(temp_file.path())
+            .context("Failed to save amendments")?;
+
+        let handler =
+            AmendmentHandler::new(repo_root).context("Failed to initialize amendment handler")?;
+        handler
+            .apply_amendments(&temp_file.path().to_string_lossy())
+            .context("Failed to apply amendments")?;
+
+        println!("✅ Suggested fixes applied successfully!");
+        Ok(())
+    }
+}
+
 // --- Interactive retry helper ---
 
 impl CheckCommand {
@@ -923,7 +1198,10 @@ pub async fn run_check(
     // Preflight: validate AI credentials.
     crate::utils::check_ai_command_prerequisites(model.as_deref(), &repo_root)?;
 
-    let claude_client = crate::claude::create_default_claude_client(model, None).await?;
+    let context_dir = crate::claude::context::resolve_context_dir_at(None, &repo_root);
+    let claude_client = crate::claude::create_default_claude_client(model, None)
+        .await?
+        .with_commit_types(crate::claude::context::load_project_types(&context_dir));
     run_check_with_client(range, guidelines_path, strict, &claude_client, &repo_root).await
 }
 
@@ -981,6 +1259,7 @@ pub(crate) async fn run_check_with_client(
     };
 
     let mut repo_view = RepositoryView {
+        schema_version: crate::data::SCHEMA_VERSION,
         versions: Some(VersionInfo {
             omni_dev: env!("CARGO_PKG_VERSION").to_string(),
         }),
@@ -990,6 +1269,7 @@ pub(crate) async fn run_check_with_client(
         ai: ai_info,
         branch_info: Some(BranchInfo {
             branch: current_branch,
+            merge_conflicts: None,
         }),
         pr_template: None,
         pr_template_location: None,
@@ -1012,6 +1292,9 @@ pub(crate) async fn run_check_with_client(
     let valid_scopes = crate::claude::context::load_project_scopes(&context_dir, repo_root);
     for commit in &mut repo_view.commits {
         commit.analysis.refine_scope(&valid_scopes);
+        commit
+            .analysis
+            .refine_type(&commit.original_message, claude_client.commit_types());
     }
 
     let report = claude_client
@@ -1265,6 +1548,164 @@ mod run_check_tests {
     }
 }
 
+/// Fetches the commit hashes of a GitHub pull request via `gh pr view`.
+///
+/// Runs `gh` pinned to `repo_root` so the PR number resolves against the
+/// injected repository rather than the process CWD.
+pub(super) fn fetch_pr_commit_hashes(
+    number: u64,
+    repo_root: &std::path::Path,
+) -> Result<Vec<String>> {
+    let number_arg = number.to_string();
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        ["pr", "view", number_arg.as_str(), "--json", "commits"],
+        "pr view",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh pr view {number} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let hashes = parse_pr_commit_hashes(&String::from_utf8_lossy(&output.stdout))?;
+    if hashes.is_empty() {
+        anyhow::bail!("PR #{number} has no commits");
+    }
+    Ok(hashes)
+}
+
+/// Extracts commit OIDs from `gh pr view --json commits` output, oldest first.
+fn parse_pr_commit_hashes(json: &str) -> Result<Vec<String>> {
+    let value: serde_json::Value =
+        serde_json::from_str(json).context("Failed to parse PR JSON from gh")?;
+    let commits = value
+        .get("commits")
+        .and_then(serde_json::Value::as_array)
+        .context("gh output has no `commits` array")?;
+    Ok(commits
+        .iter()
+        .filter_map(|c| c.get("oid").and_then(serde_json::Value::as_str))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Hidden marker identifying the check report comment on a pull request.
+const REPORT_COMMENT_MARKER: &str = "<!-- omni-dev-check-report -->";
+
+/// Resolves the pull request for `--comment-pr`: the explicit number, else
+/// `--pr`, else the PR of a GitHub Actions `pull_request` run (whose
+/// `GITHUB_REF` is `refs/pull/<number>/merge`).
+fn comment_pr_number(
+    explicit: Option<u64>,
+    pr: Option<u64>,
+    github_ref: Option<&str>,
+) -> Result<u64> {
+    explicit
+        .or(pr)
+        .or_else(|| {
+            github_ref?
+                .strip_prefix("refs/pull/")?
+                .split('/')
+                .next()?
+                .parse()
+                .ok()
+        })
+        .context(
+            "--comment-pr needs a PR number outside a GitHub Actions pull_request run; \
+             pass --comment-pr <NUMBER>",
+        )
+}
+
+/// Posts `markdown` as the check report comment on PR `number`, replacing
+/// the comment left by a previous run.
+async fn post_report_comment(
+    repo_root: &std::path::Path,
+    number: u64,
+    markdown: &str,
+) -> Result<()> {
+    let body = format!("{}\n\n{REPORT_COMMENT_MARKER}\n", markdown.trim_end());
+    match crate::forge::Forge::detect(repo_root).api_client()? {
+        Some(api) => {
+            api.upsert_pr_comment(number, REPORT_COMMENT_MARKER, &body)
+                .await
+        }
+        None => gh_upsert_report_comment(repo_root, number, &body),
+    }
+    .with_context(|| format!("Failed to post the check report to PR #{number}"))
+}
+
+/// Upserts the report comment with `gh` (the fallback when no GitHub token
+/// resolves for the native API client).
+fn gh_upsert_report_comment(repo_root: &std::path::Path, number: u64, body: &str) -> Result<()> {
+    let gh = crate::pr_status::resolve_gh_binary();
+    let list_path = format!("repos/{{owner}}/{{repo}}/issues/{number}/comments");
+    let filter = format!(".[] | select(.body | contains(\"{REPORT_COMMENT_MARKER}\")) | .id");
+    let output = crate::github_metrics::run_gh(
+        &gh,
+        [
+            "api",
+            list_path.as_str(),
+            "--paginate",
+            "--jq",
+            filter.as_str(),
+        ],
+        "api",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to list comments on PR #{number}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let existing = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().parse::<u64>().ok());
+
+    let body_field = format!("body={body}");
+    let output = match existing {
+        Some(id) => {
+            let edit_path = format!("repos/{{owner}}/{{repo}}/issues/comments/{id}");
+            crate::github_metrics::run_gh(
+                &gh,
+                [
+                    "api",
+                    "-X",
+                    "PATCH",
+                    edit_path.as_str(),
+                    "-f",
+                    body_field.as_str(),
+                ],
+                "api",
+                Some(repo_root),
+            )
+        }
+        None => {
+            let number = number.to_string();
+            crate::github_metrics::run_gh(
+                &gh,
+                ["pr", "comment", number.as_str(), "--body", body],
+                "pr comment",
+                Some(repo_root),
+            )
+        }
+    }
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to post comment on PR #{number}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
 // --- Extracted pure functions ---
 
 /// Returns whether a commit should be displayed based on its pass status.
@@ -1280,6 +1721,106 @@ fn has_errors_or_warnings(issues: &[crate::data::check::CommitIssue]) -> bool {
         .any(|i| matches!(i.severity, IssueSeverity::Error | IssueSeverity::Warning))
 }
 
+/// The full-screen row state for a check result.
+fn check_row_state(result: &crate::data::check::CommitCheckResult) -> super::tui::RowState {
+    use super::tui::RowState;
+    use crate::data::check::IssueSeverity;
+
+    if result
+        .issues
+        .iter()
+        .any(|i| i.severity == IssueSeverity::Error)
+    {
+        RowState::Error
+    } else if result
+        .issues
+        .iter()
+        .any(|i| i.severity == IssueSeverity::Warning)
+    {
+        RowState::Warning
+    } else {
+        RowState::Passed
+    }
+}
+
+/// Adds an issue to each commit the changelog rule finds without a required
+/// changelog entry, failing the commit unless the rule's severity is `info`.
+fn apply_changelog_rule(
+    report: crate::data::check::CheckReport,
+    rule: &crate::git::changelog_rule::ChangelogRule,
+    commits: &[crate::git::CommitInfo],
+) -> crate::data::check::CheckReport {
+    use crate::data::check::{CheckReport, CommitIssue};
+
+    let missing = rule.missing_entries(commits);
+    if missing.is_empty() {
+        return report;
+    }
+    let mut results = report.commits;
+    for commit in missing {
+        let Some(result) = results
+            .iter_mut()
+            .find(|r| commit.hash.starts_with(&r.hash))
+        else {
+            continue;
+        };
+        result.issues.push(CommitIssue {
+            severity: rule.severity,
+            section: "Changelog".to_string(),
+            rule: crate::git::changelog_rule::CHANGELOG_RULE.to_string(),
+            explanation: format!(
+                "No change to {}; add an entry (`omni-dev git changelog check --fix` drafts one) \
+                 or mark the commit [skip changelog]",
+                rule.paths.join(", ")
+            ),
+        });
+        result.passes = result.passes && !has_errors_or_warnings(&result.issues);
+    }
+    CheckReport::new(results)
+}
+
+/// Shows the check results on the full-screen view and reviews the
+/// suggested fixes, none of which start out accepted. Returns the accepted
+/// fixes as amendments, or `None` when the user quits.
+fn review_report(
+    screen: &super::tui::Tui,
+    report: &crate::data::check::CheckReport,
+) -> Result<Option<Vec<crate::data::amendments::Amendment>>> {
+    use crate::data::amendments::Amendment;
+
+    screen.update(|view| {
+        for result in &report.commits {
+            view.set_state(&result.hash, check_row_state(result));
+            for issue in &result.issues {
+                view.add_note(
+                    &result.hash,
+                    format!(
+                        "{} [{}] {}",
+                        issue.severity, issue.section, issue.explanation
+                    ),
+                );
+            }
+            if let Some(suggestion) = &result.suggestion {
+                view.propose(
+                    &result.hash,
+                    &suggestion.message,
+                    super::tui::Decision::Skip,
+                );
+                view.add_note(
+                    &result.hash,
+                    format!("Suggestion: {}", suggestion.explanation),
+                );
+            }
+        }
+    });
+    Ok(screen.review()?.map(|accepted| {
+        accepted
+            .into_iter()
+            .map(|(hash, message)| Amendment::new(hash, message))
+            .collect()
+    }))
+}
+
 /// Returns whether the twiddle (auto-fix) flow should be offered.
 fn should_offer_twiddle(
     twiddle_flag: bool,
@@ -1329,6 +1870,134 @@ fn format_commit_line(icon: &str, short_hash: &str, message: &str) -> String {
     format!("{icon} {short_hash} - \"{message}\"")
 }
 
+/// Formats a check report as GitHub Actions workflow commands.
+///
+/// Each issue becomes one `::error`/`::warning`/`::notice` line so the Actions
+/// UI renders it inline and attaches it to the PR as an annotation. A final
+/// `::notice` carries the summary counts.
+fn format_github_annotations(report: &crate::data::check::CheckReport) -> String {
+    use crate::data::check::IssueSeverity;
+
+    let mut output = String::new();
+    for result in &report.commits {
+        let short_hash = super::formatting::truncate_hash(&result.hash);
+        for issue in &result.issues {
+            let command = match issue.severity {
+                IssueSeverity::Error => "error",
+                IssueSeverity::Warning => "warning",
+                IssueSeverity::Info => "notice",
+            };
+            let title = format!("{short_hash}: {} ({})", issue.section, issue.rule);
+            let message = format!("{short_hash} \"{}\": {}", result.message, issue.explanation);
+            output.push_str(&format!(
+                "::{command} title={}::{}\n",
+                escape_workflow_property(&title),
+                escape_workflow_data(&message)
+            ));
+        }
+    }
+
+    let summary = &report.summary;
+    output.push_str(&format!(
+        "::notice title=omni-dev check::{}\n",
+        escape_workflow_data(&format!(
+            "{} commits checked: {} errors, {} warnings, {} passed, {} with issues",
+            summary.total_commits,
+            summary.error_count,
+            summary.warning_count,
+            summary.passing_commits,
+            summary.failing_commits,
+        ))
+    ));
+    output
+}
+
+/// Formats a check report as Markdown for a PR comment or CI job summary.
+///
+/// A summary table is followed by one section per commit with issues
+/// (passing commits too when `show_passing` is set), listing each finding
+/// and the suggested replacement message.
+fn format_markdown_report(report: &crate::data::check::CheckReport, show_passing: bool) -> String {
+    let summary = &report.summary;
+    let mut output = String::from("## omni-dev check\n\n");
+    output.push_str("| Commits | Passed | With issues | Errors | Warnings | Info |\n");
+    output.push_str("|---:|---:|---:|---:|---:|---:|\n");
+    output.push_str(&format!(
+        "| {} | {} | {} | {} | {} | {} |\n",
+        summary.total_commits,
+        summary.passing_commits,
+        summary.failing_commits,
+        summary.error_count,
+        summary.warning_count,
+        summary.info_count,
+    ));
+
+    if summary.failing_commits == 0 {
+        output.push_str("\nAll commits follow the guidelines.\n");
+    }
+
+    for result in &report.commits {
+        if !should_display_commit(result.passes, show_passing) {
+            continue;
+        }
+        let icon = super::formatting::determine_commit_icon(result.passes, &result.issues);
+        let short_hash = super::formatting::truncate_hash(&result.hash);
+        output.push_str(&format!(
+            "\n### {} `{short_hash}` {}\n",
+            icon.trim_end(),
+            escape_markdown_cell(&result.message)
+        ));
+
+        if !result.issues.is_empty() {
+            output.push_str("\n| Severity | Section | Rule | Explanation |\n");
+            output.push_str("|---|---|---|---|\n");
+            for issue in &result.issues {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    issue.severity,
+                    escape_markdown_cell(&issue.section),
+                    escape_markdown_cell(&issue.rule),
+                    escape_markdown_cell(&issue.explanation),
+                ));
+            }
+        }
+
+        if let Some(suggestion) = &result.suggestion {
+            output.push_str("\n**Suggested message:**\n\n```text\n");
+            output.push_str(suggestion.message.trim_end());
+            output.push_str("\n```\n");
+            if !suggestion.explanation.is_empty() {
+                output.push_str(&format!("\n_Why:_ {}\n", suggestion.explanation.trim()));
+            }
+        }
+    }
+
+    output
+}
+
+/// Escapes text for a single-line Markdown table cell or heading.
+fn escape_markdown_cell(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace("\r\n", " ")
+        .replace('\n', " ")
+}
+
+/// Escapes the message part of a GitHub Actions workflow command.
+fn escape_workflow_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes a `key=value` property of a GitHub Actions workflow command.
+fn escape_workflow_property(value: &str) -> String {
+    escape_workflow_data(value)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -1417,6 +2086,11 @@ mod tests {
         assert!(!should_offer_twiddle(true, true, OutputFormat::Json));
     }
 
+    #[test]
+    fn offer_twiddle_github_format() {
+        assert!(!should_offer_twiddle(true, true, OutputFormat::Github));
+    }
+
     // --- format_suggestion_text ---
 
     #[test]
@@ -1470,24 +2144,230 @@ mod tests {
         assert_eq!(line, "✅ abc1234 - \"feat: add feature\"");
     }
 
+    // --- parse_pr_commit_hashes ---
+
+    #[test]
+    fn comment_pr_number_prefers_explicit_then_pr_then_ci() {
+        let ci = Some("refs/pull/77/merge");
+        assert_eq!(comment_pr_number(Some(5), Some(6), ci).unwrap(), 5);
+        assert_eq!(comment_pr_number(None, Some(6), ci).unwrap(), 6);
+        assert_eq!(comment_pr_number(None, None, ci).unwrap(), 77);
+        assert!(comment_pr_number(None, None, Some("refs/heads/main")).is_err());
+        assert!(comment_pr_number(None, None, None).is_err());
+    }
+
+    #[test]
+    fn pr_commit_hashes_in_order() {
+        let json = r#"{"commits":[{"oid":"aaa","messageHeadline":"one"},{"oid":"bbb"}]}"#;
+        assert_eq!(parse_pr_commit_hashes(json).unwrap(), vec!["aaa", "bbb"]);
+    }
+
+    #[test]
+    fn pr_commit_hashes_missing_array_errors() {
+        assert!(parse_pr_commit_hashes(r#"{"title":"x"}"#).is_err());
+        assert!(parse_pr_commit_hashes("not json").is_err());
+    }
+
+    // --- format_github_annotations ---
+
+    #[test]
+    fn github_annotations_one_line_per_issue() {
+        use crate::data::check::{CheckReport, CommitCheckResult};
+
+        let report = CheckReport::new(vec![
+            CommitCheckResult {
+                hash: "abc1234567890".to_string(),
+                message: "added stuff".to_string(),
+                issues: vec![
+                    CommitIssue {
+                        severity: IssueSeverity::Error,
+                        section: "Format".to_string(),
+                        rule: "conventional-type".to_string(),
+                        explanation: "missing type".to_string(),
+                    },
+                    CommitIssue {
+                        severity: IssueSeverity::Info,
+                        section: "Content".to_string(),
+                        rule: "body".to_string(),
+                        explanation: "consider a body".to_string(),
+                    },
+                ],
+                suggestion: None,
+                passes: false,
+                summary: None,
+            },
+            CommitCheckResult {
+                hash: "def5678".to_string(),
+                message: "feat: ok".to_string(),
+                issues: vec![],
+                suggestion: None,
+                passes: true,
+                summary: None,
+            },
+        ]);
+
+        let output = format_github_annotations(&report);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "::error title=abc12345%3A Format (conventional-type)::abc12345 \"added stuff\": missing type"
+        );
+        assert!(lines[1].starts_with("::notice title=abc12345%3A Content (body)::"));
+        assert!(lines[2].starts_with("::notice title=omni-dev check::2 commits checked"));
+        assert!(lines[2].contains("1 errors, 0 warnings"));
+    }
+
+    #[test]
+    fn workflow_data_escapes_newlines_and_percent() {
+        assert_eq!(escape_workflow_data("50%\nnext\r"), "50%25%0Anext%0D");
+    }
+
+    #[test]
+    fn workflow_property_escapes_colon_and_comma() {
+        assert_eq!(escape_workflow_property("a:b,c"), "a%3Ab%2Cc");
+    }
+
+    // --- apply_changelog_rule ---
+
+    #[test]
+    fn changelog_rule_flags_feature_commits_without_an_entry() {
+        use crate::data::check::{CheckReport, CommitCheckResult};
+        use crate::git::changelog_rule::ChangelogRule;
+        use crate::git::commit::{FileChange, FileChanges};
+        use crate::git::{CommitAnalysis, CommitInfo};
+
+        let commit = |hash: &str, message: &str| CommitInfo {
+            hash: hash.to_string(),
+            author: "Dev <dev@example.com>".to_string(),
+            date: chrono::Utc::now().fixed_offset(),
+            original_message: message.to_string(),
+            in_main_branches: vec![],
+            analysis: CommitAnalysis {
+                detected_type: "chore".to_string(),
+                detected_scope: String::new(),
+                proposed_message: message.to_string(),
+                file_changes: FileChanges {
+                    total_files: 1,
+                    files_added: 0,
+                    files_deleted: 0,
+                    file_list: vec![FileChange {
+                        status: "M".to_string(),
+                        file: "src/lib.rs".to_string(),
+                    }],
+                },
+                diff_summary: String::new(),
+                diff_file: String::new(),
+                file_diffs: vec![],
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
+            },
+        };
+        let commits = vec![
+            commit(&"a".repeat(40), "feat: add notes"),
+            commit(&"b".repeat(40), "chore: bump deps"),
+        ];
+        let report = CheckReport::new(
+            commits
+                .iter()
+                .map(|c| CommitCheckResult {
+                    hash: c.hash[..8].to_string(),
+                    message: c.original_message.clone(),
+                    issues: vec![],
+                    suggestion: None,
+                    passes: true,
+                    summary: None,
+                })
+                .collect(),
+        );
+
+        let rule = ChangelogRule::from_config("severity: error\n").unwrap();
+        let report = apply_changelog_rule(report, &rule, &commits);
+        assert_eq!(report.summary.error_count, 1);
+        assert!(!report.commits[0].passes);
+        assert_eq!(report.commits[0].issues[0].rule, "changelog-entry");
+        assert!(report.commits[1].passes);
+    }
+
+    // --- format_markdown_report ---
+
+    #[test]
+    fn markdown_report_lists_failing_commits_with_suggestions() {
+        use crate::data::check::{CheckReport, CommitCheckResult};
+
+        let report = CheckReport::new(vec![
+            CommitCheckResult {
+                hash: "abc1234567890".to_string(),
+                message: "added stuff".to_string(),
+                issues: vec![CommitIssue {
+                    severity: IssueSeverity::Error,
+                    section: "Format".to_string(),
+                    rule: "conventional-type".to_string(),
+                    explanation: "missing type | scope".to_string(),
+                }],
+                suggestion: Some(CommitSuggestion {
+                    message: "feat(cli): add stuff\n\nBody.".to_string(),
+                    explanation: "uses conventional format".to_string(),
+                }),
+                passes: false,
+                summary: None,
+            },
+            CommitCheckResult {
+                hash: "def5678".to_string(),
+                message: "feat: ok".to_string(),
+                issues: vec![],
+                suggestion: None,
+                passes: true,
+                summary: None,
+            },
+        ]);
+
+        let output = format_markdown_report(&report, false);
+        assert!(output.starts_with("## omni-dev check\n"));
+        assert!(output.contains("| 2 | 1 | 1 | 1 | 0 | 0 |"));
+        assert!(output.contains("### ❌ `abc12345` added stuff"));
+        assert!(output.contains("| ERROR | Format | conventional-type | missing type \\| scope |"));
+        assert!(output.contains("```text\nfeat(cli): add stuff\n\nBody.\n```"));
+        assert!(output.contains("_Why:_ uses conventional format"));
+        assert!(!output.contains("def5678"));
+
+        let with_passing = format_markdown_report(&report, true);
+        assert!(with_passing.contains("### ✅ `def5678` feat: ok"));
+    }
+
+    #[test]
+    fn markdown_report_all_passing() {
+        let report = crate::data::check::CheckReport::new(vec![]);
+        let output = format_markdown_report(&report, false);
+        assert!(output.contains("All commits follow the guidelines."));
+    }
+
     // --- check_with_map_reduce (error path coverage) ---
 
     fn make_check_cmd(quiet: bool) -> CheckCommand {
         CheckCommand {
             commit_range: None,
+            pr: None,
             context_dir: None,
             guidelines: None,
-            output: OutputFormat::Text,
+            output: Some(OutputFormat::Text),
             format: None,
+            report: None,
+            report_file: None,
             strict: false,
             quiet,
             verbose: false,
             show_passing: false,
-            concurrency: 4,
+            concurrency: Some(4),
             batch_size: None,
             no_coherence: true,
             no_suggestions: false,
+            comment_pr: None,
             twiddle: false,
+            tui: false,
+            no_cache: true,
+            save_view: None,
+            update_view: None,
         }
     }
 
@@ -1525,6 +2405,8 @@ mod tests {
                 diff_summary: String::new(),
                 diff_file: tmp.path().to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         (commit, tmp)
@@ -1533,6 +2415,7 @@ mod tests {
     fn make_check_repo_view(commits: Vec<crate::git::CommitInfo>) -> crate::data::RepositoryView {
         use crate::data::{AiInfo, FieldExplanation, RepositoryView, WorkingDirectoryInfo};
         RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -1579,7 +2462,7 @@ mod tests {
         let repo_view = make_check_repo_view(vec![commit]);
         let client = make_client(errs(3));
         let result = cmd
-            .check_with_map_reduce(&client, &repo_view, None, &[])
+            .check_with_map_reduce(&client, &repo_view, None, &[], None)
             .await;
         assert!(result.is_err(), "empty successes should bail");
     }
@@ -1592,7 +2475,7 @@ mod tests {
         let repo_view = make_check_repo_view(vec![commit]);
         let client = make_client(vec![Ok(check_yaml("abc00000"))]);
         let result = cmd
-            .check_with_map_reduce(&client, &repo_view, None, &[])
+            .check_with_map_reduce(&client, &repo_view, None, &[], None)
             .await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().commits.len(), 1);
@@ -1611,7 +2494,7 @@ mod tests {
         responses.push(Ok(check_yaml("def00000"))); // def individual
         let client = make_client(responses);
         let result = cmd
-            .check_with_map_reduce(&client, &repo_view, None, &[])
+            .check_with_map_reduce(&client, &repo_view, None, &[], None)
             .await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().commits.len(), 2);
@@ -1631,7 +2514,7 @@ mod tests {
         responses.extend(errs(3)); // def individual exhausts retries
         let client = make_client(responses);
         let result = cmd
-            .check_with_map_reduce(&client, &repo_view, None, &[])
+            .check_with_map_reduce(&client, &repo_view, None, &[], None)
             .await;
         // abc succeeded, so successes is non-empty and the method returns Ok
         assert!(result.is_ok());
@@ -1651,7 +2534,7 @@ mod tests {
         responses.extend(errs(3)); // def individual exhausts retries
         let client = make_client(responses);
         let result = cmd
-            .check_with_map_reduce(&client, &repo_view, None, &[])
+            .check_with_map_reduce(&client, &repo_view, None, &[], None)
             .await;
         assert!(result.is_err(), "no successes should bail");
     }
@@ -1675,7 +2558,7 @@ mod tests {
         responses.push(Ok(check_yaml("def00000")));
         let client = make_client(responses);
         let result = cmd
-            .check_with_map_reduce(&client, &repo_view, None, &[])
+            .check_with_map_reduce(&client, &repo_view, None, &[], None)
             .await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().commits.len(), 2);
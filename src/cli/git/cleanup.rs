@@ -0,0 +1,355 @@
+//! Branch cleanup command — deletes local branches whose work has landed:
+//! those fully contained in the main branch, or whose pull request was
+//! merged or closed on the forge.
+
+use std::fmt;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use super::defaults::AutoApply;
+use super::info::InfoCommand;
+use crate::data::PullRequest;
+use crate::git::GitRepository;
+
+/// Branches never offered for deletion, besides the current and base branch.
+const PROTECTED_BRANCHES: &[&str] = &["main", "master", "develop"];
+
+/// Remote whose branches `--remote` deletes.
+const REMOTE: &str = "origin";
+
+/// Branch cleanup command options.
+#[derive(Parser)]
+pub struct CleanupCommand {
+    /// Main branch to compare against (defaults to origin/main, origin/master, main, or master).
+    #[arg(long, value_name = "BRANCH")]
+    pub base: Option<String>,
+
+    /// Lists the branches that would be deleted without deleting them.
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Also deletes each branch from origin.
+    #[arg(long)]
+    pub remote: bool,
+
+    #[command(flatten)]
+    pub apply: AutoApply,
+}
+
+/// Why a branch is safe to delete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StaleReason {
+    /// Every commit on the branch is already on the base branch.
+    Contained(String),
+    /// The branch's pull request was merged (e.g. squashed or rebased).
+    PrMerged(u64),
+    /// The branch's pull request was closed without merging.
+    PrClosed(u64),
+}
+
+impl fmt::Display for StaleReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Contained(base) => write!(f, "merged into {base}"),
+            Self::PrMerged(number) => write!(f, "PR #{number} merged"),
+            Self::PrClosed(number) => write!(f, "PR #{number} closed"),
+        }
+    }
+}
+
+impl CleanupCommand {
+    /// Executes the branch cleanup command.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let base = match &self.base {
+            Some(base) => base.clone(),
+            None => git_repo.resolve_default_base_branch().context(
+                "No default base branch found (checked origin/main, origin/master, main, master). \
+                 Pass one with --base.",
+            )?,
+        };
+
+        let candidates = local_branch_candidates(&git_repo, &base)?;
+        let mut stale: Vec<(String, StaleReason)> = Vec::new();
+        let mut forge_available = true;
+        for (branch, contained) in candidates {
+            // Branches already on the base need no forge lookup.
+            let pr = if !contained && forge_available {
+                match InfoCommand::get_latest_branch_pr(&branch, repo_root).await {
+                    Ok(pr) => pr,
+                    Err(e) => {
+                        println!(
+                            "⚠️  Could not look up pull requests ({e:#}); only branches merged into {base} are considered"
+                        );
+                        forge_available = false;
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let Some(reason) = stale_reason(&base, contained, pr.as_ref()) else {
+                continue;
+            };
+            // A branch reused or committed to after its PR closed still has
+            // work of its own, and deleting it would lose that work.
+            if let Some(pr) = pr {
+                let in_pr = InfoCommand::get_pr_commits(pr.number, repo_root)
+                    .await
+                    .is_ok_and(|commits| tip_in_pr(&git_repo, &branch, &commits));
+                if !in_pr {
+                    println!(
+                        "⚠️  Skipping {branch}: it has commits that are not in PR #{}",
+                        pr.number
+                    );
+                    continue;
+                }
+            }
+            stale.push((branch, reason));
+        }
+
+        if stale.is_empty() {
+            println!("✨ No merged or closed branches to clean up");
+            return Ok(());
+        }
+
+        let width = stale.iter().map(|(b, _)| b.len()).max().unwrap_or(0);
+        println!("🧹 Branches to delete ({}):", stale.len());
+        for (branch, reason) in &stale {
+            println!("  {branch:<width$}  {reason}");
+        }
+
+        if self.dry_run {
+            println!("\nℹ️  Dry run: no branches were deleted");
+            return Ok(());
+        }
+        if !self.apply.destructive()? && !confirm_cleanup(stale.len(), self.remote)? {
+            println!("❌ Branch cleanup cancelled by user");
+            return Ok(());
+        }
+
+        let mut deleted = 0;
+        for (branch, _) in &stale {
+            match self.delete_branch(&git_repo, branch) {
+                Ok(()) => {
+                    println!("   🗑️  Deleted {branch}");
+                    deleted += 1;
+                }
+                Err(e) => println!("⚠️  Could not delete {branch}: {e:#}"),
+            }
+        }
+        println!("✅ Deleted {deleted} of {} branch(es)", stale.len());
+        Ok(())
+    }
+
+    /// Deletes `branch` from `origin` (with `--remote`, when it was pushed)
+    /// and then locally.
+    fn delete_branch(&self, git_repo: &GitRepository, branch: &str) -> Result<()> {
+        let repo = git_repo.repository();
+        if self.remote
+            && repo
+                .find_branch(&format!("{REMOTE}/{branch}"), git2::BranchType::Remote)
+                .is_ok()
+        {
+            git_repo.delete_remote_branch(branch, REMOTE)?;
+        }
+        repo.find_branch(branch, git2::BranchType::Local)
+            .and_then(|mut b| b.delete())
+            .with_context(|| format!("Failed to delete local branch '{branch}'"))
+    }
+}
+
+/// Lists the local branches eligible for cleanup with whether each is fully
+/// contained in `base`. The current branch, the base branch, and
+/// [`PROTECTED_BRANCHES`] are skipped.
+fn local_branch_candidates(git_repo: &GitRepository, base: &str) -> Result<Vec<(String, bool)>> {
+    let repo = git_repo.repository();
+    let base_oid = repo
+        .revparse_single(base)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("Base branch '{base}' does not exist"))?
+        .id();
+    let current = git_repo.get_current_branch().ok();
+    let base_name = base
+        .strip_prefix(REMOTE)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .unwrap_or(base);
+
+    let mut candidates = Vec::new();
+    for entry in repo.branches(Some(git2::BranchType::Local))? {
+        let (branch, _) = entry?;
+        let Some(name) = branch.name()?.map(str::to_string) else {
+            continue;
+        };
+        if PROTECTED_BRANCHES.contains(&name.as_str())
+            || name == base_name
+            || current.as_deref() == Some(name.as_str())
+        {
+            continue;
+        }
+        let Some(tip) = branch.get().target() else {
+            continue;
+        };
+        let contained = tip == base_oid || repo.graph_descendant_of(base_oid, tip)?;
+        candidates.push((name, contained));
+    }
+    candidates.sort();
+    Ok(candidates)
+}
+
+/// Decides whether a branch is stale: contained in `base`, or its latest
+/// pull request is merged or closed. A branch with an open PR or no PR stays.
+fn stale_reason(base: &str, contained: bool, pr: Option<&PullRequest>) -> Option<StaleReason> {
+    if contained {
+        return Some(StaleReason::Contained(base.to_string()));
+    }
+    let pr = pr?;
+    match pr.state.to_uppercase().as_str() {
+        "MERGED" => Some(StaleReason::PrMerged(pr.number)),
+        "CLOSED" => Some(StaleReason::PrClosed(pr.number)),
+        _ => None,
+    }
+}
+
+/// Returns whether the tip of local `branch` is one of `pr_commits` or an
+/// ancestor of one, i.e. the pull request holds all of the branch's work.
+fn tip_in_pr(git_repo: &GitRepository, branch: &str, pr_commits: &[String]) -> bool {
+    let repo = git_repo.repository();
+    let Some(tip) = repo
+        .find_branch(branch, git2::BranchType::Local)
+        .ok()
+        .and_then(|b| b.get().target())
+    else {
+        return false;
+    };
+    pr_commits.iter().any(|hash| {
+        git2::Oid::from_str(hash)
+            .is_ok_and(|oid| oid == tip || repo.graph_descendant_of(oid, tip).unwrap_or(false))
+    })
+}
+
+/// Asks whether to delete `count` branches.
+fn confirm_cleanup(count: usize, remote: bool) -> Result<bool> {
+    use std::io::{self, Write};
+
+    let key = if remote {
+        "confirm-cleanup-remote"
+    } else {
+        "confirm-cleanup"
+    };
+    print!("❓ {} ", crate::utils::i18n::tr(key, &[("count", &count)]));
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(crate::utils::i18n::is_yes(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64, state: &str) -> PullRequest {
+        PullRequest {
+            number,
+            title: String::new(),
+            state: state.to_string(),
+            url: String::new(),
+            body: String::new(),
+            base: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn classifies_stale_branches() {
+        assert_eq!(
+            stale_reason("main", true, Some(&pr(3, "OPEN"))),
+            Some(StaleReason::Contained("main".to_string()))
+        );
+        assert_eq!(
+            stale_reason("main", false, Some(&pr(4, "MERGED"))),
+            Some(StaleReason::PrMerged(4))
+        );
+        assert_eq!(
+            stale_reason("main", false, Some(&pr(5, "closed"))),
+            Some(StaleReason::PrClosed(5))
+        );
+        assert_eq!(stale_reason("main", false, Some(&pr(6, "OPEN"))), None);
+        assert_eq!(stale_reason("main", false, None), None);
+    }
+
+    #[test]
+    fn candidates_skip_protected_and_report_containment() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let first = repo
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        let first = repo.find_commit(first).unwrap();
+        repo.branch("merged-work", &first, false).unwrap();
+        let second = repo
+            .commit(None, &sig, &sig, "wip", &tree, &[&first])
+            .unwrap();
+        repo.branch("open-work", &repo.find_commit(second).unwrap(), false)
+            .unwrap();
+        repo.branch("main", &first, true).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+
+        let git_repo = GitRepository::open_at(dir.path()).unwrap();
+        let candidates = local_branch_candidates(&git_repo, "main").unwrap();
+        assert_eq!(
+            candidates,
+            vec![
+                ("merged-work".to_string(), true),
+                ("open-work".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn tip_must_be_in_the_pr() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let first = repo
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+        let second = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "second",
+                &tree,
+                &[&repo.find_commit(first).unwrap()],
+            )
+            .unwrap();
+        repo.branch("behind", &repo.find_commit(first).unwrap(), false)
+            .unwrap();
+        repo.branch("ahead", &repo.find_commit(second).unwrap(), false)
+            .unwrap();
+
+        let git_repo = GitRepository::open_at(dir.path()).unwrap();
+        let pr_head = [second.to_string()];
+        assert!(tip_in_pr(&git_repo, "behind", &pr_head));
+        assert!(tip_in_pr(&git_repo, "ahead", &pr_head));
+        assert!(!tip_in_pr(&git_repo, "ahead", &[first.to_string()]));
+        assert!(!tip_in_pr(&git_repo, "missing", &pr_head));
+    }
+}
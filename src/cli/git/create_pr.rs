@@ -4,8 +4,14 @@ use anyhow::{bail, Context, Result};
 use clap::Parser;
 use tracing::{debug, error, warn};
 
+use super::defaults::{parse_bool_string, AutoApply};
 use super::info::InfoCommand;
 use crate::claude::error::is_transient_ai_error as ai_error_is_transient;
+use crate::git::PathFilter;
+
+/// Opens the hidden comment recording which branch head a PR description was
+/// generated from, so `branch ready` can tell when it has gone stale.
+const GENERATED_HEAD_MARKER: &str = "<!-- omni-dev-head: ";
 
 /// Create PR command options.
 #[derive(Parser)]
@@ -14,9 +20,8 @@ pub struct CreatePrCommand {
     #[arg(long, value_name = "BRANCH")]
     pub base: Option<String>,
 
-    /// Skips confirmation prompt and creates PR automatically.
-    #[arg(long)]
-    pub auto_apply: bool,
+    #[command(flatten)]
+    pub apply: AutoApply,
 
     /// Saves generated PR details to file without creating PR.
     #[arg(long, value_name = "FILE")]
@@ -41,6 +46,23 @@ pub struct CreatePrCommand {
     /// Use commit messages (not the diff) as the primary input for PR generation.
     #[arg(long)]
     pub from_commits: bool,
+
+    /// Skips suggesting reviewers from CODEOWNERS and recent blame.
+    #[arg(long)]
+    pub no_reviewers: bool,
+
+    /// Skips applying labels inferred from commit types and scopes.
+    #[arg(long)]
+    pub no_labels: bool,
+
+    /// Issue the PR closes on merge: 123, #123, or owner/repo#123 (repeatable).
+    #[arg(long, value_name = "ISSUE")]
+    pub fixes: Vec<String>,
+
+    /// Restricts analysis to files matching these globs (comma-separated or
+    /// repeated); commits touching none of them are skipped.
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub paths: Vec<String>,
 }
 
 /// PR action choices.
@@ -65,11 +87,11 @@ pub struct PrContent {
 ///
 /// `used_fallback` is deliberately kept out of [`PrContent`], which is
 /// serialized to `pr-details.yaml` and must keep its two-field schema.
-struct GeneratedPr {
+pub(super) struct GeneratedPr {
     /// The title and description to apply.
-    content: PrContent,
+    pub(super) content: PrContent,
     /// True when the AI call failed and `content` is template-derived.
-    used_fallback: bool,
+    pub(super) used_fallback: bool,
 }
 
 impl GeneratedPr {
@@ -117,7 +139,10 @@ impl CreatePrCommand {
     }
 
     /// Executes the create PR command.
-    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+    pub async fn execute(mut self, repo: Option<&std::path::Path>) -> Result<()> {
+        self.apply = self
+            .apply
+            .with_defaults(&crate::utils::settings::SettingsEnv::load());
         // Resolve the repo root once; every git, config, scratch, PR-template,
         // and `gh` read below anchors to it (the CWD is the default when no
         // path is injected).
@@ -126,25 +151,35 @@ impl CreatePrCommand {
             None => std::env::current_dir().context("Failed to determine current directory")?,
         };
         let repo_root = repo_root.as_path();
+        crate::git::issue_links::parse_issue_refs(&self.fixes)?;
+        PathFilter::new(&self.paths)?;
 
         // Preflight check: validate all prerequisites before any processing
         // This catches missing credentials/tools early before wasting time
         // Model selection uses the global `--model` flag (propagated as
         // OMNI_DEV_MODEL) and the per-backend env chain.
-        let ai_info = crate::utils::check_pr_command_prerequisites(None, repo_root)?;
+        let ai_info = crate::utils::check_pr_command_prerequisites(None, repo_root).await?;
         println!(
             "✓ {} credentials verified (model: {})",
             ai_info.provider, ai_info.model
         );
-        println!("✓ GitHub CLI verified");
+        let forge = crate::forge::Forge::detect(repo_root);
+        let forge_api = forge.api_client()?;
+        if forge_api.is_some() {
+            println!("✓ {} API access verified", forge.name());
+        } else {
+            println!("✓ GitHub CLI verified");
+        }
 
         println!("🔄 Starting pull request creation process...");
 
         // 1. Generate repository view (reuse InfoCommand logic)
-        let repo_view = self.generate_repository_view(repo_root)?;
+        let repo_view = self.generate_repository_view(repo_root).await?;
 
         // 2. Validate branch state (always needed)
         self.validate_branch_state(&repo_view)?;
+        let reviewers = self.suggest_reviewers(repo_root, &repo_view);
+        let labels = self.infer_labels(repo_root, &repo_view);
 
         // 3. Show guidance files status early (before AI processing)
         use crate::claude::context::ProjectDiscovery;
@@ -170,11 +205,18 @@ impl CreatePrCommand {
 
             // Quick analysis for display
             if let Some(branch_info) = &repo_view.branch_info {
-                context.branch = BranchAnalyzer::analyze(&branch_info.branch).unwrap_or_default();
+                context.branch = BranchAnalyzer::analyze_with(
+                    &branch_info.branch,
+                    &context.project.branch_conventions,
+                )
+                .unwrap_or_default();
             }
 
             if !repo_view.commits.is_empty() {
-                context.range = WorkPatternAnalyzer::analyze_commit_range(&repo_view.commits);
+                context.range = WorkPatternAnalyzer::analyze_commit_range_with(
+                    &repo_view.commits,
+                    &context.project.work_pattern_rules,
+                );
                 context.files = FileAnalyzer::analyze_commits(&repo_view.commits);
             }
             context
@@ -236,7 +278,7 @@ impl CreatePrCommand {
         std::fs::write(&pr_file, &pr_yaml)?;
 
         // 7. Handle PR details file - show path and get user choice
-        let pr_action = if self.auto_apply {
+        let pr_action = if self.apply.auto_apply {
             // For auto-apply, default to update if PR exists, otherwise create new
             if repo_view
                 .branch_prs
@@ -248,7 +290,7 @@ impl CreatePrCommand {
                 PrAction::CreateNew
             }
         } else {
-            self.handle_pr_file(&pr_file, &repo_view)?
+            self.handle_pr_file(&pr_file, &repo_view, &reviewers, &labels)?
         };
 
         if pr_action == PrAction::Cancel {
@@ -256,7 +298,7 @@ impl CreatePrCommand {
             return Ok(());
         }
 
-        if used_fallback && self.auto_apply && pr_action == PrAction::UpdateExisting {
+        if used_fallback && self.apply.auto_apply && pr_action == PrAction::UpdateExisting {
             self.refuse_template_clobber(&repo_view)?;
         }
 
@@ -282,27 +324,58 @@ impl CreatePrCommand {
 
         // Determine draft status
         let is_draft = self.should_create_as_draft();
+        let reviewer_logins: Vec<String> = reviewers.into_iter().map(|r| r.login).collect();
 
         match pr_action {
             PrAction::CreateNew => {
-                self.create_github_pr(
-                    repo_root,
-                    &repo_view,
-                    &final_pr_content.title,
-                    &final_pr_content.description,
-                    is_draft,
-                    self.base.as_deref(),
-                )?;
+                if let Some(api) = forge_api.as_deref() {
+                    self.create_forge_pr(
+                        repo_root,
+                        api,
+                        &repo_view,
+                        &final_pr_content.title,
+                        &final_pr_content.description,
+                        is_draft,
+                        self.base.as_deref(),
+                        &reviewer_logins,
+                        &labels,
+                    )
+                    .await?;
+                } else {
+                    self.create_github_pr(
+                        repo_root,
+                        &repo_view,
+                        &final_pr_content.title,
+                        &final_pr_content.description,
+                        is_draft,
+                        self.base.as_deref(),
+                        &reviewer_logins,
+                        &labels,
+                    )?;
+                }
                 println!("✅ Pull request created successfully!");
             }
             PrAction::UpdateExisting => {
-                self.update_github_pr(
-                    repo_root,
-                    &repo_view,
-                    &final_pr_content.title,
-                    &final_pr_content.description,
-                    self.base.as_deref(),
-                )?;
+                if let Some(api) = forge_api.as_deref() {
+                    self.update_forge_pr(
+                        api,
+                        &repo_view,
+                        &final_pr_content.title,
+                        &final_pr_content.description,
+                        self.base.as_deref(),
+                        &labels,
+                    )
+                    .await?;
+                } else {
+                    self.update_github_pr(
+                        repo_root,
+                        &repo_view,
+                        &final_pr_content.title,
+                        &final_pr_content.description,
+                        self.base.as_deref(),
+                        &labels,
+                    )?;
+                }
                 println!("✅ Pull request updated successfully!");
             }
             PrAction::Cancel => unreachable!(), // Already handled above
@@ -312,14 +385,15 @@ impl CreatePrCommand {
     }
 
     /// Generates the repository view (reuses InfoCommand logic).
-    fn generate_repository_view(
+    ///
+    /// The forge query for the branch's PRs, the PR template read, and the
+    /// working-directory scan run on blocking threads while the commits are
+    /// extracted, since none depends on another.
+    pub(super) async fn generate_repository_view(
         &self,
         repo_root: &std::path::Path,
     ) -> Result<crate::data::RepositoryView> {
-        use crate::data::{
-            AiInfo, BranchInfo, FieldExplanation, FileStatusInfo, RepositoryView, VersionInfo,
-            WorkingDirectoryInfo,
-        };
+        use crate::data::{AiInfo, BranchInfo, FieldExplanation, RepositoryView, VersionInfo};
         use crate::git::{GitRepository, RemoteInfo};
         use crate::utils::ai_scratch;
 
@@ -385,36 +459,41 @@ impl CreatePrCommand {
         // Calculate commit range: [remote_base]..HEAD
         let commit_range = format!("{base_branch}..HEAD");
 
-        // Get working directory status
-        let wd_status = repo.get_working_directory_status()?;
-        let working_directory = WorkingDirectoryInfo {
-            clean: wd_status.clean,
-            untracked_changes: wd_status
-                .untracked_changes
-                .into_iter()
-                .map(|fs| FileStatusInfo {
-                    status: fs.status,
-                    file: fs.file,
-                })
-                .collect(),
+        // Start the independent lookups: PRs for the current branch (a forge
+        // round trip), the PR template, and working directory status
+        let prs_task = {
+            let (branch, root) = (current_branch.clone(), repo_root.to_path_buf());
+            tokio::spawn(async move { InfoCommand::get_branch_prs(&branch, &root).await })
+        };
+        let template_task = {
+            let root = repo_root.to_path_buf();
+            tokio::task::spawn_blocking(move || InfoCommand::read_pr_template(&root))
         };
+        let status_task = super::spawn_working_directory_scan(repo_root);
 
-        // Get remote information
-        let remotes = RemoteInfo::get_all_remotes(repo.repository())?;
+        // Parse commit range and get commits, scoped to --paths
+        let mut commits = repo.get_commits_in_range(&commit_range)?;
+        if let Some(paths) = PathFilter::new(&self.paths)? {
+            commits = paths.restrict(commits)?;
+        }
+
+        // Check whether the branch merges cleanly into the base
+        let merge_conflicts = repo.merge_conflicts(&base_branch).ok();
 
-        // Parse commit range and get commits
-        let commits = repo.get_commits_in_range(&commit_range)?;
+        let (working_directory, pr_template_result, branch_prs) =
+            tokio::join!(status_task, template_task, prs_task);
+        let working_directory = working_directory?;
 
         // Check for PR template
-        let pr_template_result = InfoCommand::read_pr_template(repo_root).ok();
         let (pr_template, pr_template_location) = match pr_template_result {
-            Some((content, location)) => (Some(content), Some(location)),
-            None => (None, None),
+            Ok(Ok((content, location))) => (Some(content), Some(location)),
+            _ => (None, None),
         };
 
         // Get PRs for current branch
-        let branch_prs = InfoCommand::get_branch_prs(&current_branch, repo_root)
+        let branch_prs = branch_prs
             .ok()
+            .and_then(Result::ok)
             .filter(|prs| !prs.is_empty());
 
         // Create version information
@@ -431,6 +510,7 @@ impl CreatePrCommand {
 
         // Build repository view with branch info
         let mut repo_view = RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions,
             explanation: FieldExplanation::default(),
             working_directory,
@@ -438,6 +518,7 @@ impl CreatePrCommand {
             ai: ai_info,
             branch_info: Some(BranchInfo {
                 branch: current_branch,
+                merge_conflicts,
             }),
             pr_template,
             pr_template_location,
@@ -451,6 +532,46 @@ impl CreatePrCommand {
         Ok(repo_view)
     }
 
+    /// Suggests reviewers for a new PR from the files the branch touches,
+    /// unless `--no-reviewers` was given.
+    pub(super) fn suggest_reviewers(
+        &self,
+        repo_root: &std::path::Path,
+        repo_view: &crate::data::RepositoryView,
+    ) -> Vec<crate::git::SuggestedReviewer> {
+        if self.no_reviewers {
+            return Vec::new();
+        }
+        let mut files: Vec<String> = Vec::new();
+        for commit in &repo_view.commits {
+            for change in &commit.analysis.file_changes.file_list {
+                if !files.contains(&change.file) {
+                    files.push(change.file.clone());
+                }
+            }
+        }
+        let hashes: Vec<String> = repo_view.commits.iter().map(|c| c.hash.clone()).collect();
+        let reviewers = crate::git::suggest_reviewers(repo_root, &files, &hashes);
+        debug!(count = reviewers.len(), "Suggested PR reviewers");
+        reviewers
+    }
+
+    /// Infers PR labels from the commits' detected types and scopes using
+    /// the project's `labels.yaml` mapping, unless `--no-labels` was given.
+    pub(super) fn infer_labels(
+        &self,
+        repo_root: &std::path::Path,
+        repo_view: &crate::data::RepositoryView,
+    ) -> Vec<String> {
+        if self.no_labels {
+            return Vec::new();
+        }
+        let context_dir =
+            crate::claude::context::resolve_context_dir_at(self.context_dir.as_deref(), repo_root);
+        let config = crate::claude::context::load_pr_label_config(&context_dir);
+        infer_pr_labels(&config, &repo_view.commits)
+    }
+
     /// Validates the branch state for PR creation.
     fn validate_branch_state(&self, repo_view: &crate::data::RepositoryView) -> Result<()> {
         // Check if working directory is clean
@@ -557,6 +678,14 @@ impl CreatePrCommand {
         println!("   🌿 Current branch: {current_branch}");
         println!("   📏 Commit range: {commit_range}");
         println!("   📝 Commits found: {commit_count} commits");
+        match branch_merge_conflicts(repo_view) {
+            Some([]) => println!("   🔀 Merges cleanly into {base_branch}"),
+            Some(files) => println!(
+                "   ⚠️  Conflicts with {base_branch} in {} file(s)",
+                files.len()
+            ),
+            None => {}
+        }
         println!();
 
         Ok(())
@@ -568,6 +697,7 @@ impl CreatePrCommand {
         repo_root: &std::path::Path,
         repo_view: &crate::data::RepositoryView,
     ) -> Result<crate::data::context::CommitContext> {
+        use crate::claude::context::budget::{ContextBudget, ContextTarget};
         use crate::claude::context::{
             BranchAnalyzer, FileAnalyzer, ProjectDiscovery, WorkPatternAnalyzer,
         };
@@ -596,11 +726,33 @@ impl CreatePrCommand {
         let current_branch = repo
             .get_current_branch()
             .unwrap_or_else(|_| "HEAD".to_string());
-        context.branch = BranchAnalyzer::analyze(&current_branch).unwrap_or_default();
+        context.branch =
+            BranchAnalyzer::analyze_with(&current_branch, &context.project.branch_conventions)
+                .unwrap_or_default();
+
+        // 2.5. Fetch the referenced ticket when enrichment is enabled
+        if let Some(ticket_id) = &context.branch.ticket_id {
+            context.branch.ticket =
+                crate::claude::context::ticket::fetch_ticket_context(ticket_id).await;
+        }
+
+        // 2.6. Pull in review feedback when the branch already has a PR
+        if let Some(existing) = repo_view
+            .branch_prs
+            .as_ref()
+            .and_then(|prs| prs.iter().find(|pr| pr.state.eq_ignore_ascii_case("open")))
+        {
+            context.branch.review_comments =
+                crate::claude::context::review::fetch_review_comments(existing.number, repo_root)
+                    .await;
+        }
 
         // 3. Analyze commit range patterns
         if !repo_view.commits.is_empty() {
-            context.range = WorkPatternAnalyzer::analyze_commit_range(&repo_view.commits);
+            context.range = WorkPatternAnalyzer::analyze_commit_range_with(
+                &repo_view.commits,
+                &context.project.work_pattern_rules,
+            );
         }
 
         // 3.5. Analyze file-level context
@@ -608,6 +760,11 @@ impl CreatePrCommand {
             context.files = FileAnalyzer::analyze_commits(&repo_view.commits);
         }
 
+        // 4. Trim the project context to its token budget, if one is set
+        if let Some(budget) = ContextBudget::from_settings() {
+            context.trimmed = budget.apply(&mut context, ContextTarget::PullRequest);
+        }
+
         Ok(context)
     }
 
@@ -688,10 +845,18 @@ impl CreatePrCommand {
                 "   🌿 Branch: {} ({})",
                 context.branch.description, context.branch.work_type
             );
-            if let Some(ref ticket) = context.branch.ticket_id {
-                println!("   🎫 Ticket: {ticket}");
+            match (&context.branch.ticket, &context.branch.ticket_id) {
+                (Some(ticket), _) => println!("   🎫 Ticket: {} — {}", ticket.id, ticket.title),
+                (None, Some(ticket)) => println!("   🎫 Ticket: {ticket}"),
+                (None, None) => {}
             }
         }
+        if !context.branch.review_comments.is_empty() {
+            println!(
+                "   💬 Review feedback: {} comments",
+                context.branch.review_comments.len()
+            );
+        }
 
         // Work pattern
         match context.range.work_pattern {
@@ -702,6 +867,9 @@ impl CreatePrCommand {
             WorkPattern::Configuration => println!("   ⚙️  Pattern: Configuration changes"),
             WorkPattern::Unknown => {}
         }
+        for pattern in &context.range.project_patterns {
+            println!("   🧩 Project pattern: {}", pattern.name);
+        }
 
         // File analysis
         if let Some(label) = super::formatting::format_file_analysis(&context.files) {
@@ -717,6 +885,11 @@ impl CreatePrCommand {
             VerbosityLevel::Concise => println!("   📝 Detail level: Concise"),
         }
 
+        // Context trimmed to the token budget
+        for trimmed in &context.trimmed {
+            println!("   ✂️  Context budget: {trimmed}");
+        }
+
         println!();
         Ok(())
     }
@@ -726,7 +899,7 @@ impl CreatePrCommand {
     /// The returned [`GeneratedPr::used_fallback`] reports whether the content
     /// came from the AI or from the template fallback, so callers can refuse to
     /// overwrite a populated PR body with template text (issue #1333).
-    async fn generate_pr_content_with_client_internal(
+    pub(super) async fn generate_pr_content_with_client_internal(
         &self,
         repo_root: &std::path::Path,
         repo_view: &crate::data::RepositoryView,
@@ -771,6 +944,7 @@ impl CreatePrCommand {
                     ai_generated_description_preview = %pr_content.description.lines().take(3).collect::<Vec<_>>().join("\\n"),
                     "AI successfully generated PR content"
                 );
+                let pr_content = self.link_issues(pr_content, repo_view)?;
                 Ok((GeneratedPr::from_ai(pr_content), claude_client))
             }
             // A permanent failure can never succeed on a retry, so degrading to
@@ -780,11 +954,36 @@ impl CreatePrCommand {
             }
             Err(e) => {
                 let content = self.fallback_pr_content(&e, pr_template, repo_view)?;
+                let content = self.link_issues(content, repo_view)?;
                 Ok((GeneratedPr::from_fallback(content), claude_client))
             }
         }
     }
 
+    /// Appends `Fixes #123` / `Closes owner/repo#123` lines for the issues
+    /// given with `--fixes`, named in the branch, or closed by a commit
+    /// message, so the forge closes them when the PR merges.
+    fn link_issues(
+        &self,
+        mut content: PrContent,
+        repo_view: &crate::data::RepositoryView,
+    ) -> Result<PrContent> {
+        use crate::git::issue_links::{
+            closing_references, ensure_issue_links, issues_from_branch, parse_issue_refs,
+        };
+
+        let mut issues = parse_issue_refs(&self.fixes)?;
+        if let Some(branch_info) = &repo_view.branch_info {
+            issues.extend(issues_from_branch(&branch_info.branch));
+        }
+        for commit in &repo_view.commits {
+            issues.extend(closing_references(&commit.original_message));
+        }
+        debug!(issue_count = issues.len(), "Linking referenced issues");
+        content.description = ensure_issue_links(&content.description, &issues);
+        Ok(content)
+    }
+
     /// Builds template-derived PR content after a transient AI failure, warning
     /// the user that the result is degraded.
     ///
@@ -982,6 +1181,8 @@ impl CreatePrCommand {
         &self,
         pr_file: &std::path::Path,
         repo_view: &crate::data::RepositoryView,
+        reviewers: &[crate::git::SuggestedReviewer],
+        labels: &[String],
     ) -> Result<PrAction> {
         use std::io::{self, Write};
 
@@ -992,6 +1193,18 @@ impl CreatePrCommand {
         let is_draft = self.should_create_as_draft();
         let (status_icon, status_text) = format_draft_status(is_draft);
         println!("{status_icon} PR will be created as: {status_text}");
+        if !reviewers.is_empty() {
+            println!(
+                "👥 Suggested reviewers for a new PR: {}",
+                format_reviewers(reviewers)
+            );
+        }
+        if !labels.is_empty() {
+            println!("🏷️  Labels: {}", labels.join(", "));
+        }
+        if let Some(warning) = branch_merge_conflicts(repo_view).and_then(format_merge_conflicts) {
+            println!("{warning}");
+        }
         println!();
 
         // Check if there are existing PRs and show different options
@@ -1038,7 +1251,7 @@ impl CreatePrCommand {
     }
 
     /// Shows the contents of the PR details file.
-    fn show_pr_file(&self, pr_file: &std::path::Path) -> Result<()> {
+    pub(super) fn show_pr_file(&self, pr_file: &std::path::Path) -> Result<()> {
         use std::fs;
 
         println!("\n📄 PR details file contents:");
@@ -1052,7 +1265,7 @@ impl CreatePrCommand {
     }
 
     /// Opens the PR details file in an external editor.
-    fn edit_pr_file(&self, pr_file: &std::path::Path) -> Result<()> {
+    pub(super) fn edit_pr_file(&self, pr_file: &std::path::Path) -> Result<()> {
         use std::env;
         use std::io::{self, Write};
         use std::process::Command;
@@ -1132,63 +1345,268 @@ impl CreatePrCommand {
         format!("feat: {}", clean_branch_name(branch_name))
     }
 
-    /// Creates a new GitHub PR using gh CLI.
-    fn create_github_pr(
+    /// Pushes the PR branch to `origin` unless `--no-push` was specified.
+    fn push_branch_for_pr(&self, repo_root: &std::path::Path, branch_name: &str) -> Result<()> {
+        let push_action = if self.no_push {
+            determine_push_action(true, false)
+        } else {
+            debug!("Opening git repository to check branch status");
+            let git_repo = crate::git::GitRepository::open_at(repo_root)
+                .context("Failed to open git repository at the given path")?;
+
+            debug!(
+                "Checking if branch '{}' exists on remote 'origin'",
+                branch_name
+            );
+            let branch_on_remote = git_repo.branch_exists_on_remote(branch_name, "origin")?;
+            let action = determine_push_action(false, branch_on_remote);
+
+            debug!("Push action for branch '{}': {:?}", branch_name, action);
+            println!("📤 Pushing branch to remote...");
+            git_repo
+                .push_branch(branch_name, "origin")
+                .context("Failed to push branch to remote")?;
+
+            action
+        };
+
+        if push_action == PushAction::Skip {
+            debug!("Skipping push (--no-push flag set)");
+        }
+
+        Ok(())
+    }
+
+    /// Asks whether an existing PR's base branch should be changed to
+    /// `new_base`. Returns `false` without prompting when no different base
+    /// was requested, and `true` in non-interactive mode.
+    fn confirm_base_change(current_base: &str, new_base: Option<&str>) -> Result<bool> {
+        use std::io::{self, Write};
+
+        let Some(base) = new_base else {
+            return Ok(false);
+        };
+        if current_base.is_empty() || current_base == base {
+            return Ok(false);
+        }
+        if crate::utils::interactive::assume_yes(false) {
+            return Ok(true);
+        }
+
+        let args: [(&str, &dyn std::fmt::Display); 2] =
+            [("current", &current_base), ("base", &base)];
+        print!(
+            "   🎯 {} ",
+            crate::utils::i18n::tr("confirm-base-change", &args)
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(crate::utils::i18n::is_yes(&input))
+    }
+
+    /// Creates a new PR through a forge's REST API.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_forge_pr(
         &self,
         repo_root: &std::path::Path,
+        api: &dyn crate::forge::PullRequestApi,
         repo_view: &crate::data::RepositoryView,
         title: &str,
         description: &str,
         is_draft: bool,
         new_base: Option<&str>,
+        reviewers: &[String],
+        labels: &[String],
     ) -> Result<()> {
-        // Get branch name
         let branch_name = repo_view
             .branch_info
             .as_ref()
             .map(|bi| &bi.branch)
             .context("Branch info not available")?;
+        let description = &stamp_generated_head(description, repo_view);
 
         let pr_status = if is_draft {
             "draft"
         } else {
             "ready for review"
         };
-        println!("🚀 Creating pull request ({pr_status})...");
+        println!(
+            "🚀 Creating {} pull request ({pr_status})...",
+            api.forge_name()
+        );
         println!("   📋 Title: {title}");
         println!("   🌿 Branch: {branch_name}");
         if let Some(base) = new_base {
             println!("   🎯 Base: {base}");
         }
+        if !reviewers.is_empty() {
+            println!("   👥 Reviewers: {}", reviewers.join(", "));
+        }
+        if !labels.is_empty() {
+            println!("   🏷️  Labels: {}", labels.join(", "));
+        }
 
-        // Push branch to remote unless --no-push was specified
-        let push_action = if self.no_push {
-            determine_push_action(true, false)
-        } else {
-            debug!("Opening git repository to check branch status");
-            let git_repo = crate::git::GitRepository::open_at(repo_root)
-                .context("Failed to open git repository at the given path")?;
+        self.push_branch_for_pr(repo_root, branch_name)?;
 
-            debug!(
-                "Checking if branch '{}' exists on remote 'origin'",
-                branch_name
-            );
-            let branch_on_remote = git_repo.branch_exists_on_remote(branch_name, "origin")?;
-            let action = determine_push_action(false, branch_on_remote);
+        debug!(
+            forge = api.forge_name(),
+            draft = is_draft,
+            "Creating PR with forge API"
+        );
+        let pr = api
+            .create_pr(branch_name, new_base, title, description, is_draft)
+            .await
+            .context("Failed to create pull request")?;
+
+        println!("🎉 Pull request created: {}", pr.url);
+        if !reviewers.is_empty() {
+            // The PR already exists, so a rejected reviewer is not fatal.
+            if let Err(e) = api.request_reviewers(pr.number, reviewers).await {
+                warn!("Failed to request reviewers: {e:#}");
+                println!("⚠️  Could not request reviewers: {e:#}");
+            }
+        }
+        Self::apply_forge_labels(api, pr.number, labels).await;
+        Ok(())
+    }
 
-            debug!("Push action for branch '{}': {:?}", branch_name, action);
-            println!("📤 Pushing branch to remote...");
-            git_repo
-                .push_branch(branch_name, "origin")
-                .context("Failed to push branch to remote")?;
+    /// Updates an existing PR through a forge's REST API.
+    pub(super) async fn update_forge_pr(
+        &self,
+        api: &dyn crate::forge::PullRequestApi,
+        repo_view: &crate::data::RepositoryView,
+        title: &str,
+        description: &str,
+        new_base: Option<&str>,
+        labels: &[String],
+    ) -> Result<()> {
+        let existing_pr = repo_view
+            .branch_prs
+            .as_ref()
+            .and_then(|prs| prs.first())
+            .context("No existing PR found to update")?;
+        let description = &stamp_generated_head(description, repo_view);
 
-            action
-        };
+        println!("🚀 Updating pull request #{}...", existing_pr.number);
+        println!("   📋 Title: {title}");
 
-        if push_action == PushAction::Skip {
-            debug!("Skipping push (--no-push flag set)");
+        let change_base = Self::confirm_base_change(&existing_pr.base, new_base)?;
+        let destination = if change_base { new_base } else { None };
+
+        debug!(
+            pr_number = existing_pr.number,
+            title = %title,
+            description_length = description.len(),
+            change_base = change_base,
+            forge = api.forge_name(),
+            "Updating forge PR with title and description"
+        );
+        let pr = api
+            .update_pr(existing_pr.number, title, description, destination)
+            .await
+            .context("Failed to update pull request")?;
+
+        println!("🎉 Pull request updated: {}", pr.url);
+        if let Some(base) = destination {
+            println!("   🎯 Base branch changed to: {base}");
+        }
+        Self::apply_forge_labels(api, pr.number, labels).await;
+        Ok(())
+    }
+
+    /// Adds `labels` to PR `number` through a forge's REST API. A failure
+    /// only warns, since the PR itself was already written.
+    async fn apply_forge_labels(
+        api: &dyn crate::forge::PullRequestApi,
+        number: u64,
+        labels: &[String],
+    ) {
+        if labels.is_empty() {
+            return;
+        }
+        match api.add_labels(number, labels).await {
+            Ok(()) => println!("   🏷️  Labels applied: {}", labels.join(", ")),
+            Err(e) => {
+                warn!("Failed to apply labels: {e:#}");
+                println!("⚠️  Could not apply labels: {e:#}");
+            }
+        }
+    }
+
+    /// Adds `labels` to the PR identified by `target` (number or URL) with
+    /// `gh pr edit`. A failure (e.g. a label missing from the repository)
+    /// only warns, since the PR itself was already written.
+    fn apply_github_labels(repo_root: &std::path::Path, target: &str, labels: &[String]) {
+        if labels.is_empty() {
+            return;
+        }
+        let joined = labels.join(",");
+        let result = crate::github_metrics::run_gh(
+            &crate::pr_status::resolve_gh_binary(),
+            vec!["pr", "edit", target, "--add-label", &joined],
+            "pr edit",
+            Some(repo_root),
+        );
+        match result {
+            Ok(output) if output.status.success() => {
+                println!("   🏷️  Labels applied: {}", labels.join(", "));
+            }
+            Ok(output) => {
+                let error_msg = String::from_utf8_lossy(&output.stderr);
+                warn!("gh failed to apply labels: {}", error_msg.trim());
+                println!("⚠️  Could not apply labels: {}", error_msg.trim());
+            }
+            Err(e) => {
+                warn!("Failed to apply labels: {e:#}");
+                println!("⚠️  Could not apply labels: {e:#}");
+            }
+        }
+    }
+
+    /// Creates a new GitHub PR using gh CLI (the fallback when no GitHub
+    /// token resolves for the native API client).
+    #[allow(clippy::too_many_arguments)]
+    fn create_github_pr(
+        &self,
+        repo_root: &std::path::Path,
+        repo_view: &crate::data::RepositoryView,
+        title: &str,
+        description: &str,
+        is_draft: bool,
+        new_base: Option<&str>,
+        reviewers: &[String],
+        labels: &[String],
+    ) -> Result<()> {
+        // Get branch name
+        let branch_name = repo_view
+            .branch_info
+            .as_ref()
+            .map(|bi| &bi.branch)
+            .context("Branch info not available")?;
+        let description = &stamp_generated_head(description, repo_view);
+
+        let pr_status = if is_draft {
+            "draft"
+        } else {
+            "ready for review"
+        };
+        println!("🚀 Creating pull request ({pr_status})...");
+        println!("   📋 Title: {title}");
+        println!("   🌿 Branch: {branch_name}");
+        if let Some(base) = new_base {
+            println!("   🎯 Base: {base}");
+        }
+        if !reviewers.is_empty() {
+            println!("   👥 Reviewers: {}", reviewers.join(", "));
+        }
+        if !labels.is_empty() {
+            println!("   🏷️  Labels: {}", labels.join(", "));
         }
 
+        self.push_branch_for_pr(repo_root, branch_name)?;
+
         // Create PR using gh CLI with explicit head branch
         debug!("Creating PR with gh CLI - title: '{}'", title);
         debug!("PR description length: {} characters", description.len());
@@ -1217,6 +1635,11 @@ impl CreatePrCommand {
             args.push("--draft");
         }
 
+        for reviewer in reviewers {
+            args.push("--reviewer");
+            args.push(reviewer);
+        }
+
         let pr_result = crate::github_metrics::run_gh(
             &crate::pr_status::resolve_gh_binary(),
             args,
@@ -1230,6 +1653,7 @@ impl CreatePrCommand {
             let pr_url = pr_url.trim();
             debug!("PR created successfully with URL: {}", pr_url);
             println!("🎉 Pull request created: {pr_url}");
+            Self::apply_github_labels(repo_root, pr_url, labels);
         } else {
             let error_msg = String::from_utf8_lossy(&pr_result.stderr);
             error!("gh CLI failed to create PR: {}", error_msg);
@@ -1240,22 +1664,22 @@ impl CreatePrCommand {
     }
 
     /// Updates an existing GitHub PR using gh CLI.
-    fn update_github_pr(
+    pub(super) fn update_github_pr(
         &self,
         repo_root: &std::path::Path,
         repo_view: &crate::data::RepositoryView,
         title: &str,
         description: &str,
         new_base: Option<&str>,
+        labels: &[String],
     ) -> Result<()> {
-        use std::io::{self, Write};
-
         // Get the first existing PR (assuming we're updating the most recent one)
         let existing_pr = repo_view
             .branch_prs
             .as_ref()
             .and_then(|prs| prs.first())
             .context("No existing PR found to update")?;
+        let description = &stamp_generated_head(description, repo_view);
 
         let pr_number = existing_pr.number;
         let current_base = &existing_pr.base;
@@ -1264,21 +1688,7 @@ impl CreatePrCommand {
         println!("   📋 Title: {title}");
 
         // Check if base branch should be changed
-        let change_base = if let Some(base) = new_base {
-            if !current_base.is_empty() && current_base != base {
-                print!("   🎯 Current base: {current_base} → New base: {base}. Change? [y/N]: ");
-                io::stdout().flush()?;
-
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let response = input.trim().to_lowercase();
-                response == "y" || response == "yes"
-            } else {
-                false
-            }
-        } else {
-            false
-        };
+        let change_base = Self::confirm_base_change(current_base, new_base)?;
 
         debug!(
             pr_number = pr_number,
@@ -1323,12 +1733,17 @@ impl CreatePrCommand {
 
         if pr_result.status.success() {
             // Get the PR URL using the existing PR data
-            println!("🎉 Pull request updated: {}", existing_pr.url);
+            if existing_pr.url.is_empty() {
+                println!("🎉 Pull request #{pr_number} updated");
+            } else {
+                println!("🎉 Pull request updated: {}", existing_pr.url);
+            }
             if change_base {
                 if let Some(base) = new_base {
                     println!("   🎯 Base branch changed to: {base}");
                 }
             }
+            Self::apply_github_labels(repo_root, &pr_number_str, labels);
         } else {
             let error_msg = String::from_utf8_lossy(&pr_result.stderr);
             anyhow::bail!("Failed to update pull request: {error_msg}");
@@ -1338,7 +1753,7 @@ impl CreatePrCommand {
     }
 
     /// Shows model information from the actual AI client.
-    fn show_model_info_from_client(
+    pub(super) fn show_model_info_from_client(
         &self,
         client: &crate::claude::client::ClaudeClient,
     ) -> Result<()> {
@@ -1423,18 +1838,6 @@ fn determine_push_action(no_push: bool, branch_on_remote: bool) -> PushAction {
     }
 }
 
-/// Parses a boolean-like string value.
-///
-/// Accepts "true"/"1"/"yes" as `true` and "false"/"0"/"no" as `false`.
-/// Returns `None` for unrecognized values.
-fn parse_bool_string(val: &str) -> Option<bool> {
-    match val.to_lowercase().as_str() {
-        "true" | "1" | "yes" => Some(true),
-        "false" | "0" | "no" => Some(false),
-        _ => None,
-    }
-}
-
 /// Returns whether a commit represents a breaking change.
 fn is_breaking_change(detected_type: &str, original_message: &str) -> bool {
     detected_type.contains("BREAKING") || original_message.contains("BREAKING CHANGE")
@@ -1447,6 +1850,61 @@ fn check_checkbox(description: &mut String, search_text: &str) {
     }
 }
 
+/// Collects the labels for `commits` from their detected types and scopes,
+/// in order of first appearance.
+fn infer_pr_labels(
+    config: &crate::data::context::PrLabelConfig,
+    commits: &[crate::git::CommitInfo],
+) -> Vec<String> {
+    let mut labels: Vec<String> = Vec::new();
+    for commit in commits {
+        for label in config.labels_for(
+            &commit.analysis.detected_type,
+            &commit.analysis.detected_scope,
+        ) {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+    }
+    labels
+}
+
+/// Returns the files that conflict with the base branch, when the merge was
+/// checked.
+fn branch_merge_conflicts(repo_view: &crate::data::RepositoryView) -> Option<&[String]> {
+    repo_view
+        .branch_info
+        .as_ref()
+        .and_then(|bi| bi.merge_conflicts.as_deref())
+}
+
+/// Formats a warning listing the files that conflict with the base branch.
+///
+/// Returns `None` if there are no conflicts.
+fn format_merge_conflicts(files: &[String]) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+    let mut output = format!(
+        "⚠️  Branch does not merge cleanly into the base; rebase before requesting review.\n   Conflicting files ({}):\n",
+        files.len()
+    );
+    for file in files {
+        output.push_str(&format!("   - {file}\n"));
+    }
+    Some(output.trim_end().to_string())
+}
+
+/// Formats reviewer suggestions with their sources for display.
+fn format_reviewers(reviewers: &[crate::git::SuggestedReviewer]) -> String {
+    reviewers
+        .iter()
+        .map(|r| format!("{} ({})", r.login, r.source))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Formats a list of scopes as a markdown "Affected areas" section.
 ///
 /// Returns an empty string if the list is empty.
@@ -1466,6 +1924,32 @@ fn format_commit_list(entries: &[(&str, &str)]) -> String {
     output
 }
 
+/// Appends a hidden marker with the branch head (the newest commit in
+/// `repo_view`) to a PR description, replacing any earlier marker.
+fn stamp_generated_head(description: &str, repo_view: &crate::data::RepositoryView) -> String {
+    let mut stamped: String = description
+        .lines()
+        .filter(|line| !line.starts_with(GENERATED_HEAD_MARKER))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string();
+    if let Some(head) = repo_view.commits.last() {
+        stamped.push_str(&format!("\n\n{GENERATED_HEAD_MARKER}{} -->", head.hash));
+    }
+    stamped.push('\n');
+    stamped
+}
+
+/// Returns the branch head a PR description was generated from, as recorded
+/// by [`stamp_generated_head`].
+pub(super) fn generated_head(body: &str) -> Option<&str> {
+    body.lines()
+        .find_map(|line| line.trim().strip_prefix(GENERATED_HEAD_MARKER))
+        .and_then(|rest| rest.strip_suffix("-->"))
+        .map(str::trim)
+}
+
 /// Replaces path separators (`/`, `-`, `_`) in a branch name with spaces.
 fn clean_branch_name(branch: &str) -> String {
     branch.replace(['/', '-', '_'], " ")
@@ -1494,6 +1978,9 @@ pub struct CreatePrOutcome {
     pub description: String,
     /// YAML serialisation of the [`PrContent`].
     pub pr_yaml: String,
+    /// Labels inferred from the commits' types and scopes (empty with
+    /// `no_labels`).
+    pub labels: Vec<String>,
 }
 
 /// Non-interactive core for `omni-dev git branch create pr`.
@@ -1507,6 +1994,7 @@ pub struct CreatePrOutcome {
 pub async fn run_create_pr(
     model: Option<String>,
     base_branch: Option<&str>,
+    no_labels: bool,
     repo_path: Option<&std::path::Path>,
 ) -> Result<CreatePrOutcome> {
     // Resolve the repo root once; the repository view and context discovery
@@ -1517,23 +2005,29 @@ pub async fn run_create_pr(
         None => std::env::current_dir().context("Failed to determine current directory")?,
     };
 
-    crate::utils::check_pr_command_prerequisites(model.as_deref(), &repo_root)?;
+    crate::utils::check_pr_command_prerequisites(model.as_deref(), &repo_root).await?;
 
     let cmd = CreatePrCommand {
         base: base_branch.map(str::to_string),
-        auto_apply: true,
+        apply: AutoApply { auto_apply: true },
         save_only: None,
         ready: false,
         draft: false,
         context_dir: None,
         no_push: true,
         from_commits: false,
+        no_reviewers: true,
+        no_labels,
+        fixes: Vec::new(),
+        paths: Vec::new(),
     };
 
-    let repo_view = cmd.generate_repository_view(&repo_root)?;
+    let repo_view = cmd.generate_repository_view(&repo_root).await?;
     let context = cmd.collect_context(&repo_root, &repo_view).await?;
     let claude_client = crate::claude::create_default_claude_client(model, None).await?;
-    run_create_pr_with_client(&cmd, &repo_view, &context, &claude_client).await
+    let mut outcome = run_create_pr_with_client(&cmd, &repo_view, &context, &claude_client).await?;
+    outcome.labels = cmd.infer_labels(&repo_root, &repo_view);
+    Ok(outcome)
 }
 
 /// Non-credential-gated inner core of [`run_create_pr`] for unit tests.
@@ -1542,7 +2036,8 @@ pub async fn run_create_pr(
 /// and [`crate::data::context::CommitContext`] so tests can construct those
 /// in-memory (avoiding the git-remote setup `generate_repository_view`
 /// requires). Callers are responsible for preflight, CWD, and context
-/// assembly.
+/// assembly, and for filling in [`CreatePrOutcome::labels`], which need the
+/// repository root.
 pub(crate) async fn run_create_pr_with_client(
     cmd: &CreatePrCommand,
     repo_view: &crate::data::RepositoryView,
@@ -1570,6 +2065,7 @@ pub(crate) async fn run_create_pr_with_client(
         }
         Err(e) => cmd.fallback_pr_content(&e, pr_template, repo_view)?,
     };
+    let pr_content = cmd.link_issues(pr_content, repo_view)?;
 
     let pr_yaml = crate::data::to_yaml(&pr_content).context("Failed to serialise PrContent")?;
 
@@ -1577,6 +2073,7 @@ pub(crate) async fn run_create_pr_with_client(
         title: pr_content.title,
         description: pr_content.description,
         pr_yaml,
+        labels: Vec::new(),
     })
 }
 
@@ -1599,6 +2096,7 @@ mod run_create_pr_tests {
         let err = run_create_pr(
             None,
             None,
+            false,
             Some(std::path::Path::new("/no/such/path/exists")),
         )
         .await
@@ -1621,13 +2119,17 @@ mod run_create_pr_tests {
     fn fresh_cmd() -> CreatePrCommand {
         CreatePrCommand {
             base: None,
-            auto_apply: true,
+            apply: AutoApply { auto_apply: true },
             save_only: None,
             ready: false,
             draft: false,
             context_dir: None,
             no_push: true,
             from_commits: false,
+            no_reviewers: true,
+            no_labels: true,
+            fixes: Vec::new(),
+            paths: Vec::new(),
         }
     }
 
@@ -1652,11 +2154,31 @@ mod run_create_pr_tests {
                 diff_summary: String::new(),
                 diff_file: tmp.path().to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         (commit, tmp)
     }
 
+    #[test]
+    fn infer_pr_labels_dedupes_across_commits() {
+        let config = crate::data::context::PrLabelConfig {
+            scope_prefix: Some("area/".to_string()),
+            ..Default::default()
+        };
+        let (mut first, _tmp1) = sample_commit("a", "feat(cli): add flag");
+        let (mut second, _tmp2) = sample_commit("b", "fix(cli): fix flag");
+        first.analysis.detected_scope = "cli".to_string();
+        second.analysis.detected_type = "fix".to_string();
+        second.analysis.detected_scope = "cli".to_string();
+        let (third, _tmp3) = sample_commit("c", "feat: more");
+        assert_eq!(
+            infer_pr_labels(&config, &[first, second, third]),
+            vec!["enhancement", "area/cli", "bug"]
+        );
+    }
+
     /// Builds a repo view carrying one existing PR with the given body, as
     /// `gh pr list --json …,body` would populate it.
     fn repo_view_with_existing_pr(pr_template: Option<String>, body: &str) -> RepositoryView {
@@ -1732,6 +2254,7 @@ mod run_create_pr_tests {
 
     fn sample_repo_view(commits: Vec<CommitInfo>, pr_template: Option<String>) -> RepositoryView {
         RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: Some(VersionInfo {
                 omni_dev: "0.0.0".to_string(),
             }),
@@ -1746,6 +2269,7 @@ mod run_create_pr_tests {
             },
             branch_info: Some(BranchInfo {
                 branch: "feature/test".to_string(),
+                merge_conflicts: None,
             }),
             pr_template,
             pr_template_location: None,
@@ -1773,6 +2297,35 @@ mod run_create_pr_tests {
         assert!(outcome.pr_yaml.contains("title:"));
     }
 
+    #[tokio::test]
+    async fn run_create_pr_with_client_links_referenced_issues() {
+        let (c1, _tmp) = sample_commit("abcdef00", "fix: handle crash\n\nCloses #12");
+        let mut repo_view = sample_repo_view(vec![c1], None);
+        repo_view.branch_info = Some(BranchInfo {
+            branch: "fix/42-crash".to_string(),
+            merge_conflicts: None,
+        });
+        let context = CommitContext::new();
+        let mut cmd = fresh_cmd();
+        cmd.fixes = vec!["acme/api#3".to_string()];
+
+        let yaml = "title: Fix crash\ndescription: |\n  Body text\n\n  Fixes #42\n".to_string();
+        let mock = ConfigurableMockAiClient::new(vec![Ok(yaml)]);
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let outcome = run_create_pr_with_client(&cmd, &repo_view, &context, &client)
+            .await
+            .unwrap();
+        assert!(
+            outcome
+                .description
+                .ends_with("Fixes #42\n\nCloses acme/api#3\nFixes #12\n"),
+            "unexpected description: {}",
+            outcome.description
+        );
+        assert_eq!(outcome.description.matches("#42").count(), 1);
+    }
+
     #[tokio::test]
     async fn run_create_pr_with_client_ai_failure_falls_back_to_commit_summary() {
         let (c1, _tmp) = sample_commit("abcdef00", "feat: single commit subject");
@@ -1900,6 +2453,8 @@ mod run_create_pr_tests {
                 diff_summary: String::new(),
                 diff_file: diff_path.to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         let repo_view = sample_repo_view(vec![commit], None);
@@ -1939,6 +2494,7 @@ mod run_create_pr_tests {
             title: "t".to_string(),
             description: "d".to_string(),
             pr_yaml: "y".to_string(),
+            labels: vec!["bug".to_string()],
         };
         let cloned = outcome.clone();
         assert_eq!(format!("{outcome:?}"), format!("{cloned:?}"));
@@ -2028,13 +2584,13 @@ mod run_create_pr_tests {
     /// `.github/pull_request_template.md`). We leave the process CWD untouched
     /// and assert the returned view reflects the injected repo's distinctive
     /// template and feature branch.
-    #[test]
-    fn generate_repository_view_anchors_to_injected_repo() {
+    #[tokio::test]
+    async fn generate_repository_view_anchors_to_injected_repo() {
         let marker = "## INJECTED_PR_TEMPLATE_MARKER_42";
         let temp_dir = init_repo_with_remote_and_template(marker);
         let cmd = fresh_cmd();
 
-        let repo_view = cmd.generate_repository_view(temp_dir.path()).unwrap();
+        let repo_view = cmd.generate_repository_view(temp_dir.path()).await.unwrap();
 
         // PR template came from the injected repo, not the ambient CWD.
         assert_eq!(
@@ -2064,6 +2620,28 @@ mod tests {
 
     // --- parse_bool_string ---
 
+    #[test]
+    fn generated_head_marker_round_trips() {
+        let (commit, _diff) = sample_commit("abc123def4567890", "feat: x");
+        let repo_view = sample_repo_view(vec![commit], None);
+
+        let stamped = stamp_generated_head("## Summary\n\nAdds x.\n", &repo_view);
+        assert_eq!(generated_head(&stamped), Some("abc123def4567890"));
+        let restamped = stamp_generated_head(&stamped, &repo_view);
+        assert_eq!(restamped, stamped);
+        assert_eq!(generated_head("## Summary\n"), None);
+    }
+
+    #[test]
+    #[allow(clippy::unwrap_used)]
+    fn merge_conflicts_warning_lists_files() {
+        assert_eq!(format_merge_conflicts(&[]), None);
+        let warning =
+            format_merge_conflicts(&["src/a.rs".to_string(), "src/b.rs".to_string()]).unwrap();
+        assert!(warning.contains("Conflicting files (2):"));
+        assert!(warning.ends_with("   - src/a.rs\n   - src/b.rs"));
+    }
+
     #[test]
     fn parse_bool_true_variants() {
         assert_eq!(parse_bool_string("true"), Some(true));
@@ -2152,6 +2730,27 @@ mod tests {
         assert_eq!(format_scopes_section(&[]), "");
     }
 
+    // --- format_reviewers ---
+
+    #[test]
+    fn reviewers_show_their_source() {
+        use crate::git::{ReviewerSource, SuggestedReviewer};
+        let reviewers = vec![
+            SuggestedReviewer {
+                login: "acme/core".to_string(),
+                source: ReviewerSource::CodeOwners,
+            },
+            SuggestedReviewer {
+                login: "alice".to_string(),
+                source: ReviewerSource::Blame,
+            },
+        ];
+        assert_eq!(
+            format_reviewers(&reviewers),
+            "acme/core (CODEOWNERS), alice (recent blame)"
+        );
+    }
+
     // --- format_commit_list ---
 
     #[test]
@@ -0,0 +1,155 @@
+//! Configured defaults for commit and pull request command flags.
+//!
+//! Each default is a settings key, so it resolves like any other (see
+//! [`crate::utils::config`]): the environment, then `.omni-dev/config.yaml`,
+//! then `settings.json`. A flag given on the command line always wins, so a
+//! team can standardize `concurrency: 8` or `auto_apply: true` in the
+//! repository and still override it per invocation.
+
+use anyhow::Result;
+use clap::Args;
+
+use crate::utils::env::EnvSource;
+
+/// Key supplying the commit range of `twiddle` and `check` when none is given.
+pub(crate) const COMMIT_RANGE_KEY: &str = "OMNI_DEV_COMMIT_RANGE";
+
+/// Key supplying `--concurrency` when the flag is not given.
+pub(crate) const CONCURRENCY_KEY: &str = "OMNI_DEV_CONCURRENCY";
+
+/// Key making `--auto-apply` the default for `twiddle`, `create-pr`, and
+/// `update-pr`. Set it to `false` in the environment to prompt again for one
+/// invocation.
+pub(crate) const AUTO_APPLY_KEY: &str = "OMNI_DEV_AUTO_APPLY";
+
+/// Concurrent AI requests when neither the flag nor [`CONCURRENCY_KEY`] is set.
+pub(crate) const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Parses a boolean-like string value.
+///
+/// Accepts "true"/"1"/"yes" as `true` and "false"/"0"/"no" as `false`.
+/// Returns `None` for unrecognized values.
+pub(crate) fn parse_bool_string(val: &str) -> Option<bool> {
+    match val.to_lowercase().as_str() {
+        "true" | "1" | "yes" => Some(true),
+        "false" | "0" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// The commit range from the command line, else from [`COMMIT_RANGE_KEY`].
+pub(crate) fn commit_range(flag: Option<&str>, env: &impl EnvSource) -> Option<String> {
+    flag.map(str::to_string).or_else(|| {
+        env.var(COMMIT_RANGE_KEY)
+            .filter(|range| !range.trim().is_empty())
+    })
+}
+
+/// `--concurrency` from the command line, else from [`CONCURRENCY_KEY`],
+/// else [`DEFAULT_CONCURRENCY`]. An unparsable or zero setting is logged and
+/// ignored.
+pub(crate) fn concurrency(flag: Option<usize>, env: &impl EnvSource) -> usize {
+    if let Some(flag) = flag {
+        return flag;
+    }
+    env.var(CONCURRENCY_KEY)
+        .and_then(|raw| match raw.trim().parse::<usize>() {
+            Ok(value) if value > 0 => Some(value),
+            _ => {
+                tracing::warn!("Ignoring {CONCURRENCY_KEY}={raw}: expected a positive integer");
+                None
+            }
+        })
+        .unwrap_or(DEFAULT_CONCURRENCY)
+}
+
+/// `--auto-apply` from the command line, else non-interactive mode (see
+/// [`crate::utils::interactive::assume_yes_in`]), else [`AUTO_APPLY_KEY`].
+pub(crate) fn auto_apply(flag: bool, env: &impl EnvSource) -> bool {
+    crate::utils::interactive::assume_yes_in(flag, env)
+        || env
+            .var(AUTO_APPLY_KEY)
+            .and_then(|raw| parse_bool_string(&raw))
+            .unwrap_or(false)
+}
+
+/// The `--auto-apply` flag shared by every command that confirms before it
+/// changes anything, with the one place each kind of confirmation is
+/// resolved.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct AutoApply {
+    /// Skips the confirmation prompt and goes ahead automatically.
+    #[arg(long)]
+    pub auto_apply: bool,
+}
+
+impl AutoApply {
+    /// Folds in non-interactive mode and [`AUTO_APPLY_KEY`], for the
+    /// commands whose auto-apply can be configured (see [`auto_apply`]).
+    pub(crate) fn with_defaults(self, env: &impl EnvSource) -> Self {
+        Self {
+            auto_apply: auto_apply(self.auto_apply, env),
+        }
+    }
+
+    /// Whether a routine confirmation is skipped: the flag or
+    /// non-interactive mode.
+    pub(crate) fn assumed(self) -> bool {
+        crate::utils::interactive::assume_yes(self.auto_apply)
+    }
+
+    /// Whether a destructive action may go ahead without asking; see
+    /// [`crate::utils::interactive::destructive_preapproved`].
+    pub(crate) fn destructive(self) -> Result<bool> {
+        crate::utils::interactive::destructive_preapproved(self.auto_apply, "--auto-apply")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    #[test]
+    fn flags_win_over_settings() {
+        let env = MapEnv::new()
+            .with(COMMIT_RANGE_KEY, "origin/develop..HEAD")
+            .with(CONCURRENCY_KEY, "8")
+            .with(AUTO_APPLY_KEY, "yes");
+        assert_eq!(
+            commit_range(Some("HEAD~2..HEAD"), &env).as_deref(),
+            Some("HEAD~2..HEAD")
+        );
+        assert_eq!(
+            commit_range(None, &env).as_deref(),
+            Some("origin/develop..HEAD")
+        );
+        assert_eq!(concurrency(Some(2), &env), 2);
+        assert_eq!(concurrency(None, &env), 8);
+        assert!(auto_apply(false, &env));
+    }
+
+    #[test]
+    fn falls_back_to_built_in_defaults() {
+        let env = MapEnv::new();
+        assert_eq!(commit_range(None, &env), None);
+        assert_eq!(concurrency(None, &env), DEFAULT_CONCURRENCY);
+        assert!(!auto_apply(false, &env));
+        assert!(auto_apply(true, &env));
+
+        let invalid = MapEnv::new()
+            .with(CONCURRENCY_KEY, "0")
+            .with(AUTO_APPLY_KEY, "sometimes");
+        assert_eq!(concurrency(None, &invalid), DEFAULT_CONCURRENCY);
+        assert!(!auto_apply(false, &invalid));
+    }
+
+    #[test]
+    fn non_interactive_mode_implies_auto_apply() {
+        let env = MapEnv::new()
+            .with(crate::utils::interactive::NON_INTERACTIVE_ENV_VAR, "true")
+            .with(AUTO_APPLY_KEY, "false");
+        assert!(auto_apply(false, &env));
+    }
+}
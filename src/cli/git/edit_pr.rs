@@ -0,0 +1,226 @@
+//! Edit PR command — opens an existing pull request's title and description
+//! in the same YAML-file and editor loop `create pr` uses, then writes the
+//! result back.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use super::create_pr::{CreatePrCommand, PrContent};
+use super::defaults::AutoApply;
+use super::info::InfoCommand;
+use super::update_pr::select_pr;
+use crate::git::GitRepository;
+
+/// Edit PR command options.
+#[derive(Parser)]
+pub struct EditPrCommand {
+    /// PR number to edit (defaults to the open PR for the current branch).
+    #[arg(long, value_name = "N")]
+    pub number: Option<u64>,
+}
+
+impl EditPrCommand {
+    /// Executes the edit PR command.
+    ///
+    /// No AI is involved: the PR's current title and body are written to a
+    /// temporary `pr-details.yaml`, opened in `OMNI_DEV_EDITOR` / `EDITOR`,
+    /// and pushed back once confirmed.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        crate::utils::interactive::ensure_interactive("git branch edit pr")?;
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        crate::utils::check_git_repository_at(repo_root)?;
+        let forge = crate::forge::Forge::detect(repo_root);
+        crate::utils::check_forge_access(&forge, repo_root).await?;
+        let forge_api = forge.api_client()?;
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let branch = git_repo.get_current_branch().context(
+            "Failed to get current branch. Make sure you're not in detached HEAD state.",
+        )?;
+        let prs = InfoCommand::get_branch_prs(&branch, repo_root).await?;
+        let target = select_pr(&prs, self.number, &branch)?;
+        if target.title.is_empty() {
+            bail!(
+                "PR #{} is not an open pull request of branch '{branch}'; check out its branch to edit it.",
+                target.number
+            );
+        }
+
+        let original = PrContent {
+            title: target.title.clone(),
+            description: target.body.clone(),
+        };
+        let temp_dir = tempfile::tempdir()?;
+        let pr_file = temp_dir.path().join("pr-details.yaml");
+        let pr_yaml =
+            crate::data::to_yaml(&original).context("Failed to serialize PR content to YAML")?;
+        std::fs::write(&pr_file, &pr_yaml)?;
+
+        // `edit_pr_file` and `show_pr_file` only read the file they are given.
+        let create_cmd = CreatePrCommand {
+            base: None,
+            apply: AutoApply { auto_apply: false },
+            save_only: None,
+            ready: false,
+            draft: false,
+            context_dir: None,
+            no_push: true,
+            from_commits: false,
+            no_reviewers: true,
+            no_labels: true,
+            fixes: Vec::new(),
+            paths: Vec::new(),
+        };
+
+        println!("📝 Editing PR #{}: {}", target.number, target.title);
+        println!("💾 Details saved to: {}", pr_file.display());
+        create_cmd.edit_pr_file(&pr_file)?;
+
+        loop {
+            match prompt_edit_choice()? {
+                Some(EditChoice::Apply) => break,
+                Some(EditChoice::Quit) => {
+                    println!("❌ PR edit cancelled by user");
+                    return Ok(());
+                }
+                Some(EditChoice::Show) => create_cmd.show_pr_file(&pr_file)?,
+                Some(EditChoice::Edit) => create_cmd.edit_pr_file(&pr_file)?,
+                None => println!(
+                    "Invalid choice. Please enter 'a' to apply, 's' to show, 'e' to edit, or 'q' to quit."
+                ),
+            }
+            println!();
+        }
+
+        let edited = read_pr_file(&pr_file)?;
+
+        if edited.title.trim().is_empty() {
+            bail!("PR title cannot be empty");
+        }
+        if edited.title == original.title && edited.description == original.description {
+            println!("✓ PR #{} left unchanged", target.number);
+            return Ok(());
+        }
+
+        println!("🚀 Updating pull request #{}...", target.number);
+        match forge_api.as_deref() {
+            Some(api) => {
+                let pr = api
+                    .update_pr(target.number, &edited.title, &edited.description, None)
+                    .await
+                    .context("Failed to update pull request")?;
+                println!("🎉 Pull request updated: {}", pr.url);
+            }
+            None => {
+                gh_edit_pr(repo_root, target.number, &edited.title, &edited.description)?;
+                if target.url.is_empty() {
+                    println!("🎉 Pull request #{} updated", target.number);
+                } else {
+                    println!("🎉 Pull request updated: {}", target.url);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses the PR details file, which the user may have edited.
+fn read_pr_file(pr_file: &std::path::Path) -> Result<PrContent> {
+    let yaml = std::fs::read_to_string(pr_file).context("Failed to read PR details file")?;
+    serde_yaml::from_str(&yaml)
+        .context("Failed to parse PR details YAML. Please check the file format.")
+}
+
+/// Asks what to do with the PR details file.
+fn prompt_edit_choice() -> Result<Option<EditChoice>> {
+    use std::io::{self, Write};
+
+    print!("❓ [A]pply changes to PR, [S]how file, [E]dit file, or [Q]uit? [A/s/e/q] ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(parse_edit_choice(&input))
+}
+
+/// A menu answer in the edit loop.
+#[derive(Debug, PartialEq, Eq)]
+enum EditChoice {
+    Apply,
+    Show,
+    Edit,
+    Quit,
+}
+
+/// Parses a menu answer; an empty answer applies, like `create pr`'s accept.
+fn parse_edit_choice(input: &str) -> Option<EditChoice> {
+    match input.trim().to_lowercase().as_str() {
+        "a" | "apply" | "" => Some(EditChoice::Apply),
+        "s" | "show" => Some(EditChoice::Show),
+        "e" | "edit" => Some(EditChoice::Edit),
+        "q" | "quit" => Some(EditChoice::Quit),
+        _ => None,
+    }
+}
+
+/// Writes `title` and `body` to PR `number` with `gh pr edit` (the fallback
+/// when no GitHub token resolves for the native API client).
+fn gh_edit_pr(repo_root: &std::path::Path, number: u64, title: &str, body: &str) -> Result<()> {
+    let number = number.to_string();
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        [
+            "pr",
+            "edit",
+            number.as_str(),
+            "--title",
+            title,
+            "--body",
+            body,
+        ],
+        "pr edit",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to update pull request: {}", error_msg.trim());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_edit_choices() {
+        assert_eq!(parse_edit_choice("\n"), Some(EditChoice::Apply));
+        assert_eq!(parse_edit_choice("A\n"), Some(EditChoice::Apply));
+        assert_eq!(parse_edit_choice("show"), Some(EditChoice::Show));
+        assert_eq!(parse_edit_choice("e"), Some(EditChoice::Edit));
+        assert_eq!(parse_edit_choice("Q"), Some(EditChoice::Quit));
+        assert_eq!(parse_edit_choice("x"), None);
+    }
+
+    #[test]
+    fn pr_file_round_trips_multiline_body() {
+        let dir = tempfile::tempdir().unwrap();
+        let pr_file = dir.path().join("pr-details.yaml");
+        let content = PrContent {
+            title: "feat: add widgets".to_string(),
+            description: "## Summary\n\n- one: two\n- `code`\n".to_string(),
+        };
+        std::fs::write(&pr_file, crate::data::to_yaml(&content).unwrap()).unwrap();
+        let read = read_pr_file(&pr_file).unwrap();
+        assert_eq!(read.title, content.title);
+        assert_eq!(read.description, content.description);
+    }
+}
@@ -1,10 +1,15 @@
 //! Info command — analyzes branch commits and outputs repository information.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use clap::Parser;
 
+use crate::cli::format::YamlOrJson;
+use crate::data::{RenderFormat, RepositoryView};
+use crate::git::PathFilter;
+use crate::utils::env::SystemEnv;
+
 /// Info command options.
 #[derive(Parser)]
 pub struct InfoCommand {
@@ -12,6 +17,24 @@ pub struct InfoCommand {
     /// origin/master, main, or master).
     #[arg(value_name = "BASE_BRANCH")]
     pub base_branch: Option<String>,
+
+    /// Output format (default: yaml, or the top-level `--output`).
+    #[arg(short = 'o', long, value_enum)]
+    pub output: Option<YamlOrJson>,
+
+    /// Restricts analysis to files matching these globs (comma-separated or
+    /// repeated); commits touching none of them are skipped.
+    #[arg(long, value_name = "GLOB", value_delimiter = ',')]
+    pub paths: Vec<String>,
+
+    /// Analyzes at most N commits, the newest ones on the branch.
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Omits the diff file references, keeping the file statistics and
+    /// diff summary, for a quick overview of a large branch.
+    #[arg(long)]
+    pub no_diff: bool,
 }
 
 impl InfoCommand {
@@ -19,10 +42,20 @@ impl InfoCommand {
     ///
     /// `repo` is the repository location resolved at the CLI boundary
     /// (`None` = current working directory).
-    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
-        let yaml_output = run_info(self.base_branch.as_deref(), repo)?;
-        println!("{yaml_output}");
-        Ok(())
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let paths = PathFilter::new(&self.paths)?;
+        let mut repo_view = info_view(
+            self.base_branch.as_deref(),
+            repo,
+            paths.as_ref(),
+            self.limit,
+        )
+        .await?;
+        if self.no_diff {
+            repo_view.strip_diffs();
+        }
+        let output = repository_view_format(self.output);
+        crate::utils::pager::page_stream(|out| write_repository_view(repo_view, output, out))
     }
 
     /// Reads the PR template file if it exists, returning both content and location.
@@ -45,12 +78,16 @@ impl InfoCommand {
     /// Returns pull requests for the current branch using gh CLI.
     ///
     /// Runs `gh` pinned to `repo_root` (via `.current_dir`) so it resolves the
-    /// repository from the injected path rather than the process CWD.
-    pub(crate) fn get_branch_prs(
+    /// repository from the injected path rather than the process CWD. When the
+    /// `origin` remote is hosted on another forge (Bitbucket Cloud, Gitea,
+    /// Forgejo), that forge's REST API is queried instead.
+    pub(crate) async fn get_branch_prs(
         branch_name: &str,
         repo_root: &Path,
     ) -> Result<Vec<crate::data::PullRequest>> {
-        use serde_json::Value;
+        if let Some(api) = crate::forge::Forge::detect(repo_root).api_client()? {
+            return api.list_branch_prs(branch_name).await;
+        }
 
         // Use gh CLI to get PRs for the branch, routed through the metrics choke
         // point so the invocation is counted (#1387).
@@ -78,51 +115,194 @@ impl InfoCommand {
             );
         }
 
-        let json_str = String::from_utf8_lossy(&output.stdout);
-        let prs_json: Value =
-            serde_json::from_str(&json_str).context("Failed to parse PR JSON from gh")?;
-
-        let mut prs = Vec::new();
-        if let Some(prs_array) = prs_json.as_array() {
-            for pr_json in prs_array {
-                if let (Some(number), Some(title), Some(state), Some(url), Some(body)) = (
-                    pr_json.get("number").and_then(serde_json::Value::as_u64),
-                    pr_json.get("title").and_then(|t| t.as_str()),
-                    pr_json.get("state").and_then(|s| s.as_str()),
-                    pr_json.get("url").and_then(|u| u.as_str()),
-                    pr_json.get("body").and_then(|b| b.as_str()),
-                ) {
-                    let base = pr_json
-                        .get("baseRefName")
-                        .and_then(|b| b.as_str())
-                        .unwrap_or("")
-                        .to_string();
-                    prs.push(crate::data::PullRequest {
-                        number,
-                        title: title.to_string(),
-                        state: state.to_string(),
-                        url: url.to_string(),
-                        body: body.to_string(),
-                        base,
-                    });
-                }
-            }
+        parse_gh_pr_list(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Returns the hashes of pull request `number`'s commits, in no
+    /// particular order, via the forge API or `gh pr view`.
+    pub(crate) async fn get_pr_commits(number: u64, repo_root: &Path) -> Result<Vec<String>> {
+        if let Some(api) = crate::forge::Forge::detect(repo_root).api_client()? {
+            return api.pr_commits(number).await;
+        }
+        super::check::fetch_pr_commit_hashes(number, repo_root)
+    }
+
+    /// Returns the most recent pull request from `branch` in any state, via
+    /// the forge API or `gh pr list --state all`.
+    pub(crate) async fn get_latest_branch_pr(
+        branch_name: &str,
+        repo_root: &Path,
+    ) -> Result<Option<crate::data::PullRequest>> {
+        if let Some(api) = crate::forge::Forge::detect(repo_root).api_client()? {
+            return api.latest_branch_pr(branch_name).await;
+        }
+
+        let output = crate::github_metrics::run_gh(
+            &crate::pr_status::resolve_gh_binary(),
+            [
+                "pr",
+                "list",
+                "--head",
+                branch_name,
+                "--state",
+                "all",
+                "--json",
+                "number,title,state,url,body,baseRefName",
+                "--limit",
+                "1",
+            ],
+            "pr list",
+            Some(repo_root),
+        )
+        .context("Failed to execute gh command")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "gh command failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
         }
 
-        Ok(prs)
+        Ok(parse_gh_pr_list(&String::from_utf8_lossy(&output.stdout))?
+            .into_iter()
+            .next())
     }
 }
 
+/// Parses the JSON array printed by `gh pr list --json
+/// number,title,state,url,body,baseRefName`.
+fn parse_gh_pr_list(json_str: &str) -> Result<Vec<crate::data::PullRequest>> {
+    use serde_json::Value;
+
+    let prs_json: Value =
+        serde_json::from_str(json_str).context("Failed to parse PR JSON from gh")?;
+
+    let mut prs = Vec::new();
+    if let Some(prs_array) = prs_json.as_array() {
+        for pr_json in prs_array {
+            if let (Some(number), Some(title), Some(state), Some(url), Some(body)) = (
+                pr_json.get("number").and_then(serde_json::Value::as_u64),
+                pr_json.get("title").and_then(|t| t.as_str()),
+                pr_json.get("state").and_then(|s| s.as_str()),
+                pr_json.get("url").and_then(|u| u.as_str()),
+                pr_json.get("body").and_then(|b| b.as_str()),
+            ) {
+                let base = pr_json
+                    .get("baseRefName")
+                    .and_then(|b| b.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                prs.push(crate::data::PullRequest {
+                    number,
+                    title: title.to_string(),
+                    state: state.to_string(),
+                    url: url.to_string(),
+                    body: body.to_string(),
+                    base,
+                });
+            }
+        }
+    }
+
+    Ok(prs)
+}
+
 /// Runs the info logic and returns the repository YAML as a `String`.
 ///
 /// Shared by the CLI (which prints the result) and the MCP server (which
 /// returns it as tool content). When `repo_path` is `Some`, opens the
 /// repository at that path; otherwise opens at the current working directory.
 /// `base_branch` defaults to `main` or `master` when omitted.
-pub fn run_info<P: AsRef<Path>>(base_branch: Option<&str>, repo_path: Option<P>) -> Result<String> {
+pub async fn run_info<P: AsRef<Path>>(
+    base_branch: Option<&str>,
+    repo_path: Option<P>,
+) -> Result<String> {
+    render_repository_view(
+        info_view(base_branch, repo_path, None, None).await?,
+        RenderFormat::Yaml,
+    )
+}
+
+/// The `-o/--output` flag, else the top-level `--output`, else YAML.
+pub(super) fn repository_view_format(output: Option<YamlOrJson>) -> RenderFormat {
+    crate::data::output::resolve(
+        output.map(RenderFormat::from),
+        &SystemEnv,
+        RenderFormat::Yaml,
+    )
+}
+
+/// Renders a repository view, with secret-looking values masked, in the
+/// requested output format. Its YAML document doubles as the text form.
+pub(super) fn render_repository_view(
+    mut repo_view: RepositoryView,
+    output: RenderFormat,
+) -> Result<String> {
+    repo_view.redact_secrets();
+    repo_view.update_field_presence();
+    crate::data::output::render(&repo_view, output)
+}
+
+/// Writes a repository view, with secret-looking values masked, to `out` in
+/// the requested output format, ending with a newline.
+///
+/// Unlike [`render_repository_view`], the document is written as it is
+/// serialized — commit by commit for YAML — so a range of hundreds of
+/// commits never exists as a second, rendered copy in memory.
+pub(super) fn write_repository_view(
+    mut repo_view: RepositoryView,
+    output: RenderFormat,
+    out: &mut dyn std::io::Write,
+) -> Result<()> {
+    repo_view.redact_secrets();
+    repo_view.update_field_presence();
+    match output {
+        RenderFormat::Json => serde_json::to_writer_pretty(&mut *out, &repo_view)
+            // Keeps a closed pipe recognisable as an I/O error.
+            .map_err(std::io::Error::from)
+            .context("Failed to serialize output as JSON")?,
+        RenderFormat::Yaml | RenderFormat::Text => {
+            let commits = std::mem::take(&mut repo_view.commits);
+            crate::data::yaml::write_yaml_with_items(out, &repo_view, "commits", &commits)?;
+        }
+    }
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Builds the repository view behind [`run_info`], keeping only the newest
+/// `limit` commits, and the commits and files within `paths`, when given.
+async fn info_view<P: AsRef<Path>>(
+    base_branch: Option<&str>,
+    repo_path: Option<P>,
+    paths: Option<&PathFilter>,
+    limit: Option<usize>,
+) -> Result<RepositoryView> {
+    let (mut repo_view, repo_root) = local_info_view(base_branch, repo_path, paths, limit)?;
+    if let Some(branch) = repo_view
+        .branch_info
+        .as_ref()
+        .map(|info| info.branch.clone())
+    {
+        repo_view.branch_prs = InfoCommand::get_branch_prs(&branch, &repo_root)
+            .await
+            .ok()
+            .filter(|prs| !prs.is_empty());
+    }
+    Ok(repo_view)
+}
+
+/// The git-derived part of [`info_view`], without the branch's pull
+/// requests, and the repository's working directory. The repository is
+/// closed before the forge is queried.
+fn local_info_view<P: AsRef<Path>>(
+    base_branch: Option<&str>,
+    repo_path: Option<P>,
+    paths: Option<&PathFilter>,
+    limit: Option<usize>,
+) -> Result<(RepositoryView, PathBuf)> {
     use crate::data::{
-        AiInfo, BranchInfo, FieldExplanation, FileStatusInfo, RepositoryView, VersionInfo,
-        WorkingDirectoryInfo,
+        AiInfo, BranchInfo, FieldExplanation, FileStatusInfo, VersionInfo, WorkingDirectoryInfo,
     };
     use crate::git::{GitRepository, RemoteInfo};
     use crate::utils::ai_scratch;
@@ -173,17 +353,27 @@ pub fn run_info<P: AsRef<Path>>(base_branch: Option<&str>, repo_path: Option<P>)
     };
 
     let remotes = RemoteInfo::get_all_remotes(repo.repository())?;
-    let commits = repo.get_commits_in_range(&commit_range)?;
+    let mut commits = match limit {
+        Some(limit) => repo.get_filtered_commits(
+            Some(&commit_range),
+            &crate::git::CommitFilter {
+                limit: Some(limit),
+                ..Default::default()
+            },
+        )?,
+        None => repo.get_commits_in_range(&commit_range)?,
+    };
+    if let Some(paths) = paths {
+        commits = paths.restrict(commits)?;
+    }
+    let base = commit_range.trim_end_matches("..HEAD");
+    let merge_conflicts = repo.merge_conflicts(base).ok();
 
     let (pr_template, pr_template_location) = match InfoCommand::read_pr_template(repo_root).ok() {
         Some((content, location)) => (Some(content), Some(location)),
         None => (None, None),
     };
 
-    let branch_prs = InfoCommand::get_branch_prs(&current_branch, repo_root)
-        .ok()
-        .filter(|prs| !prs.is_empty());
-
     let versions = Some(VersionInfo {
         omni_dev: env!("CARGO_PKG_VERSION").to_string(),
     });
@@ -194,7 +384,8 @@ pub fn run_info<P: AsRef<Path>>(base_branch: Option<&str>, repo_path: Option<P>)
         scratch: ai_scratch_path.to_string_lossy().to_string(),
     };
 
-    let mut repo_view = RepositoryView {
+    let repo_view = RepositoryView {
+        schema_version: crate::data::SCHEMA_VERSION,
         versions,
         explanation: FieldExplanation::default(),
         working_directory,
@@ -202,14 +393,14 @@ pub fn run_info<P: AsRef<Path>>(base_branch: Option<&str>, repo_path: Option<P>)
         ai: ai_info,
         branch_info: Some(BranchInfo {
             branch: current_branch,
+            merge_conflicts,
         }),
         pr_template,
         pr_template_location,
-        branch_prs,
+        branch_prs: None,
         commits,
     };
-
-    repo_view.to_yaml_output()
+    Ok((repo_view, repo_root.to_path_buf()))
 }
 
 #[cfg(test)]
@@ -264,34 +455,55 @@ mod tests {
         (temp_dir, commits)
     }
 
-    #[test]
-    fn run_info_default_branch_uses_main() {
+    #[tokio::test]
+    async fn run_info_default_branch_uses_main() {
         let (temp_dir, _commits) = init_repo_with_commits();
         // With only a `main` branch, HEAD==main → main..HEAD is empty, so the
         // output lacks commits but still returns YAML with branch_info.
-        let yaml = run_info(None, Some(temp_dir.path())).unwrap();
+        let yaml = run_info(None, Some(temp_dir.path())).await.unwrap();
         assert!(
             yaml.contains("branch:"),
             "yaml should include branch_info: {yaml}"
         );
     }
 
-    #[test]
-    fn execute_against_injected_repo_succeeds() {
+    #[tokio::test]
+    async fn execute_against_injected_repo_succeeds() {
         // Drives `execute()` (not just `run_info`) so its run_info+println body
         // is covered deterministically via the injected repo path. Those lines
         // were previously only hit by the live-repo dispatch test and flickered
         // covered<->uncovered depending on the checkout state.
         let (temp_dir, _commits) = init_repo_with_commits();
-        InfoCommand { base_branch: None }
-            .execute(Some(temp_dir.path()))
+        InfoCommand {
+            base_branch: None,
+            output: Some(YamlOrJson::Yaml),
+            paths: Vec::new(),
+            limit: None,
+            no_diff: false,
+        }
+        .execute(Some(temp_dir.path()))
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn json_output_has_branch_info() {
+        let (temp_dir, _commits) = init_repo_with_commits();
+        let repo_view = info_view(None, Some(temp_dir.path()), None, None)
+            .await
             .unwrap();
+        let json = render_repository_view(repo_view, RenderFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["branch_info"]["branch"], "main");
+        assert!(value["commits"].as_array().unwrap().is_empty());
     }
 
-    #[test]
-    fn run_info_with_explicit_missing_base_errors() {
+    #[tokio::test]
+    async fn run_info_with_explicit_missing_base_errors() {
         let (temp_dir, _commits) = init_repo_with_commits();
-        let err = run_info(Some("no-such-branch"), Some(temp_dir.path())).unwrap_err();
+        let err = run_info(Some("no-such-branch"), Some(temp_dir.path()))
+            .await
+            .unwrap_err();
         let msg = format!("{err:#}");
         assert!(
             msg.contains("no-such-branch"),
@@ -299,8 +511,8 @@ mod tests {
         );
     }
 
-    #[test]
-    fn run_info_no_default_base_branch_errors() {
+    #[tokio::test]
+    async fn run_info_no_default_base_branch_errors() {
         // Init an empty repo with only a non-main branch.
         let tmp_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tmp");
         std::fs::create_dir_all(&tmp_root).unwrap();
@@ -323,7 +535,7 @@ mod tests {
         repo.commit(Some("HEAD"), &signature, &signature, "first", &tree, &[])
             .unwrap();
 
-        let err = run_info(None, Some(temp_dir.path())).unwrap_err();
+        let err = run_info(None, Some(temp_dir.path())).await.unwrap_err();
         let msg = format!("{err:#}");
         assert!(msg.contains("No default base branch found"), "got: {msg}");
     }
@@ -332,8 +544,8 @@ mod tests {
     /// `main` and an up-to-date `origin/main` exist, the default range must
     /// bind to the remote-tracking ref, excluding commits already merged
     /// into the remote mainline.
-    #[test]
-    fn run_info_prefers_origin_main_over_stale_local_main() {
+    #[tokio::test]
+    async fn run_info_prefers_origin_main_over_stale_local_main() {
         let (temp_dir, commits) = init_repo_with_commits();
         let repo = Repository::open(temp_dir.path()).unwrap();
 
@@ -347,7 +559,7 @@ mod tests {
         repo.reference("refs/remotes/origin/main", commits[1], true, "test")
             .unwrap();
 
-        let yaml = run_info(None, Some(temp_dir.path())).unwrap();
+        let yaml = run_info(None, Some(temp_dir.path())).await.unwrap();
         // origin/main..HEAD is empty; the stale local main..HEAD would have
         // included the "feat: work" commit.
         assert!(
@@ -356,9 +568,11 @@ mod tests {
         );
     }
 
-    #[test]
-    fn run_info_with_invalid_path_returns_error() {
-        let err = run_info(None, Some("/no/such/path/exists")).unwrap_err();
+    #[tokio::test]
+    async fn run_info_with_invalid_path_returns_error() {
+        let err = run_info(None, Some("/no/such/path/exists"))
+            .await
+            .unwrap_err();
         let msg = format!("{err:#}");
         assert!(
             msg.to_lowercase().contains("git") || msg.to_lowercase().contains("repo"),
@@ -369,23 +583,23 @@ mod tests {
     /// The injected `repo_path` fully determines the repository with no
     /// dependence on the process current working directory — the path is passed
     /// directly rather than via any process-CWD mutation.
-    #[test]
-    fn run_info_uses_injected_repo_without_cwd() {
+    #[tokio::test]
+    async fn run_info_uses_injected_repo_without_cwd() {
         let (temp_dir, _commits) = init_repo_with_commits();
-        let yaml = run_info(None, Some(temp_dir.path())).unwrap();
+        let yaml = run_info(None, Some(temp_dir.path())).await.unwrap();
         assert!(yaml.contains("branch:"));
     }
 
-    #[test]
-    fn run_info_with_explicit_existing_base_succeeds() {
+    #[tokio::test]
+    async fn run_info_with_explicit_existing_base_succeeds() {
         let (temp_dir, _commits) = init_repo_with_commits();
         // Explicitly pass "main" as base — branch exists, validation succeeds.
-        let yaml = run_info(Some("main"), Some(temp_dir.path())).unwrap();
+        let yaml = run_info(Some("main"), Some(temp_dir.path())).await.unwrap();
         assert!(yaml.contains("branch:"));
     }
 
-    #[test]
-    fn run_info_falls_back_to_master_when_main_missing() {
+    #[tokio::test]
+    async fn run_info_falls_back_to_master_when_main_missing() {
         // Init a repo with a `master` branch (no `main`) — exercises the
         // master fallback in the default-base resolution.
         let tmp_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tmp");
@@ -408,15 +622,15 @@ mod tests {
         repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
             .unwrap();
 
-        let yaml = run_info(None, Some(temp_dir.path())).unwrap();
+        let yaml = run_info(None, Some(temp_dir.path())).await.unwrap();
         assert!(yaml.contains("branch:"));
     }
 
     /// Exercises the `read_pr_template` Some arm by placing a PR template in
     /// the injected repo root's `.github/` — proving the template is read from
     /// the repo root, not the process current working directory.
-    #[test]
-    fn run_info_picks_up_pr_template_from_repo_root() {
+    #[tokio::test]
+    async fn run_info_picks_up_pr_template_from_repo_root() {
         let (temp_dir, _commits) = init_repo_with_commits();
         let github_dir = temp_dir.path().join(".github");
         std::fs::create_dir_all(&github_dir).unwrap();
@@ -426,7 +640,7 @@ mod tests {
         )
         .unwrap();
 
-        let yaml = run_info(None, Some(temp_dir.path())).unwrap();
+        let yaml = run_info(None, Some(temp_dir.path())).await.unwrap();
         assert!(
             yaml.contains("pr_template:") || yaml.contains("Sample Template"),
             "expected PR template info in yaml: {yaml}"
@@ -0,0 +1,226 @@
+//! Log search command — finds commits whose message (and, optionally, diff)
+//! matches a query, or answers a question about the history with AI from the
+//! commits that best match its keywords.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Serialize;
+
+use super::formatting::truncate_hash;
+use super::release_notes::strip_markdown_fence;
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::{GitRepository, HistorySearch, SearchHit};
+use crate::utils::env::SystemEnv;
+
+/// Commits listed, or handed to the AI, when `--limit` is not given.
+const DEFAULT_LIMIT: usize = 20;
+
+/// Log search command options.
+#[derive(Parser)]
+pub struct LogSearchCommand {
+    /// Text to search for; with --ask, a question about the history.
+    #[arg(value_name = "QUERY")]
+    pub query: String,
+
+    /// Also searches the lines each commit added or removed.
+    #[arg(long)]
+    pub diff: bool,
+
+    /// Treats QUERY as a regular expression.
+    #[arg(long, conflicts_with = "ask")]
+    pub regex: bool,
+
+    /// Matches case exactly.
+    #[arg(long, conflicts_with = "ask")]
+    pub case_sensitive: bool,
+
+    /// Searches the history reachable from this revision.
+    #[arg(long, value_name = "REV", default_value = "HEAD")]
+    pub rev: String,
+
+    /// Maximum number of commits listed, or handed to the AI with --ask.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_LIMIT)]
+    pub limit: usize,
+
+    /// Answers QUERY as a question with AI, from the commits that best match its keywords.
+    #[arg(long)]
+    pub ask: bool,
+}
+
+impl LogSearchCommand {
+    /// Executes the log search command.
+    ///
+    /// Results go to stdout and progress to stderr; `--output yaml|json`
+    /// prints the matching commits as structured data.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+
+        let mut search = if self.ask {
+            HistorySearch::keywords(&self.query)?
+        } else if self.regex {
+            HistorySearch::regex(&self.query, self.case_sensitive)?
+        } else {
+            HistorySearch::literal(&self.query, self.case_sensitive)?
+        };
+        search.search_diffs = self.diff;
+        search.limit = self.limit.max(1);
+        let hits = search.run(git_repo.repository(), &self.rev)?;
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+
+        if !self.ask {
+            let results = SearchResults {
+                query: self.query,
+                hits,
+            };
+            println!("{}", render(&results, format)?);
+            return Ok(());
+        }
+
+        if hits.is_empty() {
+            bail!(
+                "No commits match the keywords of '{}'; rephrase the question{}",
+                self.query,
+                if self.diff { "" } else { " or add --diff" }
+            );
+        }
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        eprintln!("🤖 Answering from {} candidate commit(s)...", hits.len());
+        let answer = answer_with_client(&claude_client, &self.query, &hits).await?;
+        let answer = HistoryAnswer {
+            question: self.query,
+            answer,
+            candidates: hits,
+        };
+        println!("{}", render(&answer, format)?);
+        Ok(())
+    }
+}
+
+/// Matching commits, newest first, as printed.
+#[derive(Serialize)]
+struct SearchResults {
+    query: String,
+    hits: Vec<SearchHit>,
+}
+
+impl Render for SearchResults {
+    fn render_text(&self) -> Result<String> {
+        if self.hits.is_empty() {
+            return Ok(format!("No commits match '{}'.", self.query));
+        }
+        Ok(format_hits(&self.hits).trim_end().to_string())
+    }
+}
+
+/// An AI answer with the candidate commits it was given.
+#[derive(Serialize)]
+struct HistoryAnswer {
+    question: String,
+    answer: String,
+    candidates: Vec<SearchHit>,
+}
+
+impl Render for HistoryAnswer {
+    fn render_text(&self) -> Result<String> {
+        Ok(self.answer.clone())
+    }
+}
+
+/// One line per hit — short hash, date, subject, author — followed by its
+/// matching lines, indented.
+fn format_hits(hits: &[SearchHit]) -> String {
+    let mut out = String::new();
+    for hit in hits {
+        let day = hit.date.get(..10).unwrap_or(&hit.date);
+        out.push_str(&format!(
+            "{} {day} {} ({})\n",
+            truncate_hash(&hit.hash),
+            hit.subject,
+            hit.author
+        ));
+        for line in &hit.lines {
+            out.push_str(&format!("    {line}\n"));
+        }
+    }
+    out
+}
+
+/// Asks the AI to answer `question` from the candidate commits.
+pub(crate) async fn answer_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    question: &str,
+    hits: &[SearchHit],
+) -> Result<String> {
+    let user =
+        crate::claude::prompts::generate_history_search_user_prompt(question, &format_hits(hits));
+    let response = claude_client
+        .send_message(crate::claude::prompts::HISTORY_SEARCH_SYSTEM_PROMPT, &user)
+        .await?;
+    let answer = strip_markdown_fence(response.trim());
+    if answer.is_empty() {
+        bail!("AI returned an empty answer");
+    }
+    Ok(answer.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::git::MatchSource;
+
+    fn hit(hash: &str, subject: &str, lines: &[&str]) -> SearchHit {
+        SearchHit {
+            hash: hash.to_string(),
+            author: "Dev <dev@example.com>".to_string(),
+            date: "2024-03-01T10:00:00+00:00".to_string(),
+            subject: subject.to_string(),
+            matched_in: vec![MatchSource::Diff],
+            score: 1,
+            lines: lines.iter().map(|l| (*l).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn hits_list_subject_then_matching_lines() {
+        let hits = [hit(
+            "aaaaaaaaaaaa",
+            "fix: raise retries",
+            &["retry.rs: +let attempts = 5;"],
+        )];
+        assert_eq!(
+            format_hits(&hits),
+            "aaaaaaaa 2024-03-01 fix: raise retries (Dev <dev@example.com>)\n    retry.rs: +let attempts = 5;\n"
+        );
+        let empty = SearchResults {
+            query: "nothing".to_string(),
+            hits: vec![],
+        };
+        assert_eq!(empty.render_text().unwrap(), "No commits match 'nothing'.");
+    }
+
+    #[tokio::test]
+    async fn answer_comes_from_ai_response() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "```markdown\nIn aaaaaaaa (2024-03-01).\n```".to_string(),
+        )]);
+        let client = ClaudeClient::new(Box::new(mock));
+        let answer = answer_with_client(
+            &client,
+            "When did retries change?",
+            &[hit("aaaaaaaaaaaa", "fix: raise retries", &[])],
+        )
+        .await
+        .unwrap();
+        assert_eq!(answer, "In aaaaaaaa (2024-03-01).");
+    }
+}
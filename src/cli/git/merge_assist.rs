@@ -0,0 +1,361 @@
+//! `omni-dev git merge assist` — AI-proposed resolutions for the conflicts of
+//! a merge, rebase, cherry-pick, or revert in progress.
+//!
+//! Each conflicted file is sent to the AI once; every conflict is then shown
+//! with its proposal and rationale, and only the proposals accepted are
+//! written into the file. Skipped conflicts keep their markers, and nothing is
+//! staged or committed: the user reviews the result, then runs `git add` and
+//! `git <operation> --continue` as usual.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+
+use super::release_notes::strip_markdown_fence;
+use crate::git::conflict::{self, Conflict};
+use crate::git::GitRepository;
+use crate::utils::i18n::tr;
+use crate::utils::redact::redact_secrets;
+
+/// `omni-dev git merge assist` CLI command.
+#[derive(Parser)]
+pub struct MergeAssistCommand {
+    /// Only assists with these conflicted files (defaults to all of them).
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<String>,
+
+    /// Prints the proposals without asking or changing any file.
+    #[arg(long, conflicts_with = "accept_all")]
+    pub dry_run: bool,
+
+    /// Applies every proposal without asking; the files are still left unstaged for review.
+    #[arg(long)]
+    pub accept_all: bool,
+}
+
+/// The AI's proposal for one conflict.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Proposal {
+    /// 1-based number of the conflict in the file.
+    pub conflict: usize,
+    /// Text replacing the conflict, markers included.
+    pub resolution: String,
+    /// Why the resolution is right.
+    #[serde(default)]
+    pub rationale: String,
+}
+
+/// The AI's response.
+#[derive(Deserialize)]
+struct ProposalResponse {
+    #[serde(default)]
+    resolutions: Vec<Proposal>,
+}
+
+/// How proposals are decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Ask for each proposal.
+    Ask,
+    /// Accept every proposal.
+    AcceptAll,
+    /// Show the proposals only.
+    DryRun,
+}
+
+/// The decisions for one file.
+struct Decisions {
+    /// The accepted resolution per conflict, `None` for those left marked.
+    resolutions: Vec<Option<String>>,
+    /// Whether the user asked to stop.
+    quit: bool,
+}
+
+impl MergeAssistCommand {
+    /// Executes the merge assist command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let Some(operation) = git_repo.operation_in_progress() else {
+            bail!("No merge, rebase, cherry-pick, or revert is in progress");
+        };
+        let mut files = git_repo.conflicted_files()?;
+        if !self.paths.is_empty() {
+            files.retain(|file| self.paths.iter().any(|p| p == file));
+        }
+        if files.is_empty() {
+            bail!("No conflicted files to assist with");
+        }
+
+        let mode = if self.dry_run {
+            Mode::DryRun
+        } else if self.accept_all {
+            Mode::AcceptAll
+        } else {
+            crate::utils::interactive::ensure_interactive("Reviewing conflict resolutions")?;
+            Mode::Ask
+        };
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+
+        let mut reader = io::BufReader::new(io::stdin());
+        let (mut applied, mut proposed) = (0, 0);
+        let mut changed = Vec::new();
+        for path in &files {
+            let full_path = repo_root.join(path);
+            let text = fs::read_to_string(&full_path)
+                .with_context(|| format!("Failed to read {}", full_path.display()))?;
+            let segments = conflict::parse(&text).with_context(|| format!("In {path}"))?;
+            let conflicts: Vec<&Conflict> = conflict::conflicts(&segments).collect();
+            if conflicts.is_empty() {
+                println!("✅ {path}: no conflict markers left");
+                continue;
+            }
+            if redact_secrets(&text) != text {
+                eprintln!(
+                    "⚠️  {path} contains what looks like a credential and was not sent to the AI; \
+                     resolve it by hand"
+                );
+                continue;
+            }
+
+            eprintln!(
+                "🤝 Proposing resolutions for {} conflict(s) in {path}...",
+                conflicts.len()
+            );
+            let proposals =
+                propose_with_client(&claude_client, operation, path, &text, conflicts.len())
+                    .await?;
+            proposed += proposals.len();
+            let decisions = decide(path, &conflicts, &proposals, mode, &mut reader)?;
+            let accepted = decisions.resolutions.iter().flatten().count();
+            if accepted > 0 {
+                fs::write(
+                    &full_path,
+                    conflict::resolve(&segments, &decisions.resolutions),
+                )
+                .with_context(|| format!("Failed to write {}", full_path.display()))?;
+                applied += accepted;
+                changed.push(path.clone());
+            }
+            if decisions.quit {
+                break;
+            }
+        }
+
+        println!();
+        if mode == Mode::DryRun {
+            println!("🤖 {proposed} AI proposal(s) shown; no file was changed.");
+            return Ok(());
+        }
+        println!("🤖 Applied {applied} of {proposed} AI proposal(s).");
+        if !changed.is_empty() {
+            println!("   Changed: {}", changed.join(", "));
+            println!(
+                "   Nothing was staged or committed. Review the changes (`git diff`), then \
+                 `git add` the files and run `git {operation} --continue`."
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Asks the AI to resolve the `count` conflicts in `content`, the text of
+/// `path`. Proposals for conflicts the file does not have are dropped.
+pub(crate) async fn propose_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    operation: &str,
+    path: &str,
+    content: &str,
+    count: usize,
+) -> Result<Vec<Proposal>> {
+    let user =
+        crate::claude::prompts::generate_merge_assist_user_prompt(operation, path, count, content);
+    let response = claude_client
+        .send_message(crate::claude::prompts::MERGE_ASSIST_SYSTEM_PROMPT, &user)
+        .await?;
+    let response: ProposalResponse = serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned conflict resolutions that are not valid YAML")?;
+    Ok(response
+        .resolutions
+        .into_iter()
+        .filter(|p| (1..=count).contains(&p.conflict))
+        .filter(|p| !p.resolution.lines().any(is_marker_line))
+        .collect())
+}
+
+/// Shows each conflict of `path` with its proposal and decides which to
+/// apply according to `mode`.
+fn decide(
+    path: &str,
+    conflicts: &[&Conflict],
+    proposals: &[Proposal],
+    mode: Mode,
+    reader: &mut (dyn io::BufRead + Send),
+) -> Result<Decisions> {
+    let mut decisions = Decisions {
+        resolutions: vec![None; conflicts.len()],
+        quit: false,
+    };
+    for (index, conflict) in conflicts.iter().enumerate() {
+        let number = index + 1;
+        println!(
+            "\n━━ {path}:{} — conflict {number} of {} ━━",
+            conflict.line,
+            conflicts.len()
+        );
+        print!("{}", conflict.raw);
+        let Some(proposal) = proposals.iter().find(|p| p.conflict == number) else {
+            println!("🤖 No AI proposal; the conflict is left as is.");
+            continue;
+        };
+        print!("{}", format_proposal(proposal));
+
+        let accept = match mode {
+            Mode::DryRun => false,
+            Mode::AcceptAll => true,
+            Mode::Ask => match ask(reader)? {
+                Some(accept) => accept,
+                None => {
+                    decisions.quit = true;
+                    break;
+                }
+            },
+        };
+        if accept {
+            decisions.resolutions[index] = Some(proposal.resolution.clone());
+        }
+    }
+    Ok(decisions)
+}
+
+/// A proposal as shown to the user, clearly marked as the AI's.
+fn format_proposal(proposal: &Proposal) -> String {
+    let mut out = String::from("🤖 AI-proposed resolution (not applied unless accepted):\n");
+    for line in proposal.resolution.lines() {
+        let _ = writeln!(out, "  │ {line}");
+    }
+    if !proposal.rationale.trim().is_empty() {
+        let _ = writeln!(out, "   Rationale: {}", proposal.rationale.trim());
+    }
+    out
+}
+
+/// Asks whether to accept a proposal: `Some(true)` to accept, `Some(false)`
+/// to skip, `None` to quit (also on end of input).
+fn ask(reader: &mut (dyn io::BufRead + Send)) -> Result<Option<bool>> {
+    loop {
+        print!("❓ {} ", tr("merge-assist-menu", &[]));
+        io::stdout().flush()?;
+        let Some(input) = super::read_interactive_line(reader)? else {
+            eprintln!("warning: stdin closed, leaving the remaining conflicts as they are");
+            return Ok(None);
+        };
+        match input.trim().to_lowercase().as_str() {
+            "a" | "accept" => return Ok(Some(true)),
+            "s" | "skip" => return Ok(Some(false)),
+            "q" | "quit" => return Ok(None),
+            _ => println!("{}", tr("merge-assist-invalid-choice", &[])),
+        }
+    }
+}
+
+/// Whether `line` is a conflict marker, which a resolution must not contain.
+fn is_marker_line(line: &str) -> bool {
+    ["<<<<<<<", "|||||||", "=======", ">>>>>>>"]
+        .iter()
+        .any(|marker| line == *marker || line.starts_with(&format!("{marker} ")))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+
+    const FILE: &str = "a\n<<<<<<< HEAD\none\n=======\ntwo\n>>>>>>> feature\nb\n\
+        <<<<<<< HEAD\nthree\n=======\nfour\n>>>>>>> feature\n";
+
+    fn proposal(conflict: usize, resolution: &str) -> Proposal {
+        Proposal {
+            conflict,
+            resolution: resolution.to_string(),
+            rationale: "Both sides.".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn proposals_outside_the_file_or_with_markers_are_dropped() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok("resolutions:\n  \
+            - conflict: 1\n    resolution: |\n      one\n      two\n    rationale: Keep both.\n  \
+            - conflict: 2\n    resolution: |\n      <<<<<<< HEAD\n      three\n  \
+            - conflict: 7\n    resolution: x\n"
+            .to_string())]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+        let proposals = propose_with_client(&client, "rebase", "src/a.rs", FILE, 2)
+            .await
+            .unwrap();
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].resolution, "one\ntwo\n");
+        assert_eq!(proposals[0].rationale, "Keep both.");
+
+        let sent = prompts.prompts();
+        assert!(sent[0]
+            .1
+            .contains("the 2 conflict(s) a rebase left in `src/a.rs`"));
+        assert!(sent[0].1.contains("the commit being replayed"));
+    }
+
+    #[test]
+    fn decide_applies_only_accepted_proposals() {
+        let segments = conflict::parse(FILE).unwrap();
+        let conflicts: Vec<&Conflict> = conflict::conflicts(&segments).collect();
+        let proposals = [proposal(1, "one\ntwo\n"), proposal(2, "four\n")];
+
+        let mut input = io::Cursor::new("x\ns\na\n");
+        let decisions = decide("a.txt", &conflicts, &proposals, Mode::Ask, &mut input).unwrap();
+        assert_eq!(decisions.resolutions, [None, Some("four\n".to_string())]);
+        assert!(!decisions.quit);
+        assert_eq!(
+            conflict::resolve(&segments, &decisions.resolutions),
+            "a\n<<<<<<< HEAD\none\n=======\ntwo\n>>>>>>> feature\nb\nfour\n"
+        );
+
+        let mut input = io::Cursor::new("a\nq\n");
+        let decisions = decide("a.txt", &conflicts, &proposals, Mode::Ask, &mut input).unwrap();
+        assert_eq!(
+            decisions.resolutions,
+            [Some("one\ntwo\n".to_string()), None]
+        );
+        assert!(decisions.quit);
+
+        let mut input = io::Cursor::new("");
+        let dry = decide("a.txt", &conflicts, &proposals, Mode::DryRun, &mut input).unwrap();
+        assert_eq!(dry.resolutions, [None, None]);
+        let all = decide("a.txt", &conflicts, &proposals, Mode::AcceptAll, &mut input).unwrap();
+        assert_eq!(all.resolutions.iter().flatten().count(), 2);
+    }
+
+    #[test]
+    fn proposals_are_marked_as_the_ais() {
+        let text = format_proposal(&proposal(1, "one\n"));
+        assert_eq!(
+            text,
+            "🤖 AI-proposed resolution (not applied unless accepted):\n  │ one\n   Rationale: Both sides.\n"
+        );
+        assert!(is_marker_line(">>>>>>> feature"));
+        assert!(!is_marker_line("======== heading"));
+    }
+}
@@ -0,0 +1,458 @@
+//! Merge PR command — merges the current branch's pull request once its CI
+//! checks are green and its commits pass `omni-dev git commit message check`.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde_json::Value;
+
+use super::defaults::AutoApply;
+use super::info::InfoCommand;
+use super::update_pr::select_pr;
+use crate::forge::{MergeStrategy, PullRequestApi};
+use crate::git::GitRepository;
+use crate::pr_status::PrCheckState;
+
+/// Settings key choosing the default merge strategy.
+const MERGE_STRATEGY_KEY: &str = "OMNI_DEV_MERGE_STRATEGY";
+
+/// Merge PR command options.
+#[derive(Parser)]
+pub struct MergePrCommand {
+    /// PR number to merge (defaults to the open PR for the current branch).
+    #[arg(long, value_name = "N")]
+    pub number: Option<u64>,
+
+    /// Merge strategy (defaults to OMNI_DEV_MERGE_STRATEGY, else squash).
+    #[arg(long, value_enum)]
+    pub strategy: Option<MergeStrategy>,
+
+    #[command(flatten)]
+    pub apply: AutoApply,
+
+    /// Treats commit check warnings as blocking, like `check --strict`.
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+}
+
+impl MergePrCommand {
+    /// Executes the merge PR command.
+    ///
+    /// Refuses to merge while CI is failing or still running, when the local
+    /// branch differs from the pushed one, or when the commit check reports
+    /// errors. For squash merges the commit message is written by the AI from
+    /// the PR and its commit messages.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let ai_info = crate::utils::check_pr_command_prerequisites(None, repo_root).await?;
+        println!(
+            "✓ {} credentials verified (model: {})",
+            ai_info.provider, ai_info.model
+        );
+        let forge = crate::forge::Forge::detect(repo_root);
+        let forge_api = forge.api_client()?;
+        if forge_api.is_some() {
+            println!("✓ {} API access verified", forge.name());
+        } else {
+            println!("✓ GitHub CLI verified");
+        }
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let branch = git_repo.get_current_branch().context(
+            "Failed to get current branch. Make sure you're not in detached HEAD state.",
+        )?;
+        let prs = InfoCommand::get_branch_prs(&branch, repo_root)
+            .await
+            .unwrap_or_default();
+        let target = select_pr(&prs, self.number, &branch)?;
+        ensure_pushed(&git_repo, &branch)?;
+        let pr_commits = match forge_api.as_deref() {
+            Some(api) => api.pr_commits(target.number).await?,
+            None => super::check::fetch_pr_commit_hashes(target.number, repo_root)?,
+        };
+        if !pr_ends_at_head(&git_repo, &pr_commits)? {
+            bail!(
+                "PR #{} does not end at the local HEAD; check out its branch and pull \
+                 so the checked commits are the ones merged.",
+                target.number
+            );
+        }
+
+        let checks = match forge_api.as_deref() {
+            Some(api) => api.check_state(target.number).await?,
+            None => gh_check_state(repo_root, target.number)?,
+        };
+        match checks {
+            PrCheckState::Success => println!("✓ CI checks passed"),
+            PrCheckState::None => println!("ℹ️  No CI checks reported for PR #{}", target.number),
+            PrCheckState::Pending => bail!(
+                "CI checks are still running on PR #{}; merge once they finish.",
+                target.number
+            ),
+            PrCheckState::Failure => bail!(
+                "CI checks are failing on PR #{}; fix them before merging.",
+                target.number
+            ),
+        }
+
+        let range = pr_commit_range(&git_repo, &target.base)?;
+        println!("🔍 Checking commits in {range}...");
+        let outcome = super::run_check(&range, None, Some(repo_root), self.strict, None).await?;
+        if outcome.exit_code != 0 {
+            println!("{}", outcome.report_yaml);
+            bail!(
+                "Commit check failed for PR #{}; fix the reported commits \
+                 (e.g. with 'omni-dev git commit message twiddle') before merging.",
+                target.number
+            );
+        }
+        println!(
+            "✓ {} commit(s) passed the commit check",
+            outcome.total_commits
+        );
+
+        let strategy = self.resolve_strategy();
+        let message = if strategy == MergeStrategy::Squash {
+            let commits = git_repo.get_commits_in_range(&range)?;
+            let messages: Vec<String> =
+                commits.iter().map(|c| c.original_message.clone()).collect();
+            let message = self
+                .generate_squash_message(repo_root, &target.title, &target.body, &messages)
+                .await?;
+            println!("\n📝 Squash commit message:\n{message}\n");
+            Some(message)
+        } else {
+            None
+        };
+
+        if !self.apply.destructive()? && !confirm_merge(target.number, strategy)? {
+            println!("❌ PR merge cancelled by user");
+            return Ok(());
+        }
+
+        match forge_api.as_deref() {
+            Some(api) => merge_forge_pr(api, target.number, strategy, message.as_deref()).await?,
+            None => gh_merge_pr(repo_root, target.number, strategy, message.as_deref())?,
+        }
+        println!(
+            "✅ Pull request #{} merged ({})",
+            target.number,
+            strategy.as_str()
+        );
+        Ok(())
+    }
+
+    /// Returns `--strategy`, else the configured strategy, else squash.
+    fn resolve_strategy(&self) -> MergeStrategy {
+        self.strategy.unwrap_or_else(|| {
+            let configured = crate::utils::settings::get_env_var(MERGE_STRATEGY_KEY).ok();
+            strategy_from_setting(configured.as_deref())
+        })
+    }
+
+    /// Asks the AI for the squash commit message.
+    async fn generate_squash_message(
+        &self,
+        repo_root: &std::path::Path,
+        pr_title: &str,
+        pr_body: &str,
+        commit_messages: &[String],
+    ) -> Result<String> {
+        let context_dir =
+            crate::claude::context::resolve_context_dir_at(self.context_dir.as_deref(), repo_root);
+        let valid_scopes = crate::claude::context::load_project_scopes(&context_dir, repo_root);
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+
+        println!("🤖 Generating squash commit message...");
+        let system = crate::claude::prompts::generate_squash_commit_system_prompt(&valid_scopes);
+        let user = crate::claude::prompts::generate_squash_commit_user_prompt(
+            pr_title,
+            pr_body,
+            commit_messages,
+        );
+        let message = claude_client.send_message(&system, &user).await?;
+        let message = message.trim().to_string();
+        if message.is_empty() {
+            bail!("AI returned an empty squash commit message");
+        }
+        Ok(message)
+    }
+}
+
+/// Parses the configured merge strategy, falling back to squash when unset or
+/// unrecognised.
+fn strategy_from_setting(value: Option<&str>) -> MergeStrategy {
+    match value {
+        Some(value) => MergeStrategy::parse(value).unwrap_or_else(|| {
+            tracing::warn!("Ignoring unknown {MERGE_STRATEGY_KEY} value: {value}");
+            MergeStrategy::Squash
+        }),
+        None => MergeStrategy::Squash,
+    }
+}
+
+/// Fails unless `origin/<branch>` exists and points at the local HEAD,
+/// since the checks ran on the pushed commits and the commit check runs on
+/// the local ones.
+pub(super) fn ensure_pushed(repo: &GitRepository, branch: &str) -> Result<()> {
+    let git = repo.repository();
+    let head = git.head()?.peel_to_commit()?.id();
+    let Ok(remote) = git.refname_to_id(&format!("refs/remotes/origin/{branch}")) else {
+        bail!(
+            "Branch '{branch}' has no 'origin/{branch}'; \
+             push (or fetch) it so the checked commits are the ones merged."
+        );
+    };
+    if remote != head {
+        bail!(
+            "Local branch '{branch}' differs from 'origin/{branch}'; \
+             push or pull so the checked commits are the ones merged."
+        );
+    }
+    Ok(())
+}
+
+/// Returns whether the PR whose commits are `pr_commits` (in any order)
+/// ends at the local HEAD: HEAD is one of them and every other is its
+/// ancestor.
+fn pr_ends_at_head(repo: &GitRepository, pr_commits: &[String]) -> Result<bool> {
+    let git = repo.repository();
+    let head = git.head()?.peel_to_commit()?.id();
+    let head_hex = head.to_string();
+    if !pr_commits.contains(&head_hex) {
+        return Ok(false);
+    }
+    Ok(pr_commits.iter().all(|hash| {
+        git2::Oid::from_str(hash)
+            .is_ok_and(|oid| oid == head || git.graph_descendant_of(head, oid).unwrap_or(false))
+    }))
+}
+
+/// Returns the commit range of the PR: `origin/<base>..HEAD` when the PR's
+/// base branch is known and fetched, else the default range.
+pub(super) fn pr_commit_range(repo: &GitRepository, base: &str) -> Result<String> {
+    let remote_base = format!("origin/{base}");
+    if !base.is_empty()
+        && repo
+            .repository()
+            .find_reference(&format!("refs/remotes/{remote_base}"))
+            .is_ok()
+    {
+        return Ok(format!("{remote_base}..HEAD"));
+    }
+    super::default_commit_range(repo)
+}
+
+/// Merges PR `number` through a forge REST API.
+async fn merge_forge_pr(
+    api: &dyn PullRequestApi,
+    number: u64,
+    strategy: MergeStrategy,
+    message: Option<&str>,
+) -> Result<()> {
+    println!(
+        "🔀 Merging pull request #{number} via {} API...",
+        api.forge_name()
+    );
+    api.merge_pr(number, strategy, message)
+        .await
+        .with_context(|| format!("Failed to merge pull request #{number}"))
+}
+
+/// Reads the CI verdict for PR `number` with `gh pr view` (the fallback when
+/// no GitHub token resolves for the native API client).
+fn gh_check_state(repo_root: &std::path::Path, number: u64) -> Result<PrCheckState> {
+    let number = number.to_string();
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        ["pr", "view", number.as_str(), "--json", "statusCheckRollup"],
+        "pr view",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "Failed to read checks for PR #{number}: {}",
+            error_msg.trim()
+        );
+    }
+    let response: Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse gh pr view output")?;
+    let contexts = response
+        .get("statusCheckRollup")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    Ok(crate::pr_status::rollup_check_state(contexts))
+}
+
+/// Merges PR `number` with `gh pr merge`.
+fn gh_merge_pr(
+    repo_root: &std::path::Path,
+    number: u64,
+    strategy: MergeStrategy,
+    message: Option<&str>,
+) -> Result<()> {
+    println!("🔀 Merging pull request #{number} via gh...");
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        gh_merge_args(number, strategy, message),
+        "pr merge",
+        Some(repo_root),
+    )
+    .context("Failed to merge pull request")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to merge pull request: {}", error_msg.trim());
+    }
+    Ok(())
+}
+
+/// Builds the `gh pr merge` arguments.
+fn gh_merge_args(number: u64, strategy: MergeStrategy, message: Option<&str>) -> Vec<String> {
+    let mut args = vec![
+        "pr".to_string(),
+        "merge".to_string(),
+        number.to_string(),
+        format!("--{}", strategy.as_str()),
+    ];
+    if let Some(message) = message {
+        let (subject, body) = crate::forge::split_commit_message(message);
+        args.extend([
+            "--subject".to_string(),
+            subject.to_string(),
+            "--body".to_string(),
+            body.to_string(),
+        ]);
+    }
+    args
+}
+
+/// Asks whether to merge PR `number` with `strategy`.
+fn confirm_merge(number: u64, strategy: MergeStrategy) -> Result<bool> {
+    use std::io::{self, Write};
+
+    let args: [(&str, &dyn std::fmt::Display); 2] =
+        [("number", &number), ("strategy", &strategy.as_str())];
+    print!("❓ {} ", crate::utils::i18n::tr("confirm-merge", &args));
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(crate::utils::i18n::is_yes(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strategy_setting_defaults_to_squash() {
+        assert_eq!(strategy_from_setting(None), MergeStrategy::Squash);
+        assert_eq!(strategy_from_setting(Some("rebase")), MergeStrategy::Rebase);
+        assert_eq!(
+            strategy_from_setting(Some("octopus")),
+            MergeStrategy::Squash
+        );
+    }
+
+    #[test]
+    fn gh_merge_args_carry_squash_message() {
+        assert_eq!(
+            gh_merge_args(7, MergeStrategy::Squash, Some("feat: x\n\n- a\n- b")),
+            vec![
+                "pr",
+                "merge",
+                "7",
+                "--squash",
+                "--subject",
+                "feat: x",
+                "--body",
+                "- a\n- b"
+            ]
+        );
+        assert_eq!(
+            gh_merge_args(7, MergeStrategy::Rebase, None),
+            vec!["pr", "merge", "7", "--rebase"]
+        );
+    }
+
+    #[test]
+    fn pushed_and_pr_head_must_match_local_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = git
+            .find_tree(git.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let first = git
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+        let parent = git.find_commit(first).unwrap();
+        let second = git
+            .commit(Some("HEAD"), &sig, &sig, "second", &tree, &[&parent])
+            .unwrap();
+        let repo = GitRepository::open_at(dir.path()).unwrap();
+        let branch = repo.get_current_branch().unwrap();
+
+        let err = ensure_pushed(&repo, &branch).unwrap_err();
+        assert!(err.to_string().contains("has no 'origin/"), "{err}");
+        git.reference(
+            &format!("refs/remotes/origin/{branch}"),
+            first,
+            false,
+            "test",
+        )
+        .unwrap();
+        assert!(ensure_pushed(&repo, &branch).is_err());
+        git.reference(
+            &format!("refs/remotes/origin/{branch}"),
+            second,
+            true,
+            "test",
+        )
+        .unwrap();
+        ensure_pushed(&repo, &branch).unwrap();
+
+        let (first, second) = (first.to_string(), second.to_string());
+        assert!(pr_ends_at_head(&repo, &[second.clone(), first.clone()]).unwrap());
+        assert!(!pr_ends_at_head(&repo, &[first]).unwrap());
+        assert!(!pr_ends_at_head(&repo, &[second, "f".repeat(40)]).unwrap());
+    }
+
+    #[test]
+    fn pr_commit_range_prefers_fetched_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = git
+            .find_tree(git.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let oid = git
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        git.reference("refs/remotes/origin/develop", oid, false, "test")
+            .unwrap();
+
+        let repo = GitRepository::open_at(dir.path()).unwrap();
+        assert_eq!(
+            pr_commit_range(&repo, "develop").unwrap(),
+            "origin/develop..HEAD"
+        );
+        assert_eq!(
+            pr_commit_range(&repo, "release").ok(),
+            super::super::default_commit_range(&repo).ok()
+        );
+    }
+}
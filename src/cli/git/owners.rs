@@ -0,0 +1,191 @@
+//! Stats owners command — reports who owns each area of the repository from
+//! blame, commit frequency, and recency, and can turn the report into a
+//! `CODEOWNERS` file or save it for reviewer suggestions.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::ownership::{Owner, OwnershipOptions, OwnershipReport};
+use crate::git::GitRepository;
+use crate::utils::env::SystemEnv;
+
+/// Stats owners command options.
+#[derive(Parser)]
+pub struct StatsOwnersCommand {
+    /// Only reports on this file or directory, relative to the repository
+    /// root (defaults to the whole repository).
+    #[arg(value_name = "PATH")]
+    pub path: Option<String>,
+
+    /// Directory depth areas are grouped at.
+    #[arg(long, value_name = "N", default_value_t = 2)]
+    pub depth: usize,
+
+    /// Only counts commits made since this date (`YYYY-MM-DD`, RFC 3339, or
+    /// a duration back from now such as `52w`).
+    #[arg(long, value_name = "WHEN")]
+    pub since: Option<String>,
+
+    /// Owners listed per area.
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub top: usize,
+
+    /// Prints the report as a CODEOWNERS file.
+    #[arg(long)]
+    pub codeowners: bool,
+
+    /// Saves the report as owners.yaml in the context directory, where `create pr` uses it to suggest reviewers.
+    #[arg(long)]
+    pub save: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+}
+
+impl StatsOwnersCommand {
+    /// Executes the stats owners command.
+    ///
+    /// The report is markdown by default; `--output yaml|json` prints it as
+    /// structured data, in the same shape `--save` writes.
+    pub fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        if self.depth == 0 {
+            bail!("--depth must be at least 1");
+        }
+        let options = OwnershipOptions {
+            depth: self.depth,
+            since: self
+                .since
+                .as_deref()
+                .map(crate::cli::log::parse_time_bound)
+                .transpose()?,
+            top: self.top.max(1),
+        };
+
+        let path = self.path.as_deref().unwrap_or("");
+        eprintln!("🔍 Reading blame and history...");
+        let report =
+            OwnershipReport::analyze(git_repo.repository(), path, &options, chrono::Utc::now())?;
+        if report.areas.is_empty() {
+            bail!("No tracked files under '{path}'");
+        }
+
+        if self.save {
+            let context_dir = crate::claude::context::resolve_context_dir_at(
+                self.context_dir.as_deref(),
+                &repo_root,
+            );
+            let saved = report.save(&context_dir)?;
+            eprintln!("💾 Saved the report to {}", saved.display());
+        }
+        if self.codeowners {
+            print!("{}", report.to_codeowners());
+            return Ok(());
+        }
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&report, format)?);
+        Ok(())
+    }
+}
+
+impl Render for OwnershipReport {
+    fn render_text(&self) -> Result<String> {
+        let title = if self.path.is_empty() {
+            "# Code ownership\n\n".to_string()
+        } else {
+            format!("# Code ownership of `{}`\n\n", self.path)
+        };
+        let mut out = title;
+        out.push_str("| Area | Files | Lines | Commits | Owners |\n");
+        out.push_str("|------|------:|------:|--------:|--------|\n");
+        for area in &self.areas {
+            let owners: Vec<String> = area.owners.iter().map(format_owner).collect();
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} |\n",
+                area.area,
+                area.files,
+                area.lines,
+                area.commits,
+                if owners.is_empty() {
+                    "—".to_string()
+                } else {
+                    owners.join(", ")
+                }
+            ));
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+/// `Name (@login) 62.5%`, or `Name 62.5%` without a login.
+fn format_owner(owner: &Owner) -> String {
+    match &owner.login {
+        Some(login) => format!("{} (@{login}) {:.1}%", owner.name, owner.score),
+        None => format!("{} {:.1}%", owner.name, owner.score),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::git::ownership::AreaOwnership;
+
+    #[test]
+    fn text_report_is_a_markdown_table() {
+        let report = OwnershipReport {
+            path: "src".to_string(),
+            depth: 2,
+            areas: vec![
+                AreaOwnership {
+                    area: "src/git".to_string(),
+                    files: 3,
+                    lines: 120,
+                    commits: 7,
+                    owners: vec![
+                        Owner {
+                            name: "Ann".to_string(),
+                            email: "1+ann@users.noreply.github.com".to_string(),
+                            login: Some("ann".to_string()),
+                            score: 62.5,
+                            lines: 90,
+                            commits: 4,
+                            last_commit: Some("2024-03-01".to_string()),
+                        },
+                        Owner {
+                            name: "Bob".to_string(),
+                            email: "bob@example.com".to_string(),
+                            login: None,
+                            score: 37.5,
+                            lines: 30,
+                            commits: 3,
+                            last_commit: None,
+                        },
+                    ],
+                },
+                AreaOwnership {
+                    area: "src/vendor".to_string(),
+                    files: 1,
+                    lines: 0,
+                    commits: 0,
+                    owners: vec![],
+                },
+            ],
+        };
+        assert_eq!(
+            report.render_text().unwrap(),
+            "# Code ownership of `src`\n\n\
+             | Area | Files | Lines | Commits | Owners |\n\
+             |------|------:|------:|--------:|--------|\n\
+             | `src/git` | 3 | 120 | 7 | Ann (@ann) 62.5%, Bob 37.5% |\n\
+             | `src/vendor` | 1 | 0 | 0 | — |"
+        );
+    }
+}
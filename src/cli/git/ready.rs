@@ -0,0 +1,337 @@
+//! Ready command — takes the current branch's draft pull request to ready
+//! for review: re-checks the commits, refreshes a stale description, flips
+//! the draft flag, and requests reviewers.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use tracing::warn;
+
+use super::create_pr::{generated_head, CreatePrCommand, GeneratedPr};
+use super::defaults::AutoApply;
+use super::merge_pr::{ensure_pushed, pr_commit_range};
+use super::update_pr::select_pr;
+use crate::data::RepositoryView;
+use crate::git::GitRepository;
+
+/// Branch ready command options.
+#[derive(Parser)]
+pub struct ReadyCommand {
+    /// PR number to mark ready (defaults to the open PR for the current branch).
+    #[arg(long, value_name = "N")]
+    pub number: Option<u64>,
+
+    /// Treats commit check warnings as blocking, like `check --strict`.
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Skips requesting reviewers suggested from CODEOWNERS and recent blame.
+    #[arg(long)]
+    pub no_reviewers: bool,
+
+    #[command(flatten)]
+    pub apply: AutoApply,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+}
+
+impl ReadyCommand {
+    /// Executes the ready command.
+    ///
+    /// Refuses to continue when the local branch is unpushed or differs from
+    /// the pushed one, or the commit check reports errors. The description is regenerated
+    /// only when the branch head moved since it was last generated.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let ai_info = crate::utils::check_pr_command_prerequisites(None, repo_root).await?;
+        println!(
+            "✓ {} credentials verified (model: {})",
+            ai_info.provider, ai_info.model
+        );
+        let forge = crate::forge::Forge::detect(repo_root);
+        let forge_api = forge.api_client()?;
+        if forge_api.is_some() {
+            println!("✓ {} API access verified", forge.name());
+        } else {
+            println!("✓ GitHub CLI verified");
+        }
+
+        let create_cmd = CreatePrCommand {
+            base: None,
+            apply: self.apply,
+            save_only: None,
+            ready: true,
+            draft: false,
+            context_dir: self.context_dir.clone(),
+            no_push: true,
+            from_commits: false,
+            no_reviewers: self.no_reviewers,
+            no_labels: true,
+            fixes: Vec::new(),
+            paths: Vec::new(),
+        };
+
+        let mut repo_view = create_cmd.generate_repository_view(repo_root).await?;
+        let branch = repo_view
+            .branch_info
+            .as_ref()
+            .map(|bi| bi.branch.clone())
+            .context("Branch info not available")?;
+        let target = select_pr(
+            repo_view.branch_prs.as_deref().unwrap_or_default(),
+            self.number,
+            &branch,
+        )?;
+        // The update helpers act on the first listed PR.
+        repo_view.branch_prs = Some(vec![target.clone()]);
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        ensure_pushed(&git_repo, &branch)?;
+        let range = pr_commit_range(&git_repo, &target.base)?;
+        println!("🔍 Checking commits in {range}...");
+        let outcome = super::run_check(&range, None, Some(repo_root), self.strict, None).await?;
+        if outcome.exit_code != 0 {
+            println!("{}", outcome.report_yaml);
+            bail!(
+                "Commit check failed for PR #{}; fix the reported commits \
+                 (e.g. with 'omni-dev git commit message twiddle') before marking it ready.",
+                target.number
+            );
+        }
+        println!(
+            "✓ {} commit(s) passed the commit check",
+            outcome.total_commits
+        );
+
+        let regenerated = if description_is_stale(&target.body, &repo_view) {
+            println!(
+                "🔄 Commits changed since the description of PR #{} was generated; regenerating...",
+                target.number
+            );
+            let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+            let (generated, _claude_client) = create_cmd
+                .generate_pr_content_with_client_internal(repo_root, &repo_view, claude_client)
+                .await?;
+            let GeneratedPr {
+                content,
+                used_fallback,
+            } = generated;
+            if used_fallback {
+                bail!(
+                    "AI PR generation failed; PR #{} was left as a draft.",
+                    target.number
+                );
+            }
+            println!("\n📋 Title: {}", content.title);
+            println!("📝 Description:\n{}\n", content.description);
+            Some(content)
+        } else {
+            println!("✓ PR description is up to date with the branch");
+            None
+        };
+
+        let reviewers: Vec<String> = create_cmd
+            .suggest_reviewers(repo_root, &repo_view)
+            .into_iter()
+            .map(|r| r.login)
+            .collect();
+        if !reviewers.is_empty() {
+            println!("👥 Reviewers: {}", reviewers.join(", "));
+        }
+
+        if !self.apply.assumed() && !confirm_ready(target.number)? {
+            println!("❌ Marking PR ready cancelled by user");
+            return Ok(());
+        }
+
+        if let Some(content) = &regenerated {
+            match forge_api.as_deref() {
+                Some(api) => {
+                    create_cmd
+                        .update_forge_pr(
+                            api,
+                            &repo_view,
+                            &content.title,
+                            &content.description,
+                            None,
+                            &[],
+                        )
+                        .await?
+                }
+                None => create_cmd.update_github_pr(
+                    repo_root,
+                    &repo_view,
+                    &content.title,
+                    &content.description,
+                    None,
+                    &[],
+                )?,
+            }
+        }
+
+        match forge_api.as_deref() {
+            Some(api) => api
+                .mark_ready(target.number)
+                .await
+                .with_context(|| format!("Failed to mark PR #{} ready", target.number))?,
+            None => gh_mark_ready(repo_root, target.number)?,
+        }
+        println!("✅ Pull request #{} is ready for review", target.number);
+
+        if !reviewers.is_empty() {
+            // The PR is already ready, so a rejected reviewer is not fatal.
+            let result = match forge_api.as_deref() {
+                Some(api) => api.request_reviewers(target.number, &reviewers).await,
+                None => gh_request_reviewers(repo_root, target.number, &reviewers),
+            };
+            match result {
+                Ok(()) => println!("   👥 Reviewers requested: {}", reviewers.join(", ")),
+                Err(e) => {
+                    warn!("Failed to request reviewers: {e:#}");
+                    println!("⚠️  Could not request reviewers: {e:#}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether `body` was generated from a branch head other than the
+/// newest commit in `repo_view` (or carries no record of one).
+fn description_is_stale(body: &str, repo_view: &RepositoryView) -> bool {
+    generated_head(body) != repo_view.commits.last().map(|c| c.hash.as_str())
+}
+
+/// Marks PR `number` ready with `gh pr ready` (the fallback when no GitHub
+/// token resolves for the native API client).
+fn gh_mark_ready(repo_root: &std::path::Path, number: u64) -> Result<()> {
+    let number = number.to_string();
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        ["pr", "ready", number.as_str()],
+        "pr ready",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to mark PR #{number} ready: {}", error_msg.trim());
+    }
+    Ok(())
+}
+
+/// Requests `reviewers` on PR `number` with `gh pr edit`.
+fn gh_request_reviewers(
+    repo_root: &std::path::Path,
+    number: u64,
+    reviewers: &[String],
+) -> Result<()> {
+    let number = number.to_string();
+    let joined = reviewers.join(",");
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        [
+            "pr",
+            "edit",
+            number.as_str(),
+            "--add-reviewer",
+            joined.as_str(),
+        ],
+        "pr edit",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!("{}", error_msg.trim());
+    }
+    Ok(())
+}
+
+/// Asks whether to mark PR `number` ready for review.
+fn confirm_ready(number: u64) -> Result<bool> {
+    use std::io::{self, Write};
+
+    print!(
+        "❓ {} ",
+        crate::utils::i18n::tr("confirm-ready", &[("number", &number)])
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(crate::utils::i18n::is_yes(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::data::{AiInfo, FieldExplanation, WorkingDirectoryInfo};
+    use crate::git::commit::FileChanges;
+    use crate::git::{CommitAnalysis, CommitInfo};
+
+    fn repo_view(hashes: &[&str]) -> RepositoryView {
+        RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
+            versions: None,
+            explanation: FieldExplanation::default(),
+            working_directory: WorkingDirectoryInfo {
+                clean: true,
+                untracked_changes: vec![],
+            },
+            remotes: vec![],
+            ai: AiInfo {
+                scratch: String::new(),
+            },
+            branch_info: None,
+            pr_template: None,
+            pr_template_location: None,
+            branch_prs: None,
+            commits: hashes
+                .iter()
+                .map(|hash| CommitInfo {
+                    hash: (*hash).to_string(),
+                    author: "Dev <dev@example.com>".to_string(),
+                    date: chrono::Utc::now().fixed_offset(),
+                    original_message: "feat: x".to_string(),
+                    in_main_branches: vec![],
+                    analysis: CommitAnalysis {
+                        detected_type: "feat".to_string(),
+                        detected_scope: String::new(),
+                        proposed_message: "feat: x".to_string(),
+                        file_changes: FileChanges {
+                            total_files: 0,
+                            files_added: 0,
+                            files_deleted: 0,
+                            file_list: vec![],
+                        },
+                        diff_summary: String::new(),
+                        diff_file: String::new(),
+                        file_diffs: vec![],
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn description_is_stale_when_head_moved() {
+        let body = "## Summary\n\n<!-- omni-dev-head: bbbb -->\n";
+        assert!(!description_is_stale(body, &repo_view(&["aaaa", "bbbb"])));
+        assert!(description_is_stale(
+            body,
+            &repo_view(&["aaaa", "bbbb", "cccc"])
+        ));
+        assert!(description_is_stale("## Summary\n", &repo_view(&["aaaa"])));
+    }
+}
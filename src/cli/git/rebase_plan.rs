@@ -0,0 +1,280 @@
+//! `omni-dev git rebase plan` — an AI-written `git rebase -i` todo list that
+//! reorders, melds, and rewords a range of commits.
+//!
+//! The plan is printed (and optionally saved) for review; with `--apply` the
+//! rebase is started with the plan as its todo list, through
+//! `GIT_SEQUENCE_EDITOR`, so the only editors that open are the ones
+//! `reword` and `squash` need.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+
+use super::formatting::truncate_hash;
+use super::release_notes::strip_markdown_fence;
+use crate::git::rebase_todo::{RebaseTodo, Step};
+use crate::git::{CommitInfo, GitRepository};
+
+/// `omni-dev git rebase plan` CLI command.
+#[derive(Parser)]
+pub struct RebasePlanCommand {
+    /// Commit range to plan (defaults to the commits since origin/main,
+    /// origin/master, main, or master).
+    #[arg(value_name = "RANGE")]
+    pub range: Option<String>,
+
+    /// Also writes the todo list to FILE.
+    #[arg(long, value_name = "FILE")]
+    pub save: Option<PathBuf>,
+
+    /// Starts `git rebase -i` with the plan as its todo list.
+    #[arg(long)]
+    pub apply: bool,
+
+    /// Allows applying a plan to commits that already exist in remote main branches (rewrites published history).
+    #[arg(long, requires = "apply")]
+    pub allow_pushed: bool,
+}
+
+/// The AI's response.
+#[derive(Deserialize)]
+struct PlanResponse {
+    #[serde(default)]
+    steps: Vec<Step>,
+}
+
+impl RebasePlanCommand {
+    /// Executes the rebase plan command.
+    ///
+    /// The todo list goes to stdout and progress to stderr. With `--apply`,
+    /// the checks that the rebase can run in place happen before the AI is
+    /// asked, so a plan is never generated only to be refused.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let range = match &self.range {
+            Some(range) => range.clone(),
+            None => super::default_commit_range(&git_repo)?,
+        };
+        let commits = git_repo
+            .get_commits_in_range(&range)
+            .with_context(|| format!("Failed to read commits in {range}"))?;
+        let Some(oldest) = commits.first() else {
+            bail!("No commits in {range}; nothing to plan");
+        };
+        if self.apply {
+            check_can_apply(&git_repo, repo_root, &range, &commits, self.allow_pushed)?;
+        }
+
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+
+        eprintln!(
+            "🤖 Planning a rebase of {} commit(s) in {range}...",
+            commits.len()
+        );
+        let original = RebaseTodo::picks(commits.iter().map(|c| (c.hash.as_str(), subject(c))));
+        let plan = plan_with_client(&claude_client, &range, &commits, &original).await?;
+        let todo = format!(
+            "# Rebase plan for {range}, oldest commit first.\n{}",
+            plan.render()
+        );
+        print!("{todo}");
+
+        if let Some(path) = &self.save {
+            std::fs::write(path, &todo)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("💾 Saved the plan to {}", path.display());
+        }
+        if plan.is_noop(&original) {
+            eprintln!("✅ The history already reads well; the plan picks every commit in place.");
+            return Ok(());
+        }
+        if !self.apply {
+            eprintln!("💡 Re-run with --apply to start the rebase with this plan.");
+            return Ok(());
+        }
+
+        eprintln!("🔀 Starting git rebase -i with the plan...");
+        git_repo.rebase_with_todo(&oldest.hash, &todo)?;
+        eprintln!("✅ Rebase complete");
+        Ok(())
+    }
+}
+
+/// Asks the AI for a plan and checks it against the commits.
+pub(crate) async fn plan_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    range: &str,
+    commits: &[CommitInfo],
+    original: &RebaseTodo,
+) -> Result<RebaseTodo> {
+    let user =
+        crate::claude::prompts::generate_rebase_plan_user_prompt(range, &format_commits(commits));
+    let response = claude_client
+        .send_message(crate::claude::prompts::REBASE_PLAN_SYSTEM_PROMPT, &user)
+        .await?;
+    let response: PlanResponse = serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned a rebase plan that is not valid YAML")?;
+    original
+        .replan(&response.steps)
+        .context("AI returned a rebase plan that cannot be applied")
+}
+
+/// Refuses `--apply` unless the rebase can run in place: a clean working
+/// directory, a range ending at `HEAD`, and no pushed commits without
+/// `allow_pushed`.
+fn check_can_apply(
+    git_repo: &GitRepository,
+    repo_root: &std::path::Path,
+    range: &str,
+    commits: &[CommitInfo],
+    allow_pushed: bool,
+) -> Result<()> {
+    crate::utils::preflight::check_working_directory_clean_at(repo_root)
+        .context("Cannot rebase with uncommitted changes")?;
+    if let Some(operation) = git_repo.operation_in_progress() {
+        bail!("Cannot rebase while a {operation} is in progress");
+    }
+
+    let head = git_repo
+        .repository()
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .context("Failed to resolve HEAD")?
+        .id()
+        .to_string();
+    if commits.last().map(|c| c.hash.as_str()) != Some(head.as_str()) {
+        bail!("--apply rebases the current branch, so {range} must end at HEAD");
+    }
+
+    let pushed: Vec<&str> = commits
+        .iter()
+        .filter(|c| !c.in_main_branches.is_empty())
+        .map(|c| truncate_hash(&c.hash))
+        .collect();
+    if !pushed.is_empty() {
+        let message = format!(
+            "commit(s) {} already exist in remote main branches",
+            pushed.join(", ")
+        );
+        if !allow_pushed {
+            bail!(
+                "Refusing to rebase: {message}.\n\
+                 Rebasing pushed commits rewrites published history. Re-run with \
+                 --allow-pushed to override."
+            );
+        }
+        eprintln!("⚠️  Rebasing {message} (--allow-pushed)");
+    }
+    Ok(())
+}
+
+/// The first line of the commit's message.
+fn subject(commit: &CommitInfo) -> &str {
+    commit.original_message.lines().next().unwrap_or("").trim()
+}
+
+/// Lists the commits (oldest first) with their full messages and the files
+/// each one touches.
+fn format_commits(commits: &[CommitInfo]) -> String {
+    let mut out = String::new();
+    for commit in commits {
+        let _ = writeln!(out, "- {} {}", truncate_hash(&commit.hash), subject(commit));
+        for line in commit.original_message.lines().skip(1) {
+            let line = line.trim_end();
+            if !line.is_empty() {
+                let _ = writeln!(out, "    {line}");
+            }
+        }
+        let files: Vec<&str> = commit
+            .analysis
+            .file_changes
+            .file_list
+            .iter()
+            .map(|f| f.file.as_str())
+            .collect();
+        if !files.is_empty() {
+            let _ = writeln!(out, "  files: {}", files.join(", "));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::git::rebase_todo::Action;
+    use crate::test_support::commits::commit;
+
+    fn commits() -> Vec<CommitInfo> {
+        vec![
+            commit(
+                "aaaaaaaa11111111",
+                "feat(parser): add parser\n\nHandles nested lists.",
+                &["src/parser.rs"],
+            ),
+            commit("bbbbbbbb22222222", "wip", &["src/cli.rs"]),
+            commit("cccccccc33333333", "fix typo", &["src/parser.rs"]),
+        ]
+    }
+
+    #[test]
+    fn commit_list_has_messages_and_files() {
+        assert_eq!(
+            format_commits(&commits()[..2]),
+            "- aaaaaaaa feat(parser): add parser\n    Handles nested lists.\n  \
+             files: src/parser.rs\n- bbbbbbbb wip\n  files: src/cli.rs\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn plan_comes_from_ai_steps() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok("```yaml\nsteps:\n  \
+             - action: pick\n    commit: aaaaaaaa\n  \
+             - action: fixup\n    commit: cccccccc\n    reason: Typo in the parser.\n  \
+             - action: reword\n    commit: bbbbbbbb\n    reason: Says nothing.\n```"
+            .to_string())]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+        let commits = commits();
+        let original = RebaseTodo::picks(commits.iter().map(|c| (c.hash.as_str(), subject(c))));
+
+        let plan = plan_with_client(&client, "main..HEAD", &commits, &original)
+            .await
+            .unwrap();
+        let actions: Vec<Action> = plan.entries.iter().map(|e| e.action).collect();
+        assert_eq!(actions, [Action::Pick, Action::Fixup, Action::Reword]);
+        assert_eq!(plan.entries[1].hash, "cccccccc33333333");
+        let sent = prompts.prompts();
+        let user = &sent[0].1;
+        assert!(user.contains("`main..HEAD`") && user.contains("- bbbbbbbb wip"));
+    }
+
+    #[tokio::test]
+    async fn plans_that_drop_commits_are_rejected() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "steps:\n  - action: pick\n    commit: aaaaaaaa\n".to_string(),
+        )]);
+        let client = ClaudeClient::new(Box::new(mock));
+        let commits = commits();
+        let original = RebaseTodo::picks(commits.iter().map(|c| (c.hash.as_str(), subject(c))));
+
+        let err = plan_with_client(&client, "main..HEAD", &commits, &original)
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("leaves out commit(s) bbbbbbbb, cccccccc"));
+    }
+}
@@ -0,0 +1,304 @@
+//! Release create command — computes the next version from the commits since
+//! the last release tag, generates release notes, tags HEAD, pushes the tag,
+//! and publishes the release on the forge.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use super::defaults::AutoApply;
+use super::release_notes::generate_release_notes;
+use crate::forge::{Forge, PullRequestApi};
+use crate::git::release::{bump_level, latest_version_tag, release_entry};
+use crate::git::{GitRepository, ReleaseVersion};
+
+/// Remote the release tag is pushed to.
+const REMOTE: &str = "origin";
+
+/// Release create command options.
+#[derive(Parser)]
+pub struct ReleaseCreateCommand {
+    /// Version to release (defaults to the next semantic version computed from the commits).
+    #[arg(long, value_name = "VERSION")]
+    pub version: Option<String>,
+
+    /// Ref of the previous release (defaults to the highest version tag).
+    #[arg(long, value_name = "REF")]
+    pub from: Option<String>,
+
+    /// Marks the release as a pre-release.
+    #[arg(long)]
+    pub prerelease: bool,
+
+    /// Uses the grouped commit list as the notes without an AI summary.
+    #[arg(long)]
+    pub no_ai: bool,
+
+    #[command(flatten)]
+    pub apply: AutoApply,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+}
+
+impl ReleaseCreateCommand {
+    /// Executes the release create command.
+    ///
+    /// Nothing is tagged or pushed until the notes are generated and the
+    /// release is confirmed.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let forge = Forge::detect(repo_root);
+        if matches!(forge, Forge::Bitbucket(_)) {
+            bail!("Releases are not supported for Bitbucket");
+        }
+        let forge_api = forge.api_client()?;
+        if forge_api.is_some() {
+            println!("✓ {} API access verified", forge.name());
+        } else {
+            println!("✓ GitHub CLI verified");
+        }
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let previous = latest_version_tag(git_repo.repository())?;
+        let from = match (&self.from, &previous) {
+            (Some(from), _) => from.clone(),
+            (None, Some((tag, _))) => tag.clone(),
+            (None, None) => {
+                bail!("No previous release tag found; pass --from <REF> and --version <VERSION>")
+            }
+        };
+        let range = format!("{from}..HEAD");
+        let commits = git_repo
+            .get_commits_in_range(&range)
+            .with_context(|| format!("Failed to read commits in {range}"))?;
+        if commits.is_empty() {
+            bail!("No commits since {from}; nothing to release");
+        }
+
+        let version = match &self.version {
+            Some(version) => ReleaseVersion::parse(version).with_context(|| {
+                format!("Invalid version '{version}'; expected MAJOR.MINOR.PATCH")
+            })?,
+            None => {
+                let Some((_, current)) = previous else {
+                    bail!("No previous release tag to compute the version from; pass --version");
+                };
+                let entries: Vec<_> = commits.iter().map(release_entry).collect();
+                current.bump(bump_level(&entries, current))
+            }
+        };
+        let tag = format!("v{version}");
+        if git_repo
+            .repository()
+            .refname_to_id(&format!("refs/tags/{tag}"))
+            .is_ok()
+        {
+            bail!("Tag {tag} already exists");
+        }
+
+        println!(
+            "📦 Releasing {tag} with {} commit(s) since {from}",
+            commits.len()
+        );
+        let notes =
+            generate_release_notes(repo_root, &range, self.context_dir.as_deref(), !self.no_ai)
+                .await?;
+        println!("\n{notes}\n");
+
+        if !self.apply.destructive()? && !confirm_release(&tag)? {
+            println!("❌ Release cancelled by user");
+            return Ok(());
+        }
+
+        create_tag(git_repo.repository(), &tag)?;
+        println!("🏷️  Tagged HEAD as {tag}");
+        if let Err(e) = git_repo.push_tag(&tag, REMOTE) {
+            roll_back_tag(&git_repo, &tag, false);
+            return Err(e);
+        }
+        println!("⬆️  Pushed {tag} to {REMOTE}");
+
+        let published = match forge_api.as_deref() {
+            Some(api) => create_forge_release(api, &tag, &notes, self.prerelease).await,
+            None => gh_create_release(repo_root, &tag, &notes, self.prerelease),
+        };
+        let url = match published {
+            Ok(url) => url,
+            Err(e) => {
+                roll_back_tag(&git_repo, &tag, true);
+                return Err(e);
+            }
+        };
+        println!("✅ Published release {tag}: {url}");
+        Ok(())
+    }
+}
+
+/// Creates an annotated tag `tag` at HEAD.
+fn create_tag(repo: &git2::Repository, tag: &str) -> Result<()> {
+    let head = repo
+        .head()
+        .and_then(|h| h.peel(git2::ObjectType::Commit))
+        .context("Failed to resolve HEAD")?;
+    let signature = repo
+        .signature()
+        .context("Failed to read the git user name and email for the tag")?;
+    repo.tag(tag, &head, &signature, &format!("Release {tag}"), false)
+        .with_context(|| format!("Failed to create tag {tag}"))?;
+    Ok(())
+}
+
+/// Removes `tag` after its push or publish failed — from the remote as well
+/// once it was `pushed` — so rerunning the release starts over cleanly.
+/// Failures here are only reported, leaving the original error to surface.
+fn roll_back_tag(git_repo: &GitRepository, tag: &str, pushed: bool) {
+    if pushed {
+        if let Err(e) = git_repo.delete_remote_tag(tag, REMOTE) {
+            eprintln!("warning: failed to delete {tag} from {REMOTE}: {e:#}");
+            return;
+        }
+    }
+    match delete_tag(git_repo.repository(), tag) {
+        Ok(()) => println!("↩️  Removed tag {tag}"),
+        Err(e) => eprintln!("warning: {e:#}"),
+    }
+}
+
+/// Deletes the local tag `tag`.
+fn delete_tag(repo: &git2::Repository, tag: &str) -> Result<()> {
+    repo.tag_delete(tag)
+        .with_context(|| format!("Failed to delete tag {tag}"))
+}
+
+/// Publishes the release for `tag` through a forge REST API.
+async fn create_forge_release(
+    api: &dyn PullRequestApi,
+    tag: &str,
+    notes: &str,
+    prerelease: bool,
+) -> Result<String> {
+    println!("🚀 Publishing release via {} API...", api.forge_name());
+    api.create_release(tag, tag, notes, prerelease)
+        .await
+        .with_context(|| format!("Failed to publish release {tag}"))
+}
+
+/// Publishes the release for `tag` with `gh release create`.
+fn gh_create_release(
+    repo_root: &std::path::Path,
+    tag: &str,
+    notes: &str,
+    prerelease: bool,
+) -> Result<String> {
+    println!("🚀 Publishing release via gh...");
+    let output = crate::github_metrics::run_gh(
+        &crate::pr_status::resolve_gh_binary(),
+        gh_release_args(tag, notes, prerelease),
+        "release create",
+        Some(repo_root),
+    )
+    .context("Failed to execute gh command")?;
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        bail!("Failed to publish release {tag}: {}", error_msg.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Builds the `gh release create` arguments. `--verify-tag` makes gh fail
+/// rather than create the tag itself if the push did not land.
+fn gh_release_args(tag: &str, notes: &str, prerelease: bool) -> Vec<String> {
+    let mut args: Vec<String> = [
+        "release",
+        "create",
+        tag,
+        "--title",
+        tag,
+        "--notes",
+        notes,
+        "--verify-tag",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect();
+    if prerelease {
+        args.push("--prerelease".to_string());
+    }
+    args
+}
+
+/// Asks whether to tag, push, and publish `tag`.
+fn confirm_release(tag: &str) -> Result<bool> {
+    use std::io::{self, Write};
+
+    let args: [(&str, &dyn std::fmt::Display); 2] = [("tag", &tag), ("remote", &REMOTE)];
+    print!("❓ {} ", crate::utils::i18n::tr("confirm-release", &args));
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(crate::utils::i18n::is_yes(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gh_release_args_verify_the_pushed_tag() {
+        assert_eq!(
+            gh_release_args("v1.2.0", "notes", true),
+            vec![
+                "release",
+                "create",
+                "v1.2.0",
+                "--title",
+                "v1.2.0",
+                "--notes",
+                "notes",
+                "--verify-tag",
+                "--prerelease",
+            ]
+        );
+        assert!(!gh_release_args("v1.2.0", "notes", false).contains(&"--prerelease".to_string()));
+    }
+
+    #[test]
+    fn creates_annotated_tag_at_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Dev").unwrap();
+        config.set_str("user.email", "dev@example.com").unwrap();
+        let sig = repo.signature().unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let head = repo
+            .commit(Some("HEAD"), &sig, &sig, "feat: x", &tree, &[])
+            .unwrap();
+
+        create_tag(&repo, "v1.0.0").unwrap();
+        let tag = repo
+            .find_reference("refs/tags/v1.0.0")
+            .unwrap()
+            .peel_to_tag()
+            .unwrap();
+        assert_eq!(tag.target_id(), head);
+        assert_eq!(tag.message().map(str::trim), Some("Release v1.0.0"));
+        assert!(create_tag(&repo, "v1.0.0").is_err());
+
+        // A rolled-back tag can be created again on the next attempt.
+        delete_tag(&repo, "v1.0.0").unwrap();
+        assert!(repo.find_reference("refs/tags/v1.0.0").is_err());
+        create_tag(&repo, "v1.0.0").unwrap();
+    }
+}
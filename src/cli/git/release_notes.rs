@@ -0,0 +1,218 @@
+//! Release notes command — groups the commits in a range by conventional
+//! type and scope, has the AI summarise them (following the project's
+//! `release-notes.md` template when present), and prints markdown ready for a
+//! GitHub Release body.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::Serialize;
+
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::{group_commits, render_groups_markdown, GitRepository};
+use crate::utils::env::SystemEnv;
+
+/// Release notes template looked up through the `.omni-dev/` resolution chain.
+pub(crate) const RELEASE_NOTES_TEMPLATE_FILE: &str = "release-notes.md";
+
+/// Release notes command options.
+#[derive(Parser)]
+pub struct ReleaseNotesCommand {
+    /// Commit range to describe, e.g. v1.2.0..v1.3.0 (a single ref means <ref>..HEAD).
+    #[arg(value_name = "RANGE")]
+    pub range: String,
+
+    /// Prints the grouped commit list without an AI summary.
+    #[arg(long)]
+    pub no_ai: bool,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+}
+
+impl ReleaseNotesCommand {
+    /// Executes the release notes command.
+    ///
+    /// The notes go to stdout and progress to stderr, so the output can be
+    /// piped straight into `gh release create --notes-file -`.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let range = normalize_range(&self.range)?;
+        let notes =
+            generate_release_notes(repo_root, &range, self.context_dir.as_deref(), !self.no_ai)
+                .await?;
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&ReleaseNotes { range, notes }, format)?);
+        Ok(())
+    }
+}
+
+/// Release notes as printed, with the range they cover for `--output yaml|json`.
+#[derive(Serialize)]
+struct ReleaseNotes {
+    range: String,
+    notes: String,
+}
+
+impl Render for ReleaseNotes {
+    fn render_text(&self) -> Result<String> {
+        Ok(self.notes.clone())
+    }
+}
+
+/// Expands a single ref to `<ref>..HEAD` and an open-ended `<ref>..` likewise.
+pub(crate) fn normalize_range(range: &str) -> Result<String> {
+    let range = range.trim();
+    match range.split_once("..") {
+        None if range.is_empty() => bail!("Release range must not be empty"),
+        None => Ok(format!("{range}..HEAD")),
+        Some(("", _)) => bail!("Release range '{range}' has no start; use e.g. v1.2.0..HEAD"),
+        Some((from, "")) => Ok(format!("{from}..HEAD")),
+        Some(_) => Ok(range.to_string()),
+    }
+}
+
+/// Generates release notes markdown for `range`.
+///
+/// With `use_ai` the grouped commits are summarised by the AI, following the
+/// project's release notes template when one exists; otherwise the grouped
+/// list is returned as is.
+pub(crate) async fn generate_release_notes(
+    repo_root: &std::path::Path,
+    range: &str,
+    context_dir: Option<&std::path::Path>,
+    use_ai: bool,
+) -> Result<String> {
+    let git_repo = GitRepository::open_at(repo_root)
+        .context("Failed to open git repository at the given path")?;
+    let commits = git_repo
+        .get_commits_in_range(range)
+        .with_context(|| format!("Failed to read commits in {range}"))?;
+    if commits.is_empty() {
+        bail!("No commits in {range}");
+    }
+    let grouped = render_groups_markdown(&group_commits(&commits));
+    if !use_ai {
+        return Ok(grouped);
+    }
+
+    crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+    let context_dir = crate::claude::context::resolve_context_dir_at(context_dir, repo_root);
+    let template =
+        crate::claude::context::load_config_content(&context_dir, RELEASE_NOTES_TEMPLATE_FILE)?;
+    let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+
+    eprintln!("🤖 Summarising {} commit(s) in {range}...", commits.len());
+    release_notes_with_client(&claude_client, range, &grouped, template.as_deref()).await
+}
+
+/// Asks the AI to turn the grouped commit list into release notes.
+pub(crate) async fn release_notes_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    range: &str,
+    grouped: &str,
+    template: Option<&str>,
+) -> Result<String> {
+    let user = crate::claude::prompts::generate_release_notes_user_prompt(range, grouped, template);
+    let response = claude_client
+        .send_message(crate::claude::prompts::RELEASE_NOTES_SYSTEM_PROMPT, &user)
+        .await?;
+    let notes = strip_markdown_fence(response.trim());
+    if notes.is_empty() {
+        bail!("AI returned empty release notes");
+    }
+    Ok(notes.to_string())
+}
+
+/// Removes a ```` ```markdown ```` fence wrapped around the whole response.
+pub(crate) fn strip_markdown_fence(text: &str) -> &str {
+    let Some(rest) = text.strip_prefix("```") else {
+        return text;
+    };
+    let Some(body) = rest.strip_suffix("```") else {
+        return text;
+    };
+    body.split_once('\n').map_or(text, |(_, body)| body.trim())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+
+    #[test]
+    fn normalizes_ranges() {
+        assert_eq!(normalize_range("v1.2.0").unwrap(), "v1.2.0..HEAD");
+        assert_eq!(normalize_range("v1.2.0..").unwrap(), "v1.2.0..HEAD");
+        assert_eq!(normalize_range("v1.2.0..v1.3.0").unwrap(), "v1.2.0..v1.3.0");
+        assert!(normalize_range("..v1.3.0").is_err());
+        assert!(normalize_range(" ").is_err());
+    }
+
+    #[test]
+    fn strips_wrapping_markdown_fence() {
+        assert_eq!(
+            strip_markdown_fence("```markdown\n### Features\n```"),
+            "### Features"
+        );
+        assert_eq!(strip_markdown_fence("### Features"), "### Features");
+    }
+
+    #[tokio::test]
+    async fn release_notes_come_from_ai_response() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "```markdown\nAdds release notes.\n\n### Features\n\n- add notes (bbbbbbbb)\n```"
+                .to_string(),
+        )]);
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let notes = release_notes_with_client(
+            &client,
+            "v1.0.0..HEAD",
+            "### Features\n\n- add notes (bbbbbbbb)\n",
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(notes.starts_with("Adds release notes."));
+        assert!(!notes.contains("```"));
+    }
+
+    #[tokio::test]
+    async fn generates_grouped_notes_without_ai() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let base = repo
+            .commit(Some("HEAD"), &sig, &sig, "chore: init", &tree, &[])
+            .unwrap();
+        repo.tag_lightweight("v1.0.0", &repo.find_object(base, None).unwrap(), false)
+            .unwrap();
+        let base = repo.find_commit(base).unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "feat(git): add release notes",
+            &tree,
+            &[&base],
+        )
+        .unwrap();
+
+        let notes = generate_release_notes(dir.path(), "v1.0.0..HEAD", None, false)
+            .await
+            .unwrap();
+        assert!(notes.starts_with("### Features\n\n- **git:** add release notes ("));
+        assert!(!notes.contains("init"));
+    }
+}
@@ -0,0 +1,205 @@
+//! `omni-dev git commit review docs` — flags changes to public APIs and CLI
+//! flags that leave the documentation behind.
+//!
+//! Path and line heuristics find the user-facing changes and the documentation
+//! the diff touches; the AI then decides which changes need a documentation
+//! update the diff does not make. Findings are warnings, so like `check` the
+//! command only fails with `--strict`.
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+
+use super::release_notes::strip_markdown_fence;
+use super::review::{print_report, FindingCategory, ReviewFinding, ReviewReport};
+use super::staged::read_target_diff;
+use crate::data::check::IssueSeverity;
+use crate::git::doc_drift::{tracked_doc_files, DocDrift, SurfaceKind};
+use crate::utils::redact::redact_secrets;
+
+/// Most documentation files listed in the prompt.
+const MAX_DOC_FILES: usize = 50;
+
+/// `omni-dev git commit review docs` CLI command.
+#[derive(Parser)]
+pub struct ReviewDocsCommand {
+    /// Commit range to check (e.g. origin/main..HEAD; a single ref means <ref>..HEAD), or `staged` for the staged changes (defaults to the branch against its base).
+    #[arg(value_name = "RANGE|staged")]
+    pub target: Option<String>,
+
+    /// Skips the AI pass: reports every public API or CLI change when the diff touches no documentation.
+    #[arg(long)]
+    pub no_ai: bool,
+
+    /// Treats warnings as blocking: exits with code 2 when any are found.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+/// One drifting change, as the AI reports it.
+#[derive(Deserialize)]
+struct DriftFinding {
+    file: String,
+    #[serde(default)]
+    line: Option<u32>,
+    message: String,
+}
+
+/// The AI's response.
+#[derive(Deserialize)]
+struct DriftResponse {
+    #[serde(default)]
+    findings: Vec<DriftFinding>,
+}
+
+impl ReviewDocsCommand {
+    /// Executes the review docs command, exiting with code 2 under
+    /// `--strict` when documentation drift is found.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let diff = read_target_diff(repo_root, self.target.as_deref())?;
+        let drift = DocDrift::from_diff(&diff);
+        let subject = match self.target.as_deref() {
+            Some("staged") => "the staged changes".to_string(),
+            Some(range) => range.to_string(),
+            None => "the branch".to_string(),
+        };
+
+        let findings = if self.no_ai || drift.surface.is_empty() {
+            heuristic_findings(&drift)
+        } else {
+            crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+            let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+            let mut doc_files = tracked_doc_files(repo_root)?;
+            doc_files.truncate(MAX_DOC_FILES);
+            eprintln!(
+                "📚 Checking {} user-facing change(s) against the documentation...",
+                drift.surface.len()
+            );
+            review_with_client(&claude_client, &drift, &doc_files, &diff).await?
+        };
+        print_report(&ReviewReport { subject, findings }, self.strict)
+    }
+}
+
+/// A warning for every surface change when the diff touches no
+/// documentation, for `--no-ai`.
+fn heuristic_findings(drift: &DocDrift) -> Vec<ReviewFinding> {
+    if !drift.is_suspect() {
+        return Vec::new();
+    }
+    drift
+        .surface
+        .iter()
+        .map(|change| ReviewFinding {
+            category: FindingCategory::DocDrift,
+            severity: IssueSeverity::Warning,
+            file: change.file.clone(),
+            line: change.line,
+            message: format!(
+                "`{}` changes {} without a documentation update",
+                change.text,
+                match change.kind {
+                    SurfaceKind::PublicApi => "the public API",
+                    SurfaceKind::CliFlag => "the command line",
+                }
+            ),
+        })
+        .collect()
+}
+
+/// Asks the AI which of the surface changes in `drift` need documentation the
+/// diff does not update, as doc-drift warnings.
+pub(crate) async fn review_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    drift: &DocDrift,
+    doc_files: &[String],
+    diff: &str,
+) -> Result<Vec<ReviewFinding>> {
+    let user = crate::claude::prompts::generate_doc_drift_user_prompt(
+        &crate::data::to_yaml(drift)?,
+        doc_files,
+        &redact_secrets(diff),
+    );
+    let response = claude_client
+        .send_message(crate::claude::prompts::DOC_DRIFT_SYSTEM_PROMPT, &user)
+        .await?;
+    let response: DriftResponse = serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned documentation drift findings that are not valid YAML")?;
+    Ok(response
+        .findings
+        .into_iter()
+        .map(|finding| ReviewFinding {
+            category: FindingCategory::DocDrift,
+            severity: IssueSeverity::Warning,
+            file: finding.file,
+            line: finding.line,
+            message: finding.message,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+
+    const DIFF: &str = "--- a/src/cli.rs\n\
+        +++ b/src/cli.rs\n\
+        @@ -4,2 +4,4 @@ pub struct Cli {\n \
+        verbose: bool,\n\
+        +    #[arg(long)]\n\
+        +    dry_run: bool,\n";
+
+    #[test]
+    fn heuristic_warns_only_without_doc_changes() {
+        let findings = heuristic_findings(&DocDrift::from_diff(DIFF));
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].category, FindingCategory::DocDrift);
+        assert_eq!(findings[0].line, Some(5));
+        assert_eq!(
+            findings[0].message,
+            "`+#[arg(long)]` changes the command line without a documentation update"
+        );
+
+        let documented = format!("{DIFF}--- a/README.md\n+++ b/README.md\n@@ -1 +1 @@\n+x\n");
+        assert!(heuristic_findings(&DocDrift::from_diff(&documented)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn ai_findings_become_doc_drift_warnings() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok("findings:\n  \
+            - file: src/cli.rs\n    line: 5\n    \
+            message: New `--dry-run` flag is not in docs/user-guide.md.\n"
+            .to_string())]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+        let drift = DocDrift::from_diff(DIFF);
+        let findings =
+            review_with_client(&client, &drift, &["docs/user-guide.md".to_string()], DIFF)
+                .await
+                .unwrap();
+
+        let sent = prompts.prompts();
+        assert!(sent[0]
+            .1
+            .contains("=== DOCUMENTATION FILES ===\ndocs/user-guide.md\n"));
+        assert!(sent[0].1.contains("kind: cli-flag"));
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, IssueSeverity::Warning);
+        let report = ReviewReport {
+            subject: "the branch".to_string(),
+            findings,
+        };
+        assert_eq!(report.exit_code(false), 0);
+        assert_eq!(report.exit_code(true), 2);
+    }
+}
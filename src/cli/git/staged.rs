@@ -11,6 +11,7 @@ use clap::Parser;
 use std::process::{Command, Stdio};
 
 use crate::data::context::ScopeDefinition;
+use crate::git::GitBackend;
 
 /// `omni-dev git commit message staged` CLI command.
 ///
@@ -132,45 +133,74 @@ pub(crate) async fn run_staged_with_client(
     })
 }
 
-/// Returns `true` if `git diff --cached --quiet` reports staged changes.
-///
-/// Exit codes per `git diff --quiet`:
-/// - `0` ⇒ no diff (nothing staged)
-/// - `1` ⇒ diff present (staged changes exist)
-/// - other ⇒ a real error (not in a repo, permission denied, etc.)
-fn has_staged_changes(repo_root: &std::path::Path) -> Result<bool> {
-    let output = Command::new("git")
-        .current_dir(repo_root)
-        .args(["diff", "--cached", "--quiet"])
-        .stdin(Stdio::null())
-        .env("GIT_TERMINAL_PROMPT", "0")
-        .output()
-        .context("Failed to execute git diff --cached --quiet")?;
-    match output.status.code() {
-        Some(0) => Ok(false),
-        Some(1) => Ok(true),
-        Some(code) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("git diff --cached --quiet exited with code {code}: {stderr}")
+/// Returns `true` if the index of the repository at `repo_root` differs from
+/// `HEAD` (what `git diff --cached --quiet` reports).
+pub(crate) fn has_staged_changes(repo_root: &std::path::Path) -> Result<bool> {
+    crate::git::GitRepository::open_at(repo_root)
+        .context("Failed to open git repository at the given path")?
+        .backend()
+        .has_staged_changes()
+}
+
+/// Reads the staged diff (`git diff --cached`), with generated and vendored
+/// files reduced to stubs and the configured size limits applied.
+pub(super) fn read_staged_diff(repo_root: &std::path::Path) -> Result<String> {
+    let diff = crate::git::GitRepository::open_at(repo_root)
+        .context("Failed to open git repository at the given path")?
+        .backend()
+        .staged_diff()?;
+    Ok(reduce_diff(repo_root, &diff))
+}
+
+/// Target naming the staged changes instead of a commit range.
+const STAGED_TARGET: &str = "staged";
+
+/// Reads the diff of a command's `[RANGE|staged]` target: the staged changes
+/// for `staged`, `<ref>..HEAD` for a single ref, and the branch against its
+/// base when `target` is `None`.
+pub(super) fn read_target_diff(
+    repo_root: &std::path::Path,
+    target: Option<&str>,
+) -> Result<String> {
+    let range = match target {
+        Some(STAGED_TARGET) => {
+            if !has_staged_changes(repo_root)? {
+                anyhow::bail!(
+                    "no staged changes — stage files with `git add` before running this command"
+                );
+            }
+            return read_staged_diff(repo_root);
+        }
+        Some(range) if range.contains("..") => range.to_string(),
+        Some(reference) => format!("{reference}..HEAD"),
+        None => {
+            let git_repo = crate::git::GitRepository::open_at(repo_root)
+                .context("Failed to open git repository at the given path")?;
+            super::default_commit_range(&git_repo)?
         }
-        None => anyhow::bail!("git diff --cached --quiet was terminated by a signal"),
+    };
+    let diff = read_range_diff(repo_root, &range)?;
+    if diff.trim().is_empty() {
+        anyhow::bail!("No changes in {range}");
     }
+    Ok(diff)
 }
 
-/// Reads the staged diff via `git diff --cached`.
-fn read_staged_diff(repo_root: &std::path::Path) -> Result<String> {
-    let output = Command::new("git")
-        .current_dir(repo_root)
-        .args(["diff", "--cached"])
-        .stdin(Stdio::null())
-        .env("GIT_TERMINAL_PROMPT", "0")
-        .output()
-        .context("Failed to execute git diff --cached")?;
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("git diff --cached failed: {stderr}");
-    }
-    String::from_utf8(output.stdout).context("git diff --cached produced non-UTF-8 output")
+/// Reads the diff of `range` (`git diff <range>`), with generated and
+/// vendored files reduced to stubs and the configured size limits applied.
+fn read_range_diff(repo_root: &std::path::Path, range: &str) -> Result<String> {
+    let diff = crate::git::GitRepository::open_at(repo_root)
+        .context("Failed to open git repository at the given path")?
+        .backend()
+        .range_diff(range)?;
+    Ok(reduce_diff(repo_root, &diff))
+}
+
+/// Reduces generated and vendored files in `diff` to stubs and applies the
+/// configured size limits.
+fn reduce_diff(repo_root: &std::path::Path, diff: &str) -> String {
+    let diff = crate::git::GeneratedFiles::for_repo(repo_root).strip_diff(diff);
+    crate::git::DiffLimits::from_settings().apply(&diff)
 }
 
 /// Commits staged changes via `git commit -m <msg>` as a subprocess.
@@ -184,7 +214,7 @@ fn read_staged_diff(repo_root: &std::path::Path) -> Result<String> {
 /// can block reading from an inherited stdin fd. On CI runners (Linux), an
 /// inherited stdin from `cargo test` can produce indefinite waits that don't
 /// reproduce on developer terminals.
-fn commit_with_message(message: &str, repo_root: &std::path::Path) -> Result<()> {
+pub(crate) fn commit_with_message(message: &str, repo_root: &std::path::Path) -> Result<()> {
     let status = Command::new("git")
         .current_dir(repo_root)
         .args(["commit", "-m", message])
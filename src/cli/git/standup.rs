@@ -0,0 +1,382 @@
+//! Standup command — groups a developer's recent commits by branch and has
+//! the AI turn them into a short "what I did / what's next" update, as plain
+//! text or Slack mrkdwn.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Utc, Weekday};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use super::formatting::truncate_hash;
+use super::release_notes::strip_markdown_fence;
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::standup::{collect_activity, BranchActivity};
+use crate::git::GitRepository;
+use crate::utils::env::SystemEnv;
+
+/// Uncommitted files listed in the prompt at most.
+const MAX_UNCOMMITTED_FILES: usize = 20;
+
+/// Standup command options.
+#[derive(Parser)]
+pub struct StandupCommand {
+    /// Covers commits made since this time: `yesterday` (the start of the
+    /// previous working day), `today`, a date (`YYYY-MM-DD`), RFC 3339, or a
+    /// duration back from now such as `3d`.
+    #[arg(long, value_name = "WHEN", default_value = "yesterday")]
+    pub since: String,
+
+    /// Whose commits to cover: `me` (git `user.email`), or part of an author
+    /// name or email.
+    #[arg(long, value_name = "WHO", default_value = "me")]
+    pub author: String,
+
+    /// Formats the update as Slack mrkdwn instead of plain text.
+    #[arg(long)]
+    pub slack: bool,
+
+    /// Lists the commits by branch without an AI summary.
+    #[arg(long)]
+    pub no_ai: bool,
+}
+
+impl StandupCommand {
+    /// Executes the standup command.
+    ///
+    /// The update goes to stdout and progress to stderr, so it can be piped
+    /// straight into the clipboard.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let since = resolve_since(&self.since, chrono::Local::now())?;
+        let author = resolve_author(git_repo.repository(), &self.author)?;
+
+        let base = git_repo.resolve_default_base_branch();
+        let branches = collect_activity(git_repo.repository(), base.as_deref(), &author, since)?;
+        let since_label = since
+            .with_timezone(&chrono::Local)
+            .format("%a %Y-%m-%d %H:%M")
+            .to_string();
+        if branches.is_empty() {
+            bail!("No commits by '{author}' since {since_label}");
+        }
+
+        let summary = if self.no_ai {
+            None
+        } else {
+            let uncommitted: Vec<String> = git_repo
+                .get_working_directory_status()?
+                .untracked_changes
+                .into_iter()
+                .take(MAX_UNCOMMITTED_FILES)
+                .map(|file| format!("{} {}", file.status, file.file))
+                .collect();
+            crate::utils::check_ai_command_prerequisites(None, &repo_root)?;
+            let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+            eprintln!(
+                "🤖 Summarizing {} commit(s) on {} branch(es)...",
+                branches.iter().map(|b| b.commits.len()).sum::<usize>(),
+                branches.len()
+            );
+            Some(standup_with_client(&claude_client, &since_label, &branches, &uncommitted).await?)
+        };
+
+        let standup = Standup {
+            since: since.to_rfc3339(),
+            author,
+            branches,
+            summary,
+            slack: self.slack,
+        };
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&standup, format)?);
+        Ok(())
+    }
+}
+
+/// The AI's standup update.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StandupSummary {
+    /// What got done.
+    #[serde(default)]
+    pub did: Vec<String>,
+    /// What comes next.
+    #[serde(default)]
+    pub next: Vec<String>,
+}
+
+/// The standup as printed.
+#[derive(Serialize)]
+struct Standup {
+    since: String,
+    author: String,
+    branches: Vec<BranchActivity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<StandupSummary>,
+    #[serde(skip)]
+    slack: bool,
+}
+
+impl Render for Standup {
+    fn render_text(&self) -> Result<String> {
+        let bullet = if self.slack { "•" } else { "-" };
+        let mut sections: Vec<String> = Vec::new();
+        match &self.summary {
+            Some(summary) => {
+                let lists = [("What I did", &summary.did), ("What's next", &summary.next)];
+                for (title, items) in lists {
+                    if items.is_empty() {
+                        continue;
+                    }
+                    let mut section = if self.slack {
+                        format!("*{title}*")
+                    } else {
+                        format!("{title}:")
+                    };
+                    for item in items {
+                        section.push_str(&format!("\n{bullet} {item}"));
+                    }
+                    sections.push(section);
+                }
+            }
+            None => {
+                for branch in &self.branches {
+                    let name = if self.slack {
+                        format!("*{}*", branch.branch)
+                    } else {
+                        branch.branch.clone()
+                    };
+                    let mut section = match branch_labels(branch) {
+                        labels if labels.is_empty() => name,
+                        labels => format!("{name} ({})", labels.join(", ")),
+                    };
+                    for commit in &branch.commits {
+                        let hash = truncate_hash(&commit.hash);
+                        if self.slack {
+                            section.push_str(&format!("\n{bullet} `{hash}` {}", commit.subject));
+                        } else {
+                            section.push_str(&format!("\n{bullet} {hash} {}", commit.subject));
+                        }
+                    }
+                    sections.push(section);
+                }
+            }
+        }
+        Ok(sections.join("\n\n"))
+    }
+}
+
+/// Status labels of a branch: `current`, and `open` or `merged` for
+/// branches other than the base.
+fn branch_labels(branch: &BranchActivity) -> Vec<&'static str> {
+    let mut labels = Vec::new();
+    if branch.current {
+        labels.push("current");
+    }
+    if !branch.base {
+        labels.push(if branch.merged { "merged" } else { "open" });
+    }
+    labels
+}
+
+/// Lists the branches with their labels and commits, newest first.
+fn format_activity(branches: &[BranchActivity]) -> String {
+    let mut out = String::new();
+    for branch in branches {
+        let mut labels = branch_labels(branch);
+        if branch.base {
+            labels.push("base branch");
+        }
+        out.push_str(&format!("{} ({})\n", branch.branch, labels.join(", ")));
+        for commit in &branch.commits {
+            out.push_str(&format!(
+                "- {} {} {}\n",
+                truncate_hash(&commit.hash),
+                commit.date,
+                commit.subject
+            ));
+        }
+    }
+    out
+}
+
+/// Asks the AI for the standup update.
+pub(crate) async fn standup_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    since: &str,
+    branches: &[BranchActivity],
+    uncommitted: &[String],
+) -> Result<StandupSummary> {
+    let user = crate::claude::prompts::generate_standup_user_prompt(
+        since,
+        &format_activity(branches),
+        uncommitted,
+    );
+    let response = claude_client
+        .send_message(crate::claude::prompts::STANDUP_SYSTEM_PROMPT, &user)
+        .await?;
+    let summary: StandupSummary = serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned a standup that is not valid YAML")?;
+    if summary.did.is_empty() && summary.next.is_empty() {
+        bail!("AI returned an empty standup");
+    }
+    Ok(summary)
+}
+
+/// Resolves `--since` against `now`: `today` is midnight today and
+/// `yesterday` midnight of the previous working day (Friday on a Monday),
+/// both in `now`'s time zone; anything else is parsed like `log --since`.
+fn resolve_since<Tz: TimeZone>(value: &str, now: DateTime<Tz>) -> Result<DateTime<Utc>> {
+    let today = now.date_naive();
+    let day = match value.trim().to_lowercase().as_str() {
+        "today" => today,
+        "yesterday" => {
+            let mut day = today - Duration::days(1);
+            while matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+                day -= Duration::days(1);
+            }
+            day
+        }
+        _ => return crate::cli::log::parse_time_bound(value),
+    };
+    now.timezone()
+        .from_local_datetime(&day.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|midnight| midnight.with_timezone(&Utc))
+        .with_context(|| format!("Midnight of {day} does not exist in the local time zone"))
+}
+
+/// Resolves `--author me` to git's `user.email`; other values are used as
+/// given.
+pub(super) fn resolve_author(repo: &git2::Repository, author: &str) -> Result<String> {
+    let author = author.trim();
+    if author.is_empty() {
+        bail!("--author must not be empty");
+    }
+    if author != "me" {
+        return Ok(author.to_string());
+    }
+    repo.config()
+        .and_then(|config| config.get_string("user.email"))
+        .context("git user.email is not set; set it or pass --author <name or email>")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::git::standup::ActivityCommit;
+
+    fn branches() -> Vec<BranchActivity> {
+        vec![
+            BranchActivity {
+                branch: "feature/standup".to_string(),
+                current: true,
+                base: false,
+                merged: false,
+                commits: vec![ActivityCommit {
+                    hash: "a".repeat(40),
+                    date: "2026-10-15 09:30".to_string(),
+                    subject: "feat(git): add standup".to_string(),
+                }],
+            },
+            BranchActivity {
+                branch: "main".to_string(),
+                current: false,
+                base: true,
+                merged: false,
+                commits: vec![ActivityCommit {
+                    hash: "b".repeat(40),
+                    date: "2026-10-15 08:00".to_string(),
+                    subject: "fix: typo".to_string(),
+                }],
+            },
+        ]
+    }
+
+    #[test]
+    fn yesterday_skips_the_weekend() {
+        // Monday 2026-10-12, 10:00 UTC.
+        let monday = Utc.with_ymd_and_hms(2026, 10, 12, 10, 0, 0).unwrap();
+        assert_eq!(
+            resolve_since("yesterday", monday).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 9, 0, 0, 0).unwrap()
+        );
+        let wednesday = Utc.with_ymd_and_hms(2026, 10, 14, 10, 0, 0).unwrap();
+        assert_eq!(
+            resolve_since("Yesterday", wednesday).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 13, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            resolve_since("today", wednesday).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 14, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            resolve_since("2026-10-01", wednesday).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap()
+        );
+        assert!(resolve_since("last sprint", wednesday).is_err());
+    }
+
+    #[tokio::test]
+    async fn summary_comes_from_ai_response() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "```yaml\ndid:\n  - \"Added `git standup`.\"\nnext:\n  - \"Ship it.\"\n```".to_string(),
+        )]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let summary = standup_with_client(
+            &client,
+            "Thu 2026-10-15 00:00",
+            &branches(),
+            &["M  src/cli/git.rs".to_string()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary.did, vec!["Added `git standup`."]);
+        assert_eq!(summary.next, vec!["Ship it."]);
+
+        let sent = prompts.prompts();
+        let (_, user) = &sent[0];
+        assert!(user.contains("feature/standup (current, open)"));
+        assert!(user.contains("main (base branch)"));
+        assert!(user.contains("M  src/cli/git.rs"));
+    }
+
+    #[test]
+    fn renders_plain_and_slack() {
+        let mut standup = Standup {
+            since: "2026-10-15T00:00:00+00:00".to_string(),
+            author: "ann@example.com".to_string(),
+            branches: branches(),
+            summary: Some(StandupSummary {
+                did: vec!["Added `git standup`.".to_string()],
+                next: vec![],
+            }),
+            slack: false,
+        };
+        assert_eq!(
+            standup.render_text().unwrap(),
+            "What I did:\n- Added `git standup`."
+        );
+        standup.slack = true;
+        assert_eq!(
+            standup.render_text().unwrap(),
+            "*What I did*\n• Added `git standup`."
+        );
+
+        standup.summary = None;
+        assert_eq!(
+            standup.render_text().unwrap(),
+            "*feature/standup* (current, open)\n• `aaaaaaaa` feat(git): add standup\n\n\
+             *main*\n• `bbbbbbbb` fix: typo"
+        );
+    }
+}
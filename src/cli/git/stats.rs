@@ -0,0 +1,177 @@
+//! Stats quality command — scores historical commit messages against the
+//! offline rules and reports the trend per period, author, or scope.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::commit::DEFAULT_COMMIT_TYPES;
+use crate::git::quality::{
+    score_history, GroupBy, Period, QualityReport, QualityTally, MAX_SUBJECT_LENGTH,
+};
+use crate::git::GitRepository;
+use crate::utils::env::SystemEnv;
+
+/// Stats quality command options.
+#[derive(Parser)]
+pub struct StatsQualityCommand {
+    /// Scores the history reachable from this revision.
+    #[arg(long, value_name = "REV", default_value = "HEAD")]
+    pub rev: String,
+
+    /// Only scores commits made since this date (`YYYY-MM-DD`, RFC 3339, or
+    /// a duration back from now such as `12w`).
+    #[arg(long, value_name = "WHEN")]
+    pub since: Option<String>,
+
+    /// Period scores are bucketed into.
+    #[arg(long, value_enum, default_value_t = Period::Month)]
+    pub period: Period,
+
+    /// Groups the per-group trends by author or by conventional scope.
+    #[arg(long, value_enum, default_value_t = GroupBy::Author)]
+    pub by: GroupBy,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+}
+
+impl StatsQualityCommand {
+    /// Executes the stats quality command.
+    ///
+    /// `--output yaml|json` prints the full report, every group with every
+    /// period, as structured data.
+    pub fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let since = self
+            .since
+            .as_deref()
+            .map(crate::cli::log::parse_time_bound)
+            .transpose()?;
+
+        let types = self.commit_types(&repo_root);
+        let commits = score_history(git_repo.repository(), &self.rev, since, &types)?;
+        if commits.is_empty() {
+            bail!("No commits to score in {}", self.rev);
+        }
+        let report = QualityReport::from_commits(&commits, self.period, self.by);
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&report, format)?);
+        Ok(())
+    }
+
+    /// The project's commit types, or the built-in ones without `types.yaml`.
+    fn commit_types(&self, repo_root: &std::path::Path) -> Vec<String> {
+        let context_dir =
+            crate::claude::context::resolve_context_dir_at(self.context_dir.as_deref(), repo_root);
+        let project = crate::claude::context::load_project_types(&context_dir);
+        if project.is_empty() {
+            DEFAULT_COMMIT_TYPES
+                .iter()
+                .map(|(name, _)| (*name).to_string())
+                .collect()
+        } else {
+            project.into_iter().map(|t| t.name).collect()
+        }
+    }
+}
+
+impl Render for QualityReport {
+    fn render_text(&self) -> Result<String> {
+        let mut out = format!(
+            "Commit message quality: {} commits, {:.1}% of rules passed\n\
+             Rules: conventional type, body, subject ≤ {MAX_SUBJECT_LENGTH} characters\n\n",
+            self.total.commits, self.total.score
+        );
+        out.push_str(&format!(
+            "{:<10} {:>7} {:>6} {:>6} {:>8} {:>7}\n",
+            "Period", "Commits", "Type", "Body", "Subject", "Score"
+        ));
+        for period in &self.periods {
+            out.push_str(&tally_row(&period.period, &period.tally));
+        }
+
+        let label = match self.group_by {
+            GroupBy::Author => "author",
+            GroupBy::Scope => "scope",
+        };
+        out.push_str(&format!("\nBy {label}:\n"));
+        for group in &self.groups {
+            out.push_str(&format!(
+                "  {} — {} commit(s), {:.1}%",
+                group.name, group.total.commits, group.total.score
+            ));
+            if let (Some(first), Some(last)) = (group.periods.first(), group.periods.last()) {
+                if group.periods.len() > 1 {
+                    out.push_str(&format!(
+                        " ({} {:.1}% → {} {:.1}%)",
+                        first.period, first.tally.score, last.period, last.tally.score
+                    ));
+                }
+            }
+            out.push('\n');
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+/// One table row: the share of commits passing each rule, then the score.
+fn tally_row(label: &str, tally: &QualityTally) -> String {
+    format!(
+        "{label:<10} {:>7} {:>6} {:>6} {:>8} {:>6.1}%\n",
+        tally.commits,
+        percent(tally.typed, tally.commits),
+        percent(tally.with_body, tally.commits),
+        percent(tally.short_subject, tally.commits),
+        tally.score
+    )
+}
+
+/// `part` as a whole-number percentage of `whole`.
+fn percent(part: usize, whole: usize) -> String {
+    format!("{}%", (part * 100 + whole / 2) / whole.max(1))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::git::quality::{MessageQuality, ScoredCommit};
+    use chrono::{TimeZone, Utc};
+
+    fn scored(author: &str, month: u32, message: &str) -> ScoredCommit {
+        ScoredCommit {
+            author: author.to_string(),
+            date: Utc.with_ymd_and_hms(2024, month, 1, 0, 0, 0).unwrap(),
+            scopes: vec![],
+            quality: MessageQuality::assess(message, &["feat".to_string(), "fix".to_string()]),
+        }
+    }
+
+    #[test]
+    fn text_report_lists_periods_and_group_trends() {
+        let commits = [
+            scored("Ann", 3, "feat: add stats\n\nBody.\n"),
+            scored("Ann", 1, "Add stats"),
+        ];
+        let report = QualityReport::from_commits(&commits, Period::Month, GroupBy::Author);
+        let text = report.render_text().unwrap();
+        assert!(text.starts_with("Commit message quality: 2 commits, 66.7% of rules passed\n"));
+        assert!(text.contains("\n2024-01          1     0%     0%     100%   33.3%\n"));
+        assert!(text.contains("\n2024-03          1   100%   100%     100%  100.0%\n"));
+        assert!(text
+            .ends_with("By author:\n  Ann — 2 commit(s), 66.7% (2024-01 33.3% → 2024-03 100.0%)"));
+    }
+
+    #[test]
+    fn percent_rounds_to_whole_numbers() {
+        assert_eq!(percent(2, 3), "67%");
+        assert_eq!(percent(0, 0), "0%");
+    }
+}
@@ -0,0 +1,348 @@
+//! Suggest tests command — proposes concrete test cases for the functions a
+//! commit range or the staged changes add or change, placed where the
+//! project's test layout says they belong.
+//!
+//! The suggestions print as a markdown checklist, or with `--patch` as a
+//! patch adding `TODO(test)` comments to the test files, for `git apply`.
+
+use std::fmt::Write as _;
+use std::path::{Component, Path};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use super::release_notes::strip_markdown_fence;
+use super::staged::read_target_diff;
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::test_gaps::TestGapAnalysis;
+use crate::utils::env::SystemEnv;
+use crate::utils::redact::redact_secrets;
+
+/// Lines of existing content kept as context above the appended comments.
+const PATCH_CONTEXT: usize = 3;
+
+/// `omni-dev git commit suggest tests` CLI command.
+#[derive(Parser)]
+pub struct SuggestTestsCommand {
+    /// Commit range to analyze (e.g. origin/main..HEAD; a single ref means <ref>..HEAD), or `staged` for the staged changes (defaults to the branch against its base).
+    #[arg(value_name = "RANGE|staged")]
+    pub target: Option<String>,
+
+    /// Prints the suggestions as a patch adding TODO comments to the test files, for `git apply`.
+    #[arg(long)]
+    pub patch: bool,
+}
+
+/// One proposed test case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestCase {
+    /// Test function name.
+    pub name: String,
+    /// Setup and expected outcome, in one sentence.
+    pub description: String,
+}
+
+/// Test cases proposed for one function.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestSuggestion {
+    /// Repository-relative path of the file the tests belong in.
+    pub file: String,
+    /// Function under test.
+    pub target: String,
+    /// Proposed cases.
+    #[serde(default)]
+    pub cases: Vec<TestCase>,
+}
+
+/// The proposed tests, as printed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TestSuggestions {
+    /// Proposals, in the order the AI gave them.
+    #[serde(default)]
+    pub suggestions: Vec<TestSuggestion>,
+}
+
+impl Render for TestSuggestions {
+    fn render_text(&self) -> Result<String> {
+        if self.suggestions.iter().all(|s| s.cases.is_empty()) {
+            return Ok("No test gaps found in the changes.".to_string());
+        }
+        let mut files: Vec<&str> = Vec::new();
+        for suggestion in &self.suggestions {
+            if !files.contains(&suggestion.file.as_str()) {
+                files.push(&suggestion.file);
+            }
+        }
+        let mut out = String::from("## Suggested tests\n");
+        for file in files {
+            let _ = write!(out, "\n### `{file}`\n\n");
+            for suggestion in self.suggestions.iter().filter(|s| s.file == file) {
+                for case in &suggestion.cases {
+                    let _ = writeln!(
+                        out,
+                        "- [ ] `{}` (`{}`): {}",
+                        case.name, suggestion.target, case.description
+                    );
+                }
+            }
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+impl SuggestTestsCommand {
+    /// Executes the suggest tests command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let diff = read_target_diff(repo_root, self.target.as_deref())?;
+        let analysis = TestGapAnalysis::from_diff(repo_root, &diff)?;
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        eprintln!(
+            "🧪 Looking for test gaps in {} changed function(s)...",
+            analysis.functions.len()
+        );
+        let suggestions = suggest_with_client(&claude_client, &analysis, &diff).await?;
+
+        if self.patch {
+            print!("{}", todo_patch(repo_root, &suggestions)?);
+        } else {
+            let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+            println!("{}", render(&suggestions, format)?);
+        }
+        Ok(())
+    }
+}
+
+/// Asks the AI for the tests the change still needs.
+pub(crate) async fn suggest_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    analysis: &TestGapAnalysis,
+    diff: &str,
+) -> Result<TestSuggestions> {
+    let user = crate::claude::prompts::generate_test_suggestion_user_prompt(
+        &crate::data::to_yaml(analysis)?,
+        &redact_secrets(diff),
+    );
+    let response = claude_client
+        .send_message(crate::claude::prompts::TEST_SUGGESTION_SYSTEM_PROMPT, &user)
+        .await?;
+    serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned test suggestions that are not valid YAML")
+}
+
+/// A patch appending the suggested cases, as `TODO(test)` comments, to each
+/// suggested file under `repo_root`, creating the files that do not exist.
+pub(crate) fn todo_patch(repo_root: &Path, suggestions: &TestSuggestions) -> Result<String> {
+    let mut files: Vec<(&str, Vec<String>)> = Vec::new();
+    for suggestion in &suggestions.suggestions {
+        let path = Path::new(&suggestion.file);
+        if suggestion.file.is_empty()
+            || !path
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        {
+            bail!(
+                "AI suggested a test file outside the repository: {}",
+                suggestion.file
+            );
+        }
+        let prefix = comment_prefix(&suggestion.file);
+        let comments = suggestion.cases.iter().map(|case| {
+            format!(
+                "{prefix} TODO(test): {} ({}): {}",
+                case.name,
+                suggestion.target,
+                case.description.replace('\n', " ")
+            )
+        });
+        match files.iter_mut().find(|(file, _)| *file == suggestion.file) {
+            Some((_, lines)) => lines.extend(comments),
+            None => files.push((suggestion.file.as_str(), comments.collect())),
+        }
+    }
+
+    let mut patch = String::new();
+    for (file, comments) in files.into_iter().filter(|(_, c)| !c.is_empty()) {
+        let full = repo_root.join(file);
+        let _ = writeln!(patch, "diff --git a/{file} b/{file}");
+        if !full.exists() {
+            let _ = write!(
+                patch,
+                "new file mode 100644\n--- /dev/null\n+++ b/{file}\n@@ -0,0 +1,{} @@\n",
+                comments.len()
+            );
+            for comment in &comments {
+                let _ = writeln!(patch, "+{comment}");
+            }
+            continue;
+        }
+
+        let content = std::fs::read_to_string(&full)
+            .with_context(|| format!("Failed to read {}", full.display()))?;
+        let lines: Vec<&str> = content.lines().collect();
+        let _ = writeln!(patch, "--- a/{file}\n+++ b/{file}");
+        if lines.is_empty() {
+            let _ = writeln!(patch, "@@ -0,0 +1,{} @@", comments.len());
+            for comment in &comments {
+                let _ = writeln!(patch, "+{comment}");
+            }
+            continue;
+        }
+
+        // A blank line, then the comments, after the last lines of the file.
+        let context = PATCH_CONTEXT.min(lines.len());
+        let start = lines.len() - context + 1;
+        let added = comments.len() + 1;
+        let _ = writeln!(
+            patch,
+            "@@ -{start},{context} +{start},{} @@",
+            context + added
+        );
+        let last = lines[lines.len() - 1];
+        for line in &lines[lines.len() - context..lines.len() - 1] {
+            let _ = writeln!(patch, " {line}");
+        }
+        if content.ends_with('\n') {
+            let _ = writeln!(patch, " {last}");
+        } else {
+            let _ = writeln!(patch, "-{last}\n\\ No newline at end of file\n+{last}");
+        }
+        patch.push_str("+\n");
+        for comment in &comments {
+            let _ = writeln!(patch, "+{comment}");
+        }
+    }
+    Ok(patch)
+}
+
+/// The line comment marker for `file`'s language.
+fn comment_prefix(file: &str) -> &'static str {
+    let extension = file.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension {
+        "py" | "rb" | "sh" | "bash" | "pl" | "r" | "R" | "ex" | "exs" | "yaml" | "yml" | "toml"
+        | "cr" | "nim" => "#",
+        "lua" | "sql" | "hs" | "elm" => "--",
+        _ => "//",
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::git::test_gaps::TestLayout;
+
+    fn suggestions(file: &str) -> TestSuggestions {
+        TestSuggestions {
+            suggestions: vec![TestSuggestion {
+                file: file.to_string(),
+                target: "parse_range".to_string(),
+                cases: vec![TestCase {
+                    name: "rejects_empty_range".to_string(),
+                    description: "An empty string is an error.".to_string(),
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn markdown_groups_cases_by_file() {
+        let text = suggestions("tests/range.rs").render_text().unwrap();
+        assert_eq!(
+            text,
+            "## Suggested tests\n\n### `tests/range.rs`\n\n\
+             - [ ] `rejects_empty_range` (`parse_range`): An empty string is an error."
+        );
+        assert_eq!(
+            TestSuggestions::default().render_text().unwrap(),
+            "No test gaps found in the changes."
+        );
+    }
+
+    #[test]
+    fn patch_appends_to_existing_files_and_creates_new_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("tests")).unwrap();
+        std::fs::write(dir.path().join("tests/range.rs"), "a\nb\nc\nd\n").unwrap();
+        std::fs::write(dir.path().join("tests/open.py"), "x").unwrap();
+
+        let patch = todo_patch(dir.path(), &suggestions("tests/range.rs")).unwrap();
+        assert_eq!(
+            patch,
+            "diff --git a/tests/range.rs b/tests/range.rs\n--- a/tests/range.rs\n+++ b/tests/range.rs\n\
+             @@ -2,3 +2,5 @@\n b\n c\n d\n+\n\
+             +// TODO(test): rejects_empty_range (parse_range): An empty string is an error.\n"
+        );
+
+        let patch = todo_patch(dir.path(), &suggestions("tests/open.py")).unwrap();
+        assert!(patch
+            .contains("@@ -1,1 +1,3 @@\n-x\n\\ No newline at end of file\n+x\n+\n+# TODO(test):"));
+
+        let patch = todo_patch(dir.path(), &suggestions("tests/new_test.go")).unwrap();
+        assert!(patch.contains("new file mode 100644\n--- /dev/null\n+++ b/tests/new_test.go\n@@ -0,0 +1,1 @@\n+// TODO(test):"));
+
+        assert!(todo_patch(dir.path(), &suggestions("../outside.rs")).is_err());
+        assert!(todo_patch(dir.path(), &suggestions("/etc/passwd")).is_err());
+    }
+
+    #[test]
+    fn patch_applies_with_git() {
+        let dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        std::fs::write(
+            dir.path().join("lib_test.go"),
+            "package lib\n\nfunc A() {}\n",
+        )
+        .unwrap();
+        let mut all = suggestions("lib_test.go");
+        all.suggestions
+            .extend(suggestions("tests/new.rs").suggestions);
+        let patch_path = dir.path().join("todo.patch");
+        std::fs::write(&patch_path, todo_patch(dir.path(), &all).unwrap()).unwrap();
+
+        let status = std::process::Command::new("git")
+            .current_dir(dir.path())
+            .args(["apply", "todo.patch"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let content = std::fs::read_to_string(dir.path().join("lib_test.go")).unwrap();
+        assert!(content.ends_with("func A() {}\n\n// TODO(test): rejects_empty_range (parse_range): An empty string is an error.\n"));
+        assert!(dir.path().join("tests/new.rs").exists());
+    }
+
+    #[tokio::test]
+    async fn suggestions_come_from_ai_response() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok("suggestions:\n  \
+            - file: tests/range.rs\n    target: parse_range\n    cases:\n      \
+            - name: rejects_empty_range\n        description: An empty string is an error.\n"
+            .to_string())]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+        let analysis = TestGapAnalysis {
+            functions: vec![],
+            tests_changed: vec![],
+            layout: TestLayout {
+                conventions: vec!["tests/ (3 files)".to_string()],
+                related: vec![],
+            },
+        };
+        let result = suggest_with_client(&client, &analysis, "+fn parse_range() {}")
+            .await
+            .unwrap();
+        assert_eq!(
+            result.suggestions,
+            suggestions("tests/range.rs").suggestions
+        );
+        assert!(prompts.prompts()[0].1.contains("tests/ (3 files)"));
+    }
+}
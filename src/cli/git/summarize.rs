@@ -0,0 +1,208 @@
+//! Summarize command — writes a short narrative of what the current branch
+//! does (goal, major changes, risky areas) for standups, handoffs, and review
+//! requests, without creating a pull request.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use super::formatting::truncate_hash;
+use super::release_notes::strip_markdown_fence;
+use crate::git::{CommitInfo, GitRepository};
+
+/// Maximum characters of branch diff sent to the AI.
+const MAX_DIFF_CHARS: usize = 40_000;
+
+/// Branch summarize command options.
+#[derive(Parser)]
+pub struct SummarizeCommand {
+    /// Base branch to compare against (defaults to origin/main,
+    /// origin/master, main, or master).
+    #[arg(value_name = "BASE_BRANCH")]
+    pub base_branch: Option<String>,
+
+    /// Use commit messages (not the diff) as the only input for the summary.
+    #[arg(long)]
+    pub from_commits: bool,
+}
+
+impl SummarizeCommand {
+    /// Executes the summarize command.
+    ///
+    /// The summary goes to stdout and progress to stderr, so the output can
+    /// be piped or copied straight into chat.
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let branch = git_repo.get_current_branch().context(
+            "Failed to get current branch. Make sure you're not in detached HEAD state.",
+        )?;
+        let range = match &self.base_branch {
+            Some(base) => {
+                if !git_repo.branch_exists(base)? {
+                    bail!("Base branch '{base}' does not exist");
+                }
+                format!("{base}..HEAD")
+            }
+            None => super::default_commit_range(&git_repo)?,
+        };
+        let commits = git_repo
+            .get_commits_in_range(&range)
+            .with_context(|| format!("Failed to read commits in {range}"))?;
+        if commits.is_empty() {
+            bail!("No commits in {range}; nothing to summarize");
+        }
+        let diff = if self.from_commits {
+            None
+        } else {
+            Some(read_branch_diff(&commits)?)
+        };
+
+        crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+
+        eprintln!(
+            "🤖 Summarizing {} commit(s) on {branch} ({range})...",
+            commits.len()
+        );
+        let summary = summarize_branch_with_client(
+            &claude_client,
+            &branch,
+            &range,
+            &format_commit_list(&commits),
+            diff.as_deref(),
+        )
+        .await?;
+        println!("{summary}");
+        Ok(())
+    }
+}
+
+/// Asks the AI for the branch summary.
+pub(crate) async fn summarize_branch_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    branch: &str,
+    range: &str,
+    commits: &str,
+    diff: Option<&str>,
+) -> Result<String> {
+    let user =
+        crate::claude::prompts::generate_branch_summary_user_prompt(branch, range, commits, diff);
+    let response = claude_client
+        .send_message(crate::claude::prompts::BRANCH_SUMMARY_SYSTEM_PROMPT, &user)
+        .await?;
+    let summary = strip_markdown_fence(response.trim());
+    if summary.is_empty() {
+        bail!("AI returned an empty branch summary");
+    }
+    Ok(summary.to_string())
+}
+
+/// Lists the commits (oldest first, as the range yields them), one subject per
+/// line with the files each one touches.
+fn format_commit_list(commits: &[CommitInfo]) -> String {
+    let mut out = String::new();
+    for commit in commits {
+        let subject = commit.original_message.lines().next().unwrap_or("").trim();
+        out.push_str(&format!("- {} {subject}\n", truncate_hash(&commit.hash)));
+        let files: Vec<&str> = commit
+            .analysis
+            .file_changes
+            .file_list
+            .iter()
+            .map(|f| f.file.as_str())
+            .collect();
+        if !files.is_empty() {
+            out.push_str(&format!("  files: {}\n", files.join(", ")));
+        }
+    }
+    out
+}
+
+/// Concatenates the commits' diffs, truncated to [`MAX_DIFF_CHARS`].
+fn read_branch_diff(commits: &[CommitInfo]) -> Result<String> {
+    let mut diff = String::new();
+    for commit in commits {
+        let path = &commit.analysis.diff_file;
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read diff file: {path}"))?;
+        diff.push_str(&content);
+        if diff.len() > MAX_DIFF_CHARS {
+            let mut end = MAX_DIFF_CHARS;
+            while !diff.is_char_boundary(end) {
+                end -= 1;
+            }
+            diff.truncate(end);
+            diff.push_str("\n# (diff truncated)\n");
+            break;
+        }
+    }
+    Ok(diff)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+    use crate::test_support::commits;
+
+    fn commit(hash: &str, message: &str, files: &[&str], diff_file: &str) -> CommitInfo {
+        let mut commit = commits::commit(hash, message, files);
+        commit.analysis.diff_file = diff_file.to_string();
+        commit
+    }
+
+    #[test]
+    fn commit_list_has_subjects_and_files() {
+        let commits = vec![
+            commit("aaaaaaaaaaaa", "feat: first", &["src/a.rs", "src/b.rs"], ""),
+            commit("bbbbbbbbbbbb", "fix: second\n\nBody", &[], ""),
+        ];
+        assert_eq!(
+            format_commit_list(&commits),
+            "- aaaaaaaa feat: first\n  files: src/a.rs, src/b.rs\n- bbbbbbbb fix: second\n"
+        );
+    }
+
+    #[test]
+    fn branch_diff_is_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("a.diff");
+        std::fs::write(&path, "+x\n".repeat(MAX_DIFF_CHARS)).unwrap();
+        let path = path.to_string_lossy().to_string();
+        let commits = vec![
+            commit("aaaaaaaa", "feat: a", &[], &path),
+            commit("bbbbbbbb", "fix: b", &[], &path),
+        ];
+
+        let diff = read_branch_diff(&commits).unwrap();
+        assert!(diff.ends_with("# (diff truncated)\n"));
+        assert!(diff.len() < MAX_DIFF_CHARS + 32);
+    }
+
+    #[tokio::test]
+    async fn summary_comes_from_ai_response() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok(
+            "```markdown\n**Goal** — Add summaries.\n```".to_string(),
+        )]);
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let summary = summarize_branch_with_client(
+            &client,
+            "feat/summarize",
+            "main..HEAD",
+            "- aaaaaaaa feat: first\n",
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(summary, "**Goal** — Add summaries.");
+    }
+}
@@ -0,0 +1,626 @@
+//! Full-screen terminal interface for `twiddle --tui` and `check --tui`.
+//!
+//! The screen is a commit list over a detail pane showing the selected
+//! commit's message diff and notes. While the AI works, rows move from
+//! `queued` through `running` to `done` (or `failed`); afterwards the same
+//! screen turns into a review where each proposed message is accepted,
+//! edited, or skipped from the keyboard.
+//!
+//! Drawn with `crossterm` directly, as `ai chat` is: [`Screen`] renders the
+//! state to plain lines and maps keys to actions, and [`Tui`] owns the
+//! terminal (alternate screen, raw mode during review) and redraws the whole
+//! frame on every change.
+
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{bail, Context, Result};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetForegroundColor};
+use crossterm::terminal::{
+    self, disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use similar::{ChangeTag, TextDiff};
+
+/// Progress of one commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RowState {
+    /// Waiting for a concurrency slot.
+    Queued,
+    /// Sent to the AI.
+    Running,
+    /// The AI answered.
+    Done,
+    /// The AI request failed.
+    Failed,
+    /// Checked without issues.
+    Passed,
+    /// Checked with warnings only.
+    Warning,
+    /// Checked with errors.
+    Error,
+}
+
+impl RowState {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+            Self::Passed => "pass",
+            Self::Warning => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    const fn is_finished(self) -> bool {
+        !matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+/// What the review does with a proposed message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Decision {
+    Accept,
+    Skip,
+}
+
+/// One commit on the screen.
+#[derive(Debug, Clone)]
+struct Row {
+    hash: String,
+    original: String,
+    proposed: Option<String>,
+    decision: Decision,
+    notes: Vec<String>,
+    state: RowState,
+}
+
+impl Row {
+    fn matches(&self, hash: &str) -> bool {
+        !hash.is_empty() && (self.hash.starts_with(hash) || hash.starts_with(&self.hash))
+    }
+}
+
+/// How a line is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tone {
+    Normal,
+    Header,
+    Selected,
+    Added,
+    Removed,
+    Dim,
+}
+
+/// What a key press asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    /// Redraw and keep reading keys.
+    Continue,
+    /// Open the selected message in the editor.
+    Edit,
+    /// Leave the review and apply the accepted messages.
+    Apply,
+    /// Leave the review without applying anything.
+    Quit,
+}
+
+/// The state behind the screen, free of any terminal I/O.
+#[derive(Debug)]
+pub(crate) struct Screen {
+    title: String,
+    rows: Vec<Row>,
+    selected: usize,
+    status: String,
+    reviewing: bool,
+}
+
+impl Screen {
+    /// A screen listing `commits` as `(hash, original message)` pairs, all
+    /// queued.
+    pub(crate) fn new(title: &str, commits: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            title: title.to_string(),
+            rows: commits
+                .into_iter()
+                .map(|(hash, original)| Row {
+                    hash,
+                    original,
+                    proposed: None,
+                    decision: Decision::Skip,
+                    notes: Vec::new(),
+                    state: RowState::Queued,
+                })
+                .collect(),
+            selected: 0,
+            status: String::new(),
+            reviewing: false,
+        }
+    }
+
+    fn row_mut(&mut self, hash: &str) -> Option<&mut Row> {
+        self.rows.iter_mut().find(|row| row.matches(hash))
+    }
+
+    pub(crate) fn set_state(&mut self, hash: &str, state: RowState) {
+        if let Some(row) = self.row_mut(hash) {
+            row.state = state;
+        }
+    }
+
+    pub(crate) fn add_note(&mut self, hash: &str, note: String) {
+        if let Some(row) = self.row_mut(hash) {
+            row.notes.push(note);
+        }
+    }
+
+    pub(crate) fn propose(&mut self, hash: &str, message: &str, decision: Decision) {
+        if let Some(row) = self.row_mut(hash) {
+            row.proposed = Some(message.to_string());
+            row.decision = decision;
+        }
+    }
+
+    /// The accepted messages as `(hash, message)` pairs, in list order.
+    pub(crate) fn accepted(&self) -> Vec<(String, String)> {
+        self.rows
+            .iter()
+            .filter(|row| row.decision == Decision::Accept)
+            .filter_map(|row| Some((row.hash.clone(), row.proposed.clone()?)))
+            .collect()
+    }
+
+    /// Maps a key press during review to an action, updating the selection
+    /// and decisions.
+    pub(crate) fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.status.clear();
+        let last = self.rows.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(last),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.selected = last,
+            KeyCode::Char('a') => self.decide(Decision::Accept),
+            KeyCode::Char('s') => self.decide(Decision::Skip),
+            KeyCode::Char('e') if self.selected_row().is_some_and(|r| r.proposed.is_some()) => {
+                return Action::Edit;
+            }
+            KeyCode::Char('e') => self.status = "No proposed message to edit".to_string(),
+            KeyCode::Enter => return Action::Apply,
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            _ => {}
+        }
+        Action::Continue
+    }
+
+    fn decide(&mut self, decision: Decision) {
+        match self.rows.get_mut(self.selected) {
+            Some(row) if row.proposed.is_some() => {
+                row.decision = decision;
+                self.selected = (self.selected + 1).min(self.rows.len() - 1);
+            }
+            Some(_) => self.status = "No proposed message for this commit".to_string(),
+            None => {}
+        }
+    }
+
+    fn selected_row(&self) -> Option<&Row> {
+        self.rows.get(self.selected)
+    }
+
+    /// Replaces the selected row's proposed message and accepts it.
+    fn set_selected_message(&mut self, message: String) {
+        if let Some(row) = self.rows.get_mut(self.selected) {
+            row.proposed = Some(message);
+            row.decision = Decision::Accept;
+        }
+    }
+
+    /// Renders the screen as at most `height` lines of at most `width`
+    /// characters.
+    pub(crate) fn render(&self, width: usize, height: usize) -> Vec<(Tone, String)> {
+        let mut lines = Vec::with_capacity(height);
+        let finished = self.rows.iter().filter(|r| r.state.is_finished()).count();
+        let failed = self
+            .rows
+            .iter()
+            .filter(|r| r.state == RowState::Failed)
+            .count();
+        let mut header = format!(
+            "omni-dev {} · {finished}/{} commits",
+            self.title,
+            self.rows.len()
+        );
+        if failed > 0 {
+            header.push_str(&format!(" · {failed} failed"));
+        }
+        if self.reviewing {
+            header.push_str(&format!(" · {} accepted", self.accepted().len()));
+        }
+        lines.push((Tone::Header, fit(&header, width)));
+
+        // The list takes up to half the screen; the detail pane the rest.
+        let body = height.saturating_sub(3);
+        let list_height = self.rows.len().min((body / 2).max(1));
+        let offset = self
+            .selected
+            .saturating_sub(list_height.saturating_sub(1))
+            .min(self.rows.len().saturating_sub(list_height));
+        for (index, row) in self.rows.iter().enumerate().skip(offset).take(list_height) {
+            let selected = self.reviewing && index == self.selected;
+            let decision = match (self.reviewing, &row.proposed, row.decision) {
+                (false, _, _) => "",
+                (true, None, _) => "[  --  ] ",
+                (true, Some(_), Decision::Accept) => "[accept] ",
+                (true, Some(_), Decision::Skip) => "[ skip ] ",
+            };
+            let text = format!(
+                "{} {:<7} {} {decision}{}",
+                if selected { '>' } else { ' ' },
+                row.state.label(),
+                super::formatting::truncate_hash(&row.hash),
+                row.original.lines().next().unwrap_or_default()
+            );
+            let tone = match row.state {
+                _ if selected => Tone::Selected,
+                RowState::Queued => Tone::Dim,
+                RowState::Failed | RowState::Error => Tone::Removed,
+                _ => Tone::Normal,
+            };
+            lines.push((tone, fit(&text, width)));
+        }
+        lines.push((Tone::Dim, "─".repeat(width)));
+
+        let detail_height = height.saturating_sub(lines.len() + 1);
+        let detail = self.selected_row().map(detail_lines).unwrap_or_default();
+        lines.extend(
+            detail
+                .into_iter()
+                .take(detail_height)
+                .map(|(tone, text)| (tone, fit(&text, width))),
+        );
+        while lines.len() + 1 < height {
+            lines.push((Tone::Normal, String::new()));
+        }
+
+        let footer = if !self.status.is_empty() {
+            self.status.clone()
+        } else if self.reviewing {
+            "j/k move · a accept · s skip · e edit · enter apply accepted · q quit".to_string()
+        } else {
+            "Waiting for the AI…".to_string()
+        };
+        lines.push((Tone::Dim, fit(&footer, width)));
+        lines.truncate(height);
+        lines
+    }
+}
+
+/// The detail pane for `row`: the message diff (or the message itself) and
+/// the notes.
+fn detail_lines(row: &Row) -> Vec<(Tone, String)> {
+    let mut lines = vec![(Tone::Header, format!("commit {}", row.hash))];
+    match &row.proposed {
+        Some(proposed) => {
+            let diff = TextDiff::from_lines(row.original.trim_end(), proposed.trim_end());
+            for change in diff.iter_all_changes() {
+                let text = change.value().trim_end_matches('\n');
+                lines.push(match change.tag() {
+                    ChangeTag::Delete => (Tone::Removed, format!("- {text}")),
+                    ChangeTag::Insert => (Tone::Added, format!("+ {text}")),
+                    ChangeTag::Equal => (Tone::Normal, format!("  {text}")),
+                });
+            }
+        }
+        None => lines.extend(
+            row.original
+                .trim_end()
+                .lines()
+                .map(|line| (Tone::Normal, format!("  {line}"))),
+        ),
+    }
+    if !row.notes.is_empty() {
+        lines.push((Tone::Normal, String::new()));
+        lines.extend(row.notes.iter().map(|note| (Tone::Dim, note.clone())));
+    }
+    lines
+}
+
+/// Truncates `text` to `width` characters.
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+/// Fails unless both stdin and stdout are terminals and non-interactive
+/// mode is off.
+pub(crate) fn ensure_terminal() -> Result<()> {
+    crate::utils::interactive::ensure_interactive("--tui")?;
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        bail!("--tui needs an interactive terminal");
+    }
+    Ok(())
+}
+
+/// The full-screen interface, drawn on the alternate screen until dropped.
+///
+/// Every update redraws the frame. The state sits behind a mutex so the
+/// concurrent map-phase futures can report progress through a shared
+/// reference.
+pub(crate) struct Tui {
+    screen: Mutex<Screen>,
+}
+
+impl Tui {
+    /// Switches to the alternate screen and draws `screen`.
+    pub(crate) fn enter(screen: Screen) -> Result<Self> {
+        execute!(io::stdout(), EnterAlternateScreen, Hide)
+            .context("Failed to switch to the alternate screen")?;
+        let tui = Self {
+            screen: Mutex::new(screen),
+        };
+        tui.redraw();
+        Ok(tui)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Screen> {
+        self.screen
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Applies `change` to the screen and redraws it.
+    pub(crate) fn update(&self, change: impl FnOnce(&mut Screen)) {
+        change(&mut self.lock());
+        self.redraw();
+    }
+
+    pub(crate) fn set_state<'a>(&self, hashes: impl IntoIterator<Item = &'a str>, state: RowState) {
+        self.update(|screen| {
+            for hash in hashes {
+                screen.set_state(hash, state);
+            }
+        });
+    }
+
+    /// Marks the commit as failed, keeping the error chain as a note.
+    pub(crate) fn fail(&self, hash: &str, error: &anyhow::Error) {
+        self.update(|screen| {
+            screen.set_state(hash, RowState::Failed);
+            screen.add_note(hash, format!("error: {error:#}"));
+        });
+    }
+
+    pub(crate) fn set_status(&self, status: impl Into<String>) {
+        let status = status.into();
+        self.update(|screen| screen.status = status);
+    }
+
+    /// Runs the keyboard review. Returns the accepted `(hash, message)`
+    /// pairs, or `None` when the user quits.
+    pub(crate) fn review(&self) -> Result<Option<Vec<(String, String)>>> {
+        self.update(|screen| {
+            screen.reviewing = true;
+            screen.status.clear();
+        });
+        enable_raw_mode().context("Failed to enable raw mode")?;
+        let result = self.review_loop();
+        let _ = disable_raw_mode();
+        result
+    }
+
+    fn review_loop(&self) -> Result<Option<Vec<(String, String)>>> {
+        loop {
+            let key = match event::read().context("Failed to read terminal input")? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                Event::Resize(..) => {
+                    self.redraw();
+                    continue;
+                }
+                _ => continue,
+            };
+            let action = self.lock().handle_key(key);
+            match action {
+                Action::Continue => {}
+                Action::Edit => self.edit_selected()?,
+                Action::Apply => return Ok(Some(self.lock().accepted())),
+                Action::Quit => return Ok(None),
+            }
+            self.redraw();
+        }
+    }
+
+    /// Leaves the screen to edit the selected message in the user's editor.
+    fn edit_selected(&self) -> Result<()> {
+        let Some(editor) = std::env::var("OMNI_DEV_EDITOR")
+            .or_else(|_| std::env::var("EDITOR"))
+            .ok()
+            .filter(|e| !e.trim().is_empty())
+        else {
+            self.lock().status = "Set OMNI_DEV_EDITOR or EDITOR to edit messages".to_string();
+            return Ok(());
+        };
+        let message = self
+            .lock()
+            .selected_row()
+            .and_then(|row| row.proposed.clone())
+            .unwrap_or_default();
+        let file = tempfile::Builder::new()
+            .prefix("omni-dev-message-")
+            .suffix(".txt")
+            .tempfile()
+            .context("Failed to create a file for the message")?;
+        std::fs::write(file.path(), &message).context("Failed to write the message")?;
+
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let (program, args) = super::formatting::parse_editor_command(&editor);
+        let status = std::process::Command::new(program)
+            .args(args)
+            .arg(file.path())
+            .status();
+        execute!(io::stdout(), EnterAlternateScreen, Hide)
+            .context("Failed to return to the alternate screen")?;
+        enable_raw_mode().context("Failed to enable raw mode")?;
+
+        match status {
+            Ok(status) if status.success() => {
+                let edited = std::fs::read_to_string(file.path())
+                    .context("Failed to read the edited message")?;
+                let edited = edited.trim_end();
+                let mut screen = self.lock();
+                if edited.trim().is_empty() {
+                    screen.status = "Empty message; kept the previous one".to_string();
+                } else {
+                    screen.set_selected_message(format!("{edited}\n"));
+                }
+            }
+            Ok(status) => {
+                self.lock().status = format!("Editor exited with {status}; message unchanged");
+            }
+            Err(e) => self.lock().status = format!("Failed to run editor '{editor}': {e}"),
+        }
+        Ok(())
+    }
+
+    fn redraw(&self) {
+        let (width, height) = terminal::size().unwrap_or((80, 24));
+        let lines = self.lock().render(width.into(), height.into());
+        let _ = draw(&lines);
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    }
+}
+
+fn draw(lines: &[(Tone, String)]) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for (row, (tone, text)) in (0u16..).zip(lines) {
+        queue!(out, MoveTo(0, row), Clear(ClearType::CurrentLine))?;
+        match tone {
+            Tone::Normal => {}
+            Tone::Header => queue!(out, SetAttribute(Attribute::Bold))?,
+            Tone::Selected => queue!(out, SetAttribute(Attribute::Reverse))?,
+            Tone::Added => queue!(out, SetForegroundColor(Color::Green))?,
+            Tone::Removed => queue!(out, SetForegroundColor(Color::Red))?,
+            Tone::Dim => queue!(out, SetAttribute(Attribute::Dim))?,
+        }
+        queue!(out, Print(text), SetAttribute(Attribute::Reset), ResetColor)?;
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn review_screen() -> Screen {
+        let mut screen = Screen::new(
+            "twiddle",
+            [
+                ("a".repeat(40), "fix stuff\n".to_string()),
+                ("b".repeat(40), "feat(cli): add flag\n".to_string()),
+            ],
+        );
+        screen.propose(
+            &"a".repeat(40),
+            "fix(git): handle detached HEAD\n",
+            Decision::Accept,
+        );
+        screen.set_state("aaaaaaa", RowState::Done);
+        screen.set_state("bbbbbbb", RowState::Done);
+        screen.reviewing = true;
+        screen
+    }
+
+    #[test]
+    fn keys_accept_skip_and_apply() {
+        let mut screen = review_screen();
+        assert_eq!(screen.accepted().len(), 1);
+
+        assert_eq!(screen.handle_key(key(KeyCode::Char('s'))), Action::Continue);
+        assert!(screen.accepted().is_empty());
+        assert_eq!(screen.selected, 1, "a decision moves to the next commit");
+
+        // The second commit has no proposal, so it cannot be accepted.
+        screen.handle_key(key(KeyCode::Char('a')));
+        assert!(screen.accepted().is_empty());
+        assert!(screen.status.contains("No proposed message"));
+
+        screen.handle_key(key(KeyCode::Up));
+        screen.handle_key(key(KeyCode::Char('a')));
+        assert_eq!(screen.handle_key(key(KeyCode::Char('e'))), Action::Continue);
+        screen.handle_key(key(KeyCode::Char('k')));
+        assert_eq!(screen.handle_key(key(KeyCode::Char('e'))), Action::Edit);
+        screen.set_selected_message("fix: edited\n".to_string());
+        assert_eq!(screen.handle_key(key(KeyCode::Enter)), Action::Apply);
+        assert_eq!(
+            screen.accepted(),
+            vec![("a".repeat(40), "fix: edited\n".to_string())]
+        );
+        assert_eq!(screen.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+    }
+
+    #[test]
+    fn render_shows_progress_list_and_diff() {
+        let mut screen = review_screen();
+        screen.reviewing = false;
+        screen.set_state(&"b".repeat(40), RowState::Running);
+        let lines = screen.render(60, 12);
+        assert_eq!(lines.len(), 12);
+        assert_eq!(lines[0].1, "omni-dev twiddle · 1/2 commits");
+        assert!(
+            lines[1].1.contains("done    aaaaaaaa fix stuff"),
+            "{lines:?}"
+        );
+        assert!(lines[2].1.contains("running bbbbbbbb feat(cli): add flag"));
+        assert!(lines
+            .iter()
+            .any(|(tone, text)| *tone == Tone::Removed && text == "- fix stuff"));
+        assert!(
+            lines
+                .iter()
+                .any(|(tone, text)| *tone == Tone::Added
+                    && text == "+ fix(git): handle detached HEAD")
+        );
+        assert!(lines.iter().all(|(_, text)| text.chars().count() <= 60));
+
+        screen.reviewing = true;
+        let lines = screen.render(60, 12);
+        assert!(lines[1]
+            .1
+            .starts_with("> done    aaaaaaaa [accept] fix stuff"));
+        assert_eq!(lines[1].0, Tone::Selected);
+        assert!(lines[11].1.starts_with("j/k move"));
+    }
+
+    #[test]
+    fn render_scrolls_to_the_selection() {
+        let mut screen = Screen::new(
+            "check",
+            (0..20).map(|i| (format!("{i:040}"), format!("commit {i}\n"))),
+        );
+        screen.reviewing = true;
+        screen.selected = 15;
+        let lines = screen.render(80, 10);
+        assert!(lines.iter().any(|(_, text)| text.contains("commit 15")));
+        assert!(!lines.iter().any(|(_, text)| text.ends_with(" commit 0")));
+        assert_eq!(lines.len(), 10);
+    }
+}
@@ -2,10 +2,14 @@
 
 use anyhow::{Context, Result};
 use clap::Parser;
+use serde::Serialize;
 use tracing::debug;
 
-use crate::data::amendments::AmendmentFile;
+use super::defaults::AutoApply;
+use crate::data::amendments::{Amendment, AmendmentFile};
+use crate::data::output::{render, requested, Render, RenderFormat};
 use crate::data::RepositoryView;
+use crate::utils::env::SystemEnv;
 
 /// Twiddle command options.
 #[derive(Parser)]
@@ -14,9 +18,8 @@ pub struct TwiddleCommand {
     #[arg(value_name = "COMMIT_RANGE")]
     pub commit_range: Option<String>,
 
-    /// Skips confirmation prompt and applies amendments automatically.
-    #[arg(long)]
-    pub auto_apply: bool,
+    #[command(flatten)]
+    pub apply: AutoApply,
 
     /// Allows amending commits that already exist in remote main branches (rewrites published history).
     #[arg(long)]
@@ -26,6 +29,15 @@ pub struct TwiddleCommand {
     #[arg(long, value_name = "FILE")]
     pub save_only: Option<String>,
 
+    /// Applies amendments from a YAML file instead of generating them with
+    /// AI; `-` reads the file from stdin and applies it without prompting.
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["save_only", "no_ai", "fresh", "refine", "tui"]
+    )]
+    pub from_file: Option<String>,
+
     /// Uses additional project context for better suggestions (Phase 3).
     #[arg(long, default_value = "true")]
     pub use_context: bool,
@@ -46,9 +58,13 @@ pub struct TwiddleCommand {
     #[arg(long)]
     pub no_context: bool,
 
-    /// Maximum number of concurrent AI requests (default: 4).
-    #[arg(long, default_value = "4")]
-    pub concurrency: usize,
+    /// Includes the N best recent commit messages as style examples (unless .omni-dev/examples.md exists).
+    #[arg(long, value_name = "N")]
+    pub examples: Option<usize>,
+
+    /// Starting number of concurrent AI requests, adjusted for rate limits and latency (default: 4, or OMNI_DEV_CONCURRENCY).
+    #[arg(long)]
+    pub concurrency: Option<usize>,
 
     /// Deprecated: use --concurrency instead.
     #[arg(long, hide = true)]
@@ -62,6 +78,10 @@ pub struct TwiddleCommand {
     #[arg(long)]
     pub no_ai: bool,
 
+    /// Writes dependency-bump commit messages (lockfile-only or Renovate/Dependabot updates) from a template listing the updated packages, without the AI.
+    #[arg(long)]
+    pub no_ai_for_deps: bool,
+
     /// Ignores existing commit messages and generates fresh ones based solely on diffs.
     /// This is the default behavior.
     #[arg(long, conflicts_with = "refine")]
@@ -79,6 +99,69 @@ pub struct TwiddleCommand {
     /// Only shows errors/warnings, suppresses info-level output.
     #[arg(long)]
     pub quiet: bool,
+
+    /// Shows a full-screen view with live per-commit progress, then reviews
+    /// each proposed message with accept/edit/skip keys (needs a terminal).
+    #[arg(long, conflicts_with_all = ["auto_apply", "save_only", "no_ai", "quiet"])]
+    pub tui: bool,
+
+    /// Sends a desktop notification, and posts to OMNI_DEV_NOTIFY_WEBHOOK
+    /// when set, when the run finishes or stops for input.
+    #[arg(long)]
+    pub notify: bool,
+
+    /// Saves the analyzed repository view to this file, for `--update-view`.
+    #[arg(long, value_name = "FILE", conflicts_with = "update_view")]
+    pub save_view: Option<std::path::PathBuf>,
+
+    /// Updates the view saved in this file, analyzing only commits added or rewritten since, and saves it back.
+    #[arg(long, value_name = "FILE")]
+    pub update_view: Option<std::path::PathBuf>,
+
+    /// Resumes the interrupted run saved as job ID, sending only the commits it has no amendment for yet.
+    #[arg(long, value_name = "ID", conflicts_with_all = ["from_file", "no_ai"])]
+    pub resume_job: Option<String>,
+}
+
+/// How a twiddle run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TwiddleStatus {
+    /// The amendments were applied.
+    Applied,
+    /// The amendments were written to the `--save-only` file.
+    Saved,
+    /// The user declined the amendments.
+    Cancelled,
+    /// There was nothing to amend.
+    NothingToDo,
+}
+
+/// The end-of-run summary; a status line as text, or a document listing the
+/// amendments with the top-level `--output yaml|json`.
+#[derive(Serialize)]
+struct TwiddleSummary<'a> {
+    outcome: TwiddleStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saved_to: Option<&'a str>,
+    amendments: &'a [Amendment],
+    /// Whether the messages did not come from the AI (`--no-ai`,
+    /// `--from-file`), for the text form.
+    #[serde(skip)]
+    no_ai: bool,
+}
+
+impl Render for TwiddleSummary<'_> {
+    fn render_text(&self) -> Result<String> {
+        Ok(match self.outcome {
+            TwiddleStatus::Applied if self.no_ai => "✅ Commit messages applied successfully!",
+            TwiddleStatus::Applied => "✅ Commit messages improved successfully!",
+            TwiddleStatus::Saved => "💾 Amendments saved to file",
+            TwiddleStatus::Cancelled => "❌ Amendment cancelled by user",
+            TwiddleStatus::NothingToDo => "✨ No commits found to process!",
+        }
+        .to_string())
+    }
 }
 
 impl TwiddleCommand {
@@ -88,12 +171,44 @@ impl TwiddleCommand {
         !self.refine
     }
 
+    /// Maximum concurrent AI requests, once `execute` has resolved the
+    /// configured default into `concurrency`.
+    fn concurrency(&self) -> usize {
+        self.concurrency
+            .unwrap_or(super::defaults::DEFAULT_CONCURRENCY)
+    }
+
+    /// Prints the end-of-run summary in the format of the top-level
+    /// `--output` (text by default).
+    fn report_outcome(&self, outcome: TwiddleStatus, amendments: &AmendmentFile) -> Result<()> {
+        let summary = TwiddleSummary {
+            outcome,
+            saved_to: self.save_only.as_deref(),
+            amendments: &amendments.amendments,
+            no_ai: self.no_ai || self.from_file.is_some(),
+        };
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&summary, format)?);
+        Ok(())
+    }
+
     /// Executes the twiddle command with contextual intelligence.
     pub async fn execute(mut self, repo: Option<&std::path::Path>) -> Result<()> {
         // Resolve deprecated --batch-size into --concurrency
         if let Some(bs) = self.batch_size {
             eprintln!("warning: --batch-size is deprecated; use --concurrency instead");
-            self.concurrency = bs;
+            self.concurrency = Some(bs);
+        }
+        // Fill omitted flags from the configured defaults (CLI > project > global)
+        let settings = crate::utils::settings::SettingsEnv::load();
+        self.concurrency = Some(super::defaults::concurrency(self.concurrency, &settings));
+        self.commit_range = super::defaults::commit_range(self.commit_range.as_deref(), &settings);
+        self.apply = self.apply.with_defaults(&settings);
+        if self.tui {
+            super::tui::ensure_terminal()?;
+            // The review screen replaces the apply prompt, configured
+            // auto-apply included.
+            self.apply.auto_apply = false;
         }
 
         // Resolve the repo root once; every git, config, and scratch read below
@@ -111,6 +226,9 @@ impl TwiddleCommand {
         if self.no_ai {
             return self.execute_no_ai(repo_root).await;
         }
+        if let Some(source) = &self.from_file {
+            return self.execute_from_file(repo_root, source).await;
+        }
 
         // Preflight check: validate AI credentials before any processing.
         // Model/beta-header selection uses the global `--model`/`--beta-header`
@@ -129,7 +247,30 @@ impl TwiddleCommand {
         // Initialize Claude client
         let claude_client = crate::claude::create_default_claude_client(None, None).await?;
 
-        self.execute_with_client(repo_root, claude_client).await
+        let notifier = self
+            .notify
+            .then(|| crate::utils::notify::Notifier::from_env(&settings));
+        let result = self.execute_with_client(repo_root, claude_client).await;
+        if let Some(notifier) = notifier {
+            notifier
+                .send(&crate::utils::notify::Notification::finished(
+                    "twiddle", &result,
+                ))
+                .await;
+        }
+        result
+    }
+
+    /// With `--notify`, reports that the run is waiting at a prompt.
+    async fn notify_needs_input(&self, message: String) {
+        if self.notify {
+            let settings = crate::utils::settings::SettingsEnv::load();
+            crate::utils::notify::Notifier::from_env(&settings)
+                .send(&crate::utils::notify::Notification::needs_input(
+                    "twiddle", message,
+                ))
+                .await;
+        }
     }
 
     /// Test-injectable inner core of [`Self::execute`].
@@ -143,6 +284,8 @@ impl TwiddleCommand {
         repo_root: &std::path::Path,
         claude_client: crate::claude::client::ClaudeClient,
     ) -> Result<()> {
+        let claude_client = claude_client.with_dependency_bump_template(self.no_ai_for_deps);
+
         // Determine if contextual analysis should be used
         let use_contextual = self.use_context && !self.no_context;
 
@@ -171,13 +314,23 @@ impl TwiddleCommand {
             None
         };
 
-        // Refine detected scopes using file_patterns from scope definitions
-        let scope_defs = match &context {
-            Some(ctx) => ctx.project.valid_scopes.clone(),
-            None => self.load_check_scopes(repo_root),
+        // Refine detected scopes using file_patterns from scope definitions,
+        // and detected types against the project's commit types
+        let (scope_defs, commit_types) = match &context {
+            Some(ctx) => (
+                ctx.project.valid_scopes.clone(),
+                ctx.project.commit_types.clone(),
+            ),
+            None => (
+                self.load_check_scopes(repo_root),
+                self.load_check_types(repo_root),
+            ),
         };
         for commit in &mut full_repo_view.commits {
             commit.analysis.refine_scope(&scope_defs);
+            commit
+                .analysis
+                .refine_type(&commit.original_message, &commit_types);
         }
 
         // 4. Show context summary if available
@@ -209,25 +362,47 @@ impl TwiddleCommand {
         };
 
         refine_amendment_scopes(&mut amendments, &full_repo_view, &scope_defs);
-        {
-            use std::io::IsTerminal;
-            resolve_duplicate_amendments(
-                &mut amendments,
-                self.auto_apply,
-                std::io::stdin().is_terminal(),
-                &mut std::io::BufReader::new(std::io::stdin()),
-            )?;
-        }
+        resolve_duplicate_amendments(
+            &mut amendments,
+            self.apply.auto_apply || self.tui,
+            crate::utils::interactive::stdin_is_interactive(),
+            &mut std::io::BufReader::new(std::io::stdin()),
+        )?;
 
         // 6. Handle different output modes
-        if let Some(save_path) = self.save_only {
+        if let Some(save_path) = &self.save_only {
             amendments.save_to_file(save_path)?;
-            println!("💾 Amendments saved to file");
+            self.report_outcome(TwiddleStatus::Saved, &amendments)?;
             return Ok(());
         }
 
         // 7. Handle amendments
         if !amendments.amendments.is_empty() {
+            // With --tui, the review screen replaces the apply prompt below
+            if self.tui {
+                let screen = super::tui::Tui::enter(super::tui::Screen::new(
+                    "twiddle",
+                    full_repo_view
+                        .commits
+                        .iter()
+                        .map(|c| (c.hash.clone(), c.original_message.clone())),
+                ))?;
+                screen.set_state(
+                    full_repo_view.commits.iter().map(|c| c.hash.as_str()),
+                    super::tui::RowState::Done,
+                );
+                self.notify_needs_input(format!(
+                    "{} amendment(s) ready to review",
+                    amendments.amendments.len()
+                ))
+                .await;
+                if !review_amendments(&screen, &mut amendments)? {
+                    drop(screen);
+                    self.report_outcome(TwiddleStatus::Cancelled, &amendments)?;
+                    return Ok(());
+                }
+            }
+
             // Create temporary file for amendments
             let temp_dir = tempfile::tempdir()?;
             let amendments_file = temp_dir.path().join("twiddle_amendments.yaml");
@@ -235,16 +410,26 @@ impl TwiddleCommand {
 
             // Show file path and get user choice
             {
-                use std::io::IsTerminal;
-                if !self.auto_apply
+                if !self.apply.auto_apply
+                    && !self.tui
+                    && crate::utils::interactive::stdin_is_interactive()
+                {
+                    self.notify_needs_input(format!(
+                        "{} amendment(s) ready to apply",
+                        amendments.amendments.len()
+                    ))
+                    .await;
+                }
+                if !self.apply.auto_apply
+                    && !self.tui
                     && !self.handle_amendments_file(
                         &amendments_file,
                         &amendments,
-                        std::io::stdin().is_terminal(),
+                        crate::utils::interactive::stdin_is_interactive(),
                         &mut std::io::BufReader::new(std::io::stdin()),
                     )?
                 {
-                    println!("❌ Amendment cancelled by user");
+                    self.report_outcome(TwiddleStatus::Cancelled, &amendments)?;
                     return Ok(());
                 }
             }
@@ -252,14 +437,14 @@ impl TwiddleCommand {
             // 8. Apply amendments (re-read from file to capture any user edits)
             self.apply_amendments_from_file(repo_root, &amendments_file)
                 .await?;
-            println!("✅ Commit messages improved successfully!");
+            self.report_outcome(TwiddleStatus::Applied, &amendments)?;
 
             // 9. Run post-twiddle check if --check flag is set
             if self.check {
                 self.run_post_twiddle_check(repo_root).await?;
             }
         } else {
-            println!("✨ No commits found to process!");
+            self.report_outcome(TwiddleStatus::NothingToDo, &amendments)?;
         }
 
         Ok(())
@@ -282,9 +467,11 @@ impl TwiddleCommand {
         use std::sync::Arc;
 
         use crate::claude::batch;
+        use crate::claude::concurrency::AdaptiveLimiter;
+        use crate::claude::job::Job;
         use crate::claude::token_budget;
 
-        let concurrency = self.concurrency;
+        let concurrency = self.concurrency();
 
         // Show model information
         self.show_model_info_from_client(&claude_client)?;
@@ -309,13 +496,22 @@ impl TwiddleCommand {
             self.show_context_summary(ctx)?;
         }
 
-        // Refine scopes on all commits upfront
-        let scope_defs = match &context {
-            Some(ctx) => ctx.project.valid_scopes.clone(),
-            None => self.load_check_scopes(repo_root),
+        // Refine scopes and types on all commits upfront
+        let (scope_defs, commit_types) = match &context {
+            Some(ctx) => (
+                ctx.project.valid_scopes.clone(),
+                ctx.project.commit_types.clone(),
+            ),
+            None => (
+                self.load_check_scopes(repo_root),
+                self.load_check_types(repo_root),
+            ),
         };
         for commit in &mut full_repo_view.commits {
             commit.analysis.refine_scope(&scope_defs);
+            commit
+                .analysis
+                .refine_type(&commit.original_message, &commit_types);
         }
 
         // Plan batches based on token budget
@@ -334,38 +530,92 @@ impl TwiddleCommand {
         let batch_plan =
             batch::plan_batches(&full_repo_view.commits, &metadata, system_prompt_tokens);
 
-        if batch_plan.batches.len() < total_commits {
+        // Save progress as batches finish, so an interrupted run can resume
+        // without paying for them again; a resumed run keeps its saved plan
+        let hashes: Vec<String> = full_repo_view
+            .commits
+            .iter()
+            .map(|c| c.hash.clone())
+            .collect();
+        let job = if let Some(id) = &self.resume_job {
+            let job = Job::resume(repo_root, id, "twiddle", &hashes)?;
+            println!(
+                "♻️  Resuming job {id}: {} of {total_commits} commits already processed",
+                job.completed().len()
+            );
+            job
+        } else {
+            let batches = batch_plan
+                .batches
+                .iter()
+                .map(|batch| batch.commit_indices.clone())
+                .collect();
+            let job = Job::start(repo_root, "twiddle", hashes, batches)?;
+            println!(
+                "💾 Saving progress as job {id}; if interrupted, rerun with --resume-job {id}",
+                id = job.id()
+            );
+            job
+        };
+        let pending_batches = job.pending_batches();
+        let restored = job.completed();
+
+        if job.batch_count() < total_commits {
             println!(
                 "   📦 Grouped {} commits into {} batches by token budget",
                 total_commits,
-                batch_plan.batches.len()
+                job.batch_count()
+            );
+        }
+
+        // With --tui, progress goes to the full-screen view instead of lines
+        let screen = if self.tui {
+            Some(super::tui::Tui::enter(super::tui::Screen::new(
+                "twiddle",
+                full_repo_view
+                    .commits
+                    .iter()
+                    .map(|c| (c.hash.clone(), c.original_message.clone())),
+            ))?)
+        } else {
+            None
+        };
+        if let Some(screen) = &screen {
+            screen.set_state(
+                restored.iter().map(|a| a.commit.as_str()),
+                super::tui::RowState::Done,
             );
         }
 
         // Map phase: process batches in parallel
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(concurrency));
-        let completed = Arc::new(AtomicUsize::new(0));
+        let limiter = AdaptiveLimiter::new(concurrency);
+        let completed = Arc::new(AtomicUsize::new(restored.len()));
 
         let repo_ref = &full_repo_view;
         let client_ref = &claude_client;
         let context_ref = &context;
+        let screen_ref = screen.as_ref();
+        let job_ref = &job;
         let fresh = self.is_fresh();
 
-        let futs: Vec<_> = batch_plan
-            .batches
+        let futs: Vec<_> = pending_batches
             .iter()
-            .map(|batch| {
-                let sem = semaphore.clone();
+            .map(|batch_indices| {
+                let limiter = limiter.clone();
                 let completed = completed.clone();
-                let batch_indices = &batch.commit_indices;
 
                 async move {
-                    let _permit = sem
-                        .acquire()
-                        .await
-                        .map_err(|e| anyhow::anyhow!("semaphore closed: {e}"))?;
+                    let mut permit = limiter.acquire().await;
 
                     let batch_size = batch_indices.len();
+                    let batch_hashes = move || {
+                        batch_indices
+                            .iter()
+                            .map(move |&i| repo_ref.commits[i].hash.as_str())
+                    };
+                    if let Some(screen) = screen_ref {
+                        screen.set_state(batch_hashes(), super::tui::RowState::Running);
+                    }
 
                     // Create view for this batch
                     let batch_view = if batch_size == 1 {
@@ -389,11 +639,18 @@ impl TwiddleCommand {
                             .await
                     };
 
+                    permit.record(&result, batch_size);
+
                     match result {
                         Ok(amendment_file) => {
+                            job_ref.record_amendments(&amendment_file.amendments);
                             let done =
                                 completed.fetch_add(batch_size, Ordering::Relaxed) + batch_size;
-                            println!("   ✅ {done}/{total_commits} commits processed");
+                            if let Some(screen) = screen_ref {
+                                screen.set_state(batch_hashes(), super::tui::RowState::Done);
+                            } else {
+                                println!("   ✅ {done}/{total_commits} commits processed");
+                            }
 
                             let items: Vec<_> = amendment_file
                                 .amendments
@@ -407,9 +664,15 @@ impl TwiddleCommand {
                         }
                         Err(e) if batch_size > 1 => {
                             // Split-and-retry: fall back to individual commits
-                            eprintln!(
-                                "warning: batch of {batch_size} failed, retrying individually: {e}"
-                            );
+                            if let Some(screen) = screen_ref {
+                                screen.set_status(format!(
+                                    "Batch of {batch_size} failed, retrying individually: {e}"
+                                ));
+                            } else {
+                                eprintln!(
+                                    "warning: batch of {batch_size} failed, retrying individually: {e}"
+                                );
+                            }
                             let mut items = Vec::new();
                             let mut failed_indices = Vec::new();
                             for &idx in batch_indices {
@@ -428,22 +691,38 @@ impl TwiddleCommand {
                                         .generate_amendments_with_options(&single_view, fresh)
                                         .await
                                 };
+                                permit.record(&single_result, 1);
                                 match single_result {
                                     Ok(af) => {
+                                        job_ref.record_amendments(af.amendments.first());
                                         if let Some(a) = af.amendments.into_iter().next() {
                                             let summary = a.summary.clone();
                                             items.push((a, summary));
                                         }
                                         let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
-                                        println!("   ✅ {done}/{total_commits} commits processed");
+                                        if let Some(screen) = screen_ref {
+                                            screen.set_state(
+                                                [repo_ref.commits[idx].hash.as_str()],
+                                                super::tui::RowState::Done,
+                                            );
+                                        } else {
+                                            println!(
+                                                "   ✅ {done}/{total_commits} commits processed"
+                                            );
+                                        }
                                     }
                                     Err(e) => {
+                                        job_ref.record_failure(&repo_ref.commits[idx].hash, &e);
+                                        failed_indices.push(idx);
+                                        if let Some(screen) = screen_ref {
+                                            screen.fail(&repo_ref.commits[idx].hash, &e);
+                                            continue;
+                                        }
                                         eprintln!("warning: failed to process commit: {e}");
                                         // Print the full error chain for debugging using anyhow's chain()
                                         for (i, cause) in e.chain().skip(1).enumerate() {
                                             eprintln!("  caused by [{i}]: {cause}");
                                         }
-                                        failed_indices.push(idx);
                                         println!("   ❌ commit processing failed");
                                     }
                                 }
@@ -453,12 +732,17 @@ impl TwiddleCommand {
                         Err(e) => {
                             // Single-commit batch failed; record the index so the user can retry
                             let idx = batch_indices[0];
+                            job_ref.record_failure(&repo_ref.commits[idx].hash, &e);
+                            let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                            if let Some(screen) = screen_ref {
+                                screen.fail(&repo_ref.commits[idx].hash, &e);
+                                return Ok((vec![], vec![idx]));
+                            }
                             eprintln!("warning: failed to process commit: {e}");
                             // Print the full error chain for debugging using anyhow's chain()
                             for (i, cause) in e.chain().skip(1).enumerate() {
                                 eprintln!("  caused by [{i}]: {cause}");
                             }
-                            let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
                             println!("   ❌ {done}/{total_commits} commits processed (failed)");
                             Ok((vec![], vec![idx]))
                         }
@@ -469,11 +753,17 @@ impl TwiddleCommand {
 
         let results = futures::future::join_all(futs).await;
 
-        // Flatten batch results
-        let mut successes: Vec<(crate::data::amendments::Amendment, String)> = Vec::new();
+        // Flatten batch results after those of the resumed job
+        let mut successes: Vec<(crate::data::amendments::Amendment, String)> = restored
+            .into_iter()
+            .map(|a| {
+                let summary = a.summary.clone();
+                (a, summary)
+            })
+            .collect();
         let mut failed_indices: Vec<usize> = Vec::new();
 
-        for (result, batch) in results.into_iter().zip(&batch_plan.batches) {
+        for (result, batch) in results.into_iter().zip(&pending_batches) {
             match result {
                 Ok((items, failed)) => {
                     successes.extend(items);
@@ -481,14 +771,28 @@ impl TwiddleCommand {
                 }
                 Err(e) => {
                     eprintln!("warning: batch processing error: {e}");
-                    failed_indices.extend(&batch.commit_indices);
+                    failed_indices.extend(batch);
                 }
             }
         }
 
-        // Offer interactive retry for commits that failed
-        if !failed_indices.is_empty() && !self.quiet {
-            use std::io::IsTerminal;
+        // Offer interactive retry for commits that failed (the full-screen
+        // view lists them as failed instead)
+        if let Some(screen) = &screen {
+            if !failed_indices.is_empty() {
+                screen.set_status(format!(
+                    "{} commit(s) failed to process",
+                    failed_indices.len()
+                ));
+            }
+        } else if !failed_indices.is_empty() && !self.quiet {
+            if crate::utils::interactive::stdin_is_interactive() {
+                self.notify_needs_input(format!(
+                    "{} commit(s) failed; retry, skip, or abort",
+                    failed_indices.len()
+                ))
+                .await;
+            }
             self.run_interactive_retry_generate_amendments(
                 &mut failed_indices,
                 &full_repo_view,
@@ -496,7 +800,7 @@ impl TwiddleCommand {
                 context.as_ref(),
                 fresh,
                 &mut successes,
-                std::io::stdin().is_terminal(),
+                crate::utils::interactive::stdin_is_interactive(),
                 &mut std::io::BufReader::new(std::io::stdin()),
             )
             .await?;
@@ -507,7 +811,7 @@ impl TwiddleCommand {
             );
         }
 
-        if !failed_indices.is_empty() {
+        if !failed_indices.is_empty() && screen.is_none() {
             eprintln!(
                 "warning: {} commit(s) ultimately failed to process",
                 failed_indices.len()
@@ -520,44 +824,69 @@ impl TwiddleCommand {
 
         // Reduce phase: optional coherence pass
         // Skip when all commits were in a single batch (AI already saw them together)
-        let single_batch = batch_plan.batches.len() <= 1;
+        let single_batch = job.batch_count() <= 1;
         let mut all_amendments = if !self.no_coherence && !single_batch && successes.len() >= 2 {
-            println!("🔗 Running cross-commit coherence pass...");
+            if let Some(screen) = &screen {
+                screen.set_status("Running cross-commit coherence pass…");
+            } else {
+                println!("🔗 Running cross-commit coherence pass...");
+            }
             match claude_client.refine_amendments_coherence(&successes).await {
                 Ok(refined) => refined,
                 Err(e) => {
-                    eprintln!("warning: coherence pass failed, using individual results: {e}");
+                    if screen.is_none() {
+                        eprintln!("warning: coherence pass failed, using individual results: {e}");
+                    }
                     AmendmentFile {
+                        schema_version: crate::data::SCHEMA_VERSION,
                         amendments: successes.into_iter().map(|(a, _)| a).collect(),
                     }
                 }
             }
         } else {
             AmendmentFile {
+                schema_version: crate::data::SCHEMA_VERSION,
                 amendments: successes.into_iter().map(|(a, _)| a).collect(),
             }
         };
 
         refine_amendment_scopes(&mut all_amendments, &full_repo_view, &scope_defs);
-        {
-            use std::io::IsTerminal;
-            resolve_duplicate_amendments(
-                &mut all_amendments,
-                self.auto_apply,
-                std::io::stdin().is_terminal(),
-                &mut std::io::BufReader::new(std::io::stdin()),
-            )?;
+        resolve_duplicate_amendments(
+            &mut all_amendments,
+            self.apply.auto_apply || screen.is_some(),
+            crate::utils::interactive::stdin_is_interactive(),
+            &mut std::io::BufReader::new(std::io::stdin()),
+        )?;
+
+        // With --tui, the review screen replaces the apply prompt below
+        if let Some(screen) = screen {
+            if !all_amendments.amendments.is_empty() {
+                self.notify_needs_input(format!(
+                    "{} amendment(s) ready to review",
+                    all_amendments.amendments.len()
+                ))
+                .await;
+            }
+            if !all_amendments.amendments.is_empty()
+                && !review_amendments(&screen, &mut all_amendments)?
+            {
+                drop(screen);
+                job.discard();
+                self.report_outcome(TwiddleStatus::Cancelled, &all_amendments)?;
+                return Ok(());
+            }
+        } else {
+            println!(
+                "✅ All commits processed! Found {} amendments.",
+                all_amendments.amendments.len()
+            );
         }
 
-        println!(
-            "✅ All commits processed! Found {} amendments.",
-            all_amendments.amendments.len()
-        );
-
         // Handle different output modes
         if let Some(save_path) = &self.save_only {
             all_amendments.save_to_file(save_path)?;
-            println!("💾 Amendments saved to file");
+            job.discard();
+            self.report_outcome(TwiddleStatus::Saved, &all_amendments)?;
             return Ok(());
         }
 
@@ -568,29 +897,42 @@ impl TwiddleCommand {
             all_amendments.save_to_file(&amendments_file)?;
 
             {
-                use std::io::IsTerminal;
-                if !self.auto_apply
+                if !self.apply.auto_apply
+                    && !self.tui
+                    && crate::utils::interactive::stdin_is_interactive()
+                {
+                    self.notify_needs_input(format!(
+                        "{} amendment(s) ready to apply",
+                        all_amendments.amendments.len()
+                    ))
+                    .await;
+                }
+                if !self.apply.auto_apply
+                    && !self.tui
                     && !self.handle_amendments_file(
                         &amendments_file,
                         &all_amendments,
-                        std::io::stdin().is_terminal(),
+                        crate::utils::interactive::stdin_is_interactive(),
                         &mut std::io::BufReader::new(std::io::stdin()),
                     )?
                 {
-                    println!("❌ Amendment cancelled by user");
+                    job.discard();
+                    self.report_outcome(TwiddleStatus::Cancelled, &all_amendments)?;
                     return Ok(());
                 }
             }
 
             self.apply_amendments_from_file(repo_root, &amendments_file)
                 .await?;
-            println!("✅ Commit messages improved successfully!");
+            job.discard();
+            self.report_outcome(TwiddleStatus::Applied, &all_amendments)?;
 
             if self.check {
                 self.run_post_twiddle_check(repo_root).await?;
             }
         } else {
-            println!("✨ No commits found to process!");
+            job.discard();
+            self.report_outcome(TwiddleStatus::NothingToDo, &all_amendments)?;
         }
 
         Ok(())
@@ -601,10 +943,7 @@ impl TwiddleCommand {
         &self,
         repo_root: &std::path::Path,
     ) -> Result<crate::data::RepositoryView> {
-        use crate::data::{
-            AiInfo, BranchInfo, FieldExplanation, FileStatusInfo, RepositoryView, VersionInfo,
-            WorkingDirectoryInfo,
-        };
+        use crate::data::{AiInfo, BranchInfo, FieldExplanation, RepositoryView, VersionInfo};
         use crate::git::{GitRepository, RemoteInfo};
         use crate::utils::ai_scratch;
 
@@ -619,25 +958,15 @@ impl TwiddleCommand {
             .get_current_branch()
             .unwrap_or_else(|_| "HEAD".to_string());
 
-        // Get working directory status
-        let wd_status = repo.get_working_directory_status()?;
-        let working_directory = WorkingDirectoryInfo {
-            clean: wd_status.clean,
-            untracked_changes: wd_status
-                .untracked_changes
-                .into_iter()
-                .map(|fs| FileStatusInfo {
-                    status: fs.status,
-                    file: fs.file,
-                })
-                .collect(),
-        };
+        // Scan the working directory while the commits are extracted
+        let working_directory = super::spawn_working_directory_scan(repo_root);
 
         // Get remote information
         let remotes = RemoteInfo::get_all_remotes(repo.repository())?;
 
-        // Parse commit range and get commits
-        let commits = repo.get_commits_in_range(commit_range)?;
+        // Parse commit range and get commits, reusing a saved view's analyses
+        let known = super::saved_view_commits(self.update_view.as_deref())?;
+        let commits = repo.get_commits_in_range_reusing(commit_range, &known)?;
 
         // Create version information
         let versions = Some(VersionInfo {
@@ -653,13 +982,15 @@ impl TwiddleCommand {
 
         // Build repository view with branch info
         let mut repo_view = RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions,
             explanation: FieldExplanation::default(),
-            working_directory,
+            working_directory: working_directory.await?,
             remotes,
             ai: ai_info,
             branch_info: Some(BranchInfo {
                 branch: current_branch,
+                merge_conflicts: None,
             }),
             pr_template: None,
             pr_template_location: None,
@@ -670,6 +1001,10 @@ impl TwiddleCommand {
         // Update field presence based on actual data
         repo_view.update_field_presence();
 
+        if let Some(path) = self.save_view.as_deref().or(self.update_view.as_deref()) {
+            repo_view.save_to_file(path)?;
+        }
+
         Ok(repo_view)
     }
 
@@ -686,11 +1021,12 @@ impl TwiddleCommand {
     ) -> Result<bool> {
         use std::io::{self, Write};
 
-        println!(
-            "\n📝 Found {} commits that could be improved.",
-            amendments.amendments.len()
-        );
-        println!("💾 Amendments saved to: {}", amendments_file.display());
+        use crate::utils::i18n::tr;
+
+        let count = amendments.amendments.len();
+        println!("\n📝 {}", tr("twiddle-found", &[("count", &count)]));
+        let path = amendments_file.display();
+        println!("💾 {}", tr("twiddle-saved", &[("path", &path)]));
         println!();
 
         if !is_terminal {
@@ -699,7 +1035,7 @@ impl TwiddleCommand {
         }
 
         loop {
-            print!("❓ [A]pply amendments, [S]how file, [E]dit file, or [Q]uit? [A/s/e/q] ");
+            print!("❓ {} ", tr("twiddle-menu", &[]));
             io::stdout().flush()?;
 
             let Some(input) = super::read_interactive_line(reader)? else {
@@ -719,9 +1055,7 @@ impl TwiddleCommand {
                 }
                 "q" | "quit" => return Ok(false),
                 _ => {
-                    println!(
-                        "Invalid choice. Please enter 'a' to apply, 's' to show, 'e' to edit, or 'q' to quit."
-                    );
+                    println!("{}", tr("twiddle-invalid-choice", &[]));
                 }
             }
         }
@@ -731,16 +1065,12 @@ impl TwiddleCommand {
     fn show_amendments_file(&self, amendments_file: &std::path::Path) -> Result<()> {
         use std::fs;
 
-        println!("\n📄 Amendments file contents:");
-        println!("─────────────────────────────");
-
         let contents =
             fs::read_to_string(amendments_file).context("Failed to read amendments file")?;
 
-        println!("{contents}");
-        println!("─────────────────────────────");
-
-        Ok(())
+        crate::utils::pager::page(&format!(
+            "\n📄 Amendments file contents:\n─────────────────────────────\n{contents}\n─────────────────────────────"
+        ))
     }
 
     /// Opens the amendments file in an external editor.
@@ -824,6 +1154,7 @@ impl TwiddleCommand {
         repo_root: &std::path::Path,
         repo_view: &crate::data::RepositoryView,
     ) -> Result<crate::data::context::CommitContext> {
+        use crate::claude::context::budget::{ContextBudget, ContextTarget};
         use crate::claude::context::{
             BranchAnalyzer, FileAnalyzer, ProjectDiscovery, WorkPatternAnalyzer,
         };
@@ -856,21 +1187,41 @@ impl TwiddleCommand {
         }
 
         // 2. Analyze current branch from repository view
-        if let Some(branch_info) = &repo_view.branch_info {
-            context.branch = BranchAnalyzer::analyze(&branch_info.branch).unwrap_or_default();
+        let current_branch = if let Some(branch_info) = &repo_view.branch_info {
+            branch_info.branch.clone()
         } else {
             // Fallback to getting current branch directly if not in repo view
             use crate::git::GitRepository;
             let repo = GitRepository::open_at(repo_root)?;
-            let current_branch = repo
-                .get_current_branch()
-                .unwrap_or_else(|_| "HEAD".to_string());
-            context.branch = BranchAnalyzer::analyze(&current_branch).unwrap_or_default();
+            repo.get_current_branch()
+                .unwrap_or_else(|_| "HEAD".to_string())
+        };
+        context.branch =
+            BranchAnalyzer::analyze_with(&current_branch, &context.project.branch_conventions)
+                .unwrap_or_default();
+
+        // 2.5. Fetch the referenced ticket when enrichment is enabled
+        if let Some(ticket_id) = &context.branch.ticket_id {
+            context.branch.ticket =
+                crate::claude::context::ticket::fetch_ticket_context(ticket_id).await;
+        }
+
+        // 2.6. Pull in review feedback from the branch's open PR, if any
+        if context.branch.is_feature_branch {
+            context.branch.review_comments =
+                crate::claude::context::review::fetch_branch_review_comments(
+                    &current_branch,
+                    repo_root,
+                )
+                .await;
         }
 
         // 3. Analyze commit range patterns
         if !repo_view.commits.is_empty() {
-            context.range = WorkPatternAnalyzer::analyze_commit_range(&repo_view.commits);
+            context.range = WorkPatternAnalyzer::analyze_commit_range_with(
+                &repo_view.commits,
+                &context.project.work_pattern_rules,
+            );
         }
 
         // 3.5. Analyze file-level context
@@ -878,6 +1229,27 @@ impl TwiddleCommand {
             context.files = FileAnalyzer::analyze_commits(&repo_view.commits);
         }
 
+        // 3.6. Pick house-style examples from history when none are curated
+        let example_count = self
+            .examples
+            .unwrap_or_else(crate::claude::context::examples::count_from_settings);
+        if context.project.commit_examples.is_empty() && example_count > 0 {
+            let exclude = repo_view
+                .commits
+                .iter()
+                .map(|commit| commit.hash.clone())
+                .collect();
+            let repo = crate::git::GitRepository::open_at(repo_root)?;
+            match crate::claude::context::examples::best_recent_messages(
+                repo.repository(),
+                example_count,
+                &exclude,
+            ) {
+                Ok(examples) => context.project.commit_examples = examples,
+                Err(e) => debug!(error = %e, "Could not pick commit examples from history"),
+            }
+        }
+
         // 4. Apply user-provided context overrides
         if let Some(ref work_ctx) = self.work_context {
             context.user_provided = Some(work_ctx.clone());
@@ -887,6 +1259,11 @@ impl TwiddleCommand {
             context.branch.description.clone_from(branch_ctx);
         }
 
+        // 5. Trim the project context to its token budget, if one is set
+        if let Some(budget) = ContextBudget::from_settings() {
+            context.trimmed = budget.apply(&mut context, ContextTarget::Commits);
+        }
+
         Ok(context)
     }
 
@@ -908,15 +1285,32 @@ impl TwiddleCommand {
                 "   🌿 Branch: {} ({})",
                 context.branch.description, context.branch.work_type
             );
-            if let Some(ref ticket) = context.branch.ticket_id {
-                println!("   🎫 Ticket: {ticket}");
+            match (&context.branch.ticket, &context.branch.ticket_id) {
+                (Some(ticket), _) => println!("   🎫 Ticket: {} — {}", ticket.id, ticket.title),
+                (None, Some(ticket)) => println!("   🎫 Ticket: {ticket}"),
+                (None, None) => {}
             }
         }
+        if !context.branch.review_comments.is_empty() {
+            println!(
+                "   💬 Review feedback: {} comments",
+                context.branch.review_comments.len()
+            );
+        }
+        if !context.project.commit_examples.is_empty() {
+            println!(
+                "   🖋️  Style examples: {} commit messages",
+                context.project.commit_examples.len()
+            );
+        }
 
         // Work pattern
         if let Some(label) = format_work_pattern(&context.range.work_pattern) {
             println!("   {label}");
         }
+        for pattern in &context.range.project_patterns {
+            println!("   🧩 Project pattern: {}", pattern.name);
+        }
 
         // File analysis
         if let Some(label) = super::formatting::format_file_analysis(&context.files) {
@@ -934,6 +1328,11 @@ impl TwiddleCommand {
             println!("   👤 User context: {user_ctx}");
         }
 
+        // Context trimmed to the token budget
+        for trimmed in &context.trimmed {
+            println!("   ✂️  Context budget: {trimmed}");
+        }
+
         println!();
         Ok(())
     }
@@ -1043,8 +1442,6 @@ impl TwiddleCommand {
 
     /// Executes the twiddle command without AI, creating amendments with original messages.
     async fn execute_no_ai(&self, repo_root: &std::path::Path) -> Result<()> {
-        use crate::data::amendments::{Amendment, AmendmentFile};
-
         println!("📋 Generating amendments YAML without AI processing...");
 
         // Generate repository view to get all commits
@@ -1061,12 +1458,15 @@ impl TwiddleCommand {
             })
             .collect();
 
-        let amendment_file = AmendmentFile { amendments };
+        let amendment_file = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
+            amendments,
+        };
 
         // Handle different output modes
         if let Some(save_path) = &self.save_only {
             amendment_file.save_to_file(save_path)?;
-            println!("💾 Amendments saved to file");
+            self.report_outcome(TwiddleStatus::Saved, &amendment_file)?;
             return Ok(());
         }
 
@@ -1079,16 +1479,15 @@ impl TwiddleCommand {
 
             // Show file path and get user choice
             {
-                use std::io::IsTerminal;
-                if !self.auto_apply
+                if !self.apply.auto_apply
                     && !self.handle_amendments_file(
                         &amendments_file,
                         &amendment_file,
-                        std::io::stdin().is_terminal(),
+                        crate::utils::interactive::stdin_is_interactive(),
                         &mut std::io::BufReader::new(std::io::stdin()),
                     )?
                 {
-                    println!("❌ Amendment cancelled by user");
+                    self.report_outcome(TwiddleStatus::Cancelled, &amendment_file)?;
                     return Ok(());
                 }
             }
@@ -1096,16 +1495,54 @@ impl TwiddleCommand {
             // Apply amendments (re-read from file to capture any user edits)
             self.apply_amendments_from_file(repo_root, &amendments_file)
                 .await?;
-            println!("✅ Commit messages applied successfully!");
+            self.report_outcome(TwiddleStatus::Applied, &amendment_file)?;
 
             // Run post-twiddle check if --check flag is set
             if self.check {
                 self.run_post_twiddle_check(repo_root).await?;
             }
         } else {
-            println!("✨ No commits found to process!");
+            self.report_outcome(TwiddleStatus::NothingToDo, &amendment_file)?;
+        }
+
+        Ok(())
+    }
+
+    /// Applies the amendments named by `--from-file`.
+    ///
+    /// A file goes through the usual apply prompt; amendments piped in on
+    /// stdin are applied directly, since stdin is then the document rather
+    /// than a terminal that could answer the prompt.
+    async fn execute_from_file(&self, repo_root: &std::path::Path, source: &str) -> Result<()> {
+        crate::utils::check_working_directory_clean_at(repo_root)?;
+        let amendment_file = AmendmentFile::load_from_source(source)?;
+        if amendment_file.amendments.is_empty() {
+            self.report_outcome(TwiddleStatus::NothingToDo, &amendment_file)?;
+            return Ok(());
+        }
+
+        let temp_dir = tempfile::tempdir()?;
+        let amendments_file = temp_dir.path().join("twiddle_amendments.yaml");
+        amendment_file.save_to_file(&amendments_file)?;
+        if !self.apply.auto_apply
+            && source != "-"
+            && !self.handle_amendments_file(
+                &amendments_file,
+                &amendment_file,
+                crate::utils::interactive::stdin_is_interactive(),
+                &mut std::io::BufReader::new(std::io::stdin()),
+            )?
+        {
+            self.report_outcome(TwiddleStatus::Cancelled, &amendment_file)?;
+            return Ok(());
         }
 
+        self.apply_amendments_from_file(repo_root, &amendments_file)
+            .await?;
+        self.report_outcome(TwiddleStatus::Applied, &amendment_file)?;
+        if self.check {
+            self.run_post_twiddle_check(repo_root).await?;
+        }
         Ok(())
     }
 
@@ -1113,12 +1550,29 @@ impl TwiddleCommand {
     /// If the check finds errors with suggestions, automatically applies the
     /// suggestions and re-checks, up to 3 retries.
     async fn run_post_twiddle_check(&self, repo_root: &std::path::Path) -> Result<()> {
+        use crate::claude::check_cache::{self, CheckCache, CheckCacheKeyer};
+
         const MAX_CHECK_RETRIES: u32 = 3;
 
-        // Load guidelines, scopes, and Claude client once (they don't change between retries)
+        // Load guidelines, scopes, types, and Claude client once (they don't
+        // change between retries)
         let guidelines = self.load_check_guidelines(repo_root)?;
         let valid_scopes = self.load_check_scopes(repo_root);
-        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        let claude_client = crate::claude::create_default_claude_client(None, None)
+            .await?
+            .with_commit_types(self.load_check_types(repo_root));
+
+        // Commits left untouched by the amendments keep their hashes, so their
+        // verdicts (and those from an earlier `check`) come from the cache.
+        let cache = CheckCache::open_default();
+        let keyer = CheckCacheKeyer::new(
+            guidelines.as_deref(),
+            &valid_scopes,
+            claude_client.commit_types(),
+            &claude_client.get_ai_client_metadata().model,
+            true,
+            !self.no_coherence,
+        );
 
         for attempt in 0..=MAX_CHECK_RETRIES {
             println!();
@@ -1141,36 +1595,57 @@ impl TwiddleCommand {
             // Refine detected scopes using file_patterns from scope definitions
             for commit in &mut repo_view.commits {
                 commit.analysis.refine_scope(&valid_scopes);
+                commit
+                    .analysis
+                    .refine_type(&commit.original_message, claude_client.commit_types());
             }
 
             if attempt == 0 {
                 self.show_check_guidance_files_status(repo_root, &guidelines, &valid_scopes);
             }
 
-            // Run check
-            let report = if repo_view.commits.len() > 1 {
+            // Run check on the commits without a cached verdict
+            let commit_order: Vec<String> =
+                repo_view.commits.iter().map(|c| c.hash.clone()).collect();
+            let (cached_results, pending_hashes) =
+                check_cache::partition(cache.as_ref(), &keyer, &commit_order);
+            let mut pending_view = repo_view.clone();
+            pending_view
+                .commits
+                .retain(|c| pending_hashes.contains(&c.hash));
+
+            let fresh_report = if pending_view.commits.len() > 1 {
                 println!(
                     "🔄 Checking {} commits in parallel...",
-                    repo_view.commits.len()
+                    pending_view.commits.len()
                 );
                 self.check_commits_map_reduce(
                     &claude_client,
-                    &repo_view,
+                    &pending_view,
                     guidelines.as_deref(),
                     &valid_scopes,
                 )
                 .await?
-            } else {
+            } else if pending_view.commits.len() == 1 {
                 println!("🤖 Analyzing commits with AI...");
                 claude_client
                     .check_commits_with_scopes(
-                        &repo_view,
+                        &pending_view,
                         guidelines.as_deref(),
                         &valid_scopes,
                         true,
                     )
                     .await?
+            } else {
+                crate::data::check::CheckReport::new(Vec::new())
             };
+            let report = check_cache::store_and_merge(
+                cache.as_ref(),
+                &keyer,
+                &commit_order,
+                cached_results,
+                fresh_report,
+            );
 
             // Output text report
             self.output_check_text_report(&report)?;
@@ -1208,7 +1683,10 @@ impl TwiddleCommand {
                 "🔄 Applying {} suggested fix(es) and re-checking...",
                 amendments.len()
             );
-            let amendment_file = AmendmentFile { amendments };
+            let amendment_file = AmendmentFile {
+                schema_version: crate::data::SCHEMA_VERSION,
+                amendments,
+            };
             let temp_file = tempfile::NamedTempFile::new()
                 .context("Failed to create temp file for retry amendments")?;
             amendment_file
@@ -1266,6 +1744,16 @@ impl TwiddleCommand {
         crate::claude::context::load_project_scopes(&context_dir, repo_root)
     }
 
+    /// Loads the project's commit types (empty when the built-in types apply).
+    fn load_check_types(
+        &self,
+        repo_root: &std::path::Path,
+    ) -> Vec<crate::data::context::TypeDefinition> {
+        let context_dir =
+            crate::claude::context::resolve_context_dir_at(self.context_dir.as_deref(), repo_root);
+        crate::claude::context::load_project_types(&context_dir)
+    }
+
     /// Shows guidance files status for check.
     fn show_check_guidance_files_status(
         &self,
@@ -1323,6 +1811,7 @@ impl TwiddleCommand {
         use std::sync::Arc;
 
         use crate::claude::batch;
+        use crate::claude::concurrency::AdaptiveLimiter;
         use crate::claude::token_budget;
         use crate::data::check::{CheckReport, CommitCheckResult};
 
@@ -1330,9 +1819,10 @@ impl TwiddleCommand {
 
         // Plan batches based on token budget
         let metadata = claude_client.get_ai_client_metadata();
-        let system_prompt = crate::claude::prompts::generate_check_system_prompt_with_scopes(
+        let system_prompt = crate::claude::prompts::generate_check_system_prompt_with_types(
             guidelines,
             valid_scopes,
+            claude_client.commit_types(),
         );
         let system_prompt_tokens = token_budget::estimate_tokens(&system_prompt);
         let batch_plan =
@@ -1346,22 +1836,19 @@ impl TwiddleCommand {
             );
         }
 
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(self.concurrency));
+        let limiter = AdaptiveLimiter::new(self.concurrency());
         let completed = Arc::new(AtomicUsize::new(0));
 
         let futs: Vec<_> = batch_plan
             .batches
             .iter()
             .map(|batch| {
-                let sem = semaphore.clone();
+                let limiter = limiter.clone();
                 let completed = completed.clone();
                 let batch_indices = &batch.commit_indices;
 
                 async move {
-                    let _permit = sem
-                        .acquire()
-                        .await
-                        .map_err(|e| anyhow::anyhow!("semaphore closed: {e}"))?;
+                    let mut permit = limiter.acquire().await;
 
                     let batch_size = batch_indices.len();
 
@@ -1379,6 +1866,8 @@ impl TwiddleCommand {
                         .check_commits_with_scopes(&batch_view, guidelines, valid_scopes, true)
                         .await;
 
+                    permit.record(&result, batch_size);
+
                     match result {
                         Ok(report) => {
                             let done =
@@ -1412,6 +1901,7 @@ impl TwiddleCommand {
                                         true,
                                     )
                                     .await;
+                                permit.record(&single_result, 1);
                                 match single_result {
                                     Ok(report) => {
                                         if let Some(r) = report.commits.into_iter().next() {
@@ -1463,8 +1953,7 @@ impl TwiddleCommand {
 
         // Offer interactive retry for commits that failed
         if !failed_indices.is_empty() && !self.quiet {
-            use std::io::IsTerminal;
-            if std::io::stdin().is_terminal() {
+            if crate::utils::interactive::stdin_is_interactive() {
                 self.run_interactive_retry_twiddle_check(
                     &mut failed_indices,
                     full_repo_view,
@@ -1847,7 +2336,10 @@ pub(crate) async fn run_twiddle_with_client(
     let commits = repo.get_commits_in_range(resolved_range)?;
 
     if commits.is_empty() {
-        let empty_file = AmendmentFile { amendments: vec![] };
+        let empty_file = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
+            amendments: vec![],
+        };
         let yaml =
             crate::data::to_yaml(&empty_file).context("Failed to serialise empty AmendmentFile")?;
         return Ok(TwiddleOutcome {
@@ -1864,6 +2356,7 @@ pub(crate) async fn run_twiddle_with_client(
     };
 
     let mut repo_view = RepositoryView {
+        schema_version: crate::data::SCHEMA_VERSION,
         versions: Some(VersionInfo {
             omni_dev: env!("CARGO_PKG_VERSION").to_string(),
         }),
@@ -1873,6 +2366,7 @@ pub(crate) async fn run_twiddle_with_client(
         ai: ai_info,
         branch_info: Some(BranchInfo {
             branch: current_branch,
+            merge_conflicts: None,
         }),
         pr_template: None,
         pr_template_location: None,
@@ -2221,22 +2715,30 @@ mod execute_tests {
     fn make_cmd(commit_range: &str, save_path: std::path::PathBuf) -> TwiddleCommand {
         TwiddleCommand {
             commit_range: Some(commit_range.to_string()),
-            auto_apply: false,
+            apply: AutoApply { auto_apply: false },
             allow_pushed: false,
             save_only: Some(save_path.to_string_lossy().into_owned()),
+            from_file: None,
             use_context: false,
             context_dir: None,
             work_context: None,
             branch_context: None,
             no_context: true,
-            concurrency: 1,
+            examples: None,
+            concurrency: Some(1),
             batch_size: None,
             no_coherence: true,
             no_ai: false,
+            no_ai_for_deps: false,
             fresh: false,
             refine: false,
             check: false,
             quiet: true,
+            tui: false,
+            notify: false,
+            save_view: None,
+            update_view: None,
+            resume_job: None,
         }
     }
 
@@ -2376,22 +2878,30 @@ mod execute_tests {
         let save_path = temp_dir.path().join("amendments.yaml");
         let cmd = TwiddleCommand {
             commit_range: Some("HEAD".to_string()),
-            auto_apply: false,
+            apply: AutoApply { auto_apply: false },
             allow_pushed: false,
             save_only: Some(save_path.to_string_lossy().into_owned()),
+            from_file: None,
             use_context: false,
             context_dir: None,
             work_context: None,
             branch_context: None,
             no_context: true,
-            concurrency: 1,
+            examples: None,
+            concurrency: Some(1),
             batch_size: None,
             no_coherence: true,
             no_ai: true,
+            no_ai_for_deps: false,
             fresh: false,
             refine: false,
             check: false,
             quiet: true,
+            tui: false,
+            notify: false,
+            save_view: None,
+            update_view: None,
+            resume_job: None,
         };
 
         cmd.execute(Some(temp_dir.path())).await.unwrap();
@@ -2408,10 +2918,58 @@ mod execute_tests {
         // The whole point of line 1031: `summary: String::new()`.
         assert_eq!(amendment.summary, "");
     }
+
+    /// `--from-file` short-circuits before the AI preflight; an empty
+    /// document leaves the repository untouched.
+    #[tokio::test]
+    async fn execute_from_file_with_no_amendments_is_noop() {
+        let (temp_dir, hash) = init_test_repo_with_commit();
+        let source = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(source.path(), "amendments: []\n").unwrap();
+
+        let mut cmd = make_cmd("HEAD", temp_dir.path().join("unused.yaml"));
+        cmd.save_only = None;
+        cmd.from_file = Some(source.path().to_string_lossy().into_owned());
+        cmd.execute(Some(temp_dir.path())).await.unwrap();
+
+        let head = git2::Repository::open(temp_dir.path())
+            .unwrap()
+            .head()
+            .unwrap()
+            .target()
+            .unwrap();
+        assert_eq!(head.to_string(), hash);
+    }
 }
 
 // --- Extracted pure functions ---
 
+/// Reviews `amendments` on the full-screen view, keeping only the accepted
+/// ones with their (possibly edited) messages. Returns false when the user
+/// quits or accepts nothing.
+fn review_amendments(screen: &super::tui::Tui, amendments: &mut AmendmentFile) -> Result<bool> {
+    screen.update(|view| {
+        for amendment in &amendments.amendments {
+            view.propose(
+                &amendment.commit,
+                &amendment.message,
+                super::tui::Decision::Accept,
+            );
+        }
+    });
+    let Some(accepted) = screen.review()? else {
+        return Ok(false);
+    };
+    amendments.amendments.retain_mut(|amendment| {
+        accepted
+            .iter()
+            .find(|(hash, _)| *hash == amendment.commit)
+            .map(|(_, message)| amendment.message.clone_from(message))
+            .is_some()
+    });
+    Ok(!amendments.amendments.is_empty())
+}
+
 /// Formats a work pattern as a display label with emoji.
 ///
 /// Returns `None` for `WorkPattern::Unknown` since it should not be displayed.
@@ -2754,27 +3312,44 @@ mod tests {
         assert!(result.is_err(), "--fresh and --refine should conflict");
     }
 
+    #[test]
+    fn from_file_accepts_stdin_and_conflicts_with_no_ai() {
+        let cmd = parse_twiddle(&["--from-file", "-"]);
+        assert_eq!(cmd.from_file.as_deref(), Some("-"));
+        let result =
+            TwiddleCommand::try_parse_from(["twiddle", "--from-file", "a.yaml", "--no-ai"]);
+        assert!(result.is_err(), "--from-file and --no-ai should conflict");
+    }
+
     // --- check_commits_map_reduce (success paths via mock client) ---
 
     fn make_twiddle_cmd() -> TwiddleCommand {
         TwiddleCommand {
             commit_range: None,
-            auto_apply: false,
+            apply: AutoApply { auto_apply: false },
             allow_pushed: false,
             save_only: None,
+            from_file: None,
             use_context: false,
             context_dir: None,
             work_context: None,
             branch_context: None,
             no_context: true,
-            concurrency: 4,
+            examples: None,
+            concurrency: Some(4),
             batch_size: None,
             no_coherence: true,
             no_ai: false,
+            no_ai_for_deps: false,
             fresh: false,
             refine: false,
             check: false,
             quiet: false,
+            tui: false,
+            notify: false,
+            save_view: None,
+            update_view: None,
+            resume_job: None,
         }
     }
 
@@ -2801,6 +3376,8 @@ mod tests {
                 diff_summary: String::new(),
                 diff_file: tmp.path().to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
         (commit, tmp)
@@ -2809,6 +3386,7 @@ mod tests {
     fn make_twiddle_repo_view(commits: Vec<crate::git::CommitInfo>) -> crate::data::RepositoryView {
         use crate::data::{AiInfo, FieldExplanation, RepositoryView, WorkingDirectoryInfo};
         RepositoryView {
+            schema_version: crate::data::SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -3040,6 +3618,7 @@ mod tests {
 
     fn make_amendment_file() -> crate::data::amendments::AmendmentFile {
         crate::data::amendments::AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![crate::data::amendments::Amendment {
                 commit: "abc0000000000000000000000000000000000001".to_string(),
                 message: "feat: improved commit message".to_string(),
@@ -3305,6 +3884,7 @@ mod tests {
         }];
 
         let mut amendments = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![Amendment {
                 commit: "aaa00000".to_string(),
                 message: "fix(wrong-scope): tweak something".to_string(),
@@ -3328,6 +3908,7 @@ mod tests {
         let repo_view = make_twiddle_repo_view(vec![commit]);
 
         let mut amendments = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![Amendment {
                 commit: "bbb00000".to_string(),
                 message: "feat(stuff): add feature".to_string(),
@@ -3350,6 +3931,7 @@ mod tests {
     fn dup_amendments(items: &[(&str, &str)]) -> AmendmentFile {
         use crate::data::amendments::Amendment;
         AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: items
                 .iter()
                 .map(|(hash, msg)| Amendment {
@@ -3363,7 +3945,10 @@ mod tests {
 
     #[test]
     fn resolve_duplicates_empty_is_noop() {
-        let mut af = AmendmentFile { amendments: vec![] };
+        let mut af = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
+            amendments: vec![],
+        };
         let mut reader = std::io::Cursor::new(b"" as &[u8]);
         resolve_duplicate_amendments(&mut af, false, true, &mut reader).unwrap();
         assert!(af.amendments.is_empty());
@@ -3501,6 +4086,7 @@ mod tests {
         }];
 
         let mut amendments = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![Amendment {
                 commit: "unknown_hash".to_string(),
                 message: "fix(wrong): something".to_string(),
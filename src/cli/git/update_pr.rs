@@ -0,0 +1,247 @@
+//! Update PR command — regenerates an existing pull request's title and
+//! description from the current branch state.
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+
+use super::create_pr::{CreatePrCommand, GeneratedPr};
+use super::defaults::AutoApply;
+use crate::data::PullRequest;
+
+/// Update PR command options.
+#[derive(Parser)]
+pub struct UpdatePrCommand {
+    /// PR number to update (defaults to the open PR for the current branch).
+    #[arg(long, value_name = "N")]
+    pub number: Option<u64>,
+
+    #[command(flatten)]
+    pub apply: AutoApply,
+
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+
+    /// Use commit messages (not the diff) as the primary input for PR generation.
+    #[arg(long)]
+    pub from_commits: bool,
+
+    /// Skips applying labels inferred from commit types and scopes.
+    #[arg(long)]
+    pub no_labels: bool,
+
+    /// Issue the PR closes on merge: 123, #123, or owner/repo#123 (repeatable).
+    #[arg(long, value_name = "ISSUE")]
+    pub fixes: Vec<String>,
+}
+
+impl UpdatePrCommand {
+    /// Executes the update PR command.
+    ///
+    /// Unlike `create pr`, this skips the working-directory and push checks:
+    /// it only rewrites the title and body of a PR that already exists.
+    pub async fn execute(mut self, repo: Option<&std::path::Path>) -> Result<()> {
+        self.apply = self
+            .apply
+            .with_defaults(&crate::utils::settings::SettingsEnv::load());
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        crate::git::issue_links::parse_issue_refs(&self.fixes)?;
+
+        let ai_info = crate::utils::check_pr_command_prerequisites(None, repo_root).await?;
+        println!(
+            "✓ {} credentials verified (model: {})",
+            ai_info.provider, ai_info.model
+        );
+        let forge = crate::forge::Forge::detect(repo_root);
+        let forge_api = forge.api_client()?;
+        if forge_api.is_some() {
+            println!("✓ {} API access verified", forge.name());
+        } else {
+            println!("✓ GitHub CLI verified");
+        }
+
+        let create_cmd = CreatePrCommand {
+            base: None,
+            apply: self.apply,
+            save_only: None,
+            ready: false,
+            draft: false,
+            context_dir: self.context_dir.clone(),
+            no_push: true,
+            from_commits: self.from_commits,
+            no_reviewers: true,
+            no_labels: self.no_labels,
+            fixes: self.fixes.clone(),
+            paths: Vec::new(),
+        };
+
+        let mut repo_view = create_cmd.generate_repository_view(repo_root).await?;
+        let branch = repo_view
+            .branch_info
+            .as_ref()
+            .map(|bi| bi.branch.clone())
+            .context("Branch info not available")?;
+        let target = select_pr(
+            repo_view.branch_prs.as_deref().unwrap_or_default(),
+            self.number,
+            &branch,
+        )?;
+        println!("🔄 Regenerating description for PR #{}...", target.number);
+        // The update helpers act on the first listed PR.
+        repo_view.branch_prs = Some(vec![target.clone()]);
+
+        let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+        create_cmd.show_model_info_from_client(&claude_client)?;
+
+        let (generated, _claude_client) = create_cmd
+            .generate_pr_content_with_client_internal(repo_root, &repo_view, claude_client)
+            .await?;
+        let GeneratedPr {
+            content,
+            used_fallback,
+        } = generated;
+        if used_fallback {
+            bail!(
+                "AI PR generation failed; PR #{} was left unchanged.",
+                target.number
+            );
+        }
+
+        let labels = create_cmd.infer_labels(repo_root, &repo_view);
+        println!("\n📋 Title: {}", content.title);
+        println!("📝 Description:\n{}\n", content.description);
+        if !labels.is_empty() {
+            println!("🏷️  Labels: {}\n", labels.join(", "));
+        }
+        if !self.apply.auto_apply && !confirm_update(target.number)? {
+            println!("❌ PR update cancelled by user");
+            return Ok(());
+        }
+
+        if let Some(api) = forge_api.as_deref() {
+            create_cmd
+                .update_forge_pr(
+                    api,
+                    &repo_view,
+                    &content.title,
+                    &content.description,
+                    None,
+                    &labels,
+                )
+                .await?;
+        } else {
+            create_cmd.update_github_pr(
+                repo_root,
+                &repo_view,
+                &content.title,
+                &content.description,
+                None,
+                &labels,
+            )?;
+        }
+        println!("✅ Pull request updated successfully!");
+        Ok(())
+    }
+}
+
+/// Picks the PR to update from the current branch's open PRs.
+///
+/// With `number`, the PR must be one of the branch's, so a typo cannot
+/// overwrite another branch's PR with this branch's content. Without it,
+/// the branch must have exactly one open PR.
+pub(super) fn select_pr(
+    prs: &[PullRequest],
+    number: Option<u64>,
+    branch: &str,
+) -> Result<PullRequest> {
+    if let Some(number) = number {
+        return prs
+            .iter()
+            .find(|pr| pr.number == number)
+            .cloned()
+            .with_context(|| {
+                format!(
+                    "PR #{number} is not an open pull request of branch '{branch}'.\n\
+                     Check out the PR's branch and run the command from there."
+                )
+            });
+    }
+    match prs {
+        [] => bail!(
+            "No open pull request found for branch '{branch}'.\n\
+             Use 'omni-dev git branch create pr' to open one, or pass --number."
+        ),
+        [pr] => Ok(pr.clone()),
+        _ => {
+            let numbers: Vec<String> = prs.iter().map(|pr| format!("#{}", pr.number)).collect();
+            bail!(
+                "Branch '{branch}' has several open pull requests ({}); pass --number to pick one.",
+                numbers.join(", ")
+            )
+        }
+    }
+}
+
+/// Asks whether to apply the regenerated content to PR `number`.
+fn confirm_update(number: u64) -> Result<bool> {
+    use std::io::{self, Write};
+
+    print!(
+        "❓ {} ",
+        crate::utils::i18n::tr("confirm-update-pr", &[("number", &number)])
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(crate::utils::i18n::is_yes(&input))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64) -> PullRequest {
+        PullRequest {
+            number,
+            title: format!("PR {number}"),
+            state: "OPEN".to_string(),
+            url: format!("https://github.com/o/r/pull/{number}"),
+            body: String::new(),
+            base: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn select_single_open_pr() {
+        assert_eq!(select_pr(&[pr(7)], None, "feature").unwrap().number, 7);
+    }
+
+    #[test]
+    fn select_requires_a_pr() {
+        let err = select_pr(&[], None, "feature").unwrap_err();
+        assert!(err.to_string().contains("No open pull request"));
+    }
+
+    #[test]
+    fn select_requires_number_when_ambiguous() {
+        let err = select_pr(&[pr(7), pr(9)], None, "feature").unwrap_err();
+        assert!(err.to_string().contains("#7, #9"));
+        assert_eq!(
+            select_pr(&[pr(7), pr(9)], Some(9), "feature").unwrap().base,
+            "main"
+        );
+    }
+
+    #[test]
+    fn select_rejects_number_outside_branch_list() {
+        let err = select_pr(&[pr(7)], Some(12), "feature").unwrap_err();
+        assert!(err.to_string().contains("#12"));
+        assert!(err.to_string().contains("'feature'"));
+    }
+}
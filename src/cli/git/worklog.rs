@@ -0,0 +1,191 @@
+//! Worklog export command — estimates the time spent per ticket and day from
+//! a developer's commits and writes it as CSV or JSON, for reconciling
+//! commits against time tracking.
+
+use anyhow::{bail, Context, Result};
+use chrono::Duration;
+use clap::{Parser, ValueEnum};
+use regex::Regex;
+
+use super::formatting::truncate_hash;
+use crate::git::worklog::{
+    collect_work_commits, estimate, WorklogEntry, WorklogOptions, DEFAULT_TICKET_PATTERN,
+};
+use crate::git::GitRepository;
+
+/// Output format of `worklog export`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum WorklogFormat {
+    /// One row per day and ticket, with a header row (RFC 4180 quoting).
+    #[default]
+    Csv,
+    /// An array of entries with the full commit hashes.
+    Json,
+}
+
+/// Worklog export command options.
+#[derive(Parser)]
+pub struct WorklogExportCommand {
+    /// Covers commits made since this date (`YYYY-MM-DD`, RFC 3339, or a
+    /// duration back from now such as `2w`).
+    #[arg(long, value_name = "WHEN")]
+    pub since: String,
+
+    /// Covers commits made up to this date (same forms as `--since`;
+    /// defaults to now).
+    #[arg(long, value_name = "WHEN")]
+    pub until: Option<String>,
+
+    /// Whose commits to cover: `me` (git `user.email`), or part of an author
+    /// name or email.
+    #[arg(long, value_name = "WHO", default_value = "me")]
+    pub author: String,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = WorklogFormat::Csv)]
+    pub format: WorklogFormat,
+
+    /// Longest gap, in minutes, between two commits of one work session.
+    #[arg(long, value_name = "MINUTES", default_value_t = 120)]
+    pub session_gap: i64,
+
+    /// Minutes credited to the first commit of a session.
+    #[arg(long, value_name = "MINUTES", default_value_t = 30)]
+    pub first_commit: i64,
+
+    /// Regex recognising ticket references in commit messages and branch
+    /// names (defaults to Jira keys such as `PROJ-123` and `#123`).
+    #[arg(long, value_name = "REGEX")]
+    pub ticket_pattern: Option<String>,
+}
+
+impl WorklogExportCommand {
+    /// Executes the worklog export command.
+    pub fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        if self.session_gap <= 0 || self.first_commit < 0 {
+            bail!("--session-gap must be positive and --first-commit must not be negative");
+        }
+        let pattern = self
+            .ticket_pattern
+            .as_deref()
+            .unwrap_or(DEFAULT_TICKET_PATTERN);
+        let options = WorklogOptions {
+            author: super::standup::resolve_author(git_repo.repository(), &self.author)?,
+            since: crate::cli::log::parse_time_bound(&self.since)?,
+            until: self
+                .until
+                .as_deref()
+                .map(crate::cli::log::parse_time_bound)
+                .transpose()?,
+            session_gap: Duration::minutes(self.session_gap),
+            first_commit: Duration::minutes(self.first_commit),
+            ticket_pattern: Regex::new(pattern)
+                .with_context(|| format!("Invalid --ticket-pattern: {pattern}"))?,
+        };
+
+        let base = git_repo.resolve_default_base_branch();
+        let commits = collect_work_commits(git_repo.repository(), base.as_deref(), &options)?;
+        if commits.is_empty() {
+            bail!("No commits by '{}' in the period", options.author);
+        }
+        let entries = estimate(&commits, options.session_gap, options.first_commit);
+        let total: i64 = entries.iter().map(|e| e.minutes).sum();
+        eprintln!(
+            "⏱️  {} commit(s), about {} over {} day(s)",
+            commits.len(),
+            format_hours(total),
+            entries
+                .iter()
+                .map(|e| &e.date)
+                .collect::<std::collections::HashSet<_>>()
+                .len()
+        );
+
+        match self.format {
+            WorklogFormat::Csv => print!("{}", to_csv(&entries)),
+            WorklogFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        }
+        Ok(())
+    }
+}
+
+/// Renders the entries as CSV: `date,ticket,hours,minutes,commits`, with
+/// short commit hashes separated by spaces.
+fn to_csv(entries: &[WorklogEntry]) -> String {
+    let mut out = String::from("date,ticket,hours,minutes,commits\n");
+    for entry in entries {
+        let commits: Vec<&str> = entry.commits.iter().map(|h| truncate_hash(h)).collect();
+        out.push_str(&format!(
+            "{},{},{:.2},{},{}\n",
+            entry.date,
+            escape_field(entry.ticket.as_deref().unwrap_or_default()),
+            entry.minutes as f64 / 60.0,
+            entry.minutes,
+            commits.join(" ")
+        ));
+    }
+    out
+}
+
+/// Quotes a CSV field that contains a comma, quote, or line break.
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// `2h 15m`, or `45m` under an hour.
+fn format_hours(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, 0) => format!("{h}h"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_has_one_row_per_day_and_ticket() {
+        let entries = vec![
+            WorklogEntry {
+                date: "2026-10-14".to_string(),
+                ticket: Some("PROJ-1".to_string()),
+                minutes: 75,
+                commits: vec!["a".repeat(40), "b".repeat(40)],
+            },
+            WorklogEntry {
+                date: "2026-10-14".to_string(),
+                ticket: None,
+                minutes: 30,
+                commits: vec!["c".repeat(40)],
+            },
+        ];
+        assert_eq!(
+            to_csv(&entries),
+            "date,ticket,hours,minutes,commits\n\
+             2026-10-14,PROJ-1,1.25,75,aaaaaaaa bbbbbbbb\n\
+             2026-10-14,,0.50,30,cccccccc\n"
+        );
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+    }
+
+    #[test]
+    fn formats_hours() {
+        assert_eq!(format_hours(45), "45m");
+        assert_eq!(format_hours(120), "2h");
+        assert_eq!(format_hours(135), "2h 15m");
+    }
+}
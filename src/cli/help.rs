@@ -1,12 +1,40 @@
 //! Help command implementation for comprehensive CLI documentation.
+//!
+//! `help-all` walks the clap command tree and renders every command's help as
+//! plain text (the default), as man pages, or as one markdown reference. All
+//! three read the same clap definitions, so the generated reference cannot
+//! drift from `--help`.
 
-use anyhow::Result;
-use clap::{builder::StyledStr, Command, CommandFactory, Parser};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{builder::StyledStr, Arg, Command, CommandFactory, Parser, ValueEnum};
 
 /// Help command for displaying comprehensive usage information.
 #[derive(Parser)]
 pub struct HelpCommand {
-    // No subcommands needed - this command shows all help
+    /// Output format: plain text, a man page, or a markdown reference.
+    #[arg(long, value_enum, default_value_t = HelpFormat::Text)]
+    pub format: HelpFormat,
+
+    /// Writes one man page per command into this directory instead of a
+    /// single page on stdout (with `--format man`).
+    #[arg(long, value_name = "DIR")]
+    pub out_dir: Option<PathBuf>,
+}
+
+/// Output format of `help-all`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum HelpFormat {
+    /// Every command's `--help`, separated by rules.
+    #[default]
+    Text,
+    /// roff man pages, for `man` and packaging.
+    Man,
+    /// A single markdown document, for documentation sites.
+    Markdown,
 }
 
 /// Help generator for creating comprehensive CLI documentation.
@@ -113,14 +141,303 @@ impl HelpGenerator {
     fn styled_str_to_string(&self, styled: &StyledStr) -> String {
         styled.to_string()
     }
+
+    /// Every command with its full path (`omni-dev git commit`), depth-first
+    /// in the same sorted order as [`Self::generate_all_help`].
+    fn command_tree(&self) -> Vec<(String, &Command)> {
+        fn walk<'a>(cmd: &'a Command, path: &str, out: &mut Vec<(String, &'a Command)>) {
+            for sub in visible_subcommands(cmd) {
+                let sub_path = format!("{path} {}", sub.get_name());
+                out.push((sub_path.clone(), sub));
+                walk(sub, &sub_path, out);
+            }
+        }
+
+        let root = self.app.get_name().to_string();
+        let mut commands = vec![(root.clone(), &self.app)];
+        walk(&self.app, &root, &mut commands);
+        commands
+    }
+
+    /// Generates a single markdown reference covering every command.
+    pub fn generate_markdown(&self) -> String {
+        let mut out = format!("# {} command reference\n", self.app.get_name());
+        for (path, cmd) in self.command_tree() {
+            let _ = write!(out, "\n## `{path}`\n\n");
+            if let Some(about) = description(cmd) {
+                let _ = write!(out, "{about}\n\n");
+            }
+            let _ = write!(out, "```text\n{}\n```\n", usage(cmd, &path));
+
+            let (positionals, options) = documented_args(cmd);
+            for (title, args) in [("Arguments", positionals), ("Options", options)] {
+                if args.is_empty() {
+                    continue;
+                }
+                let _ = write!(out, "\n**{title}:**\n\n");
+                for arg in args {
+                    let _ = write!(out, "- `{}`", arg_label(arg));
+                    let details = arg_details(arg);
+                    if !details.is_empty() {
+                        let _ = write!(out, ": {}", details.replace('\n', " "));
+                    }
+                    out.push('\n');
+                }
+            }
+
+            let subcommands = visible_subcommands(cmd);
+            if !subcommands.is_empty() {
+                out.push_str("\n**Commands:**\n\n");
+                for sub in subcommands {
+                    let sub_path = format!("{path} {}", sub.get_name());
+                    let _ = write!(
+                        out,
+                        "- [`{}`](#{}): {}\n",
+                        sub.get_name(),
+                        sub_path.replace(' ', "-"),
+                        about_line(sub)
+                    );
+                }
+            }
+        }
+        out
+    }
+
+    /// Generates one man page covering every command, with a subsection per
+    /// subcommand.
+    pub fn generate_man_page(&self) -> String {
+        let tree = self.command_tree();
+        let (root_path, root) = &tree[0];
+        let mut out = man_header(root_path, root);
+        for (path, cmd) in &tree[1..] {
+            let _ = write!(out, ".SS \"{}\"\n", roff(path));
+            if let Some(about) = description(cmd) {
+                let _ = writeln!(out, "{}", roff(&about));
+            }
+            let _ = write!(out, ".PP\n.B {}\n", roff(&usage(cmd, path)));
+            let (positionals, options) = documented_args(cmd);
+            for arg in positionals.into_iter().chain(options) {
+                out.push_str(&man_arg(arg));
+            }
+        }
+        out
+    }
+
+    /// Generates one man page per command, named like `omni-dev-git-commit.1`.
+    pub fn generate_man_pages(&self) -> Vec<(String, String)> {
+        self.command_tree()
+            .into_iter()
+            .map(|(path, cmd)| {
+                let mut page = man_header(&path, cmd);
+                let subcommands = visible_subcommands(cmd);
+                if !subcommands.is_empty() {
+                    page.push_str(".SH COMMANDS\n");
+                    for sub in subcommands {
+                        let _ = write!(
+                            page,
+                            ".TP\n\\fB{}\\fR(1)\n{}\n",
+                            roff(&format!("{path} {}", sub.get_name()).replace(' ', "-")),
+                            roff(&about_line(sub))
+                        );
+                    }
+                }
+                if path != self.app.get_name() {
+                    let _ = write!(page, ".SH \"SEE ALSO\"\n{}(1)\n", roff(self.app.get_name()));
+                }
+                (format!("{}.1", path.replace(' ', "-")), page)
+            })
+            .collect()
+    }
+}
+
+/// The long description of `cmd`, falling back to its one-line about.
+fn description(cmd: &Command) -> Option<String> {
+    cmd.get_long_about()
+        .or_else(|| cmd.get_about())
+        .map(ToString::to_string)
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// The one-line about of `cmd`, without its trailing period.
+fn about_line(cmd: &Command) -> String {
+    cmd.get_about()
+        .map(|about| about.to_string().trim_end_matches('.').to_string())
+        .unwrap_or_default()
+}
+
+/// The usage line of `cmd` with its full command path, e.g.
+/// `omni-dev git commit message view [OPTIONS] [COMMIT_RANGE]`.
+fn usage(cmd: &Command, path: &str) -> String {
+    let rendered = cmd.clone().render_usage().to_string();
+    let rendered = rendered.trim().trim_start_matches("Usage:").trim_start();
+    let rest = rendered.split_once(' ').map_or("", |(_, rest)| rest).trim();
+    if rest.is_empty() {
+        path.to_string()
+    } else {
+        format!("{path} {rest}")
+    }
+}
+
+/// The subcommands listed under `cmd`, sorted.
+fn visible_subcommands(cmd: &Command) -> Vec<&Command> {
+    let mut subcommands: Vec<_> = cmd
+        .get_subcommands()
+        .filter(|sub| sub.get_name() != "help" && !sub.is_hide_set())
+        .collect();
+    subcommands.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+    subcommands
+}
+
+/// The visible positional arguments and options of `cmd`.
+fn documented_args(cmd: &Command) -> (Vec<&Arg>, Vec<&Arg>) {
+    cmd.get_arguments()
+        .filter(|arg| !arg.is_hide_set())
+        .partition(|arg| arg.is_positional())
+}
+
+/// How an argument is written on the command line: `<RANGE>`, `[RANGE]`, or
+/// `-o, --output <OUTPUT>`.
+fn arg_label(arg: &Arg) -> String {
+    let value_name = || {
+        arg.get_value_names()
+            .and_then(|names| names.first())
+            .map_or_else(|| arg.get_id().as_str().to_uppercase(), ToString::to_string)
+    };
+    if arg.is_positional() {
+        return if arg.is_required_set() {
+            format!("<{}>", value_name())
+        } else {
+            format!("[{}]", value_name())
+        };
+    }
+    let mut label = [
+        arg.get_short().map(|short| format!("-{short}")),
+        arg.get_long().map(|long| format!("--{long}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(", ");
+    if arg.get_action().takes_values() {
+        let _ = write!(label, " <{}>", value_name());
+    }
+    label
+}
+
+/// An argument's help, followed by its possible and default values.
+fn arg_details(arg: &Arg) -> String {
+    let mut details = arg
+        .get_long_help()
+        .or_else(|| arg.get_help())
+        .map(|help| help.to_string().trim().to_string())
+        .unwrap_or_default();
+    if !arg.get_action().takes_values() {
+        return details;
+    }
+    let possible: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| format!("`{}`", value.get_name()))
+        .collect();
+    if !possible.is_empty() {
+        let _ = write!(details, " Possible values: {}.", possible.join(", "));
+    }
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|value| format!("`{}`", value.to_string_lossy()))
+        .collect();
+    if !defaults.is_empty() {
+        let _ = write!(details, " Default: {}.", defaults.join(", "));
+    }
+    details.trim().to_string()
+}
+
+/// Escapes text for roff: backslashes and hyphens, control characters at the
+/// start of a line, and blank lines as paragraph breaks.
+fn roff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.trim().is_empty() {
+                return ".PP".to_string();
+            }
+            let escaped = line.replace('\\', "\\e").replace('-', "\\-");
+            if escaped.starts_with('.') || escaped.starts_with('\'') {
+                format!("\\&{escaped}")
+            } else {
+                escaped
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The `.TH`, NAME, SYNOPSIS, DESCRIPTION, and argument sections of the man
+/// page for `cmd`.
+fn man_header(path: &str, cmd: &Command) -> String {
+    let page = path.replace(' ', "-");
+    let mut out = format!(
+        ".TH {} 1 \"\" \"omni-dev {}\" \"omni-dev Manual\"\n",
+        roff(&page.to_uppercase()),
+        roff(crate::VERSION)
+    );
+    let _ = write!(
+        out,
+        ".SH NAME\n{} \\- {}\n",
+        roff(&page),
+        roff(&about_line(cmd))
+    );
+    let _ = write!(out, ".SH SYNOPSIS\n.B {}\n", roff(&usage(cmd, path)));
+    if let Some(about) = description(cmd) {
+        let _ = write!(out, ".SH DESCRIPTION\n{}\n", roff(&about));
+    }
+    let (positionals, options) = documented_args(cmd);
+    for (title, args) in [("ARGUMENTS", positionals), ("OPTIONS", options)] {
+        if !args.is_empty() {
+            let _ = writeln!(out, ".SH {title}");
+            for arg in args {
+                out.push_str(&man_arg(arg));
+            }
+        }
+    }
+    out
+}
+
+/// One `.TP` entry for an argument.
+fn man_arg(arg: &Arg) -> String {
+    format!(
+        ".TP\n\\fB{}\\fR\n{}\n",
+        roff(&arg_label(arg)),
+        roff(&arg_details(arg).replace('`', ""))
+    )
+}
+
+/// Writes `pages` into `dir`, creating it, and returns how many were written.
+fn write_pages(dir: &Path, pages: &[(String, String)]) -> Result<usize> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for (name, page) in pages {
+        let path = dir.join(name);
+        std::fs::write(&path, page)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(pages.len())
 }
 
 impl HelpCommand {
     /// Executes the help command, showing comprehensive help for all commands.
     pub fn execute(self) -> Result<()> {
         let generator = HelpGenerator::new();
-        let help_output = generator.generate_all_help()?;
-        println!("{help_output}");
+        match (self.format, self.out_dir) {
+            (HelpFormat::Man, Some(dir)) => {
+                let written = write_pages(&dir, &generator.generate_man_pages())?;
+                eprintln!("✅ Wrote {written} man page(s) to {}", dir.display());
+            }
+            (_, Some(_)) => bail!("--out-dir is only supported with --format man"),
+            (HelpFormat::Text, None) => println!("{}", generator.generate_all_help()?),
+            (HelpFormat::Man, None) => print!("{}", generator.generate_man_page()),
+            (HelpFormat::Markdown, None) => print!("{}", generator.generate_markdown()),
+        }
         Ok(())
     }
 }
@@ -192,4 +509,61 @@ mod tests {
         let result = gen.styled_str_to_string(&styled);
         assert_eq!(result, "hello world");
     }
+
+    #[test]
+    fn markdown_reference_covers_nested_commands_and_options() {
+        let markdown = HelpGenerator::new().generate_markdown();
+        assert!(markdown.starts_with("# omni-dev command reference\n"));
+        assert!(markdown.contains("\n## `omni-dev git commit message view`\n"));
+        assert!(markdown.contains("omni-dev help-all [OPTIONS]"));
+        assert!(markdown.contains("- `--format <FORMAT>`"));
+        assert!(markdown.contains("Possible values: `text`, `man`, `markdown`."));
+        assert!(markdown.contains("(#omni-dev-git-commit)"));
+        assert!(!markdown.contains("## `omni-dev help`"));
+    }
+
+    #[test]
+    fn man_pages_are_named_per_command_and_escaped() {
+        let pages = HelpGenerator::new().generate_man_pages();
+        assert_eq!(pages[0].0, "omni-dev.1");
+        let (_, page) = pages
+            .iter()
+            .find(|(name, _)| name == "omni-dev-help-all.1")
+            .unwrap();
+        assert!(page.starts_with(".TH OMNI\\-DEV\\-HELP\\-ALL 1 "));
+        assert!(page.contains(".SH SYNOPSIS\n.B omni\\-dev help\\-all [OPTIONS]\n"));
+        assert!(page.contains("\\fB\\-\\-out\\-dir <DIR>\\fR"));
+        assert!(page.contains(".SH \"SEE ALSO\"\nomni\\-dev(1)\n"));
+    }
+
+    #[test]
+    fn roff_escapes_control_lines_and_backslashes() {
+        assert_eq!(roff(".hidden"), "\\&.hidden");
+        assert_eq!(roff("a\\b-c\n\nd"), "a\\eb\\-c\n.PP\nd");
+    }
+
+    #[test]
+    fn out_dir_requires_man_format() {
+        let err = HelpCommand {
+            format: HelpFormat::Markdown,
+            out_dir: Some(PathBuf::from("unused")),
+        }
+        .execute()
+        .unwrap_err();
+        assert!(err.to_string().contains("--format man"), "{err}");
+    }
+
+    #[test]
+    fn man_out_dir_writes_a_page_per_command() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("man1");
+        HelpCommand {
+            format: HelpFormat::Man,
+            out_dir: Some(out.clone()),
+        }
+        .execute()
+        .unwrap();
+        assert!(out.join("omni-dev.1").is_file());
+        assert!(out.join("omni-dev-git-commit-message-view.1").is_file());
+    }
 }
@@ -0,0 +1,208 @@
+//! Project onboarding: scaffolds `.omni-dev/` for a repository.
+
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use crate::claude::context::scaffold::{self, HookInstall};
+use crate::claude::context::workspace::{detect_packages, load_workspace_scopes};
+use crate::claude::context::{
+    detect_ecosystem, resolve_context_dir_at, sample_history, LearnedConventions,
+};
+use crate::git::GitRepository;
+
+/// Number of recent commits sampled to draft guidelines and scopes.
+const HISTORY_LIMIT: usize = 500;
+
+/// Init command options.
+#[derive(Parser)]
+pub struct InitCommand {
+    /// Path to custom context directory (defaults to .omni-dev/).
+    #[arg(long)]
+    pub context_dir: Option<std::path::PathBuf>,
+
+    /// Accepts the default answer to every question without prompting.
+    #[arg(short = 'y', long)]
+    pub yes: bool,
+
+    /// Installs a pre-push hook that runs `git commit message check`.
+    #[arg(long)]
+    pub hooks: bool,
+
+    /// Overwrites existing guidance files and pre-push hook.
+    #[arg(long)]
+    pub force: bool,
+}
+
+impl InitCommand {
+    /// Executes the init command.
+    ///
+    /// Every step asks before writing; existing files are kept unless
+    /// `--force` is given. Without a terminal on stdin (or with `--yes`) the
+    /// defaults are taken: write the guidance files and `.gitignore` entry,
+    /// install the hook only with `--hooks`.
+    pub fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let git_repo = GitRepository::open_at(&repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let repo_root = git_repo
+            .repository()
+            .workdir()
+            .map_or(repo_root, Path::to_path_buf);
+        let context_dir = resolve_context_dir_at(self.context_dir.as_deref(), &repo_root);
+
+        let stdin = std::io::stdin();
+        let mut prompter =
+            if crate::utils::interactive::assume_yes(self.yes) || !stdin.is_terminal() {
+                Prompter::Defaults
+            } else {
+                Prompter::Interactive(Box::new(stdin.lock()))
+            };
+
+        println!("🚀 Setting up omni-dev in {}", repo_root.display());
+        println!("🧭 Ecosystem: {:?}", detect_ecosystem(&repo_root));
+        let packages = detect_packages(&repo_root);
+        if !packages.is_empty() {
+            let names: Vec<String> = packages.iter().map(|p| p.scope_name()).collect();
+            println!("📦 Workspace packages: {}", names.join(", "));
+        }
+        let history = sample_history(git_repo.repository(), HISTORY_LIMIT).unwrap_or_default();
+        let learned = (!history.is_empty()).then(|| LearnedConventions::from_history(&history));
+        match &learned {
+            Some(learned) => println!("📜 Sampled {} commits of history", learned.sampled),
+            None => println!("📜 No history yet; using the default guidelines"),
+        }
+        println!("📂 Context dir: {}\n", context_dir.display());
+
+        let scopes = scaffold::scopes(load_workspace_scopes(&repo_root), learned.as_ref());
+        let mut drafts = vec![(
+            "commit-guidelines.md",
+            scaffold::commit_guidelines(learned.as_ref()),
+        )];
+        if scopes.is_empty() {
+            println!("ℹ️  No package or history scopes found; ecosystem default scopes apply without a scopes.yaml.");
+        } else {
+            drafts.push(("scopes.yaml", scaffold::scopes_yaml(&scopes)?));
+        }
+        for (name, content) in &drafts {
+            let path = context_dir.join(name);
+            if path.exists() && !self.force {
+                println!(
+                    "⏭️  Keeping existing {} (pass --force to replace)",
+                    path.display()
+                );
+                continue;
+            }
+            if !prompter.confirm(&format!("Write {}?", path.display()), true)? {
+                continue;
+            }
+            std::fs::create_dir_all(&context_dir).with_context(|| {
+                format!(
+                    "Failed to create context directory: {}",
+                    context_dir.display()
+                )
+            })?;
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("✅ Wrote {}", path.display());
+        }
+
+        if let Ok(relative) = context_dir.strip_prefix(&repo_root) {
+            let entry = format!("{}/local/", relative.to_string_lossy().replace('\\', "/"));
+            let gitignore = repo_root.join(".gitignore");
+            let current = std::fs::read_to_string(&gitignore).unwrap_or_default();
+            if let Some(updated) = scaffold::gitignore_with_entry(&current, &entry) {
+                if prompter.confirm(
+                    &format!("Ignore personal overrides ({entry}) in .gitignore?"),
+                    true,
+                )? {
+                    std::fs::write(&gitignore, updated)
+                        .with_context(|| format!("Failed to write {}", gitignore.display()))?;
+                    println!("✅ Added {entry} to .gitignore");
+                }
+            }
+        }
+
+        if prompter.confirm(
+            "Install a pre-push hook that checks commit messages?",
+            self.hooks,
+        )? {
+            let hooks_dir = scaffold::hooks_dir(git_repo.repository());
+            match scaffold::install_pre_push_hook(&hooks_dir, self.force)? {
+                HookInstall::Installed(path) => println!("✅ Installed {}", path.display()),
+                HookInstall::Kept(path) => println!(
+                    "⏭️  Keeping existing {} (pass --force to replace)",
+                    path.display()
+                ),
+            }
+        }
+
+        println!("\n📝 Next: review the files, then run `omni-dev context doctor`.");
+        Ok(())
+    }
+}
+
+/// Answers the yes/no questions of [`InitCommand`].
+enum Prompter {
+    /// Takes the default answer to every question.
+    Defaults,
+    /// Asks on stdout and reads answers from the reader.
+    Interactive(Box<dyn BufRead>),
+}
+
+impl Prompter {
+    /// Asks `question`; an empty answer (or EOF) selects `default`.
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool> {
+        let Self::Interactive(reader) = self else {
+            return Ok(default);
+        };
+        print!("{question} {} ", if default { "[Y/n]" } else { "[y/N]" });
+        std::io::stdout()
+            .flush()
+            .context("Failed to flush stdout")?;
+        let mut answer = String::new();
+        reader
+            .read_line(&mut answer)
+            .context("Failed to read answer")?;
+        Ok(parse_answer(&answer).unwrap_or(default))
+    }
+}
+
+/// Parses a yes/no answer; `None` for an empty or unrecognised one.
+fn parse_answer(answer: &str) -> Option<bool> {
+    match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Some(true),
+        "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_answers() {
+        assert_eq!(parse_answer("Y\n"), Some(true));
+        assert_eq!(parse_answer(" no "), Some(false));
+        assert_eq!(parse_answer("\n"), None);
+        assert_eq!(parse_answer("maybe"), None);
+    }
+
+    #[test]
+    fn prompter_falls_back_to_default() {
+        assert!(Prompter::Defaults.confirm("Write?", true).unwrap());
+
+        let mut prompter = Prompter::Interactive(Box::new(std::io::Cursor::new("n\n\n")));
+        assert!(!prompter.confirm("Write?", true).unwrap());
+        assert!(prompter.confirm("Write?", true).unwrap());
+        // EOF takes the default too.
+        assert!(!prompter.confirm("Install?", false).unwrap());
+    }
+}
@@ -7,7 +7,7 @@
 mod count;
 mod format;
 mod prune;
-mod query;
+pub(crate) mod query;
 mod stream;
 
 use anyhow::{Context, Result};
@@ -20,6 +20,9 @@ use query::Filter;
 /// reused by the `count` subcommand so its time bounds match `omni-dev log`.
 pub(crate) use query::parse_time_bound;
 
+/// Short human byte counts (`12.3 KB`), shared with `repo hygiene`.
+pub(crate) use prune::human_bytes;
+
 /// Output rendering for `omni-dev log`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "lowercase")]
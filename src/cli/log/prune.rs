@@ -68,7 +68,7 @@ impl PruneCommand {
 }
 
 /// Formats a byte count as a short human string (`0 B`, `12.3 KB`, `4.5 MB`).
-fn human_bytes(n: u64) -> String {
+pub(crate) fn human_bytes(n: u64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = KB * 1024.0;
     const GB: f64 = MB * 1024.0;
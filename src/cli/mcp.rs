@@ -0,0 +1,63 @@
+//! `omni-dev mcp` — the MCP server as a subcommand of the CLI binary.
+//!
+//! `omni-dev mcp serve` serves the same [`crate::mcp::OmniDevServer`] as the
+//! `omni-dev-mcp` binary, so an agent can be pointed at whichever binary is on
+//! its `PATH`. Like the binary, it exists only in `--features mcp` builds.
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use rmcp::transport::stdio;
+
+use crate::mcp;
+
+/// MCP server operations.
+#[derive(Parser)]
+pub struct McpCommand {
+    /// MCP subcommand to execute.
+    #[command(subcommand)]
+    pub command: McpSubcommands,
+}
+
+/// MCP subcommands.
+#[derive(Subcommand)]
+pub enum McpSubcommands {
+    /// Serves omni-dev's tools and resources over MCP on stdio, as `omni-dev-mcp` does.
+    Serve(ServeCommand),
+}
+
+/// `omni-dev mcp serve`.
+#[derive(Parser)]
+pub struct ServeCommand {}
+
+impl McpCommand {
+    /// Executes the MCP command.
+    pub async fn execute(self) -> Result<()> {
+        match self.command {
+            McpSubcommands::Serve(c) => c.execute().await,
+        }
+    }
+}
+
+impl ServeCommand {
+    /// Executes `mcp serve`, returning when the client disconnects.
+    ///
+    /// Tracing is already initialised by `main` (to stderr or `--log-file`),
+    /// so stdout carries nothing but protocol frames.
+    pub async fn execute(self) -> Result<()> {
+        mcp::log_startup_event();
+        mcp::serve_with(stdio()).await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use crate::Cli;
+    use clap::Parser;
+
+    #[test]
+    fn parses_mcp_serve() {
+        let cli = Cli::try_parse_from(["omni-dev", "mcp", "serve"]).unwrap();
+        assert!(matches!(cli.command, crate::cli::Commands::Mcp(_)));
+    }
+}
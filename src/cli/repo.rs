@@ -0,0 +1,33 @@
+//! Repo CLI commands.
+
+pub(crate) mod hygiene;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+/// Repo: upkeep of the repository's tracked contents.
+#[derive(Parser)]
+pub struct RepoCommand {
+    /// The repo subcommand to execute.
+    #[command(subcommand)]
+    pub command: RepoSubcommands,
+}
+
+/// Repo subcommands.
+#[derive(Subcommand)]
+pub enum RepoSubcommands {
+    /// Flags committed files that look generated (build output, editor files, large binaries), proposes .gitignore additions, and can commit the cleanup.
+    Hygiene(hygiene::RepoHygieneCommand),
+}
+
+impl RepoCommand {
+    /// Executes the repo command.
+    ///
+    /// `repo` is the repository location resolved at the CLI boundary
+    /// (`None` = current working directory).
+    pub async fn execute(self, repo: Option<&std::path::Path>) -> Result<()> {
+        match self.command {
+            RepoSubcommands::Hygiene(cmd) => cmd.execute(repo).await,
+        }
+    }
+}
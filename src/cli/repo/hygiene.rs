@@ -0,0 +1,497 @@
+//! Repo hygiene command — flags tracked files that look generated (build
+//! output, editor files, compiled artifacts, large binaries), has the AI weed
+//! out deliberate ones and round out the `.gitignore`, and with `--commit`
+//! untracks the rest and commits the cleanup.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use crate::cli::git::release_notes::strip_markdown_fence;
+use crate::cli::log::human_bytes;
+use crate::data::output::{render, requested, Render, RenderFormat};
+use crate::git::hygiene::{
+    append_patterns, missing_patterns, scan, HygieneFinding, HygieneKind, HygieneOptions,
+};
+use crate::git::{GeneratedFiles, GitRepository};
+use crate::utils::env::SystemEnv;
+
+/// Sample files listed per finding.
+const SAMPLE_FILES: usize = 5;
+
+/// Repo hygiene command options.
+#[derive(Parser)]
+pub struct RepoHygieneCommand {
+    /// Flags binary files at least this size (e.g. `1mb`, `512kb`).
+    #[arg(long, value_name = "SIZE", default_value = "1mb")]
+    pub large_file: String,
+
+    /// Proposes a .gitignore entry for each finding and a templated commit message without asking the AI.
+    #[arg(long)]
+    pub no_ai: bool,
+
+    /// Untracks the flagged files (keeping them on disk), adds the proposed entries to .gitignore, and commits the cleanup.
+    #[arg(long)]
+    pub commit: bool,
+}
+
+/// A proposed `.gitignore` line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct GitignoreAddition {
+    pattern: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+}
+
+/// The AI's response.
+#[derive(Deserialize)]
+struct HygieneResponse {
+    #[serde(default)]
+    gitignore: Vec<GitignoreAddition>,
+    #[serde(default)]
+    dismiss: Vec<String>,
+    message: String,
+}
+
+/// The findings and the cleanup proposed for them.
+#[derive(Debug, Serialize)]
+struct HygienePlan {
+    findings: Vec<HygieneFinding>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dismissed: Vec<HygieneFinding>,
+    gitignore: Vec<GitignoreAddition>,
+    message: String,
+}
+
+impl HygienePlan {
+    /// Files the cleanup commit untracks.
+    fn untracked_files(&self) -> impl Iterator<Item = &str> {
+        self.findings
+            .iter()
+            .filter(|f| f.is_cleanup())
+            .flat_map(|f| f.files.iter().map(String::as_str))
+    }
+
+    /// Whether the cleanup commit would change anything.
+    fn is_empty(&self) -> bool {
+        self.gitignore.is_empty() && self.untracked_files().next().is_none()
+    }
+}
+
+impl Render for HygienePlan {
+    fn render_text(&self) -> Result<String> {
+        if self.findings.is_empty() && self.gitignore.is_empty() {
+            return Ok("✅ No committed artifacts found and nothing to add to .gitignore".into());
+        }
+        let mut out = String::new();
+        if self.findings.is_empty() {
+            out.push_str("✅ No committed artifacts found\n");
+        } else {
+            out.push_str("🧹 Tracked files that look generated:\n");
+            out.push_str(&format_findings(&self.findings));
+        }
+        if !self.dismissed.is_empty() {
+            out.push_str("\n🙈 Dismissed as deliberate:\n");
+            out.push_str(&format_findings(&self.dismissed));
+        }
+        if !self.gitignore.is_empty() {
+            out.push_str("\n📝 Proposed .gitignore additions:\n");
+            for addition in &self.gitignore {
+                match &addition.reason {
+                    Some(reason) => out.push_str(&format!("  {}  # {reason}\n", addition.pattern)),
+                    None => out.push_str(&format!("  {}\n", addition.pattern)),
+                }
+            }
+        }
+        if self
+            .findings
+            .iter()
+            .any(|f| f.kind == HygieneKind::LargeBinary)
+        {
+            out.push_str(
+                "\n💡 Large binaries stay tracked; move them to Git LFS or ignore them by hand if they are not needed\n",
+            );
+        }
+        if !self.is_empty() {
+            out.push_str("\n✉️  Cleanup commit message:\n");
+            for line in self.message.lines() {
+                out.push_str(&format!("  {line}\n"));
+            }
+        }
+        Ok(out.trim_end().to_string())
+    }
+}
+
+impl RepoHygieneCommand {
+    /// Executes the repo hygiene command.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo_root = match repo {
+            Some(p) => p.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        let repo_root = repo_root.as_path();
+        let git_repo = GitRepository::open_at(repo_root)
+            .context("Failed to open git repository at the given path")?;
+        let options = HygieneOptions {
+            large_file_bytes: crate::request_log::parse_size(&self.large_file)
+                .context("invalid --large-file")?,
+        };
+        if self.commit && crate::cli::git::has_staged_changes(repo_root)? {
+            bail!(
+                "Changes are already staged; commit or unstage them before running with --commit"
+            );
+        }
+
+        eprintln!("🔍 Scanning tracked files...");
+        let findings = scan(
+            git_repo.repository(),
+            &GeneratedFiles::for_repo(repo_root),
+            &options,
+        )?;
+        let gitignore_path = repo_root.join(".gitignore");
+        let gitignore = match std::fs::read_to_string(&gitignore_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", gitignore_path.display()));
+            }
+        };
+
+        let plan = if self.no_ai {
+            template_plan(findings, &gitignore)
+        } else {
+            crate::utils::check_ai_command_prerequisites(None, repo_root)?;
+            let claude_client = crate::claude::create_default_claude_client(None, None).await?;
+            eprintln!(
+                "🤖 Reviewing {} finding(s) and the .gitignore...",
+                findings.len()
+            );
+            let ecosystem = format!(
+                "{:?}",
+                crate::claude::context::discovery::detect_ecosystem(repo_root)
+            );
+            plan_with_client(
+                &claude_client,
+                &ecosystem,
+                &top_level_entries(git_repo.repository())?,
+                findings,
+                &gitignore,
+            )
+            .await?
+        };
+
+        let format = requested(&SystemEnv).unwrap_or(RenderFormat::Text);
+        println!("{}", render(&plan, format)?);
+
+        if self.commit {
+            if plan.is_empty() {
+                eprintln!("Nothing to commit");
+                return Ok(());
+            }
+            commit_cleanup(&git_repo, repo_root, &plan, &gitignore)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds the plan without the AI: every finding kept, a `.gitignore` entry
+/// for each missing pattern, and a templated message.
+fn template_plan(findings: Vec<HygieneFinding>, gitignore: &str) -> HygienePlan {
+    let gitignore = missing_patterns(
+        gitignore,
+        findings.iter().filter_map(|f| f.pattern.as_deref()),
+    )
+    .into_iter()
+    .map(|pattern| GitignoreAddition {
+        pattern,
+        reason: None,
+    })
+    .collect();
+    let mut plan = HygienePlan {
+        findings,
+        dismissed: Vec::new(),
+        gitignore,
+        message: String::new(),
+    };
+    plan.message = template_message(&plan);
+    plan
+}
+
+/// Asks the AI to review the findings and propose the cleanup.
+pub(crate) async fn plan_with_client(
+    claude_client: &crate::claude::client::ClaudeClient,
+    ecosystem: &str,
+    top_level: &[String],
+    findings: Vec<HygieneFinding>,
+    gitignore: &str,
+) -> Result<HygienePlan> {
+    let user = crate::claude::prompts::generate_repo_hygiene_user_prompt(
+        ecosystem,
+        top_level,
+        gitignore,
+        &format_findings(&findings),
+    );
+    let response = claude_client
+        .send_message(crate::claude::prompts::REPO_HYGIENE_SYSTEM_PROMPT, &user)
+        .await?;
+    let response: HygieneResponse = serde_yaml::from_str(strip_markdown_fence(response.trim()))
+        .context("AI returned a hygiene review that is not valid YAML")?;
+
+    // Already-ignored files are never deliberate, whatever the AI says.
+    let (dismissed, findings): (Vec<_>, Vec<_>) = findings.into_iter().partition(|f| {
+        f.kind != HygieneKind::Ignored
+            && f.pattern
+                .as_deref()
+                .is_some_and(|p| response.dismiss.iter().any(|d| d.trim() == p))
+    });
+
+    // Kept findings need their pattern whether or not the AI listed it.
+    let kept_patterns = findings.iter().filter_map(|f| f.pattern.as_deref());
+    let proposed = response.gitignore.iter().map(|a| a.pattern.as_str());
+    let gitignore = missing_patterns(gitignore, proposed.chain(kept_patterns))
+        .into_iter()
+        .map(|pattern| {
+            let reason = response
+                .gitignore
+                .iter()
+                .find(|a| a.pattern.trim() == pattern)
+                .and_then(|a| a.reason.clone());
+            GitignoreAddition { pattern, reason }
+        })
+        .collect();
+
+    let mut plan = HygienePlan {
+        findings,
+        dismissed,
+        gitignore,
+        message: response.message.trim().to_string(),
+    };
+    if plan.message.is_empty() {
+        plan.message = template_message(&plan);
+    }
+    Ok(plan)
+}
+
+/// The cleanup commit message written without the AI.
+fn template_message(plan: &HygienePlan) -> String {
+    let cleanup: Vec<&HygieneFinding> = plan.findings.iter().filter(|f| f.is_cleanup()).collect();
+    let subject = match (cleanup.is_empty(), plan.gitignore.is_empty()) {
+        (false, false) => "chore: untrack generated files and extend .gitignore",
+        (false, true) => "chore: untrack generated files",
+        _ => "chore: extend .gitignore",
+    };
+    let mut message = format!("{subject}\n\n");
+    for finding in cleanup {
+        let what = finding
+            .pattern
+            .clone()
+            .unwrap_or_else(|| finding.files.join(", "));
+        message.push_str(&format!(
+            "- untrack {what} ({}, {} file(s))\n",
+            finding.kind.label(),
+            finding.files.len()
+        ));
+    }
+    for addition in &plan.gitignore {
+        message.push_str(&format!("- ignore {}\n", addition.pattern));
+    }
+    message.trim_end().to_string()
+}
+
+/// Lists findings as markdown bullets with a few sample files each.
+fn format_findings(findings: &[HygieneFinding]) -> String {
+    let mut out = String::new();
+    for finding in findings {
+        let name = finding
+            .pattern
+            .as_deref()
+            .unwrap_or_else(|| finding.files.first().map_or("", String::as_str));
+        out.push_str(&format!(
+            "  - {name} [{}]: {} file(s), {}\n",
+            finding.kind.label(),
+            finding.files.len(),
+            human_bytes(finding.bytes)
+        ));
+        if finding.pattern.is_some() || finding.files.len() > 1 {
+            for file in finding.files.iter().take(SAMPLE_FILES) {
+                out.push_str(&format!("      {file}\n"));
+            }
+            if finding.files.len() > SAMPLE_FILES {
+                out.push_str(&format!(
+                    "      … {} more\n",
+                    finding.files.len() - SAMPLE_FILES
+                ));
+            }
+        }
+    }
+    out
+}
+
+/// The distinct first path components of the tracked files, directories
+/// with a trailing `/`.
+fn top_level_entries(repo: &git2::Repository) -> Result<Vec<String>> {
+    let index = repo.index().context("Failed to read the git index")?;
+    let mut entries: Vec<String> = index
+        .iter()
+        .map(|entry| {
+            let path = String::from_utf8_lossy(&entry.path).into_owned();
+            match path.split_once('/') {
+                Some((dir, _)) => format!("{dir}/"),
+                None => path,
+            }
+        })
+        .collect();
+    entries.sort();
+    entries.dedup();
+    Ok(entries)
+}
+
+/// Writes the `.gitignore` additions, untracks the flagged files, and
+/// commits the result with the plan's message.
+fn commit_cleanup(
+    git_repo: &GitRepository,
+    repo_root: &Path,
+    plan: &HygienePlan,
+    gitignore: &str,
+) -> Result<()> {
+    let patterns: Vec<String> = plan.gitignore.iter().map(|a| a.pattern.clone()).collect();
+    let mut index = git_repo
+        .repository()
+        .index()
+        .context("Failed to read the git index")?;
+    for file in plan.untracked_files() {
+        index
+            .remove_path(Path::new(file))
+            .with_context(|| format!("Failed to untrack {file}"))?;
+    }
+    if !patterns.is_empty() {
+        let path = repo_root.join(".gitignore");
+        std::fs::write(&path, append_patterns(gitignore, &patterns))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        index
+            .add_path(Path::new(".gitignore"))
+            .context("Failed to stage .gitignore")?;
+    }
+    index.write().context("Failed to write the git index")?;
+    crate::cli::git::commit_with_message(&plan.message, repo_root)?;
+    println!(
+        "✅ Committed the cleanup: {} file(s) untracked, {} .gitignore entr{}",
+        plan.untracked_files().count(),
+        patterns.len(),
+        if patterns.len() == 1 { "y" } else { "ies" }
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::claude::client::ClaudeClient;
+    use crate::claude::test_utils::ConfigurableMockAiClient;
+
+    fn findings() -> Vec<HygieneFinding> {
+        vec![
+            HygieneFinding {
+                kind: HygieneKind::BuildOutput,
+                pattern: Some("build/".to_string()),
+                files: vec!["build/release.sh".to_string()],
+                bytes: 120,
+            },
+            HygieneFinding {
+                kind: HygieneKind::BuildOutput,
+                pattern: Some("target/".to_string()),
+                files: vec![
+                    "target/debug/app".to_string(),
+                    "target/debug/app.d".to_string(),
+                ],
+                bytes: 4_096,
+            },
+            HygieneFinding {
+                kind: HygieneKind::EditorFile,
+                pattern: Some(".DS_Store".to_string()),
+                files: vec![".DS_Store".to_string()],
+                bytes: 6_148,
+            },
+        ]
+    }
+
+    #[test]
+    fn template_plan_ignores_every_missing_pattern() {
+        let plan = template_plan(findings(), "# editors\n.DS_Store\n");
+        let patterns: Vec<&str> = plan.gitignore.iter().map(|a| a.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["build/", "target/"]);
+        assert_eq!(
+            plan.message,
+            "chore: untrack generated files and extend .gitignore\n\n\
+             - untrack build/ (build output, 1 file(s))\n\
+             - untrack target/ (build output, 2 file(s))\n\
+             - untrack .DS_Store (editor file, 1 file(s))\n\
+             - ignore build/\n\
+             - ignore target/"
+        );
+        assert_eq!(plan.untracked_files().count(), 4);
+    }
+
+    #[tokio::test]
+    async fn ai_dismissals_keep_deliberate_files_tracked() {
+        let mock = ConfigurableMockAiClient::new(vec![Ok("gitignore:\n  \
+               - pattern: \"target/\"\n    reason: \"Cargo build output\"\n  \
+               - pattern: \"*.profraw\"\n    reason: \"coverage data\"\n\
+             dismiss:\n  - \"build/\"\n\
+             message: |\n  chore: untrack build output and Finder metadata\n"
+            .to_string())]);
+        let prompts = mock.prompt_handle();
+        let client = ClaudeClient::new(Box::new(mock));
+
+        let plan = plan_with_client(
+            &client,
+            "Rust",
+            &["Cargo.toml".to_string(), "src/".to_string()],
+            findings(),
+            "",
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(plan.dismissed.len(), 1);
+        assert_eq!(plan.dismissed[0].pattern.as_deref(), Some("build/"));
+        let untracked: Vec<&str> = plan.untracked_files().collect();
+        assert_eq!(
+            untracked,
+            vec!["target/debug/app", "target/debug/app.d", ".DS_Store"]
+        );
+        // The kept .DS_Store finding gets its pattern even though the AI
+        // did not list it.
+        assert_eq!(
+            plan.gitignore,
+            vec![
+                GitignoreAddition {
+                    pattern: "target/".to_string(),
+                    reason: Some("Cargo build output".to_string()),
+                },
+                GitignoreAddition {
+                    pattern: "*.profraw".to_string(),
+                    reason: Some("coverage data".to_string()),
+                },
+                GitignoreAddition {
+                    pattern: ".DS_Store".to_string(),
+                    reason: None,
+                },
+            ]
+        );
+        assert_eq!(
+            plan.message,
+            "chore: untrack build output and Finder metadata"
+        );
+
+        let sent = prompts.prompts();
+        let (system, user) = &sent[0];
+        assert_eq!(system, crate::claude::prompts::REPO_HYGIENE_SYSTEM_PROMPT);
+        assert!(user.contains("  - target/ [build output]: 2 file(s), 4.0 KB"));
+        assert!(user.contains("=== .gitignore ===\n(none)"));
+    }
+}
@@ -0,0 +1,131 @@
+//! `omni-dev schema` — JSON Schemas of the versioned output formats, so
+//! downstream tooling can validate what it reads across releases.
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use schemars::schema_for;
+use serde_json::{json, Map, Value};
+
+use crate::data::check::CheckReport;
+use crate::data::{AmendmentFile, RepositoryView, SCHEMA_VERSION};
+
+/// Output schema operations.
+#[derive(Parser)]
+pub struct SchemaCommand {
+    /// Schema subcommand to execute.
+    #[command(subcommand)]
+    pub command: SchemaSubcommands,
+}
+
+/// Schema subcommands.
+#[derive(Subcommand)]
+pub enum SchemaSubcommands {
+    /// Prints the JSON Schema of a versioned output format, or of all of them.
+    Dump(DumpCommand),
+}
+
+/// A documented, versioned output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaFormat {
+    /// `git commit message view` and `git branch info` output.
+    RepositoryView,
+    /// Amendment files written by `twiddle --save-only` and read by `amend`.
+    Amendments,
+    /// `git commit message check` reports.
+    CheckReport,
+}
+
+impl SchemaFormat {
+    /// Key of the format in the combined dump.
+    const fn key(self) -> &'static str {
+        match self {
+            Self::RepositoryView => "repository_view",
+            Self::Amendments => "amendments",
+            Self::CheckReport => "check_report",
+        }
+    }
+
+    /// The format's JSON Schema.
+    fn schema(self) -> Result<Value> {
+        let schema = match self {
+            Self::RepositoryView => schema_for!(RepositoryView),
+            Self::Amendments => schema_for!(AmendmentFile),
+            Self::CheckReport => schema_for!(CheckReport),
+        };
+        serde_json::to_value(schema).context("Failed to serialize JSON Schema")
+    }
+}
+
+/// `omni-dev schema dump [FORMAT]`.
+#[derive(Parser)]
+pub struct DumpCommand {
+    /// Format to print; without one, prints every format keyed by name,
+    /// next to the current `schema_version`.
+    #[arg(value_enum)]
+    pub format: Option<SchemaFormat>,
+}
+
+impl SchemaCommand {
+    /// Executes the schema command.
+    pub fn execute(self) -> Result<()> {
+        match self.command {
+            SchemaSubcommands::Dump(c) => c.execute(),
+        }
+    }
+}
+
+impl DumpCommand {
+    /// Executes `schema dump`.
+    pub fn execute(self) -> Result<()> {
+        let document = dump(self.format)?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&document).context("Failed to serialize schemas")?
+        );
+        Ok(())
+    }
+}
+
+/// The schema of `format`, or every schema with the current version.
+fn dump(format: Option<SchemaFormat>) -> Result<Value> {
+    if let Some(format) = format {
+        return format.schema();
+    }
+    let mut schemas = Map::new();
+    for format in SchemaFormat::value_variants() {
+        schemas.insert(format.key().to_string(), format.schema()?);
+    }
+    Ok(json!({ "schema_version": SCHEMA_VERSION, "schemas": schemas }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_format_documents_schema_version() {
+        for format in SchemaFormat::value_variants() {
+            let schema = format.schema().unwrap();
+            assert!(
+                schema["properties"]["schema_version"].is_object(),
+                "{}: {schema}",
+                format.key()
+            );
+        }
+    }
+
+    #[test]
+    fn dump_without_format_keys_every_schema() {
+        let document = dump(None).unwrap();
+        assert_eq!(document["schema_version"], SCHEMA_VERSION);
+        let mut keys: Vec<&str> = document["schemas"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["amendments", "check_report", "repository_view"]);
+    }
+}
@@ -0,0 +1,73 @@
+//! `omni-dev serve` — the HTTP API for dashboards and bots.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+
+use crate::serve::{self, ServeConfig, DEFAULT_ADDR};
+
+/// Serves view, check, amendment generation, and apply as a JSON API.
+///
+/// Every endpoint but `GET /v1/health` requires `Authorization: Bearer
+/// <token>`. The token comes from `--token-file` or `OMNI_DEV_SERVE_TOKEN`;
+/// without either, one is generated and printed at startup.
+#[derive(Parser)]
+pub struct ServeCommand {
+    /// Address to listen on. A non-loopback address exposes the API, and its
+    /// token, over plain HTTP.
+    #[arg(long, value_name = "ADDR", default_value = DEFAULT_ADDR)]
+    pub addr: SocketAddr,
+
+    /// Read the API token from this `0600` file instead of generating one.
+    /// The token is never accepted as a command-line argument.
+    #[arg(long, value_name = "PATH")]
+    pub token_file: Option<PathBuf>,
+}
+
+impl ServeCommand {
+    /// Executes the serve command, serving the repository at `repo` (the
+    /// current directory when `None`) until Ctrl-C.
+    pub async fn execute(self, repo: Option<&Path>) -> Result<()> {
+        let repo = match repo {
+            Some(path) => path.to_path_buf(),
+            None => std::env::current_dir().context("Failed to determine current directory")?,
+        };
+        crate::utils::check_git_repository_at(&repo)?;
+        let (token, generated) = serve::resolve_token(self.token_file.as_deref())?;
+        let config = ServeConfig {
+            addr: self.addr,
+            repo,
+        };
+        serve::run(config, token, generated).await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use crate::cli::Commands;
+    use crate::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> super::ServeCommand {
+        let mut full = vec!["omni-dev", "serve"];
+        full.extend_from_slice(args);
+        match Cli::try_parse_from(full).unwrap().command {
+            Commands::Serve(c) => c,
+            _ => panic!("expected serve"),
+        }
+    }
+
+    #[test]
+    fn addr_defaults_to_loopback() {
+        assert_eq!(parse(&[]).addr.to_string(), "127.0.0.1:8787");
+        assert_eq!(parse(&["--addr", "0.0.0.0:9000"]).addr.port(), 9000);
+    }
+
+    #[test]
+    fn token_is_not_a_flag() {
+        assert!(Cli::try_parse_from(["omni-dev", "serve", "--token", "secret"]).is_err());
+    }
+}
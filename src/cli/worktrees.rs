@@ -301,7 +301,9 @@ impl CloseCommand {
             .get("risks")
             .and_then(Value::as_array)
             .is_some_and(|r| !r.is_empty());
-        if !self.yes && !confirm(has_risks).await {
+        if !crate::utils::interactive::destructive_preapproved(self.yes, "-y")?
+            && !confirm(has_risks).await
+        {
             println!("Aborted; nothing was deleted.");
             return Ok(());
         }
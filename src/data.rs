@@ -1,5 +1,9 @@
 //! Data processing and serialization.
 
+use std::collections::HashMap;
+use std::path::Path;
+
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::git::{CommitInfo, CommitInfoForAI, RemoteInfo};
@@ -7,13 +11,27 @@ use crate::git::{CommitInfo, CommitInfoForAI, RemoteInfo};
 pub mod amendments;
 pub mod check;
 pub mod context;
+pub mod output;
 pub mod yaml;
 
 pub use amendments::*;
 pub use check::*;
 pub use context::*;
+pub use output::{Render, RenderFormat};
 pub use yaml::*;
 
+/// Version of the documented formats — [`RepositoryView`], [`AmendmentFile`],
+/// and [`CheckReport`] — carried in their `schema_version` field. Bumped when
+/// a field is removed, renamed, or changes meaning; `omni-dev schema dump`
+/// prints the matching JSON Schemas.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// `schema_version` of a document written before the field existed, whose
+/// shape is that of version 1.
+pub(crate) const fn unversioned_schema() -> u32 {
+    1
+}
+
 /// Root node of the YAML output produced by `view`, `info`, `check`, and the branch
 /// subcommands.
 ///
@@ -24,8 +42,11 @@ pub use yaml::*;
 ///
 /// Generic over the commit type so the same shape serves both human-facing output
 /// (`CommitInfo`) and AI-facing output ([`RepositoryViewForAI`], using `CommitInfoForAI`).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RepositoryView<C = CommitInfo> {
+    /// Version of this output format, bumped on incompatible changes.
+    #[serde(default = "unversioned_schema")]
+    pub schema_version: u32,
     /// Version information for the omni-dev tool.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub versions: Option<VersionInfo>,
@@ -53,6 +74,8 @@ pub struct RepositoryView<C = CommitInfo> {
     pub commits: Vec<C>,
 }
 
+impl<C: Serialize> Render for RepositoryView<C> {}
+
 /// Enhanced repository view for AI processing with full diff content.
 pub type RepositoryViewForAI = RepositoryView<CommitInfoForAI>;
 
@@ -84,7 +107,7 @@ pub type RepositoryViewForAiFromCommits = RepositoryView<CommitInfoFromCommits>;
 /// read a single YAML document and know what every field means and whether it is
 /// populated in this serialization. See [ADR-0013](../../docs/adrs/adr-0013.md) for the
 /// rationale.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FieldExplanation {
     /// Descriptive text explaining the overall structure.
     pub text: String,
@@ -98,7 +121,7 @@ pub struct FieldExplanation {
 /// links a `git` command that produces the underlying data, and carries a runtime
 /// `present` flag set by [`RepositoryView::update_field_presence`] before serialization.
 /// See [ADR-0013](../../docs/adrs/adr-0013.md).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FieldDocumentation {
     /// Name of the field being documented.
     pub name: String,
@@ -116,7 +139,7 @@ pub struct FieldDocumentation {
 /// Always present. Mirrors `git status` at invocation time: a `clean` flag plus the list of
 /// modified or untracked files. Used by AI consumers to decide whether staged changes
 /// should influence the proposed commit message.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkingDirectoryInfo {
     /// Whether the working directory has no changes.
     pub clean: bool,
@@ -129,7 +152,7 @@ pub struct WorkingDirectoryInfo {
 /// One per file with uncommitted or untracked changes, carrying the porcelain status
 /// flags (e.g. `"AM"`, `"??"`, `"M "`) and the repository-relative path. Sourced from
 /// `git status --porcelain`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FileStatusInfo {
     /// Git status flags (e.g., "AM", "??", "M ").
     pub status: String,
@@ -142,7 +165,7 @@ pub struct FileStatusInfo {
 /// Present only when the producing command opts to embed version data (the `view`
 /// command does; lightweight commands omit it). Absent in `single_commit_view` /
 /// `multi_commit_view` projections used for AI dispatch.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VersionInfo {
     /// Version of the omni-dev tool.
     pub omni_dev: String,
@@ -153,7 +176,7 @@ pub struct VersionInfo {
 /// Always present. Exposes the scratch directory path (controlled by the `AI_SCRATCH`
 /// environment variable) so downstream prompts and agents can resolve the per-commit
 /// diff files referenced from `commits[].analysis.diff_file` and `file_diffs[].diff_file`.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AiInfo {
     /// Path to AI scratch directory.
     pub scratch: String,
@@ -164,10 +187,14 @@ pub struct AiInfo {
 /// Present only for branch-aware commands (e.g. branch analysis / PR-message
 /// generation); absent on plain `view`. Preserved by `single_commit_view` projections
 /// because the branch name carries useful scope information for per-commit AI dispatch.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BranchInfo {
     /// Current branch name.
     pub branch: String,
+    /// Files that conflict when the branch is merged into its base (only
+    /// present when the merge was checked).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub merge_conflicts: Option<Vec<String>>,
 }
 
 /// GitHub pull-request metadata. Appears as an entry in optional
@@ -177,7 +204,7 @@ pub struct BranchInfo {
 /// GitHub API resolves one or more PRs against it; absent on local-only branches or when
 /// the lookup fails. Field presence for the `branch_prs[].*` paths is tracked per
 /// [ADR-0013](../../docs/adrs/adr-0013.md).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PullRequest {
     /// PR number.
     pub number: u64,
@@ -197,9 +224,14 @@ pub struct PullRequest {
 impl RepositoryView {
     /// Updates the present field for all field documentation entries based on actual data.
     pub fn update_field_presence(&mut self) {
+        let has_diffs = self
+            .commits
+            .iter()
+            .any(|c| !c.analysis.diff_file.is_empty());
         for field in &mut self.explanation.fields {
             field.present = match field.name.as_str() {
-                "working_directory.clean"
+                "schema_version"
+                | "working_directory.clean"
                 | "working_directory.untracked_changes"
                 | "remotes"
                 | "ai.scratch" => true, // Always present
@@ -215,14 +247,26 @@ impl RepositoryView {
                 | "commits[].analysis.file_changes.files_added"
                 | "commits[].analysis.file_changes.files_deleted"
                 | "commits[].analysis.file_changes.file_list"
-                | "commits[].analysis.diff_summary"
-                | "commits[].analysis.diff_file"
+                | "commits[].analysis.diff_summary" => !self.commits.is_empty(),
+                "commits[].analysis.diff_file"
                 | "commits[].analysis.file_diffs"
                 | "commits[].analysis.file_diffs[].path"
                 | "commits[].analysis.file_diffs[].diff_file"
-                | "commits[].analysis.file_diffs[].byte_len" => !self.commits.is_empty(),
+                | "commits[].analysis.file_diffs[].byte_len" => has_diffs,
+                "commits[].analysis.submodule_changes" => self
+                    .commits
+                    .iter()
+                    .any(|c| !c.analysis.submodule_changes.is_empty()),
+                "commits[].analysis.dependency_updates" => self
+                    .commits
+                    .iter()
+                    .any(|c| !c.analysis.dependency_updates.is_empty()),
                 "versions.omni_dev" => self.versions.is_some(),
                 "branch_info.branch" => self.branch_info.is_some(),
+                "branch_info.merge_conflicts" => self
+                    .branch_info
+                    .as_ref()
+                    .is_some_and(|bi| bi.merge_conflicts.is_some()),
                 "pr_template" => self.pr_template.is_some(),
                 "pr_template_location" => self.pr_template_location.is_some(),
                 "branch_prs" => self.branch_prs.is_some(),
@@ -251,6 +295,86 @@ impl RepositoryView {
         yaml::to_yaml(self)
     }
 
+    /// Drops every commit's diff references (`diff_file`, `file_diffs`),
+    /// keeping the file statistics and `diff_summary`, for summary-only
+    /// output.
+    pub fn strip_diffs(&mut self) {
+        for commit in &mut self.commits {
+            commit.analysis.diff_file.clear();
+            commit.analysis.file_diffs.clear();
+        }
+    }
+
+    /// Masks secret-looking values in the free-text fields (commit messages,
+    /// pull request titles and bodies, the PR template) before the view is
+    /// printed; the diff files are masked when they are written.
+    pub fn redact_secrets(&mut self) {
+        use crate::utils::redact::redact_in_place;
+
+        for commit in &mut self.commits {
+            redact_in_place(&mut commit.original_message);
+            redact_in_place(&mut commit.analysis.proposed_message);
+        }
+        for pr in self.branch_prs.iter_mut().flatten() {
+            redact_in_place(&mut pr.title);
+            redact_in_place(&mut pr.body);
+        }
+        if let Some(template) = &mut self.pr_template {
+            redact_in_place(template);
+        }
+    }
+
+    /// Loads a view written by [`Self::save_to_file`].
+    pub fn load_from_file(path: &Path) -> anyhow::Result<Self> {
+        use anyhow::Context;
+
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read saved view: {}", path.display()))?;
+        serde_yaml::from_str(&content)
+            .with_context(|| format!("Failed to parse saved view: {}", path.display()))
+    }
+
+    /// Writes this view as YAML, for a later run to update with
+    /// [`Self::into_reusable_commits`].
+    pub fn save_to_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        use anyhow::Context;
+
+        let yaml = self.to_yaml_output()?;
+        std::fs::write(path, yaml)
+            .with_context(|| format!("Failed to write saved view: {}", path.display()))
+    }
+
+    /// Returns the commits of a saved view whose analysis a new run can
+    /// reuse, keyed by hash: none when another omni-dev version wrote the
+    /// view, and otherwise those whose diff files are still on disk.
+    pub fn into_reusable_commits(self) -> HashMap<String, CommitInfo> {
+        let same_version = self
+            .versions
+            .is_some_and(|v| v.omni_dev == env!("CARGO_PKG_VERSION"));
+        if !same_version {
+            return HashMap::new();
+        }
+        self.commits
+            .into_iter()
+            .filter(|commit| commit.analysis.diff_files_present())
+            .map(|commit| (commit.hash.clone(), commit))
+            .collect()
+    }
+
+    /// Serializes this view to pretty-printed JSON, calling
+    /// [`update_field_presence`] first.
+    ///
+    /// The JSON document has the same fields as the YAML one, so the
+    /// `explanation` section describes both.
+    ///
+    /// [`update_field_presence`]: Self::update_field_presence
+    pub fn to_json_output(&mut self) -> anyhow::Result<String> {
+        use anyhow::Context;
+
+        self.update_field_presence();
+        serde_json::to_string_pretty(self).context("Failed to serialize repository view as JSON")
+    }
+
     /// Creates a minimal view containing a single commit for parallel dispatch.
     ///
     /// Strips metadata not relevant to per-commit AI analysis (versions,
@@ -259,6 +383,7 @@ impl RepositoryView {
     #[must_use]
     pub fn single_commit_view(&self, commit: &CommitInfo) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation {
                 text: String::new(),
@@ -287,6 +412,7 @@ impl RepositoryView {
     #[must_use]
     pub(crate) fn multi_commit_view(&self, commits: &[&CommitInfo]) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation {
                 text: String::new(),
@@ -325,6 +451,12 @@ impl Default for FieldExplanation {
                 "IMPORTANT FOR AI ASSISTANTS: If a field shows present=true, it is guaranteed to be somewhere in this document. AI assistants should search the entire document thoroughly for any field marked as present=true, as it is definitely included in the output."
             ].join("\n"),
             fields: vec![
+                FieldDocumentation {
+                    name: "schema_version".to_string(),
+                    text: "Version of this output format, bumped when a field is removed, renamed, or changes meaning".to_string(),
+                    command: Some("omni-dev schema dump repository-view".to_string()),
+                    present: false,
+                },
                 FieldDocumentation {
                     name: "working_directory.clean".to_string(),
                     text: "Boolean indicating if the working directory has no uncommitted changes".to_string(),
@@ -455,6 +587,24 @@ impl Default for FieldExplanation {
                     command: None,
                     present: false,
                 },
+                FieldDocumentation {
+                    name: "commits[].analysis.submodule_changes".to_string(),
+                    text: "Submodules whose pointer the commit moves, each with its path, status (A=added, D=deleted, M=moved), \
+                           old and new commits, URL, and upstream_log: the commits the bump adds (>) or drops (<).\n\
+                           Describe a bump by what those upstream commits bring in, not as an opaque pointer change."
+                        .to_string(),
+                    command: Some("git diff --submodule=log <commit>^ <commit>".to_string()),
+                    present: false,
+                },
+                FieldDocumentation {
+                    name: "commits[].analysis.dependency_updates".to_string(),
+                    text: "Packages a dependency-bump commit (lockfiles only, or a Renovate/Dependabot update) changes, \
+                           each with its name and the version it moves from and to (from absent: added; to absent: removed).\n\
+                           Describe such a commit by the packages and versions it updates, not by its lockfile diff."
+                        .to_string(),
+                    command: None,
+                    present: false,
+                },
                 FieldDocumentation {
                     name: "versions.omni_dev".to_string(),
                     text: "Version of the omni-dev tool".to_string(),
@@ -473,6 +623,12 @@ impl Default for FieldExplanation {
                     command: Some("git branch --show-current".to_string()),
                     present: false,
                 },
+                FieldDocumentation {
+                    name: "branch_info.merge_conflicts".to_string(),
+                    text: "Files that conflict when the branch is merged into its base; empty when it merges cleanly (only present in branch commands)".to_string(),
+                    command: None,
+                    present: false,
+                },
                 FieldDocumentation {
                     name: "pr_template".to_string(),
                     text: "Pull request template content from .github/pull_request_template.md (only present in branch commands when file exists)".to_string(),
@@ -540,6 +696,7 @@ impl<C> RepositoryView<C> {
     ) -> anyhow::Result<RepositoryView<D>> {
         let commits: anyhow::Result<Vec<D>> = self.commits.into_iter().map(f).collect();
         Ok(RepositoryView {
+            schema_version: self.schema_version,
             versions: self.versions,
             explanation: self.explanation,
             working_directory: self.working_directory,
@@ -586,6 +743,7 @@ impl RepositoryViewForAI {
     #[must_use]
     pub(crate) fn single_commit_view_for_ai(&self, commit: &CommitInfoForAI) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation {
                 text: String::new(),
@@ -652,6 +810,7 @@ impl RepositoryViewForAiFromCommits {
     #[must_use]
     pub(crate) fn single_commit_view_from_commits(&self, commit: &CommitInfoFromCommits) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation {
                 text: String::new(),
@@ -686,6 +845,7 @@ mod tests {
 
     fn make_repo_view(commits: Vec<crate::git::CommitInfo>) -> RepositoryView {
         RepositoryView {
+            schema_version: SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -736,6 +896,25 @@ mod tests {
         assert_eq!(field_present(&view, "branch_prs"), Some(false));
     }
 
+    #[test]
+    fn json_output_matches_yaml_fields() {
+        let mut view = make_repo_view(vec![]);
+        view.branch_info = Some(BranchInfo {
+            branch: "feature/x".to_string(),
+            merge_conflicts: None,
+        });
+        let json: serde_json::Value =
+            serde_json::from_str(&view.to_json_output().unwrap()).unwrap();
+        let yaml: serde_json::Value =
+            serde_yaml::from_str(&view.to_yaml_output().unwrap()).unwrap();
+
+        let keys =
+            |v: &serde_json::Value| v.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&json), keys(&yaml));
+        assert_eq!(json["branch_info"]["branch"], "feature/x");
+        assert!(json.get("pr_template").is_none());
+    }
+
     #[test]
     fn field_presence_with_versions() {
         let mut view = make_repo_view(vec![]);
@@ -752,6 +931,7 @@ mod tests {
         let mut view = make_repo_view(vec![]);
         view.branch_info = Some(BranchInfo {
             branch: "main".to_string(),
+            merge_conflicts: None,
         });
         view.update_field_presence();
 
@@ -825,6 +1005,7 @@ mod tests {
         });
         view.branch_info = Some(BranchInfo {
             branch: "main".to_string(),
+            merge_conflicts: None,
         });
         view.pr_template = Some("template".to_string());
         view.pr_template_location = Some(".github/pull_request_template.md".to_string());
@@ -869,6 +1050,8 @@ mod tests {
                 diff_summary: String::new(),
                 diff_file: String::new(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         }
     }
@@ -881,6 +1064,7 @@ mod tests {
         });
         view.branch_info = Some(BranchInfo {
             branch: "feature/test".to_string(),
+            merge_conflicts: None,
         });
         view.pr_template = Some("template".to_string());
 
@@ -946,6 +1130,7 @@ mod tests {
         };
 
         let ai_view = RepositoryViewForAI {
+            schema_version: SCHEMA_VERSION,
             versions: Some(VersionInfo {
                 omni_dev: "1.0.0".to_string(),
             }),
@@ -964,6 +1149,7 @@ mod tests {
             },
             branch_info: Some(BranchInfo {
                 branch: "feature/test".to_string(),
+                merge_conflicts: None,
             }),
             pr_template: Some("template".to_string()),
             pr_template_location: Some(".github/PULL_REQUEST_TEMPLATE.md".to_string()),
@@ -1004,6 +1190,27 @@ mod tests {
         assert_eq!(commits_view.commits[1].analysis.detected_type, "feat"); // make_commit_info hard-codes feat
     }
 
+    #[test]
+    fn strip_diffs_keeps_stats_and_drops_diff_references() {
+        let mut commit = make_commit_info("aaa");
+        commit.analysis.diff_file = "/tmp/aaa.diff".to_string();
+        commit.analysis.diff_summary = "x | 1 +".to_string();
+        let mut view = make_repo_view(vec![commit]);
+        view.strip_diffs();
+
+        let yaml = view.to_yaml_output().unwrap();
+        assert!(yaml.contains("x | 1 +"));
+        assert!(!yaml.contains("/tmp/aaa.diff"));
+        let present = |name: &str| {
+            view.explanation
+                .fields
+                .iter()
+                .any(|f| f.name == name && f.present)
+        };
+        assert!(present("commits[].analysis.diff_summary"));
+        assert!(!present("commits[].analysis.diff_file"));
+    }
+
     #[test]
     fn from_commits_view_serialization_contains_no_diff_content() {
         let dir = tempfile::tempdir().unwrap();
@@ -1039,6 +1246,7 @@ mod tests {
         let field_names: Vec<&str> = explanation.fields.iter().map(|f| f.name.as_str()).collect();
 
         // Core fields that must be documented
+        assert!(field_names.contains(&"schema_version"));
         assert!(field_names.contains(&"working_directory.clean"));
         assert!(field_names.contains(&"remotes"));
         assert!(field_names.contains(&"commits[].hash"));
@@ -1097,12 +1305,15 @@ mod tests {
                         diff_summary: "file.rs | 1 +".to_string(),
                         diff_file: diff_path.to_string_lossy().to_string(),
                         file_diffs: Vec::new(),
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                 }
             })
             .collect();
 
         RepositoryView {
+            schema_version: SCHEMA_VERSION,
             versions: None,
             explanation: FieldExplanation::default(),
             working_directory: WorkingDirectoryInfo {
@@ -11,6 +11,9 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct AmendmentFile {
+    /// Version of this file format, bumped on incompatible changes.
+    #[serde(default = "crate::data::unversioned_schema")]
+    pub schema_version: u32,
     /// List of commit amendments to apply.
     pub amendments: Vec<Amendment>,
 }
@@ -22,7 +25,7 @@ pub struct AmendmentFile {
 /// would normally exclude it: OpenAI's strict-subset rule requires every
 /// property in `properties` to appear in `required`, while we still want
 /// graceful YAML loading for files written before the field existed.
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 #[schemars(extend("required" = ["commit", "message", "summary"]))]
 pub struct Amendment {
@@ -46,6 +49,25 @@ impl AmendmentFile {
         Self::from_yaml_str(&content)
     }
 
+    /// Loads amendments from `source`: a YAML file, or stdin when `source`
+    /// is `-`, so another tool can pipe the document in.
+    pub fn load_from_source(source: &str) -> Result<Self> {
+        if source == "-" {
+            Self::from_reader(std::io::stdin().lock())
+        } else {
+            Self::load_from_file(source)
+        }
+    }
+
+    /// Reads and parses amendments from `reader`.
+    pub fn from_reader(mut reader: impl std::io::Read) -> Result<Self> {
+        let mut content = String::new();
+        reader
+            .read_to_string(&mut content)
+            .context("Failed to read amendments from stdin")?;
+        Self::from_yaml_str(&content)
+    }
+
     /// Parses and validates amendments from an in-memory YAML string.
     ///
     /// Shared by [`Self::load_from_file`] (which reads the file first) and the
@@ -238,13 +260,17 @@ mod tests {
 
     #[test]
     fn validate_empty_amendments_ok() {
-        let file = AmendmentFile { amendments: vec![] };
+        let file = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
+            amendments: vec![],
+        };
         assert!(file.validate().is_ok());
     }
 
     #[test]
     fn validate_propagates_amendment_errors() {
         let file = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![Amendment::new("short".to_string(), "msg".to_string())],
         };
         let err = file.validate().unwrap_err();
@@ -262,6 +288,7 @@ mod tests {
         let path = dir.path().join("amendments.yaml");
 
         let original = AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![
                 Amendment {
                     commit: "a".repeat(40),
@@ -331,6 +358,27 @@ mod tests {
         assert!(file.amendments.is_empty());
     }
 
+    // ── AmendmentFile::from_reader ───────────────────────────────────
+
+    #[test]
+    fn from_reader_parses_piped_yaml() {
+        let yaml = format!(
+            "amendments:\n  - commit: {}\n    message: \"feat: piped amend\"\n",
+            "b".repeat(40)
+        );
+        let file = AmendmentFile::from_reader(std::io::Cursor::new(yaml)).unwrap();
+        assert_eq!(file.amendments.len(), 1);
+        assert_eq!(file.amendments[0].message, "feat: piped amend");
+    }
+
+    #[test]
+    fn unversioned_file_loads_as_version_one() {
+        let file = AmendmentFile::from_yaml_str("amendments: []\n").unwrap();
+        assert_eq!(file.schema_version, 1);
+        let yaml = crate::data::to_yaml(&file).unwrap();
+        assert!(yaml.starts_with("schema_version: 1\n"), "{yaml}");
+    }
+
     // ── property tests ────────────────────────────────────────────
 
     mod prop {
@@ -392,7 +440,9 @@ mod tests {
                         Amendment::new(hash, format!("feat: message {i}"))
                     })
                     .collect();
-                let original = AmendmentFile { amendments };
+                let original = AmendmentFile {
+                    schema_version: crate::data::SCHEMA_VERSION,
+                    amendments };
                 original.save_to_file(&path).unwrap();
                 let loaded = AmendmentFile::load_from_file(&path).unwrap();
                 prop_assert_eq!(loaded.amendments.len(), original.amendments.len());
@@ -6,8 +6,11 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Complete check report containing all commit analysis results.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CheckReport {
+    /// Version of this output format, bumped on incompatible changes.
+    #[serde(default = "crate::data::unversioned_schema")]
+    pub schema_version: u32,
     /// Individual commit check results.
     pub commits: Vec<CommitCheckResult>,
     /// Summary statistics.
@@ -15,7 +18,7 @@ pub struct CheckReport {
 }
 
 /// Result of checking a single commit.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommitCheckResult {
     /// Commit hash (short form).
     pub hash: String,
@@ -34,7 +37,7 @@ pub struct CommitCheckResult {
 }
 
 /// A single issue found in a commit message.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommitIssue {
     /// Severity level of the issue.
     pub severity: IssueSeverity,
@@ -47,7 +50,7 @@ pub struct CommitIssue {
 }
 
 /// Suggested correction for a commit message.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommitSuggestion {
     /// The suggested improved commit message.
     pub message: String,
@@ -104,7 +107,7 @@ impl IssueSeverity {
 }
 
 /// Summary statistics for a check report.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CheckSummary {
     /// Total number of commits checked.
     pub total_commits: usize,
@@ -156,7 +159,11 @@ impl CheckReport {
     /// Creates a new check report from commit results.
     pub fn new(commits: Vec<CommitCheckResult>) -> Self {
         let summary = CheckSummary::from_results(&commits);
-        Self { commits, summary }
+        Self {
+            schema_version: crate::data::SCHEMA_VERSION,
+            commits,
+            summary,
+        }
     }
 
     /// Checks if the report has any errors.
@@ -193,6 +200,18 @@ pub enum OutputFormat {
     Json,
     /// YAML format.
     Yaml,
+    /// GitHub Actions workflow commands (`::error`/`::warning`/`::notice`).
+    Github,
+}
+
+impl From<super::output::RenderFormat> for OutputFormat {
+    fn from(format: super::output::RenderFormat) -> Self {
+        match format {
+            super::output::RenderFormat::Yaml => Self::Yaml,
+            super::output::RenderFormat::Json => Self::Json,
+            super::output::RenderFormat::Text => Self::Text,
+        }
+    }
 }
 
 impl std::str::FromStr for OutputFormat {
@@ -203,6 +222,7 @@ impl std::str::FromStr for OutputFormat {
             "text" => Ok(Self::Text),
             "json" => Ok(Self::Json),
             "yaml" => Ok(Self::Yaml),
+            "github" => Ok(Self::Github),
             _ => Err(()),
         }
     }
@@ -214,6 +234,22 @@ impl fmt::Display for OutputFormat {
             Self::Text => write!(f, "text"),
             Self::Json => write!(f, "json"),
             Self::Yaml => write!(f, "yaml"),
+            Self::Github => write!(f, "github"),
+        }
+    }
+}
+
+/// Human-oriented report format for check results (`--report`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Markdown suitable for a PR comment or a CI job summary.
+    Markdown,
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Markdown => write!(f, "markdown"),
         }
     }
 }
@@ -353,6 +389,7 @@ mod tests {
         assert_eq!("text".parse::<OutputFormat>(), Ok(OutputFormat::Text));
         assert_eq!("json".parse::<OutputFormat>(), Ok(OutputFormat::Json));
         assert_eq!("yaml".parse::<OutputFormat>(), Ok(OutputFormat::Yaml));
+        assert_eq!("github".parse::<OutputFormat>(), Ok(OutputFormat::Github));
         assert!("unknown".parse::<OutputFormat>().is_err());
     }
 
@@ -361,6 +398,12 @@ mod tests {
         assert_eq!(OutputFormat::Text.to_string(), "text");
         assert_eq!(OutputFormat::Json.to_string(), "json");
         assert_eq!(OutputFormat::Yaml.to_string(), "yaml");
+        assert_eq!(OutputFormat::Github.to_string(), "github");
+    }
+
+    #[test]
+    fn report_format_display() {
+        assert_eq!(ReportFormat::Markdown.to_string(), "markdown");
     }
 
     // ── CheckSummary ─────────────────────────────────────────────────
@@ -1,9 +1,10 @@
 //! Context data structures for enhanced commit message analysis.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use anyhow::Result;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Complete context information for intelligent commit message improvement.
@@ -19,6 +20,10 @@ pub struct CommitContext {
     pub files: Vec<FileContext>,
     /// User-provided context information.
     pub user_provided: Option<String>,
+    /// Parts of the project context truncated or dropped to fit the context
+    /// token budget, for reporting.
+    #[serde(default)]
+    pub trimmed: Vec<String>,
 }
 
 /// Project-level context discovered from configuration files.
@@ -30,16 +35,57 @@ pub struct ProjectContext {
     pub pr_guidelines: Option<String>,
     /// Valid scopes and their descriptions from .omni-dev/scopes.yaml.
     pub valid_scopes: Vec<ScopeDefinition>,
+    /// Allowed commit types from .omni-dev/types.yaml (empty when the
+    /// project uses the built-in conventional types).
+    #[serde(default)]
+    pub commit_types: Vec<TypeDefinition>,
     /// Feature-specific context from .omni-dev/context/.
     pub feature_contexts: HashMap<String, FeatureContext>,
     /// Parsed conventions from CONTRIBUTING.md.
     pub project_conventions: ProjectConventions,
     /// Detected ecosystem (rust, node, python, etc.).
     pub ecosystem: Ecosystem,
+    /// Scope pack chosen with `pack:` in .omni-dev/scopes.yaml, overriding
+    /// the one selected by the detected ecosystem.
+    #[serde(default)]
+    pub scope_pack: Option<String>,
+    /// Guidance from nested `.omni-dev/` directories, applied only to
+    /// changes under their subtree.
+    #[serde(default)]
+    pub subtree_contexts: Vec<SubtreeContext>,
+    /// Organization-specific branch naming patterns from
+    /// .omni-dev/branch-conventions.yaml, tried before the built-in ones.
+    #[serde(default)]
+    pub branch_conventions: Vec<BranchConvention>,
+    /// Project-defined work patterns from .omni-dev/work-patterns.yaml.
+    #[serde(default)]
+    pub work_pattern_rules: Vec<WorkPatternRule>,
+    /// System prompt overrides from .omni-dev/prompts/, keyed by template
+    /// name (e.g. `twiddle-system`).
+    #[serde(default)]
+    pub prompt_templates: HashMap<String, String>,
+    /// Example commit messages in the house style, from .omni-dev/examples.md
+    /// or picked from recent history.
+    #[serde(default)]
+    pub commit_examples: Vec<String>,
+}
+
+/// Guidelines from a nested `.omni-dev/` directory such as
+/// `services/payments/.omni-dev/`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SubtreeContext {
+    /// Directory containing the nested `.omni-dev/`, relative to the
+    /// repository root (e.g. `services/payments`).
+    pub path: PathBuf,
+    /// Commit guidelines from the nested `commit-guidelines.md`.
+    pub commit_guidelines: Option<String>,
+    /// PR guidelines from the nested `pr-guidelines.md`.
+    pub pr_guidelines: Option<String>,
 }
 
 /// Definition of a valid scope in the project.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct ScopeDefinition {
     /// Name of the scope.
     pub name: String,
@@ -51,6 +97,106 @@ pub struct ScopeDefinition {
     pub file_patterns: Vec<String>,
 }
 
+/// Definition of an allowed conventional commit type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct TypeDefinition {
+    /// Type keyword used before the scope (e.g. `feat`, `infra`).
+    pub name: String,
+    /// Human-readable description of when to use the type.
+    #[serde(default)]
+    pub description: String,
+}
+
+/// A branch naming pattern from .omni-dev/branch-conventions.yaml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct BranchConvention {
+    /// Regex matched against the whole branch name. The named captures
+    /// `type`, `ticket`, `scope`, and `description` fill the branch context.
+    pub pattern: String,
+    /// Work type for branches matching a pattern without a `type` capture.
+    #[serde(default, rename = "type")]
+    pub work_type: Option<String>,
+}
+
+/// A project-defined work pattern from .omni-dev/work-patterns.yaml.
+///
+/// The rule matches a commit range when any changed file matches one of
+/// `files` and any commit's conventional type is in `types`; an empty list
+/// places no constraint, but at least one of the two must be given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct WorkPatternRule {
+    /// Pattern name shown in the analysis (e.g. `database-migration`).
+    pub name: String,
+    /// Glob patterns for the changed files that signal the pattern.
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Conventional commit types that signal the pattern.
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Instructions added to the prompt when the pattern matches.
+    pub guidance: String,
+}
+
+/// Mapping from commit types and scopes to pull request labels, from
+/// .omni-dev/labels.yaml.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct PrLabelConfig {
+    /// Label for each commit type. Replaces the built-in
+    /// `feat`/`fix`/`docs` mapping when present.
+    #[serde(default = "default_type_labels")]
+    pub types: BTreeMap<String, String>,
+    /// Label for each scope (e.g. `cli` → `area/cli`).
+    #[serde(default)]
+    pub scopes: BTreeMap<String, String>,
+    /// Prefix forming a label for scopes without an entry in `scopes`
+    /// (e.g. `area/`); such scopes get no label when unset.
+    #[serde(default)]
+    pub scope_prefix: Option<String>,
+}
+
+impl Default for PrLabelConfig {
+    fn default() -> Self {
+        Self {
+            types: default_type_labels(),
+            scopes: BTreeMap::new(),
+            scope_prefix: None,
+        }
+    }
+}
+
+impl PrLabelConfig {
+    /// Returns the labels for a commit of `commit_type` touching `scope`
+    /// (which may join several scopes with commas).
+    pub fn labels_for(&self, commit_type: &str, scope: &str) -> Vec<String> {
+        let mut labels: Vec<String> = self.types.get(commit_type).cloned().into_iter().collect();
+        for scope in scope.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let label = self.scopes.get(scope).cloned().or_else(|| {
+                self.scope_prefix
+                    .as_ref()
+                    .map(|prefix| format!("{prefix}{scope}"))
+            });
+            labels.extend(label);
+        }
+        labels
+    }
+}
+
+/// Built-in type labels, named after GitHub's default repository labels.
+fn default_type_labels() -> BTreeMap<String, String> {
+    [
+        ("feat", "enhancement"),
+        ("fix", "bug"),
+        ("docs", "documentation"),
+    ]
+    .into_iter()
+    .map(|(t, label)| (t.to_string(), label.to_string()))
+    .collect()
+}
+
 /// Context for a specific feature or work area.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeatureContext {
@@ -104,6 +250,8 @@ pub enum Ecosystem {
     Go,
     /// Java ecosystem with Maven/Gradle.
     Java,
+    /// Terraform infrastructure repository.
+    Terraform,
     /// Generic project without specific ecosystem.
     Generic,
 }
@@ -117,6 +265,12 @@ pub struct BranchContext {
     pub scope: Option<String>,
     /// Associated ticket or issue ID.
     pub ticket_id: Option<String>,
+    /// Title and description of the ticket, when fetched from the tracker.
+    #[serde(default)]
+    pub ticket: Option<TicketContext>,
+    /// Recent review discussion on the branch's open pull request.
+    #[serde(default)]
+    pub review_comments: Vec<ReviewComment>,
     /// Human-readable description of the branch purpose.
     pub description: String,
     /// Whether this is a feature branch (vs main/master).
@@ -125,6 +279,34 @@ pub struct BranchContext {
     pub base_branch: Option<String>,
 }
 
+/// A comment from the review discussion on a pull request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewComment {
+    /// Login or display name of the commenter.
+    pub author: String,
+    /// Comment text as written (Markdown).
+    pub body: String,
+    /// File the comment is attached to, for inline comments.
+    #[serde(default)]
+    pub path: Option<String>,
+    /// Creation time as an RFC 3339 timestamp.
+    #[serde(default)]
+    pub created_at: String,
+}
+
+/// A ticket fetched from the issue tracker for the branch's ticket ID.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TicketContext {
+    /// Ticket key (e.g. `PROJ-123`).
+    pub id: String,
+    /// Ticket title.
+    pub title: String,
+    /// Ticket description as Markdown, truncated to a prompt-friendly size.
+    pub description: Option<String>,
+    /// Link to the ticket in the tracker.
+    pub url: Option<String>,
+}
+
 /// Type of work being performed.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum WorkType {
@@ -202,6 +384,9 @@ pub struct CommitRangeContext {
     pub architectural_impact: ArchitecturalImpact,
     /// Significance of changes for commit message detail.
     pub change_significance: ChangeSignificance,
+    /// Project-defined work patterns matched by the range.
+    #[serde(default)]
+    pub project_patterns: Vec<WorkPatternRule>,
 }
 
 /// Detected work pattern across commits.
@@ -293,6 +478,8 @@ pub enum FilePurpose {
     Build,
     /// Development tools and scripts.
     Tooling,
+    /// Lockfiles, generated code, and vendored dependencies.
+    Generated,
 }
 
 /// Architectural layer of the file.
@@ -342,6 +529,19 @@ impl CommitContext {
         Self::default()
     }
 
+    /// Returns the nested `.omni-dev/` contexts whose subtree contains at
+    /// least one of the changed files, outermost first.
+    pub fn applicable_subtrees(&self) -> Vec<&SubtreeContext> {
+        let mut subtrees: Vec<&SubtreeContext> = self
+            .project
+            .subtree_contexts
+            .iter()
+            .filter(|subtree| self.files.iter().any(|f| f.path.starts_with(&subtree.path)))
+            .collect();
+        subtrees.sort_by_key(|subtree| subtree.path.components().count());
+        subtrees
+    }
+
     /// Checks if this context suggests a significant change needing detailed commit message.
     #[must_use]
     pub fn is_significant_change(&self) -> bool {
@@ -401,6 +601,26 @@ mod tests {
     use super::*;
     use std::str::FromStr;
 
+    // ── PrLabelConfig ────────────────────────────────────────────────
+
+    #[test]
+    fn default_label_config_maps_types_only() {
+        let config = PrLabelConfig::default();
+        assert_eq!(config.labels_for("feat", "cli"), vec!["enhancement"]);
+        assert_eq!(config.labels_for("fix", ""), vec!["bug"]);
+        assert!(config.labels_for("chore", "cli").is_empty());
+    }
+
+    #[test]
+    fn label_config_maps_scopes_and_prefix() {
+        let config: PrLabelConfig =
+            serde_yaml::from_str("scopes:\n  cli: command-line\nscope_prefix: area/\n").unwrap();
+        assert_eq!(
+            config.labels_for("feat", "cli, git"),
+            vec!["enhancement", "command-line", "area/git"]
+        );
+    }
+
     // ── WorkType::from_str ───────────────────────────────────────────
 
     #[test]
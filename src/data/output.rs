@@ -0,0 +1,143 @@
+//! Rendering shared by the commands that honour the top-level `--output`
+//! flag.
+//!
+//! `omni-dev --output yaml|json|text <command>` is propagated to
+//! [`OUTPUT_ENV_VAR`] before dispatch (like the other top-level flags), and
+//! each supporting command resolves its format with [`resolve`]: the
+//! command's own `-o/--output` first, then the top-level flag, then the
+//! command's default. The value a command prints implements [`Render`], so
+//! YAML and JSON come from its `Serialize` impl and only the text form is
+//! written per command.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::utils::env::EnvSource;
+
+/// Variable carrying the top-level `--output` flag to the commands.
+pub const OUTPUT_ENV_VAR: &str = "OMNI_DEV_OUTPUT";
+
+/// Format selected by the top-level `--output` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum RenderFormat {
+    /// YAML document.
+    Yaml,
+    /// Pretty-printed JSON.
+    Json,
+    /// The command's human-readable output.
+    Text,
+}
+
+impl RenderFormat {
+    /// The flag value, as stored in [`OUTPUT_ENV_VAR`].
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl std::str::FromStr for RenderFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "yaml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A command result that can be printed in every [`RenderFormat`].
+pub trait Render: Serialize {
+    /// The human-readable form. Defaults to the YAML document, for results
+    /// whose YAML already is the form people read.
+    fn render_text(&self) -> Result<String> {
+        super::yaml::to_yaml(self)
+    }
+}
+
+/// Renders `value` in `format`.
+pub fn render<T: Render + ?Sized>(value: &T, format: RenderFormat) -> Result<String> {
+    match format {
+        RenderFormat::Yaml => super::yaml::to_yaml(value),
+        RenderFormat::Json => {
+            serde_json::to_string_pretty(value).context("Failed to serialize output as JSON")
+        }
+        RenderFormat::Text => value.render_text(),
+    }
+}
+
+/// The format requested by the top-level `--output` flag, if any. An
+/// unrecognised value is logged and ignored.
+pub fn requested(env: &impl EnvSource) -> Option<RenderFormat> {
+    let raw = env.var(OUTPUT_ENV_VAR).filter(|v| !v.trim().is_empty())?;
+    let format = raw.parse().ok();
+    if format.is_none() {
+        tracing::warn!("Ignoring {OUTPUT_ENV_VAR}={raw}: expected yaml, json, or text");
+    }
+    format
+}
+
+/// A command's output format: its own `-o/--output` when given, else the
+/// top-level `--output`, else `default`.
+pub fn resolve<F: From<RenderFormat>>(flag: Option<F>, env: &impl EnvSource, default: F) -> F {
+    flag.or_else(|| requested(env).map(F::from))
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    #[derive(Serialize)]
+    struct Notes {
+        range: &'static str,
+        notes: &'static str,
+    }
+
+    impl Render for Notes {
+        fn render_text(&self) -> Result<String> {
+            Ok(self.notes.to_string())
+        }
+    }
+
+    #[test]
+    fn renders_each_format() {
+        let notes = Notes {
+            range: "v1..v2",
+            notes: "## Features",
+        };
+        assert_eq!(render(&notes, RenderFormat::Text).unwrap(), "## Features");
+        let json: serde_json::Value =
+            serde_json::from_str(&render(&notes, RenderFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["range"], "v1..v2");
+        let yaml: serde_yaml::Value =
+            serde_yaml::from_str(&render(&notes, RenderFormat::Yaml).unwrap()).unwrap();
+        assert_eq!(yaml["notes"], serde_yaml::Value::from("## Features"));
+    }
+
+    #[test]
+    fn command_flag_beats_top_level_flag() {
+        let env = MapEnv::new().with(OUTPUT_ENV_VAR, "JSON");
+        assert_eq!(
+            resolve(Some(RenderFormat::Yaml), &env, RenderFormat::Text),
+            RenderFormat::Yaml
+        );
+        assert_eq!(resolve(None, &env, RenderFormat::Text), RenderFormat::Json);
+        assert_eq!(
+            resolve(None, &MapEnv::new(), RenderFormat::Text),
+            RenderFormat::Text
+        );
+        let invalid = MapEnv::new().with(OUTPUT_ENV_VAR, "xml");
+        assert_eq!(requested(&invalid), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! YAML processing utilities.
 
 use std::fs;
+use std::io::Write;
 use std::path::Path;
 
 use anyhow::{Context, Result};
@@ -17,7 +18,79 @@ pub fn to_yaml<T: Serialize>(data: &T) -> Result<String> {
     let serde_value = serde_yaml::to_value(data).context("Failed to serialize to serde value")?;
     debug!("Converted to serde_yaml::Value successfully");
 
-    let yaml_rust_value = convert_serde_to_yaml_rust(&serde_value)?;
+    let output = emit(&serde_value)?;
+
+    debug!(
+        output_length = output.len(),
+        output_preview = %output.lines().take(10).collect::<Vec<_>>().join("\\n"),
+        "YAML serialization completed"
+    );
+
+    Ok(output)
+}
+
+/// Writes `head` followed by `items` as YAML to `writer`: the same document
+/// [`to_yaml`] produces for a value whose last field, `key`, holds `items`.
+///
+/// [`to_yaml`] builds the whole document twice over (as a value tree, then
+/// as text) before returning it. Here only the fields of `head` and one item
+/// at a time are converted, so a view of hundreds of commits is written
+/// without holding a second copy of it. A `key` field in `head` itself is
+/// ignored.
+pub fn write_yaml_with_items<W, T, I>(
+    writer: &mut W,
+    head: &T,
+    key: &str,
+    items: &[I],
+) -> Result<()>
+where
+    W: Write + ?Sized,
+    T: Serialize,
+    I: Serialize,
+{
+    let mut head = serde_yaml::to_value(head).context("Failed to serialize to serde value")?;
+    if let serde_yaml::Value::Mapping(fields) = &mut head {
+        fields.shift_remove(key);
+    }
+    writer
+        .write_all(emit(&head)?.as_bytes())
+        .context("Failed to write YAML")?;
+
+    // An item renders as it would inside the full document when emitted as
+    // the only element of a one-field document; dropping that document's
+    // start leaves the item's own lines.
+    let items_doc = |items: Vec<serde_yaml::Value>| {
+        let mut fields = serde_yaml::Mapping::new();
+        fields.insert(key.into(), serde_yaml::Value::Sequence(items));
+        emit(&serde_yaml::Value::Mapping(fields))
+    };
+    let empty = items_doc(Vec::new())?;
+    if items.is_empty() {
+        let field = empty.trim_start_matches("---\n");
+        return write!(writer, "\n{field}").context("Failed to write YAML");
+    }
+    let start = empty
+        .strip_suffix(" []")
+        .context("Unexpected YAML layout for an empty sequence")?;
+    write!(writer, "\n{}", start.trim_start_matches("---\n")).context("Failed to write YAML")?;
+    for item in items {
+        let item = serde_yaml::to_value(item).context("Failed to serialize to serde value")?;
+        let doc = items_doc(vec![item])?;
+        let lines = doc
+            .strip_prefix(start)
+            .context("Unexpected YAML layout for a sequence item")?;
+        writer
+            .write_all(lines.as_bytes())
+            .context("Failed to write YAML")?;
+    }
+    Ok(())
+}
+
+/// Emits `value` as a YAML document, multi-line strings as literal blocks.
+fn emit(value: &serde_yaml::Value) -> Result<String> {
+    use tracing::debug;
+
+    let yaml_rust_value = convert_serde_to_yaml_rust(value)?;
     debug!("Converted to yaml-rust format successfully");
 
     // Use yaml-rust emitter with multiline strings enabled
@@ -29,13 +102,6 @@ pub fn to_yaml<T: Serialize>(data: &T) -> Result<String> {
     emitter
         .dump(&yaml_rust_value)
         .context("Failed to emit YAML")?;
-
-    debug!(
-        output_length = output.len(),
-        output_preview = %output.lines().take(10).collect::<Vec<_>>().join("\\n"),
-        "YAML serialization completed"
-    );
-
     Ok(output)
 }
 
@@ -363,6 +429,49 @@ description: |
         assert_eq!(restored, data);
     }
 
+    #[test]
+    fn streamed_items_match_the_whole_document() {
+        #[derive(Serialize)]
+        struct Commit {
+            hash: &'static str,
+            message: &'static str,
+            files: Vec<&'static str>,
+        }
+        #[derive(Serialize)]
+        struct View {
+            branch: &'static str,
+            notes: &'static str,
+            commits: Vec<Commit>,
+        }
+
+        let mut view = View {
+            branch: "main",
+            notes: "first line\nsecond line",
+            commits: vec![
+                Commit {
+                    hash: "abc123",
+                    message: "feat: add a flag\n\nLonger body.",
+                    files: vec!["src/cli.rs", "README.md"],
+                },
+                Commit {
+                    hash: "def456",
+                    message: "fix: handle empty input",
+                    files: vec![],
+                },
+            ],
+        };
+        let expected = to_yaml(&view).unwrap();
+        let commits = std::mem::take(&mut view.commits);
+        let mut streamed = Vec::new();
+        write_yaml_with_items(&mut streamed, &view, "commits", &commits).unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), expected);
+
+        let expected = to_yaml(&view).unwrap();
+        let mut streamed = Vec::new();
+        write_yaml_with_items(&mut streamed, &view, "commits", &[] as &[Commit]).unwrap();
+        assert_eq!(String::from_utf8(streamed).unwrap(), expected);
+    }
+
     #[test]
     fn yaml_empty_sequence() {
         let data = InnerData {
@@ -13,6 +13,7 @@ use crate::datadog::auth::{base_url_for_site, DatadogCredentials};
 use crate::datadog::error::DatadogError;
 use crate::request_log;
 use crate::utils::http::{retry_429, REQUEST_TIMEOUT};
+use crate::utils::network::NetworkSettings;
 use crate::utils::secret::Secret;
 
 /// HTTP client for Datadog REST APIs.
@@ -31,8 +32,8 @@ impl DatadogClient {
     /// For production use, construct via [`Self::from_credentials`]; tests
     /// pass a wiremock URL directly.
     pub fn new(base_url: &str, api_key: &str, app_key: &str) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(REQUEST_TIMEOUT)
+        let client = NetworkSettings::load()
+            .apply_reqwest(Client::builder().timeout(REQUEST_TIMEOUT))?
             .build()
             .context("Failed to build HTTP client")?;
 
@@ -0,0 +1,595 @@
+//! Forge detection for pull request operations.
+//!
+//! Pull requests are created, updated, listed, and merged through a forge's
+//! REST API via a [`PullRequestApi`] client. GitHub repositories without a
+//! resolvable token (and GitHub Enterprise hosts) fall back to the `gh` CLI.
+//! [`Forge::detect`] decides which applies from the `origin` remote URL;
+//! `create pr`, `merge pr`, `branch info`, `release create`, and the PR
+//! preflight check all dispatch on it.
+
+use std::path::Path;
+use std::time::Instant;
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use crate::bitbucket::{BitbucketClient, BitbucketRepo};
+use crate::data::context::ReviewComment;
+use crate::data::PullRequest;
+use crate::gitea::{GiteaClient, GiteaRepo};
+use crate::github_api::{GitHubClient, GitHubRepo};
+use crate::pr_status::PrCheckState;
+use crate::request_log;
+use crate::utils::http::REQUEST_TIMEOUT;
+use crate::utils::network::NetworkSettings;
+
+/// `User-Agent` sent to the forge APIs; GitHub rejects requests without one.
+const USER_AGENT: &str = concat!("omni-dev/", env!("CARGO_PKG_VERSION"));
+
+/// Pull request operations backed by a forge REST API.
+#[async_trait]
+pub trait PullRequestApi: Send + Sync {
+    /// Human-readable forge name for progress and error messages.
+    fn forge_name(&self) -> &'static str;
+
+    /// Verifies the credentials can read the repository.
+    async fn verify_access(&self) -> Result<()>;
+
+    /// Lists the open pull requests whose source branch is `branch`
+    /// (matching `gh pr list --head`).
+    async fn list_branch_prs(&self, branch: &str) -> Result<Vec<PullRequest>>;
+
+    /// Returns the most recently created pull request from `branch` in any
+    /// state (open, merged, or closed), or `None` when it never had one.
+    async fn latest_branch_pr(&self, branch: &str) -> Result<Option<PullRequest>>;
+
+    /// Creates a pull request from `source` into `destination` (the
+    /// repository's default branch when `None`) and returns it.
+    async fn create_pr(
+        &self,
+        source: &str,
+        destination: Option<&str>,
+        title: &str,
+        description: &str,
+        draft: bool,
+    ) -> Result<PullRequest>;
+
+    /// Updates the title, description, and optionally the destination branch
+    /// of pull request `number` and returns it.
+    async fn update_pr(
+        &self,
+        number: u64,
+        title: &str,
+        description: &str,
+        destination: Option<&str>,
+    ) -> Result<PullRequest>;
+
+    /// Requests reviews on pull request `number` from `reviewers` (user
+    /// logins, or `org/team` for teams).
+    async fn request_reviewers(&self, number: u64, reviewers: &[String]) -> Result<()> {
+        let _ = (number, reviewers);
+        bail!(
+            "Requesting reviewers is not supported for {}",
+            self.forge_name()
+        )
+    }
+
+    /// Adds `labels` to pull request `number`.
+    async fn add_labels(&self, number: u64, labels: &[String]) -> Result<()> {
+        let _ = (number, labels);
+        bail!("Labels are not supported for {}", self.forge_name())
+    }
+
+    /// Marks draft pull request `number` as ready for review. A pull request
+    /// that is not a draft is left as it is.
+    async fn mark_ready(&self, number: u64) -> Result<()>;
+
+    /// Returns the hashes of pull request `number`'s commits. The order is
+    /// the forge's own, so callers needing history order must sort them.
+    async fn pr_commits(&self, number: u64) -> Result<Vec<String>>;
+
+    /// Returns the rolled-up CI verdict for the head commit of pull request
+    /// `number`.
+    async fn check_state(&self, number: u64) -> Result<PrCheckState>;
+
+    /// Merges pull request `number` with `strategy`. `message` overrides the
+    /// merge commit message (subject line, blank line, body) where the
+    /// strategy creates one.
+    async fn merge_pr(
+        &self,
+        number: u64,
+        strategy: MergeStrategy,
+        message: Option<&str>,
+    ) -> Result<()>;
+
+    /// Posts `body` as a comment on pull request `number`, or edits the
+    /// existing comment containing `marker` so re-runs replace it. `body`
+    /// must contain `marker`.
+    async fn upsert_pr_comment(&self, number: u64, marker: &str, body: &str) -> Result<()>;
+
+    /// Returns the review discussion on pull request `number`: review
+    /// summaries, inline comments, and conversation comments, oldest first.
+    async fn review_comments(&self, number: u64) -> Result<Vec<ReviewComment>> {
+        let _ = number;
+        bail!(
+            "Reading review comments is not supported for {}",
+            self.forge_name()
+        )
+    }
+
+    /// Publishes a release for the already-pushed tag `tag` with `notes` as
+    /// its markdown body and returns the release's web URL.
+    async fn create_release(
+        &self,
+        tag: &str,
+        name: &str,
+        notes: &str,
+        prerelease: bool,
+    ) -> Result<String> {
+        let _ = (tag, name, notes, prerelease);
+        bail!("Releases are not supported for {}", self.forge_name())
+    }
+}
+
+/// How a pull request's commits land on its destination branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum MergeStrategy {
+    /// Squash every commit into a single commit.
+    Squash,
+    /// Rebase the commits onto the destination branch.
+    Rebase,
+    /// Create a merge commit.
+    Merge,
+}
+
+impl MergeStrategy {
+    /// Parses a configured strategy name, case-insensitively.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "squash" => Some(Self::Squash),
+            "rebase" => Some(Self::Rebase),
+            "merge" => Some(Self::Merge),
+            _ => None,
+        }
+    }
+
+    /// Returns the strategy's name as accepted by `--strategy`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Squash => "squash",
+            Self::Rebase => "rebase",
+            Self::Merge => "merge",
+        }
+    }
+}
+
+/// Splits a commit message into its subject line and body.
+pub(crate) fn split_commit_message(message: &str) -> (&str, &str) {
+    let message = message.trim();
+    match message.split_once('\n') {
+        Some((subject, body)) => (subject.trim(), body.trim()),
+        None => (message, ""),
+    }
+}
+
+/// Returns the id of the first comment whose text (at JSON pointer
+/// `body_pointer`) contains `marker`.
+pub(crate) fn find_marked_comment<'a>(
+    comments: impl IntoIterator<Item = &'a Value>,
+    marker: &str,
+    body_pointer: &str,
+) -> Option<u64> {
+    comments
+        .into_iter()
+        .find(|comment| {
+            comment
+                .pointer(body_pointer)
+                .and_then(Value::as_str)
+                .is_some_and(|body| body.contains(marker))
+        })
+        .and_then(|comment| comment.get("id"))
+        .and_then(Value::as_u64)
+}
+
+/// Collects review comments from a page of comment objects, reading each
+/// field at the given JSON pointers. Comments without a body are skipped.
+pub(crate) fn review_comments_at(
+    comments: &[Value],
+    author_pointer: &str,
+    body_pointer: &str,
+    path_pointer: Option<&str>,
+    created_pointer: &str,
+) -> Vec<ReviewComment> {
+    let text = |comment: &Value, pointer: &str| {
+        comment
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    comments
+        .iter()
+        .filter_map(|comment| {
+            let body = text(comment, body_pointer).filter(|b| !b.trim().is_empty())?;
+            Some(ReviewComment {
+                author: text(comment, author_pointer).unwrap_or_default(),
+                body,
+                path: path_pointer.and_then(|pointer| text(comment, pointer)),
+                created_at: text(comment, created_pointer).unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Collects the commit hashes at `hash_pointer` (a JSON pointer such as
+/// `/sha`) from a page of commit objects.
+pub(crate) fn commit_hashes(commits: &[Value], hash_pointer: &str) -> Vec<String> {
+    commits
+        .iter()
+        .filter_map(|commit| commit.pointer(hash_pointer).and_then(Value::as_str))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Splits reviewers into user logins and team slugs (the part after the
+/// `org/` prefix).
+pub(crate) fn split_reviewers(reviewers: &[String]) -> (Vec<&str>, Vec<&str>) {
+    let mut users = Vec::new();
+    let mut teams = Vec::new();
+    for reviewer in reviewers {
+        match reviewer.split_once('/') {
+            Some((_, team)) => teams.push(team),
+            None => users.push(reviewer.as_str()),
+        }
+    }
+    (users, teams)
+}
+
+/// The forge hosting a repository's `origin` remote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Forge {
+    /// GitHub, or an unrecognised host. `Some` for `github.com` and `GH_HOST`
+    /// (GitHub Enterprise) remotes, which use the native REST client when a
+    /// token resolves; `None` always goes through the `gh` CLI.
+    GitHub(Option<GitHubRepo>),
+    /// Bitbucket Cloud.
+    Bitbucket(BitbucketRepo),
+    /// A Gitea or Forgejo instance.
+    Gitea(GiteaRepo),
+}
+
+impl Forge {
+    /// Detects the forge from the `origin` remote of the git repository at
+    /// `repo_root`, falling back to [`Forge::GitHub`].
+    pub fn detect(repo_root: &Path) -> Self {
+        origin_url(repo_root).map_or(Self::GitHub(None), |url| {
+            Self::from_remote_url(
+                &url,
+                &crate::gitea::configured_instances(),
+                crate::github_api::configured_host().as_deref(),
+            )
+        })
+    }
+
+    /// Classifies a remote URL. `gitea_instances` lists the base URLs of the
+    /// known Gitea/Forgejo instances; `github_host` is the configured GitHub
+    /// Enterprise host, if any.
+    pub fn from_remote_url(
+        url: &str,
+        gitea_instances: &[String],
+        github_host: Option<&str>,
+    ) -> Self {
+        if let Some(repo) = BitbucketRepo::from_remote_url(url) {
+            return Self::Bitbucket(repo);
+        }
+        if let Some(repo) = GiteaRepo::from_remote_url(url, gitea_instances) {
+            return Self::Gitea(repo);
+        }
+        Self::GitHub(GitHubRepo::from_remote_url(url, github_host))
+    }
+
+    /// Returns the forge's display name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GitHub(_) => "GitHub",
+            Self::Bitbucket(_) => "Bitbucket",
+            Self::Gitea(_) => "Gitea",
+        }
+    }
+
+    /// Returns the GitHub host the repository lives on, when known.
+    pub fn github_host(&self) -> Option<&str> {
+        match self {
+            Self::GitHub(Some(repo)) => Some(repo.host.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns a REST client for the forge, or `None` when GitHub must be
+    /// driven through `gh` (no token resolves, or an unrecognised host). Fails
+    /// when another forge's credentials are missing.
+    pub fn api_client(&self) -> Result<Option<Box<dyn PullRequestApi>>> {
+        Ok(match self {
+            Self::GitHub(None) => None,
+            Self::GitHub(Some(repo)) => match crate::github_api::resolve_token(&repo.host) {
+                Some(token) => Some(Box::new(GitHubClient::new(repo.clone(), token)?)),
+                None => None,
+            },
+            Self::Bitbucket(repo) => Some(Box::new(BitbucketClient::from_env(repo.clone())?)),
+            Self::Gitea(repo) => Some(Box::new(GiteaClient::from_env(repo.clone())?)),
+        })
+    }
+}
+
+/// Returns the URL of the `origin` remote of the repository at `repo_root`.
+fn origin_url(repo_root: &Path) -> Option<String> {
+    let repo = git2::Repository::discover(repo_root).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    remote.url().map(str::to_string)
+}
+
+/// Splits a git remote URL into its host (without user or port) and its
+/// repository path (without surrounding slashes or a `.git` suffix).
+///
+/// Accepts scp-style (`git@host:owner/repo.git`), `ssh://`, and `http(s)://`
+/// forms. Returns `None` for local paths.
+pub(crate) fn split_remote_url(url: &str) -> Option<(&str, &str)> {
+    let url = url.trim();
+    let (authority, path) = if let Some((_, rest)) = url.split_once("://") {
+        rest.split_once('/')?
+    } else {
+        url.split_once(':')?
+    };
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = host.split_once(':').map_or(host, |(h, _)| h);
+    if host.is_empty() {
+        return None;
+    }
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    Some((host, path))
+}
+
+/// Splits `owner/repo` into its two non-empty components.
+pub(crate) fn owner_and_repo(path: &str) -> Option<(&str, &str)> {
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner, repo))
+}
+
+/// Builds the HTTP client a forge client sends its requests through, with
+/// the standard timeout and the configured proxy and TLS settings.
+pub(crate) fn http_client() -> Result<reqwest::Client> {
+    NetworkSettings::load()
+        .apply_reqwest(
+            reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .user_agent(USER_AGENT),
+        )?
+        .build()
+        .context("Failed to build HTTP client")
+}
+
+/// Sends a JSON request to a forge API and parses the JSON response.
+///
+/// Non-2xx responses become errors carrying the API's own message when the
+/// body has one. An empty success body parses as `Value::Null`.
+pub(crate) async fn send_json(
+    client: &reqwest::Client,
+    forge: &str,
+    method: &str,
+    url: &str,
+    authorization: &str,
+    body: Option<&Value>,
+) -> Result<Value> {
+    let http_method = reqwest::Method::from_bytes(method.as_bytes())
+        .with_context(|| format!("Invalid HTTP method {method}"))?;
+    let mut request = client
+        .request(http_method, url)
+        .header(reqwest::header::AUTHORIZATION, authorization)
+        .header(reqwest::header::ACCEPT, "application/json");
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+    let started = Instant::now();
+    let result = request.send().await;
+    request_log::record_http_result(&forge.to_lowercase(), method, url, started, &result);
+    let response = result.with_context(|| {
+        format!(
+            "Failed to reach {forge} API ({method} {url}){}",
+            NetworkSettings::load().route_to(url)
+        )
+    })?;
+
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read {forge} API response"))?;
+    if !status.is_success() {
+        bail!(
+            "{forge} API returned {}: {}",
+            status.as_u16(),
+            api_error_message(&text)
+        );
+    }
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_str(&text).with_context(|| format!("Failed to parse {forge} API response"))
+}
+
+/// Pulls the human-readable message out of a forge error response, falling
+/// back to the raw body.
+fn api_error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| {
+            v.pointer("/error/message")
+                .or_else(|| v.get("message"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_remote_url_forms() {
+        assert_eq!(
+            split_remote_url("git@git.example.com:team/app.git"),
+            Some(("git.example.com", "team/app"))
+        );
+        assert_eq!(
+            split_remote_url("ssh://git@git.example.com:2222/team/app.git"),
+            Some(("git.example.com", "team/app"))
+        );
+        assert_eq!(
+            split_remote_url("https://user@git.example.com/team/app/"),
+            Some(("git.example.com", "team/app"))
+        );
+        assert_eq!(split_remote_url("/srv/git/app.git"), None);
+    }
+
+    #[test]
+    fn classifies_remotes() {
+        let instances = vec!["https://git.example.com".to_string()];
+        assert_eq!(
+            Forge::from_remote_url("git@github.com:acme/widgets.git", &instances, None),
+            Forge::GitHub(Some(GitHubRepo {
+                host: "github.com".to_string(),
+                owner: "acme".to_string(),
+                repo: "widgets".to_string(),
+            }))
+        );
+        assert_eq!(
+            Forge::from_remote_url("git@bitbucket.org:acme/widgets.git", &instances, None).name(),
+            "Bitbucket"
+        );
+        assert_eq!(
+            Forge::from_remote_url("git@git.example.com:acme/widgets.git", &instances, None).name(),
+            "Gitea"
+        );
+        assert_eq!(
+            Forge::from_remote_url("git@git.example.com:acme/widgets.git", &[], None),
+            Forge::GitHub(None)
+        );
+        assert_eq!(
+            Forge::from_remote_url(
+                "git@github.example.com:acme/widgets.git",
+                &instances,
+                Some("github.example.com")
+            )
+            .github_host(),
+            Some("github.example.com")
+        );
+    }
+
+    #[test]
+    fn detect_reads_origin_remote() {
+        let dir = tempfile::tempdir().unwrap();
+        let git_repo = git2::Repository::init(dir.path()).unwrap();
+        assert_eq!(Forge::detect(dir.path()), Forge::GitHub(None));
+        git_repo
+            .remote("origin", "git@bitbucket.org:acme/widgets.git")
+            .unwrap();
+        assert_eq!(Forge::detect(dir.path()).name(), "Bitbucket");
+    }
+
+    #[test]
+    fn splits_users_from_teams() {
+        let reviewers = vec!["alice".to_string(), "acme/core".to_string()];
+        assert_eq!(split_reviewers(&reviewers), (vec!["alice"], vec!["core"]));
+    }
+
+    #[test]
+    fn parses_merge_strategies() {
+        assert_eq!(
+            MergeStrategy::parse(" Squash "),
+            Some(MergeStrategy::Squash)
+        );
+        assert_eq!(MergeStrategy::parse("rebase"), Some(MergeStrategy::Rebase));
+        assert_eq!(MergeStrategy::parse("MERGE"), Some(MergeStrategy::Merge));
+        assert_eq!(MergeStrategy::parse("fast-forward"), None);
+    }
+
+    #[test]
+    fn splits_commit_message_subject_from_body() {
+        assert_eq!(
+            split_commit_message("feat: add x\n\n- first\n- second\n"),
+            ("feat: add x", "- first\n- second")
+        );
+        assert_eq!(split_commit_message("fix: y"), ("fix: y", ""));
+    }
+
+    #[test]
+    fn collects_commit_hashes() {
+        let commits = serde_json::json!([
+            {"sha": "aaa"},
+            {"other": "x"},
+            {"sha": "bbb"},
+        ]);
+        let commits = commits.as_array().unwrap();
+        assert_eq!(commit_hashes(commits, "/sha"), vec!["aaa", "bbb"]);
+    }
+
+    #[test]
+    fn finds_marked_comment() {
+        let comments = serde_json::json!([
+            {"id": 1, "body": "LGTM"},
+            {"id": 2, "body": "report\n<!-- marker -->"},
+            {"id": 3, "content": {"raw": "<!-- marker -->"}},
+        ]);
+        let comments = comments.as_array().unwrap();
+        assert_eq!(
+            find_marked_comment(comments, "<!-- marker -->", "/body"),
+            Some(2)
+        );
+        assert_eq!(
+            find_marked_comment(comments, "<!-- marker -->", "/content/raw"),
+            Some(3)
+        );
+        assert_eq!(
+            find_marked_comment(comments, "<!-- other -->", "/body"),
+            None
+        );
+    }
+
+    #[test]
+    fn collects_review_comments() {
+        let comments = serde_json::json!([
+            {"user": {"login": "alice"}, "body": "Rename this per our naming guide",
+             "path": "src/lib.rs", "created_at": "2026-01-02T00:00:00Z"},
+            {"user": {"login": "bob"}, "body": "", "created_at": "2026-01-03T00:00:00Z"},
+        ]);
+        let comments = review_comments_at(
+            comments.as_array().unwrap(),
+            "/user/login",
+            "/body",
+            Some("/path"),
+            "/created_at",
+        );
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].author, "alice");
+        assert_eq!(comments[0].path.as_deref(), Some("src/lib.rs"));
+    }
+
+    #[test]
+    fn error_message_prefers_api_message() {
+        assert_eq!(
+            api_error_message(r#"{"type":"error","error":{"message":"Bad branch"}}"#),
+            "Bad branch"
+        );
+        assert_eq!(
+            api_error_message(r#"{"message":"token is required","url":"x"}"#),
+            "token is required"
+        );
+        assert_eq!(api_error_message("  gateway down \n"), "gateway down");
+    }
+}
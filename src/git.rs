@@ -1,21 +1,60 @@
 //! Git operations and repository management.
 
 pub mod amendment;
+pub mod analysis_cache;
+pub mod backend;
+pub mod bisect;
+pub mod changelog;
+pub mod changelog_rule;
 pub mod commit;
+pub mod commit_filter;
+pub mod conflict;
+pub mod dependency_bump;
+pub mod diff_limit;
 pub mod diff_split;
+pub mod doc_drift;
+pub mod generated;
+pub mod history_search;
+pub mod hygiene;
+pub mod issue_links;
+pub mod license_header;
 pub mod main_branches;
+pub mod ownership;
+pub mod path_filter;
+pub mod quality;
+pub mod rebase_todo;
+pub mod release;
 pub mod remote;
+pub mod repo_map;
 pub mod repository;
+pub mod reviewers;
+pub mod standup;
+pub mod submodule;
+pub mod test_gaps;
+pub mod worklog;
 
 pub use amendment::AmendmentHandler;
+pub use backend::{Git2Backend, GitBackend};
+pub use changelog::Changelog;
 pub use commit::{
     refine_message_scope, resolve_scope, CommitAnalysis, CommitAnalysisForAI, CommitInfo,
     CommitInfoForAI, FileDiffRef,
 };
+pub use commit_filter::CommitFilter;
+pub use dependency_bump::DependencyUpdate;
+pub use diff_limit::DiffLimits;
 pub use diff_split::{split_by_file, split_file_by_hunk, FileDiff, HunkDiff};
+pub use generated::GeneratedFiles;
+pub use history_search::{HistorySearch, MatchSource, SearchHit};
 pub use main_branches::{branches_containing, detect_main_branch_tips, MainBranchTip};
+pub use path_filter::PathFilter;
+pub use release::{
+    group_commits, render_groups_markdown, ReleaseEntry, ReleaseGroup, ReleaseVersion,
+};
 pub use remote::RemoteInfo;
 pub use repository::GitRepository;
+pub use reviewers::{suggest_reviewers, ReviewerSource, SuggestedReviewer};
+pub use submodule::{SubmoduleChange, UpstreamLog};
 
 /// Number of hex characters to show in abbreviated commit hashes.
 pub const SHORT_HASH_LEN: usize = 8;
@@ -51,10 +51,11 @@ impl AmendmentHandler {
         cmd
     }
 
-    /// Applies amendments from a YAML file.
+    /// Applies amendments from a YAML file, or from stdin when `yaml_file`
+    /// is `-`.
     pub fn apply_amendments(&self, yaml_file: &str) -> Result<()> {
         // Load and validate amendment file
-        let amendment_file = AmendmentFile::load_from_file(yaml_file)?;
+        let amendment_file = AmendmentFile::load_from_source(yaml_file)?;
         self.apply_amendment_file(&amendment_file)
     }
 
@@ -483,6 +484,7 @@ mod tests {
     /// its path as a string.
     fn amendment_yaml(scratch: &Path, hash: &str, message: &str) -> String {
         let file = crate::data::amendments::AmendmentFile {
+            schema_version: crate::data::SCHEMA_VERSION,
             amendments: vec![crate::data::amendments::Amendment {
                 commit: hash.to_string(),
                 message: message.to_string(),
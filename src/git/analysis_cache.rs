@@ -0,0 +1,193 @@
+//! On-disk cache of per-commit [`CommitAnalysis`] results.
+//!
+//! A commit hash pins the message, tree, and parents, so the detected type
+//! and scope, file statistics, and diff summary never change for it. What
+//! can change is how the diff files are written: the diff size limits, the
+//! generated-file patterns in `ignore.yaml`, and the omni-dev version. Those
+//! are folded into a fingerprint stored alongside each entry, and an entry
+//! is used only while its fingerprint matches and the diff files it points
+//! at are still on disk.
+//!
+//! Entries live in `<context_dir>/cache/analysis/<hash>.json`, and only when
+//! the context directory exists. The cache is strictly best effort — read
+//! and write failures are logged at `tracing::debug` and treated as misses.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::claude::context::cache::CACHE_DIR_NAME;
+use crate::git::commit::CommitAnalysis;
+use crate::git::generated::IGNORE_FILE;
+use crate::git::DiffLimits;
+use crate::utils::digest::hex_encode;
+use crate::utils::json_store;
+
+/// Name of the analysis subdirectory inside the cache directory.
+const ANALYSIS_DIR_NAME: &str = "analysis";
+
+/// A cached analysis and the fingerprint it was computed under.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    fingerprint: String,
+    analysis: CommitAnalysis,
+}
+
+/// The commit analysis cache of one repository.
+#[derive(Debug, Clone)]
+pub struct AnalysisCache {
+    dir: PathBuf,
+    fingerprint: String,
+}
+
+impl AnalysisCache {
+    /// Opens the cache of the repository at `repo_root`. Returns `None` when
+    /// the repository has no context directory.
+    pub fn for_repo(repo_root: &Path) -> Option<Self> {
+        let context_dir = crate::claude::context::resolve_context_dir_at(None, repo_root);
+        if !context_dir.is_dir() {
+            return None;
+        }
+        let ignore_path =
+            crate::claude::context::discovery::resolve_config_file(&context_dir, IGNORE_FILE);
+        let ignore = std::fs::read_to_string(ignore_path).unwrap_or_default();
+        Some(Self::at(
+            context_dir.join(CACHE_DIR_NAME).join(ANALYSIS_DIR_NAME),
+            &ignore,
+            DiffLimits::from_settings(),
+        ))
+    }
+
+    /// Creates a cache rooted at `dir` (created lazily on first write) for
+    /// diffs written under the given ignore config and size limits.
+    pub fn at(dir: impl Into<PathBuf>, ignore_config: &str, limits: DiffLimits) -> Self {
+        let mut hasher = Sha256::new();
+        for part in [
+            env!("CARGO_PKG_VERSION"),
+            ignore_config,
+            &format!("{:?}/{:?}", limits.per_commit, limits.per_file),
+        ] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        Self {
+            dir: dir.into(),
+            fingerprint: hex_encode(&hasher.finalize()),
+        }
+    }
+
+    /// Returns the cached analysis of the commit with full hash `hash`, if
+    /// any.
+    pub fn load(&self, hash: &str) -> Option<CommitAnalysis> {
+        let entry: CacheEntry = json_store::read_json(&self.entry_path(hash), "analysis cache")?;
+        if entry.fingerprint != self.fingerprint {
+            tracing::debug!("analysis cache: diff settings changed for {hash}");
+            return None;
+        }
+        if !entry.analysis.diff_files_present() {
+            tracing::debug!("analysis cache: diff files missing for {hash}");
+            return None;
+        }
+        Some(entry.analysis)
+    }
+
+    /// Stores `analysis` for the commit with full hash `hash`. Best effort:
+    /// failures are only logged.
+    ///
+    /// Analyses of submodule bumps whose upstream log could not be read are
+    /// not stored, since checking the submodule out later fills it in.
+    pub fn store(&self, hash: &str, analysis: &CommitAnalysis) {
+        if analysis
+            .submodule_changes
+            .iter()
+            .any(|change| change.upstream_log.is_none())
+        {
+            return;
+        }
+        let entry = CacheEntry {
+            fingerprint: self.fingerprint.clone(),
+            analysis: analysis.clone(),
+        };
+        if let Err(e) = json_store::write_ignored_json(&self.entry_path(hash), &entry) {
+            tracing::debug!("analysis cache: failed to store entry: {e}");
+        }
+    }
+
+    fn entry_path(&self, hash: &str) -> PathBuf {
+        self.dir.join(format!("{hash}.json"))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::git::commit::FileChanges;
+
+    const HASH: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    fn analysis_with_diff(diff_file: &Path) -> CommitAnalysis {
+        CommitAnalysis {
+            detected_type: "feat".to_string(),
+            detected_scope: "cli".to_string(),
+            proposed_message: "feat(cli): add a flag".to_string(),
+            file_changes: FileChanges {
+                total_files: 1,
+                files_added: 0,
+                files_deleted: 0,
+                file_list: Vec::new(),
+            },
+            diff_summary: " src/cli.rs | 2 +-".to_string(),
+            diff_file: diff_file.to_string_lossy().to_string(),
+            file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn round_trips_while_settings_and_diff_files_are_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let diff_file = dir.path().join("commit.diff");
+        std::fs::write(&diff_file, "+added\n").unwrap();
+        let cache = AnalysisCache::at(dir.path().join("analysis"), "", DiffLimits::default());
+
+        cache.store(HASH, &analysis_with_diff(&diff_file));
+        let loaded = cache.load(HASH).unwrap();
+        assert_eq!(loaded.detected_scope, "cli");
+        assert_eq!(loaded.diff_summary, " src/cli.rs | 2 +-");
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("analysis/.gitignore")).unwrap(),
+            "*\n"
+        );
+    }
+
+    #[test]
+    fn misses_when_settings_change_or_diff_files_are_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let diff_file = dir.path().join("commit.diff");
+        std::fs::write(&diff_file, "+added\n").unwrap();
+        let cache_dir = dir.path().join("analysis");
+        let cache = AnalysisCache::at(&cache_dir, "", DiffLimits::default());
+        cache.store(HASH, &analysis_with_diff(&diff_file));
+
+        let limited = DiffLimits {
+            per_commit: Some(1024),
+            per_file: None,
+        };
+        assert!(AnalysisCache::at(&cache_dir, "", limited)
+            .load(HASH)
+            .is_none());
+        assert!(AnalysisCache::at(
+            &cache_dir,
+            "exclude: [\"*.pb.go\"]\n",
+            DiffLimits::default()
+        )
+        .load(HASH)
+        .is_none());
+
+        std::fs::remove_file(&diff_file).unwrap();
+        assert!(cache.load(HASH).is_none());
+    }
+}
@@ -0,0 +1,377 @@
+//! The single seam through which commands read and update a repository.
+//!
+//! Commands used to reach git three ways: libgit2 through `git2`, `git`
+//! subprocesses for diffs and staged-change checks, and `git` subprocesses
+//! for remote operations. [`GitBackend`] names the operations those paths
+//! share, and [`Git2Backend`] implements them once on top of `git2`.
+//!
+//! Local reads — the staged, working-tree, and range diffs, the files they
+//! touch, whether anything is staged, and the working-tree status — run
+//! in-process. Pushes still shell out to the
+//! user's `git`, for the transport reasons given on [`run_git_cli`]; keeping
+//! that call behind the trait means it is the only place left to change if
+//! a native transport becomes viable.
+//!
+//! A gitoxide (`gix`) implementation would slot in beside [`Git2Backend`].
+//! `omni-dev bench` times the local reads through this trait (its `backend`
+//! stage) as the baseline such a backend has to beat; the `gix` prototype
+//! itself, which brings in the new dependency, is a separate follow-up, so
+//! `git2` is the only backend for now.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use anyhow::{Context, Result};
+use git2::{Diff, DiffFormat, Repository, Status};
+
+use super::repository::{format_status_flags, FileStatus, WorkingDirectoryStatus};
+
+/// Repository operations commands rely on, independent of how they reach
+/// git.
+pub trait GitBackend {
+    /// Returns the unified diff of the index against `HEAD`, as
+    /// `git diff --cached` prints it.
+    fn staged_diff(&self) -> Result<String>;
+
+    /// Returns whether the index differs from `HEAD`.
+    fn has_staged_changes(&self) -> Result<bool>;
+
+    /// Returns the unified diff of the working tree, staged and unstaged
+    /// changes alike, against `HEAD`, as `git diff HEAD` prints it.
+    fn working_diff(&self) -> Result<String>;
+
+    /// Returns the unified diff of `range` (`<from>..<to>`, or
+    /// `<from>...<to>` to diff from their merge base), as `git diff <range>`
+    /// prints it.
+    fn range_diff(&self, range: &str) -> Result<String>;
+
+    /// Returns the files `range` changes — the staged files without one —
+    /// leaving out deleted files, as `git diff --name-only --diff-filter=d`
+    /// lists them.
+    fn changed_files(&self, range: Option<&str>) -> Result<Vec<String>>;
+
+    /// Returns the working-tree status, ignored files excluded.
+    fn working_directory_status(&self) -> Result<WorkingDirectoryStatus>;
+
+    /// Pushes `refspecs` to `remote_name`, recording the upstream of a pushed
+    /// branch when `set_upstream` is set. The error carries git's own
+    /// message.
+    fn push(&self, remote_name: &str, refspecs: &[&str], set_upstream: bool) -> Result<()>;
+}
+
+/// [`GitBackend`] over an open `git2::Repository`.
+pub struct Git2Backend<'r> {
+    repo: &'r Repository,
+}
+
+impl<'r> Git2Backend<'r> {
+    /// Creates the backend for `repo`.
+    pub fn new(repo: &'r Repository) -> Self {
+        Self { repo }
+    }
+
+    /// Returns the `HEAD` tree, or `None` before the first commit.
+    fn head_tree(&self) -> Result<Option<git2::Tree<'r>>> {
+        match self.repo.head() {
+            Ok(head) => Ok(Some(
+                head.peel_to_tree()
+                    .context("Failed to read the HEAD tree")?,
+            )),
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+            Err(e) => Err(e).context("Failed to resolve HEAD"),
+        }
+    }
+
+    /// Diffs the index against `HEAD`, or against the empty tree before the
+    /// first commit.
+    fn index_diff(&self) -> Result<Diff<'r>> {
+        let mut diff = self
+            .repo
+            .diff_tree_to_index(self.head_tree()?.as_ref(), None, None)
+            .context("Failed to diff the index against HEAD")?;
+        // `git diff` reports renames by default; match it.
+        diff.find_similar(None)
+            .context("Failed to detect renames in the staged diff")?;
+        Ok(diff)
+    }
+
+    /// Diffs the two ends of `range`; a range without an end diffs against
+    /// the working tree, like `git diff <from>`.
+    fn range_tree_diff(&self, range: &str) -> Result<Diff<'r>> {
+        let spec = self
+            .repo
+            .revparse(range)
+            .with_context(|| format!("Failed to resolve '{range}'"))?;
+        let from = spec
+            .from()
+            .with_context(|| format!("'{range}' has no start"))?
+            .peel_to_commit()
+            .with_context(|| format!("'{range}' does not start at a commit"))?;
+        let to = spec
+            .to()
+            .map(git2::Object::peel_to_commit)
+            .transpose()
+            .with_context(|| format!("'{range}' does not end at a commit"))?;
+        let from = match &to {
+            Some(to) if spec.mode().contains(git2::RevparseMode::MERGE_BASE) => {
+                let base = self
+                    .repo
+                    .merge_base(from.id(), to.id())
+                    .with_context(|| format!("'{range}' has no merge base"))?;
+                self.repo.find_commit(base)?
+            }
+            _ => from,
+        };
+        let from_tree = from.tree().context("Failed to read the start tree")?;
+        let mut diff = match to {
+            Some(to) => {
+                let to_tree = to.tree().context("Failed to read the end tree")?;
+                self.repo
+                    .diff_tree_to_tree(Some(&from_tree), Some(&to_tree), None)
+            }
+            None => self
+                .repo
+                .diff_tree_to_workdir_with_index(Some(&from_tree), None),
+        }
+        .with_context(|| format!("Failed to diff {range}"))?;
+        diff.find_similar(None)
+            .with_context(|| format!("Failed to detect renames in {range}"))?;
+        Ok(diff)
+    }
+}
+
+impl GitBackend for Git2Backend<'_> {
+    fn staged_diff(&self) -> Result<String> {
+        patch_text(&self.index_diff()?)
+    }
+
+    fn has_staged_changes(&self) -> Result<bool> {
+        Ok(self.index_diff()?.deltas().len() > 0)
+    }
+
+    fn working_diff(&self) -> Result<String> {
+        let mut diff = self
+            .repo
+            .diff_tree_to_workdir_with_index(self.head_tree()?.as_ref(), None)
+            .context("Failed to diff the working tree against HEAD")?;
+        diff.find_similar(None)
+            .context("Failed to detect renames in the working-tree diff")?;
+        patch_text(&diff)
+    }
+
+    fn range_diff(&self, range: &str) -> Result<String> {
+        patch_text(&self.range_tree_diff(range)?)
+    }
+
+    fn changed_files(&self, range: Option<&str>) -> Result<Vec<String>> {
+        let diff = match range {
+            Some(range) => self.range_tree_diff(range)?,
+            None => self.index_diff()?,
+        };
+        Ok(diff
+            .deltas()
+            .filter(|delta| delta.status() != git2::Delta::Deleted)
+            .filter_map(|delta| delta.new_file().path())
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    fn working_directory_status(&self) -> Result<WorkingDirectoryStatus> {
+        let statuses = self
+            .repo
+            .statuses(None)
+            .context("Failed to get repository status")?;
+
+        let mut untracked_changes = Vec::new();
+        for entry in statuses.iter() {
+            // Skip ignored files - they should not affect clean status
+            if entry.status().contains(Status::IGNORED) {
+                continue;
+            }
+            if let Ok(path) = entry.path() {
+                untracked_changes.push(FileStatus {
+                    status: format_status_flags(entry.status()),
+                    file: path.to_string(),
+                });
+            }
+        }
+
+        Ok(WorkingDirectoryStatus {
+            clean: untracked_changes.is_empty(),
+            untracked_changes,
+        })
+    }
+
+    fn push(&self, remote_name: &str, refspecs: &[&str], set_upstream: bool) -> Result<()> {
+        let workdir = self
+            .repo
+            .workdir()
+            .context("Cannot push: repository has no working directory")?;
+        let mut args = vec!["push"];
+        if set_upstream {
+            args.push("--set-upstream");
+        }
+        args.push(remote_name);
+        args.extend_from_slice(refspecs);
+        let output = run_git_cli(workdir, &args)?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("{}", stderr.trim())
+        }
+    }
+}
+
+/// Runs a `git` CLI subcommand in `workdir`.
+///
+/// Remote operations shell out to the user's `git` rather than using
+/// libgit2's network transport so they work across all URL schemes (SSH,
+/// HTTPS) and honour the user's existing authentication configuration
+/// (`ssh-agent`, `~/.ssh/config`, credential helpers). The vendored libgit2
+/// lacks a reliable SSH transport on some platforms. See issue #903.
+pub(crate) fn run_git_cli(workdir: &Path, args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .current_dir(workdir)
+        .args(args)
+        .output()
+        .context("Failed to execute git command")
+}
+
+/// Renders `diff` as the unified patch text `git diff` prints.
+fn patch_text(diff: &Diff<'_>) -> Result<String> {
+    let mut text = String::new();
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        // Content lines come without their marker; headers carry their own.
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            text.push(line.origin());
+        }
+        text.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .context("Failed to format the diff")?;
+    Ok(text)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn repo_with_commit() -> (tempfile::TempDir, Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn one() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        drop(tree);
+        (dir, repo)
+    }
+
+    #[test]
+    fn staged_diff_matches_git_diff_cached() {
+        let (dir, repo) = repo_with_commit();
+        let backend = Git2Backend::new(&repo);
+        assert!(!backend.has_staged_changes().unwrap());
+        assert_eq!(backend.staged_diff().unwrap(), "");
+
+        std::fs::write(dir.path().join("lib.rs"), "fn one() {}\nfn two() {}\n").unwrap();
+        std::fs::write(dir.path().join("unstaged.rs"), "fn three() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        index.write().unwrap();
+
+        assert!(backend.has_staged_changes().unwrap());
+        let diff = backend.staged_diff().unwrap();
+        assert!(diff.starts_with("diff --git a/lib.rs b/lib.rs\n"), "{diff}");
+        assert!(diff.contains("@@ -1 +1,2 @@\n fn one() {}\n+fn two() {}\n"));
+        assert!(!diff.contains("unstaged.rs"));
+    }
+
+    #[test]
+    fn working_diff_matches_git_diff_head() {
+        let (dir, repo) = repo_with_commit();
+        let backend = Git2Backend::new(&repo);
+        assert_eq!(backend.working_diff().unwrap(), "");
+
+        std::fs::write(dir.path().join("lib.rs"), "fn one() {}\nfn two() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        index.write().unwrap();
+        std::fs::write(
+            dir.path().join("lib.rs"),
+            "fn one() {}\nfn two() {}\nfn three() {}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("untracked.rs"), "fn four() {}\n").unwrap();
+
+        // Staged and unstaged edits show as one change; untracked files don't.
+        let diff = backend.working_diff().unwrap();
+        assert!(diff.contains("@@ -1 +1,3 @@\n fn one() {}\n+fn two() {}\n+fn three() {}\n"));
+        assert!(!diff.contains("untracked.rs"));
+    }
+
+    #[test]
+    fn range_diff_and_changed_files_follow_git_diff() {
+        let (dir, repo) = repo_with_commit();
+        let backend = Git2Backend::new(&repo);
+        let first = repo.head().unwrap().peel_to_commit().unwrap();
+
+        std::fs::write(dir.path().join("lib.rs"), "fn one() {}\nfn two() {}\n").unwrap();
+        std::fs::write(dir.path().join("new.rs"), "fn new() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        index.add_path(Path::new("new.rs")).unwrap();
+        index.write().unwrap();
+        assert_eq!(backend.changed_files(None).unwrap(), ["lib.rs", "new.rs"]);
+
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "second",
+            &tree,
+            &[&first],
+        )
+        .unwrap();
+        assert!(backend.changed_files(None).unwrap().is_empty());
+
+        let range = format!("{}..HEAD", first.id());
+        let diff = backend.range_diff(&range).unwrap();
+        assert!(diff.contains("@@ -1 +1,2 @@\n fn one() {}\n+fn two() {}\n"));
+        assert!(diff.contains("diff --git a/new.rs b/new.rs\n"));
+        assert_eq!(
+            backend.changed_files(Some(&range)).unwrap(),
+            ["lib.rs", "new.rs"]
+        );
+        // Deleted files are left out, as with `--diff-filter=d`.
+        let reverse = format!("HEAD..{}", first.id());
+        assert_eq!(backend.changed_files(Some(&reverse)).unwrap(), ["lib.rs"]);
+    }
+
+    #[test]
+    fn staged_changes_before_the_first_commit_diff_against_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let backend = Git2Backend::new(&repo);
+        assert!(!backend.has_staged_changes().unwrap());
+
+        std::fs::write(dir.path().join("new.rs"), "fn new() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("new.rs")).unwrap();
+        index.write().unwrap();
+
+        assert!(backend.has_staged_changes().unwrap());
+        assert!(backend.staged_diff().unwrap().contains("+fn new() {}\n"));
+        let status = backend.working_directory_status().unwrap();
+        assert!(!status.clean);
+        assert_eq!(status.untracked_changes[0].status, "A ");
+    }
+}
@@ -0,0 +1,161 @@
+//! The `git bisect` session in progress, for `git bisect assist`.
+//!
+//! Git keeps the session under `.git`: `BISECT_START` marks that one is
+//! running, `BISECT_TERMS` names the terms when they are not `bad`/`good`,
+//! and the marked commits are refs under `refs/bisect/`.
+
+use anyhow::{Context, Result};
+use git2::{Oid, Repository};
+
+/// Terms used when the session was started without `--term-new`/`--term-old`.
+const DEFAULT_TERMS: (&str, &str) = ("bad", "good");
+
+/// A bisect session: the commits marked so far.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BisectSession {
+    /// Term for commits that have the bug, `bad` unless renamed.
+    pub bad_term: String,
+    /// Term for commits without it, `good` unless renamed.
+    pub good_term: String,
+    /// The commit marked bad, once there is one.
+    pub bad: Option<String>,
+    /// The commits marked good.
+    pub good: Vec<String>,
+    /// The commits skipped because they cannot be tested.
+    pub skipped: Vec<String>,
+}
+
+impl BisectSession {
+    /// Reads the session in progress in `repo`, or `None` when no bisect is
+    /// running.
+    pub fn read(repo: &Repository) -> Result<Option<Self>> {
+        if !repo.path().join("BISECT_START").exists() {
+            return Ok(None);
+        }
+        let terms = std::fs::read_to_string(repo.path().join("BISECT_TERMS")).unwrap_or_default();
+        let mut terms = terms.lines().map(str::trim).filter(|t| !t.is_empty());
+        let (bad_term, good_term) = match (terms.next(), terms.next()) {
+            (Some(bad), Some(good)) => (bad.to_string(), good.to_string()),
+            _ => (DEFAULT_TERMS.0.to_string(), DEFAULT_TERMS.1.to_string()),
+        };
+
+        let bad = repo
+            .find_reference(&format!("refs/bisect/{bad_term}"))
+            .ok()
+            .and_then(|r| r.target())
+            .map(|oid| oid.to_string());
+        let good = marked(repo, &format!("refs/bisect/{good_term}-*"))?;
+        let skipped = marked(repo, "refs/bisect/skip-*")?;
+        Ok(Some(Self {
+            bad_term,
+            good_term,
+            bad,
+            good,
+            skipped,
+        }))
+    }
+
+    /// The commits that may have introduced the bug, newest first: those
+    /// reachable from the bad commit, itself included, but from no good
+    /// one. Merge commits are left out.
+    ///
+    /// Empty until both a bad and a good commit are marked.
+    pub fn suspects(&self, repo: &Repository) -> Result<Vec<String>> {
+        let Some(bad) = &self.bad else {
+            return Ok(Vec::new());
+        };
+        if self.good.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut walker = repo.revwalk().context("Failed to create revwalk")?;
+        walker.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        walker.push(Oid::from_str(bad)?)?;
+        for good in &self.good {
+            walker.hide(Oid::from_str(good)?)?;
+        }
+
+        let mut suspects = Vec::new();
+        for oid in walker {
+            let oid = oid.context("Failed to walk the bisect range")?;
+            let commit = repo.find_commit(oid).context("Failed to find commit")?;
+            if commit.parent_count() <= 1 {
+                suspects.push(oid.to_string());
+            }
+        }
+        Ok(suspects)
+    }
+
+    /// Whether `hash` was skipped in this session.
+    pub fn is_skipped(&self, hash: &str) -> bool {
+        self.skipped.iter().any(|s| s == hash)
+    }
+}
+
+/// The targets of the references matching `glob`, sorted.
+fn marked(repo: &Repository, glob: &str) -> Result<Vec<String>> {
+    let mut hashes = Vec::new();
+    for reference in repo
+        .references_glob(glob)
+        .with_context(|| format!("Failed to list {glob}"))?
+    {
+        if let Some(oid) = reference?.target() {
+            hashes.push(oid.to_string());
+        }
+    }
+    hashes.sort();
+    Ok(hashes)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &std::path::Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .current_dir(dir)
+            .args([
+                "-c",
+                "user.email=test@example.com",
+                "-c",
+                "user.name=Test",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed: {output:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn reads_the_session_and_its_suspects() {
+        let tmp_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tmp");
+        std::fs::create_dir_all(&tmp_root).unwrap();
+        let temp_dir = tempfile::tempdir_in(&tmp_root).unwrap();
+        let p = temp_dir.path();
+        git(p, &["init", "-q"]);
+        for n in 1..=5 {
+            std::fs::write(p.join("n.txt"), format!("{n}\n")).unwrap();
+            git(p, &["add", "."]);
+            git(p, &["commit", "-qm", &format!("commit {n}")]);
+        }
+        let repo = Repository::open(p).unwrap();
+        assert_eq!(BisectSession::read(&repo).unwrap(), None);
+
+        let head = git(p, &["rev-parse", "HEAD"]);
+        let good = git(p, &["rev-parse", "HEAD~3"]);
+        git(p, &["bisect", "start", "HEAD", "HEAD~3"]);
+        let skipped = git(p, &["rev-parse", "HEAD"]);
+        git(p, &["bisect", "skip"]);
+
+        let session = BisectSession::read(&repo).unwrap().unwrap();
+        assert_eq!(session.bad.as_deref(), Some(head.as_str()));
+        assert_eq!(session.good, vec![good]);
+        assert!(session.is_skipped(&skipped));
+        let suspects = session.suspects(&repo).unwrap();
+        assert_eq!(suspects.len(), 3);
+        assert_eq!(suspects[0], head);
+    }
+}
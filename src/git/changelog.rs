@@ -0,0 +1,430 @@
+//! Keep a Changelog (`CHANGELOG.md`) parsing and updating.
+//!
+//! The changelog is kept as its original lines plus the position of each
+//! `## ` release heading, so updating the `Unreleased` section rewrites only
+//! that section and leaves every other line — including hand-written entries
+//! and the link references at the bottom — exactly as it was.
+
+use crate::git::release::ReleaseEntry;
+
+/// Keep a Changelog categories in their conventional order.
+pub const CHANGELOG_CATEGORIES: &[&str] = &[
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
+/// Title of the section collecting changes not yet released.
+pub const UNRELEASED: &str = "Unreleased";
+
+/// One `## ` section of the changelog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogRelease {
+    /// Released version, or `None` for the `Unreleased` section.
+    pub version: Option<String>,
+    /// Release date as written after the version, if any.
+    pub date: Option<String>,
+    /// Index of the heading line.
+    start: usize,
+    /// Index one past the section's last line.
+    end: usize,
+}
+
+/// A parsed `CHANGELOG.md`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changelog {
+    lines: Vec<String>,
+    releases: Vec<ChangelogRelease>,
+}
+
+impl Changelog {
+    /// Parses changelog text. Any markdown parses; text without `## `
+    /// headings simply has no releases.
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<String> = text.lines().map(str::to_string).collect();
+        // Link reference definitions (`[0.1.0]: https://…`) end the last section.
+        let body_end = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty() && !is_link_definition(line))
+            .map_or(0, |i| i + 1);
+
+        let starts: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("## "))
+            .map(|(i, _)| i)
+            .collect();
+        let releases = starts
+            .iter()
+            .enumerate()
+            .map(|(n, &start)| {
+                let end = starts
+                    .get(n + 1)
+                    .copied()
+                    .unwrap_or(body_end.max(start + 1));
+                let (version, date) = parse_release_heading(&lines[start]);
+                ChangelogRelease {
+                    version,
+                    date,
+                    start,
+                    end,
+                }
+            })
+            .collect();
+        Self { lines, releases }
+    }
+
+    /// Returns the sections in file order.
+    pub fn releases(&self) -> &[ChangelogRelease] {
+        &self.releases
+    }
+
+    /// Returns the most recent released version (the first versioned section).
+    pub fn latest_version(&self) -> Option<&str> {
+        self.releases.iter().find_map(|r| r.version.as_deref())
+    }
+
+    /// Adds `entries` to the `Unreleased` section, creating the section (and
+    /// any missing category) as needed. Entries whose short hash already
+    /// appears in the section are skipped so repeated updates are idempotent.
+    ///
+    /// Returns the bullet lines added.
+    pub fn add_unreleased(&mut self, entries: &[ReleaseEntry]) -> Vec<String> {
+        let existing = self.releases.iter().find(|r| r.version.is_none()).cloned();
+        let (start, end, mut section) = match &existing {
+            Some(r) => (
+                r.start,
+                r.end,
+                UnreleasedSection::parse(&self.lines[r.start + 1..r.end]),
+            ),
+            None => {
+                let at = self.releases.first().map_or_else(
+                    || {
+                        self.lines
+                            .iter()
+                            .rposition(|l| !l.trim().is_empty() && !is_link_definition(l))
+                            .map_or(0, |i| i + 1)
+                    },
+                    |r| r.start,
+                );
+                (at, at, UnreleasedSection::default())
+            }
+        };
+
+        let mut added = Vec::new();
+        for entry in entries {
+            let Some(category) = changelog_category(entry) else {
+                continue;
+            };
+            let marker = format!("({})", entry.short_hash);
+            if section.contains(&marker) {
+                continue;
+            }
+            let line = format_changelog_entry(entry);
+            section.push(category, line.clone());
+            added.push(line);
+        }
+        if added.is_empty() {
+            return added;
+        }
+
+        let mut replacement = vec![format!("## [{UNRELEASED}]"), String::new()];
+        replacement.extend(section.render());
+        if existing.is_none() && start > 0 && !self.lines[start - 1].trim().is_empty() {
+            replacement.insert(0, String::new());
+        }
+        if existing.is_none() && start == self.lines.len() {
+            // Appended at the very end: drop the separator blank line.
+            replacement.pop();
+        }
+        self.lines.splice(start..end, replacement);
+        *self = Self::parse(&self.render());
+        added
+    }
+
+    /// Renders the changelog back to text with a trailing newline.
+    pub fn render(&self) -> String {
+        let mut out = self.lines.join("\n");
+        out.push('\n');
+        out
+    }
+}
+
+/// The body of the `Unreleased` section: free text before the first `### `
+/// heading followed by category subsections.
+#[derive(Debug, Default)]
+struct UnreleasedSection {
+    intro: Vec<String>,
+    categories: Vec<(String, Vec<String>)>,
+}
+
+impl UnreleasedSection {
+    fn parse(lines: &[String]) -> Self {
+        let mut section = Self::default();
+        for line in lines {
+            if let Some(title) = line.strip_prefix("### ") {
+                section
+                    .categories
+                    .push((title.trim().to_string(), Vec::new()));
+            } else if let Some((_, body)) = section.categories.last_mut() {
+                body.push(line.clone());
+            } else {
+                section.intro.push(line.clone());
+            }
+        }
+        trim_blank_lines(&mut section.intro);
+        for (_, body) in &mut section.categories {
+            trim_blank_lines(body);
+        }
+        section
+    }
+
+    fn contains(&self, needle: &str) -> bool {
+        self.intro
+            .iter()
+            .chain(self.categories.iter().flat_map(|(_, body)| body))
+            .any(|line| line.contains(needle))
+    }
+
+    /// Appends `line` to `category`, creating the subsection in canonical
+    /// order before the first known category that sorts after it.
+    fn push(&mut self, category: &str, line: String) {
+        if let Some((_, body)) = self
+            .categories
+            .iter_mut()
+            .find(|(title, _)| title.eq_ignore_ascii_case(category))
+        {
+            body.push(line);
+            return;
+        }
+        let rank = |title: &str| {
+            CHANGELOG_CATEGORIES
+                .iter()
+                .position(|c| c.eq_ignore_ascii_case(title))
+        };
+        let new_rank = rank(category);
+        let at = self
+            .categories
+            .iter()
+            .position(|(title, _)| matches!((rank(title), new_rank), (Some(r), Some(n)) if r > n))
+            .unwrap_or(self.categories.len());
+        self.categories
+            .insert(at, (category.to_string(), vec![line]));
+    }
+
+    /// Renders the section body, each block followed by a blank line.
+    fn render(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        if !self.intro.is_empty() {
+            out.extend(self.intro.iter().cloned());
+            out.push(String::new());
+        }
+        for (title, body) in &self.categories {
+            out.push(format!("### {title}"));
+            out.extend(body.iter().cloned());
+            out.push(String::new());
+        }
+        out
+    }
+}
+
+/// Removes leading and trailing blank lines.
+fn trim_blank_lines(lines: &mut Vec<String>) {
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    let leading = lines.iter().take_while(|l| l.trim().is_empty()).count();
+    lines.drain(..leading);
+}
+
+/// Whether `line` is a markdown link reference definition (`[label]: url`).
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[')
+        && line
+            .split_once("]:")
+            .is_some_and(|(label, _)| !label.contains(']'))
+}
+
+/// Parses a `## ` heading such as `## [1.2.0] - 2026-01-31`, `## v1.2.0`, or
+/// `## [Unreleased]` into its version (`None` for Unreleased) and date.
+fn parse_release_heading(line: &str) -> (Option<String>, Option<String>) {
+    let heading = line.trim_start_matches('#').trim();
+    let (title, date) = match heading.split_once(" - ") {
+        Some((title, date)) => (title.trim(), Some(date.trim().to_string())),
+        None => (heading, None),
+    };
+    let title = title.trim_start_matches('[').trim_end_matches(']').trim();
+    if title.eq_ignore_ascii_case(UNRELEASED) {
+        (None, None)
+    } else {
+        (Some(title.to_string()), date)
+    }
+}
+
+/// Chooses the Keep a Changelog category for a commit, or `None` for commits
+/// that are not user-facing (tests, CI, build, chores, docs, style).
+pub fn changelog_category(entry: &ReleaseEntry) -> Option<&'static str> {
+    let description = entry.description.to_ascii_lowercase();
+    if entry.kind == "security" || entry.scope.as_deref() == Some("security") {
+        return Some("Security");
+    }
+    if matches!(entry.kind.as_str(), "feat" | "refactor" | "perf" | "revert") || entry.breaking {
+        if description.starts_with("deprecate") {
+            return Some("Deprecated");
+        }
+        if description.starts_with("remove") || description.starts_with("drop") {
+            return Some("Removed");
+        }
+    }
+    if entry.breaking {
+        return Some("Changed");
+    }
+    match entry.kind.as_str() {
+        "feat" => Some("Added"),
+        "fix" => Some("Fixed"),
+        "perf" | "refactor" | "revert" => Some("Changed"),
+        _ => None,
+    }
+}
+
+/// Formats a changelog bullet: `- **scope:** Description (hash)`, with
+/// breaking changes flagged.
+pub fn format_changelog_entry(entry: &ReleaseEntry) -> String {
+    let mut description = entry.description.trim().to_string();
+    if let Some(first) = description.get(..1) {
+        description.replace_range(..1, &first.to_uppercase());
+    }
+    let breaking = if entry.breaking { "**BREAKING:** " } else { "" };
+    let scope = entry
+        .scope
+        .as_deref()
+        .map(|s| format!("**{s}:** "))
+        .unwrap_or_default();
+    format!("- {breaking}{scope}{description} ({})", entry.short_hash)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &str, scope: Option<&str>, description: &str, hash: &str) -> ReleaseEntry {
+        ReleaseEntry {
+            kind: kind.to_string(),
+            scope: scope.map(str::to_string),
+            description: description.to_string(),
+            short_hash: hash.to_string(),
+            breaking: false,
+            body: String::new(),
+        }
+    }
+
+    const CHANGELOG: &str = "# Changelog
+
+Intro text.
+
+## [Unreleased]
+
+### Fixed
+- Hand-written fix
+
+## [1.1.0] - 2026-02-01
+
+### Added
+- Old feature
+
+## 1.0.0 - 2026-01-01
+
+- First release
+
+[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0
+";
+
+    #[test]
+    fn parses_releases() {
+        let changelog = Changelog::parse(CHANGELOG);
+        let versions: Vec<Option<&str>> = changelog
+            .releases()
+            .iter()
+            .map(|r| r.version.as_deref())
+            .collect();
+        assert_eq!(versions, vec![None, Some("1.1.0"), Some("1.0.0")]);
+        assert_eq!(changelog.latest_version(), Some("1.1.0"));
+        assert_eq!(changelog.releases()[1].date.as_deref(), Some("2026-02-01"));
+        assert_eq!(changelog.render(), CHANGELOG);
+    }
+
+    #[test]
+    fn categorizes_commits() {
+        assert_eq!(
+            changelog_category(&entry("feat", None, "add x", "a")),
+            Some("Added")
+        );
+        assert_eq!(
+            changelog_category(&entry("fix", None, "fix x", "a")),
+            Some("Fixed")
+        );
+        assert_eq!(
+            changelog_category(&entry("refactor", None, "remove old flag", "a")),
+            Some("Removed")
+        );
+        assert_eq!(
+            changelog_category(&entry("feat", None, "deprecate --foo", "a")),
+            Some("Deprecated")
+        );
+        assert_eq!(
+            changelog_category(&entry("fix", Some("security"), "escape input", "a")),
+            Some("Security")
+        );
+        assert_eq!(changelog_category(&entry("chore", None, "bump", "a")), None);
+        let mut breaking = entry("fix", None, "change default", "a");
+        breaking.breaking = true;
+        assert_eq!(changelog_category(&breaking), Some("Changed"));
+        assert_eq!(
+            format_changelog_entry(&breaking),
+            "- **BREAKING:** Change default (a)"
+        );
+    }
+
+    #[test]
+    fn adds_entries_under_unreleased_preserving_content() {
+        let mut changelog = Changelog::parse(CHANGELOG);
+        let entries = vec![
+            entry("fix", Some("cli"), "handle empty input", "aaaaaaaa"),
+            entry("feat", None, "add notes", "bbbbbbbb"),
+            entry("chore", None, "bump deps", "cccccccc"),
+        ];
+        assert_eq!(changelog.add_unreleased(&entries).len(), 2);
+        let text = changelog.render();
+        assert!(text.contains(
+            "## [Unreleased]\n\n### Added\n- Add notes (bbbbbbbb)\n\n### Fixed\n- Hand-written fix\n- **cli:** Handle empty input (aaaaaaaa)\n\n## [1.1.0]"
+        ));
+        assert!(text.starts_with("# Changelog\n\nIntro text.\n\n## [Unreleased]"));
+        assert!(text.ends_with("[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0\n"));
+
+        // A second run adds nothing.
+        assert!(changelog.add_unreleased(&entries).is_empty());
+        assert_eq!(changelog.render(), text);
+    }
+
+    #[test]
+    fn creates_unreleased_section_when_missing() {
+        let mut changelog =
+            Changelog::parse("# Changelog\n\n## [1.0.0] - 2026-01-01\n\n- First release\n");
+        changelog.add_unreleased(&[entry("feat", None, "add notes", "bbbbbbbb")]);
+        assert_eq!(
+            changelog.render(),
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n- Add notes (bbbbbbbb)\n\n## [1.0.0] - 2026-01-01\n\n- First release\n"
+        );
+
+        let mut changelog = Changelog::parse("# Changelog\n");
+        changelog.add_unreleased(&[entry("fix", None, "fix notes", "aaaaaaaa")]);
+        assert_eq!(
+            changelog.render(),
+            "# Changelog\n\n## [Unreleased]\n\n### Fixed\n- Fix notes (aaaaaaaa)\n"
+        );
+    }
+}
@@ -0,0 +1,257 @@
+//! The changelog rule: user-facing commits must come with a changelog entry.
+//!
+//! A commit needs an entry when its conventional type is one of the rule's
+//! types (`feat` and `fix` by default), unless its scope is exempt, every
+//! file it changes matches an exempt path, or its message carries a
+//! `[skip changelog]` marker. By default the rule looks at the branch as a
+//! whole — one commit touching a changelog file covers the rest — and can be
+//! made to require an entry from each commit instead.
+//!
+//! The rule is enabled for `git commit message check` by
+//! `.omni-dev/changelog-rule.yaml` (resolved like the other context files):
+//!
+//! ```yaml
+//! paths: [CHANGELOG.md]         # files that count as a changelog entry
+//! types: [feat, fix]            # commit types that need one
+//! per: branch                   # or `commit`
+//! severity: warning             # or `error` or `info`
+//! exempt_scopes: [ci, deps]
+//! exempt_paths: ["docs/**", "**/tests/**"]
+//! ```
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+
+use crate::data::check::IssueSeverity;
+use crate::git::release::release_entry;
+use crate::git::CommitInfo;
+
+/// Name of the project configuration file in the context directory.
+pub const CHANGELOG_RULE_FILE: &str = "changelog-rule.yaml";
+
+/// Rule name reported for missing entries.
+pub const CHANGELOG_RULE: &str = "changelog-entry";
+
+/// Message markers that exempt a commit, matched case-insensitively.
+const SKIP_MARKERS: &[&str] = &["[skip changelog]", "[no changelog]"];
+
+/// Whether the rule applies to the branch as a whole or to each commit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleScope {
+    /// A changelog change anywhere in the range covers every commit.
+    #[default]
+    Branch,
+    /// Each commit that needs an entry must change a changelog file itself.
+    Commit,
+}
+
+/// Contents of `changelog-rule.yaml`.
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct ChangelogRuleConfig {
+    #[serde(default = "default_paths")]
+    paths: Vec<String>,
+    #[serde(default = "default_types")]
+    types: Vec<String>,
+    #[serde(default)]
+    per: RuleScope,
+    #[serde(default = "default_severity")]
+    severity: IssueSeverity,
+    #[serde(default)]
+    exempt_scopes: Vec<String>,
+    #[serde(default)]
+    exempt_paths: Vec<String>,
+}
+
+fn default_paths() -> Vec<String> {
+    vec!["CHANGELOG.md".to_string()]
+}
+
+fn default_types() -> Vec<String> {
+    vec!["feat".to_string(), "fix".to_string()]
+}
+
+fn default_severity() -> IssueSeverity {
+    IssueSeverity::Warning
+}
+
+/// JSON Schema of `changelog-rule.yaml`, for `omni-dev config validate`.
+pub fn rule_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(ChangelogRuleConfig))
+        .unwrap_or(serde_json::Value::Null)
+}
+
+/// The compiled changelog rule.
+#[derive(Debug, Clone)]
+pub struct ChangelogRule {
+    /// Changelog file patterns as configured; the first names the file
+    /// `--fix` writes.
+    pub paths: Vec<String>,
+    /// Whether the rule applies per branch or per commit.
+    pub per: RuleScope,
+    /// Severity of the issue reported for a missing entry.
+    pub severity: IssueSeverity,
+    types: Vec<String>,
+    exempt_scopes: Vec<String>,
+    changelog: GlobSet,
+    exempt: GlobSet,
+}
+
+impl ChangelogRule {
+    /// Builds the rule from a `changelog-rule.yaml` document; an empty
+    /// document gives the defaults.
+    pub fn from_config(content: &str) -> Result<Self> {
+        let config: ChangelogRuleConfig = if content.trim().is_empty() {
+            serde_yaml::from_str("{}")
+        } else {
+            serde_yaml::from_str(content)
+        }
+        .context("Failed to parse changelog-rule.yaml")?;
+        Ok(Self {
+            changelog: build_globset(&config.paths)?,
+            exempt: build_globset(&config.exempt_paths)?,
+            paths: config.paths,
+            per: config.per,
+            severity: config.severity,
+            types: config.types,
+            exempt_scopes: config.exempt_scopes,
+        })
+    }
+
+    /// Loads the rule configured for the repository at `repo_root`, or
+    /// `None` when the project has no `changelog-rule.yaml`.
+    pub fn load(repo_root: &Path) -> Result<Option<Self>> {
+        let context_dir = crate::claude::context::resolve_context_dir_at(None, repo_root);
+        let path = crate::claude::context::discovery::resolve_config_file(
+            &context_dir,
+            CHANGELOG_RULE_FILE,
+        );
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        Self::from_config(&content)
+            .with_context(|| format!("Invalid {}", path.display()))
+            .map(Some)
+    }
+
+    /// Returns whether `commit` changes a changelog file.
+    pub fn touches_changelog(&self, commit: &CommitInfo) -> bool {
+        files(commit).any(|file| self.changelog.is_match(file))
+    }
+
+    /// Returns whether `commit` is one that needs a changelog entry.
+    pub fn needs_entry(&self, commit: &CommitInfo) -> bool {
+        let message = commit.original_message.to_lowercase();
+        if SKIP_MARKERS.iter().any(|marker| message.contains(marker)) {
+            return false;
+        }
+        let entry = release_entry(commit);
+        if !self
+            .types
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(&entry.kind))
+        {
+            return false;
+        }
+        let exempt_scope = entry.scope.as_deref().is_some_and(|scopes| {
+            scopes
+                .split(',')
+                .any(|scope| self.exempt_scopes.iter().any(|e| e == scope.trim()))
+        });
+        let mut changed = files(commit).peekable();
+        let only_exempt_paths =
+            changed.peek().is_some() && changed.all(|file| self.exempt.is_match(file));
+        !exempt_scope && !only_exempt_paths
+    }
+
+    /// Returns the commits that lack a changelog entry the rule requires, in
+    /// the order given.
+    pub fn missing_entries<'a>(&self, commits: &'a [CommitInfo]) -> Vec<&'a CommitInfo> {
+        if self.per == RuleScope::Branch && commits.iter().any(|c| self.touches_changelog(c)) {
+            return Vec::new();
+        }
+        commits
+            .iter()
+            .filter(|c| self.needs_entry(c) && !self.touches_changelog(c))
+            .collect()
+    }
+}
+
+fn files(commit: &CommitInfo) -> impl Iterator<Item = &str> {
+    commit
+        .analysis
+        .file_changes
+        .file_list
+        .iter()
+        .map(|change| change.file.as_str())
+}
+
+fn build_globset(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            GlobBuilder::new(pattern.trim().trim_start_matches("./"))
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid changelog rule pattern '{pattern}'"))?,
+        );
+    }
+    builder
+        .build()
+        .context("Failed to compile changelog rule patterns")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::commits;
+
+    fn commit(message: &str, files: &[&str]) -> CommitInfo {
+        commits::commit(&"a".repeat(40), message, files)
+    }
+
+    #[test]
+    fn only_user_facing_commits_need_an_entry() {
+        let rule = ChangelogRule::from_config("exempt_scopes: [ci]\nexempt_paths: [\"docs/**\"]\n")
+            .unwrap();
+        assert!(rule.needs_entry(&commit("feat(cli): add --fix", &["src/cli.rs"])));
+        assert!(rule.needs_entry(&commit("fix: handle empty input", &["src/lib.rs"])));
+        assert!(!rule.needs_entry(&commit("chore: bump deps", &["Cargo.toml"])));
+        assert!(!rule.needs_entry(&commit("fix(ci): pin runner", &["src/lib.rs"])));
+        assert!(!rule.needs_entry(&commit("fix: typo", &["docs/guide.md"])));
+        assert!(!rule.needs_entry(&commit(
+            "feat: internal flag\n\n[Skip Changelog]",
+            &["src/lib.rs"]
+        )));
+        assert!(ChangelogRule::from_config("pers: commit\n").is_err());
+    }
+
+    #[test]
+    fn branch_rule_is_satisfied_by_any_changelog_change() {
+        let commits = vec![
+            commit("feat: add notes", &["src/notes.rs"]),
+            commit("fix: notes crash", &["src/notes.rs"]),
+            commit("docs: changelog", &["CHANGELOG.md"]),
+        ];
+        let rule = ChangelogRule::from_config("").unwrap();
+        assert!(rule.missing_entries(&commits).is_empty());
+        assert_eq!(rule.missing_entries(&commits[..2]).len(), 2);
+
+        let per_commit = ChangelogRule::from_config("per: commit\n").unwrap();
+        let missing = per_commit.missing_entries(&commits);
+        let subjects: Vec<&str> = missing
+            .iter()
+            .map(|c| c.original_message.as_str())
+            .collect();
+        assert_eq!(subjects, vec!["feat: add notes", "fix: notes crash"]);
+    }
+}
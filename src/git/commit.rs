@@ -10,22 +10,45 @@ use globset::Glob;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-use crate::data::context::ScopeDefinition;
+use crate::data::context::{ScopeDefinition, TypeDefinition};
+use crate::git::analysis_cache::AnalysisCache;
+use crate::git::dependency_bump::{dependency_updates, DependencyUpdate};
 use crate::git::diff_split::split_by_file;
+use crate::git::release::split_conventional_subject;
+use crate::git::submodule::{annotate_diff, submodule_changes, SubmoduleChange};
 
 /// Matches conventional commit scope patterns including breaking-change syntax.
 #[allow(clippy::unwrap_used)] // Compile-time constant regex pattern
 static SCOPE_RE: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[a-z]+!\(([^)]+)\):|^[a-z]+\(([^)]+)\):").unwrap());
 
+/// Built-in conventional commit types, used when a project does not define
+/// its own in `.omni-dev/types.yaml`.
+pub const DEFAULT_COMMIT_TYPES: &[(&str, &str)] = &[
+    ("feat", "A new feature"),
+    ("fix", "A bug fix"),
+    ("docs", "Documentation-only changes"),
+    ("style", "Formatting changes that do not affect behaviour"),
+    (
+        "refactor",
+        "A code change that neither fixes a bug nor adds a feature",
+    ),
+    ("test", "Adding or correcting tests"),
+    ("chore", "Maintenance that does not touch source or tests"),
+    ("build", "Build system or dependency changes"),
+    ("ci", "CI configuration changes"),
+    ("perf", "A code change that improves performance"),
+];
+
 /// Commit information structure, generic over analysis type.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CommitInfo<A = CommitAnalysis> {
     /// Full SHA-1 hash of the commit.
     pub hash: String,
     /// Commit author name and email address.
     pub author: String,
     /// Commit date in ISO format with timezone.
+    #[schemars(with = "String")]
     pub date: DateTime<FixedOffset>,
     /// The original commit message as written by the author.
     pub original_message: String,
@@ -36,7 +59,7 @@ pub struct CommitInfo<A = CommitAnalysis> {
 }
 
 /// Commit analysis information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct CommitAnalysis {
     /// Automatically detected conventional commit type (feat, fix, docs, test, chore, etc.).
     pub detected_type: String,
@@ -48,11 +71,20 @@ pub struct CommitAnalysis {
     pub file_changes: FileChanges,
     /// Git diff --stat output showing lines changed per file.
     pub diff_summary: String,
-    /// Path to diff file showing line-by-line changes.
+    /// Path to diff file showing line-by-line changes; empty (and omitted)
+    /// in summary-only output.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub diff_file: String,
     /// Per-file diff references for individual file changes.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub file_diffs: Vec<FileDiffRef>,
+    /// Submodule pointer changes, with the upstream commits each bump brings in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submodule_changes: Vec<SubmoduleChange>,
+    /// Package versions changed by a dependency-bump commit (one that only
+    /// touches lockfiles, or a bot's lockfile and manifest update).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependency_updates: Vec<DependencyUpdate>,
 }
 
 /// Reference to a per-file diff stored on disk.
@@ -60,7 +92,7 @@ pub struct CommitAnalysis {
 /// Tracks the repository-relative file path, the absolute path to the
 /// diff file on disk, and the byte length of that diff. Gives consumers
 /// per-file size information without loading diff content into memory.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FileDiffRef {
     /// Repository-relative path of the changed file.
     pub path: String,
@@ -92,7 +124,7 @@ pub struct CommitInfoForAI {
 }
 
 /// File changes statistics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FileChanges {
     /// Total number of files modified in this commit.
     pub total_files: usize,
@@ -105,7 +137,7 @@ pub struct FileChanges {
 }
 
 /// Individual file change.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FileChange {
     /// Git status code (A=added, M=modified, D=deleted, R=renamed).
     pub status: String,
@@ -161,7 +193,25 @@ impl CommitInfo {
 
 impl CommitAnalysis {
     /// Analyzes a commit and generates analysis information.
+    ///
+    /// Served from the repository's analysis cache when the commit was
+    /// analyzed before (see [`crate::git::analysis_cache`]).
     pub fn analyze_commit(repo: &Repository, commit: &Commit) -> Result<Self> {
+        let repo_root = repo.workdir().unwrap_or_else(|| repo.path());
+        let cache = AnalysisCache::for_repo(repo_root);
+        let hash = commit.id().to_string();
+        if let Some(analysis) = cache.as_ref().and_then(|c| c.load(&hash)) {
+            return Ok(analysis);
+        }
+        let analysis = Self::analyze_uncached(repo, commit)?;
+        if let Some(cache) = &cache {
+            cache.store(&hash, &analysis);
+        }
+        Ok(analysis)
+    }
+
+    /// Analyzes a commit from its diff, bypassing the cache.
+    pub(crate) fn analyze_uncached(repo: &Repository, commit: &Commit) -> Result<Self> {
         // Get file changes
         let file_changes = Self::analyze_file_changes(repo, commit)?;
 
@@ -178,8 +228,14 @@ impl CommitAnalysis {
         // Get diff summary
         let diff_summary = Self::get_diff_summary(repo, commit)?;
 
+        // Find submodule bumps and what they bring in
+        let submodule_changes = Self::analyze_submodule_changes(repo, commit)?;
+
+        // List the package versions a dependency bump changes
+        let dependency_updates = dependency_updates(repo, commit, &file_changes);
+
         // Write diff to file and get path
-        let (diff_file, file_diffs) = Self::write_diff_to_file(repo, commit)?;
+        let (diff_file, file_diffs) = Self::write_diff_to_file(repo, commit, &submodule_changes)?;
 
         Ok(Self {
             detected_type,
@@ -189,6 +245,8 @@ impl CommitAnalysis {
             diff_summary,
             diff_file,
             file_diffs,
+            submodule_changes,
+            dependency_updates,
         })
     }
 
@@ -270,6 +328,29 @@ impl CommitAnalysis {
         })
     }
 
+    /// Finds submodule pointer changes in the commit.
+    fn analyze_submodule_changes(
+        repo: &Repository,
+        commit: &Commit,
+    ) -> Result<Vec<SubmoduleChange>> {
+        let commit_tree = commit.tree().context("Failed to get commit tree")?;
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(
+                commit
+                    .parent(0)
+                    .context("Failed to get parent commit")?
+                    .tree()
+                    .context("Failed to get parent tree")?,
+            )
+        } else {
+            None
+        };
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), None)
+            .context("Failed to create diff")?;
+        Ok(submodule_changes(repo, &diff))
+    }
+
     /// Detects conventional commit type based on files and existing message.
     fn detect_commit_type(commit: &Commit, file_changes: &FileChanges) -> String {
         Self::detect_commit_type_from_message(commit.message().unwrap_or(""), file_changes)
@@ -331,36 +412,14 @@ impl CommitAnalysis {
 
     /// Extracts conventional commit type from an existing message.
     fn extract_conventional_type(message: &str) -> Option<String> {
-        let first_line = message.lines().next().unwrap_or("");
-        if let Some(colon_pos) = first_line.find(':') {
-            let prefix = &first_line[..colon_pos];
-            if let Some(paren_pos) = prefix.find('(') {
-                let type_part = &prefix[..paren_pos];
-                if Self::is_valid_conventional_type(type_part) {
-                    return Some(type_part.to_string());
-                }
-            } else if Self::is_valid_conventional_type(prefix) {
-                return Some(prefix.to_string());
-            }
-        }
-        None
+        conventional_type_prefix(message)
+            .filter(|t| Self::is_valid_conventional_type(t))
+            .map(str::to_string)
     }
 
     /// Checks if a string is a valid conventional commit type.
     fn is_valid_conventional_type(s: &str) -> bool {
-        matches!(
-            s,
-            "feat"
-                | "fix"
-                | "docs"
-                | "style"
-                | "refactor"
-                | "test"
-                | "chore"
-                | "build"
-                | "ci"
-                | "perf"
-        )
+        DEFAULT_COMMIT_TYPES.iter().any(|(name, _)| *name == s)
     }
 
     /// Detects scope from file paths.
@@ -392,6 +451,16 @@ impl CommitAnalysis {
         }
     }
 
+    /// Whether the diff files this analysis points at are all still on disk;
+    /// the AI scratch directory they live in may have been cleared.
+    pub fn diff_files_present(&self) -> bool {
+        std::path::Path::new(&self.diff_file).is_file()
+            && self
+                .file_diffs
+                .iter()
+                .all(|d| std::path::Path::new(&d.diff_file).is_file())
+    }
+
     /// Re-detects scope using file_patterns from scope definitions.
     ///
     /// More specific patterns (more literal path components) win regardless of
@@ -411,6 +480,20 @@ impl CommitAnalysis {
         }
     }
 
+    /// Re-detects the commit type against the project's commit types.
+    ///
+    /// Built-in detection only recognises the standard conventional types, so
+    /// a message already using a project-defined type (e.g. `infra:`) would
+    /// otherwise be classified by its files. When `commit_types` is empty the
+    /// built-in detection is kept.
+    pub fn refine_type(&mut self, message: &str, commit_types: &[TypeDefinition]) {
+        if let Some(existing) = conventional_type_prefix(message) {
+            if commit_types.iter().any(|t| t.name == existing) {
+                self.detected_type = existing.to_string();
+            }
+        }
+    }
+
     /// Generates a proposed conventional commit message.
     fn generate_proposed_message(
         commit: &Commit,
@@ -525,6 +608,7 @@ impl CommitAnalysis {
     fn write_diff_to_file(
         repo: &Repository,
         commit: &Commit,
+        submodule_changes: &[SubmoduleChange],
     ) -> Result<(String, Vec<FileDiffRef>)> {
         // Get AI scratch directory, anchored to the opened repository's workdir
         // (#967) so the per-commit diff files land under the same repo the rest
@@ -586,6 +670,19 @@ impl CommitAnalysis {
             diff_content.push('\n');
         }
 
+        // Spell out submodule bumps, which the patch shows only as pointer lines.
+        let diff_content = annotate_diff(&diff_content, submodule_changes);
+
+        // Replace lockfile, generated, and vendored diffs with stubs so they
+        // don't crowd the prompt, then apply any configured size limits.
+        let diff_content =
+            crate::git::GeneratedFiles::for_repo(repo_root).strip_diff(&diff_content);
+        let diff_content = crate::git::DiffLimits::from_settings().apply(&diff_content);
+
+        // Mask committed tokens so neither the diff files nor the prompts
+        // built from them carry the raw value.
+        let diff_content = crate::utils::redact::redact_secrets(&diff_content).into_owned();
+
         // Write flat diff content to file
         fs::write(&diff_path, &diff_content).context("Failed to write diff file")?;
 
@@ -729,7 +826,22 @@ impl CommitInfoForAI {
     /// Runs deterministic pre-validation checks on the commit message.
     /// Passing checks are recorded in pre_validated_checks so the LLM
     /// can skip re-checking them. Failing checks are not recorded.
-    pub fn run_pre_validation_checks(&mut self, valid_scopes: &[ScopeDefinition]) {
+    pub fn run_pre_validation_checks(
+        &mut self,
+        valid_scopes: &[ScopeDefinition],
+        commit_types: &[TypeDefinition],
+    ) {
+        // Deterministic type validity check against the project's types
+        if !commit_types.is_empty() {
+            if let Some(commit_type) = conventional_type_prefix(&self.base.original_message) {
+                if commit_types.iter().any(|t| t.name == commit_type) {
+                    self.pre_validated_checks.push(format!(
+                        "Type validity verified: '{commit_type}' is in the project's commit types list"
+                    ));
+                }
+            }
+        }
+
         if let Some(caps) = SCOPE_RE.captures(&self.base.original_message) {
             let scope = caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str());
             if let Some(scope) = scope {
@@ -756,6 +868,15 @@ impl CommitInfoForAI {
     }
 }
 
+/// Returns the type keyword of a conventional commit subject (the text before
+/// an optional `(scope)`, `!`, and the first colon), whether or not it is a
+/// known type.
+fn conventional_type_prefix(message: &str) -> Option<&str> {
+    split_conventional_subject(message)
+        .map(|parts| parts.kind)
+        .filter(|kind| !kind.is_empty())
+}
+
 /// Resolves the best scope for a set of files using scope definition file patterns.
 ///
 /// More specific patterns (more literal path components) win regardless of
@@ -877,7 +998,7 @@ fn scope_matches_files(files: &[&str], patterns: &[String]) -> Option<usize> {
 /// - `docs/**` → 1 (`docs`)
 /// - `*.md` → 0
 /// - `src/main/scala/**` → 3
-fn count_specificity(pattern: &str) -> usize {
+pub(crate) fn count_specificity(pattern: &str) -> usize {
     pattern
         .split('/')
         .filter(|segment| !segment.contains('*') && !segment.contains('?'))
@@ -886,10 +1007,16 @@ fn count_specificity(pattern: &str) -> usize {
 
 impl CommitAnalysisForAI {
     /// Converts from a basic `CommitAnalysis` by loading diff content from file.
+    ///
+    /// A diff over the `OMNI_DEV_SPILL_DIFF_BYTES` threshold is loaded only
+    /// as a head and tail excerpt; see [`crate::git::diff_limit`].
     pub fn from_commit_analysis(analysis: CommitAnalysis) -> Result<Self> {
-        // Read the actual diff content from the file
-        let diff_content = fs::read_to_string(&analysis.diff_file)
-            .with_context(|| format!("Failed to read diff file: {}", analysis.diff_file))?;
+        let diff_content = crate::git::diff_limit::read_diff_excerpt(
+            std::path::Path::new(&analysis.diff_file),
+            crate::git::diff_limit::spill_threshold(),
+            &analysis.diff_summary,
+        )
+        .with_context(|| format!("Failed to read diff file: {}", analysis.diff_file))?;
 
         Ok(Self {
             base: analysis,
@@ -922,6 +1049,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn conventional_type_with_breaking_marker() {
+        // Parsed like the release notes do, so the `!` is not part of the type.
+        assert_eq!(
+            CommitAnalysis::extract_conventional_type("feat(cli)!: drop flag"),
+            Some("feat".to_string())
+        );
+    }
+
     #[test]
     fn conventional_type_invalid_message() {
         assert_eq!(
@@ -1107,6 +1243,8 @@ mod tests {
             diff_summary: String::new(),
             diff_file: String::new(),
             file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
         analysis.refine_scope(&[]);
         assert_eq!(analysis.detected_scope, "original");
@@ -1126,6 +1264,8 @@ mod tests {
             diff_summary: String::new(),
             diff_file: String::new(),
             file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
         analysis.refine_scope(&scope_defs);
         assert_eq!(analysis.detected_scope, "cli");
@@ -1142,6 +1282,8 @@ mod tests {
             diff_summary: String::new(),
             diff_file: String::new(),
             file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
         analysis.refine_scope(&scope_defs);
         // No match → keeps original
@@ -1165,6 +1307,8 @@ mod tests {
             diff_summary: String::new(),
             diff_file: String::new(),
             file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
         analysis.refine_scope(&scope_defs);
         // Both have specificity 2 and both match → joined
@@ -1267,6 +1411,8 @@ mod tests {
                         diff_summary: String::new(),
                         diff_file: String::new(),
                         file_diffs: Vec::new(),
+                        submodule_changes: Vec::new(),
+                        dependency_updates: Vec::new(),
                     },
                     diff_content: String::new(),
                 },
@@ -1279,7 +1425,7 @@ mod tests {
     fn pre_validation_valid_single_scope() {
         let scopes = vec![make_scope_def("cli", &["src/cli/**"])];
         let mut info = make_commit_info_for_ai("feat(cli): add command");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(
             info.pre_validated_checks
                 .iter()
@@ -1296,7 +1442,7 @@ mod tests {
             make_scope_def("git", &["src/git/**"]),
         ];
         let mut info = make_commit_info_for_ai("feat(cli,git): cross-cutting change");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(info
             .pre_validated_checks
             .iter()
@@ -1314,7 +1460,7 @@ mod tests {
             make_scope_def("lib", &["src/lib/**"]),
         ];
         let mut info = make_commit_info_for_ai("feat(cli, lib): add something");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(
             info.pre_validated_checks
                 .iter()
@@ -1338,7 +1484,7 @@ mod tests {
             make_scope_def("lib", &["src/lib/**"]),
         ];
         let mut info = make_commit_info_for_ai("feat(cli,  lib): add something");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(
             !info
                 .pre_validated_checks
@@ -1356,7 +1502,7 @@ mod tests {
             make_scope_def("lib", &["src/lib/**"]),
         ];
         let mut info = make_commit_info_for_ai("feat(cli ,lib): add something");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(
             !info
                 .pre_validated_checks
@@ -1371,7 +1517,7 @@ mod tests {
     fn pre_validation_invalid_scope_not_added() {
         let scopes = vec![make_scope_def("cli", &["src/cli/**"])];
         let mut info = make_commit_info_for_ai("feat(unknown): something");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(
             !info
                 .pre_validated_checks
@@ -1385,10 +1531,51 @@ mod tests {
     fn pre_validation_no_scope_message() {
         let scopes = vec![make_scope_def("cli", &["src/cli/**"])];
         let mut info = make_commit_info_for_ai("feat: no scope here");
-        info.run_pre_validation_checks(&scopes);
+        info.run_pre_validation_checks(&scopes, &[]);
         assert!(info.pre_validated_checks.is_empty());
     }
 
+    fn make_type_def(name: &str) -> TypeDefinition {
+        TypeDefinition {
+            name: name.to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn pre_validation_records_project_type() {
+        let types = vec![make_type_def("feat"), make_type_def("infra")];
+        let mut info = make_commit_info_for_ai("infra(ci): bump runners");
+        info.run_pre_validation_checks(&[], &types);
+        assert!(info
+            .pre_validated_checks
+            .iter()
+            .any(|c| c.contains("Type validity verified: 'infra'")));
+
+        let mut info = make_commit_info_for_ai("chore: tidy");
+        info.run_pre_validation_checks(&[], &types);
+        assert!(info.pre_validated_checks.is_empty());
+    }
+
+    #[test]
+    fn refine_type_recognises_project_types() {
+        let mut analysis = CommitAnalysis {
+            detected_type: "chore".to_string(),
+            detected_scope: String::new(),
+            proposed_message: String::new(),
+            file_changes: make_file_changes(&[("M", "deploy/main.tf")]),
+            diff_summary: String::new(),
+            diff_file: String::new(),
+            file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
+        };
+        analysis.refine_type("infra(deploy): add bucket", &[]);
+        assert_eq!(analysis.detected_type, "chore");
+        analysis.refine_type("infra(deploy): add bucket", &[make_type_def("infra")]);
+        assert_eq!(analysis.detected_type, "infra");
+    }
+
     // ── property tests ────────────────────────────────────────────
 
     mod prop {
@@ -1461,6 +1648,8 @@ mod tests {
             diff_summary: "file.rs | 2 +-".to_string(),
             diff_file: diff_path.to_string_lossy().to_string(),
             file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
 
         let ai = CommitAnalysisForAI::from_commit_analysis(analysis.clone()).unwrap();
@@ -1489,6 +1678,8 @@ mod tests {
                 diff_summary: "cli.rs | 1 +".to_string(),
                 diff_file: diff_path.to_string_lossy().to_string(),
                 file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         };
 
@@ -1527,6 +1718,8 @@ diff_file: "/tmp/test.diff"
             diff_summary: String::new(),
             diff_file: String::new(),
             file_diffs: Vec::new(),
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
         let yaml = serde_yaml::to_string(&analysis).unwrap();
         assert!(!yaml.contains("file_diffs"));
@@ -1546,6 +1739,8 @@ diff_file: "/tmp/test.diff"
                 diff_file: "/tmp/diffs/abc/0000.diff".to_string(),
                 byte_len: 42,
             }],
+            submodule_changes: Vec::new(),
+            dependency_updates: Vec::new(),
         };
         let yaml = serde_yaml::to_string(&analysis).unwrap();
         assert!(yaml.contains("file_diffs"));
@@ -1590,6 +1785,8 @@ diff_file: "/tmp/test.diff"
                 diff_summary: " src/main.rs | 10 ++++\n src/lib.rs | 5 ++\n".to_string(),
                 diff_file: dir.path().join("full.diff").to_string_lossy().to_string(),
                 file_diffs,
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
             },
         }
     }
@@ -0,0 +1,160 @@
+//! Commit selection for `view --author/--since/--path/--limit`.
+//!
+//! Unlike [`PathFilter::restrict`], which trims analyzed commits to a slice
+//! of the tree, a [`CommitFilter`] decides which commits are analyzed at
+//! all; a selected commit is kept whole, as `git log -- <path>` shows it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use git2::{Commit, Repository};
+
+use crate::git::PathFilter;
+
+/// Criteria a commit must meet to be analyzed; every set criterion applies.
+#[derive(Debug, Clone, Default)]
+pub struct CommitFilter {
+    /// Case-insensitive substring of the author's `Name <email>`.
+    pub author: Option<String>,
+    /// Earliest author date kept.
+    pub since: Option<DateTime<Utc>>,
+    /// Paths the commit must touch.
+    pub paths: Option<PathFilter>,
+    /// At most this many commits are kept, the newest first.
+    pub limit: Option<usize>,
+}
+
+impl CommitFilter {
+    /// Whether no criterion or limit is set, so every commit is kept.
+    pub fn is_empty(&self) -> bool {
+        self.author.is_none()
+            && self.since.is_none()
+            && self.paths.is_none()
+            && self.limit.is_none()
+    }
+
+    /// Returns whether `commit` meets every criterion; [`Self::limit`] is
+    /// applied by the caller walking the history.
+    pub fn matches(&self, repo: &Repository, commit: &Commit<'_>) -> Result<bool> {
+        let author = commit.author();
+        if let Some(pattern) = &self.author {
+            let identity = format!(
+                "{} <{}>",
+                author.name().unwrap_or_default(),
+                author.email().unwrap_or_default()
+            );
+            if !identity.to_lowercase().contains(&pattern.to_lowercase()) {
+                return Ok(false);
+            }
+        }
+        if let Some(since) = self.since {
+            if author.when().seconds() < since.timestamp() {
+                return Ok(false);
+            }
+        }
+        match &self.paths {
+            Some(paths) => touches(repo, commit, paths),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Whether `commit` changes a file in `paths`, compared with its first
+/// parent (or the empty tree for a root commit).
+fn touches(repo: &Repository, commit: &Commit<'_>, paths: &PathFilter) -> Result<bool> {
+    let tree = commit.tree().context("Failed to read commit tree")?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().context("Failed to read parent tree")?),
+        Err(_) => None,
+    };
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+        .context("Failed to diff commit against its parent")?;
+    Ok(diff.deltas().any(|delta| {
+        [delta.old_file().path(), delta.new_file().path()]
+            .into_iter()
+            .flatten()
+            .any(|path| paths.matches(&path.to_string_lossy()))
+    }))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    /// A repo with a root commit by Alice touching `src/a.rs` and a second
+    /// commit by Bob touching `docs/b.md`, a day apart.
+    fn repo_with_history() -> (tempfile::TempDir, Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let commits = [
+            ("Alice", "alice@example.com", "src/a.rs", 1_700_000_000),
+            ("Bob", "bob@example.com", "docs/b.md", 1_700_086_400),
+        ];
+        for (name, email, file, time) in commits {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, name).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(std::path::Path::new(file)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = Signature::new(name, email, &git2::Time::new(time, 0)).unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<&Commit<'_>> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, file, &tree, &parents)
+                .unwrap();
+        }
+        (dir, repo)
+    }
+
+    fn selected(repo: &Repository, filter: &CommitFilter) -> Vec<String> {
+        let mut walk = repo.revwalk().unwrap();
+        walk.push_head().unwrap();
+        walk.map(|oid| repo.find_commit(oid.unwrap()).unwrap())
+            .filter(|commit| filter.matches(repo, commit).unwrap())
+            .map(|commit| commit.summary().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let (_dir, repo) = repo_with_history();
+        let filter = CommitFilter::default();
+        assert!(filter.is_empty());
+        assert_eq!(selected(&repo, &filter), ["docs/b.md", "src/a.rs"]);
+    }
+
+    #[test]
+    fn author_matches_name_or_email_case_insensitively() {
+        let (_dir, repo) = repo_with_history();
+        for pattern in ["alice", "ALICE@example"] {
+            let filter = CommitFilter {
+                author: Some(pattern.to_string()),
+                ..CommitFilter::default()
+            };
+            assert_eq!(selected(&repo, &filter), ["src/a.rs"], "{pattern}");
+        }
+    }
+
+    #[test]
+    fn since_drops_older_commits() {
+        let (_dir, repo) = repo_with_history();
+        let filter = CommitFilter {
+            since: DateTime::from_timestamp(1_700_050_000, 0),
+            ..CommitFilter::default()
+        };
+        assert_eq!(selected(&repo, &filter), ["docs/b.md"]);
+    }
+
+    #[test]
+    fn paths_keep_commits_touching_them() {
+        let (_dir, repo) = repo_with_history();
+        let filter = CommitFilter {
+            paths: PathFilter::new(&["src".to_string()]).unwrap(),
+            ..CommitFilter::default()
+        };
+        assert_eq!(selected(&repo, &filter), ["src/a.rs"]);
+    }
+}
@@ -0,0 +1,218 @@
+//! Conflict markers in a conflicted file, for `git merge assist`.
+//!
+//! [`parse`] splits a file into plain text and the conflicts git marked in
+//! it, in both the default and the `diff3`/`zdiff3` styles; [`resolve`] puts
+//! the file back together with some conflicts replaced by their resolution
+//! and the rest left marked.
+
+use anyhow::{bail, Result};
+
+/// Marker opening a conflict, followed by the "ours" label.
+const OURS_MARKER: &str = "<<<<<<<";
+/// Marker opening the common ancestor section in `diff3` style.
+const BASE_MARKER: &str = "|||||||";
+/// Marker separating the two sides.
+const SEPARATOR_MARKER: &str = "=======";
+/// Marker closing a conflict, followed by the "theirs" label.
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// One conflict: both sides, and the common ancestor when the file was
+/// written in `diff3` style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// 1-based line of the opening marker.
+    pub line: usize,
+    /// Label after `<<<<<<<`, e.g. `HEAD`.
+    pub ours_label: String,
+    /// Lines on our side, each ending in a newline.
+    pub ours: String,
+    /// Lines of the common ancestor, in `diff3` style.
+    pub base: Option<String>,
+    /// Label after `>>>>>>>`, e.g. the merged branch.
+    pub theirs_label: String,
+    /// Lines on their side, each ending in a newline.
+    pub theirs: String,
+    /// The conflict as it appears in the file, markers included.
+    pub raw: String,
+}
+
+/// A piece of a conflicted file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// Text outside any conflict.
+    Text(String),
+    /// A marked conflict.
+    Conflict(Conflict),
+}
+
+/// Which section of an open conflict the parser is in.
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Splits `text` into plain text and conflicts.
+pub fn parse(text: &str) -> Result<Vec<Segment>> {
+    let mut segments = Vec::new();
+    let mut plain = String::new();
+    let mut open: Option<(Conflict, Section)> = None;
+
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim_end_matches(['\n', '\r']);
+        let Some((conflict, section)) = open.as_mut() else {
+            match marker_label(trimmed, OURS_MARKER) {
+                Some(label) => {
+                    if !plain.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut plain)));
+                    }
+                    let conflict = Conflict {
+                        line: index + 1,
+                        ours_label: label.to_string(),
+                        ours: String::new(),
+                        base: None,
+                        theirs_label: String::new(),
+                        theirs: String::new(),
+                        raw: line.to_string(),
+                    };
+                    open = Some((conflict, Section::Ours));
+                }
+                None => plain.push_str(line),
+            }
+            continue;
+        };
+
+        conflict.raw.push_str(line);
+        match section {
+            Section::Ours | Section::Base if trimmed == SEPARATOR_MARKER => {
+                *section = Section::Theirs;
+            }
+            Section::Ours if marker_label(trimmed, BASE_MARKER).is_some() => {
+                conflict.base = Some(String::new());
+                *section = Section::Base;
+            }
+            Section::Ours => conflict.ours.push_str(line),
+            Section::Base => conflict.base.get_or_insert_with(String::new).push_str(line),
+            Section::Theirs => match marker_label(trimmed, THEIRS_MARKER) {
+                Some(label) => conflict.theirs_label = label.to_string(),
+                None => conflict.theirs.push_str(line),
+            },
+        }
+        if matches!(open, Some((_, Section::Theirs)))
+            && marker_label(trimmed, THEIRS_MARKER).is_some()
+        {
+            if let Some((done, _)) = open.take() {
+                segments.push(Segment::Conflict(done));
+            }
+        }
+    }
+
+    if let Some((conflict, _)) = open {
+        bail!(
+            "Conflict starting at line {} has no closing '{THEIRS_MARKER}' marker",
+            conflict.line
+        );
+    }
+    if !plain.is_empty() {
+        segments.push(Segment::Text(plain));
+    }
+    Ok(segments)
+}
+
+/// The conflicts in `segments`, in file order.
+pub fn conflicts(segments: &[Segment]) -> impl Iterator<Item = &Conflict> {
+    segments.iter().filter_map(|segment| match segment {
+        Segment::Conflict(conflict) => Some(conflict),
+        Segment::Text(_) => None,
+    })
+}
+
+/// Puts `segments` back together, replacing the `n`th conflict with
+/// `resolutions[n]` when it is `Some` and leaving it marked otherwise.
+pub fn resolve(segments: &[Segment], resolutions: &[Option<String>]) -> String {
+    let mut out = String::new();
+    let mut n = 0;
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => out.push_str(text),
+            Segment::Conflict(conflict) => {
+                match resolutions.get(n).and_then(Option::as_deref) {
+                    Some(resolution) => {
+                        out.push_str(resolution);
+                        if !resolution.is_empty() && !resolution.ends_with('\n') {
+                            out.push('\n');
+                        }
+                    }
+                    None => out.push_str(&conflict.raw),
+                }
+                n += 1;
+            }
+        }
+    }
+    out
+}
+
+/// The label after `marker` when `line` is that marker, alone or followed by
+/// a space and a label.
+fn marker_label<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "fn main() {\n\
+        <<<<<<< HEAD\n    \
+        run(1);\n\
+        =======\n    \
+        run(2);\n\
+        >>>>>>> feature\n\
+        }\n";
+
+    #[test]
+    fn parses_default_style_conflicts() {
+        let segments = parse(FILE).unwrap();
+        assert_eq!(segments.len(), 3);
+        let conflict = conflicts(&segments).next().unwrap();
+        assert_eq!(conflict.line, 2);
+        assert_eq!(conflict.ours_label, "HEAD");
+        assert_eq!(conflict.ours, "    run(1);\n");
+        assert_eq!(conflict.base, None);
+        assert_eq!(conflict.theirs_label, "feature");
+        assert_eq!(conflict.theirs, "    run(2);\n");
+    }
+
+    #[test]
+    fn parses_diff3_style_conflicts() {
+        let text = "<<<<<<< ours\na\n||||||| base\nb\n=======\nc\n>>>>>>> theirs\n";
+        let segments = parse(text).unwrap();
+        let conflict = conflicts(&segments).next().unwrap();
+        assert_eq!(conflict.ours, "a\n");
+        assert_eq!(conflict.base.as_deref(), Some("b\n"));
+        assert_eq!(conflict.theirs, "c\n");
+        assert_eq!(conflict.raw, text);
+    }
+
+    #[test]
+    fn resolve_replaces_accepted_conflicts_only() {
+        let segments = parse(&format!("{FILE}{FILE}")).unwrap();
+        let resolved = resolve(&segments, &[Some("    run(3);".to_string()), None]);
+        assert_eq!(resolved, format!("fn main() {{\n    run(3);\n}}\n{FILE}"));
+        assert_eq!(resolve(&segments, &[]), format!("{FILE}{FILE}"));
+    }
+
+    #[test]
+    fn rejects_unterminated_conflicts() {
+        let err = parse("<<<<<<< HEAD\na\n=======\nb\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+        assert_eq!(parse("no conflicts\n").unwrap().len(), 1);
+        assert_eq!(marker_label("=======x", SEPARATOR_MARKER), None);
+    }
+}
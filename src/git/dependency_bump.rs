@@ -0,0 +1,364 @@
+//! Dependency-bump commits.
+//!
+//! A commit that only moves lockfile versions — a `cargo update`, or a
+//! Renovate or Dependabot pull request — has a diff of version and checksum
+//! lines that is long, costly to send, and tells the AI little. This module
+//! recognises those commits and reads the package versions each one changes
+//! from the lockfiles before and after it, so the message can list the
+//! updated packages instead of summarising the diff.
+//!
+//! A commit is a dependency bump when every file it changes is a lockfile,
+//! or when its author is Renovate or Dependabot and every file it changes is
+//! a lockfile or a package manifest.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use git2::{Commit, Repository, Tree};
+use serde::{Deserialize, Serialize};
+
+use crate::git::commit::FileChanges;
+
+/// Lockfiles whose package versions can be read.
+pub const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "poetry.lock",
+    "uv.lock",
+    "composer.lock",
+];
+
+/// Package manifests a dependency bot edits alongside the lockfile.
+const MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "composer.json",
+];
+
+/// Author names of dependency bots, matched case-insensitively.
+const BOT_AUTHORS: &[&str] = &["renovate", "dependabot"];
+
+/// Most updates listed in a templated message body.
+const MAX_LISTED_UPDATES: usize = 30;
+
+/// A package version change in a dependency bump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DependencyUpdate {
+    /// Package name as the lockfile spells it.
+    pub name: String,
+    /// Version before the commit; absent when the package was added.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    /// Version after the commit; absent when the package was removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+}
+
+impl DependencyUpdate {
+    /// Describes the update as `bump serde from 1.0.1 to 1.0.2`, `add serde
+    /// 1.0.2`, or `remove serde 1.0.1`.
+    pub fn describe(&self) -> String {
+        match (&self.from, &self.to) {
+            (Some(from), Some(to)) => format!("bump {} from {from} to {to}", self.name),
+            (None, Some(to)) => format!("add {} {to}", self.name),
+            (Some(from), None) => format!("remove {} {from}", self.name),
+            (None, None) => format!("update {}", self.name),
+        }
+    }
+}
+
+/// Returns whether `author` (`Name <email>`) is a dependency bot.
+pub fn is_bot_author(author: &str) -> bool {
+    let author = author.to_lowercase();
+    BOT_AUTHORS.iter().any(|bot| author.contains(bot))
+}
+
+/// Returns whether a commit by `author` changing `files` is a dependency
+/// bump.
+pub fn is_dependency_bump<'a>(author: &str, files: impl IntoIterator<Item = &'a str>) -> bool {
+    let names: Vec<&str> = files.into_iter().map(file_name).collect();
+    if names.is_empty() {
+        return false;
+    }
+    if names.iter().all(|name| LOCKFILES.contains(name)) {
+        return true;
+    }
+    is_bot_author(author)
+        && names.iter().any(|name| LOCKFILES.contains(name))
+        && names
+            .iter()
+            .all(|name| LOCKFILES.contains(name) || MANIFESTS.contains(name))
+}
+
+/// Lists the package versions `commit` changes when it is a dependency
+/// bump; empty for any other commit, or when no lockfile it changes could be
+/// read.
+pub fn dependency_updates(
+    repo: &Repository,
+    commit: &Commit,
+    file_changes: &FileChanges,
+) -> Vec<DependencyUpdate> {
+    let author = format!(
+        "{} <{}>",
+        commit.author().name().unwrap_or(""),
+        commit.author().email().unwrap_or("")
+    );
+    let files = file_changes.file_list.iter().map(|f| f.file.as_str());
+    if !is_dependency_bump(&author, files) {
+        return Vec::new();
+    }
+    let Ok(new_tree) = commit.tree() else {
+        return Vec::new();
+    };
+    let old_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+
+    let mut updates = Vec::new();
+    for change in &file_changes.file_list {
+        if !LOCKFILES.contains(&file_name(&change.file)) {
+            continue;
+        }
+        let old = old_tree
+            .as_ref()
+            .and_then(|tree| blob_text(repo, tree, &change.file))
+            .unwrap_or_default();
+        let new = blob_text(repo, &new_tree, &change.file).unwrap_or_default();
+        for update in diff_versions(
+            &lockfile_versions(&change.file, &old),
+            &lockfile_versions(&change.file, &new),
+        ) {
+            if !updates.contains(&update) {
+                updates.push(update);
+            }
+        }
+    }
+    updates
+}
+
+/// Writes the conventional commit message for a dependency bump without the
+/// AI: the update itself as the subject for a single package, otherwise a
+/// count with the updates listed in the body.
+pub fn template_message(updates: &[DependencyUpdate]) -> String {
+    if let [update] = updates {
+        return format!("chore(deps): {}", update.describe());
+    }
+    let mut message = format!("chore(deps): update {} dependencies\n\n", updates.len());
+    for update in updates.iter().take(MAX_LISTED_UPDATES) {
+        message.push_str(&format!("- {}\n", update.describe()));
+    }
+    if updates.len() > MAX_LISTED_UPDATES {
+        message.push_str(&format!(
+            "- … and {} more\n",
+            updates.len() - MAX_LISTED_UPDATES
+        ));
+    }
+    message.trim_end().to_string()
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn blob_text(repo: &Repository, tree: &Tree, path: &str) -> Option<String> {
+    let entry = tree.get_path(Path::new(path)).ok()?;
+    let blob = entry.to_object(repo).ok()?.peel_to_blob().ok()?;
+    String::from_utf8(blob.content().to_vec()).ok()
+}
+
+/// Package versions pinned by a lockfile, by package name.
+type Versions = BTreeMap<String, BTreeSet<String>>;
+
+fn lockfile_versions(path: &str, content: &str) -> Versions {
+    match file_name(path) {
+        "Cargo.lock" | "poetry.lock" | "uv.lock" => toml_package_versions(content),
+        "package-lock.json" | "npm-shrinkwrap.json" => npm_package_versions(content),
+        "composer.lock" => composer_package_versions(content),
+        _ => Versions::new(),
+    }
+}
+
+/// Reads the `name`/`version` pairs of the `[[package]]` tables that
+/// Cargo, Poetry, and uv lockfiles share.
+fn toml_package_versions(content: &str) -> Versions {
+    let mut versions = Versions::new();
+    let mut name: Option<String> = None;
+    let mut in_package = false;
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[[package]]";
+            name = None;
+            continue;
+        }
+        if !in_package {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match key.trim() {
+            "name" => name = Some(value),
+            "version" => {
+                if let Some(name) = name.take() {
+                    versions.entry(name).or_default().insert(value);
+                }
+            }
+            _ => {}
+        }
+    }
+    versions
+}
+
+/// Reads `package-lock.json` versions: the `packages` map of lockfile v2
+/// and v3, or the `dependencies` map of v1.
+fn npm_package_versions(content: &str) -> Versions {
+    let mut versions = Versions::new();
+    let Ok(lock) = serde_json::from_str::<serde_json::Value>(content) else {
+        return versions;
+    };
+    if let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) {
+        for (path, package) in packages {
+            // The root project is keyed by the empty path.
+            let Some((_, name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if let Some(version) = package.get("version").and_then(|v| v.as_str()) {
+                versions
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(version.to_string());
+            }
+        }
+    } else if let Some(dependencies) = lock.get("dependencies").and_then(|d| d.as_object()) {
+        for (name, package) in dependencies {
+            if let Some(version) = package.get("version").and_then(|v| v.as_str()) {
+                versions
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(version.to_string());
+            }
+        }
+    }
+    versions
+}
+
+/// Reads the `packages` and `packages-dev` arrays of `composer.lock`.
+fn composer_package_versions(content: &str) -> Versions {
+    let mut versions = Versions::new();
+    let Ok(lock) = serde_json::from_str::<serde_json::Value>(content) else {
+        return versions;
+    };
+    for section in ["packages", "packages-dev"] {
+        for package in lock
+            .get(section)
+            .and_then(|p| p.as_array())
+            .into_iter()
+            .flatten()
+        {
+            let name = package.get("name").and_then(|n| n.as_str());
+            let version = package.get("version").and_then(|v| v.as_str());
+            if let (Some(name), Some(version)) = (name, version) {
+                versions
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(version.to_string());
+            }
+        }
+    }
+    versions
+}
+
+/// Pairs the versions each package lost with the versions it gained, newest
+/// first, so a package locked at several versions reads as separate bumps.
+fn diff_versions(old: &Versions, new: &Versions) -> Vec<DependencyUpdate> {
+    let empty = BTreeSet::new();
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut updates = Vec::new();
+    for name in names {
+        let before = old.get(name).unwrap_or(&empty);
+        let after = new.get(name).unwrap_or(&empty);
+        let removed: Vec<&String> = before.difference(after).collect();
+        let added: Vec<&String> = after.difference(before).collect();
+        let (mut removed, mut added) = (removed.into_iter().rev(), added.into_iter().rev());
+        loop {
+            let (from, to) = (removed.next(), added.next());
+            if from.is_none() && to.is_none() {
+                break;
+            }
+            updates.push(DependencyUpdate {
+                name: name.clone(),
+                from: from.cloned(),
+                to: to.cloned(),
+            });
+        }
+    }
+    updates
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OLD_CARGO_LOCK: &str = "version = 4\n\n\
+        [[package]]\nname = \"serde\"\nversion = \"1.0.200\"\n\
+        source = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n\
+        [[package]]\nname = \"syn\"\nversion = \"1.0.109\"\n\n\
+        [[package]]\nname = \"syn\"\nversion = \"2.0.60\"\n\n\
+        [[package]]\nname = \"itoa\"\nversion = \"1.0.11\"\n";
+
+    const NEW_CARGO_LOCK: &str = "version = 4\n\n\
+        [[package]]\nname = \"serde\"\nversion = \"1.0.210\"\n\
+        source = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n\
+        [[package]]\nname = \"syn\"\nversion = \"2.0.77\"\n\n\
+        [[package]]\nname = \"ryu\"\nversion = \"1.0.18\"\n";
+
+    #[test]
+    fn detects_lockfile_only_and_bot_commits() {
+        let human = "Dev <dev@example.com>";
+        let bot = "renovate[bot] <29139614+renovate[bot]@users.noreply.github.com>";
+        assert!(is_dependency_bump(human, ["Cargo.lock"]));
+        assert!(is_dependency_bump(human, ["web/package-lock.json"]));
+        assert!(!is_dependency_bump(human, ["Cargo.toml", "Cargo.lock"]));
+        assert!(is_dependency_bump(bot, ["Cargo.toml", "Cargo.lock"]));
+        assert!(!is_dependency_bump(bot, ["Cargo.toml", "src/lib.rs"]));
+        assert!(!is_dependency_bump(bot, ["Cargo.toml"]));
+        assert!(!is_dependency_bump(human, []));
+        assert!(is_bot_author("dependabot[bot] <support@github.com>"));
+    }
+
+    #[test]
+    fn lists_version_changes_between_lockfiles() {
+        let updates = diff_versions(
+            &lockfile_versions("Cargo.lock", OLD_CARGO_LOCK),
+            &lockfile_versions("Cargo.lock", NEW_CARGO_LOCK),
+        );
+        let described: Vec<String> = updates.iter().map(DependencyUpdate::describe).collect();
+        assert_eq!(
+            described,
+            vec![
+                "remove itoa 1.0.11",
+                "add ryu 1.0.18",
+                "bump serde from 1.0.200 to 1.0.210",
+                "bump syn from 2.0.60 to 2.0.77",
+                "remove syn 1.0.109",
+            ]
+        );
+
+        let npm = |version: &str| {
+            format!(
+                r#"{{"lockfileVersion": 3, "packages": {{"": {{"name": "app"}}, "node_modules/@types/node": {{"version": "{version}"}}}}}}"#
+            )
+        };
+        let updates = diff_versions(
+            &lockfile_versions("package-lock.json", &npm("20.1.0")),
+            &lockfile_versions("package-lock.json", &npm("20.2.0")),
+        );
+        assert_eq!(
+            template_message(&updates),
+            "chore(deps): bump @types/node from 20.1.0 to 20.2.0"
+        );
+    }
+}
@@ -0,0 +1,361 @@
+//! Byte limits on the diff content sent to the AI.
+//!
+//! Without limits a commit's whole diff is included, however large. With
+//! `OMNI_DEV_MAX_FILE_DIFF_BYTES` and/or `OMNI_DEV_MAX_DIFF_BYTES` set
+//! (environment or `settings.json`, sizes such as `64kb`), an oversized diff
+//! is cut down rather than sent whole: file headers and every hunk header
+//! survive, added lines are kept in preference to removed ones and removed
+//! ones in preference to context, and each run of dropped lines is replaced
+//! by a `[N lines omitted]` marker.
+//!
+//! `OMNI_DEV_SPILL_DIFF_BYTES` works differently: a commit diff above it is
+//! kept whole in its diff file, and only the first and last part of it are
+//! loaded and sent, around a marker naming the file and after the
+//! `diff_summary` stat, which still covers every file. The diff is still
+//! generated in full once, when its file is written.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use tracing::warn;
+
+use crate::git::diff_split::split_by_file;
+
+/// Setting capping the diff bytes of a whole commit (or staged change).
+pub const MAX_DIFF_BYTES_ENV: &str = "OMNI_DEV_MAX_DIFF_BYTES";
+
+/// Setting capping the diff bytes of any single file.
+pub const MAX_FILE_DIFF_BYTES_ENV: &str = "OMNI_DEV_MAX_FILE_DIFF_BYTES";
+
+/// Setting above which a commit's diff is excerpted rather than loaded whole.
+pub const SPILL_DIFF_BYTES_ENV: &str = "OMNI_DEV_SPILL_DIFF_BYTES";
+
+/// Diff size limits, in bytes. `None` leaves that dimension unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffLimits {
+    /// Maximum bytes for the whole diff.
+    pub per_commit: Option<usize>,
+    /// Maximum bytes for one file's diff.
+    pub per_file: Option<usize>,
+}
+
+impl DiffLimits {
+    /// Reads the limits from settings. An unset, empty, zero, or invalid
+    /// value (the last is warned about) leaves that limit off.
+    pub fn from_settings() -> Self {
+        Self {
+            per_commit: read_limit(MAX_DIFF_BYTES_ENV),
+            per_file: read_limit(MAX_FILE_DIFF_BYTES_ENV),
+        }
+    }
+
+    /// Applies the limits to a flat unified diff.
+    ///
+    /// Each file is first cut to `per_file`. If the diff still exceeds
+    /// `per_commit`, the budget is shared out so small files stay whole and
+    /// the largest ones are cut evenly.
+    pub fn apply(&self, diff: &str) -> String {
+        if self.per_commit.is_none() && self.per_file.is_none() {
+            return diff.to_string();
+        }
+        let files = split_by_file(diff);
+        if files.is_empty() {
+            return match self.per_commit.or(self.per_file) {
+                Some(max) if diff.len() > max => truncate_file_diff(diff, max),
+                _ => diff.to_string(),
+            };
+        }
+        let preamble_len = diff.len() - files.iter().map(|f| f.byte_len).sum::<usize>();
+
+        let mut contents: Vec<String> = files
+            .iter()
+            .map(|file| match self.per_file {
+                Some(max) if file.byte_len > max => truncate_file_diff(&file.content, max),
+                _ => file.content.clone(),
+            })
+            .collect();
+
+        if let Some(max) = self.per_commit {
+            let budget = max.saturating_sub(preamble_len);
+            let sizes: Vec<usize> = contents.iter().map(String::len).collect();
+            if sizes.iter().sum::<usize>() > budget {
+                for (content, share) in contents.iter_mut().zip(fair_shares(&sizes, budget)) {
+                    if content.len() > share {
+                        *content = truncate_file_diff(content, share);
+                    }
+                }
+            }
+        }
+
+        let mut limited = diff[..preamble_len].to_string();
+        for content in &contents {
+            limited.push_str(content);
+        }
+        limited
+    }
+}
+
+/// Reads one size limit setting.
+fn read_limit(key: &str) -> Option<usize> {
+    let raw = crate::utils::settings::get_env_var(key).ok()?;
+    if raw.trim().is_empty() {
+        return None;
+    }
+    match crate::request_log::parse_size(&raw) {
+        Ok(0) => None,
+        Ok(bytes) => Some(usize::try_from(bytes).unwrap_or(usize::MAX)),
+        Err(e) => {
+            warn!("Ignoring invalid {key}: {e:#}");
+            None
+        }
+    }
+}
+
+/// Reads the spill threshold from settings; unset, zero, or invalid
+/// leaves every diff loaded whole.
+pub fn spill_threshold() -> Option<usize> {
+    read_limit(SPILL_DIFF_BYTES_ENV)
+}
+
+/// Reads the diff file at `path`: whole when it is at most `threshold`
+/// bytes, else `stat` followed by the file's first and last
+/// `threshold / 2` bytes, cut at line boundaries, around a marker naming
+/// the file. The middle is never read.
+pub fn read_diff_excerpt(
+    path: &Path,
+    threshold: Option<usize>,
+    stat: &str,
+) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let Some(threshold) = threshold.filter(|&max| len > max as u64) else {
+        let mut whole = String::new();
+        file.read_to_string(&mut whole)?;
+        return Ok(whole);
+    };
+    let half = threshold / 2;
+
+    let mut head = Vec::with_capacity(half);
+    (&mut file).take(half as u64).read_to_end(&mut head)?;
+    // A newline byte never falls inside a UTF-8 sequence, so cutting just
+    // after one keeps both parts valid.
+    head.truncate(head.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1));
+
+    let mut tail = Vec::with_capacity(half);
+    file.seek(SeekFrom::Start(len.saturating_sub(half as u64)))?;
+    file.read_to_end(&mut tail)?;
+    let tail_start = tail
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(tail.len(), |i| i + 1);
+    tail.drain(..tail_start);
+
+    let omitted = len - (head.len() + tail.len()) as u64;
+    let mut excerpt = String::new();
+    if !stat.trim().is_empty() {
+        excerpt.push_str(stat.trim_end());
+        excerpt.push('\n');
+    }
+    excerpt.push_str(&String::from_utf8_lossy(&head));
+    excerpt.push_str(&format!(
+        "[{omitted} bytes omitted; the full diff is in {}]\n",
+        path.display()
+    ));
+    excerpt.push_str(&String::from_utf8_lossy(&tail));
+    Ok(excerpt)
+}
+
+/// Splits `budget` across items of the given sizes: items smaller than an
+/// even share keep their full size and hand the remainder to the others.
+fn fair_shares(sizes: &[usize], budget: usize) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|&i| sizes[i]);
+    let mut shares = vec![0; sizes.len()];
+    let mut remaining = budget;
+    for (position, &index) in order.iter().enumerate() {
+        let share = (remaining / (sizes.len() - position)).min(sizes[index]);
+        shares[index] = share;
+        remaining -= share;
+    }
+    shares
+}
+
+/// Priority of a diff line when space runs out; lower is kept first.
+fn line_priority(line: &str, in_hunk: bool) -> u8 {
+    if !in_hunk || line.starts_with("@@") {
+        0
+    } else if line.starts_with('+') {
+        1
+    } else if line.starts_with('-') {
+        2
+    } else {
+        3
+    }
+}
+
+/// Cuts one file's diff to roughly `max` bytes.
+///
+/// Lines are admitted by priority — headers and hunk headers, then added,
+/// removed, and context lines — each tier in file order, then written back
+/// in their original order with a marker for every run of dropped lines.
+/// Headers are always kept, and markers are not counted, so the result may
+/// exceed `max` slightly.
+pub fn truncate_file_diff(file_diff: &str, max: usize) -> String {
+    if file_diff.len() <= max {
+        return file_diff.to_string();
+    }
+    let lines: Vec<&str> = file_diff.split_inclusive('\n').collect();
+    let mut in_hunk = false;
+    let priorities: Vec<u8> = lines
+        .iter()
+        .map(|line| {
+            in_hunk |= line.starts_with("@@");
+            line_priority(line, in_hunk)
+        })
+        .collect();
+
+    let mut keep = vec![false; lines.len()];
+    let mut used = 0usize;
+    for tier in 0..=3 {
+        for (index, line) in lines.iter().enumerate() {
+            if priorities[index] != tier {
+                continue;
+            }
+            if tier == 0 || used + line.len() <= max {
+                keep[index] = true;
+                used += line.len();
+            }
+        }
+    }
+
+    let mut truncated = String::with_capacity(used + 64);
+    let mut omitted = 0usize;
+    for (line, kept) in lines.iter().zip(&keep) {
+        if *kept {
+            push_marker(&mut truncated, &mut omitted);
+            truncated.push_str(line);
+            if !line.ends_with('\n') {
+                truncated.push('\n');
+            }
+        } else {
+            omitted += 1;
+        }
+    }
+    push_marker(&mut truncated, &mut omitted);
+    truncated
+}
+
+/// Writes the marker for a pending run of omitted lines, if any.
+fn push_marker(out: &mut String, omitted: &mut usize) {
+    match *omitted {
+        0 => {}
+        1 => out.push_str("[1 line omitted]\n"),
+        n => out.push_str(&format!("[{n} lines omitted]\n")),
+    }
+    *omitted = 0;
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn file_diff(path: &str, body: &str) -> String {
+        format!("diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -1,3 +1,3 @@\n{body}")
+    }
+
+    #[test]
+    fn truncation_prefers_added_lines_and_marks_gaps() {
+        let diff = file_diff(
+            "src/lib.rs",
+            " context one\n-removed line\n+added line\n context two\n",
+        );
+        let budget = diff.len() - " context one\n context two\n".len();
+        let truncated = truncate_file_diff(&diff, budget);
+
+        assert!(truncated.contains("@@ -1,3 +1,3 @@\n"));
+        assert!(truncated.contains("+added line\n"));
+        assert!(truncated.contains("-removed line\n"));
+        assert!(!truncated.contains("context"));
+        assert!(truncated.contains("@@ -1,3 +1,3 @@\n[1 line omitted]\n-removed line"));
+        assert!(truncated.ends_with("+added line\n[1 line omitted]\n"));
+
+        let header_only = truncate_file_diff(&diff, 0);
+        assert!(header_only.contains("+++ b/src/lib.rs\n@@ -1,3 +1,3 @@\n[4 lines omitted]\n"));
+    }
+
+    #[test]
+    fn no_limits_leaves_diff_untouched() {
+        let diff = file_diff("a.rs", "+x\n");
+        assert_eq!(DiffLimits::default().apply(&diff), diff);
+    }
+
+    #[test]
+    fn per_file_limit_only_cuts_large_files() {
+        let small = file_diff("small.rs", "+tiny\n");
+        let large = file_diff("large.rs", &"+generated line of code\n".repeat(50));
+        let limits = DiffLimits {
+            per_commit: None,
+            per_file: Some(200),
+        };
+        let limited = limits.apply(&format!("{small}{large}"));
+
+        assert!(limited.starts_with(&small));
+        assert!(limited.contains("lines omitted]"));
+        assert!(limited.len() < small.len() + 300);
+    }
+
+    #[test]
+    fn commit_limit_keeps_small_files_whole() {
+        let small = file_diff("small.rs", "+tiny\n");
+        let large_a = file_diff("a.rs", &"+aaaaaaaaaaaaaaaa\n".repeat(40));
+        let large_b = file_diff("b.rs", &"+bbbbbbbbbbbbbbbb\n".repeat(40));
+        let limits = DiffLimits {
+            per_commit: Some(600),
+            per_file: None,
+        };
+        let limited = limits.apply(&format!("{small}{large_a}{large_b}"));
+
+        assert!(limited.starts_with(&small));
+        assert!(limited.contains("+++ b/a.rs\n"));
+        assert!(limited.contains("+++ b/b.rs\n"));
+        assert!(limited.len() <= 600 + 100);
+    }
+
+    #[test]
+    fn oversized_diff_files_are_excerpted_at_line_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("commit.diff");
+        let diff: String = (0..100).map(|i| format!("+line {i:03}\n")).collect();
+        std::fs::write(&path, &diff).unwrap();
+
+        let stat = " src/lines.rs | 100 ++++\n 1 file changed, 100 insertions(+)\n";
+        assert_eq!(read_diff_excerpt(&path, None, stat).unwrap(), diff);
+        assert_eq!(
+            read_diff_excerpt(&path, Some(diff.len()), stat).unwrap(),
+            diff
+        );
+
+        // Each line is 10 bytes: 25 bytes at either end keeps two whole lines.
+        let excerpt = read_diff_excerpt(&path, Some(50), "").unwrap();
+        assert_eq!(
+            excerpt,
+            format!(
+                "+line 000\n+line 001\n[960 bytes omitted; the full diff is in {}]\n+line 098\n+line 099\n",
+                path.display()
+            )
+        );
+
+        // The stat leads the excerpt, so every file is still named.
+        let excerpt = read_diff_excerpt(&path, Some(50), stat).unwrap();
+        assert!(excerpt.starts_with(&format!("{stat}+line 000\n")));
+        assert!(excerpt.ends_with("+line 099\n"));
+    }
+
+    #[test]
+    fn fair_shares_give_leftovers_to_large_items() {
+        assert_eq!(fair_shares(&[10, 100, 100], 110), vec![10, 50, 50]);
+        assert_eq!(fair_shares(&[10, 20], 100), vec![10, 20]);
+    }
+}
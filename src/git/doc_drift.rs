@@ -0,0 +1,286 @@
+//! Documentation drift heuristics for `git commit review docs`.
+//!
+//! A diff drifts from the documentation when it changes what users see — a
+//! public API item or a command-line flag — without touching any
+//! documentation: `README`s, `docs/`, other markdown, man pages, or help
+//! snapshots. [`DocDrift`] collects both sides from a diff by path and line
+//! patterns; the AI pass in the command decides which surface changes need a
+//! documentation update.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use regex::Regex;
+use serde::Serialize;
+
+use super::test_gaps::{is_test_path, tracked_files};
+
+/// What kind of user-facing surface a line changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SurfaceKind {
+    /// A public item: `pub fn`, an exported Go or JavaScript symbol, a
+    /// top-level Python definition.
+    PublicApi,
+    /// A command-line flag or subcommand definition.
+    CliFlag,
+}
+
+/// A user-facing line the diff adds or removes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SurfaceChange {
+    /// Repository-relative path of the file.
+    pub file: String,
+    /// Line in the new version of the file, for added lines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// What the line defines.
+    pub kind: SurfaceKind,
+    /// The line, with its `+` or `-` marker, trimmed.
+    pub text: String,
+}
+
+/// The user-facing changes in a diff and the documentation it touches.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DocDrift {
+    /// Public API and CLI lines added or removed outside tests and docs.
+    pub surface: Vec<SurfaceChange>,
+    /// Documentation files the diff changes.
+    pub docs_changed: Vec<String>,
+}
+
+impl DocDrift {
+    /// Collects the surface changes and documentation changes of `diff`.
+    pub fn from_diff(diff: &str) -> Self {
+        let mut drift = Self::default();
+        let mut file = String::new();
+        let mut skip = false;
+        let mut line = 0u32;
+        for text in diff.lines() {
+            if let Some(path) = text.strip_prefix("+++ ") {
+                file = path.strip_prefix("b/").unwrap_or(path).to_string();
+                skip = is_doc_path(&file) || is_test_path(&file);
+                if is_doc_path(&file) && !drift.docs_changed.contains(&file) {
+                    drift.docs_changed.push(file.clone());
+                }
+            } else if let Some(path) = text.strip_prefix("--- ") {
+                // A deleted doc counts too: its `+++` side is /dev/null.
+                if let Some(old) = path.strip_prefix("a/") {
+                    if is_doc_path(old) && !drift.docs_changed.iter().any(|d| d == old) {
+                        drift.docs_changed.push(old.to_string());
+                    }
+                }
+            } else if text.starts_with("@@") {
+                line = hunk_new_start(text).unwrap_or(1);
+            } else if let Some(added) = text.strip_prefix('+') {
+                if !skip {
+                    drift.note(&file, Some(line), '+', added);
+                }
+                line += 1;
+            } else if let Some(removed) = text.strip_prefix('-') {
+                if !skip {
+                    drift.note(&file, None, '-', removed);
+                }
+            } else if !text.starts_with('\\') {
+                line += 1;
+            }
+        }
+        drift
+    }
+
+    /// Whether the diff changes user-facing surface without touching any
+    /// documentation.
+    pub fn is_suspect(&self) -> bool {
+        !self.surface.is_empty() && self.docs_changed.is_empty()
+    }
+
+    fn note(&mut self, file: &str, line: Option<u32>, marker: char, text: &str) {
+        if let Some(kind) = surface_kind(text) {
+            self.surface.push(SurfaceChange {
+                file: file.to_string(),
+                line,
+                kind,
+                text: format!("{marker}{}", text.trim()),
+            });
+        }
+    }
+}
+
+/// The documentation files tracked in the repository at `repo_root`, so the
+/// AI pass can name the file a drifting change should update.
+pub fn tracked_doc_files(repo_root: &Path) -> Result<Vec<String>> {
+    Ok(tracked_files(repo_root)?
+        .into_iter()
+        .filter(|path| is_doc_path(path))
+        .collect())
+}
+
+/// Whether `path` is documentation: a `README`, anything under a `docs/` or
+/// `doc/` directory, markdown, reStructuredText, AsciiDoc, a man page, or a
+/// help output snapshot. Changelogs record changes rather than document
+/// them, so they do not count.
+pub fn is_doc_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    let name = lower.rsplit('/').next().unwrap_or(&lower);
+    if name.starts_with("changelog") {
+        return false;
+    }
+    let in_docs = lower
+        .split('/')
+        .rev()
+        .skip(1)
+        .any(|dir| dir == "docs" || dir == "doc");
+    in_docs
+        || name.starts_with("readme")
+        || [".md", ".rst", ".adoc"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        || name
+            .rsplit_once('.')
+            .is_some_and(|(_, ext)| ext.len() == 1 && ext.chars().all(|c| c.is_ascii_digit()))
+        || (lower.contains("snapshots/") && name.contains("help"))
+}
+
+/// Line patterns, most specific first, with the surface they define.
+fn surface_patterns() -> &'static [(Regex, SurfaceKind)] {
+    static RE: OnceLock<Vec<(Regex, SurfaceKind)>> = OnceLock::new();
+    #[allow(clippy::expect_used)]
+    RE.get_or_init(|| {
+        [
+            // clap, argparse, commander/yargs, Go flag and cobra.
+            (r"#\[(?:arg|command|clap)\(", SurfaceKind::CliFlag),
+            (r#"add_argument\(\s*["']-"#, SurfaceKind::CliFlag),
+            (r#"\.option\(\s*["']-"#, SurfaceKind::CliFlag),
+            (r"(?:^|[^.\w])flag\.[A-Z]\w*\(|\bFlags\(\)\.\w+\(", SurfaceKind::CliFlag),
+            (
+                r"^\s*pub\s+(?:(?:async|const|unsafe|extern)\s+)*(?:fn|struct|enum|trait|type|const|static|mod|use)\b",
+                SurfaceKind::PublicApi,
+            ),
+            (
+                r"^(?:func\s+(?:\([^)]*\)\s*)?|type\s+)[A-Z]",
+                SurfaceKind::PublicApi,
+            ),
+            (r"^\s*export\s+", SurfaceKind::PublicApi),
+            (r"^(?:async\s+)?(?:def|class)\s+[A-Za-z]", SurfaceKind::PublicApi),
+        ]
+        .into_iter()
+        .map(|(pattern, kind)| {
+            (
+                Regex::new(pattern).expect("surface pattern must compile"),
+                kind,
+            )
+        })
+        .collect()
+    })
+}
+
+/// The surface `line` changes, if any.
+fn surface_kind(line: &str) -> Option<SurfaceKind> {
+    surface_patterns()
+        .iter()
+        .find(|(re, _)| re.is_match(line))
+        .map(|(_, kind)| *kind)
+}
+
+/// The new-file start line of a hunk header `@@ -a,b +c,d @@`.
+pub(crate) fn hunk_new_start(header: &str) -> Option<u32> {
+    let new = header
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    new.split(',').next()?.parse().ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "--- a/src/cli.rs\n\
+        +++ b/src/cli.rs\n\
+        @@ -4,3 +4,5 @@ pub struct Cli {\n \
+        verbose: bool,\n\
+        +    #[arg(long)]\n\
+        +    pub dry_run: bool,\n\
+        -pub fn run() {\n\
+        +pub async fn run(opts: Options) {\n\
+        +    let x = 1;\n\
+        --- a/tests/cli.rs\n\
+        +++ b/tests/cli.rs\n\
+        @@ -1 +1 @@\n\
+        +pub fn helper() {}\n";
+
+    #[test]
+    fn finds_surface_changes_outside_tests() {
+        let drift = DocDrift::from_diff(DIFF);
+        let summary: Vec<_> = drift
+            .surface
+            .iter()
+            .map(|s| (s.line, s.kind, s.text.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (Some(5), SurfaceKind::CliFlag, "+#[arg(long)]"),
+                (None, SurfaceKind::PublicApi, "-pub fn run() {"),
+                (
+                    Some(7),
+                    SurfaceKind::PublicApi,
+                    "+pub async fn run(opts: Options) {"
+                ),
+            ]
+        );
+        assert!(drift.is_suspect());
+    }
+
+    #[test]
+    fn doc_changes_clear_the_suspicion() {
+        let diff =
+            format!("{DIFF}--- a/docs/cli.md\n+++ b/docs/cli.md\n@@ -1 +1 @@\n+`--dry-run`\n");
+        let drift = DocDrift::from_diff(&diff);
+        assert_eq!(drift.docs_changed, ["docs/cli.md"]);
+        assert!(!drift.is_suspect());
+    }
+
+    #[test]
+    fn recognises_doc_paths() {
+        for path in [
+            "README.md",
+            "crates/core/README",
+            "docs/configuration.md",
+            "doc/api/index.html",
+            "man/omni-dev.1",
+            "guide.rst",
+            "tests/snapshots/integration_test__help_all_output.snap",
+        ] {
+            assert!(is_doc_path(path), "{path}");
+        }
+        for path in [
+            "CHANGELOG.md",
+            "src/docs.rs",
+            "src/lib.rs",
+            "tests/snapshots/view.snap",
+        ] {
+            assert!(!is_doc_path(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn surface_kinds_cover_common_languages() {
+        assert_eq!(
+            surface_kind(r#"parser.add_argument("--dry-run")"#),
+            Some(SurfaceKind::CliFlag)
+        );
+        assert_eq!(
+            surface_kind("func (s *Server) Serve() error {"),
+            Some(SurfaceKind::PublicApi)
+        );
+        assert_eq!(surface_kind("func serve() {"), None);
+        assert_eq!(
+            surface_kind("export function load() {}"),
+            Some(SurfaceKind::PublicApi)
+        );
+        assert_eq!(surface_kind("pub(crate) fn inner() {}"), None);
+        assert_eq!(surface_kind("def _private():"), None);
+    }
+}
@@ -0,0 +1,298 @@
+//! Recognition of lockfiles, generated code, and vendored directories.
+//!
+//! Their diffs are large, mechanical, and say little about intent, so diff
+//! collection replaces each one with a one-line stub before it reaches a
+//! prompt. The file still appears in the commit's file list and diff
+//! summary, so the model knows it changed.
+//!
+//! Built-in patterns cover the common lockfiles, protobuf/gRPC and other
+//! generator output, minified bundles, and vendored dependency directories.
+//! A project extends or overrides them in `.omni-dev/ignore.yaml` (resolved
+//! like the other context files):
+//!
+//! ```yaml
+//! exclude:            # additional generated or vendored paths
+//!   - "api/openapi/client/**"
+//! include:            # paths to keep even though a pattern matches them
+//!   - "vendor/our-fork/**"
+//! builtin: true       # set to false to drop the built-in patterns
+//! ```
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::git::diff_split::split_by_file;
+
+/// Name of the project configuration file in the context directory.
+pub const IGNORE_FILE: &str = "ignore.yaml";
+
+/// Paths treated as generated or vendored unless a project opts out.
+pub const BUILTIN_PATTERNS: &[&str] = &[
+    // Lockfiles
+    "**/Cargo.lock",
+    "**/package-lock.json",
+    "**/npm-shrinkwrap.json",
+    "**/yarn.lock",
+    "**/pnpm-lock.yaml",
+    "**/bun.lockb",
+    "**/poetry.lock",
+    "**/Pipfile.lock",
+    "**/uv.lock",
+    "**/go.sum",
+    "**/go.work.sum",
+    "**/composer.lock",
+    "**/Gemfile.lock",
+    "**/Podfile.lock",
+    "**/flake.lock",
+    "**/gradle.lockfile",
+    // Protobuf, gRPC, and other generator output
+    "**/*.pb.go",
+    "**/*.pb.gw.go",
+    "**/*_grpc.pb.go",
+    "**/*_pb2.py",
+    "**/*_pb2.pyi",
+    "**/*_pb2_grpc.py",
+    "**/*.pb.h",
+    "**/*.pb.cc",
+    "**/*_pb.js",
+    "**/*_pb.d.ts",
+    "**/*.generated.*",
+    "**/*.g.dart",
+    "**/*.freezed.dart",
+    "**/zz_generated*.go",
+    "**/generated/**",
+    "**/__generated__/**",
+    "**/openapi-generated/**",
+    // Minified bundles
+    "**/*.min.js",
+    "**/*.min.css",
+    "**/*.js.map",
+    // Vendored dependencies
+    "**/vendor/**",
+    "**/third_party/**",
+    "**/node_modules/**",
+];
+
+/// The built-in patterns alone, for callers without a repository at hand.
+static BUILTIN: LazyLock<GeneratedFiles> = LazyLock::new(GeneratedFiles::builtin);
+
+/// Contents of `ignore.yaml`.
+#[derive(Debug, Default, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct IgnoreConfig {
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default = "default_builtin")]
+    builtin: bool,
+}
+
+fn default_builtin() -> bool {
+    true
+}
+
+/// JSON Schema of `ignore.yaml`, for `omni-dev config validate`.
+pub fn ignore_schema() -> serde_json::Value {
+    serde_json::to_value(schemars::schema_for!(IgnoreConfig)).unwrap_or(serde_json::Value::Null)
+}
+
+/// A set of generated or vendored path patterns.
+#[derive(Debug, Clone)]
+pub struct GeneratedFiles {
+    exclude: GlobSet,
+    include: GlobSet,
+}
+
+impl GeneratedFiles {
+    /// Builds the matcher from [`BUILTIN_PATTERNS`] only.
+    pub fn builtin() -> Self {
+        let patterns: Vec<String> = BUILTIN_PATTERNS.iter().map(|p| (*p).to_string()).collect();
+        Self {
+            exclude: build_globset(&patterns).unwrap_or_else(|_| GlobSet::empty()),
+            include: GlobSet::empty(),
+        }
+    }
+
+    /// Returns whether `path` matches a built-in pattern.
+    pub fn is_builtin_match(path: &str) -> bool {
+        BUILTIN.is_generated(path)
+    }
+
+    /// Builds the matcher from an `ignore.yaml` document.
+    pub fn from_config(content: &str) -> Result<Self> {
+        let config: IgnoreConfig = if content.trim().is_empty() {
+            IgnoreConfig {
+                builtin: true,
+                ..IgnoreConfig::default()
+            }
+        } else {
+            serde_yaml::from_str(content).context("Failed to parse ignore.yaml")?
+        };
+        let mut exclude = config.exclude;
+        if config.builtin {
+            exclude.extend(BUILTIN_PATTERNS.iter().map(|p| (*p).to_string()));
+        }
+        Ok(Self {
+            exclude: build_globset(&exclude)?,
+            include: build_globset(&config.include)?,
+        })
+    }
+
+    /// Builds the matcher for the repository at `repo_root`, reading
+    /// `ignore.yaml` from its context directory. A malformed file is
+    /// reported and the built-in patterns are used instead.
+    pub fn for_repo(repo_root: &Path) -> Self {
+        let context_dir = crate::claude::context::resolve_context_dir_at(None, repo_root);
+        let path =
+            crate::claude::context::discovery::resolve_config_file(&context_dir, IGNORE_FILE);
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return BUILTIN.clone();
+        };
+        Self::from_config(&content).unwrap_or_else(|e| {
+            warn!("Ignoring {}: {e:#}", path.display());
+            BUILTIN.clone()
+        })
+    }
+
+    /// Returns whether a repository-relative path is generated or vendored.
+    pub fn is_generated(&self, path: &str) -> bool {
+        self.exclude.is_match(path) && !self.include.is_match(path)
+    }
+
+    /// Replaces the per-file sections of a unified diff whose path is
+    /// generated with a header-only stub noting the omission.
+    pub fn strip_diff(&self, diff: &str) -> String {
+        let files = split_by_file(diff);
+        if !files.iter().any(|file| self.is_generated(&file.path)) {
+            return diff.to_string();
+        }
+        // Anything before the first file section (normally nothing) is kept.
+        let preamble_len = diff.len() - files.iter().map(|f| f.byte_len).sum::<usize>();
+        let mut stripped = diff[..preamble_len].to_string();
+        for file in &files {
+            if self.is_generated(&file.path) {
+                stripped.push_str(&stub(&file.content));
+            } else {
+                stripped.push_str(&file.content);
+            }
+        }
+        stripped
+    }
+}
+
+/// Keeps the header lines of one file's diff and replaces its hunks with a
+/// note recording how much was omitted.
+fn stub(file_diff: &str) -> String {
+    let mut header = String::new();
+    let mut changed = 0usize;
+    let mut in_hunks = false;
+    for line in file_diff.lines() {
+        if line.starts_with("@@") {
+            in_hunks = true;
+        } else if in_hunks {
+            if line.starts_with('+') || line.starts_with('-') {
+                changed += 1;
+            }
+        } else {
+            header.push_str(line);
+            header.push('\n');
+        }
+    }
+    header.push_str(&format!(
+        "[diff omitted: generated or vendored file, {changed} changed lines]\n"
+    ));
+    header
+}
+
+fn build_globset(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(
+            GlobBuilder::new(pattern.trim().trim_start_matches("./"))
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("Invalid ignore pattern '{pattern}'"))?,
+        );
+    }
+    builder.build().context("Failed to compile ignore patterns")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1 +1 @@
+-fn old() {}
++fn new() {}
+diff --git a/Cargo.lock b/Cargo.lock
+index 3333333..4444444 100644
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -1,2 +1,2 @@
+-version = \"1.0.0\"
++version = \"1.1.0\"
+ name = \"serde\"
+";
+
+    #[test]
+    fn recognizes_builtin_patterns() {
+        for path in [
+            "Cargo.lock",
+            "web/package-lock.json",
+            "api/v1/service.pb.go",
+            "proto/user_pb2.py",
+            "vendor/github.com/pkg/errors/errors.go",
+            "ui/src/__generated__/schema.ts",
+            "static/app.min.js",
+        ] {
+            assert!(GeneratedFiles::is_builtin_match(path), "{path}");
+        }
+        for path in [
+            "src/lib.rs",
+            "Cargo.toml",
+            "src/vendors.rs",
+            "proto/user.proto",
+        ] {
+            assert!(!GeneratedFiles::is_builtin_match(path), "{path}");
+        }
+    }
+
+    #[test]
+    fn project_config_extends_and_overrides_builtins() {
+        let files = GeneratedFiles::from_config(
+            "exclude: [\"api/client/**\"]\ninclude: [\"vendor/fork/**\"]\n",
+        )
+        .unwrap();
+        assert!(files.is_generated("api/client/models.ts"));
+        assert!(files.is_generated("Cargo.lock"));
+        assert!(!files.is_generated("vendor/fork/lib.go"));
+
+        let files = GeneratedFiles::from_config("builtin: false\n").unwrap();
+        assert!(!files.is_generated("Cargo.lock"));
+
+        assert!(GeneratedFiles::from_config("excludes: []\n").is_err());
+    }
+
+    #[test]
+    fn strips_generated_file_diffs() {
+        let stripped = GeneratedFiles::builtin().strip_diff(DIFF);
+        assert!(stripped.contains("+fn new() {}"));
+        assert!(stripped.contains("+++ b/Cargo.lock\n"));
+        assert!(stripped.contains("[diff omitted: generated or vendored file, 2 changed lines]"));
+        assert!(!stripped.contains("version = \"1.1.0\""));
+
+        let untouched = "diff --git a/src/lib.rs b/src/lib.rs\n+x\n";
+        assert_eq!(GeneratedFiles::builtin().strip_diff(untouched), untouched);
+    }
+}
@@ -0,0 +1,385 @@
+//! Commit history search for `git log search`.
+//!
+//! A [`HistorySearch`] holds one or more terms. A literal or `--regex` query
+//! is a single term; a natural-language question (`--ask`) becomes one term
+//! per keyword, and commits are ranked by how many keywords they match so the
+//! best candidates can be handed to the AI. Messages are always searched;
+//! with [`HistorySearch::search_diffs`] the added and removed lines of each
+//! commit's diff are searched too, as `git log -G` does.
+
+use anyhow::{bail, Context, Result};
+use git2::{Commit, DiffFormat, Repository, Sort};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use crate::utils::redact::redact_secrets;
+
+/// Matching lines kept per commit.
+const MAX_SNIPPETS: usize = 3;
+
+/// Characters kept of each matching line.
+const MAX_SNIPPET_CHARS: usize = 160;
+
+/// Words dropped from a question before its keywords become terms.
+const STOPWORDS: &[&str] = &[
+    "about", "and", "are", "can", "did", "does", "for", "from", "had", "has", "have", "how",
+    "into", "its", "that", "the", "their", "them", "then", "there", "this", "was", "were", "what",
+    "when", "where", "which", "who", "why", "with", "you", "our",
+];
+
+/// Where a commit matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchSource {
+    /// The commit message.
+    Message,
+    /// An added or removed line of the commit's diff.
+    Diff,
+}
+
+/// A commit matching a search.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    /// Full commit hash.
+    pub hash: String,
+    /// Author as `Name <email>`.
+    pub author: String,
+    /// Author date, RFC 3339.
+    pub date: String,
+    /// First line of the message.
+    pub subject: String,
+    /// Where the commit matched.
+    pub matched_in: Vec<MatchSource>,
+    /// Number of terms the commit matched; always 1 for a single-term search.
+    pub score: usize,
+    /// Matching lines, secrets masked: message lines as is, diff lines as
+    /// `path: +line`.
+    pub lines: Vec<String>,
+}
+
+/// A search over commit history.
+#[derive(Debug, Clone)]
+pub struct HistorySearch {
+    terms: Vec<Regex>,
+    /// Also search the added and removed lines of each commit's diff.
+    pub search_diffs: bool,
+    /// Hits returned at most.
+    pub limit: usize,
+}
+
+impl HistorySearch {
+    /// A search for `query` as a literal substring.
+    pub fn literal(query: &str, case_sensitive: bool) -> Result<Self> {
+        Self::regex(&regex::escape(query), case_sensitive)
+    }
+
+    /// A search for the regular expression `pattern`.
+    pub fn regex(pattern: &str, case_sensitive: bool) -> Result<Self> {
+        if pattern.is_empty() {
+            bail!("Search query must not be empty");
+        }
+        let term = RegexBuilder::new(pattern)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .with_context(|| format!("Invalid search pattern: {pattern}"))?;
+        Ok(Self::from_terms(vec![term]))
+    }
+
+    /// A ranked search for the keywords of a natural-language `question`:
+    /// words of three or more characters that are not [`STOPWORDS`], with a
+    /// common suffix dropped so `retries` and `retry` meet at `retr`.
+    pub fn keywords(question: &str) -> Result<Self> {
+        let mut stems: Vec<String> = Vec::new();
+        for word in question
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .map(str::to_lowercase)
+            .filter(|w| w.chars().count() >= 3 && !STOPWORDS.contains(&w.as_str()))
+        {
+            let stem = stem(&word).to_string();
+            if !stems.contains(&stem) {
+                stems.push(stem);
+            }
+        }
+        if stems.is_empty() {
+            bail!("Question '{question}' has no keywords to search for");
+        }
+        let terms = stems
+            .iter()
+            .map(|stem| {
+                RegexBuilder::new(&format!(r"\b{}", regex::escape(stem)))
+                    .case_insensitive(true)
+                    .build()
+                    .context("Failed to build keyword pattern")
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_terms(terms))
+    }
+
+    fn from_terms(terms: Vec<Regex>) -> Self {
+        Self {
+            terms,
+            search_diffs: false,
+            limit: usize::MAX,
+        }
+    }
+
+    /// Searches the history reachable from `rev`, newest first.
+    ///
+    /// A single-term search stops at [`Self::limit`] hits. A keyword search
+    /// scans the whole history and keeps the `limit` best hits, most matched
+    /// keywords first and newest first among equals.
+    pub fn run(&self, repo: &Repository, rev: &str) -> Result<Vec<SearchHit>> {
+        let start = repo
+            .revparse_single(rev)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Failed to resolve revision: {rev}"))?;
+        let mut walk = repo.revwalk().context("Failed to create revwalk")?;
+        walk.set_sorting(Sort::TIME)
+            .context("Failed to sort revwalk")?;
+        walk.push(start.id()).context("Failed to push revision")?;
+
+        let ranked = self.terms.len() > 1;
+        let mut hits = Vec::new();
+        for oid in walk {
+            let commit = repo
+                .find_commit(oid.context("Failed to walk history")?)
+                .context("Failed to find commit")?;
+            if let Some(hit) = self.match_commit(repo, &commit)? {
+                hits.push(hit);
+                if !ranked && hits.len() >= self.limit {
+                    break;
+                }
+            }
+        }
+        if ranked {
+            // Stable, so equally scored hits stay newest first.
+            hits.sort_by(|a, b| b.score.cmp(&a.score));
+            hits.truncate(self.limit);
+        }
+        Ok(hits)
+    }
+
+    /// The hit for `commit`, or `None` when no term matches it.
+    fn match_commit(&self, repo: &Repository, commit: &Commit<'_>) -> Result<Option<SearchHit>> {
+        let mut matched = vec![false; self.terms.len()];
+        let mut matched_in = Vec::new();
+        let mut lines = Vec::new();
+
+        let message = commit.message().unwrap_or_default();
+        if self.scan(message.lines(), &mut matched, &mut lines) {
+            matched_in.push(MatchSource::Message);
+        }
+        if self.search_diffs {
+            let diff_lines = diff_lines(repo, commit)?;
+            if self.scan(
+                diff_lines.iter().map(String::as_str),
+                &mut matched,
+                &mut lines,
+            ) {
+                matched_in.push(MatchSource::Diff);
+            }
+        }
+        if matched_in.is_empty() {
+            return Ok(None);
+        }
+
+        let author = commit.author();
+        let date = chrono::DateTime::from_timestamp(author.when().seconds(), 0)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_default();
+        Ok(Some(SearchHit {
+            hash: commit.id().to_string(),
+            author: format!(
+                "{} <{}>",
+                author.name().unwrap_or_default(),
+                author.email().unwrap_or_default()
+            ),
+            date,
+            subject: commit.summary().unwrap_or_default().to_string(),
+            matched_in,
+            score: matched.iter().filter(|m| **m).count(),
+            lines,
+        }))
+    }
+
+    /// Marks the terms found in `text` and keeps the first matching lines;
+    /// returns whether any term matched.
+    fn scan<'a>(
+        &self,
+        text: impl Iterator<Item = &'a str>,
+        matched: &mut [bool],
+        lines: &mut Vec<String>,
+    ) -> bool {
+        let mut any = false;
+        for line in text {
+            let mut line_matched = false;
+            for (term, seen) in self.terms.iter().zip(matched.iter_mut()) {
+                if term.is_match(line) {
+                    *seen = true;
+                    line_matched = true;
+                }
+            }
+            if line_matched {
+                any = true;
+                if lines.len() < MAX_SNIPPETS {
+                    lines.push(snippet(line));
+                }
+            }
+        }
+        any
+    }
+}
+
+/// `word` with one common English suffix removed, when at least four
+/// characters remain.
+fn stem(word: &str) -> &str {
+    ["ies", "ing", "ed", "es", "s", "y"]
+        .iter()
+        .find_map(|suffix| {
+            word.strip_suffix(suffix)
+                .filter(|rest| rest.chars().count() >= 4)
+        })
+        .unwrap_or(word)
+}
+
+/// A matching line, trimmed, shortened, and with secrets masked.
+fn snippet(line: &str) -> String {
+    let line = line.trim();
+    let short: String = line.chars().take(MAX_SNIPPET_CHARS).collect();
+    let short = if short.len() < line.len() {
+        format!("{short}…")
+    } else {
+        short
+    };
+    redact_secrets(&short).into_owned()
+}
+
+/// The added and removed lines of `commit` against its first parent (or the
+/// empty tree for a root commit), as `path: +line`.
+fn diff_lines(repo: &Repository, commit: &Commit<'_>) -> Result<Vec<String>> {
+    let tree = commit.tree().context("Failed to read commit tree")?;
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().context("Failed to read parent tree")?),
+        Err(_) => None,
+    };
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+        .context("Failed to diff commit against its parent")?;
+    let mut lines = Vec::new();
+    diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let origin = line.origin();
+        if origin == '+' || origin == '-' {
+            let path = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let content = String::from_utf8_lossy(line.content());
+            lines.push(format!("{path}: {origin}{}", content.trim_end()));
+        }
+        true
+    })
+    .context("Failed to read commit diff")?;
+    Ok(lines)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    /// A repo whose commits, oldest first, add `retry.rs`, tune the retry
+    /// policy in it, and document it.
+    fn repo_with_history() -> (tempfile::TempDir, Repository) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let commits = [
+            (
+                "retry.rs",
+                "let attempts = 3;\n",
+                "feat: add request client",
+                1_700_000_000,
+            ),
+            (
+                "retry.rs",
+                "let attempts = 5;\n",
+                "fix: raise retries to five",
+                1_700_086_400,
+            ),
+            (
+                "README.md",
+                "Requests back off.\n",
+                "docs: describe backoff",
+                1_700_172_800,
+            ),
+        ];
+        for (file, content, message, time) in commits {
+            std::fs::write(dir.path().join(file), content).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(std::path::Path::new(file)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let sig = Signature::new("Dev", "dev@example.com", &git2::Time::new(time, 0)).unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<&Commit<'_>> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap();
+        }
+        (dir, repo)
+    }
+
+    fn subjects(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|h| h.subject.as_str()).collect()
+    }
+
+    #[test]
+    fn literal_search_matches_messages_case_insensitively() {
+        let (_dir, repo) = repo_with_history();
+        let search = HistorySearch::literal("RETRIES", false).unwrap();
+        let hits = search.run(&repo, "HEAD").unwrap();
+        assert_eq!(subjects(&hits), ["fix: raise retries to five"]);
+        assert_eq!(hits[0].matched_in, [MatchSource::Message]);
+    }
+
+    #[test]
+    fn diff_search_finds_changed_lines() {
+        let (_dir, repo) = repo_with_history();
+        let mut search = HistorySearch::regex(r"attempts = \d", true).unwrap();
+        search.search_diffs = true;
+        let hits = search.run(&repo, "HEAD").unwrap();
+        assert_eq!(
+            subjects(&hits),
+            ["fix: raise retries to five", "feat: add request client"]
+        );
+        assert!(hits[0]
+            .lines
+            .contains(&"retry.rs: +let attempts = 5;".to_string()));
+        assert_eq!(hits[0].matched_in, [MatchSource::Diff]);
+
+        search.limit = 1;
+        assert_eq!(search.run(&repo, "HEAD").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn keyword_search_ranks_by_keywords_matched() {
+        let (_dir, repo) = repo_with_history();
+        let mut search = HistorySearch::keywords("When did we raise the retry attempts?").unwrap();
+        search.search_diffs = true;
+        let hits = search.run(&repo, "HEAD").unwrap();
+        assert_eq!(
+            subjects(&hits),
+            ["fix: raise retries to five", "feat: add request client"]
+        );
+        assert_eq!((hits[0].score, hits[1].score), (3, 2));
+    }
+
+    #[test]
+    fn questions_without_keywords_are_rejected() {
+        assert!(HistorySearch::keywords("who did it?").is_err());
+        assert_eq!(stem("retries"), "retr");
+        assert_eq!(stem("policy"), "polic");
+        assert_eq!(stem("api"), "api");
+    }
+}
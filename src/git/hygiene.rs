@@ -0,0 +1,343 @@
+//! Repository hygiene for `repo hygiene`: tracked files that look like they
+//! should never have been committed.
+//!
+//! Four kinds of file are flagged:
+//!
+//! - build output and dependency directories (`target/`, `node_modules/`,
+//!   `__pycache__/`, …),
+//! - editor and operating-system files (`.DS_Store`, `*.swp`, `.idea/`, …),
+//! - compiled artifacts and logs (`*.pyc`, `*.o`, `*.log`, …), and
+//! - binary files at or above a size threshold.
+//!
+//! Files that already match the ignore rules but are still tracked are
+//! flagged too. Each finding of the first three kinds carries the
+//! `.gitignore` pattern that keeps its files out; large binaries are
+//! reported only, since they are as often deliberate fixtures as mistakes.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::generated::GeneratedFiles;
+
+/// Directories that hold build output, installed dependencies, or caches.
+const BUILD_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "bower_components",
+    "dist",
+    "build",
+    "__pycache__",
+    ".venv",
+    "venv",
+    ".tox",
+    ".pytest_cache",
+    ".mypy_cache",
+    ".ruff_cache",
+    ".next",
+    ".nuxt",
+    ".gradle",
+    ".terraform",
+];
+
+/// Directories editors keep their per-user state in.
+const EDITOR_DIRS: &[&str] = &[".idea"];
+
+/// File names editors and operating systems leave behind.
+const EDITOR_FILES: &[&str] = &[".DS_Store", "Thumbs.db", "desktop.ini"];
+
+/// Extensions of editor swap and project files.
+const EDITOR_EXTENSIONS: &[&str] = &["swp", "swo", "iml"];
+
+/// Extensions of compiled artifacts and logs.
+const ARTIFACT_EXTENSIONS: &[&str] = &[
+    "pyc", "pyo", "class", "o", "obj", "so", "dylib", "dll", "exe", "log", "tmp",
+];
+
+/// Bytes read from the start of a file to decide whether it is binary.
+const BINARY_SNIFF_BYTES: u64 = 8_000;
+
+/// What makes a tracked file look out of place.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HygieneKind {
+    /// Tracked although the ignore rules already match it.
+    Ignored,
+    /// Build output, installed dependencies, or a tool cache.
+    BuildOutput,
+    /// Editor or operating-system state.
+    EditorFile,
+    /// A compiled artifact or log.
+    Artifact,
+    /// A binary file at or above the size threshold.
+    LargeBinary,
+}
+
+impl HygieneKind {
+    /// Label used in reports.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ignored => "already ignored but tracked",
+            Self::BuildOutput => "build output",
+            Self::EditorFile => "editor file",
+            Self::Artifact => "compiled artifact",
+            Self::LargeBinary => "large binary",
+        }
+    }
+}
+
+/// Tracked files flagged for the same reason.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HygieneFinding {
+    /// Why the files are flagged.
+    pub kind: HygieneKind,
+    /// The `.gitignore` pattern that keeps the files out; absent for files
+    /// already ignored and for large binaries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Repository-relative paths of the flagged files.
+    pub files: Vec<String>,
+    /// Combined size of the files in bytes.
+    pub bytes: u64,
+}
+
+impl HygieneFinding {
+    /// Whether the cleanup commit untracks these files.
+    pub fn is_cleanup(&self) -> bool {
+        self.kind != HygieneKind::LargeBinary
+    }
+}
+
+/// Scan options.
+#[derive(Debug, Clone, Copy)]
+pub struct HygieneOptions {
+    /// Binary files at least this many bytes are flagged.
+    pub large_file_bytes: u64,
+}
+
+/// The tracked files of `repo` that look out of place, ordered by kind and
+/// then pattern or path.
+pub fn scan(
+    repo: &Repository,
+    generated: &GeneratedFiles,
+    options: &HygieneOptions,
+) -> Result<Vec<HygieneFinding>> {
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+    let index = repo.index().context("Failed to read the git index")?;
+
+    let mut groups: BTreeMap<(HygieneKind, Option<String>), (Vec<String>, u64)> = BTreeMap::new();
+    for entry in index.iter() {
+        let file = String::from_utf8_lossy(&entry.path).into_owned();
+        let size = u64::from(entry.file_size);
+        let key = if repo.is_path_ignored(Path::new(&file)).unwrap_or(false) {
+            Some((HygieneKind::Ignored, None))
+        } else if let Some((kind, pattern)) = classify(&file) {
+            Some((kind, Some(pattern)))
+        } else if size >= options.large_file_bytes
+            && !generated.is_generated(&file)
+            && is_binary(&workdir.join(&file))
+        {
+            Some((HygieneKind::LargeBinary, Some(file.clone())))
+        } else {
+            None
+        };
+        if let Some(key) = key {
+            let (files, bytes) = groups.entry(key).or_default();
+            files.push(file);
+            *bytes += size;
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|((kind, pattern), (files, bytes))| HygieneFinding {
+            kind,
+            // A large binary's key is its path, not a pattern to add.
+            pattern: pattern.filter(|_| kind != HygieneKind::LargeBinary),
+            files,
+            bytes,
+        })
+        .collect())
+}
+
+/// Classifies a path as build output, an editor file, or an artifact, with
+/// the `.gitignore` pattern that covers it.
+pub fn classify(path: &str) -> Option<(HygieneKind, String)> {
+    let mut components: Vec<&str> = path.split('/').collect();
+    let name = components.pop().unwrap_or(path);
+    for dir in components {
+        if BUILD_DIRS.contains(&dir) {
+            return Some((HygieneKind::BuildOutput, format!("{dir}/")));
+        }
+        if EDITOR_DIRS.contains(&dir) {
+            return Some((HygieneKind::EditorFile, format!("{dir}/")));
+        }
+    }
+    if EDITOR_FILES.contains(&name) {
+        return Some((HygieneKind::EditorFile, name.to_string()));
+    }
+    if name.len() > 1 && name.ends_with('~') {
+        return Some((HygieneKind::EditorFile, "*~".to_string()));
+    }
+    let extension = name.rsplit_once('.').map(|(_, ext)| ext)?;
+    if EDITOR_EXTENSIONS.contains(&extension) {
+        return Some((HygieneKind::EditorFile, format!("*.{extension}")));
+    }
+    if ARTIFACT_EXTENSIONS.contains(&extension) {
+        return Some((HygieneKind::Artifact, format!("*.{extension}")));
+    }
+    None
+}
+
+/// Returns the patterns not already listed in `gitignore`, in order and
+/// without duplicates.
+pub fn missing_patterns<'a>(
+    gitignore: &str,
+    patterns: impl IntoIterator<Item = &'a str>,
+) -> Vec<String> {
+    let existing: Vec<&str> = gitignore.lines().map(str::trim).collect();
+    let mut missing: Vec<String> = Vec::new();
+    for pattern in patterns {
+        let pattern = pattern.trim();
+        let bare = pattern.trim_start_matches('/').trim_end_matches('/');
+        let listed = existing
+            .iter()
+            .any(|line| line.trim_start_matches('/').trim_end_matches('/') == bare);
+        if !pattern.is_empty() && !listed && !missing.iter().any(|p| p == pattern) {
+            missing.push(pattern.to_string());
+        }
+    }
+    missing
+}
+
+/// Returns `gitignore` with `patterns` appended on their own lines.
+pub fn append_patterns(gitignore: &str, patterns: &[String]) -> String {
+    let mut out = gitignore.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    for pattern in patterns {
+        out.push_str(pattern);
+        out.push('\n');
+    }
+    out
+}
+
+/// Whether the file's first bytes contain a NUL, git's own binary test.
+fn is_binary(path: &Path) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut head = Vec::new();
+    if file
+        .take(BINARY_SNIFF_BYTES)
+        .read_to_end(&mut head)
+        .is_err()
+    {
+        return false;
+    }
+    head.contains(&0)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_build_output_editor_files_and_artifacts() {
+        assert_eq!(
+            classify("web/node_modules/left-pad/index.js"),
+            Some((HygieneKind::BuildOutput, "node_modules/".to_string()))
+        );
+        assert_eq!(
+            classify("target/debug/app"),
+            Some((HygieneKind::BuildOutput, "target/".to_string()))
+        );
+        assert_eq!(
+            classify("docs/.DS_Store"),
+            Some((HygieneKind::EditorFile, ".DS_Store".to_string()))
+        );
+        assert_eq!(
+            classify("src/.main.rs.swp"),
+            Some((HygieneKind::EditorFile, "*.swp".to_string()))
+        );
+        assert_eq!(
+            classify("notes.txt~"),
+            Some((HygieneKind::EditorFile, "*~".to_string()))
+        );
+        assert_eq!(
+            classify("pkg/__init__.pyc"),
+            Some((HygieneKind::Artifact, "*.pyc".to_string()))
+        );
+        assert_eq!(classify("src/build.rs"), None);
+        assert_eq!(classify("README.md"), None);
+    }
+
+    #[test]
+    fn proposes_only_patterns_the_gitignore_lacks() {
+        let gitignore = "# build\n/target\n*.log\n";
+        let missing = missing_patterns(
+            gitignore,
+            ["target/", "*.log", ".DS_Store", "*.pyc", ".DS_Store"],
+        );
+        assert_eq!(missing, vec![".DS_Store", "*.pyc"]);
+        assert_eq!(
+            append_patterns("/target", &missing),
+            "/target\n.DS_Store\n*.pyc\n"
+        );
+    }
+
+    #[test]
+    fn scan_groups_tracked_files_by_pattern() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let files = [
+            ("src/main.rs", b"fn main() {}\n".as_slice()),
+            (".DS_Store", b"\0\0\0\x01Bud1".as_slice()),
+            ("target/debug/app.d", b"app: src/main.rs\n".as_slice()),
+            ("target/debug/app", b"\x7fELF\0\0\0\0".as_slice()),
+            (
+                "assets/logo.bin",
+                b"\0\x01\x02\x03\x04\x05\x06\x07".as_slice(),
+            ),
+        ];
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            let full = dir.path().join(path);
+            std::fs::create_dir_all(full.parent().unwrap()).unwrap();
+            std::fs::write(&full, content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+
+        let findings = scan(
+            &repo,
+            &GeneratedFiles::for_repo(dir.path()),
+            &HygieneOptions {
+                large_file_bytes: 8,
+            },
+        )
+        .unwrap();
+        let summary: Vec<(HygieneKind, Option<&str>, usize)> = findings
+            .iter()
+            .map(|f| (f.kind, f.pattern.as_deref(), f.files.len()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (HygieneKind::BuildOutput, Some("target/"), 2),
+                (HygieneKind::EditorFile, Some(".DS_Store"), 1),
+                (HygieneKind::LargeBinary, None, 1),
+            ]
+        );
+        assert_eq!(findings[2].files, vec!["assets/logo.bin"]);
+        assert!(!findings[2].is_cleanup());
+    }
+}
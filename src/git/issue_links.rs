@@ -0,0 +1,203 @@
+//! Issue references for pull request descriptions.
+//!
+//! Issues are picked up from the branch name (`fix/123-crash`,
+//! `issue-123-crash`), from closing keywords in commit messages
+//! (`Fixes #123`, `Closes org/repo#45`), or given explicitly, and appended to
+//! the description as closing lines so the forge closes them on merge.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+/// Closing keywords GitHub, Gitea, and Bitbucket all recognise.
+const CLOSING_KEYWORDS: &str = r"close[sd]?|fix(?:e[sd])?|resolve[sd]?";
+
+#[allow(clippy::unwrap_used)] // Compile-time constant regex pattern
+static BRANCH_ISSUE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:^|[/_-])(?:issue-|issues/|gh-|#)(\d+)(?:$|[/_-])|(?:^|/)(\d+)-").unwrap()
+});
+
+#[allow(clippy::unwrap_used)] // Compile-time constant regex pattern
+static CLOSING_REFERENCE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\b(?:{CLOSING_KEYWORDS}):?\s+((?:[\w.-]+/[\w.-]+)?#\d+)"
+    ))
+    .unwrap()
+});
+
+#[allow(clippy::unwrap_used)] // Compile-time constant regex pattern
+static ISSUE_REF_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:(?P<repo>[\w.-]+/[\w.-]+))?#?(?P<number>\d+)$").unwrap());
+
+/// A reference to an issue, optionally in another repository.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueRef {
+    /// `owner/repo` for an issue in another repository.
+    pub repo: Option<String>,
+    /// Issue number.
+    pub number: u64,
+}
+
+impl IssueRef {
+    /// Parses `123`, `#123`, or `owner/repo#123`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let captures = ISSUE_REF_PATTERN.captures(value.trim())?;
+        let repo = captures.name("repo").map(|m| m.as_str().to_string());
+        // A cross-repository reference needs the `#` separator.
+        if repo.is_some() && !value.contains('#') {
+            return None;
+        }
+        Some(Self {
+            repo,
+            number: captures["number"].parse().ok()?,
+        })
+    }
+
+    /// The closing line for this issue: `Fixes #123` within the repository,
+    /// `Closes owner/repo#123` across repositories.
+    pub fn closing_line(&self) -> String {
+        match self.repo {
+            Some(_) => format!("Closes {self}"),
+            None => format!("Fixes {self}"),
+        }
+    }
+}
+
+impl fmt::Display for IssueRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repo {
+            Some(repo) => write!(f, "{repo}#{}", self.number),
+            None => write!(f, "#{}", self.number),
+        }
+    }
+}
+
+/// Parses `--fixes` values, failing on the first one that is not an issue
+/// reference.
+pub fn parse_issue_refs(values: &[String]) -> Result<Vec<IssueRef>> {
+    values
+        .iter()
+        .map(|value| match IssueRef::parse(value) {
+            Some(issue) => Ok(issue),
+            None => {
+                bail!("Invalid issue reference '{value}'; expected 123, #123, or owner/repo#123")
+            }
+        })
+        .collect()
+}
+
+/// Extracts issue numbers from a branch name such as `fix/123-crash`,
+/// `issue-123-crash`, or `feat/gh-123`. Jira-style keys are not issues.
+pub fn issues_from_branch(branch: &str) -> Vec<IssueRef> {
+    BRANCH_ISSUE_PATTERN
+        .captures_iter(branch)
+        .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+        .filter_map(|m| m.as_str().parse().ok())
+        .map(|number| IssueRef { repo: None, number })
+        .collect()
+}
+
+/// Extracts the issues a text closes with a keyword, e.g. `Fixes #123` or
+/// `Closes: org/repo#45`.
+pub fn closing_references(text: &str) -> Vec<IssueRef> {
+    CLOSING_REFERENCE_PATTERN
+        .captures_iter(text)
+        .filter_map(|c| IssueRef::parse(&c[1]))
+        .collect()
+}
+
+/// Appends a closing line to `description` for every issue it does not
+/// already close, after a blank line. Duplicate issues are linked once.
+pub fn ensure_issue_links(description: &str, issues: &[IssueRef]) -> String {
+    let already_closed = closing_references(description);
+    let mut missing: Vec<&IssueRef> = Vec::new();
+    for issue in issues {
+        if !already_closed.contains(issue) && !missing.contains(&issue) {
+            missing.push(issue);
+        }
+    }
+    if missing.is_empty() {
+        return description.to_string();
+    }
+    let mut out = description.trim_end().to_string();
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    let lines: Vec<String> = missing.iter().map(|issue| issue.closing_line()).collect();
+    out.push_str(&lines.join("\n"));
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn local(number: u64) -> IssueRef {
+        IssueRef { repo: None, number }
+    }
+
+    #[test]
+    fn parses_issue_refs() {
+        assert_eq!(IssueRef::parse("123"), Some(local(123)));
+        assert_eq!(IssueRef::parse(" #7 "), Some(local(7)));
+        assert_eq!(
+            IssueRef::parse("acme/web-app#45"),
+            Some(IssueRef {
+                repo: Some("acme/web-app".to_string()),
+                number: 45,
+            })
+        );
+        assert_eq!(IssueRef::parse("PROJ-123"), None);
+        assert_eq!(IssueRef::parse("acme/web45"), None);
+        assert!(parse_issue_refs(&["#1".to_string(), "x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn finds_issues_in_branch_names() {
+        assert_eq!(
+            issues_from_branch("fix/123-crash-on-start"),
+            vec![local(123)]
+        );
+        assert_eq!(issues_from_branch("issue-45-typo"), vec![local(45)]);
+        assert_eq!(issues_from_branch("feat/GH-9"), vec![local(9)]);
+        assert_eq!(issues_from_branch("feat/PROJ-123/add-thing"), vec![]);
+        assert_eq!(issues_from_branch("feat/oauth2-login"), vec![]);
+    }
+
+    #[test]
+    fn finds_closing_references() {
+        let text = "feat: add x\n\nFixes #12, closes: acme/api#3\nRefs #99";
+        assert_eq!(
+            closing_references(text),
+            vec![
+                local(12),
+                IssueRef {
+                    repo: Some("acme/api".to_string()),
+                    number: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn appends_only_missing_closing_lines() {
+        let issues = vec![
+            local(12),
+            local(13),
+            local(13),
+            IssueRef {
+                repo: Some("acme/api".to_string()),
+                number: 3,
+            },
+        ];
+        assert_eq!(
+            ensure_issue_links("## Summary\n\nAdds x.\n\nResolves #12\n", &issues),
+            "## Summary\n\nAdds x.\n\nResolves #12\n\nFixes #13\nCloses acme/api#3\n"
+        );
+        assert_eq!(ensure_issue_links("Body\n", &[]), "Body\n");
+    }
+}
@@ -0,0 +1,193 @@
+//! License header verification and insertion for `check headers`.
+//!
+//! The required header is the plain text of `.omni-dev/license-header.txt`
+//! (resolved like the other context files), written in each file's line
+//! comment syntax. `{year}` in the text stands for a year, or a range or list
+//! of years, when checking, and for the current year when inserting:
+//!
+//! ```text
+//! Copyright {year} Example Corp.
+//! SPDX-License-Identifier: Apache-2.0
+//! ```
+//!
+//! A file carries the header when its leading comment block, after any
+//! `#!` line and blank lines, contains the header's lines in order.
+
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+
+/// Name of the header file in the context directory.
+pub const LICENSE_HEADER_FILE: &str = "license-header.txt";
+
+/// Placeholder for the copyright year.
+const YEAR_PLACEHOLDER: &str = "{year}";
+
+/// Matches what `{year}` stands for when checking: `2024`, `2019-2024`, or
+/// `2019, 2021`.
+const YEAR_PATTERN: &str = r"\d{4}(?:\s*[-–,]\s*\d{4})*";
+
+/// The required license header.
+#[derive(Debug, Clone)]
+pub struct LicenseHeader {
+    lines: Vec<String>,
+    pattern: Regex,
+}
+
+impl LicenseHeader {
+    /// Parses the header text; blank lines around it are dropped.
+    pub fn parse(text: &str) -> Result<Self> {
+        let lines: Vec<String> = text.lines().map(|l| l.trim_end().to_string()).collect();
+        let start = lines.iter().position(|l| !l.is_empty());
+        let end = lines.iter().rposition(|l| !l.is_empty());
+        let (Some(start), Some(end)) = (start, end) else {
+            bail!("{LICENSE_HEADER_FILE} is empty");
+        };
+        let lines = lines[start..=end].to_vec();
+        let pattern = lines
+            .iter()
+            .map(|line| {
+                line.split(YEAR_PLACEHOLDER)
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join(YEAR_PATTERN)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(Self {
+            lines,
+            pattern: Regex::new(&format!("(?m)^{pattern}$"))
+                .context("Failed to compile the license header")?,
+        })
+    }
+
+    /// Loads the header configured for the repository at `repo_root`, or
+    /// `None` when the project has no `license-header.txt`.
+    pub fn load(repo_root: &Path) -> Result<Option<Self>> {
+        let context_dir = crate::claude::context::resolve_context_dir_at(None, repo_root);
+        let path = crate::claude::context::discovery::resolve_config_file(
+            &context_dir,
+            LICENSE_HEADER_FILE,
+        );
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        Self::parse(&text).map(Some)
+    }
+
+    /// Returns whether `content`, commented with `prefix`, carries the
+    /// header.
+    pub fn is_present(&self, content: &str, prefix: &str) -> bool {
+        let block: Vec<&str> = content
+            .lines()
+            .skip(usize::from(content.starts_with("#!")))
+            .skip_while(|line| line.trim().is_empty())
+            .map_while(|line| line.strip_prefix(prefix))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line).trim_end())
+            .collect();
+        self.pattern.is_match(&block.join("\n"))
+    }
+
+    /// The header as comment lines for `prefix`, dated `year`.
+    pub fn render(&self, prefix: &str, year: i32) -> Vec<String> {
+        self.lines
+            .iter()
+            .map(|line| {
+                let line = line.replace(YEAR_PLACEHOLDER, &year.to_string());
+                if line.is_empty() {
+                    prefix.to_string()
+                } else {
+                    format!("{prefix} {line}")
+                }
+            })
+            .collect()
+    }
+
+    /// Returns `content` with the header inserted at the top (after any `#!`
+    /// line) and separated from the rest by a blank line.
+    pub fn insert(&self, content: &str, prefix: &str, year: i32) -> String {
+        let mut header = self.render(prefix, year).join("\n");
+        header.push('\n');
+        let (shebang, rest) = match content.split_once('\n') {
+            Some((first, rest)) if first.starts_with("#!") => (Some(first), rest),
+            _ if content.starts_with("#!") => (Some(content), ""),
+            _ => (None, content),
+        };
+        let mut out = String::new();
+        if let Some(shebang) = shebang {
+            out.push_str(shebang);
+            out.push_str("\n\n");
+        }
+        out.push_str(&header);
+        if !rest.trim().is_empty() {
+            out.push('\n');
+            out.push_str(rest.trim_start_matches('\n'));
+        }
+        out
+    }
+}
+
+/// The line comment marker for a source file, or `None` for files that are
+/// not source code (documentation, data, and files of unknown type).
+pub fn comment_prefix(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let extension = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+    match extension {
+        "rs" | "go" | "java" | "kt" | "kts" | "scala" | "swift" | "c" | "h" | "cc" | "cpp"
+        | "cxx" | "hpp" | "cs" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "dart" | "proto"
+        | "php" | "groovy" | "zig" => Some("//"),
+        "py" | "rb" | "sh" | "bash" | "zsh" | "pl" | "r" | "R" | "ex" | "exs" | "cr" | "nim"
+        | "ps1" | "tf" => Some("#"),
+        "lua" | "sql" | "hs" | "elm" => Some("--"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "\nCopyright {year} Example Corp.\n\nSPDX-License-Identifier: MIT\n";
+
+    #[test]
+    fn recognises_the_header_with_any_year() {
+        let header = LicenseHeader::parse(HEADER).unwrap();
+        assert!(header.is_present(
+            "// Copyright 2019-2024 Example Corp.\n//\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n",
+            "//"
+        ));
+        assert!(header.is_present(
+            "#!/usr/bin/env python3\n# Copyright 2026 Example Corp.\n#\n# SPDX-License-Identifier: MIT\n",
+            "#"
+        ));
+        assert!(!header.is_present(
+            "// Copyright 2026 Other Corp.\n//\n// SPDX-License-Identifier: MIT\n",
+            "//"
+        ));
+        assert!(!header.is_present("fn main() {}\n// Copyright 2026 Example Corp.\n", "//"));
+        assert!(LicenseHeader::parse("\n\n").is_err());
+    }
+
+    #[test]
+    fn inserts_the_header_after_a_shebang() {
+        let header = LicenseHeader::parse(HEADER).unwrap();
+        assert_eq!(
+            header.insert("fn main() {}\n", "//", 2026),
+            "// Copyright 2026 Example Corp.\n//\n// SPDX-License-Identifier: MIT\n\nfn main() {}\n"
+        );
+        let script = header.insert("#!/bin/sh\necho hi\n", "#", 2026);
+        assert_eq!(
+            script,
+            "#!/bin/sh\n\n# Copyright 2026 Example Corp.\n#\n# SPDX-License-Identifier: MIT\n\necho hi\n"
+        );
+        assert!(header.is_present(&script, "#"));
+        assert_eq!(comment_prefix("src/main.rs"), Some("//"));
+        assert_eq!(comment_prefix("docs/guide.md"), None);
+    }
+}
@@ -0,0 +1,513 @@
+//! Code ownership per area for `git stats owners`.
+//!
+//! An area is a directory cut to a chosen depth. Each author of an area is
+//! scored on three signals: the share of the area's current lines they last
+//! changed (`git blame` at `HEAD`), the share of the commits touching the
+//! area they wrote, and that same share with every commit weighted down by
+//! its age. The report can be saved as [`OWNERS_FILE`] in the context
+//! directory, where `create pr` reads it for reviewer suggestions, or written
+//! out as a `CODEOWNERS` file.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use git2::{DiffOptions, Repository, Sort};
+use serde::{Deserialize, Serialize};
+
+use super::reviewers::github_login_from_email;
+
+/// File name of a saved report in the context directory.
+pub const OWNERS_FILE: &str = "owners.yaml";
+
+/// Area of the files at the top of the repository.
+pub const ROOT_AREA: &str = "(root)";
+
+/// Days after which a commit counts half as much toward recency.
+const RECENCY_HALF_LIFE_DAYS: f64 = 180.0;
+
+/// Weight of the share of current lines in the score.
+const BLAME_WEIGHT: f64 = 0.4;
+
+/// Weight of the share of commits in the score.
+const FREQUENCY_WEIGHT: f64 = 0.3;
+
+/// Weight of the age-weighted share of commits in the score.
+const RECENCY_WEIGHT: f64 = 0.3;
+
+/// How a report is computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipOptions {
+    /// Directory depth areas are cut at; at least 1.
+    pub depth: usize,
+    /// Only commits made since then count toward frequency and recency.
+    pub since: Option<DateTime<Utc>>,
+    /// Owners kept per area.
+    pub top: usize,
+}
+
+/// Ownership of every area under a path.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnershipReport {
+    /// Path the report covers, empty for the whole repository.
+    #[serde(default)]
+    pub path: String,
+    /// Directory depth areas are cut at.
+    pub depth: usize,
+    /// The areas, sorted by name.
+    pub areas: Vec<AreaOwnership>,
+}
+
+/// Ownership of one area.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AreaOwnership {
+    /// Directory path, or [`ROOT_AREA`].
+    pub area: String,
+    /// Tracked files in the area.
+    pub files: usize,
+    /// Lines in those files at `HEAD`.
+    pub lines: usize,
+    /// Non-merge commits that touched the area.
+    pub commits: usize,
+    /// The owners, highest score first.
+    pub owners: Vec<Owner>,
+}
+
+/// One author's ownership of an area.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Owner {
+    /// Author name, as last committed.
+    pub name: String,
+    /// Author email.
+    pub email: String,
+    /// GitHub login, when the email is a GitHub no-reply address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    /// Ownership score, 0–100.
+    pub score: f64,
+    /// Lines of the area they last changed.
+    pub lines: usize,
+    /// Commits to the area they wrote.
+    pub commits: usize,
+    /// Date of their latest commit to the area (`YYYY-MM-DD`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit: Option<String>,
+}
+
+/// One author's raw signals in one area.
+#[derive(Debug, Default)]
+struct Evidence {
+    name: String,
+    lines: usize,
+    commits: usize,
+    recency: f64,
+    last: Option<DateTime<Utc>>,
+}
+
+/// The signals of one area, per author email.
+#[derive(Debug, Default)]
+struct AreaEvidence {
+    files: usize,
+    authors: HashMap<String, Evidence>,
+}
+
+impl OwnershipReport {
+    /// Computes the ownership of the tracked files under `path` (empty for
+    /// the whole repository) in `repo`. `now` anchors recency.
+    pub fn analyze(
+        repo: &Repository,
+        path: &str,
+        options: &OwnershipOptions,
+        now: DateTime<Utc>,
+    ) -> Result<Self> {
+        let path = normalize_path(path);
+        let depth = options.depth.max(1);
+        let mut areas: BTreeMap<String, AreaEvidence> = BTreeMap::new();
+
+        let index = repo.index().context("Failed to read the git index")?;
+        let files: Vec<String> = index
+            .iter()
+            .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+            .filter(|file| is_under(file, path))
+            .collect();
+        for file in &files {
+            let area = areas.entry(area_of(file, depth)).or_default();
+            area.files += 1;
+            // Binary, submodule, and otherwise unblamable files still count
+            // as files of the area.
+            let Ok(blame) = repo.blame_file(Path::new(file), None) else {
+                continue;
+            };
+            for hunk in blame.iter() {
+                if hunk.final_commit_id().is_zero() {
+                    continue;
+                }
+                let Some(signature) = hunk.final_signature() else {
+                    continue;
+                };
+                let email = signature.email().unwrap_or_default().to_lowercase();
+                let evidence = area.authors.entry(email).or_default();
+                if evidence.name.is_empty() {
+                    evidence.name = signature.name().unwrap_or_default().to_string();
+                }
+                evidence.lines += hunk.lines_in_hunk();
+            }
+        }
+
+        let head = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .context("Failed to resolve HEAD")?;
+        let mut walk = repo.revwalk().context("Failed to create revwalk")?;
+        walk.set_sorting(Sort::TIME)
+            .context("Failed to sort revwalk")?;
+        walk.push(head.id()).context("Failed to push HEAD")?;
+        for oid in walk {
+            let commit = repo
+                .find_commit(oid.context("Failed to walk history")?)
+                .context("Failed to find commit")?;
+            if options
+                .since
+                .is_some_and(|since| commit.time().seconds() < since.timestamp())
+            {
+                break;
+            }
+            if commit.parent_count() > 1 {
+                continue;
+            }
+
+            let touched = touched_areas(repo, &commit, path, depth)?;
+            if touched.is_empty() {
+                continue;
+            }
+            let author = commit.author();
+            let email = author.email().unwrap_or_default().to_lowercase();
+            let date = DateTime::from_timestamp(author.when().seconds(), 0).unwrap_or_default();
+            let age_days = (now - date).num_seconds().max(0) as f64 / 86_400.0;
+            let weight = 0.5_f64.powf(age_days / RECENCY_HALF_LIFE_DAYS);
+            for area in touched {
+                // Areas that no longer have files are not reported.
+                let Some(area) = areas.get_mut(&area) else {
+                    continue;
+                };
+                let evidence = area.authors.entry(email.clone()).or_default();
+                if evidence.last.is_none() {
+                    // The walk is newest first, so this is their latest name.
+                    evidence.name = author.name().unwrap_or_default().to_string();
+                    evidence.last = Some(date);
+                }
+                evidence.commits += 1;
+                evidence.recency += weight;
+            }
+        }
+
+        Ok(Self {
+            path: path.to_string(),
+            depth,
+            areas: areas
+                .into_iter()
+                .map(|(name, evidence)| rank(name, evidence, options.top))
+                .collect(),
+        })
+    }
+
+    /// Reads the report saved in `context_dir`, if there is a readable one.
+    pub fn load(context_dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(context_dir.join(OWNERS_FILE)).ok()?;
+        serde_yaml::from_str(&content).ok()
+    }
+
+    /// Saves the report in `context_dir` and returns the file written.
+    pub fn save(&self, context_dir: &Path) -> Result<PathBuf> {
+        std::fs::create_dir_all(context_dir)
+            .with_context(|| format!("Failed to create {}", context_dir.display()))?;
+        let path = context_dir.join(OWNERS_FILE);
+        let yaml = crate::data::yaml::to_yaml(self)?;
+        std::fs::write(&path, yaml)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// The GitHub logins owning the areas of `files`, highest score first
+    /// within each area, in order of first appearance.
+    pub fn owners_for_files(&self, files: &[String]) -> Vec<String> {
+        let mut logins: Vec<String> = Vec::new();
+        for file in files {
+            let area = area_of(file, self.depth);
+            let Some(area) = self.areas.iter().find(|a| a.area == area) else {
+                continue;
+            };
+            for login in area.owners.iter().filter_map(|o| o.login.as_ref()) {
+                if !logins.contains(login) {
+                    logins.push(login.clone());
+                }
+            }
+        }
+        logins
+    }
+
+    /// Writes the report as a `CODEOWNERS` file: one rule per area, owners
+    /// by `@login` when known and by email otherwise.
+    pub fn to_codeowners(&self) -> String {
+        let mut out = String::from(
+            "# Generated by `omni-dev git stats owners --codeowners` from blame and commit\n\
+             # history. Review before committing: every owner needs write access.\n\n",
+        );
+        for area in self.areas.iter().filter(|a| !a.owners.is_empty()) {
+            let pattern = if area.area == ROOT_AREA {
+                "/*".to_string()
+            } else {
+                format!("/{}/", area.area)
+            };
+            let owners: Vec<String> = area
+                .owners
+                .iter()
+                .map(|o| {
+                    o.login
+                        .as_ref()
+                        .map_or_else(|| o.email.clone(), |login| format!("@{login}"))
+                })
+                .collect();
+            out.push_str(&format!("{pattern} {}\n", owners.join(" ")));
+        }
+        out
+    }
+}
+
+/// The area of `file`: its directory cut to `depth` components, or
+/// [`ROOT_AREA`] at the top of the repository.
+pub fn area_of(file: &str, depth: usize) -> String {
+    let dirs: Vec<&str> = file.split('/').collect();
+    let dirs = &dirs[..dirs.len().saturating_sub(1)];
+    if dirs.is_empty() {
+        ROOT_AREA.to_string()
+    } else {
+        dirs[..depth.min(dirs.len())].join("/")
+    }
+}
+
+/// `path` without a leading `./` or trailing `/`.
+fn normalize_path(path: &str) -> &str {
+    let path = path.trim();
+    let path = path.strip_prefix("./").unwrap_or(path);
+    path.trim_end_matches('/')
+}
+
+/// Whether `file` is `path` or inside it; everything is under `""`.
+fn is_under(file: &str, path: &str) -> bool {
+    path.is_empty()
+        || file == path
+        || file
+            .strip_prefix(path)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The areas of the files under `path` that `commit` changed.
+fn touched_areas(
+    repo: &Repository,
+    commit: &git2::Commit<'_>,
+    path: &str,
+    depth: usize,
+) -> Result<HashSet<String>> {
+    let tree = commit.tree().context("Failed to read commit tree")?;
+    let parent_tree = match commit.parents().next() {
+        Some(parent) => Some(parent.tree().context("Failed to read parent tree")?),
+        None => None,
+    };
+    let mut options = DiffOptions::new();
+    if !path.is_empty() {
+        options.pathspec(path);
+    }
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut options))
+        .context("Failed to diff commit")?;
+    Ok(diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(|file| file.to_string_lossy().into_owned())
+        .filter(|file| is_under(file, path))
+        .map(|file| area_of(&file, depth))
+        .collect())
+}
+
+/// Scores the authors of one area and keeps the `top` ones.
+fn rank(area: String, evidence: AreaEvidence, top: usize) -> AreaOwnership {
+    let lines: usize = evidence.authors.values().map(|e| e.lines).sum();
+    let commits: usize = evidence.authors.values().map(|e| e.commits).sum();
+    let recency: f64 = evidence.authors.values().map(|e| e.recency).sum();
+
+    let mut owners: Vec<Owner> = evidence
+        .authors
+        .into_iter()
+        .map(|(email, e)| {
+            let signals = [
+                (BLAME_WEIGHT, e.lines as f64, lines as f64),
+                (FREQUENCY_WEIGHT, e.commits as f64, commits as f64),
+                (RECENCY_WEIGHT, e.recency, recency),
+            ];
+            // Signals with nothing to share, e.g. no commits since --since,
+            // drop out and the others make up the whole score.
+            let weight: f64 = signals.iter().filter(|s| s.2 > 0.0).map(|s| s.0).sum();
+            let share: f64 = signals
+                .iter()
+                .filter(|s| s.2 > 0.0)
+                .map(|(w, value, total)| w * value / total)
+                .sum();
+            let score = if weight > 0.0 {
+                (share / weight * 1000.0).round() / 10.0
+            } else {
+                0.0
+            };
+            Owner {
+                name: e.name,
+                login: github_login_from_email(&email),
+                email,
+                score,
+                lines: e.lines,
+                commits: e.commits,
+                last_commit: e.last.map(|d| d.format("%Y-%m-%d").to_string()),
+            }
+        })
+        .filter(|o| o.score > 0.0)
+        .collect();
+    owners.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    owners.truncate(top);
+
+    AreaOwnership {
+        area,
+        files: evidence.files,
+        lines,
+        commits,
+        owners,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit_file(repo: &Repository, path: &str, content: &str, email: &str, time: i64) {
+        let workdir = repo.workdir().unwrap();
+        let file = workdir.join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(&file, content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let name = email.split('@').next().unwrap();
+        let signature = git2::Signature::new(name, email, &git2::Time::new(time, 0)).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &format!("change {path}"),
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn areas_cut_directories_to_the_depth() {
+        assert_eq!(area_of("src/git/ownership.rs", 1), "src");
+        assert_eq!(area_of("src/git/ownership.rs", 2), "src/git");
+        assert_eq!(area_of("src/main.rs", 3), "src");
+        assert_eq!(area_of("README.md", 2), ROOT_AREA);
+        assert!(is_under("src/git/a.rs", "src/git"));
+        assert!(!is_under("src/gitx/a.rs", "src/git"));
+        assert_eq!(normalize_path("./src/git/"), "src/git");
+    }
+
+    #[test]
+    fn scores_combine_blame_frequency_and_recency() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let day = 86_400;
+        let now = DateTime::from_timestamp(1_000 * day, 0).unwrap();
+        commit_file(
+            &repo,
+            "src/a.rs",
+            "a\nb\nc\n",
+            "1+ann@users.noreply.github.com",
+            100 * day,
+        );
+        commit_file(
+            &repo,
+            "src/a.rs",
+            "a\nb\nc\nd\n",
+            "bob@example.com",
+            990 * day,
+        );
+        commit_file(&repo, "README.md", "hi\n", "bob@example.com", 990 * day);
+
+        let options = OwnershipOptions {
+            depth: 1,
+            since: None,
+            top: 3,
+        };
+        let report = OwnershipReport::analyze(&repo, "", &options, now).unwrap();
+        let areas: Vec<&str> = report.areas.iter().map(|a| a.area.as_str()).collect();
+        assert_eq!(areas, [ROOT_AREA, "src"]);
+
+        let src = &report.areas[1];
+        assert_eq!((src.files, src.lines, src.commits), (1, 4, 2));
+        // Ann has 3 of 4 lines but her one commit is old; Bob's is recent.
+        assert_eq!(src.owners[0].name, "bob");
+        assert_eq!(src.owners[1].login.as_deref(), Some("ann"));
+        assert_eq!(src.owners[1].last_commit.as_deref(), Some("1970-04-11"));
+        assert!(src.owners[0].score + src.owners[1].score > 99.0);
+
+        assert_eq!(
+            report.owners_for_files(&["src/b.rs".to_string()]),
+            vec!["ann".to_string()]
+        );
+        assert_eq!(
+            report.to_codeowners().lines().skip(3).collect::<Vec<_>>(),
+            ["/* bob@example.com", "/src/ bob@example.com @ann"]
+        );
+
+        let scoped = OwnershipReport::analyze(&repo, "src", &options, now).unwrap();
+        assert_eq!(scoped.areas.len(), 1);
+        assert_eq!(scoped.path, "src");
+    }
+
+    #[test]
+    fn saved_reports_load_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = OwnershipReport {
+            path: String::new(),
+            depth: 2,
+            areas: vec![AreaOwnership {
+                area: "src/git".to_string(),
+                files: 1,
+                lines: 10,
+                commits: 2,
+                owners: vec![Owner {
+                    name: "Ann".to_string(),
+                    email: "1+ann@users.noreply.github.com".to_string(),
+                    login: Some("ann".to_string()),
+                    score: 100.0,
+                    lines: 10,
+                    commits: 2,
+                    last_commit: Some("2024-03-01".to_string()),
+                }],
+            }],
+        };
+        let path = report.save(&dir.path().join(".omni-dev")).unwrap();
+        assert!(path.ends_with(OWNERS_FILE));
+        assert_eq!(
+            OwnershipReport::load(&dir.path().join(".omni-dev")),
+            Some(report)
+        );
+        assert_eq!(OwnershipReport::load(dir.path()), None);
+    }
+}
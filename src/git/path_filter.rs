@@ -0,0 +1,211 @@
+//! Path scoping for branch analysis.
+//!
+//! Monorepo teams pass `--paths` globs to restrict a branch to one package's
+//! slice: commits that touch no matching file are dropped, and the rest keep
+//! only their matching files, diff summary lines, and diffs.
+
+use std::fs;
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::git::CommitInfo;
+
+/// Characters that make a `--paths` value a glob rather than a plain path.
+const GLOB_CHARS: &[char] = &['*', '?', '[', '{'];
+
+/// A set of path globs commits are restricted to.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    patterns: Vec<String>,
+    set: GlobSet,
+}
+
+impl PathFilter {
+    /// Builds a filter from `--paths` values, or `None` when none are given.
+    ///
+    /// `*` does not cross `/`; use `**` for that. A value without glob
+    /// characters also matches everything below it, so `packages/api` works
+    /// like `packages/api/**`.
+    pub fn new(patterns: &[String]) -> Result<Option<Self>> {
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.trim().trim_start_matches("./");
+            let mut globs = vec![pattern.to_string()];
+            if !pattern.contains(GLOB_CHARS) {
+                globs.push(format!("{}/**", pattern.trim_end_matches('/')));
+            }
+            for glob in globs {
+                builder.add(
+                    GlobBuilder::new(&glob)
+                        .literal_separator(true)
+                        .build()
+                        .with_context(|| format!("Invalid --paths glob '{pattern}'"))?,
+                );
+            }
+        }
+        Ok(Some(Self {
+            patterns: patterns.to_vec(),
+            set: builder.build().context("Failed to compile --paths globs")?,
+        }))
+    }
+
+    /// The `--paths` values the filter was built from.
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Returns whether a repository-relative path is in scope.
+    pub fn matches(&self, path: &str) -> bool {
+        self.set.is_match(path)
+    }
+
+    /// Restricts `commits` to the filter's paths, dropping commits that touch
+    /// none of them.
+    ///
+    /// A kept commit's diff file is replaced by a sibling `.paths.diff` file
+    /// holding only the in-scope per-file diffs; the full diff is left as is.
+    pub fn restrict(&self, commits: Vec<CommitInfo>) -> Result<Vec<CommitInfo>> {
+        let mut kept = Vec::new();
+        for mut commit in commits {
+            let changes = &mut commit.analysis.file_changes;
+            changes.file_list.retain(|fc| self.matches(&fc.file));
+            if changes.file_list.is_empty() {
+                continue;
+            }
+            changes.total_files = changes.file_list.len();
+            changes.files_added = changes
+                .file_list
+                .iter()
+                .filter(|change| change.status == "A")
+                .count();
+            changes.files_deleted = changes
+                .file_list
+                .iter()
+                .filter(|change| change.status == "D")
+                .count();
+
+            let analysis = &mut commit.analysis;
+            analysis.diff_summary = analysis
+                .diff_summary
+                .lines()
+                .filter(|line| {
+                    line.split(" | ")
+                        .next()
+                        .is_some_and(|path| self.matches(path.trim()))
+                })
+                .map(|line| format!("{line}\n"))
+                .collect();
+            analysis
+                .file_diffs
+                .retain(|file_diff| self.matches(&file_diff.path));
+
+            let mut scoped_diff = String::new();
+            for file_diff in &analysis.file_diffs {
+                let diff = fs::read_to_string(&file_diff.diff_file).with_context(|| {
+                    format!("Failed to read per-file diff: {}", file_diff.diff_file)
+                })?;
+                scoped_diff.push_str(&diff);
+            }
+            let scoped_path = match analysis.diff_file.strip_suffix(".diff") {
+                Some(stem) => format!("{stem}.paths.diff"),
+                None => format!("{}.paths.diff", analysis.diff_file),
+            };
+            fs::write(&scoped_path, &scoped_diff)
+                .with_context(|| format!("Failed to write scoped diff file: {scoped_path}"))?;
+            analysis.diff_file = scoped_path;
+
+            kept.push(commit);
+        }
+        Ok(kept)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::git::FileDiffRef;
+    use crate::test_support::commits;
+
+    fn filter(patterns: &[&str]) -> PathFilter {
+        let patterns: Vec<String> = patterns.iter().map(|p| (*p).to_string()).collect();
+        PathFilter::new(&patterns).unwrap().unwrap()
+    }
+
+    fn commit(dir: &std::path::Path, hash: &str, files: &[(&str, &str)]) -> CommitInfo {
+        let diff_file = dir.join(format!("{hash}.diff"));
+        fs::write(&diff_file, "full diff\n").unwrap();
+        let file_diffs = files
+            .iter()
+            .enumerate()
+            .map(|(index, (_, path))| {
+                let per_file = dir.join(format!("{hash}-{index}.diff"));
+                let content = format!("diff --git a/{path} b/{path}\n");
+                fs::write(&per_file, &content).unwrap();
+                FileDiffRef {
+                    path: (*path).to_string(),
+                    diff_file: per_file.to_string_lossy().to_string(),
+                    byte_len: content.len(),
+                }
+            })
+            .collect();
+        let paths: Vec<&str> = files.iter().map(|(_, path)| *path).collect();
+        let mut commit = commits::commit(hash, "feat: x", &paths);
+        for (change, (status, _)) in commit.analysis.file_changes.file_list.iter_mut().zip(files) {
+            change.status = (*status).to_string();
+        }
+        commit.analysis.diff_summary = files
+            .iter()
+            .map(|(_, file)| format!(" {file} | 2 +1 -1\n"))
+            .collect();
+        commit.analysis.diff_file = diff_file.to_string_lossy().to_string();
+        commit.analysis.file_diffs = file_diffs;
+        commit
+    }
+
+    #[test]
+    fn no_patterns_means_no_filter() {
+        assert!(PathFilter::new(&[]).unwrap().is_none());
+        assert!(PathFilter::new(&["packages/[api".to_string()]).is_err());
+    }
+
+    #[test]
+    fn plain_paths_match_their_subtree() {
+        let filter = filter(&["packages/api", "docs/*.md"]);
+        assert!(filter.matches("packages/api/src/lib.rs"));
+        assert!(filter.matches("packages/api"));
+        assert!(!filter.matches("packages/api-client/src/lib.rs"));
+        assert!(filter.matches("docs/guide.md"));
+        assert!(!filter.matches("docs/api/guide.md"));
+    }
+
+    #[test]
+    fn restrict_drops_and_trims_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let commits = vec![
+            commit(
+                dir.path(),
+                "aaaa",
+                &[("A", "packages/api/new.rs"), ("M", "packages/web/app.ts")],
+            ),
+            commit(dir.path(), "bbbb", &[("M", "packages/web/app.ts")]),
+        ];
+
+        let kept = filter(&["packages/api"]).restrict(commits).unwrap();
+        assert_eq!(kept.len(), 1);
+        let analysis = &kept[0].analysis;
+        assert_eq!(analysis.file_changes.total_files, 1);
+        assert_eq!(analysis.file_changes.files_added, 1);
+        assert_eq!(analysis.diff_summary, " packages/api/new.rs | 2 +1 -1\n");
+        assert_eq!(analysis.file_diffs.len(), 1);
+        assert!(analysis.diff_file.ends_with("aaaa.paths.diff"));
+        assert_eq!(
+            fs::read_to_string(&analysis.diff_file).unwrap(),
+            "diff --git a/packages/api/new.rs b/packages/api/new.rs\n"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! Commit message quality over history for `git stats quality`.
+//!
+//! Each non-merge commit is scored against three rules that need no AI: the
+//! subject names a known conventional type, the message has a body, and the
+//! subject fits in [`MAX_SUBJECT_LENGTH`] characters. A [`QualityReport`]
+//! buckets the scores by period, overall and per author or scope, so adoption
+//! of the conventions can be followed over time.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Utc};
+use git2::{Repository, Sort};
+use serde::Serialize;
+
+use super::release::parse_conventional_subject;
+
+/// Longest subject, in characters, that passes the subject length rule.
+pub const MAX_SUBJECT_LENGTH: usize = 72;
+
+/// Group label for commits without a conventional scope.
+const NO_SCOPE: &str = "(none)";
+
+/// Which rules one commit message passes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MessageQuality {
+    /// The subject parses as a conventional commit with a known type.
+    pub has_type: bool,
+    /// A non-blank body follows the subject.
+    pub has_body: bool,
+    /// The subject is at most [`MAX_SUBJECT_LENGTH`] characters.
+    pub subject_length_ok: bool,
+}
+
+impl MessageQuality {
+    /// Number of rules scored.
+    pub const RULES: usize = 3;
+
+    /// Scores `message`; `types` are the commit types that count as known.
+    pub fn assess(message: &str, types: &[String]) -> Self {
+        let subject = message.lines().next().unwrap_or_default().trim();
+        let has_type = parse_conventional_subject(subject)
+            .is_some_and(|parsed| types.iter().any(|t| *t == parsed.kind));
+        let has_body = message.lines().skip(1).any(|line| !line.trim().is_empty());
+        Self {
+            has_type,
+            has_body,
+            subject_length_ok: !subject.is_empty() && subject.chars().count() <= MAX_SUBJECT_LENGTH,
+        }
+    }
+
+    /// Number of rules passed.
+    pub fn passed(&self) -> usize {
+        [self.has_type, self.has_body, self.subject_length_ok]
+            .iter()
+            .filter(|rule| **rule)
+            .count()
+    }
+}
+
+/// A scored commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoredCommit {
+    /// Author as `Name <email>`.
+    pub author: String,
+    /// Author date.
+    pub date: DateTime<Utc>,
+    /// Conventional scopes named in the subject.
+    pub scopes: Vec<String>,
+    /// The rules the message passes.
+    pub quality: MessageQuality,
+}
+
+/// Scores the non-merge commits reachable from `rev`, newest first, stopping
+/// at the first commit committed before `since`.
+pub fn score_history(
+    repo: &Repository,
+    rev: &str,
+    since: Option<DateTime<Utc>>,
+    types: &[String],
+) -> Result<Vec<ScoredCommit>> {
+    let start = repo
+        .revparse_single(rev)
+        .and_then(|object| object.peel_to_commit())
+        .with_context(|| format!("Failed to resolve revision: {rev}"))?;
+    let mut walk = repo.revwalk().context("Failed to create revwalk")?;
+    walk.set_sorting(Sort::TIME)
+        .context("Failed to sort revwalk")?;
+    walk.push(start.id()).context("Failed to push revision")?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let commit = repo
+            .find_commit(oid.context("Failed to walk history")?)
+            .context("Failed to find commit")?;
+        // The walk is in commit-time order, so the cutoff is on commit time.
+        if since.is_some_and(|since| commit.time().seconds() < since.timestamp()) {
+            break;
+        }
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let author = commit.author();
+        let date = DateTime::from_timestamp(author.when().seconds(), 0).unwrap_or_default();
+        let message = commit.message().unwrap_or_default();
+        let scopes = parse_conventional_subject(message.lines().next().unwrap_or_default())
+            .and_then(|parsed| parsed.scope)
+            .map(|scope| {
+                scope
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        commits.push(ScoredCommit {
+            author: format!(
+                "{} <{}>",
+                author.name().unwrap_or_default(),
+                author.email().unwrap_or_default()
+            ),
+            date,
+            scopes,
+            quality: MessageQuality::assess(message, types),
+        });
+    }
+    Ok(commits)
+}
+
+/// Length of the periods scores are bucketed into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    /// ISO weeks, labelled `2024-W09`.
+    Week,
+    /// Calendar months, labelled `2024-03`.
+    Month,
+    /// Calendar quarters, labelled `2024-Q1`.
+    Quarter,
+    /// Calendar years, labelled `2024`.
+    Year,
+}
+
+impl Period {
+    /// The label of the period containing `date`. Labels sort chronologically.
+    pub fn label(self, date: DateTime<Utc>) -> String {
+        match self {
+            Self::Week => {
+                let week = date.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            Self::Month => format!("{}-{:02}", date.year(), date.month()),
+            Self::Quarter => format!("{}-Q{}", date.year(), date.month0() / 3 + 1),
+            Self::Year => date.year().to_string(),
+        }
+    }
+}
+
+/// What the per-group trends are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum GroupBy {
+    /// Commit author.
+    Author,
+    /// Conventional scope; a commit naming several scopes counts for each.
+    Scope,
+}
+
+/// Rule pass counts over a set of commits.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct QualityTally {
+    /// Commits scored.
+    pub commits: usize,
+    /// Commits with a known conventional type.
+    pub typed: usize,
+    /// Commits with a body.
+    pub with_body: usize,
+    /// Commits whose subject fits the length limit.
+    pub short_subject: usize,
+    /// Percentage of all rules passed across the commits, to one decimal.
+    pub score: f64,
+}
+
+impl QualityTally {
+    fn add(&mut self, quality: &MessageQuality) {
+        self.commits += 1;
+        self.typed += usize::from(quality.has_type);
+        self.with_body += usize::from(quality.has_body);
+        self.short_subject += usize::from(quality.subject_length_ok);
+        let checks = self.commits * MessageQuality::RULES;
+        let passed = self.typed + self.with_body + self.short_subject;
+        let ratio = passed as f64 / checks as f64;
+        self.score = (ratio * 1000.0).round() / 10.0;
+    }
+}
+
+/// Quality in one period.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodQuality {
+    /// Period label, e.g. `2024-03`.
+    pub period: String,
+    /// Rule pass counts in the period.
+    #[serde(flatten)]
+    pub tally: QualityTally,
+}
+
+/// Quality of one author or scope, overall and per period.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GroupQuality {
+    /// Author or scope.
+    pub name: String,
+    /// Rule pass counts over all the group's commits.
+    pub total: QualityTally,
+    /// Per-period counts, oldest first; periods without commits are omitted.
+    pub periods: Vec<PeriodQuality>,
+}
+
+/// Quality trends over a stretch of history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QualityReport {
+    /// Period length.
+    pub period: Period,
+    /// What `groups` are grouped by.
+    pub group_by: GroupBy,
+    /// Rule pass counts over all commits.
+    pub total: QualityTally,
+    /// Per-period counts over all commits, oldest first.
+    pub periods: Vec<PeriodQuality>,
+    /// Per-author or per-scope trends, most commits first.
+    pub groups: Vec<GroupQuality>,
+}
+
+impl QualityReport {
+    /// Buckets `commits` by `period`, overall and per `group_by`.
+    pub fn from_commits(commits: &[ScoredCommit], period: Period, group_by: GroupBy) -> Self {
+        let mut total = QualityTally::default();
+        let mut periods: BTreeMap<String, QualityTally> = BTreeMap::new();
+        let mut groups: BTreeMap<String, (QualityTally, BTreeMap<String, QualityTally>)> =
+            BTreeMap::new();
+
+        for commit in commits {
+            let label = period.label(commit.date);
+            total.add(&commit.quality);
+            periods
+                .entry(label.clone())
+                .or_default()
+                .add(&commit.quality);
+            let names = match group_by {
+                GroupBy::Author => vec![commit.author.clone()],
+                GroupBy::Scope if commit.scopes.is_empty() => vec![NO_SCOPE.to_string()],
+                GroupBy::Scope => commit.scopes.clone(),
+            };
+            for name in names {
+                let (group_total, group_periods) = groups.entry(name).or_default();
+                group_total.add(&commit.quality);
+                group_periods
+                    .entry(label.clone())
+                    .or_default()
+                    .add(&commit.quality);
+            }
+        }
+
+        let mut groups: Vec<GroupQuality> = groups
+            .into_iter()
+            .map(|(name, (total, periods))| GroupQuality {
+                name,
+                total,
+                periods: period_list(periods),
+            })
+            .collect();
+        // Stable, so groups with equal counts stay in name order.
+        groups.sort_by(|a, b| b.total.commits.cmp(&a.total.commits));
+
+        Self {
+            period,
+            group_by,
+            total,
+            periods: period_list(periods),
+            groups,
+        }
+    }
+}
+
+fn period_list(periods: BTreeMap<String, QualityTally>) -> Vec<PeriodQuality> {
+    periods
+        .into_iter()
+        .map(|(period, tally)| PeriodQuality { period, tally })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use git2::{Commit, Signature};
+
+    fn types() -> Vec<String> {
+        ["feat", "fix", "docs"].map(String::from).to_vec()
+    }
+
+    fn scored(author: &str, month: u32, scopes: &[&str], message: &str) -> ScoredCommit {
+        ScoredCommit {
+            author: author.to_string(),
+            date: Utc.with_ymd_and_hms(2024, month, 15, 12, 0, 0).unwrap(),
+            scopes: scopes.iter().map(|s| (*s).to_string()).collect(),
+            quality: MessageQuality::assess(message, &types()),
+        }
+    }
+
+    fn assert_score(tally: &QualityTally, expected: f64) {
+        assert!(
+            (tally.score - expected).abs() < 1e-9,
+            "score {} != {expected}",
+            tally.score
+        );
+    }
+
+    #[test]
+    fn assess_checks_type_body_and_subject_length() {
+        let good = MessageQuality::assess("feat(cli): add stats\n\nWhy it matters.\n", &types());
+        assert_eq!(good.passed(), 3);
+
+        let untyped = MessageQuality::assess("Add stats", &types());
+        assert!(!untyped.has_type && !untyped.has_body && untyped.subject_length_ok);
+
+        let unknown_type = MessageQuality::assess("wip: stats", &types());
+        assert!(!unknown_type.has_type);
+
+        let blank_body = MessageQuality::assess("fix: stats\n\n   \n", &types());
+        assert!(!blank_body.has_body);
+
+        let long = format!("fix: {}", "x".repeat(MAX_SUBJECT_LENGTH));
+        assert!(!MessageQuality::assess(&long, &types()).subject_length_ok);
+    }
+
+    #[test]
+    fn period_labels_sort_chronologically() {
+        let date = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(Period::Week.label(date), "2024-W09");
+        assert_eq!(Period::Month.label(date), "2024-03");
+        assert_eq!(Period::Quarter.label(date), "2024-Q1");
+        assert_eq!(Period::Year.label(date), "2024");
+    }
+
+    #[test]
+    fn report_tracks_trends_per_author() {
+        let commits = [
+            scored("Ann", 3, &["cli"], "feat(cli): add stats\n\nBody.\n"),
+            scored("Bob", 3, &[], "Fix things"),
+            scored("Bob", 2, &[], "fix: things"),
+        ];
+        let report = QualityReport::from_commits(&commits, Period::Month, GroupBy::Author);
+
+        assert_eq!(report.total.commits, 3);
+        assert_score(&report.total, 66.7);
+        let months: Vec<_> = report.periods.iter().map(|p| p.period.as_str()).collect();
+        assert_eq!(months, ["2024-02", "2024-03"]);
+        assert_score(&report.periods[0].tally, 66.7);
+
+        assert_eq!(report.groups[0].name, "Bob");
+        assert_eq!(report.groups[0].total.typed, 1);
+        assert_score(&report.groups[0].periods[1].tally, 33.3);
+        assert_eq!(report.groups[1].name, "Ann");
+        assert_score(&report.groups[1].total, 100.0);
+    }
+
+    #[test]
+    fn report_by_scope_counts_unscoped_commits_separately() {
+        let commits = [
+            scored("Ann", 3, &["cli", "git"], "feat(cli,git): add stats"),
+            scored("Ann", 3, &[], "fix: things"),
+        ];
+        let report = QualityReport::from_commits(&commits, Period::Year, GroupBy::Scope);
+        let names: Vec<_> = report.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["(none)", "cli", "git"]);
+    }
+
+    #[test]
+    fn score_history_skips_merges_and_stops_at_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let tree = {
+            let mut index = repo.index().unwrap();
+            repo.find_tree(index.write_tree().unwrap()).unwrap()
+        };
+        let commit = |message: &str, time: i64, parents: &[&Commit<'_>]| {
+            let sig = Signature::new("Dev", "dev@example.com", &git2::Time::new(time, 0)).unwrap();
+            let oid = repo
+                .commit(None, &sig, &sig, message, &tree, parents)
+                .unwrap();
+            repo.find_commit(oid).unwrap()
+        };
+        let old = commit("fix: old", 1_600_000_000, &[]);
+        let side = commit("feat(api): side\n\nBody.", 1_700_000_000, &[&old]);
+        let main = commit("chore: main", 1_700_086_400, &[&old]);
+        let merge = commit("Merge side", 1_700_172_800, &[&main, &side]);
+        repo.branch("main", &merge, true).unwrap();
+
+        let since = Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap();
+        let commits = score_history(&repo, "main", Some(since), &types()).unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].author, "Dev <dev@example.com>");
+        assert!(!commits[0].quality.has_type);
+        assert_eq!(commits[1].scopes, ["api"]);
+        assert_eq!(commits[1].quality.passed(), 3);
+    }
+}
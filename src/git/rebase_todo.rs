@@ -0,0 +1,282 @@
+//! `git rebase -i` todo lists, for `git rebase plan`.
+//!
+//! [`RebaseTodo::picks`] is the list `git rebase -i` would start from;
+//! [`RebaseTodo::replan`] checks a proposed plan against it — every commit
+//! exactly once, nothing melded into a commit before the first — and
+//! [`RebaseTodo::render`] writes the result in the format git reads.
+
+use std::fmt::Write as _;
+
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use crate::git::SHORT_HASH_LEN;
+
+/// Shortest hash prefix a plan may use to name a commit.
+const MIN_PREFIX_LEN: usize = 4;
+
+/// What the rebase does with a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// Keeps the commit as is.
+    Pick,
+    /// Keeps the commit and edits its message.
+    Reword,
+    /// Melds the commit into the previous one, combining the messages.
+    Squash,
+    /// Melds the commit into the previous one, keeping that one's message.
+    Fixup,
+}
+
+impl Action {
+    /// The todo-list command.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+        }
+    }
+
+    /// Whether the commit is melded into the one before it.
+    const fn melds(self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+/// One step of a proposed plan.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Step {
+    /// What to do with the commit.
+    pub action: Action,
+    /// Hash, or a hash prefix of at least four characters, of the commit.
+    pub commit: String,
+    /// Why the commit is moved, melded, or reworded.
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// One line of a todo list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoEntry {
+    /// What to do with the commit.
+    pub action: Action,
+    /// Full hash of the commit.
+    pub hash: String,
+    /// First line of the commit message.
+    pub subject: String,
+    /// Why the commit is moved, melded, or reworded; empty for none.
+    pub reason: String,
+}
+
+/// A `git rebase -i` todo list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebaseTodo {
+    /// The lines, in the order the rebase applies them.
+    pub entries: Vec<TodoEntry>,
+}
+
+impl RebaseTodo {
+    /// Picks every `(hash, subject)` commit, in the order given (oldest
+    /// first).
+    pub fn picks<'a>(commits: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let entries = commits
+            .into_iter()
+            .map(|(hash, subject)| TodoEntry {
+                action: Action::Pick,
+                hash: hash.to_string(),
+                subject: subject.to_string(),
+                reason: String::new(),
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Rearranges the commits of this list as `steps` say.
+    ///
+    /// Fails unless every commit is named by exactly one step and the first
+    /// step keeps its commit, since there is nothing before it to meld into.
+    pub fn replan(&self, steps: &[Step]) -> Result<Self> {
+        let mut used = vec![false; self.entries.len()];
+        let mut entries = Vec::with_capacity(steps.len());
+        for step in steps {
+            let index = self.find(&step.commit)?;
+            if std::mem::replace(&mut used[index], true) {
+                bail!("The plan names commit {} more than once", step.commit);
+            }
+            let entry = &self.entries[index];
+            entries.push(TodoEntry {
+                action: step.action,
+                hash: entry.hash.clone(),
+                subject: entry.subject.clone(),
+                reason: step.reason.trim().to_string(),
+            });
+        }
+
+        let missing: Vec<&str> = self
+            .entries
+            .iter()
+            .zip(&used)
+            .filter(|(_, used)| !**used)
+            .map(|(entry, _)| short(&entry.hash))
+            .collect();
+        if !missing.is_empty() {
+            bail!("The plan leaves out commit(s) {}", missing.join(", "));
+        }
+        if let Some(first) = entries.first() {
+            if first.action.melds() {
+                bail!(
+                    "The plan starts with '{} {}', which has no earlier commit to meld into",
+                    first.action.as_str(),
+                    short(&first.hash)
+                );
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Whether this list picks the same commits as `other` in the same order,
+    /// so running it would change nothing.
+    #[must_use]
+    pub fn is_noop(&self, other: &Self) -> bool {
+        self.entries.len() == other.entries.len()
+            && self
+                .entries
+                .iter()
+                .zip(&other.entries)
+                .all(|(a, b)| a.action == Action::Pick && a.hash == b.hash)
+    }
+
+    /// Writes the list as `git rebase -i` reads it, each line preceded by
+    /// its reason as a comment.
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for entry in &self.entries {
+            if !entry.reason.is_empty() {
+                for line in entry.reason.lines() {
+                    let _ = writeln!(out, "# {line}");
+                }
+            }
+            let _ = writeln!(
+                out,
+                "{} {} {}",
+                entry.action.as_str(),
+                short(&entry.hash),
+                entry.subject
+            );
+        }
+        out
+    }
+
+    /// The index of the one entry whose hash starts with `prefix`.
+    fn find(&self, prefix: &str) -> Result<usize> {
+        let prefix = prefix.trim();
+        if prefix.len() < MIN_PREFIX_LEN {
+            bail!("The plan names commit '{prefix}', which is too short to identify one");
+        }
+        let mut matches = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.hash.starts_with(prefix))
+            .map(|(index, _)| index);
+        match (matches.next(), matches.next()) {
+            (Some(index), None) => Ok(index),
+            (None, _) => bail!("The plan names commit {prefix}, which is not in the range"),
+            (Some(_), Some(_)) => bail!("The plan names commit {prefix}, which is ambiguous"),
+        }
+    }
+}
+
+/// `hash` shortened to [`SHORT_HASH_LEN`] characters.
+fn short(hash: &str) -> &str {
+    &hash[..SHORT_HASH_LEN.min(hash.len())]
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const A: &str = "aaaaaaaa11111111";
+    const B: &str = "bbbbbbbb22222222";
+    const C: &str = "cccccccc33333333";
+
+    fn todo() -> RebaseTodo {
+        RebaseTodo::picks([
+            (A, "feat: add parser"),
+            (B, "wip"),
+            (C, "fix typo in parser"),
+        ])
+    }
+
+    fn step(action: Action, commit: &str) -> Step {
+        Step {
+            action,
+            commit: commit.to_string(),
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn replan_reorders_and_melds_commits() {
+        let mut steps = vec![
+            step(Action::Reword, "aaaaaaaa"),
+            step(Action::Fixup, "cccc"),
+            step(Action::Pick, "bbbbbbbb"),
+        ];
+        steps[1].reason = "Typo fix for the parser commit".to_string();
+        let plan = todo().replan(&steps).unwrap();
+
+        assert_eq!(
+            plan.render(),
+            "reword aaaaaaaa feat: add parser\n\
+             # Typo fix for the parser commit\n\
+             fixup cccccccc fix typo in parser\n\
+             pick bbbbbbbb wip\n"
+        );
+        assert!(!plan.is_noop(&todo()));
+    }
+
+    #[test]
+    fn replan_rejects_incomplete_or_invalid_plans() {
+        let err = |steps: &[Step]| todo().replan(steps).unwrap_err().to_string();
+
+        assert!(err(&[step(Action::Pick, A), step(Action::Pick, B)]).contains("leaves out"));
+        assert!(err(&[
+            step(Action::Pick, A),
+            step(Action::Pick, A),
+            step(Action::Pick, C)
+        ])
+        .contains("more than once"));
+        assert!(err(&[
+            step(Action::Squash, A),
+            step(Action::Pick, B),
+            step(Action::Pick, C)
+        ])
+        .contains("no earlier commit"));
+        assert!(err(&[step(Action::Pick, "abc")]).contains("too short"));
+        assert!(err(&[step(Action::Pick, "dddd")]).contains("not in the range"));
+    }
+
+    #[test]
+    fn unchanged_plans_are_noops() {
+        let steps = [
+            step(Action::Pick, A),
+            step(Action::Pick, B),
+            step(Action::Pick, C),
+        ];
+        assert!(todo().replan(&steps).unwrap().is_noop(&todo()));
+    }
+
+    #[test]
+    fn actions_deserialize_from_lowercase() {
+        let step: Step = serde_yaml::from_str("action: fixup\ncommit: abcd\n").unwrap();
+        assert_eq!(step.action, Action::Fixup);
+        assert_eq!(step.reason, "");
+    }
+}
@@ -0,0 +1,448 @@
+//! Grouping of commits into release note sections.
+//!
+//! Commits are classified by their conventional commit subject
+//! (`type(scope)!: description`), falling back to the type and scope detected
+//! from the changed files when a subject does not follow the convention.
+//! Breaking changes are pulled into their own section so they lead the notes.
+
+use anyhow::Result;
+
+use crate::git::CommitInfo;
+
+/// Release note sections in display order: (title, commit types).
+///
+/// Types not listed here land in [`OTHER_SECTION`].
+pub const RELEASE_SECTIONS: &[(&str, &[&str])] = &[
+    ("Features", &["feat"]),
+    ("Bug Fixes", &["fix"]),
+    ("Performance", &["perf"]),
+    ("Refactoring", &["refactor"]),
+    ("Documentation", &["docs"]),
+];
+
+/// Section title for breaking changes, listed before every other section.
+pub const BREAKING_SECTION: &str = "Breaking Changes";
+
+/// Section title for commit types without a dedicated section.
+pub const OTHER_SECTION: &str = "Other Changes";
+
+/// The parts of a conventional commit subject line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionalSubject {
+    /// Commit type, lower-cased (e.g. `feat`).
+    pub kind: String,
+    /// Scope inside the parentheses, if any.
+    pub scope: Option<String>,
+    /// Whether the subject carries the `!` breaking-change marker.
+    pub breaking: bool,
+    /// Text after the colon.
+    pub description: String,
+}
+
+/// The `type(scope)!: description` parts of a subject line as written,
+/// before [`parse_conventional_subject`] validates and normalises them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SubjectParts<'a> {
+    /// Text before the optional scope and the first colon; may be empty.
+    pub(crate) kind: &'a str,
+    /// Text inside the parentheses, if any.
+    pub(crate) scope: Option<&'a str>,
+    /// Whether the prefix ends with the `!` breaking-change marker.
+    pub(crate) breaking: bool,
+    /// Text after the first colon.
+    pub(crate) description: &'a str,
+}
+
+/// Splits the first line of `message` into its conventional parts. This is
+/// the one place the subject grammar lives: commit analysis reads the type
+/// from it as is, and [`parse_conventional_subject`] adds validation.
+pub(crate) fn split_conventional_subject(message: &str) -> Option<SubjectParts<'_>> {
+    let subject = message.lines().next()?.trim();
+    let (prefix, description) = subject.split_once(':')?;
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(prefix) => (prefix, true),
+        None => (prefix, false),
+    };
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((kind, rest)) => (kind, Some(rest.strip_suffix(')')?.trim())),
+        None => (prefix, None),
+    };
+    Some(SubjectParts {
+        kind,
+        scope,
+        breaking,
+        description,
+    })
+}
+
+/// Parses the first line of `message` as a conventional commit subject.
+pub fn parse_conventional_subject(message: &str) -> Option<ConventionalSubject> {
+    let parts = split_conventional_subject(message)?;
+    let description = parts.description.trim();
+    if parts.kind.is_empty()
+        || !parts.kind.chars().all(|c| c.is_ascii_alphabetic())
+        || description.is_empty()
+    {
+        return None;
+    }
+    Some(ConventionalSubject {
+        kind: parts.kind.to_ascii_lowercase(),
+        scope: parts.scope.filter(|s| !s.is_empty()).map(str::to_string),
+        breaking: parts.breaking,
+        description: description.to_string(),
+    })
+}
+
+/// One commit as listed in the release notes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseEntry {
+    /// Commit type.
+    pub kind: String,
+    /// Commit scope, if any.
+    pub scope: Option<String>,
+    /// Subject description (without the `type(scope):` prefix).
+    pub description: String,
+    /// Abbreviated commit hash.
+    pub short_hash: String,
+    /// Whether the commit is a breaking change.
+    pub breaking: bool,
+    /// Commit message body, without the subject line.
+    pub body: String,
+}
+
+/// A titled group of release note entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseGroup {
+    /// Section title, e.g. `Features`.
+    pub title: String,
+    /// Entries in commit order (oldest first).
+    pub entries: Vec<ReleaseEntry>,
+}
+
+/// Classifies one commit into a [`ReleaseEntry`].
+pub fn release_entry(commit: &CommitInfo) -> ReleaseEntry {
+    let message = commit.original_message.trim();
+    let body = message
+        .split_once('\n')
+        .map(|(_, body)| body.trim().to_string())
+        .unwrap_or_default();
+    let breaking_footer = body.contains("BREAKING CHANGE:") || body.contains("BREAKING-CHANGE:");
+    let short_hash: String = commit
+        .hash
+        .chars()
+        .take(crate::git::SHORT_HASH_LEN)
+        .collect();
+
+    match parse_conventional_subject(message) {
+        Some(subject) => ReleaseEntry {
+            kind: subject.kind,
+            scope: subject.scope,
+            description: subject.description,
+            short_hash,
+            breaking: subject.breaking || breaking_footer,
+            body,
+        },
+        None => {
+            let scope = &commit.analysis.detected_scope;
+            ReleaseEntry {
+                kind: commit.analysis.detected_type.clone(),
+                scope: (!scope.is_empty()).then(|| scope.clone()),
+                description: message.lines().next().unwrap_or_default().to_string(),
+                short_hash,
+                breaking: breaking_footer,
+                body,
+            }
+        }
+    }
+}
+
+/// Groups commits into release note sections, omitting empty sections.
+///
+/// Breaking changes appear only under [`BREAKING_SECTION`].
+pub fn group_commits(commits: &[CommitInfo]) -> Vec<ReleaseGroup> {
+    let mut groups: Vec<ReleaseGroup> = std::iter::once(BREAKING_SECTION)
+        .chain(RELEASE_SECTIONS.iter().map(|(title, _)| *title))
+        .chain(std::iter::once(OTHER_SECTION))
+        .map(|title| ReleaseGroup {
+            title: title.to_string(),
+            entries: Vec::new(),
+        })
+        .collect();
+
+    for entry in commits.iter().map(release_entry) {
+        let title = if entry.breaking {
+            BREAKING_SECTION
+        } else {
+            section_for_type(&entry.kind)
+        };
+        if let Some(group) = groups.iter_mut().find(|g| g.title == title) {
+            group.entries.push(entry);
+        }
+    }
+    groups.retain(|g| !g.entries.is_empty());
+    groups
+}
+
+/// Returns the section title for a commit type.
+pub fn section_for_type(kind: &str) -> &'static str {
+    RELEASE_SECTIONS
+        .iter()
+        .find(|(_, kinds)| kinds.contains(&kind))
+        .map_or(OTHER_SECTION, |(title, _)| title)
+}
+
+/// Renders groups as markdown: a `###` heading per section and one bullet per
+/// entry, with the scope in bold and the short hash at the end.
+pub fn render_groups_markdown(groups: &[ReleaseGroup]) -> String {
+    let mut out = String::new();
+    for group in groups {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("### {}\n\n", group.title));
+        for entry in &group.entries {
+            let scope = entry
+                .scope
+                .as_deref()
+                .map(|s| format!("**{s}:** "))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "- {scope}{} ({})\n",
+                entry.description, entry.short_hash
+            ));
+        }
+    }
+    out
+}
+
+/// A `MAJOR.MINOR.PATCH` release version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReleaseVersion {
+    /// Major version.
+    pub major: u64,
+    /// Minor version.
+    pub minor: u64,
+    /// Patch version.
+    pub patch: u64,
+}
+
+impl ReleaseVersion {
+    /// Parses `1.2.3` or `v1.2.3`. Pre-release and build suffixes are not
+    /// accepted, so such tags never count as the latest release.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let value = value.strip_prefix('v').unwrap_or(value);
+        let mut parts = value.split('.').map(|p| {
+            (!p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+                .then(|| p.parse::<u64>().ok())
+                .flatten()
+        });
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+        };
+        parts.next().is_none().then_some(version)
+    }
+
+    /// Returns the version after a release of the given level.
+    pub fn bump(self, level: BumpLevel) -> Self {
+        match level {
+            BumpLevel::Major => Self {
+                major: self.major + 1,
+                minor: 0,
+                patch: 0,
+            },
+            BumpLevel::Minor => Self {
+                minor: self.minor + 1,
+                patch: 0,
+                ..self
+            },
+            BumpLevel::Patch => Self {
+                patch: self.patch + 1,
+                ..self
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for ReleaseVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Which part of the version a release increments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BumpLevel {
+    /// Fixes and other compatible changes.
+    Patch,
+    /// New features.
+    Minor,
+    /// Breaking changes.
+    Major,
+}
+
+/// Computes the semantic version bump for `entries` released after
+/// `current`: breaking changes bump the major version (the minor version
+/// while still on `0.x`), features the minor version, anything else the
+/// patch version.
+pub fn bump_level(entries: &[ReleaseEntry], current: ReleaseVersion) -> BumpLevel {
+    let level = entries
+        .iter()
+        .map(|entry| {
+            if entry.breaking {
+                BumpLevel::Major
+            } else if entry.kind == "feat" {
+                BumpLevel::Minor
+            } else {
+                BumpLevel::Patch
+            }
+        })
+        .max()
+        .unwrap_or(BumpLevel::Patch);
+    if level == BumpLevel::Major && current.major == 0 {
+        BumpLevel::Minor
+    } else {
+        level
+    }
+}
+
+/// Returns the tag with the highest release version, if any tag parses as
+/// one.
+pub fn latest_version_tag(repo: &git2::Repository) -> Result<Option<(String, ReleaseVersion)>> {
+    let tags = repo.tag_names(None)?;
+    Ok(tags
+        .iter()
+        .flatten()
+        .filter_map(|tag| ReleaseVersion::parse(tag).map(|v| (tag.to_string(), v)))
+        .max_by_key(|(_, version)| *version))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::commits;
+
+    fn commit(hash: &str, message: &str, detected_type: &str) -> CommitInfo {
+        let mut commit = commits::commit(hash, message, &[]);
+        commit.analysis.detected_type = detected_type.to_string();
+        commit
+    }
+
+    #[test]
+    fn parses_conventional_subjects() {
+        assert_eq!(
+            parse_conventional_subject("feat(git,cli)!: add merge\n\nbody"),
+            Some(ConventionalSubject {
+                kind: "feat".to_string(),
+                scope: Some("git,cli".to_string()),
+                breaking: true,
+                description: "add merge".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_conventional_subject("Fix: typo").map(|s| s.kind),
+            Some("fix".to_string())
+        );
+        assert!(parse_conventional_subject("Merge branch 'main'").is_none());
+        assert!(parse_conventional_subject("see https://example.com").is_none());
+        assert!(parse_conventional_subject("feat(git: broken").is_none());
+    }
+
+    #[test]
+    fn groups_by_section_with_breaking_first() {
+        let commits = vec![
+            commit("aaaaaaaaaa", "fix(cli): handle empty input", "fix"),
+            commit("bbbbbbbbbb", "feat: add notes", "feat"),
+            commit(
+                "cccccccccc",
+                "refactor: drop flag\n\nBREAKING CHANGE: --old is gone",
+                "refactor",
+            ),
+            commit("dddddddddd", "Update README", "docs"),
+            commit("eeeeeeeeee", "chore: bump deps", "chore"),
+        ];
+        let groups = group_commits(&commits);
+        let titles: Vec<&str> = groups.iter().map(|g| g.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Breaking Changes",
+                "Features",
+                "Bug Fixes",
+                "Documentation",
+                "Other Changes"
+            ]
+        );
+        assert_eq!(groups[0].entries[0].description, "drop flag");
+        assert_eq!(groups[3].entries[0].description, "Update README");
+    }
+
+    #[test]
+    fn parses_and_bumps_versions() {
+        let version = ReleaseVersion::parse("v1.2.3").unwrap();
+        assert_eq!(version.to_string(), "1.2.3");
+        assert_eq!(version.bump(BumpLevel::Patch).to_string(), "1.2.4");
+        assert_eq!(version.bump(BumpLevel::Minor).to_string(), "1.3.0");
+        assert_eq!(version.bump(BumpLevel::Major).to_string(), "2.0.0");
+        assert!(ReleaseVersion::parse("1.2").is_none());
+        assert!(ReleaseVersion::parse("1.2.3-rc.1").is_none());
+        assert!(ReleaseVersion::parse("release-1").is_none());
+    }
+
+    #[test]
+    fn computes_bump_level_from_entries() {
+        let v1 = ReleaseVersion::parse("1.0.0").unwrap();
+        let v0 = ReleaseVersion::parse("0.4.0").unwrap();
+        let fix = release_entry(&commit("a", "fix: x", "fix"));
+        let feat = release_entry(&commit("b", "feat: y", "feat"));
+        let breaking = release_entry(&commit("c", "refactor!: z", "refactor"));
+
+        assert_eq!(bump_level(std::slice::from_ref(&fix), v1), BumpLevel::Patch);
+        assert_eq!(
+            bump_level(&[fix.clone(), feat.clone()], v1),
+            BumpLevel::Minor
+        );
+        assert_eq!(
+            bump_level(&[feat.clone(), breaking.clone()], v1),
+            BumpLevel::Major
+        );
+        assert_eq!(bump_level(&[feat, breaking], v0), BumpLevel::Minor);
+        assert_eq!(bump_level(&[], v1), BumpLevel::Patch);
+    }
+
+    #[test]
+    fn finds_latest_version_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        let sig = git2::Signature::now("Dev", "dev@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .unwrap();
+        let object = repo.find_object(oid, None).unwrap();
+        assert_eq!(latest_version_tag(&repo).unwrap(), None);
+        for tag in ["v1.9.0", "v1.10.0", "v2.0.0-rc.1", "nightly"] {
+            repo.tag_lightweight(tag, &object, false).unwrap();
+        }
+        let (tag, version) = latest_version_tag(&repo).unwrap().unwrap();
+        assert_eq!(tag, "v1.10.0");
+        assert_eq!(version.to_string(), "1.10.0");
+    }
+
+    #[test]
+    fn renders_markdown_sections() {
+        let groups = group_commits(&[
+            commit("aaaaaaaaaa", "fix(cli): handle empty input", "fix"),
+            commit("bbbbbbbbbb", "feat: add notes", "feat"),
+        ]);
+        assert_eq!(
+            render_groups_markdown(&groups),
+            "### Features\n\n- add notes (bbbbbbbb)\n\n### Bug Fixes\n\n- **cli:** handle empty input (aaaaaaaa)\n"
+        );
+    }
+}
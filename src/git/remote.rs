@@ -5,7 +5,7 @@ use git2::{BranchType, Repository};
 use serde::{Deserialize, Serialize};
 
 /// Remote repository information.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct RemoteInfo {
     /// Name of the remote (e.g., "origin", "upstream").
     pub name: String,
@@ -0,0 +1,482 @@
+//! Condensed repository map for `explain repo`.
+//!
+//! The map gives a model (or a reader) the shape of a repository without its
+//! contents: the directory layout cut to a chosen depth with the file types
+//! in each directory, the manifests and entry points a newcomer starts from,
+//! the start of the README, and the files that changed most often in recent
+//! history. Generated and vendored files are left out, so lockfiles do not
+//! dominate the hot spots.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use git2::{Repository, Sort};
+use serde::{Deserialize, Serialize};
+
+use super::generated::GeneratedFiles;
+use super::ownership::{area_of, ROOT_AREA};
+
+/// Characters of the README kept in the map.
+const README_EXCERPT_CHARS: usize = 4_000;
+
+/// Entry points listed in the map at most.
+const MAX_ENTRY_POINTS: usize = 40;
+
+/// Directory depth below which a file is not listed as an entry point.
+const MAX_ENTRY_POINT_DEPTH: usize = 4;
+
+/// File types listed per directory.
+const TOP_EXTENSIONS: usize = 3;
+
+/// File names that mark a manifest, a binary or library root, or a
+/// document every contributor should read.
+const ENTRY_POINT_NAMES: &[&str] = &[
+    "Cargo.toml",
+    "main.rs",
+    "lib.rs",
+    "package.json",
+    "index.js",
+    "index.ts",
+    "pyproject.toml",
+    "setup.py",
+    "__main__.py",
+    "main.py",
+    "go.mod",
+    "main.go",
+    "pom.xml",
+    "build.gradle",
+    "main.tf",
+    "Makefile",
+    "Dockerfile",
+    "CONTRIBUTING.md",
+    "ARCHITECTURE.md",
+];
+
+/// README file names, in order of preference.
+const README_NAMES: &[&str] = &["README.md", "README", "README.rst", "README.txt"];
+
+/// How a map is built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoMapOptions {
+    /// Directory depth the layout is grouped at.
+    pub depth: usize,
+    /// Only commits made at or after this time count toward hot spots.
+    pub since: Option<DateTime<Utc>>,
+    /// Hot spots listed at most.
+    pub hot_spots: usize,
+}
+
+/// A condensed map of a repository.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMap {
+    /// Repository name (its directory name).
+    pub name: String,
+    /// Detected ecosystem, e.g. `Rust` or `Node`.
+    pub ecosystem: String,
+    /// Tracked files, not counting generated or vendored ones.
+    pub files: usize,
+    /// Directories at the chosen depth, in path order.
+    pub layout: Vec<LayoutEntry>,
+    /// Manifests, binary and library roots, and contributor documents.
+    pub entry_points: Vec<String>,
+    /// Start of the README, when there is one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    /// Date the hot spots are counted from, when limited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<String>,
+    /// Non-merge commits scanned for hot spots.
+    pub commits: usize,
+    /// Most frequently changed files, most changes first.
+    pub hot_spots: Vec<HotSpot>,
+}
+
+/// One directory of the layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutEntry {
+    /// Directory path, or [`ROOT_AREA`] for the top of the repository.
+    pub dir: String,
+    /// Tracked files in and below the directory.
+    pub files: usize,
+    /// Most common file extensions, most files first.
+    pub extensions: Vec<String>,
+}
+
+/// A file that changed often in recent history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotSpot {
+    /// Path relative to the repository root.
+    pub path: String,
+    /// Commits that changed the file.
+    pub commits: usize,
+    /// Distinct authors of those commits.
+    pub authors: usize,
+    /// Date of the latest change, `YYYY-MM-DD`.
+    pub last_changed: String,
+}
+
+/// Changes seen for one file while walking history.
+#[derive(Debug, Default)]
+struct Churn {
+    commits: usize,
+    authors: HashSet<String>,
+    last: Option<DateTime<Utc>>,
+}
+
+impl RepoMap {
+    /// Builds the map of `repo` from its index and the history of `HEAD`.
+    pub fn build(
+        repo: &Repository,
+        generated: &GeneratedFiles,
+        options: &RepoMapOptions,
+    ) -> Result<Self> {
+        let workdir = repo
+            .workdir()
+            .context("Repository has no working directory")?;
+        let depth = options.depth.max(1);
+
+        let index = repo.index().context("Failed to read the git index")?;
+        let files: Vec<String> = index
+            .iter()
+            .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+            .filter(|file| !generated.is_generated(file))
+            .collect();
+
+        let mut areas: BTreeMap<String, (usize, HashMap<String, usize>)> = BTreeMap::new();
+        for file in &files {
+            let (count, extensions) = areas.entry(area_of(file, depth)).or_default();
+            *count += 1;
+            *extensions.entry(extension_of(file)).or_default() += 1;
+        }
+        let layout = areas
+            .into_iter()
+            .map(|(dir, (files, extensions))| LayoutEntry {
+                dir,
+                files,
+                extensions: top_extensions(extensions),
+            })
+            .collect();
+
+        let mut entry_points: Vec<String> = files
+            .iter()
+            .filter(|file| is_entry_point(file))
+            .cloned()
+            .collect();
+        entry_points.sort_by_key(|file| (file.matches('/').count(), file.clone()));
+        entry_points.truncate(MAX_ENTRY_POINTS);
+
+        let (commits, hot_spots) = hot_spots(repo, workdir, generated, options)?;
+
+        Ok(Self {
+            name: workdir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            ecosystem: format!(
+                "{:?}",
+                crate::claude::context::discovery::detect_ecosystem(workdir)
+            ),
+            files: files.len(),
+            layout,
+            entry_points,
+            readme: read_readme(workdir),
+            since: options
+                .since
+                .map(|since| since.format("%Y-%m-%d").to_string()),
+            commits,
+            hot_spots,
+        })
+    }
+
+    /// Renders the map as markdown, the form the AI and `--no-ai` see.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Repository map: {}\n\n", self.name);
+        out.push_str(&format!("- Ecosystem: {}\n", self.ecosystem));
+        out.push_str(&format!("- Tracked files: {}\n", self.files));
+
+        out.push_str("\n## Layout\n\n");
+        out.push_str("| Directory | Files | Main file types |\n");
+        out.push_str("|-----------|------:|-----------------|\n");
+        for entry in &self.layout {
+            out.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                entry.dir,
+                entry.files,
+                entry.extensions.join(", ")
+            ));
+        }
+
+        if !self.entry_points.is_empty() {
+            out.push_str("\n## Entry points\n\n");
+            for file in &self.entry_points {
+                out.push_str(&format!("- `{file}`\n"));
+            }
+        }
+
+        match &self.since {
+            Some(since) => out.push_str(&format!(
+                "\n## Hot spots since {since} ({} commits)\n\n",
+                self.commits
+            )),
+            None => out.push_str(&format!("\n## Hot spots ({} commits)\n\n", self.commits)),
+        }
+        if self.hot_spots.is_empty() {
+            out.push_str("No changes in this period.\n");
+        } else {
+            out.push_str("| File | Commits | Authors | Last changed |\n");
+            out.push_str("|------|--------:|--------:|--------------|\n");
+            for spot in &self.hot_spots {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} |\n",
+                    spot.path, spot.commits, spot.authors, spot.last_changed
+                ));
+            }
+        }
+
+        if let Some(readme) = &self.readme {
+            out.push_str("\n## README (excerpt)\n\n");
+            out.push_str(readme.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Counts the changes per file in the non-merge history of `HEAD` and
+/// returns the number of commits scanned with the top files.
+fn hot_spots(
+    repo: &Repository,
+    workdir: &Path,
+    generated: &GeneratedFiles,
+    options: &RepoMapOptions,
+) -> Result<(usize, Vec<HotSpot>)> {
+    let head = repo
+        .head()
+        .and_then(|head| head.peel_to_commit())
+        .context("Failed to resolve HEAD")?;
+    let mut walk = repo.revwalk().context("Failed to create revwalk")?;
+    walk.set_sorting(Sort::TIME)
+        .context("Failed to sort revwalk")?;
+    walk.push(head.id()).context("Failed to push HEAD")?;
+
+    let mut commits = 0;
+    let mut churn: HashMap<String, Churn> = HashMap::new();
+    for oid in walk {
+        let commit = repo
+            .find_commit(oid.context("Failed to walk history")?)
+            .context("Failed to find commit")?;
+        if options
+            .since
+            .is_some_and(|since| commit.time().seconds() < since.timestamp())
+        {
+            break;
+        }
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        commits += 1;
+
+        let tree = commit.tree().context("Failed to read commit tree")?;
+        let parent_tree = match commit.parents().next() {
+            Some(parent) => Some(parent.tree().context("Failed to read parent tree")?),
+            None => None,
+        };
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .context("Failed to diff commit")?;
+        let author = commit.author();
+        let email = author.email().unwrap_or_default().to_lowercase();
+        let date = DateTime::from_timestamp(author.when().seconds(), 0).unwrap_or_default();
+        for delta in diff.deltas() {
+            let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+                continue;
+            };
+            let path = path.to_string_lossy().into_owned();
+            if generated.is_generated(&path) {
+                continue;
+            }
+            let entry = churn.entry(path).or_default();
+            entry.commits += 1;
+            entry.authors.insert(email.clone());
+            // The walk is newest first.
+            entry.last.get_or_insert(date);
+        }
+    }
+
+    // Files deleted since are history, not a place to start reading.
+    let mut spots: Vec<HotSpot> = churn
+        .into_iter()
+        .filter(|(path, _)| workdir.join(path).exists())
+        .map(|(path, churn)| HotSpot {
+            path,
+            commits: churn.commits,
+            authors: churn.authors.len(),
+            last_changed: churn
+                .last
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+        })
+        .collect();
+    spots.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then_with(|| b.last_changed.cmp(&a.last_changed))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    spots.truncate(options.hot_spots);
+    Ok((commits, spots))
+}
+
+/// `.rs` for `src/main.rs`; the whole name for extensionless files such as
+/// `Makefile`.
+fn extension_of(file: &str) -> String {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!(".{ext}"),
+        _ => name.to_string(),
+    }
+}
+
+/// The `TOP_EXTENSIONS` most common extensions, ties in name order.
+fn top_extensions(extensions: HashMap<String, usize>) -> Vec<String> {
+    let mut extensions: Vec<(String, usize)> = extensions.into_iter().collect();
+    extensions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    extensions
+        .into_iter()
+        .take(TOP_EXTENSIONS)
+        .map(|(ext, _)| ext)
+        .collect()
+}
+
+/// Whether `file` is a manifest, root, or contributor document shallow
+/// enough to be worth pointing a newcomer at.
+fn is_entry_point(file: &str) -> bool {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    ENTRY_POINT_NAMES.contains(&name) && file.matches('/').count() < MAX_ENTRY_POINT_DEPTH
+}
+
+/// The first [`README_EXCERPT_CHARS`] of the top-level README.
+fn read_readme(workdir: &Path) -> Option<String> {
+    let content = README_NAMES
+        .iter()
+        .find_map(|name| std::fs::read_to_string(workdir.join(name)).ok())?;
+    let excerpt: String = content.chars().take(README_EXCERPT_CHARS).collect();
+    if excerpt.trim().is_empty() {
+        return None;
+    }
+    if excerpt.len() < content.len() {
+        Some(format!("{}\n\n[… truncated]", excerpt.trim_end()))
+    } else {
+        Some(excerpt)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit_files(repo: &Repository, files: &[(&str, &str)], email: &str, time: i64) {
+        let workdir = repo.workdir().unwrap();
+        let mut index = repo.index().unwrap();
+        for (path, content) in files {
+            let file = workdir.join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(&file, content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let name = email.split('@').next().unwrap();
+        let signature = git2::Signature::new(name, email, &git2::Time::new(time, 0)).unwrap();
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "change",
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn extensions_fall_back_to_the_file_name() {
+        assert_eq!(extension_of("src/main.rs"), ".rs");
+        assert_eq!(extension_of("Makefile"), "Makefile");
+        assert_eq!(extension_of("config/.gitignore"), ".gitignore");
+    }
+
+    #[test]
+    fn maps_layout_entry_points_and_hot_spots() {
+        let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tmp");
+        std::fs::create_dir_all(&base).unwrap();
+        let dir = tempfile::tempdir_in(&base).unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let day = 86_400;
+        commit_files(
+            &repo,
+            &[
+                ("Cargo.toml", "[package]\n"),
+                ("Cargo.lock", "# lock\n"),
+                ("README.md", "# Demo\n\nA demo crate.\n"),
+                ("src/main.rs", "fn main() {}\n"),
+                ("src/cli/run.rs", "// run\n"),
+                ("src/cli/args.rs", "// args\n"),
+                ("docs/guide.md", "guide\n"),
+            ],
+            "ann@example.com",
+            1_700_000_000,
+        );
+        commit_files(
+            &repo,
+            &[("src/cli/run.rs", "// run v2\n"), ("Cargo.lock", "# v2\n")],
+            "bob@example.com",
+            1_700_000_000 + day,
+        );
+        commit_files(
+            &repo,
+            &[("src/cli/run.rs", "// run v3\n")],
+            "ann@example.com",
+            1_700_000_000 + 2 * day,
+        );
+
+        let options = RepoMapOptions {
+            depth: 2,
+            since: None,
+            hot_spots: 2,
+        };
+        let map = RepoMap::build(&repo, &GeneratedFiles::builtin(), &options).unwrap();
+
+        assert_eq!(map.ecosystem, "Rust");
+        assert_eq!(map.files, 6, "Cargo.lock is left out");
+        let dirs: Vec<(&str, usize)> = map
+            .layout
+            .iter()
+            .map(|e| (e.dir.as_str(), e.files))
+            .collect();
+        assert_eq!(
+            dirs,
+            vec![(ROOT_AREA, 2), ("docs", 1), ("src", 1), ("src/cli", 2)]
+        );
+        assert_eq!(map.entry_points, vec!["Cargo.toml", "src/main.rs"]);
+        assert!(map.readme.as_deref().unwrap().contains("A demo crate."));
+
+        assert_eq!(map.commits, 3);
+        assert_eq!(map.hot_spots.len(), 2);
+        assert_eq!(map.hot_spots[0].path, "src/cli/run.rs");
+        assert_eq!(map.hot_spots[0].commits, 3);
+        assert_eq!(map.hot_spots[0].authors, 2);
+        assert_eq!(map.hot_spots[0].last_changed, "2023-11-16");
+
+        let markdown = map.to_markdown();
+        assert!(markdown.contains("| `src/cli` | 2 | .rs |"));
+        assert!(markdown.contains("- `src/main.rs`"));
+        assert!(markdown.contains("## Hot spots (3 commits)"));
+    }
+}
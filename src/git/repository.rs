@@ -1,10 +1,13 @@
 //! Git repository operations.
 
+use std::collections::HashMap;
+
 use anyhow::{Context, Result};
 use git2::{Repository, Status};
 use tracing::{debug, error, info};
 
-use crate::git::CommitInfo;
+use crate::git::backend::run_git_cli;
+use crate::git::{CommitFilter, CommitInfo, Git2Backend, GitBackend};
 
 /// Git repository wrapper.
 pub struct GitRepository {
@@ -39,37 +42,7 @@ impl GitRepository {
 
     /// Returns the working directory status.
     pub fn get_working_directory_status(&self) -> Result<WorkingDirectoryStatus> {
-        let statuses = self
-            .repo
-            .statuses(None)
-            .context("Failed to get repository status")?;
-
-        let mut untracked_changes = Vec::new();
-
-        for entry in statuses.iter() {
-            if let Ok(path) = entry.path() {
-                let status_flags = entry.status();
-
-                // Skip ignored files - they should not affect clean status
-                if status_flags.contains(Status::IGNORED) {
-                    continue;
-                }
-
-                let status_str = format_status_flags(status_flags);
-
-                untracked_changes.push(FileStatus {
-                    status: status_str,
-                    file: path.to_string(),
-                });
-            }
-        }
-
-        let clean = untracked_changes.is_empty();
-
-        Ok(WorkingDirectoryStatus {
-            clean,
-            untracked_changes,
-        })
+        self.backend().working_directory_status()
     }
 
     /// Checks if the working directory is clean.
@@ -93,6 +66,11 @@ impl GitRepository {
         &self.repo
     }
 
+    /// Returns the [`GitBackend`] commands reach this repository through.
+    pub fn backend(&self) -> Git2Backend<'_> {
+        Git2Backend::new(&self.repo)
+    }
+
     /// Returns the current branch name.
     pub fn get_current_branch(&self) -> Result<String> {
         let head = self.repo.head().context("Failed to get HEAD reference")?;
@@ -155,6 +133,20 @@ impl GitRepository {
 
     /// Parses a commit range and returns the commits.
     pub fn get_commits_in_range(&self, range: &str) -> Result<Vec<CommitInfo>> {
+        self.get_commits_in_range_reusing(range, &HashMap::new())
+    }
+
+    /// Returns the commits in `range` like [`Self::get_commits_in_range`],
+    /// taking the analysis of each commit whose hash is in `known` from there
+    /// instead of diffing it again.
+    ///
+    /// Only which main branches contain a reused commit is looked up afresh,
+    /// since pushing can change it without changing the hash.
+    pub fn get_commits_in_range_reusing(
+        &self,
+        range: &str,
+        known: &HashMap<String, CommitInfo>,
+    ) -> Result<Vec<CommitInfo>> {
         let mut commits = Vec::new();
 
         // Resolved once per invocation; containment is checked per commit.
@@ -166,9 +158,7 @@ impl GitRepository {
             let commit = head
                 .peel_to_commit()
                 .context("Failed to peel HEAD to commit")?;
-            commits.push(CommitInfo::from_git_commit(
-                &self.repo, &commit, &main_tips,
-            )?);
+            commits.push(self.commit_info(&commit, &main_tips, known)?);
         } else if range.contains("..") {
             // Range format like HEAD~3..HEAD
             let parts: Vec<&str> = range.split("..").collect();
@@ -217,9 +207,7 @@ impl GitRepository {
                     continue;
                 }
 
-                commits.push(CommitInfo::from_git_commit(
-                    &self.repo, &commit, &main_tips,
-                )?);
+                commits.push(self.commit_info(&commit, &main_tips, known)?);
             }
 
             // Reverse to get chronological order (oldest first)
@@ -233,17 +221,190 @@ impl GitRepository {
             let commit = obj
                 .peel_to_commit()
                 .context("Failed to peel object to commit")?;
+            commits.push(self.commit_info(&commit, &main_tips, known)?);
+        }
+
+        Ok(commits)
+    }
+
+    /// Returns the [`CommitInfo`] of `commit`, reusing its analysis from
+    /// `known` when present.
+    fn commit_info(
+        &self,
+        commit: &git2::Commit<'_>,
+        main_tips: &[crate::git::main_branches::MainBranchTip],
+        known: &HashMap<String, CommitInfo>,
+    ) -> Result<CommitInfo> {
+        let Some(saved) = known.get(&commit.id().to_string()) else {
+            return CommitInfo::from_git_commit(&self.repo, commit, main_tips);
+        };
+        Ok(CommitInfo {
+            in_main_branches: crate::git::main_branches::branches_containing(
+                &self.repo,
+                main_tips,
+                commit.id(),
+            )?,
+            ..saved.clone()
+        })
+    }
+
+    /// Returns the non-merge commits that `filter` selects, oldest first.
+    ///
+    /// Walks `range` — a single commit or `A..B`, as in
+    /// [`Self::get_commits_in_range`] — or all of `HEAD`'s history when
+    /// `range` is `None`. Commits are filtered before they are analyzed, and
+    /// the walk stops at [`CommitFilter::limit`], so a narrow filter over a
+    /// long history stays cheap.
+    pub fn get_filtered_commits(
+        &self,
+        range: Option<&str>,
+        filter: &CommitFilter,
+    ) -> Result<Vec<CommitInfo>> {
+        let main_tips = crate::git::main_branches::detect_main_branch_tips(&self.repo)?;
+
+        let oids: Vec<git2::Oid> = match range {
+            Some(spec) if !spec.contains("..") => {
+                let commit = self
+                    .repo
+                    .revparse_single(spec)
+                    .and_then(|obj| obj.peel_to_commit())
+                    .with_context(|| format!("Failed to parse commit: {spec}"))?;
+                vec![commit.id()]
+            }
+            _ => {
+                let mut walker = self.repo.revwalk().context("Failed to create revwalk")?;
+                match range {
+                    Some(range) => walker
+                        .push_range(range)
+                        .with_context(|| format!("Invalid range format: {range}"))?,
+                    None => walker.push_head().context("Failed to walk HEAD")?,
+                }
+                walker
+                    .collect::<std::result::Result<_, _>>()
+                    .context("Failed to get commit OID from walker")?
+            }
+        };
+
+        let mut commits = Vec::new();
+        for oid in oids {
+            if filter.limit.is_some_and(|limit| commits.len() >= limit) {
+                break;
+            }
+            let commit = self
+                .repo
+                .find_commit(oid)
+                .context("Failed to find commit")?;
+            if commit.parent_count() > 1 || !filter.matches(&self.repo, &commit)? {
+                continue;
+            }
             commits.push(CommitInfo::from_git_commit(
                 &self.repo, &commit, &main_tips,
             )?);
         }
+        commits.reverse();
+        Ok(commits)
+    }
+
+    /// Returns the files that conflict when `HEAD` is merged into `base`.
+    ///
+    /// The merge is done in memory, so the working tree and index are left
+    /// untouched. An empty list means the branch merges cleanly.
+    pub fn merge_conflicts(&self, base: &str) -> Result<Vec<String>> {
+        let base_commit = self
+            .repo
+            .revparse_single(base)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Failed to resolve base branch '{base}'"))?;
+        let head_commit = self.repo.head()?.peel_to_commit()?;
+        let index = self
+            .repo
+            .merge_commits(&base_commit, &head_commit, None)
+            .with_context(|| format!("Failed to merge HEAD into '{base}' in memory"))?;
+
+        let mut files: Vec<String> = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = conflict
+                .our
+                .or(conflict.their)
+                .or(conflict.ancestor)
+                .context("Merge conflict has no index entries")?;
+            let path = String::from_utf8_lossy(&entry.path).to_string();
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Returns the files left conflicted in the index by a merge, rebase,
+    /// cherry-pick, or revert in progress, sorted.
+    pub fn conflicted_files(&self) -> Result<Vec<String>> {
+        let index = self.repo.index().context("Failed to read the git index")?;
+        let mut files: Vec<String> = Vec::new();
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            let entry = conflict
+                .our
+                .or(conflict.their)
+                .or(conflict.ancestor)
+                .context("Merge conflict has no index entries")?;
+            let path = String::from_utf8_lossy(&entry.path).to_string();
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
 
+    /// Returns the operation that is in progress and can leave conflicts —
+    /// `merge`, `rebase`, `cherry-pick`, or `revert` — if any.
+    pub fn operation_in_progress(&self) -> Option<&'static str> {
+        use git2::RepositoryState;
+
+        match self.repo.state() {
+            RepositoryState::Merge => Some("merge"),
+            RepositoryState::Rebase
+            | RepositoryState::RebaseInteractive
+            | RepositoryState::RebaseMerge => Some("rebase"),
+            RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
+                Some("cherry-pick")
+            }
+            RepositoryState::Revert | RepositoryState::RevertSequence => Some("revert"),
+            _ => None,
+        }
+    }
+
+    /// Returns the commits with the given hashes, in the order given.
+    ///
+    /// Merge commits are skipped, matching [`Self::get_commits_in_range`].
+    /// Fails when any hash is not present in the local object database.
+    pub fn get_commits_by_hashes(&self, hashes: &[String]) -> Result<Vec<CommitInfo>> {
+        let main_tips = crate::git::main_branches::detect_main_branch_tips(&self.repo)?;
+
+        let mut commits = Vec::new();
+        for hash in hashes {
+            let oid = git2::Oid::from_str(hash)
+                .with_context(|| format!("Invalid commit hash: {hash}"))?;
+            let commit = self
+                .repo
+                .find_commit(oid)
+                .with_context(|| format!("Commit {hash} is not available locally"))?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            commits.push(CommitInfo::from_git_commit(
+                &self.repo, &commit, &main_tips,
+            )?);
+        }
         Ok(commits)
     }
 }
 
 /// Formats git status flags into a string representation.
-fn format_status_flags(flags: Status) -> String {
+pub(super) fn format_status_flags(flags: Status) -> String {
     let mut status = String::new();
 
     if flags.contains(Status::INDEX_NEW) {
@@ -280,22 +441,14 @@ fn format_status_flags(flags: Status) -> String {
 impl GitRepository {
     /// Runs a `git` CLI subcommand in the repository's working directory.
     ///
-    /// Remote operations shell out to the user's `git` rather than using
-    /// libgit2's network transport so they work across all URL schemes (SSH,
-    /// HTTPS) and honour the user's existing authentication configuration
-    /// (`ssh-agent`, `~/.ssh/config`, credential helpers). The vendored libgit2
-    /// lacks a reliable SSH transport on some platforms. See issue #903.
+    /// Remote operations shell out rather than use libgit2's transport; see
+    /// [`run_git_cli`].
     fn run_git(&self, args: &[&str]) -> Result<std::process::Output> {
         let workdir = self
             .repo
             .workdir()
             .context("Cannot run git command: repository has no working directory")?;
-
-        std::process::Command::new("git")
-            .current_dir(workdir)
-            .args(args)
-            .output()
-            .context("Failed to execute git command")
+        run_git_cli(workdir, args)
     }
 
     /// Pushes the current branch to remote.
@@ -305,25 +458,22 @@ impl GitRepository {
             branch_name, remote_name
         );
 
-        // Shell out to `git push` so the push works across all URL schemes and
-        // uses the user's configured authentication. `--set-upstream` records
-        // the tracking branch in the same step. See [`Self::run_git`].
+        // `--set-upstream` records the tracking branch in the same step.
         debug!("Pushing via git CLI to '{}'", remote_name);
-        let output = self.run_git(&["push", "--set-upstream", remote_name, branch_name])?;
-
-        if output.status.success() {
-            info!(
-                "Successfully pushed branch '{}' to remote '{}'",
-                branch_name, remote_name
-            );
-            Ok(())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stderr = stderr.trim();
-            error!("Failed to push branch: {}", stderr);
-            anyhow::bail!(
-                "Failed to push branch '{branch_name}' to remote '{remote_name}': {stderr}"
-            )
+        match self.backend().push(remote_name, &[branch_name], true) {
+            Ok(()) => {
+                info!(
+                    "Successfully pushed branch '{}' to remote '{}'",
+                    branch_name, remote_name
+                );
+                Ok(())
+            }
+            Err(stderr) => {
+                error!("Failed to push branch: {}", stderr);
+                anyhow::bail!(
+                    "Failed to push branch '{branch_name}' to remote '{remote_name}': {stderr}"
+                )
+            }
         }
     }
 
@@ -373,6 +523,168 @@ impl GitRepository {
         }
         Ok(exists)
     }
+
+    /// Deletes a branch from a remote.
+    pub fn delete_remote_branch(&self, branch_name: &str, remote_name: &str) -> Result<()> {
+        info!(
+            "Deleting branch '{}' from remote '{}'",
+            branch_name, remote_name
+        );
+
+        // An empty source deletes the destination ref.
+        let refspec = format!(":refs/heads/{branch_name}");
+        self.backend()
+            .push(remote_name, &[&refspec], false)
+            .map_err(|stderr| {
+                error!("Failed to delete remote branch: {}", stderr);
+                anyhow::anyhow!(
+                    "Failed to delete branch '{branch_name}' from remote '{remote_name}': {stderr}"
+                )
+            })
+    }
+
+    /// Fetches `refspec` (e.g. `refs/pull/12/head`) from a remote, making its
+    /// commits available locally without creating a branch.
+    pub fn fetch_ref(&self, refspec: &str, remote_name: &str) -> Result<()> {
+        info!("Fetching '{}' from remote '{}'", refspec, remote_name);
+
+        // Shell out; see [`Self::run_git`].
+        let output = self.run_git(&["fetch", remote_name, refspec])?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stderr = stderr.trim();
+            anyhow::bail!("Failed to fetch '{refspec}' from remote '{remote_name}': {stderr}")
+        }
+    }
+
+    /// Creates branch `branch_name` at `start`, checks it out, and
+    /// cherry-picks `commits` onto it in order.
+    ///
+    /// On a conflict the cherry-pick is aborted, the previous branch checked
+    /// out again, and the new branch deleted, so a failure leaves the
+    /// repository as it was. The error lists the conflicting files.
+    pub fn cherry_pick_onto(
+        &self,
+        branch_name: &str,
+        start: &str,
+        commits: &[String],
+    ) -> Result<()> {
+        let previous = self.get_current_branch()?;
+        let output = self.run_git(&["checkout", "-b", branch_name, start])?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(
+                "Failed to create branch '{branch_name}' at '{start}': {}",
+                stderr.trim()
+            )
+        }
+
+        let mut args = vec!["cherry-pick"];
+        args.extend(commits.iter().map(String::as_str));
+        let output = self.run_git(&args)?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let conflicts = self
+            .run_git(&["diff", "--name-only", "--diff-filter=U"])
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+            .unwrap_or_default();
+        // Best-effort cleanup; the cherry-pick failure is the error to report.
+        for cleanup in [
+            vec!["cherry-pick", "--abort"],
+            vec!["checkout", previous.as_str()],
+            vec!["branch", "-D", branch_name],
+        ] {
+            if let Err(e) = self.run_git(&cleanup) {
+                error!("Failed to run git {}: {e:#}", cleanup.join(" "));
+            }
+        }
+        if conflicts.is_empty() {
+            anyhow::bail!("Failed to cherry-pick onto '{start}': {stderr}")
+        }
+        anyhow::bail!(
+            "Cherry-picking onto '{start}' conflicts in:\n{}\nBackport the commits by hand.",
+            conflicts
+                .lines()
+                .map(|file| format!("  - {file}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+
+    /// Runs `git rebase -i` over `oldest` and every commit after it up to
+    /// `HEAD`, with `todo` as the todo list instead of the one git would
+    /// open in an editor.
+    ///
+    /// The rebase runs attached to the terminal, so the editors `reword` and
+    /// `squash` open work as usual. When it stops on a conflict it is left in
+    /// progress for the user to continue or abort.
+    pub fn rebase_with_todo(&self, oldest: &str, todo: &str) -> Result<()> {
+        let workdir = self
+            .repo
+            .workdir()
+            .context("Cannot rebase: repository has no working directory")?;
+        let temp_dir = tempfile::tempdir().context("Failed to create a temporary directory")?;
+        let todo_file = temp_dir.path().join("git-rebase-todo");
+        std::fs::write(&todo_file, todo).context("Failed to write the rebase todo list")?;
+
+        let parent = format!("{oldest}^");
+        let upstream = if self.repo.revparse_single(&parent).is_ok() {
+            parent.as_str()
+        } else {
+            "--root"
+        };
+        let status = std::process::Command::new("git")
+            .current_dir(workdir)
+            .args(["rebase", "-i", upstream])
+            .env(
+                "GIT_SEQUENCE_EDITOR",
+                format!("cp '{}'", todo_file.display()),
+            )
+            .status()
+            .context("Failed to execute git rebase")?;
+        if !status.success() {
+            anyhow::bail!(
+                "The rebase stopped before finishing the plan. Resolve it and run \
+                 `git rebase --continue`, or `git rebase --abort` to go back to where you were."
+            );
+        }
+        Ok(())
+    }
+
+    /// Pushes a tag to a remote.
+    pub fn push_tag(&self, tag_name: &str, remote_name: &str) -> Result<()> {
+        info!("Pushing tag '{}' to remote '{}'", tag_name, remote_name);
+
+        let refspec = format!("refs/tags/{tag_name}");
+        self.backend()
+            .push(remote_name, &[&refspec], false)
+            .map_err(|stderr| {
+                error!("Failed to push tag: {}", stderr);
+                anyhow::anyhow!(
+                    "Failed to push tag '{tag_name}' to remote '{remote_name}': {stderr}"
+                )
+            })
+    }
+
+    /// Deletes a tag from a remote.
+    pub fn delete_remote_tag(&self, tag_name: &str, remote_name: &str) -> Result<()> {
+        info!("Deleting tag '{}' from remote '{}'", tag_name, remote_name);
+
+        let refspec = format!(":refs/tags/{tag_name}");
+        self.backend()
+            .push(remote_name, &[&refspec], false)
+            .map_err(|stderr| {
+                anyhow::anyhow!(
+                    "Failed to delete tag '{tag_name}' from remote '{remote_name}': {stderr}"
+                )
+            })
+    }
 }
 
 #[cfg(test)]
@@ -510,6 +822,161 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn commits_by_hashes_preserves_order_and_rejects_unknown() -> Result<()> {
+        let temp_dir = init_tmp_repo();
+        let p = temp_dir.path();
+        std::fs::write(p.join("a.txt"), "a")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "first"]);
+        std::fs::write(p.join("b.txt"), "b")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "second"]);
+
+        let repo = GitRepository::open_at(p)?;
+        let head = repo.repository().head()?.peel_to_commit()?;
+        let parent = head.parent(0)?;
+        let hashes = vec![parent.id().to_string(), head.id().to_string()];
+
+        let commits = repo.get_commits_by_hashes(&hashes)?;
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].hash, hashes[0]);
+        assert_eq!(commits[1].hash, hashes[1]);
+
+        let missing = repo.get_commits_by_hashes(&["1".repeat(40)]);
+        assert!(matches!(&missing, Err(e) if e.to_string().contains("not available locally")));
+        Ok(())
+    }
+
+    #[test]
+    fn merge_conflicts_lists_files_changed_on_both_sides() -> Result<()> {
+        let temp_dir = init_tmp_repo();
+        let p = temp_dir.path();
+        git_in(p, &["checkout", "-b", "main"]);
+        std::fs::write(p.join("a.txt"), "base\n")?;
+        std::fs::write(p.join("b.txt"), "base\n")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "base"]);
+        git_in(p, &["checkout", "-b", "feature"]);
+        std::fs::write(p.join("a.txt"), "feature\n")?;
+        git_in(p, &["commit", "-am", "feature change"]);
+        git_in(p, &["checkout", "main"]);
+        std::fs::write(p.join("a.txt"), "main\n")?;
+        git_in(p, &["commit", "-am", "main change"]);
+        git_in(p, &["checkout", "feature"]);
+
+        let repo = GitRepository::open_at(p)?;
+        assert_eq!(repo.merge_conflicts("main")?, vec!["a.txt".to_string()]);
+        assert!(repo.merge_conflicts("HEAD~1")?.is_empty());
+        assert!(repo.merge_conflicts("no-such-branch").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn conflicted_files_lists_the_index_conflicts_of_a_merge() -> Result<()> {
+        let temp_dir = init_tmp_repo();
+        let p = temp_dir.path();
+        git_in(p, &["checkout", "-b", "main"]);
+        std::fs::write(p.join("a.txt"), "base\n")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "base"]);
+        git_in(p, &["checkout", "-b", "feature"]);
+        std::fs::write(p.join("a.txt"), "feature\n")?;
+        git_in(p, &["commit", "-am", "feature change"]);
+        git_in(p, &["checkout", "main"]);
+        std::fs::write(p.join("a.txt"), "main\n")?;
+        git_in(p, &["commit", "-am", "main change"]);
+
+        let repo = GitRepository::open_at(p)?;
+        assert_eq!(repo.operation_in_progress(), None);
+        assert!(repo.conflicted_files()?.is_empty());
+
+        // The merge stops on the conflict, so it cannot go through `git_in`,
+        // which asserts success.
+        let _ = std::process::Command::new("git")
+            .args(["-c", "commit.gpgsign=false", "merge", "feature"])
+            .current_dir(p)
+            .output()?;
+        let repo = GitRepository::open_at(p)?;
+        assert_eq!(repo.operation_in_progress(), Some("merge"));
+        assert_eq!(repo.conflicted_files()?, vec!["a.txt".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn rebase_with_todo_applies_the_todo_list() -> Result<()> {
+        let temp_dir = init_tmp_repo();
+        let p = temp_dir.path();
+        git_in(p, &["config", "commit.gpgsign", "false"]);
+        git_in(p, &["config", "user.email", "test@example.com"]);
+        git_in(p, &["config", "user.name", "Test"]);
+        std::fs::write(p.join("a.txt"), "a\n")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "add a"]);
+        std::fs::write(p.join("b.txt"), "b\n")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "add b"]);
+        std::fs::write(p.join("b.txt"), "b, fixed\n")?;
+        git_in(p, &["commit", "-am", "fix b"]);
+
+        let repo = GitRepository::open_at(p)?;
+        let commits = repo.get_commits_in_range("HEAD~2..HEAD")?;
+        let todo = format!(
+            "pick {} add b\nfixup {} fix b\n",
+            commits[0].hash, commits[1].hash
+        );
+        repo.rebase_with_todo(&commits[0].hash, &todo)?;
+
+        let repo = GitRepository::open_at(p)?;
+        let head = repo.get_commits_in_range("HEAD")?;
+        assert_eq!(head[0].original_message.trim(), "add b");
+        let parent = repo.get_commits_in_range("HEAD~1")?;
+        assert_eq!(parent[0].original_message.trim(), "add a");
+        assert_eq!(std::fs::read_to_string(p.join("b.txt"))?, "b, fixed\n");
+        assert_eq!(repo.operation_in_progress(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn cherry_pick_onto_applies_commits_and_cleans_up_on_conflict() -> Result<()> {
+        let temp_dir = init_tmp_repo();
+        let p = temp_dir.path();
+        git_in(p, &["config", "user.email", "test@example.com"]);
+        git_in(p, &["config", "user.name", "Test"]);
+        git_in(p, &["config", "commit.gpgsign", "false"]);
+        git_in(p, &["checkout", "-b", "main"]);
+        std::fs::write(p.join("a.txt"), "base\n")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "base"]);
+        git_in(p, &["checkout", "-b", "feature"]);
+        std::fs::write(p.join("a.txt"), "feature\n")?;
+        git_in(p, &["commit", "-am", "change a"]);
+        std::fs::write(p.join("b.txt"), "b\n")?;
+        git_in(p, &["add", "."]);
+        git_in(p, &["commit", "-m", "add b"]);
+        git_in(p, &["checkout", "main"]);
+        std::fs::write(p.join("a.txt"), "main\n")?;
+        git_in(p, &["commit", "-am", "main change"]);
+        git_in(p, &["checkout", "feature"]);
+
+        let repo = GitRepository::open_at(p)?;
+        let add_b = repo.repository().head()?.peel_to_commit()?;
+        let change_a = add_b.parent(0)?;
+
+        let result = repo.cherry_pick_onto("backport-a", "main", &[change_a.id().to_string()]);
+        assert!(
+            matches!(&result, Err(e) if e.to_string().contains("a.txt")),
+            "expected a conflict in a.txt, got: {result:?}"
+        );
+        assert_eq!(repo.get_current_branch()?, "feature");
+        assert!(!repo.branch_exists("backport-a")?);
+
+        repo.cherry_pick_onto("backport-b", "main", &[add_b.id().to_string()])?;
+        assert_eq!(repo.get_current_branch()?, "backport-b");
+        assert!(p.join("b.txt").exists());
+        Ok(())
+    }
+
     // ── remote operations via the git CLI (issue #903) ─────────────
 
     /// Runs `git` in `dir` with a deterministic identity, asserting success.
@@ -730,4 +1197,38 @@ mod tests {
         assert!(commits[0].in_main_branches.is_empty());
         Ok(())
     }
+
+    #[test]
+    fn commits_in_range_reuse_known_analyses_only_for_unchanged_hashes() -> Result<()> {
+        let temp_dir = init_tmp_repo();
+        let p = temp_dir.path();
+        for (file, message) in [("a.txt", "first"), ("b.txt", "second"), ("c.txt", "third")] {
+            std::fs::write(p.join(file), message)?;
+            git_in(p, &["add", "."]);
+            git_in(p, &["commit", "-m", message]);
+        }
+
+        let repo = GitRepository::open_at(p)?;
+        let mut saved = repo.get_commits_in_range("HEAD~2..HEAD")?;
+        for commit in &mut saved {
+            commit.analysis.detected_type = "saved".to_string();
+        }
+        // Rewrite the newest commit; its saved analysis must not be reused.
+        git_in(p, &["commit", "--amend", "-m", "third, reworded"]);
+        let known = saved
+            .into_iter()
+            .map(|commit| (commit.hash.clone(), commit))
+            .collect();
+
+        let commits = repo.get_commits_in_range_reusing("HEAD~2..HEAD", &known)?;
+        let types: Vec<&str> = commits
+            .iter()
+            .map(|c| c.analysis.detected_type.as_str())
+            .collect();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(types[0], "saved");
+        assert_ne!(types[1], "saved");
+        assert_eq!(commits[1].original_message.trim(), "third, reworded");
+        Ok(())
+    }
 }
@@ -0,0 +1,352 @@
+//! Reviewer suggestions for new pull requests.
+//!
+//! `create pr` proposes reviewers from three sources: the `CODEOWNERS` owners
+//! of the files the branch touches (the last matching rule wins, as on
+//! GitHub), the owners of their areas in a saved `git stats owners` report,
+//! then the authors whose lines in those files were most recently changed
+//! according to `git blame`. Forges request reviews by login, so a
+//! blame author only counts when their commit email identifies a GitHub
+//! account (`<id>+<login>@users.noreply.github.com`), and only `@handle` and
+//! `@org/team` owners are taken from `CODEOWNERS`.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher};
+
+use crate::claude::context::codeowners::{find_codeowners, to_globs};
+use crate::git::ownership::OwnershipReport;
+
+/// Maximum number of reviewers suggested from blame.
+pub const MAX_BLAME_REVIEWERS: usize = 3;
+
+/// Maximum number of touched files blamed, bounding the cost on large PRs.
+const MAX_BLAMED_FILES: usize = 20;
+
+/// Blamed lines older than this many seconds (one year) are ignored.
+const RECENT_BLAME_SECS: i64 = 365 * 24 * 60 * 60;
+
+/// Domain of GitHub's no-reply commit email addresses.
+const GITHUB_NOREPLY_DOMAIN: &str = "@users.noreply.github.com";
+
+/// Where a reviewer suggestion came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewerSource {
+    /// An owner of a touched file in `CODEOWNERS`.
+    CodeOwners,
+    /// An owner of a touched file's area in the saved ownership report.
+    Ownership,
+    /// A recent author of touched lines.
+    Blame,
+}
+
+impl fmt::Display for ReviewerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CodeOwners => write!(f, "CODEOWNERS"),
+            Self::Ownership => write!(f, "ownership report"),
+            Self::Blame => write!(f, "recent blame"),
+        }
+    }
+}
+
+/// A suggested pull request reviewer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestedReviewer {
+    /// User login, or `org/team` for a team.
+    pub login: String,
+    /// Why the reviewer was suggested.
+    pub source: ReviewerSource,
+}
+
+/// Suggests reviewers for a branch touching `files` in the repository at
+/// `repo_root`.
+///
+/// Lines last changed by `branch_commits` (the PR's own commits) and by the
+/// configured `user.email` are not counted, and the current user is never
+/// suggested. Failures to read `CODEOWNERS` or the ownership report, or to
+/// blame a file, only reduce the suggestions.
+pub fn suggest_reviewers(
+    repo_root: &Path,
+    files: &[String],
+    branch_commits: &[String],
+) -> Vec<SuggestedReviewer> {
+    let repo = git2::Repository::discover(repo_root).ok();
+    let user_email = repo
+        .as_ref()
+        .and_then(|r| r.config().ok())
+        .and_then(|c| c.get_string("user.email").ok());
+    let user_login = user_email.as_deref().and_then(github_login_from_email);
+
+    let mut suggestions: Vec<SuggestedReviewer> = Vec::new();
+    let mut push = |login: String, source: ReviewerSource| {
+        let is_self = user_login
+            .as_deref()
+            .is_some_and(|user| user.eq_ignore_ascii_case(&login));
+        if !is_self && !suggestions.iter().any(|s| s.login == login) {
+            suggestions.push(SuggestedReviewer { login, source });
+        }
+    };
+
+    let owners_root = repo
+        .as_ref()
+        .and_then(git2::Repository::workdir)
+        .unwrap_or(repo_root);
+    if let Some(content) =
+        find_codeowners(owners_root).and_then(|path| std::fs::read_to_string(path).ok())
+    {
+        for owner in codeowners_for_files(&content, files) {
+            push(owner, ReviewerSource::CodeOwners);
+        }
+    }
+    let context_dir = crate::claude::context::resolve_context_dir_at(None, owners_root);
+    if let Some(report) = OwnershipReport::load(&context_dir) {
+        for login in report
+            .owners_for_files(files)
+            .into_iter()
+            .take(MAX_BLAME_REVIEWERS)
+        {
+            push(login, ReviewerSource::Ownership);
+        }
+    }
+
+    if let Some(repo) = &repo {
+        let now = chrono::Utc::now().timestamp();
+        for login in recent_blame_authors(repo, files, branch_commits, user_email.as_deref(), now)
+            .into_iter()
+            .take(MAX_BLAME_REVIEWERS)
+        {
+            push(login, ReviewerSource::Blame);
+        }
+    }
+
+    suggestions
+}
+
+/// Returns the reviewable owners of `files` according to `CODEOWNERS`
+/// `content`, in order of first appearance, without the leading `@`.
+pub fn codeowners_for_files(content: &str, files: &[String]) -> Vec<String> {
+    let rules = parse_owner_rules(content);
+    let mut owners: Vec<String> = Vec::new();
+    for file in files {
+        let Some((_, rule_owners)) = rules
+            .iter()
+            .rev()
+            .find(|(matchers, _)| matchers.iter().any(|m| m.is_match(file)))
+        else {
+            continue;
+        };
+        for owner in rule_owners {
+            if !owners.contains(owner) {
+                owners.push(owner.clone());
+            }
+        }
+    }
+    owners
+}
+
+/// Parses `CODEOWNERS` content into (pattern matchers, owners) rules in file
+/// order. Email owners are dropped since reviews are requested by login.
+fn parse_owner_rules(content: &str) -> Vec<(Vec<GlobMatcher>, Vec<String>)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with('[') {
+                return None;
+            }
+            let line = line.strip_prefix('^').unwrap_or(line);
+            let mut tokens = line.split_whitespace();
+            let pattern = tokens.next()?;
+            let owners: Vec<String> = tokens
+                .take_while(|t| !t.starts_with('#'))
+                .filter_map(|t| t.strip_prefix('@'))
+                .filter(|t| !t.is_empty())
+                .map(str::to_string)
+                .collect();
+            let matchers = to_globs(pattern)
+                .iter()
+                .filter_map(|glob| Glob::new(glob).ok().map(|g| g.compile_matcher()))
+                .collect();
+            Some((matchers, owners))
+        })
+        .collect()
+}
+
+/// Ranks the GitHub logins behind recently changed lines of `files` by the
+/// number of lines they last touched.
+fn recent_blame_authors(
+    repo: &git2::Repository,
+    files: &[String],
+    branch_commits: &[String],
+    user_email: Option<&str>,
+    now: i64,
+) -> Vec<String> {
+    let mut lines_by_login: HashMap<String, usize> = HashMap::new();
+    let mut authors: HashMap<git2::Oid, Option<String>> = HashMap::new();
+    let skipped: HashSet<&str> = branch_commits.iter().map(String::as_str).collect();
+
+    for file in files.iter().take(MAX_BLAMED_FILES) {
+        // Files added or deleted by the branch have no blame at HEAD worth using.
+        let Ok(blame) = repo.blame_file(Path::new(file), None) else {
+            continue;
+        };
+        for hunk in blame.iter() {
+            let oid = hunk.final_commit_id();
+            if oid.is_zero() || skipped.contains(oid.to_string().as_str()) {
+                continue;
+            }
+            let login = authors.entry(oid).or_insert_with(|| {
+                let commit = repo.find_commit(oid).ok()?;
+                if now - commit.time().seconds() > RECENT_BLAME_SECS {
+                    return None;
+                }
+                let author = commit.author();
+                let email = author.email()?;
+                if user_email.is_some_and(|user| user.eq_ignore_ascii_case(email)) {
+                    return None;
+                }
+                github_login_from_email(email)
+            });
+            if let Some(login) = login {
+                *lines_by_login.entry(login.clone()).or_default() += hunk.lines_in_hunk();
+            }
+        }
+    }
+
+    let mut ranked: Vec<(String, usize)> = lines_by_login.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked.into_iter().map(|(login, _)| login).collect()
+}
+
+/// Extracts the GitHub login from a no-reply commit email, in either the
+/// `<id>+<login>@…` or the older `<login>@…` form.
+pub(crate) fn github_login_from_email(email: &str) -> Option<String> {
+    let split = email.len().checked_sub(GITHUB_NOREPLY_DOMAIN.len())?;
+    if !email.is_char_boundary(split) || !email[split..].eq_ignore_ascii_case(GITHUB_NOREPLY_DOMAIN)
+    {
+        return None;
+    }
+    let local = &email[..split];
+    let login = local.rsplit_once('+').map_or(local, |(_, login)| login);
+    (!login.is_empty()).then(|| login.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn files(paths: &[&str]) -> Vec<String> {
+        paths.iter().map(|p| (*p).to_string()).collect()
+    }
+
+    #[test]
+    fn last_matching_codeowners_rule_wins() {
+        let content = "* @org/everyone\n/src/git/ @alice @bob\n*.md @docs-team\n";
+        assert_eq!(
+            codeowners_for_files(content, &files(&["src/git/reviewers.rs", "README.md"])),
+            vec!["alice", "bob", "docs-team"]
+        );
+        assert_eq!(
+            codeowners_for_files(content, &files(&["Cargo.toml"])),
+            vec!["org/everyone"]
+        );
+    }
+
+    #[test]
+    fn codeowners_skips_emails_and_comments() {
+        let content = "# owners\n/api/ dev@example.com @carol # lead\n";
+        assert_eq!(
+            codeowners_for_files(content, &files(&["api/handler.rs"])),
+            vec!["carol"]
+        );
+        assert!(codeowners_for_files(content, &files(&["web/app.ts"])).is_empty());
+    }
+
+    #[test]
+    fn extracts_login_from_noreply_email() {
+        assert_eq!(
+            github_login_from_email("12345+octocat@users.noreply.github.com").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            github_login_from_email("octocat@users.noreply.github.com").as_deref(),
+            Some("octocat")
+        );
+        assert!(github_login_from_email("octocat@example.com").is_none());
+    }
+
+    #[test]
+    fn suggests_codeowners_and_recent_blame_authors() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "1+me@users.noreply.github.com")
+            .unwrap();
+        std::fs::write(dir.path().join("CODEOWNERS"), "*.rs @lead @me\n").unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let author = git2::Signature::now("Dev", "2+dev@users.noreply.github.com").unwrap();
+        repo.commit(Some("HEAD"), &author, &author, "add lib", &tree, &[])
+            .unwrap();
+
+        let suggestions = suggest_reviewers(dir.path(), &files(&["lib.rs"]), &[]);
+        assert_eq!(
+            suggestions,
+            vec![
+                SuggestedReviewer {
+                    login: "lead".to_string(),
+                    source: ReviewerSource::CodeOwners,
+                },
+                SuggestedReviewer {
+                    login: "dev".to_string(),
+                    source: ReviewerSource::Blame,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn suggests_owners_from_the_saved_ownership_report() {
+        use crate::git::ownership::{AreaOwnership, Owner};
+
+        let dir = tempfile::tempdir().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        let owner = |login: Option<&str>| Owner {
+            name: "Dev".to_string(),
+            email: "dev@example.com".to_string(),
+            login: login.map(str::to_string),
+            score: 50.0,
+            lines: 1,
+            commits: 1,
+            last_commit: None,
+        };
+        let report = OwnershipReport {
+            path: String::new(),
+            depth: 1,
+            areas: vec![AreaOwnership {
+                area: "src".to_string(),
+                files: 1,
+                lines: 2,
+                commits: 2,
+                owners: vec![owner(None), owner(Some("maintainer"))],
+            }],
+        };
+        report.save(&dir.path().join(".omni-dev")).unwrap();
+
+        assert_eq!(
+            suggest_reviewers(dir.path(), &files(&["src/lib.rs"]), &[]),
+            vec![SuggestedReviewer {
+                login: "maintainer".to_string(),
+                source: ReviewerSource::Ownership,
+            }]
+        );
+        assert!(suggest_reviewers(dir.path(), &files(&["README.md"]), &[]).is_empty());
+    }
+}
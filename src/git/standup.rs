@@ -0,0 +1,295 @@
+//! Recent commits of one author, grouped by branch, for `git standup`.
+//!
+//! Every local branch is walked back to the cutoff, and each commit is
+//! reported once. Branches that are not merged into the base branch yet go
+//! first and only contribute the commits they have on top of the base, so
+//! work in progress is told apart from work that landed. The base branch
+//! (local, then remote-tracking) comes next and claims what landed, including
+//! squash merges that exist only upstream. Merged branches come last and
+//! keep what is left.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use git2::{BranchType, Oid, Repository, Sort};
+use serde::{Deserialize, Serialize};
+
+/// The commits an author made on one branch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchActivity {
+    /// Branch name: a local branch, or the remote-tracking base branch.
+    pub branch: String,
+    /// Whether the branch is checked out.
+    pub current: bool,
+    /// Whether the branch is the base branch, e.g. `main` or `origin/main`.
+    pub base: bool,
+    /// Whether the branch tip is already contained in the base branch.
+    pub merged: bool,
+    /// The author's commits, newest first.
+    pub commits: Vec<ActivityCommit>,
+}
+
+/// One commit of a [`BranchActivity`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivityCommit {
+    /// Full commit hash.
+    pub hash: String,
+    /// Author date, `YYYY-MM-DD HH:MM` in UTC.
+    pub date: String,
+    /// First line of the commit message.
+    pub subject: String,
+}
+
+/// A branch and what is known about it before walking it.
+struct BranchTip {
+    name: String,
+    tip: Oid,
+    time: i64,
+    current: bool,
+    base: bool,
+    merged: bool,
+}
+
+/// Collects the non-merge commits authored since `since` by someone whose
+/// name or email contains `author` (case-insensitive), grouped by branch.
+/// `base` names the base branch, e.g. `origin/main`; branches that end up
+/// with no commits are left out.
+pub fn collect_activity(
+    repo: &Repository,
+    base: Option<&str>,
+    author: &str,
+    since: DateTime<Utc>,
+) -> Result<Vec<BranchActivity>> {
+    let base_tip = base
+        .and_then(|base| repo.revparse_single(base).ok())
+        .and_then(|object| object.peel_to_commit().ok())
+        .map(|commit| commit.id());
+    let base_local = base.map(|base| base.rsplit('/').next().unwrap_or(base));
+    let current = repo
+        .head()
+        .ok()
+        .filter(git2::Reference::is_branch)
+        .and_then(|head| head.shorthand().map(str::to_string));
+
+    let mut tips = Vec::new();
+    for branch in repo
+        .branches(Some(BranchType::Local))
+        .context("Failed to list local branches")?
+    {
+        let (branch, _) = branch.context("Failed to read branch")?;
+        let Some(name) = branch.name().ok().flatten().map(str::to_string) else {
+            continue;
+        };
+        let Ok(commit) = branch.get().peel_to_commit() else {
+            continue;
+        };
+        let is_base = base_local == Some(name.as_str());
+        let merged = match base_tip {
+            Some(base_tip) if !is_base => {
+                commit.id() == base_tip
+                    || repo
+                        .graph_descendant_of(base_tip, commit.id())
+                        .unwrap_or(false)
+            }
+            _ => false,
+        };
+        tips.push(BranchTip {
+            current: current.as_deref() == Some(name.as_str()),
+            name,
+            tip: commit.id(),
+            time: commit.time().seconds(),
+            base: is_base,
+            merged,
+        });
+    }
+    if let (Some(base), Some(tip)) = (base, base_tip) {
+        if Some(base) != base_local {
+            tips.push(BranchTip {
+                name: base.to_string(),
+                tip,
+                time: 0,
+                current: false,
+                base: true,
+                merged: false,
+            });
+        }
+    }
+    // Most recently committed first within each group.
+    tips.sort_by(|a, b| {
+        walk_order(a)
+            .cmp(&walk_order(b))
+            .then_with(|| b.time.cmp(&a.time))
+    });
+
+    let needle = author.trim().to_lowercase();
+    let mut claimed: HashSet<Oid> = HashSet::new();
+    let mut activity = Vec::new();
+    for tip in tips {
+        let mut walk = repo.revwalk().context("Failed to create revwalk")?;
+        walk.set_sorting(Sort::TIME)
+            .context("Failed to sort revwalk")?;
+        walk.push(tip.tip).context("Failed to push branch tip")?;
+        if let Some(base_tip) = base_tip.filter(|_| !tip.base && !tip.merged) {
+            walk.hide(base_tip).context("Failed to hide base branch")?;
+        }
+
+        let mut commits = Vec::new();
+        for oid in walk {
+            let oid = oid.context("Failed to walk history")?;
+            let commit = repo.find_commit(oid).context("Failed to find commit")?;
+            // The walk is ordered by committer date, which is never earlier
+            // than the author date of a commit made since the cutoff.
+            if commit.time().seconds() < since.timestamp() {
+                break;
+            }
+            let signature = commit.author();
+            if commit.parent_count() > 1
+                || signature.when().seconds() < since.timestamp()
+                || !matches_author(&signature, &needle)
+                || !claimed.insert(oid)
+            {
+                continue;
+            }
+            commits.push(ActivityCommit {
+                hash: oid.to_string(),
+                date: DateTime::from_timestamp(signature.when().seconds(), 0)
+                    .unwrap_or_default()
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+                subject: commit.summary().unwrap_or_default().trim().to_string(),
+            });
+        }
+        if !commits.is_empty() {
+            activity.push(BranchActivity {
+                branch: tip.name,
+                current: tip.current,
+                base: tip.base,
+                merged: tip.merged,
+                commits,
+            });
+        }
+    }
+    Ok(activity)
+}
+
+/// Unmerged branches first, then the base branch, then merged branches.
+fn walk_order(tip: &BranchTip) -> u8 {
+    match (tip.base, tip.merged) {
+        (false, false) => 0,
+        (true, _) => 1,
+        (false, true) => 2,
+    }
+}
+
+/// Whether the author's name or email contains `needle` (already lowercase).
+fn matches_author(signature: &git2::Signature<'_>, needle: &str) -> bool {
+    [signature.name(), signature.email()]
+        .into_iter()
+        .flatten()
+        .any(|value| value.to_lowercase().contains(needle))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit_on(repo: &Repository, branch: &str, message: &str, email: &str, time: i64) {
+        let parent = repo
+            .find_branch(branch, BranchType::Local)
+            .ok()
+            .and_then(|b| b.get().peel_to_commit().ok());
+        let tree = repo
+            .find_tree(repo.treebuilder(None).unwrap().write().unwrap())
+            .unwrap();
+        let name = email.split('@').next().unwrap();
+        let signature = git2::Signature::new(name, email, &git2::Time::new(time, 0)).unwrap();
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        repo.commit(
+            Some(&format!("refs/heads/{branch}")),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn groups_an_authors_recent_commits_by_branch() {
+        let base = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tmp");
+        std::fs::create_dir_all(&base).unwrap();
+        let dir = tempfile::tempdir_in(&base).unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        let t = 1_700_000_000;
+        commit_on(
+            &repo,
+            "main",
+            "old work",
+            "ann@example.com",
+            t - 10 * 86_400,
+        );
+        commit_on(
+            &repo,
+            "main",
+            "fix typo on main",
+            "ann@example.com",
+            t + 100,
+        );
+        commit_on(&repo, "main", "someone else", "bob@example.com", t + 200);
+        let main_tip = repo
+            .revparse_single("main")
+            .unwrap()
+            .peel_to_commit()
+            .unwrap();
+        repo.branch("feature", &main_tip, false).unwrap();
+        repo.branch("merged", &main_tip, false).unwrap();
+        commit_on(
+            &repo,
+            "feature",
+            "start feature",
+            "ann@example.com",
+            t + 300,
+        );
+        commit_on(
+            &repo,
+            "feature",
+            "continue feature",
+            "Ann@Example.com",
+            t + 400,
+        );
+        repo.set_head("refs/heads/feature").unwrap();
+
+        let since = DateTime::from_timestamp(t, 0).unwrap();
+        let activity = collect_activity(&repo, Some("main"), "ann@", since).unwrap();
+
+        let summary: Vec<(&str, bool, bool, Vec<&str>)> = activity
+            .iter()
+            .map(|a| {
+                (
+                    a.branch.as_str(),
+                    a.current,
+                    a.merged,
+                    a.commits.iter().map(|c| c.subject.as_str()).collect(),
+                )
+            })
+            .collect();
+        // `merged` has nothing of its own: its commits landed on `main`.
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "feature",
+                    true,
+                    false,
+                    vec!["continue feature", "start feature"]
+                ),
+                ("main", false, false, vec!["fix typo on main"]),
+            ]
+        );
+        assert!(activity[1].base);
+    }
+}
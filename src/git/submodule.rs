@@ -0,0 +1,275 @@
+//! Submodule bumps in commits.
+//!
+//! A submodule change shows up in a diff as a single `Subproject commit`
+//! line, which tells the AI nothing about what the bump brings in. This
+//! module finds those changes and, when the submodule is checked out, reads
+//! the commits between the old and new pointers so they can be described.
+
+use std::path::Path;
+
+use git2::{Diff, FileMode, Oid, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::git::SHORT_HASH_LEN;
+
+/// Most upstream commits listed for one submodule change.
+const MAX_LOG_ENTRIES: usize = 20;
+
+/// A submodule pointer change in a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SubmoduleChange {
+    /// Submodule path relative to the repository root.
+    pub path: String,
+    /// Git status code (A=added, D=deleted, M=pointer moved).
+    pub status: String,
+    /// Commit the submodule pointed to before, if it existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_commit: Option<String>,
+    /// Commit the submodule points to after, unless it was removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_commit: Option<String>,
+    /// Submodule URL from `.gitmodules`, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Commits between the two pointers; absent when the submodule is not
+    /// checked out or either commit is missing from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_log: Option<UpstreamLog>,
+}
+
+/// Commits a submodule bump pulls in or drops.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct UpstreamLog {
+    /// Number of commits the new pointer adds.
+    pub added: usize,
+    /// Number of commits the new pointer drops (a rewind).
+    pub removed: usize,
+    /// Up to 20 one-line summaries, newest first, in `git diff --submodule=log`
+    /// style: `> abc12345 subject` for added commits and `<` for dropped ones.
+    pub commits: Vec<String>,
+}
+
+impl SubmoduleChange {
+    /// Formats the change as `git diff --submodule=log` would, for adding
+    /// next to the opaque `Subproject commit` lines of a diff.
+    pub fn log_summary(&self) -> String {
+        let short = |sha: &Option<String>| {
+            sha.as_deref()
+                .map(|sha| sha[..sha.len().min(SHORT_HASH_LEN)].to_string())
+                .unwrap_or_else(|| "0".repeat(SHORT_HASH_LEN))
+        };
+        let mut summary = format!(
+            "Submodule {} {}..{}",
+            self.path,
+            short(&self.old_commit),
+            short(&self.new_commit)
+        );
+        match (&self.upstream_log, self.status.as_str()) {
+            (_, "A") => summary.push_str(" (new submodule)"),
+            (_, "D") => summary.push_str(" (submodule deleted)"),
+            (Some(log), _) => {
+                summary.push_str(&format!(" ({} added, {} removed):", log.added, log.removed));
+                for commit in &log.commits {
+                    summary.push_str(&format!("\n  {commit}"));
+                }
+                let shown = log.commits.len();
+                if log.added + log.removed > shown {
+                    summary.push_str(&format!("\n  … {} more", log.added + log.removed - shown));
+                }
+            }
+            (None, _) => summary.push_str(" (submodule not checked out; no log)"),
+        }
+        summary.push('\n');
+        summary
+    }
+}
+
+/// Finds the submodule changes in `diff`, reading upstream logs from
+/// submodules checked out in `repo`'s working tree.
+pub fn submodule_changes(repo: &Repository, diff: &Diff<'_>) -> Vec<SubmoduleChange> {
+    diff.deltas()
+        .filter(|delta| {
+            delta.new_file().mode() == FileMode::Commit
+                || delta.old_file().mode() == FileMode::Commit
+        })
+        .filter_map(|delta| {
+            let path = delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())?
+                .to_str()?
+                .to_string();
+            let pointer = |file: git2::DiffFile<'_>| {
+                (file.mode() == FileMode::Commit && !file.id().is_zero()).then(|| file.id())
+            };
+            let old = pointer(delta.old_file());
+            let new = pointer(delta.new_file());
+            let status = match (old, new) {
+                (None, Some(_)) => "A",
+                (Some(_), None) => "D",
+                _ => "M",
+            };
+            let upstream_log = match (old, new) {
+                (Some(old), Some(new)) => repo
+                    .workdir()
+                    .and_then(|workdir| upstream_log(&workdir.join(&path), old, new)),
+                _ => None,
+            };
+            let url = repo
+                .find_submodule(&path)
+                .ok()
+                .and_then(|submodule| submodule.url().map(String::from));
+            Some(SubmoduleChange {
+                path,
+                status: status.to_string(),
+                old_commit: old.map(|oid| oid.to_string()),
+                new_commit: new.map(|oid| oid.to_string()),
+                url,
+                upstream_log,
+            })
+        })
+        .collect()
+}
+
+/// Reads the commits between `old` and `new` in the submodule checked out
+/// at `path`.
+fn upstream_log(path: &Path, old: Oid, new: Oid) -> Option<UpstreamLog> {
+    let repo = Repository::open(path).ok()?;
+    repo.find_commit(old).ok()?;
+    repo.find_commit(new).ok()?;
+
+    let mut commits = Vec::new();
+    let mut walk = |from: Oid, hide: Oid, marker: char| -> Option<usize> {
+        let mut revwalk = repo.revwalk().ok()?;
+        revwalk.push(from).ok()?;
+        revwalk.hide(hide).ok()?;
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+            .ok()?;
+        let mut count = 0;
+        for oid in revwalk.flatten() {
+            count += 1;
+            if commits.len() < MAX_LOG_ENTRIES {
+                let summary = repo
+                    .find_commit(oid)
+                    .ok()
+                    .and_then(|commit| commit.summary().map(String::from))
+                    .unwrap_or_default();
+                let sha = oid.to_string();
+                commits.push(format!("{marker} {} {summary}", &sha[..SHORT_HASH_LEN]));
+            }
+        }
+        Some(count)
+    };
+    let added = walk(new, old, '>')?;
+    let removed = walk(old, new, '<')?;
+    Some(UpstreamLog {
+        added,
+        removed,
+        commits,
+    })
+}
+
+/// Adds each change's [`SubmoduleChange::log_summary`] after its
+/// `Subproject commit` line in `diff_content`.
+pub fn annotate_diff(diff_content: &str, changes: &[SubmoduleChange]) -> String {
+    let mut annotated = diff_content.to_string();
+    for change in changes {
+        let marker = match (&change.new_commit, &change.old_commit) {
+            (Some(new), _) => format!("+Subproject commit {new}\n"),
+            (None, Some(old)) => format!("-Subproject commit {old}\n"),
+            (None, None) => continue,
+        };
+        if let Some(index) = annotated.find(&marker) {
+            let end = index + marker.len();
+            annotated.insert_str(end, &change.log_summary());
+        }
+    }
+    annotated
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit(repo: &Repository, message: &str, tree: Oid, parent: Option<Oid>) -> Oid {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree = repo.find_tree(tree).unwrap();
+        let parents: Vec<git2::Commit> = parent
+            .map(|oid| repo.find_commit(oid).unwrap())
+            .into_iter()
+            .collect();
+        let refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &refs)
+            .unwrap()
+    }
+
+    fn tree_with_gitlink(repo: &Repository, target: Oid) -> Oid {
+        let mut vendor = repo.treebuilder(None).unwrap();
+        vendor.insert("lib", target, 0o160_000).unwrap();
+        let vendor = vendor.write().unwrap();
+        let mut root = repo.treebuilder(None).unwrap();
+        root.insert("vendor", vendor, 0o040_000).unwrap();
+        root.write().unwrap()
+    }
+
+    #[test]
+    fn describes_a_bump_with_the_upstream_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let sub = Repository::init(dir.path().join("vendor/lib")).unwrap();
+        let empty = sub.treebuilder(None).unwrap().write().unwrap();
+        let first = commit(&sub, "Initial import", empty, None);
+        let second = commit(&sub, "Fix parser crash", empty, Some(first));
+        let third = commit(&sub, "Add streaming API", empty, Some(second));
+
+        let before = tree_with_gitlink(&repo, first);
+        let after = tree_with_gitlink(&repo, third);
+        let diff = repo
+            .diff_tree_to_tree(
+                Some(&repo.find_tree(before).unwrap()),
+                Some(&repo.find_tree(after).unwrap()),
+                None,
+            )
+            .unwrap();
+
+        let changes = submodule_changes(&repo, &diff);
+        assert_eq!(changes.len(), 1);
+        let change = &changes[0];
+        assert_eq!(change.path, "vendor/lib");
+        assert_eq!(change.status, "M");
+        assert_eq!(change.new_commit, Some(third.to_string()));
+        let log = change.upstream_log.as_ref().unwrap();
+        assert_eq!((log.added, log.removed), (2, 0));
+        assert!(log.commits[0].ends_with(" Add streaming API"));
+        assert!(log.commits[1].starts_with("> "));
+
+        let diff_text = format!("-Subproject commit {first}\n+Subproject commit {third}\n");
+        let annotated = annotate_diff(&diff_text, &changes);
+        assert!(annotated.contains("Submodule vendor/lib "));
+        assert!(annotated.contains("(2 added, 0 removed):\n  > "));
+    }
+
+    #[test]
+    fn bump_without_checkout_has_no_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let old = Oid::from_str("1111111111111111111111111111111111111111").unwrap();
+        let new = Oid::from_str("2222222222222222222222222222222222222222").unwrap();
+        let diff = repo
+            .diff_tree_to_tree(
+                Some(&repo.find_tree(tree_with_gitlink(&repo, old)).unwrap()),
+                Some(&repo.find_tree(tree_with_gitlink(&repo, new)).unwrap()),
+                None,
+            )
+            .unwrap();
+
+        let changes = submodule_changes(&repo, &diff);
+        assert_eq!(changes[0].upstream_log, None);
+        assert_eq!(
+            changes[0].log_summary(),
+            "Submodule vendor/lib 11111111..22222222 (submodule not checked out; no log)\n"
+        );
+    }
+}
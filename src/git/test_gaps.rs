@@ -0,0 +1,395 @@
+//! Test gap analysis for `git commit suggest tests`.
+//!
+//! Finds the functions a diff adds or changes, and the tests that already
+//! exist around them, so that suggested tests name real functions and land
+//! where the project keeps its tests rather than in a generic location.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+
+/// Related test files listed per changed source file.
+const MAX_RELATED: usize = 5;
+
+/// Layout conventions listed.
+const MAX_CONVENTIONS: usize = 5;
+
+/// Directory names that hold tests.
+const TEST_DIRS: &[&str] = &["tests", "test", "__tests__", "spec"];
+
+/// A function a diff defines or changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangedFunction {
+    /// Repository-relative path of the file.
+    pub file: String,
+    /// Function name.
+    pub name: String,
+    /// `true` when the diff adds the definition, `false` when it changes an
+    /// existing function.
+    pub new: bool,
+}
+
+/// Existing tests near one changed source file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RelatedTests {
+    /// The changed source file.
+    pub file: String,
+    /// Test files whose name contains the source file's name.
+    pub tests: Vec<String>,
+    /// The source file carries its own test module (`#[cfg(test)]`).
+    pub inline: bool,
+}
+
+/// How the repository lays out its tests, as far as the changes need.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestLayout {
+    /// Test locations and naming patterns, most used first, with file counts,
+    /// e.g. `tests/ (12 files)`.
+    pub conventions: Vec<String>,
+    /// Existing tests per changed source file.
+    pub related: Vec<RelatedTests>,
+}
+
+/// What a diff changes and which tests already exist around it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestGapAnalysis {
+    /// Functions defined or changed outside test files.
+    pub functions: Vec<ChangedFunction>,
+    /// Test files the diff already touches.
+    pub tests_changed: Vec<String>,
+    /// The repository's test layout.
+    pub layout: TestLayout,
+}
+
+impl TestGapAnalysis {
+    /// Analyses `diff` against the repository at `repo_root`.
+    pub fn from_diff(repo_root: &Path, diff: &str) -> Result<Self> {
+        let files = diff_files(diff);
+        let (tests_changed, sources): (Vec<String>, Vec<String>) =
+            files.into_iter().partition(|f| is_test_path(f));
+        let functions = changed_functions(diff)
+            .into_iter()
+            .filter(|f| !is_test_path(&f.file))
+            .collect();
+        let tracked = tracked_files(repo_root)?;
+        Ok(Self {
+            functions,
+            tests_changed,
+            layout: TestLayout::detect(repo_root, &tracked, &sources),
+        })
+    }
+}
+
+impl TestLayout {
+    /// Derives the layout from the `tracked` files, with related tests for
+    /// each of the changed `sources`.
+    pub fn detect(repo_root: &Path, tracked: &[String], sources: &[String]) -> Self {
+        let tests: Vec<&String> = tracked.iter().filter(|f| is_test_path(f)).collect();
+
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for test in &tests {
+            *counts.entry(convention(test)).or_default() += 1;
+        }
+        let mut conventions: Vec<(String, usize)> = counts.into_iter().collect();
+        conventions.sort_by(|a, b| b.1.cmp(&a.1));
+        let conventions = conventions
+            .into_iter()
+            .take(MAX_CONVENTIONS)
+            .map(|(pattern, count)| format!("{pattern} ({count} files)"))
+            .collect();
+
+        let related = sources
+            .iter()
+            .map(|source| {
+                let stem = file_stem(source);
+                let tests = if stem.chars().count() < 3 {
+                    Vec::new()
+                } else {
+                    tests
+                        .iter()
+                        .filter(|t| file_stem(t).contains(stem))
+                        .take(MAX_RELATED)
+                        .map(|t| (*t).clone())
+                        .collect()
+                };
+                let inline = source.ends_with(".rs")
+                    && std::fs::read_to_string(repo_root.join(source))
+                        .is_ok_and(|content| content.contains("#[cfg(test)]"));
+                RelatedTests {
+                    file: source.clone(),
+                    tests,
+                    inline,
+                }
+            })
+            .collect();
+
+        Self {
+            conventions,
+            related,
+        }
+    }
+}
+
+/// Whether `path` looks like a test file: under a test directory, or named
+/// like one (`test_x.py`, `x_test.go`, `x.test.ts`, `x.spec.js`, `XTest.java`).
+pub fn is_test_path(path: &str) -> bool {
+    let mut parts: Vec<&str> = path.split('/').collect();
+    let name = parts.pop().unwrap_or_default();
+    if parts.iter().any(|dir| TEST_DIRS.contains(dir)) {
+        return true;
+    }
+    let stem = file_stem(name);
+    name.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("_spec")
+        || name.contains(".test.")
+        || name.contains(".spec.")
+        || stem.ends_with("Test")
+        || stem.ends_with("Tests")
+}
+
+/// The location or naming pattern `test` follows, e.g. `tests/` or `*_test.go`.
+fn convention(test: &str) -> String {
+    let parts: Vec<&str> = test.split('/').collect();
+    if let Some(index) = parts[..parts.len() - 1]
+        .iter()
+        .position(|dir| TEST_DIRS.contains(dir))
+    {
+        return format!("{}/", parts[..=index].join("/"));
+    }
+    let name = parts[parts.len() - 1];
+    let extension = name.rsplit_once('.').map_or("", |(_, ext)| ext);
+    let stem = file_stem(name);
+    if name.starts_with("test_") {
+        format!("test_*.{extension}")
+    } else if stem.ends_with("_test") {
+        format!("*_test.{extension}")
+    } else if stem.ends_with("_spec") {
+        format!("*_spec.{extension}")
+    } else if name.contains(".test.") {
+        format!("*.test.{extension}")
+    } else if name.contains(".spec.") {
+        format!("*.spec.{extension}")
+    } else {
+        format!("*Test.{extension}")
+    }
+}
+
+/// The file name of `path` without directories or extensions.
+fn file_stem(path: &str) -> &str {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.split('.').next().unwrap_or(name)
+}
+
+/// Files changed by `diff`, in diff order, from its `+++` lines (or `---`
+/// for deletions).
+fn diff_files(diff: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut old = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("--- ") {
+            old = path.strip_prefix("a/").map(str::to_string);
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let file = path.strip_prefix("b/").map(str::to_string).or(old.take());
+            if let Some(file) = file {
+                if !files.contains(&file) {
+                    files.push(file);
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Function definition patterns for Rust, Python, Go, and JavaScript or
+/// TypeScript; the first capture group is the name.
+fn definition_patterns() -> &'static [Regex] {
+    static RE: OnceLock<Vec<Regex>> = OnceLock::new();
+    #[allow(clippy::expect_used)]
+    RE.get_or_init(|| {
+        [
+            r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?fn\s+([A-Za-z_]\w*)",
+            r"^\s*(?:async\s+)?def\s+([A-Za-z_]\w*)",
+            r"^\s*func\s+(?:\([^)]*\)\s*)?([A-Za-z_]\w*)",
+            r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)",
+        ]
+        .iter()
+        .map(|pattern| Regex::new(pattern).expect("definition pattern must compile"))
+        .collect()
+    })
+}
+
+/// The function `line` defines, if any.
+fn defined_function(line: &str) -> Option<&str> {
+    definition_patterns()
+        .iter()
+        .find_map(|re| re.captures(line))
+        .and_then(|caps| caps.get(1))
+        .map(|name| name.as_str())
+}
+
+/// Functions `diff` defines in added lines, or changes: defined in removed
+/// lines, or named by a hunk header as the function a hunk falls in.
+pub fn changed_functions(diff: &str) -> Vec<ChangedFunction> {
+    let mut functions: Vec<ChangedFunction> = Vec::new();
+    let mut removed: Vec<(String, String)> = Vec::new();
+    let mut file = String::new();
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            file = path.strip_prefix("b/").unwrap_or(path).to_string();
+        } else if line.starts_with("--- ") {
+            continue;
+        } else if let Some(header) = line.strip_prefix("@@") {
+            let context = header.split_once("@@").map_or("", |(_, rest)| rest);
+            if let Some(name) = defined_function(context) {
+                note(&mut functions, &file, name, false);
+            }
+        } else if let Some(added) = line.strip_prefix('+') {
+            if let Some(name) = defined_function(added) {
+                note(&mut functions, &file, name, true);
+            }
+        } else if let Some(deleted) = line.strip_prefix('-') {
+            if let Some(name) = defined_function(deleted) {
+                removed.push((file.clone(), name.to_string()));
+            }
+        }
+    }
+    // A definition both removed and added was rewritten, not introduced.
+    for function in &mut functions {
+        if removed
+            .iter()
+            .any(|(file, name)| *file == function.file && *name == function.name)
+        {
+            function.new = false;
+        }
+    }
+    functions
+}
+
+/// Records `name` in `file`; a function stays new only while every sighting
+/// is an added definition.
+fn note(functions: &mut Vec<ChangedFunction>, file: &str, name: &str, new: bool) {
+    match functions
+        .iter_mut()
+        .find(|f| f.file == file && f.name == name)
+    {
+        Some(existing) => existing.new &= new,
+        None => functions.push(ChangedFunction {
+            file: file.to_string(),
+            name: name.to_string(),
+            new,
+        }),
+    }
+}
+
+/// Paths of the files in the repository's index.
+pub(crate) fn tracked_files(repo_root: &Path) -> Result<Vec<String>> {
+    let repo = git2::Repository::open(repo_root)
+        .with_context(|| format!("Failed to open git repository at {}", repo_root.display()))?;
+    let index = repo.index().context("Failed to read the git index")?;
+    Ok(index
+        .iter()
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+        .collect())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "diff --git a/src/range.rs b/src/range.rs\n\
+        --- a/src/range.rs\n\
+        +++ b/src/range.rs\n\
+        @@ -10,3 +10,9 @@ pub fn parse_range(input: &str) -> Range {\n\
+        -    old();\n\
+        +    new();\n\
+        +}\n\
+        +\n\
+        +pub(crate) async fn merge_ranges(a: Range, b: Range) -> Range {\n\
+        +    a\n\
+        diff --git a/tests/range_test.rs b/tests/range_test.rs\n\
+        --- a/tests/range_test.rs\n\
+        +++ b/tests/range_test.rs\n\
+        @@ -1,1 +1,2 @@\n\
+        +fn helper() {}\n";
+
+    #[test]
+    fn finds_new_and_changed_functions() {
+        let functions = changed_functions(DIFF);
+        let summary: Vec<_> = functions
+            .iter()
+            .map(|f| (f.file.as_str(), f.name.as_str(), f.new))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("src/range.rs", "parse_range", false),
+                ("src/range.rs", "merge_ranges", true),
+                ("tests/range_test.rs", "helper", true),
+            ]
+        );
+        assert_eq!(defined_function("def test_x(self):"), Some("test_x"));
+        assert_eq!(
+            defined_function("func (s *Server) Serve() {"),
+            Some("Serve")
+        );
+        assert_eq!(
+            defined_function("export async function load(url) {"),
+            Some("load")
+        );
+        assert_eq!(defined_function("let f = 1;"), None);
+    }
+
+    #[test]
+    fn recognises_test_paths() {
+        for path in [
+            "tests/cli.rs",
+            "pkg/server/server_test.go",
+            "app/test_views.py",
+            "web/src/api.test.ts",
+            "spec/models/user_spec.rb",
+            "src/test/java/FooTest.java",
+        ] {
+            assert!(is_test_path(path), "{path}");
+        }
+        assert!(!is_test_path("src/testing.rs"));
+        assert!(!is_test_path("src/contest.rs"));
+    }
+
+    #[test]
+    fn layout_counts_conventions_and_relates_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/range.rs"),
+            "fn a() {}\n#[cfg(test)]\nmod tests {}\n",
+        )
+        .unwrap();
+        let tracked: Vec<String> = [
+            "src/range.rs",
+            "tests/range_test.rs",
+            "tests/cli.rs",
+            "web/api.test.ts",
+        ]
+        .map(String::from)
+        .to_vec();
+        let layout = TestLayout::detect(dir.path(), &tracked, &["src/range.rs".to_string()]);
+        assert_eq!(
+            layout.conventions,
+            ["tests/ (2 files)", "*.test.ts (1 files)"]
+        );
+        assert_eq!(layout.related[0].tests, ["tests/range_test.rs"]);
+        assert!(layout.related[0].inline);
+    }
+
+    #[test]
+    fn diff_files_include_deletions() {
+        let diff = "--- a/src/gone.rs\n+++ /dev/null\n--- /dev/null\n+++ b/src/new.rs\n";
+        assert_eq!(diff_files(diff), ["src/gone.rs", "src/new.rs"]);
+    }
+}
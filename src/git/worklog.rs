@@ -0,0 +1,230 @@
+//! Time spent per ticket, estimated from commit history, for
+//! `git worklog export`.
+//!
+//! Commits do not record how long they took, so time is estimated from the
+//! gaps between one author's commits: a commit made within the session gap
+//! of the previous one is credited with the time since it, and the first
+//! commit of a session with a fixed allowance for the work before it. Each
+//! commit's time goes to the ticket its message names or, failing that, the
+//! ticket in the name of the branch it was made on. Time is bucketed per day
+//! in the author's own time zone, as recorded in the commit.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, FixedOffset, Offset, Utc};
+use git2::Repository;
+use regex::Regex;
+use serde::Serialize;
+
+use super::standup::collect_activity;
+
+/// Ticket references recognised by default: Jira-style keys and `#123`
+/// issue numbers.
+pub const DEFAULT_TICKET_PATTERN: &str = r"\b[A-Z][A-Z0-9]+-\d+\b|#\d+\b";
+
+/// Key prefixes that look like Jira keys but name standards, e.g. `UTF-8`.
+const NOT_TICKET_PREFIXES: &[&str] = &[
+    "AES", "CVE", "HTTP", "ISO", "MD", "RFC", "SHA", "TLS", "UTF",
+];
+
+/// How time is estimated.
+#[derive(Debug, Clone)]
+pub struct WorklogOptions {
+    /// Whose commits count (see [`collect_activity`]).
+    pub author: String,
+    /// Commits authored at or after this time count.
+    pub since: DateTime<Utc>,
+    /// Commits authored after this time do not count.
+    pub until: Option<DateTime<Utc>>,
+    /// Longest gap between two commits of the same session.
+    pub session_gap: Duration,
+    /// Time credited to the first commit of a session.
+    pub first_commit: Duration,
+    /// Recognises ticket references in messages and branch names.
+    pub ticket_pattern: Regex,
+}
+
+/// A commit with what time estimation needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkCommit {
+    /// Full commit hash.
+    pub hash: String,
+    /// Author time, in the author's time zone.
+    pub time: DateTime<FixedOffset>,
+    /// Ticket the commit is attributed to.
+    pub ticket: Option<String>,
+}
+
+/// Estimated time on one ticket on one day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorklogEntry {
+    /// Day, `YYYY-MM-DD` in the author's time zone.
+    pub date: String,
+    /// Ticket, or `None` for commits that name none.
+    pub ticket: Option<String>,
+    /// Estimated minutes.
+    pub minutes: i64,
+    /// Commits the time comes from, oldest first.
+    pub commits: Vec<String>,
+}
+
+/// Collects the author's commits in the period from every branch, with the
+/// ticket each one is attributed to, oldest first. `base` names the base
+/// branch, whose name never supplies a ticket.
+pub fn collect_work_commits(
+    repo: &Repository,
+    base: Option<&str>,
+    options: &WorklogOptions,
+) -> Result<Vec<WorkCommit>> {
+    let activity = collect_activity(repo, base, &options.author, options.since)?;
+    let mut commits = Vec::new();
+    for branch in activity {
+        let branch_ticket = if branch.base {
+            None
+        } else {
+            find_ticket(&options.ticket_pattern, &branch.branch)
+        };
+        for entry in branch.commits {
+            let oid = git2::Oid::from_str(&entry.hash).context("Invalid commit hash")?;
+            let commit = repo.find_commit(oid).context("Failed to find commit")?;
+            let when = commit.author().when();
+            let offset =
+                FixedOffset::east_opt(when.offset_minutes() * 60).unwrap_or_else(|| Utc.fix());
+            let Some(time) = DateTime::from_timestamp(when.seconds(), 0) else {
+                continue;
+            };
+            if options.until.is_some_and(|until| time > until) {
+                continue;
+            }
+            commits.push(WorkCommit {
+                hash: entry.hash,
+                time: time.with_timezone(&offset),
+                ticket: find_ticket(
+                    &options.ticket_pattern,
+                    commit.message().unwrap_or_default(),
+                )
+                .or_else(|| branch_ticket.clone()),
+            });
+        }
+    }
+    commits.sort_by_key(|commit| commit.time);
+    Ok(commits)
+}
+
+/// Estimates the time behind `commits` (oldest first) and sums it per day and
+/// ticket, in date order with unattributed time last within a day.
+pub fn estimate(
+    commits: &[WorkCommit],
+    session_gap: Duration,
+    first_commit: Duration,
+) -> Vec<WorklogEntry> {
+    let mut buckets: BTreeMap<(String, Option<String>), (i64, Vec<String>)> = BTreeMap::new();
+    let mut previous: Option<DateTime<FixedOffset>> = None;
+    for commit in commits {
+        let spent = match previous {
+            Some(previous) if commit.time - previous <= session_gap => commit.time - previous,
+            _ => first_commit,
+        };
+        previous = Some(commit.time);
+        let key = (
+            commit.time.format("%Y-%m-%d").to_string(),
+            commit.ticket.clone(),
+        );
+        let (minutes, hashes) = buckets.entry(key).or_default();
+        *minutes += spent.num_minutes();
+        hashes.push(commit.hash.clone());
+    }
+
+    let mut entries: Vec<WorklogEntry> = buckets
+        .into_iter()
+        .map(|((date, ticket), (minutes, commits))| WorklogEntry {
+            date,
+            ticket,
+            minutes,
+            commits,
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.ticket.is_none().cmp(&b.ticket.is_none()))
+            .then_with(|| a.ticket.cmp(&b.ticket))
+    });
+    entries
+}
+
+/// The first ticket reference in `text` that is not a standard's name.
+pub fn find_ticket(pattern: &Regex, text: &str) -> Option<String> {
+    pattern
+        .find_iter(text)
+        .map(|m| m.as_str())
+        .find(|reference| {
+            let prefix = reference.split('-').next().unwrap_or(reference);
+            !NOT_TICKET_PREFIXES.contains(&prefix)
+        })
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn commit(hash: &str, time: &str, ticket: Option<&str>) -> WorkCommit {
+        WorkCommit {
+            hash: hash.to_string(),
+            time: DateTime::parse_from_rfc3339(time).unwrap(),
+            ticket: ticket.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn finds_tickets_but_not_standards() {
+        let pattern = Regex::new(DEFAULT_TICKET_PATTERN).unwrap();
+        assert_eq!(
+            find_ticket(&pattern, "fix(io): read UTF-8 names (PROJ-42)").as_deref(),
+            Some("PROJ-42")
+        );
+        assert_eq!(
+            find_ticket(&pattern, "feature/ABC-7-login").as_deref(),
+            Some("ABC-7")
+        );
+        assert_eq!(find_ticket(&pattern, "Fixes #123").as_deref(), Some("#123"));
+        assert_eq!(find_ticket(&pattern, "hash with SHA-256"), None);
+    }
+
+    #[test]
+    fn credits_gaps_within_a_session_and_an_allowance_for_the_first_commit() {
+        let commits = vec![
+            commit("a", "2026-10-14T09:00:00+02:00", Some("PROJ-1")),
+            commit("b", "2026-10-14T09:45:00+02:00", Some("PROJ-1")),
+            commit("c", "2026-10-14T10:15:00+02:00", None),
+            // Over the session gap: a new session.
+            commit("d", "2026-10-14T15:00:00+02:00", Some("PROJ-2")),
+            commit("e", "2026-10-15T00:30:00+02:00", Some("PROJ-2")),
+        ];
+        let entries = estimate(&commits, Duration::hours(2), Duration::minutes(30));
+
+        let summary: Vec<(&str, Option<&str>, i64, usize)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.date.as_str(),
+                    e.ticket.as_deref(),
+                    e.minutes,
+                    e.commits.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2026-10-14", Some("PROJ-1"), 30 + 45, 2),
+                ("2026-10-14", Some("PROJ-2"), 30, 1),
+                ("2026-10-14", None, 30, 1),
+                ("2026-10-15", Some("PROJ-2"), 30, 1),
+            ]
+        );
+    }
+}
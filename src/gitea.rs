@@ -0,0 +1,670 @@
+//! Gitea and Forgejo pull request support.
+//!
+//! Self-hosted Gitea/Forgejo instances can live on any host, so a remote is
+//! only treated as Gitea when its host matches a known instance: Codeberg
+//! (always) plus the base URLs listed in `GITEA_URL` (comma-separated, from
+//! the environment or the settings.json `env` map). Matching repositories
+//! talk to the instance's `/api/v1` REST API through [`GiteaClient`],
+//! authenticated with the personal access token in `GITEA_TOKEN`.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::data::context::ReviewComment;
+use crate::data::PullRequest;
+use crate::forge::{
+    commit_hashes, find_marked_comment, http_client, owner_and_repo, review_comments_at, send_json,
+    split_commit_message, split_remote_url, split_reviewers, MergeStrategy, PullRequestApi,
+};
+use crate::pr_status::PrCheckState;
+use crate::utils::secret::Secret;
+use crate::utils::settings::Settings;
+
+/// Environment/settings key listing self-hosted instance base URLs.
+pub const GITEA_URL: &str = "GITEA_URL";
+
+/// Environment/settings key for the Gitea/Forgejo access token.
+pub const GITEA_TOKEN: &str = "GITEA_TOKEN";
+
+/// Public Forgejo instance recognised without configuration.
+const CODEBERG_URL: &str = "https://codeberg.org";
+
+/// Page size used when listing pull requests.
+const LIST_PAGE_SIZE: usize = 50;
+
+/// Maximum pages scanned when looking for a branch's pull requests.
+const MAX_LIST_PAGES: usize = 10;
+
+/// Title prefix Gitea treats as "work in progress" (its draft equivalent).
+const WIP_PREFIX: &str = "WIP: ";
+
+/// A repository hosted on a Gitea or Forgejo instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GiteaRepo {
+    /// Instance base URL without a trailing slash (e.g. `https://codeberg.org`).
+    pub base_url: String,
+    /// Repository owner (user or organization).
+    pub owner: String,
+    /// Repository name.
+    pub repo: String,
+}
+
+impl GiteaRepo {
+    /// Parses a git remote URL, returning `None` unless its host matches one
+    /// of `instances` (base URLs, optionally with a sub-path).
+    pub fn from_remote_url(url: &str, instances: &[String]) -> Option<Self> {
+        let (host, path) = split_remote_url(url)?;
+        instances.iter().find_map(|instance| {
+            let parsed = url::Url::parse(instance).ok()?;
+            if !parsed.host_str()?.eq_ignore_ascii_case(host) {
+                return None;
+            }
+            // HTTPS remotes of a sub-path install carry the prefix; SSH ones do not.
+            let prefix = parsed.path().trim_matches('/');
+            let path = if prefix.is_empty() {
+                path
+            } else {
+                path.strip_prefix(prefix)
+                    .and_then(|p| p.strip_prefix('/'))
+                    .unwrap_or(path)
+            };
+            let (owner, repo) = owner_and_repo(path)?;
+            Some(Self {
+                base_url: instance.trim_end_matches('/').to_string(),
+                owner: owner.to_string(),
+                repo: repo.to_string(),
+            })
+        })
+    }
+
+    fn api_path(&self) -> String {
+        format!(
+            "{}/api/v1/repos/{}/{}",
+            self.base_url, self.owner, self.repo
+        )
+    }
+}
+
+/// Returns the known instance base URLs: those configured in `GITEA_URL`
+/// followed by Codeberg.
+pub fn configured_instances() -> Vec<String> {
+    let settings = Settings::load().unwrap_or_default();
+    instances_from(settings.get_env_var(GITEA_URL).as_deref())
+}
+
+fn instances_from(configured: Option<&str>) -> Vec<String> {
+    let mut instances: Vec<String> = configured
+        .unwrap_or_default()
+        .split(',')
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect();
+    if !instances.iter().any(|url| url == CODEBERG_URL) {
+        instances.push(CODEBERG_URL.to_string());
+    }
+    instances
+}
+
+/// Client for the Gitea pull request endpoints of one repository.
+#[derive(Debug, Clone)]
+pub struct GiteaClient {
+    client: reqwest::Client,
+    repo: GiteaRepo,
+    token: Secret,
+}
+
+impl GiteaClient {
+    /// Creates a client for `repo` with the given access token.
+    pub fn new(repo: GiteaRepo, token: impl Into<Secret>) -> Result<Self> {
+        Ok(Self {
+            client: http_client()?,
+            repo,
+            token: token.into(),
+        })
+    }
+
+    /// Creates a client for `repo` using the token in `GITEA_TOKEN`.
+    pub fn from_env(repo: GiteaRepo) -> Result<Self> {
+        let settings = Settings::load().unwrap_or_default();
+        let Some(token) = settings
+            .get_env_var(GITEA_TOKEN)
+            .filter(|t| !t.trim().is_empty())
+        else {
+            bail!(
+                "Gitea credentials not found.\n\
+                 Set {GITEA_TOKEN} to an access token for {} in the environment or \
+                 ~/.omni-dev/settings.json.",
+                repo.base_url
+            );
+        };
+        Self::new(repo, token)
+    }
+
+    /// Returns the repository this client targets.
+    pub fn repo(&self) -> &GiteaRepo {
+        &self.repo
+    }
+
+    async fn request(&self, method: &str, url: &str, body: Option<&Value>) -> Result<Value> {
+        send_json(
+            &self.client,
+            self.forge_name(),
+            method,
+            url,
+            &format!("token {}", self.token.expose_secret()),
+            body,
+        )
+        .await
+    }
+
+    async fn default_branch(&self) -> Result<String> {
+        let repo = self.request("GET", &self.repo.api_path(), None).await?;
+        repo.get("default_branch")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .context("Gitea repository response has no default_branch")
+    }
+}
+
+#[async_trait]
+impl PullRequestApi for GiteaClient {
+    fn forge_name(&self) -> &'static str {
+        "Gitea"
+    }
+
+    async fn verify_access(&self) -> Result<()> {
+        self.request("GET", &self.repo.api_path(), None)
+            .await
+            .map(|_| ())
+            .with_context(|| {
+                format!(
+                    "Gitea API cannot access {}/{} on {}",
+                    self.repo.owner, self.repo.repo, self.repo.base_url
+                )
+            })
+    }
+
+    async fn list_branch_prs(&self, branch: &str) -> Result<Vec<PullRequest>> {
+        // The list endpoint cannot filter by head branch, so scan pages.
+        let mut prs = Vec::new();
+        for page in 1..=MAX_LIST_PAGES {
+            let url = format!(
+                "{}/pulls?state=open&sort=recentupdate&limit={LIST_PAGE_SIZE}&page={page}",
+                self.repo.api_path()
+            );
+            let response = self.request("GET", &url, None).await?;
+            let Some(values) = response.as_array() else {
+                break;
+            };
+            prs.extend(
+                values
+                    .iter()
+                    .filter(|pr| head_branch(pr) == Some(branch))
+                    .filter_map(parse_pull_request),
+            );
+            if values.len() < LIST_PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(prs)
+    }
+
+    async fn latest_branch_pr(&self, branch: &str) -> Result<Option<PullRequest>> {
+        // As in `list_branch_prs`, scan pages; the newest PR comes first.
+        for page in 1..=MAX_LIST_PAGES {
+            let url = format!(
+                "{}/pulls?state=all&sort=newest&limit={LIST_PAGE_SIZE}&page={page}",
+                self.repo.api_path()
+            );
+            let response = self.request("GET", &url, None).await?;
+            let Some(values) = response.as_array() else {
+                break;
+            };
+            if let Some(pr) = values
+                .iter()
+                .filter(|pr| head_branch(pr) == Some(branch))
+                .find_map(parse_pull_request)
+            {
+                return Ok(Some(pr));
+            }
+            if values.len() < LIST_PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    async fn create_pr(
+        &self,
+        source: &str,
+        destination: Option<&str>,
+        title: &str,
+        description: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        let base = match destination {
+            Some(base) => base.to_string(),
+            None => self.default_branch().await?,
+        };
+        let body = json!({
+            "head": source,
+            "base": base,
+            "title": draft_title(title, draft),
+            "body": description,
+        });
+        let response = self
+            .request(
+                "POST",
+                &format!("{}/pulls", self.repo.api_path()),
+                Some(&body),
+            )
+            .await?;
+        parse_pull_request(&response).context("Unexpected response creating Gitea PR")
+    }
+
+    async fn update_pr(
+        &self,
+        number: u64,
+        title: &str,
+        description: &str,
+        destination: Option<&str>,
+    ) -> Result<PullRequest> {
+        let mut body = json!({
+            "title": title,
+            "body": description,
+        });
+        if let Some(base) = destination {
+            body["base"] = json!(base);
+        }
+        let response = self
+            .request(
+                "PATCH",
+                &format!("{}/pulls/{number}", self.repo.api_path()),
+                Some(&body),
+            )
+            .await?;
+        parse_pull_request(&response).context("Unexpected response updating Gitea PR")
+    }
+
+    async fn mark_ready(&self, number: u64) -> Result<()> {
+        let path = format!("{}/pulls/{number}", self.repo.api_path());
+        let pr = self.request("GET", &path, None).await?;
+        let title = pr
+            .get("title")
+            .and_then(Value::as_str)
+            .context("Gitea PR response has no title")?;
+        let ready = ready_title(title);
+        if ready != title {
+            self.request("PATCH", &path, Some(&json!({ "title": ready })))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn request_reviewers(&self, number: u64, reviewers: &[String]) -> Result<()> {
+        let (users, teams) = split_reviewers(reviewers);
+        let body = json!({
+            "reviewers": users,
+            "team_reviewers": teams,
+        });
+        self.request(
+            "POST",
+            &format!(
+                "{}/pulls/{number}/requested_reviewers",
+                self.repo.api_path()
+            ),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn add_labels(&self, number: u64, labels: &[String]) -> Result<()> {
+        let body = json!({ "labels": labels });
+        self.request(
+            "POST",
+            &format!("{}/issues/{number}/labels", self.repo.api_path()),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn pr_commits(&self, number: u64) -> Result<Vec<String>> {
+        let mut hashes = Vec::new();
+        for page in 1..=MAX_LIST_PAGES {
+            let url = format!(
+                "{}/pulls/{number}/commits?limit={LIST_PAGE_SIZE}&page={page}",
+                self.repo.api_path()
+            );
+            let response = self.request("GET", &url, None).await?;
+            let values = response.as_array().map(Vec::as_slice).unwrap_or_default();
+            hashes.extend(commit_hashes(values, "/sha"));
+            if values.len() < LIST_PAGE_SIZE {
+                break;
+            }
+        }
+        Ok(hashes)
+    }
+
+    async fn check_state(&self, number: u64) -> Result<PrCheckState> {
+        let pr = self
+            .request(
+                "GET",
+                &format!("{}/pulls/{number}", self.repo.api_path()),
+                None,
+            )
+            .await?;
+        let sha = pr
+            .pointer("/head/sha")
+            .and_then(Value::as_str)
+            .context("Gitea PR response has no head commit")?;
+        let status = self
+            .request(
+                "GET",
+                &format!("{}/commits/{sha}/status", self.repo.api_path()),
+                None,
+            )
+            .await?;
+        Ok(combined_status_state(&status))
+    }
+
+    async fn merge_pr(
+        &self,
+        number: u64,
+        strategy: MergeStrategy,
+        message: Option<&str>,
+    ) -> Result<()> {
+        let mut body = json!({ "Do": strategy.as_str() });
+        if let Some(message) = message {
+            let (title, description) = split_commit_message(message);
+            body["MergeTitleField"] = json!(title);
+            body["MergeMessageField"] = json!(description);
+        }
+        self.request(
+            "POST",
+            &format!("{}/pulls/{number}/merge", self.repo.api_path()),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn review_comments(&self, number: u64) -> Result<Vec<ReviewComment>> {
+        let api_path = self.repo.api_path();
+        let mut comments = Vec::new();
+        for (endpoint, created_pointer) in [
+            (
+                format!("{api_path}/pulls/{number}/reviews"),
+                "/submitted_at",
+            ),
+            (
+                format!("{api_path}/issues/{number}/comments"),
+                "/created_at",
+            ),
+        ] {
+            let response = self.request("GET", &endpoint, None).await?;
+            let values = response.as_array().map(Vec::as_slice).unwrap_or_default();
+            comments.extend(review_comments_at(
+                values,
+                "/user/login",
+                "/body",
+                None,
+                created_pointer,
+            ));
+        }
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(comments)
+    }
+
+    async fn upsert_pr_comment(&self, number: u64, marker: &str, body: &str) -> Result<()> {
+        // Pull requests share the issue comment endpoints, which return every
+        // comment in one response.
+        let comments_path = format!("{}/issues/{number}/comments", self.repo.api_path());
+        let response = self.request("GET", &comments_path, None).await?;
+        let comments = response.as_array().map(Vec::as_slice).unwrap_or_default();
+        let payload = json!({ "body": body });
+        match find_marked_comment(comments, marker, "/body") {
+            Some(id) => {
+                self.request(
+                    "PATCH",
+                    &format!("{}/issues/comments/{id}", self.repo.api_path()),
+                    Some(&payload),
+                )
+                .await
+            }
+            None => self.request("POST", &comments_path, Some(&payload)).await,
+        }
+        .map(|_| ())
+    }
+
+    async fn create_release(
+        &self,
+        tag: &str,
+        name: &str,
+        notes: &str,
+        prerelease: bool,
+    ) -> Result<String> {
+        let body = json!({
+            "tag_name": tag,
+            "name": name,
+            "body": notes,
+            "prerelease": prerelease,
+        });
+        let release = self
+            .request(
+                "POST",
+                &format!("{}/releases", self.repo.api_path()),
+                Some(&body),
+            )
+            .await?;
+        release
+            .get("html_url")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .context("Gitea release response has no html_url")
+    }
+}
+
+/// Maps a Gitea combined commit status onto [`PrCheckState`]. A warning does
+/// not block, matching how Gitea itself treats it.
+fn combined_status_state(status: &Value) -> PrCheckState {
+    let total = status
+        .get("total_count")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    if total == 0 {
+        return PrCheckState::None;
+    }
+    match status.get("state").and_then(Value::as_str) {
+        Some("success" | "warning") => PrCheckState::Success,
+        Some("failure" | "error") => PrCheckState::Failure,
+        _ => PrCheckState::Pending,
+    }
+}
+
+/// Returns `title` marked as work in progress when `draft` is set.
+fn draft_title(title: &str, draft: bool) -> String {
+    let already_wip = ["WIP:", "[WIP]"]
+        .iter()
+        .any(|prefix| title.to_uppercase().starts_with(prefix));
+    if draft && !already_wip {
+        format!("{WIP_PREFIX}{title}")
+    } else {
+        title.to_string()
+    }
+}
+
+/// Returns `title` without a work-in-progress prefix, which is how Gitea
+/// marks a pull request ready.
+fn ready_title(title: &str) -> String {
+    let upper = title.to_uppercase();
+    ["WIP:", "[WIP]"]
+        .iter()
+        .find(|prefix| upper.starts_with(*prefix))
+        .map_or_else(
+            || title.to_string(),
+            |prefix| title[prefix.len()..].trim_start().to_string(),
+        )
+}
+
+fn head_branch(pr: &Value) -> Option<&str> {
+    pr.pointer("/head/ref").and_then(Value::as_str)
+}
+
+/// Maps a Gitea pull request object onto [`PullRequest`].
+///
+/// Gitea reports merged pull requests as `closed` with `merged: true`; states
+/// are normalised to the upper-case names `gh` reports.
+fn parse_pull_request(pr: &Value) -> Option<PullRequest> {
+    let number = pr.get("number").and_then(Value::as_u64)?;
+    let title = pr.get("title").and_then(Value::as_str)?;
+    let merged = pr.get("merged").and_then(Value::as_bool).unwrap_or(false);
+    let state = match pr.get("state").and_then(Value::as_str) {
+        _ if merged => "MERGED",
+        Some("closed") => "CLOSED",
+        _ => "OPEN",
+    };
+    let url = pr
+        .get("html_url")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let body = pr.get("body").and_then(Value::as_str).unwrap_or_default();
+    let base = pr
+        .pointer("/base/ref")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    Some(PullRequest {
+        number,
+        title: title.to_string(),
+        state: state.to_string(),
+        url: url.to_string(),
+        body: body.to_string(),
+        base: base.to_string(),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn instances() -> Vec<String> {
+        instances_from(Some("https://git.example.com, https://example.org/gitea/"))
+    }
+
+    #[test]
+    fn instances_always_include_codeberg() {
+        assert_eq!(instances_from(None), vec![CODEBERG_URL.to_string()]);
+        assert_eq!(
+            instances(),
+            vec![
+                "https://git.example.com".to_string(),
+                "https://example.org/gitea".to_string(),
+                CODEBERG_URL.to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_remotes_on_known_instances() {
+        let repo =
+            GiteaRepo::from_remote_url("git@git.example.com:team/app.git", &instances()).unwrap();
+        assert_eq!(repo.base_url, "https://git.example.com");
+        assert_eq!((repo.owner.as_str(), repo.repo.as_str()), ("team", "app"));
+        assert_eq!(
+            repo.api_path(),
+            "https://git.example.com/api/v1/repos/team/app"
+        );
+
+        let repo = GiteaRepo::from_remote_url("https://codeberg.org/forgejo/forgejo", &instances())
+            .unwrap();
+        assert_eq!(repo.base_url, CODEBERG_URL);
+    }
+
+    #[test]
+    fn strips_sub_path_prefix_from_https_remotes() {
+        let https =
+            GiteaRepo::from_remote_url("https://example.org/gitea/team/app.git", &instances())
+                .unwrap();
+        let ssh = GiteaRepo::from_remote_url("git@example.org:team/app.git", &instances()).unwrap();
+        assert_eq!(https, ssh);
+        assert_eq!(
+            https.api_path(),
+            "https://example.org/gitea/api/v1/repos/team/app"
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_hosts() {
+        assert!(GiteaRepo::from_remote_url("git@github.com:team/app.git", &instances()).is_none());
+        assert!(GiteaRepo::from_remote_url(
+            "git@git.example.com:team/app.git",
+            &instances_from(None)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn maps_pull_request_states() {
+        let open = json!({
+            "number": 12,
+            "title": "feat: app",
+            "state": "open",
+            "merged": false,
+            "html_url": "https://git.example.com/team/app/pulls/12",
+            "body": "Adds app",
+            "base": { "ref": "main" },
+            "head": { "ref": "feature/app" }
+        });
+        let pr = parse_pull_request(&open).unwrap();
+        assert_eq!(pr.number, 12);
+        assert_eq!(pr.state, "OPEN");
+        assert_eq!(pr.url, "https://git.example.com/team/app/pulls/12");
+        assert_eq!(pr.base, "main");
+        assert_eq!(head_branch(&open), Some("feature/app"));
+
+        let merged = json!({ "number": 3, "title": "x", "state": "closed", "merged": true });
+        assert_eq!(parse_pull_request(&merged).unwrap().state, "MERGED");
+        let closed = json!({ "number": 4, "title": "y", "state": "closed" });
+        assert_eq!(parse_pull_request(&closed).unwrap().state, "CLOSED");
+        assert!(parse_pull_request(&json!({ "title": "no number" })).is_none());
+    }
+
+    #[test]
+    fn draft_prefixes_title_once() {
+        assert_eq!(draft_title("feat: x", true), "WIP: feat: x");
+        assert_eq!(draft_title("WIP: feat: x", true), "WIP: feat: x");
+        assert_eq!(draft_title("[wip] feat: x", true), "[wip] feat: x");
+        assert_eq!(draft_title("feat: x", false), "feat: x");
+    }
+
+    #[test]
+    fn ready_strips_wip_prefix() {
+        assert_eq!(ready_title("WIP: feat: x"), "feat: x");
+        assert_eq!(ready_title("[wip] feat: x"), "feat: x");
+        assert_eq!(ready_title("feat: x"), "feat: x");
+    }
+
+    #[test]
+    fn maps_combined_commit_status() {
+        assert_eq!(
+            combined_status_state(&json!({ "state": "", "total_count": 0 })),
+            PrCheckState::None
+        );
+        assert_eq!(
+            combined_status_state(&json!({ "state": "warning", "total_count": 2 })),
+            PrCheckState::Success
+        );
+        assert_eq!(
+            combined_status_state(&json!({ "state": "error", "total_count": 1 })),
+            PrCheckState::Failure
+        );
+        assert_eq!(
+            combined_status_state(&json!({ "state": "pending", "total_count": 1 })),
+            PrCheckState::Pending
+        );
+    }
+}
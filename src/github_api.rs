@@ -0,0 +1,789 @@
+//! Native GitHub REST client for pull request operations.
+//!
+//! When a GitHub token is available, `create pr` and `branch info` talk to
+//! the GitHub REST API directly through [`GitHubClient`] rather than spawning
+//! `gh`, so the `gh` binary is no longer a hard runtime dependency and API
+//! failures surface GitHub's own error message. The token is resolved from
+//! `GH_TOKEN` / `GITHUB_TOKEN` (environment first, then the settings.json
+//! `env` map), then from the `oauth_token` the `gh` CLI stores in its
+//! `hosts.yml`. When none is found — e.g. `gh` keeps its token in the system
+//! keyring — the `gh` subprocess remains the fallback.
+//!
+//! GitHub Enterprise Server remotes are recognised when their host is set in
+//! `GH_HOST` (the variable `gh` itself reads). Their API lives at
+//! `https://<host>/api/v3` unless `GITHUB_API_URL` overrides it, and their
+//! token comes from `GH_ENTERPRISE_TOKEN` / `GITHUB_ENTERPRISE_TOKEN` or the
+//! host's entry in `hosts.yml`, mirroring `gh`.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::data::context::ReviewComment;
+use crate::data::PullRequest;
+use crate::forge::{
+    commit_hashes, find_marked_comment, http_client, owner_and_repo, review_comments_at, send_json,
+    split_commit_message, split_remote_url, split_reviewers, MergeStrategy, PullRequestApi,
+};
+use crate::pr_status::{rollup_check_state, PrCheckState};
+use crate::utils::secret::Secret;
+use crate::utils::settings::Settings;
+
+/// Environment/settings key for a GitHub token (checked first, as `gh` does).
+pub const GH_TOKEN: &str = "GH_TOKEN";
+
+/// Environment/settings key for a GitHub token.
+pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+/// Environment/settings key for a GitHub Enterprise token (checked first).
+pub const GH_ENTERPRISE_TOKEN: &str = "GH_ENTERPRISE_TOKEN";
+
+/// Environment/settings key for a GitHub Enterprise token.
+pub const GITHUB_ENTERPRISE_TOKEN: &str = "GITHUB_ENTERPRISE_TOKEN";
+
+/// Environment/settings key naming the GitHub Enterprise host.
+pub const GH_HOST: &str = "GH_HOST";
+
+/// Environment/settings key overriding the Enterprise REST API base URL.
+pub const GITHUB_API_URL: &str = "GITHUB_API_URL";
+
+/// Environment variable overriding the `gh` configuration directory.
+const GH_CONFIG_DIR: &str = "GH_CONFIG_DIR";
+
+/// Base URL of the github.com REST API.
+const API_BASE: &str = "https://api.github.com";
+
+/// Host name identifying github.com remotes.
+pub const GITHUB_HOST: &str = "github.com";
+
+/// Comments fetched per page when looking for an existing comment.
+const COMMENT_PAGE_SIZE: usize = 100;
+
+/// Upper bound on comment pages scanned, so a huge thread cannot loop for long.
+const MAX_COMMENT_PAGES: usize = 10;
+
+/// A repository hosted on github.com or a GitHub Enterprise Server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHubRepo {
+    /// Host name, e.g. `github.com` or `github.example.com`.
+    pub host: String,
+    /// Repository owner (user or organization).
+    pub owner: String,
+    /// Repository name.
+    pub repo: String,
+}
+
+impl GitHubRepo {
+    /// Parses a git remote URL, returning `None` unless it points at
+    /// `github.com` or `enterprise_host`.
+    pub fn from_remote_url(url: &str, enterprise_host: Option<&str>) -> Option<Self> {
+        let (host, path) = split_remote_url(url)?;
+        let known = host.eq_ignore_ascii_case(GITHUB_HOST)
+            || enterprise_host.is_some_and(|h| host.eq_ignore_ascii_case(h));
+        if !known {
+            return None;
+        }
+        let (owner, repo) = owner_and_repo(path)?;
+        Some(Self {
+            host: host.to_lowercase(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Returns whether the repository lives on github.com.
+    pub fn is_github_com(&self) -> bool {
+        self.host == GITHUB_HOST
+    }
+}
+
+/// Returns the GitHub Enterprise host configured in `GH_HOST`, or `None`
+/// when unset or set to `github.com`.
+pub fn configured_host() -> Option<String> {
+    let settings = Settings::load().unwrap_or_default();
+    enterprise_host_from(settings.get_env_var(GH_HOST).as_deref())
+}
+
+fn enterprise_host_from(configured: Option<&str>) -> Option<String> {
+    let host = configured?.trim();
+    // `gh` accepts a bare host; tolerate a pasted URL as well.
+    let host = host.split_once("://").map_or(host, |(_, rest)| rest);
+    let host = host.trim_end_matches('/').to_lowercase();
+    (!host.is_empty() && host != GITHUB_HOST).then_some(host)
+}
+
+/// Returns the REST API base URL for `host`: `api.github.com` for
+/// github.com, otherwise `configured` (from `GITHUB_API_URL`) or the
+/// Enterprise Server default of `https://<host>/api/v3`.
+fn api_base_for(host: &str, configured: Option<&str>) -> String {
+    if host == GITHUB_HOST {
+        return API_BASE.to_string();
+    }
+    configured
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .map_or_else(|| format!("https://{host}/api/v3"), str::to_string)
+}
+
+/// Returns the GraphQL endpoint paired with a REST API base URL; Enterprise
+/// Server serves it at `/api/graphql` beside `/api/v3`.
+fn graphql_url_for(api_base: &str) -> String {
+    api_base.strip_suffix("/v3").map_or_else(
+        || format!("{api_base}/graphql"),
+        |api| format!("{api}/graphql"),
+    )
+}
+
+/// Resolves a token for `host`, or `None` when only `gh` itself can
+/// authenticate.
+pub fn resolve_token(host: &str) -> Option<Secret> {
+    let settings = Settings::load().unwrap_or_default();
+    let keys: &[&str] = if host == GITHUB_HOST {
+        &[GH_TOKEN, GITHUB_TOKEN]
+    } else {
+        &[GH_ENTERPRISE_TOKEN, GITHUB_ENTERPRISE_TOKEN]
+    };
+    keys.iter()
+        .find_map(|key| {
+            settings
+                .get_env_var(key)
+                .filter(|token| !token.trim().is_empty())
+        })
+        .or_else(|| {
+            let content = std::fs::read_to_string(gh_config_dir()?.join("hosts.yml")).ok()?;
+            token_from_hosts_yaml(&content, host)
+        })
+        .map(Secret::from)
+}
+
+/// Returns the `gh` configuration directory: `GH_CONFIG_DIR`, else
+/// `$XDG_CONFIG_HOME/gh`, else `~/.config/gh`.
+fn gh_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(GH_CONFIG_DIR).filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("gh"));
+    }
+    dirs::home_dir().map(|home| home.join(".config").join("gh"))
+}
+
+/// Extracts the token for `host` from `gh`'s `hosts.yml`.
+///
+/// Reads the host-level `oauth_token`, falling back to the active user's
+/// entry under `users` (the multi-account layout).
+fn token_from_hosts_yaml(content: &str, host: &str) -> Option<String> {
+    let hosts: serde_yaml::Value = serde_yaml::from_str(content).ok()?;
+    let entry = hosts.get(host)?;
+    let token = entry
+        .get("oauth_token")
+        .and_then(serde_yaml::Value::as_str)
+        .or_else(|| {
+            let user = entry.get("user")?.as_str()?;
+            entry.get("users")?.get(user)?.get("oauth_token")?.as_str()
+        })?;
+    (!token.trim().is_empty()).then(|| token.to_string())
+}
+
+/// Client for the GitHub pull request endpoints of one repository.
+#[derive(Debug, Clone)]
+pub struct GitHubClient {
+    client: reqwest::Client,
+    repo: GitHubRepo,
+    api_base: String,
+    token: Secret,
+}
+
+impl GitHubClient {
+    /// Creates a client for `repo` with the given token. Enterprise
+    /// repositories use the API base from `GITHUB_API_URL` when set.
+    pub fn new(repo: GitHubRepo, token: impl Into<Secret>) -> Result<Self> {
+        let configured = if repo.is_github_com() {
+            None
+        } else {
+            Settings::load()
+                .unwrap_or_default()
+                .get_env_var(GITHUB_API_URL)
+        };
+        Ok(Self {
+            client: http_client()?,
+            api_base: api_base_for(&repo.host, configured.as_deref()),
+            repo,
+            token: token.into(),
+        })
+    }
+
+    /// Returns the repository this client targets.
+    pub fn repo(&self) -> &GitHubRepo {
+        &self.repo
+    }
+
+    fn api_path(&self) -> String {
+        format!(
+            "{}/repos/{}/{}",
+            self.api_base, self.repo.owner, self.repo.repo
+        )
+    }
+
+    async fn request(&self, method: &str, url: &str, body: Option<&Value>) -> Result<Value> {
+        send_json(
+            &self.client,
+            self.forge_name(),
+            method,
+            url,
+            &format!("Bearer {}", self.token.expose_secret()),
+            body,
+        )
+        .await
+    }
+
+    /// Lists the repository's pull requests matching `query`, unparsed.
+    async fn query_pulls(&self, query: &[(&str, &str)]) -> Result<Vec<Value>> {
+        let mut url = url::Url::parse(&format!("{}/pulls", self.api_path()))
+            .context("Invalid GitHub API URL")?;
+        url.query_pairs_mut().extend_pairs(query);
+        let response = self.request("GET", url.as_str(), None).await?;
+        Ok(response.as_array().cloned().unwrap_or_default())
+    }
+
+    async fn default_branch(&self) -> Result<String> {
+        let repo = self.request("GET", &self.api_path(), None).await?;
+        repo.get("default_branch")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .context("GitHub repository response has no default_branch")
+    }
+}
+
+#[async_trait]
+impl PullRequestApi for GitHubClient {
+    fn forge_name(&self) -> &'static str {
+        "GitHub"
+    }
+
+    async fn verify_access(&self) -> Result<()> {
+        self.request("GET", &self.api_path(), None)
+            .await
+            .map(|_| ())
+            .with_context(|| {
+                format!(
+                    "GitHub API at {} cannot access {}/{}",
+                    self.api_base, self.repo.owner, self.repo.repo
+                )
+            })
+    }
+
+    async fn list_branch_prs(&self, branch: &str) -> Result<Vec<PullRequest>> {
+        let head = format!("{}:{branch}", self.repo.owner);
+        let own = self
+            .query_pulls(&[("head", &head), ("state", "open"), ("per_page", "50")])
+            .await?;
+        if !own.is_empty() {
+            return Ok(own.iter().filter_map(parse_pull_request).collect());
+        }
+        // `head=owner:branch` only matches PRs opened from this repository;
+        // a fork's PR carries the fork owner there. Fall back to matching
+        // the branch name among the newest open PRs, as `gh pr list --head`
+        // does.
+        let open = self
+            .query_pulls(&[("state", "open"), ("per_page", "100")])
+            .await?;
+        Ok(prs_with_head_branch(&open, branch))
+    }
+
+    async fn latest_branch_pr(&self, branch: &str) -> Result<Option<PullRequest>> {
+        // Unlike `list_branch_prs` this only sees PRs opened from this
+        // repository: finding a fork's PR would mean scanning every closed
+        // PR as well as the open ones.
+        let mut url = url::Url::parse(&format!("{}/pulls", self.api_path()))
+            .context("Invalid GitHub API URL")?;
+        url.query_pairs_mut()
+            .append_pair("head", &format!("{}:{branch}", self.repo.owner))
+            .append_pair("state", "all")
+            .append_pair("sort", "created")
+            .append_pair("direction", "desc")
+            .append_pair("per_page", "1");
+
+        let response = self.request("GET", url.as_str(), None).await?;
+        Ok(response
+            .as_array()
+            .and_then(|values| values.iter().find_map(parse_pull_request)))
+    }
+
+    async fn create_pr(
+        &self,
+        source: &str,
+        destination: Option<&str>,
+        title: &str,
+        description: &str,
+        draft: bool,
+    ) -> Result<PullRequest> {
+        let base = match destination {
+            Some(base) => base.to_string(),
+            None => self.default_branch().await?,
+        };
+        let body = json!({
+            "title": title,
+            "head": source,
+            "base": base,
+            "body": description,
+            "draft": draft,
+        });
+        let response = self
+            .request("POST", &format!("{}/pulls", self.api_path()), Some(&body))
+            .await?;
+        parse_pull_request(&response).context("Unexpected response creating GitHub PR")
+    }
+
+    async fn update_pr(
+        &self,
+        number: u64,
+        title: &str,
+        description: &str,
+        destination: Option<&str>,
+    ) -> Result<PullRequest> {
+        let mut body = json!({
+            "title": title,
+            "body": description,
+        });
+        if let Some(base) = destination {
+            body["base"] = json!(base);
+        }
+        let response = self
+            .request(
+                "PATCH",
+                &format!("{}/pulls/{number}", self.api_path()),
+                Some(&body),
+            )
+            .await?;
+        parse_pull_request(&response).context("Unexpected response updating GitHub PR")
+    }
+
+    async fn request_reviewers(&self, number: u64, reviewers: &[String]) -> Result<()> {
+        let (users, teams) = split_reviewers(reviewers);
+        let body = json!({
+            "reviewers": users,
+            "team_reviewers": teams,
+        });
+        self.request(
+            "POST",
+            &format!("{}/pulls/{number}/requested_reviewers", self.api_path()),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn add_labels(&self, number: u64, labels: &[String]) -> Result<()> {
+        let body = json!({ "labels": labels });
+        self.request(
+            "POST",
+            &format!("{}/issues/{number}/labels", self.api_path()),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn mark_ready(&self, number: u64) -> Result<()> {
+        let pr = self
+            .request("GET", &format!("{}/pulls/{number}", self.api_path()), None)
+            .await?;
+        if !pr.get("draft").and_then(Value::as_bool).unwrap_or(false) {
+            return Ok(());
+        }
+        let node_id = pr
+            .get("node_id")
+            .and_then(Value::as_str)
+            .context("GitHub PR response has no node_id")?;
+        let body = json!({
+            "query": "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { clientMutationId } }",
+            "variables": { "id": node_id },
+        });
+        let response = self
+            .request("POST", &graphql_url_for(&self.api_base), Some(&body))
+            .await?;
+        graphql_error(&response).map_or(Ok(()), |message| {
+            anyhow::bail!("GitHub API error marking PR #{number} ready: {message}")
+        })
+    }
+
+    async fn pr_commits(&self, number: u64) -> Result<Vec<String>> {
+        // GitHub lists at most 250 commits for a pull request.
+        let mut hashes = Vec::new();
+        for page in 1..=3 {
+            let response = self
+                .request(
+                    "GET",
+                    &format!(
+                        "{}/pulls/{number}/commits?per_page=100&page={page}",
+                        self.api_path()
+                    ),
+                    None,
+                )
+                .await?;
+            let values = response.as_array().map(Vec::as_slice).unwrap_or_default();
+            hashes.extend(commit_hashes(values, "/sha"));
+            if values.len() < 100 {
+                break;
+            }
+        }
+        Ok(hashes)
+    }
+
+    async fn check_state(&self, number: u64) -> Result<PrCheckState> {
+        let pr = self
+            .request("GET", &format!("{}/pulls/{number}", self.api_path()), None)
+            .await?;
+        let sha = pr
+            .pointer("/head/sha")
+            .and_then(Value::as_str)
+            .context("GitHub PR response has no head commit")?;
+        let commit_path = format!("{}/commits/{sha}", self.api_path());
+        let runs = self
+            .request(
+                "GET",
+                &format!("{commit_path}/check-runs?per_page=100"),
+                None,
+            )
+            .await?;
+        let statuses = self
+            .request("GET", &format!("{commit_path}/status"), None)
+            .await?;
+        // Check runs and legacy commit statuses carry the same status,
+        // conclusion, and state fields as `gh`'s statusCheckRollup entries.
+        let contexts: Vec<Value> = [("check_runs", &runs), ("statuses", &statuses)]
+            .into_iter()
+            .filter_map(|(key, response)| response.get(key).and_then(Value::as_array))
+            .flatten()
+            .cloned()
+            .collect();
+        Ok(rollup_check_state(&contexts))
+    }
+
+    async fn merge_pr(
+        &self,
+        number: u64,
+        strategy: MergeStrategy,
+        message: Option<&str>,
+    ) -> Result<()> {
+        let mut body = json!({ "merge_method": strategy.as_str() });
+        if let Some(message) = message {
+            let (title, description) = split_commit_message(message);
+            body["commit_title"] = json!(title);
+            body["commit_message"] = json!(description);
+        }
+        self.request(
+            "PUT",
+            &format!("{}/pulls/{number}/merge", self.api_path()),
+            Some(&body),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn upsert_pr_comment(&self, number: u64, marker: &str, body: &str) -> Result<()> {
+        let comments_path = format!("{}/issues/{number}/comments", self.api_path());
+        let mut existing = None;
+        for page in 1..=MAX_COMMENT_PAGES {
+            let response = self
+                .request(
+                    "GET",
+                    &format!("{comments_path}?per_page={COMMENT_PAGE_SIZE}&page={page}"),
+                    None,
+                )
+                .await?;
+            let comments = response.as_array().map(Vec::as_slice).unwrap_or_default();
+            existing = find_marked_comment(comments, marker, "/body");
+            if existing.is_some() || comments.len() < COMMENT_PAGE_SIZE {
+                break;
+            }
+        }
+        let payload = json!({ "body": body });
+        match existing {
+            Some(id) => {
+                self.request(
+                    "PATCH",
+                    &format!("{}/issues/comments/{id}", self.api_path()),
+                    Some(&payload),
+                )
+                .await
+            }
+            None => self.request("POST", &comments_path, Some(&payload)).await,
+        }
+        .map(|_| ())
+    }
+
+    async fn review_comments(&self, number: u64) -> Result<Vec<ReviewComment>> {
+        let api_path = self.api_path();
+        let mut comments = Vec::new();
+        // Review summaries, inline review comments, and conversation comments
+        // live behind separate endpoints; one page of each is enough for a
+        // prompt.
+        for (endpoint, path_pointer, created_pointer) in [
+            (
+                format!("{api_path}/pulls/{number}/reviews"),
+                None,
+                "/submitted_at",
+            ),
+            (
+                format!("{api_path}/pulls/{number}/comments"),
+                Some("/path"),
+                "/created_at",
+            ),
+            (
+                format!("{api_path}/issues/{number}/comments"),
+                None,
+                "/created_at",
+            ),
+        ] {
+            let response = self
+                .request(
+                    "GET",
+                    &format!("{endpoint}?per_page={COMMENT_PAGE_SIZE}"),
+                    None,
+                )
+                .await?;
+            let values = response.as_array().map(Vec::as_slice).unwrap_or_default();
+            comments.extend(review_comments_at(
+                values,
+                "/user/login",
+                "/body",
+                path_pointer,
+                created_pointer,
+            ));
+        }
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(comments)
+    }
+
+    async fn create_release(
+        &self,
+        tag: &str,
+        name: &str,
+        notes: &str,
+        prerelease: bool,
+    ) -> Result<String> {
+        let body = json!({
+            "tag_name": tag,
+            "name": name,
+            "body": notes,
+            "prerelease": prerelease,
+        });
+        let release = self
+            .request(
+                "POST",
+                &format!("{}/releases", self.api_path()),
+                Some(&body),
+            )
+            .await?;
+        release
+            .get("html_url")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .context("GitHub release response has no html_url")
+    }
+}
+
+/// Returns the first error message of a GraphQL response, which reports
+/// errors with a 200 status.
+fn graphql_error(response: &Value) -> Option<&str> {
+    response
+        .get("errors")
+        .and_then(Value::as_array)
+        .and_then(|errors| errors.first())
+        .map(|error| {
+            error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error")
+        })
+}
+
+/// Maps a GitHub pull request object onto [`PullRequest`].
+///
+/// GitHub reports merged pull requests as `closed` with a `merged_at`
+/// timestamp; states are normalised to the upper-case names `gh` reports.
+/// Parses the pull requests in `pulls` whose source branch is named
+/// `branch`, whichever repository it lives in.
+fn prs_with_head_branch(pulls: &[Value], branch: &str) -> Vec<PullRequest> {
+    pulls
+        .iter()
+        .filter(|pr| pr.pointer("/head/ref").and_then(Value::as_str) == Some(branch))
+        .filter_map(parse_pull_request)
+        .collect()
+}
+
+fn parse_pull_request(pr: &Value) -> Option<PullRequest> {
+    let number = pr.get("number").and_then(Value::as_u64)?;
+    let title = pr.get("title").and_then(Value::as_str)?;
+    let merged = pr.get("merged_at").is_some_and(|m| !m.is_null());
+    let state = match pr.get("state").and_then(Value::as_str) {
+        _ if merged => "MERGED",
+        Some("closed") => "CLOSED",
+        _ => "OPEN",
+    };
+    let url = pr
+        .get("html_url")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let body = pr.get("body").and_then(Value::as_str).unwrap_or_default();
+    let base = pr
+        .pointer("/base/ref")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    Some(PullRequest {
+        number,
+        title: title.to_string(),
+        state: state.to_string(),
+        url: url.to_string(),
+        body: body.to_string(),
+        base: base.to_string(),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_github_remotes() {
+        let expected = Some(GitHubRepo {
+            host: "github.com".to_string(),
+            owner: "acme".to_string(),
+            repo: "widgets".to_string(),
+        });
+        assert_eq!(
+            GitHubRepo::from_remote_url("git@github.com:acme/widgets.git", None),
+            expected
+        );
+        assert_eq!(
+            GitHubRepo::from_remote_url("https://github.com/acme/widgets", None),
+            expected
+        );
+        assert!(
+            GitHubRepo::from_remote_url("git@github.example.com:acme/widgets.git", None).is_none()
+        );
+        let enterprise = GitHubRepo::from_remote_url(
+            "git@GitHub.Example.com:acme/widgets.git",
+            Some("github.example.com"),
+        )
+        .unwrap();
+        assert_eq!(enterprise.host, "github.example.com");
+        assert!(!enterprise.is_github_com());
+    }
+
+    #[test]
+    fn normalises_enterprise_host() {
+        assert_eq!(
+            enterprise_host_from(Some("https://github.example.com/")).as_deref(),
+            Some("github.example.com")
+        );
+        assert!(enterprise_host_from(Some("github.com")).is_none());
+        assert!(enterprise_host_from(Some("  ")).is_none());
+        assert!(enterprise_host_from(None).is_none());
+    }
+
+    #[test]
+    fn derives_api_urls_per_host() {
+        assert_eq!(api_base_for("github.com", None), "https://api.github.com");
+        assert_eq!(
+            api_base_for("github.com", Some("https://ignored.example.com")),
+            "https://api.github.com"
+        );
+        let enterprise = api_base_for("github.example.com", None);
+        assert_eq!(enterprise, "https://github.example.com/api/v3");
+        assert_eq!(
+            graphql_url_for(&enterprise),
+            "https://github.example.com/api/graphql"
+        );
+        assert_eq!(
+            api_base_for("github.example.com", Some("https://gh-api.example.com/")),
+            "https://gh-api.example.com"
+        );
+        assert_eq!(
+            graphql_url_for("https://api.github.com"),
+            "https://api.github.com/graphql"
+        );
+    }
+
+    #[test]
+    fn reads_host_level_token() {
+        let yaml = "github.com:\n    oauth_token: gho_abc\n    user: jdoe\n    git_protocol: ssh\n";
+        assert_eq!(
+            token_from_hosts_yaml(yaml, "github.com").as_deref(),
+            Some("gho_abc")
+        );
+        assert!(token_from_hosts_yaml(yaml, "ghe.example.com").is_none());
+    }
+
+    #[test]
+    fn reads_active_user_token() {
+        let yaml = "github.com:\n    user: jdoe\n    users:\n        jdoe:\n            oauth_token: gho_user\n        other: {}\n";
+        assert_eq!(
+            token_from_hosts_yaml(yaml, "github.com").as_deref(),
+            Some("gho_user")
+        );
+    }
+
+    #[test]
+    fn keyring_only_config_has_no_token() {
+        let yaml = "github.com:\n    user: jdoe\n    users:\n        jdoe: {}\n";
+        assert!(token_from_hosts_yaml(yaml, "github.com").is_none());
+    }
+
+    #[test]
+    fn reads_graphql_errors() {
+        assert_eq!(graphql_error(&json!({ "data": {} })), None);
+        assert_eq!(
+            graphql_error(&json!({ "errors": [{ "message": "Not a draft" }] })),
+            Some("Not a draft")
+        );
+    }
+
+    #[test]
+    fn matches_fork_prs_by_branch_name() {
+        let pulls = vec![
+            json!({ "number": 7, "title": "fix: a", "state": "open",
+                    "head": { "ref": "fix-typo", "repo": { "full_name": "alice/widgets" } } }),
+            json!({ "number": 8, "title": "feat: b", "state": "open",
+                    "head": { "ref": "feature", "repo": { "full_name": "acme/widgets" } } }),
+        ];
+        let prs = prs_with_head_branch(&pulls, "fix-typo");
+        assert_eq!(prs.len(), 1);
+        assert_eq!(prs[0].number, 7);
+        assert!(prs_with_head_branch(&pulls, "missing").is_empty());
+    }
+
+    #[test]
+    fn maps_pull_request_states() {
+        let open = json!({
+            "number": 42,
+            "title": "feat: widgets",
+            "state": "open",
+            "merged_at": null,
+            "html_url": "https://github.com/acme/widgets/pull/42",
+            "body": null,
+            "base": { "ref": "main" }
+        });
+        let pr = parse_pull_request(&open).unwrap();
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.state, "OPEN");
+        assert_eq!(pr.url, "https://github.com/acme/widgets/pull/42");
+        assert!(pr.body.is_empty());
+        assert_eq!(pr.base, "main");
+
+        let merged = json!({
+            "number": 1,
+            "title": "x",
+            "state": "closed",
+            "merged_at": "2026-01-01T00:00:00Z"
+        });
+        assert_eq!(parse_pull_request(&merged).unwrap().state, "MERGED");
+        let closed = json!({ "number": 2, "title": "y", "state": "closed", "merged_at": null });
+        assert_eq!(parse_pull_request(&closed).unwrap().state, "CLOSED");
+    }
+}
@@ -1,8 +1,10 @@
 //! Counting every GitHub API invocation omni-dev makes (#1387).
 //!
-//! Every GitHub call funnels through the `gh` CLI subprocess (ADR-0003 /
-//! ADR-0050 — the token never enters our process), so there is no in-process
-//! HTTP transport to instrument. Instead, [`run_gh`] is the single choke point
+//! GitHub calls funnel through the `gh` CLI subprocess (ADR-0003 /
+//! ADR-0050 — the token never enters our process). The one exception is the
+//! pull request operations of `create pr` / `branch info` when a GitHub token
+//! resolves: those use [`crate::github_api`] and are logged as `kind: "http"`
+//! records like the other REST clients. [`run_gh`] is the single choke point
 //! every Rust `gh` call site routes through: it spawns `gh`, records one
 //! `kind: "gh"` line to the request log ([`crate::request_log::record_gh`]), and
 //! returns the process `Output` **unchanged** so call-site behavior and exit
@@ -75,7 +77,8 @@ pub fn source_str(source: Source) -> &'static str {
 /// `label` is the semantic subcommand (`"api graphql"`, `"pr list"`,
 /// `"--version"`); it is split into the record's `command` for per-subcommand
 /// aggregation and category derivation. `cwd`, when given, sets the child's
-/// working directory (several call sites run `gh` inside a repo).
+/// working directory (several call sites run `gh` inside a repo). A
+/// configured GitHub Enterprise host is passed on as `GH_HOST`.
 ///
 /// **Blocking** (`Command::output`) — daemon callers must already be on a
 /// blocking thread. The `Output` (or spawn `io::Error`) is returned verbatim, so
@@ -94,6 +97,10 @@ where
     if let Some(dir) = cwd {
         cmd.current_dir(dir);
     }
+    // A `GH_HOST` kept only in settings.json must still reach `gh`.
+    if let Some(host) = crate::github_api::configured_host() {
+        cmd.env(crate::github_api::GH_HOST, host);
+    }
 
     let started = Instant::now();
     let result = cmd.output();
@@ -39,6 +39,7 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 
 pub mod atlassian;
+pub mod bitbucket;
 pub mod browser;
 pub mod build_info;
 pub mod claude;
@@ -47,7 +48,10 @@ pub mod coverage;
 pub mod daemon;
 pub mod data;
 pub mod datadog;
+pub mod forge;
 pub mod git;
+pub mod gitea;
+pub mod github_api;
 pub mod github_metrics;
 pub mod github_rate_limit;
 #[cfg(feature = "mcp")]
@@ -55,6 +59,7 @@ pub mod mcp;
 pub mod pr_status;
 pub mod request_log;
 pub mod resources;
+pub mod serve;
 pub mod sessions;
 pub mod snowflake;
 pub mod transcript;
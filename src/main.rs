@@ -1,33 +1,44 @@
-use std::process;
 use std::time::Instant;
 
-use clap::{CommandFactory, Parser};
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
 use omni_dev::request_log::{self, InvocationOutcome, RequestLogContext, Source};
+use omni_dev::utils::logging::{self, LogOptions};
+use omni_dev::utils::plain;
 use omni_dev::Cli;
 
 fn main() {
+    let launched = Instant::now();
+
     // Capture argv before clap consumes it, so the invocation record can log the
     // full command line and the resolved subcommand path — and so tracing can be
     // initialized at the right default level for the resolved command *before* any
     // log line is emitted.
     let argv: Vec<String> = std::env::args().collect();
-    let command = resolve_command_path(&argv);
+    let (cli, command) = parse_cli(&argv);
     let daemon_run = is_daemon_run(&command);
+    let mcp_serve = is_mcp_serve(&command);
 
     // The long-lived `daemon run` defaults to `info` so its lifecycle events reach
     // the log sink; short-lived CLI invocations stay at `warn`. `RUST_LOG` still
-    // overrides either. See #1316.
-    init_tracing(daemon_run);
-
-    let cli = Cli::parse();
+    // overrides either, and `--log-level` overrides `RUST_LOG`. See #1316.
+    if let Err(e) = logging::init(&LogOptions {
+        level: cli.log_level.as_deref(),
+        file: cli.log_file.as_deref(),
+        format: cli.log_format,
+        default_level: default_filter(daemon_run),
+    }) {
+        die(&e);
+    }
 
     // Install the per-invocation context up front — crucially *before* the macOS
     // menu-bar handoff below, which `return`s without ever reaching the common
     // path. Otherwise a tray-hosted daemon's `gh`/HTTP records would default to
-    // `Source::Cli` instead of `Daemon` (#1387). `daemon run` → Daemon, else Cli;
-    // set_global is first-write-wins.
+    // `Source::Cli` instead of `Daemon` (#1387). `daemon run` → Daemon, `mcp serve`
+    // → Mcp, else Cli; set_global is first-write-wins.
     let source = if daemon_run {
         Source::Daemon
+    } else if mcp_serve {
+        Source::Mcp
     } else {
         Source::Cli
     };
@@ -53,6 +64,17 @@ fn main() {
         }
     }
 
+    // Filter stdout/stderr for `--plain`, `OMNI_DEV_PLAIN`, or `NO_COLOR`. Every
+    // exit below goes through `plain::exit`/`plain::finish` so the filter
+    // drains before the process ends. `mcp serve` is exempt: its stdout
+    // carries JSON-RPC frames, which the filter must not rewrite. Only the
+    // process environment is consulted, so startup never reads settings.json.
+    if !mcp_serve {
+        if let Err(e) = plain::install(plain::mode(cli.plain, &omni_dev::utils::SystemEnv)) {
+            eprintln!("warning: plain output unavailable: {e:#}");
+        }
+    }
+
     let runtime = match tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()
@@ -60,10 +82,14 @@ fn main() {
         Ok(runtime) => runtime,
         Err(e) => {
             eprintln!("Error: failed to start the tokio runtime: {e}");
-            process::exit(1);
+            plain::exit(1);
         }
     };
 
+    // Everything up to here runs for every invocation, `--help` aside; keep it
+    // free of settings reads and registry loading so simple commands start fast.
+    tracing::debug!(elapsed = ?launched.elapsed(), "startup finished");
+
     // Time the whole command and append one invocation record after it returns.
     // Logging is best-effort and never affects the exit code. (The per-invocation
     // context was installed up front, above, before the menu-bar handoff.)
@@ -85,17 +111,25 @@ fn main() {
     if let Err(e) = result {
         die(&e);
     }
+    plain::finish();
 }
 
-/// Resolves the clap subcommand path (e.g. `["jira","read"]`) by re-deriving
-/// matches from argv and walking the subcommand chain. Generic — robust to new
-/// subcommands — and returns an empty path if re-parsing fails.
-fn resolve_command_path(argv: &[String]) -> Vec<String> {
+/// Parses argv into the CLI and its resolved subcommand path, building the
+/// clap command tree once. Prints usage or help and exits on a parse error or
+/// `--help`, as `Cli::parse` does.
+fn parse_cli(argv: &[String]) -> (Cli, Vec<String>) {
+    let mut matches = Cli::command().get_matches_from(argv);
+    let command = command_path(&matches);
+    let cli = Cli::from_arg_matches_mut(&mut matches)
+        .unwrap_or_else(|e| e.format(&mut Cli::command()).exit());
+    (cli, command)
+}
+
+/// Resolves the clap subcommand path (e.g. `["jira","read"]`) by walking the
+/// subcommand chain of `matches`. Generic — robust to new subcommands.
+fn command_path(matches: &ArgMatches) -> Vec<String> {
     let mut path = Vec::new();
-    let Ok(matches) = Cli::command().try_get_matches_from(argv) else {
-        return path;
-    };
-    let mut current = &matches;
+    let mut current = matches;
     while let Some((name, sub)) = current.subcommand() {
         path.push(name.to_string());
         current = sub;
@@ -109,6 +143,12 @@ fn is_daemon_run(command: &[String]) -> bool {
         && command.get(1).map(String::as_str) == Some("run")
 }
 
+/// Whether the resolved command path is `mcp serve` (the stdio MCP server).
+fn is_mcp_serve(command: &[String]) -> bool {
+    command.first().map(String::as_str) == Some("mcp")
+        && command.get(1).map(String::as_str) == Some("serve")
+}
+
 /// The default tracing filter for a resolved command when `RUST_LOG` is unset:
 /// `info` for the long-lived `daemon run` (so its lifecycle events — start/stop,
 /// signals — reach the log sink), `warn` for every short-lived CLI invocation.
@@ -120,19 +160,6 @@ fn default_filter(daemon_run: bool) -> &'static str {
     }
 }
 
-/// Initializes the tracing subscriber (stderr, `RUST_LOG`-driven), keeping
-/// daemon/debug logs off stdout. The default level when `RUST_LOG` is unset is
-/// [`default_filter`]; `RUST_LOG` still overrides it.
-fn init_tracing(daemon_run: bool) {
-    tracing_subscriber::fmt()
-        .with_writer(std::io::stderr)
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(default_filter(daemon_run))),
-        )
-        .init();
-}
-
 /// Prints an error and its source chain to stderr, then exits non-zero.
 fn die(e: &anyhow::Error) -> ! {
     eprintln!("Error: {e}");
@@ -141,7 +168,7 @@ fn die(e: &anyhow::Error) -> ! {
         eprintln!("  Caused by: {err}");
         source = err.source();
     }
-    process::exit(1);
+    plain::exit(1);
 }
 
 #[cfg(test)]
@@ -171,6 +198,25 @@ mod tests {
         assert!(!is_daemon_run(&[]));
     }
 
+    #[test]
+    fn is_mcp_serve_matches_only_mcp_serve() {
+        assert!(is_mcp_serve(&path(&["mcp", "serve"])));
+        assert!(!is_mcp_serve(&path(&["mcp"])));
+        assert!(!is_mcp_serve(&path(&["daemon", "run"])));
+        assert!(!is_mcp_serve(&[]));
+    }
+
+    #[test]
+    fn command_path_walks_the_subcommand_chain() {
+        let matches = Cli::command()
+            .try_get_matches_from(["omni-dev", "git", "commit", "message", "view", "HEAD"])
+            .unwrap();
+        assert_eq!(
+            command_path(&matches),
+            path(&["git", "commit", "message", "view"])
+        );
+    }
+
     #[test]
     fn default_filter_is_info_only_for_daemon_run() {
         assert_eq!(default_filter(true), "info");
@@ -139,6 +139,10 @@ pub struct GitCreatePrParams {
     /// Defaults to the primary remote's main branch when omitted.
     #[serde(default)]
     pub base_branch: Option<String>,
+    /// Skips inferring labels from the commits' types and scopes. Inferred
+    /// labels are returned as a `labels` list after the title and description.
+    #[serde(default)]
+    pub no_labels: bool,
     /// Path to the git repository. Defaults to the current working directory.
     #[serde(default)]
     pub repo_path: Option<String>,
@@ -196,12 +200,9 @@ impl OmniDevServer {
         let branch = params.branch.clone();
         let repo_path = params.repo_path.clone();
 
-        let yaml = tokio::task::spawn_blocking(move || {
-            crate::cli::git::run_info(branch.as_deref(), repo_path.as_deref())
-        })
-        .await
-        .map_err(|e| tool_error(anyhow::anyhow!("join error: {e}")))?
-        .map_err(tool_error)?;
+        let yaml = crate::cli::git::run_info(branch.as_deref(), repo_path.as_deref())
+            .await
+            .map_err(tool_error)?;
 
         Ok(CallToolResult::success(vec![Content::text(yaml)]))
     }
@@ -345,13 +346,17 @@ impl OmniDevServer {
         let base_branch = params.base_branch.clone();
         let repo_path: Option<PathBuf> = params.repo_path.as_deref().map(PathBuf::from);
 
-        let outcome =
-            crate::cli::git::run_create_pr(model, base_branch.as_deref(), repo_path.as_deref())
-                .await
-                .map_err(tool_error)?;
+        let outcome = crate::cli::git::run_create_pr(
+            model,
+            base_branch.as_deref(),
+            params.no_labels,
+            repo_path.as_deref(),
+        )
+        .await
+        .map_err(tool_error)?;
 
         Ok(CallToolResult::success(vec![Content::text(
-            outcome.pr_yaml,
+            format_create_pr_payload(&outcome),
         )]))
     }
 }
@@ -430,6 +435,28 @@ fn format_amend_payload(outcome: &crate::cli::git::AmendOutcome) -> String {
     )
 }
 
+/// Formats the payload returned by the `git_create_pr` tool: the PR YAML,
+/// with a `labels` list after the title and description when any were
+/// inferred.
+fn format_create_pr_payload(outcome: &crate::cli::git::CreatePrOutcome) -> String {
+    #[derive(serde::Serialize)]
+    struct LabelledPr<'a> {
+        title: &'a str,
+        description: &'a str,
+        labels: &'a [String],
+    }
+
+    if outcome.labels.is_empty() {
+        return outcome.pr_yaml.clone();
+    }
+    crate::data::to_yaml(&LabelledPr {
+        title: &outcome.title,
+        description: &outcome.description,
+        labels: &outcome.labels,
+    })
+    .unwrap_or_else(|_| outcome.pr_yaml.clone())
+}
+
 /// Formats the payload returned by the `git_staged_commit` tool.
 fn format_staged_payload(outcome: &crate::cli::git::StagedOutcome, print_only: bool) -> String {
     format!(
@@ -575,6 +602,23 @@ mod tests {
         assert!(payload.contains("amendment_count: 0"));
     }
 
+    #[test]
+    fn format_create_pr_payload_appends_labels() {
+        let mut outcome = crate::cli::git::CreatePrOutcome {
+            title: "feat: x".to_string(),
+            description: "Adds x.".to_string(),
+            pr_yaml: "title: 'feat: x'\ndescription: Adds x.\n".to_string(),
+            labels: Vec::new(),
+        };
+        assert_eq!(format_create_pr_payload(&outcome), outcome.pr_yaml);
+
+        outcome.labels = vec!["enhancement".to_string(), "area: cli".to_string()];
+        let payload = format_create_pr_payload(&outcome);
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&payload).unwrap();
+        assert_eq!(parsed["title"].as_str(), Some("feat: x"));
+        assert_eq!(parsed["labels"][1].as_str(), Some("area: cli"));
+    }
+
     #[test]
     fn format_staged_payload_applied() {
         let outcome = StagedOutcome {
@@ -686,6 +730,7 @@ mod tests {
         let params = GitCreatePrParams {
             model: None,
             base_branch: None,
+            no_labels: false,
             repo_path: Some("/no/such/path/for/mcp/test".to_string()),
         };
         let err = server.git_create_pr(Parameters(params)).await.unwrap_err();
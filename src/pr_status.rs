@@ -241,7 +241,7 @@ fn check_entry_state(entry: &Value) -> EntryState {
 /// (`failure`); else any still-running one (`pending`); else `success`. An empty
 /// rollup means no checks (`none`) — the only way `none` arises, since every entry
 /// classifies as one of the three.
-fn rollup_check_state(contexts: &[Value]) -> PrCheckState {
+pub(crate) fn rollup_check_state(contexts: &[Value]) -> PrCheckState {
     if contexts.is_empty() {
         return PrCheckState::None;
     }
@@ -50,9 +50,10 @@ pub enum RecordKind {
     Invocation,
     /// One per outbound HTTP request.
     Http,
-    /// One per `gh` CLI subprocess invocation (the only path to the GitHub API;
-    /// the token never enters our process, so these are subprocess records, not
-    /// [`RecordKind::Http`]). See `crate::github_metrics`.
+    /// One per `gh` CLI subprocess invocation (the token never enters our
+    /// process, so these are subprocess records, not [`RecordKind::Http`]).
+    /// The native pull request client in `crate::github_api` logs
+    /// [`RecordKind::Http`] records instead. See `crate::github_metrics`.
     Gh,
     /// One per wrapped `git worktree` subprocess invocation, carrying
     /// recovery-relevant metadata (path/branch/commit) in `context`.
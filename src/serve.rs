@@ -0,0 +1,395 @@
+//! HTTP API over the commit-message workflow, for dashboards and bots.
+//!
+//! `omni-dev serve` answers a handful of JSON endpoints backed by the same
+//! `run_*` entry points as the CLI and the MCP server:
+//!
+//! | Endpoint | Mirrors |
+//! |----------|---------|
+//! | `GET /v1/health` | — (unauthenticated liveness probe) |
+//! | `POST /v1/view` | `git commit message view` |
+//! | `POST /v1/check` | `git commit message check` |
+//! | `POST /v1/amendments` | `git commit message twiddle --save-only` |
+//! | `POST /v1/apply` | `git commit message amend` |
+//!
+//! Documents carry the schemas printed by `omni-dev schema dump`, serialized
+//! as JSON exactly as `--output json` would. Every endpoint but `health`
+//! requires `Authorization: Bearer <token>`; the server never emits CORS
+//! headers, so a web page cannot attach the token. The server operates on the
+//! one repository it was started in — requests cannot name another path.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+
+use crate::browser::auth;
+use crate::data::amendments::AmendmentFile;
+use crate::data::{RenderFormat, SCHEMA_VERSION};
+use crate::utils::env::{EnvSource, SystemEnv};
+
+/// Address `omni-dev serve` binds when `--addr` is not given.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8787";
+
+/// Environment variable pinning the API token instead of generating one.
+pub const TOKEN_ENV: &str = "OMNI_DEV_SERVE_TOKEN";
+
+/// Configuration of a running API server.
+#[derive(Debug, Clone)]
+pub struct ServeConfig {
+    /// Address to listen on.
+    pub addr: SocketAddr,
+    /// Repository every request operates on.
+    pub repo: PathBuf,
+}
+
+/// Shared state of the request handlers.
+#[derive(Clone)]
+struct ApiState {
+    token: Arc<String>,
+    repo: Arc<PathBuf>,
+    /// Serializes `apply`, the one endpoint that rewrites history.
+    apply_lock: Arc<Mutex<()>>,
+}
+
+/// Resolves the API token: `--token-file` (must be `0600`), then
+/// [`TOKEN_ENV`], else a freshly generated one. The boolean is `true` when the
+/// token was generated and so must be shown to the operator.
+pub fn resolve_token(token_file: Option<&Path>) -> Result<(String, bool)> {
+    resolve_token_with(&SystemEnv, token_file)
+}
+
+/// [`resolve_token`] over an injected [`EnvSource`].
+fn resolve_token_with(env: &impl EnvSource, token_file: Option<&Path>) -> Result<(String, bool)> {
+    if let Some(path) = token_file {
+        return Ok((auth::read_token_file(path)?, false));
+    }
+    match env.var(TOKEN_ENV) {
+        Some(value) if !value.trim().is_empty() => Ok((value.trim().to_string(), false)),
+        _ => Ok((auth::generate_token(), true)),
+    }
+}
+
+/// Binds `config.addr` and serves the API until Ctrl-C. A generated `token`
+/// is printed with the bound address so the operator can hand it to clients.
+pub async fn run(config: ServeConfig, token: String, print_token: bool) -> Result<()> {
+    let listener = TcpListener::bind(config.addr)
+        .await
+        .with_context(|| format!("Failed to bind {} (already in use?)", config.addr))?;
+    let addr = listener.local_addr()?;
+    if !addr.ip().is_loopback() {
+        tracing::warn!(
+            "Serving on non-loopback address {addr}; the bearer token travels in clear text"
+        );
+    }
+    println!("omni-dev API listening on http://{addr}");
+    println!("  repository : {}", config.repo.display());
+    if print_token {
+        println!("  token      : {token}");
+    }
+    axum::serve(listener, router(token, config.repo))
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await
+        .context("API server failed")
+}
+
+/// The API routes over `repo`, guarded by `token`.
+fn router(token: String, repo: PathBuf) -> Router {
+    let state = ApiState {
+        token: Arc::new(token),
+        repo: Arc::new(repo),
+        apply_lock: Arc::new(Mutex::new(())),
+    };
+    let api = Router::new()
+        .route("/v1/view", post(view_handler))
+        .route("/v1/check", post(check_handler))
+        .route("/v1/amendments", post(amendments_handler))
+        .route("/v1/apply", post(apply_handler))
+        .layer(middleware::from_fn_with_state(state.clone(), guard));
+    Router::new()
+        .route("/v1/health", get(health_handler))
+        .merge(api)
+        .with_state(state)
+}
+
+/// Rejects a request without the bearer token.
+async fn guard(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    if !auth::bearer_matches(authorization, &state.token) {
+        return ApiError::new(StatusCode::UNAUTHORIZED, "invalid or missing bearer token")
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// A failed request, answered as `{"error": "..."}`.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, format!("{e:#}"))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+type ApiResult = std::result::Result<Json<Value>, ApiError>;
+
+/// Runs blocking git work off the async workers.
+async fn blocking<T, F>(work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .context("API worker panicked")?
+}
+
+/// Converts a YAML document from a `run_*` entry point to JSON.
+fn yaml_to_json(yaml: &str) -> Result<Value> {
+    serde_yaml::from_str(yaml).context("Failed to convert YAML output to JSON")
+}
+
+/// `GET /v1/health`.
+async fn health_handler() -> Json<Value> {
+    Json(json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        "schema_version": SCHEMA_VERSION,
+    }))
+}
+
+/// The commit range of a request, `HEAD` when omitted.
+fn default_range() -> String {
+    "HEAD".to_string()
+}
+
+/// Body of `POST /v1/view`.
+#[derive(Debug, Deserialize)]
+struct ViewRequest {
+    #[serde(default = "default_range")]
+    range: String,
+}
+
+/// `POST /v1/view` — the repository view of `range`.
+async fn view_handler(State(state): State<ApiState>, Json(req): Json<ViewRequest>) -> ApiResult {
+    let json = blocking(move || {
+        crate::cli::git::run_view_as(&req.range, Some(state.repo.as_path()), RenderFormat::Json)
+    })
+    .await?;
+    Ok(Json(
+        serde_json::from_str(&json).context("Failed to parse view output")?,
+    ))
+}
+
+/// Body of `POST /v1/check`.
+#[derive(Debug, Deserialize)]
+struct CheckRequest {
+    #[serde(default = "default_range")]
+    range: String,
+    #[serde(default)]
+    strict: bool,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// `POST /v1/check` — the check report of `range`, with the exit code the CLI
+/// would use.
+async fn check_handler(State(state): State<ApiState>, Json(req): Json<CheckRequest>) -> ApiResult {
+    let outcome = crate::cli::git::run_check(
+        &req.range,
+        None,
+        Some(state.repo.as_path()),
+        req.strict,
+        req.model,
+    )
+    .await?;
+    Ok(Json(json!({
+        "report": yaml_to_json(&outcome.report_yaml)?,
+        "has_errors": outcome.has_errors,
+        "has_warnings": outcome.has_warnings,
+        "exit_code": outcome.exit_code,
+    })))
+}
+
+/// Body of `POST /v1/amendments`.
+#[derive(Debug, Deserialize)]
+struct AmendmentsRequest {
+    #[serde(default)]
+    range: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+}
+
+/// `POST /v1/amendments` — proposed amendments for `range`, never applied.
+async fn amendments_handler(
+    State(state): State<ApiState>,
+    Json(req): Json<AmendmentsRequest>,
+) -> ApiResult {
+    let outcome = crate::cli::git::run_twiddle(
+        req.range.as_deref(),
+        req.model,
+        true,
+        Some(state.repo.as_path()),
+    )
+    .await?;
+    Ok(Json(yaml_to_json(&outcome.amendments_yaml)?))
+}
+
+/// Body of `POST /v1/apply`.
+#[derive(Debug, Deserialize)]
+struct ApplyRequest {
+    amendments: AmendmentFile,
+    #[serde(default)]
+    allow_pushed: bool,
+}
+
+/// `POST /v1/apply` — rewrites commit messages from an amendments document,
+/// one request at a time.
+async fn apply_handler(State(state): State<ApiState>, Json(req): Json<ApplyRequest>) -> ApiResult {
+    let yaml = crate::data::to_yaml(&req.amendments).context("Failed to serialize amendments")?;
+    let _guard = state.apply_lock.lock().await;
+    let outcome = blocking(move || {
+        crate::cli::git::run_amend(&yaml, req.allow_pushed, Some(state.repo.as_path()))
+    })
+    .await?;
+    Ok(Json(json!({
+        "applied": outcome.applied,
+        "amendment_count": outcome.amendment_count,
+    })))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    /// A repository with one commit, and the address of an API serving it.
+    async fn serve_repo() -> (tempfile::TempDir, SocketAddr) {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Alice", "alice@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "feat: add a", &tree, &[])
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = router("tok".to_string(), dir.path().to_path_buf());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (dir, addr)
+    }
+
+    #[tokio::test]
+    async fn health_needs_no_token() {
+        let (_dir, addr) = serve_repo().await;
+        let body: Value = reqwest::get(format!("http://{addr}/v1/health"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[tokio::test]
+    async fn endpoints_reject_a_missing_or_wrong_token() {
+        let (_dir, addr) = serve_repo().await;
+        let client = reqwest::Client::new();
+        for token in [None, Some("nope")] {
+            let mut request = client
+                .post(format!("http://{addr}/v1/view"))
+                .json(&json!({}));
+            if let Some(token) = token {
+                request = request.bearer_auth(token);
+            }
+            let response = request.send().await.unwrap();
+            assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn view_returns_the_versioned_repository_view() {
+        let (_dir, addr) = serve_repo().await;
+        let response = reqwest::Client::new()
+            .post(format!("http://{addr}/v1/view"))
+            .bearer_auth("tok")
+            .json(&json!({ "range": "HEAD" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["schema_version"], SCHEMA_VERSION);
+        assert_eq!(body["commits"][0]["original_message"], "feat: add a");
+    }
+
+    #[tokio::test]
+    async fn failures_are_json_errors() {
+        let (_dir, addr) = serve_repo().await;
+        let response = reqwest::Client::new()
+            .post(format!("http://{addr}/v1/view"))
+            .bearer_auth("tok")
+            .json(&json!({ "range": "no-such-ref..HEAD" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        );
+        let body: Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("no-such-ref"));
+    }
+
+    #[test]
+    fn token_comes_from_the_environment_or_is_generated() {
+        let env = MapEnv::new().with(TOKEN_ENV, " pinned ");
+        assert_eq!(
+            resolve_token_with(&env, None).unwrap(),
+            ("pinned".to_string(), false)
+        );
+        let (token, generated) = resolve_token_with(&MapEnv::new(), None).unwrap();
+        assert!(generated);
+        assert!(!token.is_empty());
+    }
+}
@@ -153,6 +153,48 @@ pub(crate) mod atlassian_env {
     }
 }
 
+pub(crate) mod commits {
+    //! [`CommitInfo`] fixture for tests that work on analyzed commits without
+    //! a repository behind them.
+    use crate::git::commit::{FileChange, FileChanges};
+    use crate::git::{CommitAnalysis, CommitInfo};
+
+    /// A `feat` commit `hash` whose original and proposed message are both
+    /// `message`, modifying `files`. Tests set the fields they care about
+    /// on the returned value.
+    pub(crate) fn commit(hash: &str, message: &str, files: &[&str]) -> CommitInfo {
+        CommitInfo {
+            hash: hash.to_string(),
+            author: "Dev <dev@example.com>".to_string(),
+            date: chrono::Utc::now().fixed_offset(),
+            original_message: message.to_string(),
+            in_main_branches: Vec::new(),
+            analysis: CommitAnalysis {
+                detected_type: "feat".to_string(),
+                detected_scope: String::new(),
+                proposed_message: message.to_string(),
+                file_changes: FileChanges {
+                    total_files: files.len(),
+                    files_added: 0,
+                    files_deleted: 0,
+                    file_list: files
+                        .iter()
+                        .map(|f| FileChange {
+                            status: "M".to_string(),
+                            file: (*f).to_string(),
+                        })
+                        .collect(),
+                },
+                diff_summary: String::new(),
+                diff_file: String::new(),
+                file_diffs: Vec::new(),
+                submodule_changes: Vec::new(),
+                dependency_updates: Vec::new(),
+            },
+        }
+    }
+}
+
 #[cfg(unix)]
 pub(crate) mod shim {
     //! Helpers for tests that write an executable shim and then `execve` it.
@@ -1,19 +1,33 @@
 //! Utility functions and helpers.
 
 pub mod ai_scratch;
+pub mod config;
+pub mod digest;
 pub mod env;
 pub(crate) mod http;
+pub mod i18n;
+pub mod interactive;
+pub(crate) mod json_store;
+pub mod logging;
+pub mod network;
+pub mod notify;
+pub mod pager;
 pub(crate) mod path;
+pub mod plain;
 pub mod preflight;
+pub mod redact;
+pub mod schema;
 pub mod secret;
+pub mod secret_helper;
 pub mod settings;
+pub mod settings_migration;
 
 pub use env::{EnvSource, SystemEnv};
 
 pub use preflight::{
-    check_ai_command_prerequisites, check_ai_credentials, check_git_repository_at,
-    check_github_cli, check_pr_command_prerequisites, check_working_directory_clean_at,
-    AiCredentialInfo, AiProvider,
+    check_ai_command_prerequisites, check_ai_credentials, check_forge_access,
+    check_git_repository_at, check_github_cli, check_pr_command_prerequisites,
+    check_working_directory_clean_at, AiCredentialInfo, AiProvider,
 };
 pub use secret::Secret;
 pub use settings::{get_env_var, get_env_vars, Settings};
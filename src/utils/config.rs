@@ -0,0 +1,868 @@
+//! Layered configuration behind `omni-dev config get/set/list/unset/path`.
+//!
+//! Every setting is a key read through [`get_env_var`](super::settings::get_env_var),
+//! resolved from three layers, highest precedence first:
+//!
+//! 1. [`ConfigLayer::Env`] — the process environment.
+//! 2. [`ConfigLayer::Project`] — `config.yaml` in the project context
+//!    directory (`.omni-dev/config.yaml`), a flat `KEY: value` map shared with
+//!    the repository.
+//! 3. [`ConfigLayer::Global`] — the `env` map in
+//!    `$HOME/.omni-dev/settings.json` (the active profile's map when one is
+//!    selected).
+//!
+//! The project file is committed alongside the code, so it is only honoured
+//! for keys marked [`ConfigKey::project`]: credentials, binary paths, and
+//! endpoints are never taken from it, so cloning a repository cannot redirect
+//! secrets or run a different executable.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+/// Project configuration file in the context directory.
+pub const PROJECT_CONFIG_FILE: &str = "config.yaml";
+
+/// A configuration layer, in precedence order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigLayer {
+    /// The process environment.
+    Env,
+    /// `.omni-dev/config.yaml`.
+    Project,
+    /// `$HOME/.omni-dev/settings.json`.
+    Global,
+}
+
+impl fmt::Display for ConfigLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env => write!(f, "env"),
+            Self::Project => write!(f, "project"),
+            Self::Global => write!(f, "global"),
+        }
+    }
+}
+
+/// A documented configuration key.
+#[derive(Debug)]
+pub struct ConfigKey {
+    /// Key name, as exported in the environment.
+    pub name: &'static str,
+    /// One-line summary shown by `config list`.
+    pub description: &'static str,
+    /// Whether the project layer may set it.
+    pub project: bool,
+    /// Whether the value is a credential, masked by `config list`.
+    pub secret: bool,
+}
+
+/// Shorthand for building [`KEYS`].
+const fn key(
+    name: &'static str,
+    description: &'static str,
+    project: bool,
+    secret: bool,
+) -> ConfigKey {
+    ConfigKey {
+        name,
+        description,
+        project,
+        secret,
+    }
+}
+
+/// Every documented key, grouped by area.
+pub const KEYS: &[ConfigKey] = &[
+    key(
+        "OMNI_DEV_AI_BACKEND",
+        "AI backend: claude, claude-cli, openai, ollama, or bedrock",
+        true,
+        false,
+    ),
+    key("OMNI_DEV_MODEL", "Model used by AI commands", true, false),
+    key(
+        "CLAUDE_MODEL",
+        "Claude model, when OMNI_DEV_MODEL is unset",
+        true,
+        false,
+    ),
+    key(
+        "CLAUDE_CODE_MODEL",
+        "Claude model, read after CLAUDE_MODEL",
+        true,
+        false,
+    ),
+    key(
+        "ANTHROPIC_MODEL",
+        "Claude model, read after CLAUDE_CODE_MODEL",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_AI_TIMEOUT_SECS",
+        "Timeout for AI API requests, in seconds",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_BETA_HEADER",
+        "Extra Anthropic beta header, as name:value",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_MODELS_YAML",
+        "Path to a model catalog replacing the layered lookup",
+        false,
+        false,
+    ),
+    key("ANTHROPIC_API_KEY", "Anthropic API key", false, true),
+    key(
+        "CLAUDE_API_KEY",
+        "Anthropic API key (legacy name)",
+        false,
+        true,
+    ),
+    key(
+        "ANTHROPIC_AUTH_TOKEN",
+        "Anthropic bearer token",
+        false,
+        true,
+    ),
+    key(
+        "CLAUDE_CODE_USE_BEDROCK",
+        "Selects the bedrock backend when true (legacy)",
+        true,
+        false,
+    ),
+    key(
+        "ANTHROPIC_BEDROCK_BASE_URL",
+        "Bedrock endpoint URL",
+        false,
+        false,
+    ),
+    key("OPENAI_API_KEY", "OpenAI API key", false, true),
+    key("OPENAI_AUTH_TOKEN", "OpenAI bearer token", false, true),
+    key(
+        "USE_OPENAI",
+        "Selects the openai backend when true (legacy)",
+        true,
+        false,
+    ),
+    key(
+        "OPENAI_MODEL",
+        "Model used by the openai backend",
+        true,
+        false,
+    ),
+    key("OLLAMA_BASE_URL", "Ollama server URL", false, false),
+    key(
+        "OLLAMA_MODEL",
+        "Model used by the ollama backend",
+        true,
+        false,
+    ),
+    key(
+        "USE_OLLAMA",
+        "Selects the ollama backend when true (legacy)",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CLAUDE_CLI_BIN",
+        "Path to the claude binary for the claude-cli backend",
+        false,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CLAUDE_CLI_TIMEOUT_SECS",
+        "claude-cli subprocess timeout, in seconds",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CLAUDE_CLI_STDOUT_MAX_BYTES",
+        "Cap on claude-cli output, in bytes",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CLAUDE_CLI_MAX_BUDGET_USD",
+        "Per-invocation spend cap for claude-cli, in USD",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CLAUDE_CLI_ALLOW_TOOLS",
+        "Lets claude-cli use tools (escape hatch)",
+        false,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CLAUDE_CLI_ALLOW_MCP",
+        "Lets claude-cli load MCP servers (escape hatch)",
+        false,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CONTEXT_TOKEN_BUDGET",
+        "Token budget for project context in AI prompts",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_COMMIT_EXAMPLES",
+        "Number of history commit messages shown as style examples",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_TICKET_CONTEXT",
+        "Ticket enrichment for AI prompts: off, auto, jira, or linear",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_REVIEW_CONTEXT",
+        "PR review discussion in AI prompts (off to disable)",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_MAX_DIFF_BYTES",
+        "Largest diff sent to the AI per commit, in bytes",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_MAX_FILE_DIFF_BYTES",
+        "Largest per-file diff sent to the AI, in bytes",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_DEFAULT_DRAFT_PR",
+        "Create pull requests as drafts by default",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_MERGE_STRATEGY",
+        "Default merge strategy for merge-pr",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_COMMIT_RANGE",
+        "Commit range for twiddle and check when none is given",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CONCURRENCY",
+        "Concurrent AI requests for twiddle and check when --concurrency is not given",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_LANG",
+        "Language of prompts and status lines: en (default) or de",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_AUTO_APPLY",
+        "Apply twiddle, create-pr, and update-pr results without confirming",
+        true,
+        false,
+    ),
+    key(
+        "OMNI_DEV_NOTIFY_WEBHOOK",
+        "Webhook URL that twiddle --notify posts to (Slack-compatible JSON)",
+        false,
+        true,
+    ),
+    key("OMNI_DEV_GH_BIN", "Path to the gh binary", false, false),
+    key("GH_TOKEN", "GitHub token for github.com", false, true),
+    key(
+        "GITHUB_TOKEN",
+        "GitHub token for github.com, read after GH_TOKEN",
+        false,
+        true,
+    ),
+    key(
+        "GH_ENTERPRISE_TOKEN",
+        "GitHub Enterprise token",
+        false,
+        true,
+    ),
+    key(
+        "GITHUB_ENTERPRISE_TOKEN",
+        "GitHub Enterprise token, read after GH_ENTERPRISE_TOKEN",
+        false,
+        true,
+    ),
+    key("GH_HOST", "GitHub Enterprise host", false, false),
+    key("GITHUB_API_URL", "GitHub API base URL", false, false),
+    key("GITEA_URL", "Gitea or Forgejo instance URLs", false, false),
+    key("GITEA_TOKEN", "Gitea or Forgejo token", false, true),
+    key("BITBUCKET_TOKEN", "Bitbucket access token", false, true),
+    key(
+        "BITBUCKET_USERNAME",
+        "Bitbucket username for app-password auth",
+        false,
+        false,
+    ),
+    key(
+        "BITBUCKET_APP_PASSWORD",
+        "Bitbucket app password",
+        false,
+        true,
+    ),
+    key(
+        "ATLASSIAN_INSTANCE_URL",
+        "Atlassian instance URL",
+        false,
+        false,
+    ),
+    key("ATLASSIAN_EMAIL", "Atlassian account email", false, false),
+    key("ATLASSIAN_API_TOKEN", "Atlassian API token", false, true),
+    key("LINEAR_API_KEY", "Linear personal API key", false, true),
+    key("DATADOG_API_KEY", "Datadog API key", false, true),
+    key("DATADOG_APP_KEY", "Datadog application key", false, true),
+    key("DATADOG_SITE", "Datadog site", false, false),
+    key(
+        "SNOWFLAKE_ACCOUNT",
+        "Default Snowflake account",
+        false,
+        false,
+    ),
+    key("SNOWFLAKE_USER", "Default Snowflake user", false, false),
+    key(
+        "SNOWFLAKE_HOST",
+        "Snowflake API host override",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_WAREHOUSE",
+        "Default Snowflake warehouse",
+        false,
+        false,
+    ),
+    key("SNOWFLAKE_ROLE", "Default Snowflake role", false, false),
+    key(
+        "SNOWFLAKE_DATABASE",
+        "Default Snowflake database",
+        false,
+        false,
+    ),
+    key("SNOWFLAKE_SCHEMA", "Default Snowflake schema", false, false),
+    key(
+        "SNOWFLAKE_POOL_SIZE",
+        "Snowflake sessions per account and user",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_HTTP_TIMEOUT",
+        "Snowflake per-request timeout, in seconds",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_AUTH_TIMEOUT",
+        "Snowflake sign-in deadline, in seconds",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_QUERY_TIMEOUT",
+        "Snowflake per-query deadline, in seconds",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_HEARTBEAT_INTERVAL",
+        "Snowflake keep-alive interval, in seconds",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_AUTHENTICATOR",
+        "Snowflake auth method",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_TOKEN",
+        "Snowflake programmatic access token",
+        false,
+        true,
+    ),
+    key(
+        "SNOWFLAKE_PRIVATE_KEY_PATH",
+        "Path to a Snowflake key-pair private key",
+        false,
+        false,
+    ),
+    key(
+        "SNOWFLAKE_PRIVATE_KEY",
+        "Inline Snowflake key-pair private key",
+        false,
+        true,
+    ),
+    key(
+        "SNOWFLAKE_PRIVATE_KEY_PASSPHRASE",
+        "Passphrase of an encrypted Snowflake private key",
+        false,
+        true,
+    ),
+    key(
+        "SNOWFLAKE_BROWSER_COMMAND",
+        "Command opening the Snowflake SSO page",
+        false,
+        false,
+    ),
+    key(
+        "HTTPS_PROXY",
+        "Proxy for HTTPS requests (may embed credentials)",
+        false,
+        true,
+    ),
+    key(
+        "HTTP_PROXY",
+        "Proxy for plain HTTP requests (may embed credentials)",
+        false,
+        true,
+    ),
+    key(
+        "ALL_PROXY",
+        "Proxy when HTTPS_PROXY or HTTP_PROXY is unset",
+        false,
+        true,
+    ),
+    key(
+        "NO_PROXY",
+        "Comma-separated hosts and domains reached without the proxy",
+        false,
+        false,
+    ),
+    key(
+        "OMNI_DEV_CA_BUNDLE",
+        "PEM bundle of certificate authorities trusted instead of the built-in roots",
+        false,
+        false,
+    ),
+    key(
+        "SSL_CERT_FILE",
+        "CA bundle, when OMNI_DEV_CA_BUNDLE is unset",
+        false,
+        false,
+    ),
+    key(
+        "OMNI_DEV_TLS_INSECURE",
+        "Disable TLS certificate verification (dangerous; prefer OMNI_DEV_CA_BUNDLE)",
+        false,
+        false,
+    ),
+];
+
+/// A variable read only from the process environment.
+#[derive(Debug)]
+pub struct EnvVar {
+    /// Variable name.
+    pub name: &'static str,
+    /// One-line summary shown by `config env`.
+    pub description: &'static str,
+}
+
+/// Shorthand for building [`ENV_ONLY`].
+const fn env_var(name: &'static str, description: &'static str) -> EnvVar {
+    EnvVar { name, description }
+}
+
+/// Variables omni-dev reads from the process environment alone, not from
+/// `config.yaml` or `settings.json`: they locate those files, configure
+/// logging or plain output before settings load, or are set by other tools.
+pub const ENV_ONLY: &[EnvVar] = &[
+    env_var(
+        "OMNI_DEV_CONFIG_DIR",
+        "Context directory, instead of walking up to .omni-dev/",
+    ),
+    env_var(
+        "OMNI_DEV_PROFILE",
+        "settings.json profile to use (--profile)",
+    ),
+    env_var(
+        "OMNI_DEV_ATLASSIAN_INSTANCE",
+        "Atlassian instance for this invocation (--instance)",
+    ),
+    env_var(
+        "OMNI_DEV_OUTPUT",
+        "Output format of report commands for this invocation (--output)",
+    ),
+    env_var(
+        "OMNI_DEV_CLAUDE_CLI_KEEP_ENV",
+        "Variables passed through to claude-cli when tools are allowed",
+    ),
+    env_var(
+        "OMNI_DEV_REMOTE_CACHE_DIR",
+        "Cache for remote `extends:` sources",
+    ),
+    env_var(
+        "OMNI_DEV_CHECK_CACHE_DIR",
+        "Cache for `git commit message check` results",
+    ),
+    env_var(
+        "OMNI_DEV_EDITOR",
+        "Editor for interactive edits, before EDITOR",
+    ),
+    env_var("EDITOR", "Editor for interactive edits"),
+    env_var("OMNI_DEV_PAGER", "Pager for long output, before PAGER"),
+    env_var("PAGER", "Pager for long output (default: less -FRX)"),
+    env_var(
+        "OMNI_DEV_NO_PAGER",
+        "Print long output without a pager (--no-pager)",
+    ),
+    env_var(
+        "OMNI_DEV_NON_INTERACTIVE",
+        "Never prompt; take the default answer (--yes)",
+    ),
+    env_var(
+        "OMNI_DEV_PLAIN",
+        "Strip emoji and ANSI color codes from all output (--plain)",
+    ),
+    env_var(
+        "NO_COLOR",
+        "Strip ANSI color codes from all output when non-empty",
+    ),
+    env_var(
+        "OMNI_DEV_SERVE_TOKEN",
+        "Bearer token for `omni-dev serve`, instead of a generated one",
+    ),
+    env_var("OMNI_DEV_LOG_FILE", "Request log path"),
+    env_var("OMNI_DEV_LOG_MAX_SIZE", "Request log size before rotation"),
+    env_var("OMNI_DEV_LOG_KEEP_FILES", "Rotated request logs to keep"),
+    env_var("RUST_LOG", "Log filter"),
+    env_var(
+        "AI_SCRATCH",
+        "Scratch directory for AI output files (git-root:<path> for repo-relative)",
+    ),
+    env_var("TMPDIR", "Scratch directory when AI_SCRATCH is unset"),
+    env_var(
+        "XDG_CONFIG_HOME",
+        "Base of the XDG config tier ($XDG_CONFIG_HOME/omni-dev)",
+    ),
+    env_var(
+        "ANTHROPIC_DEFAULT_OPUS_MODEL",
+        "claude-cli model for the opus alias",
+    ),
+    env_var(
+        "ANTHROPIC_DEFAULT_SONNET_MODEL",
+        "claude-cli model for the sonnet alias",
+    ),
+    env_var(
+        "ANTHROPIC_DEFAULT_HAIKU_MODEL",
+        "claude-cli model for the haiku alias",
+    ),
+    env_var(
+        "AWS_REGION",
+        "Region prefix for claude-cli Bedrock model ids",
+    ),
+    env_var(
+        "CLAUDE_CONFIG_DIR",
+        "Claude configuration directory watched by `sessions`",
+    ),
+    env_var(
+        "GH_CONFIG_DIR",
+        "gh configuration directory read for GitHub tokens",
+    ),
+    env_var(
+        "DATADOG_API_URL",
+        "Datadog API base URL, instead of one derived from DATADOG_SITE",
+    ),
+    env_var(
+        "GITHUB_REF",
+        "Pull request ref used by `check --comment` in GitHub Actions",
+    ),
+];
+
+/// Flag-style names the project file accepts for the keys that default
+/// command flags, so `.omni-dev/config.yaml` can read `concurrency: 8`.
+pub const FLAG_ALIASES: &[(&str, &str)] = &[
+    ("auto_apply", "OMNI_DEV_AUTO_APPLY"),
+    ("commit_range", "OMNI_DEV_COMMIT_RANGE"),
+    ("concurrency", "OMNI_DEV_CONCURRENCY"),
+    ("draft", "OMNI_DEV_DEFAULT_DRAFT_PR"),
+    ("model", "OMNI_DEV_MODEL"),
+];
+
+/// The key a project file entry sets: `name` itself, or the key a
+/// [`FLAG_ALIASES`] name stands for.
+pub fn canonical_key(name: &str) -> &str {
+    FLAG_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == name)
+        .map_or(name, |(_, key)| key)
+}
+
+/// Looks up a documented key by exact name.
+pub fn find_key(name: &str) -> Option<&'static ConfigKey> {
+    KEYS.iter().find(|key| key.name == name)
+}
+
+/// Whether `name` looks like a credential: documented as one, or named like
+/// one (`*_TOKEN`, `*_KEY`, `*SECRET*`, `*PASSWORD*`, `*PASSPHRASE*`).
+pub fn is_secret(name: &str) -> bool {
+    find_key(name).map_or_else(
+        || {
+            ["TOKEN", "_KEY", "SECRET", "PASSWORD", "PASSPHRASE"]
+                .iter()
+                .any(|marker| name.contains(marker))
+        },
+        |key| key.secret,
+    )
+}
+
+/// Masks a secret value for display, keeping only its length visible.
+pub fn mask(value: &str) -> String {
+    format!("******** ({} chars)", value.chars().count())
+}
+
+/// Checks that `name` may be written to `layer`. Unknown keys are refused
+/// unless `force` is set, so a typo does not silently become a no-op setting.
+pub fn validate_key(name: &str, layer: ConfigLayer, force: bool) -> Result<()> {
+    let known = find_key(name);
+    if known.is_none() && !force {
+        bail!(
+            "Unknown configuration key '{name}'; run `omni-dev config list --all` to see known \
+             keys, or pass --force to set it anyway"
+        );
+    }
+    if layer == ConfigLayer::Project && !known.is_some_and(|key| key.project) {
+        bail!(
+            "'{name}' cannot be set in {PROJECT_CONFIG_FILE}: project configuration is shared \
+             with everyone who clones the repository, so it never holds credentials, binary \
+             paths, or endpoints. Set it globally instead (omit --project)"
+        );
+    }
+    Ok(())
+}
+
+/// Path of the project configuration file in `context_dir`.
+pub fn project_config_path(context_dir: &Path) -> PathBuf {
+    context_dir.join(PROJECT_CONFIG_FILE)
+}
+
+/// Reads the project configuration at `path`, keeping only keys the project
+/// layer may set. A missing file is empty; malformed content and refused keys
+/// are warned about and skipped.
+pub fn load_project_config(path: &Path) -> BTreeMap<String, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+    parse_project_config(&content, path)
+}
+
+/// Parses project configuration `content`; `path` is only used in warnings.
+fn parse_project_config(content: &str, path: &Path) -> BTreeMap<String, String> {
+    if content.trim().is_empty() {
+        return BTreeMap::new();
+    }
+    let mapping = match serde_yaml::from_str::<BTreeMap<String, serde_yaml::Value>>(content) {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            tracing::warn!("Ignoring {}: {e}", path.display());
+            return BTreeMap::new();
+        }
+    };
+    let mut config = BTreeMap::new();
+    let entries = mapping.into_iter().filter_map(|(name, value)| {
+        let key = canonical_key(&name).to_string();
+        if !find_key(&key).is_some_and(|key| key.project) {
+            tracing::warn!(
+                "Ignoring '{name}' in {}: not a key the project configuration may set",
+                path.display()
+            );
+            return None;
+        }
+        let value = match value {
+            serde_yaml::Value::String(s) => s,
+            serde_yaml::Value::Bool(b) => b.to_string(),
+            serde_yaml::Value::Number(n) => n.to_string(),
+            _ => {
+                tracing::warn!(
+                    "Ignoring '{name}' in {}: expected a string, number, or boolean",
+                    path.display()
+                );
+                return None;
+            }
+        };
+        Some((name, key, value))
+    });
+    for (name, key, value) in entries {
+        if config.contains_key(&key) {
+            tracing::warn!(
+                "Ignoring '{name}' in {}: {key} is already set",
+                path.display()
+            );
+            continue;
+        }
+        config.insert(key, value);
+    }
+    config
+}
+
+/// Returns `content` (the current project file, if any) with `name` set to
+/// `value`, replacing an existing top-level entry in place or appending one.
+/// Comments and other entries are left as they are.
+pub fn set_project_value(content: Option<&str>, name: &str, value: &str) -> String {
+    // A JSON string is a valid double-quoted YAML scalar, escapes included.
+    let line = format!("{name}: {}", serde_json::Value::String(value.to_string()));
+    let mut lines: Vec<String> = content
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect();
+    match lines.iter().position(|l| is_entry_for(l, name)) {
+        Some(index) => lines[index] = line,
+        None => lines.push(line),
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Returns `content` without the top-level entry for `name`, or `None` when
+/// there was no such entry.
+pub fn unset_project_value(content: &str, name: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let index = lines.iter().position(|l| is_entry_for(l, name))?;
+    let mut out: Vec<&str> = lines[..index].to_vec();
+    out.extend(&lines[index + 1..]);
+    let mut out = out.join("\n");
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    Some(out)
+}
+
+/// Whether `line` is the top-level `name:` entry.
+fn is_entry_for(line: &str, name: &str) -> bool {
+    line.strip_prefix(name)
+        .is_some_and(|rest| rest.trim_start().starts_with(':'))
+}
+
+/// Writes `content` to the project configuration at `path`, creating the
+/// context directory when needed.
+pub fn write_project_config(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_are_unique_and_secrets_stay_out_of_projects() {
+        for (index, key) in KEYS.iter().enumerate() {
+            assert!(
+                !KEYS[..index].iter().any(|k| k.name == key.name),
+                "{} is listed twice",
+                key.name
+            );
+            assert!(
+                !(key.secret && key.project),
+                "{} is a project secret",
+                key.name
+            );
+        }
+        assert!(is_secret("ATLASSIAN_API_TOKEN"));
+        assert!(is_secret("SNOWFLAKE_PRIVATE_KEY_PASSPHRASE"));
+        assert!(!is_secret("OMNI_DEV_MODEL"));
+    }
+
+    #[test]
+    fn validates_keys_per_layer() {
+        assert!(validate_key("OMNI_DEV_MODEL", ConfigLayer::Project, false).is_ok());
+        assert!(validate_key("ANTHROPIC_API_KEY", ConfigLayer::Global, false).is_ok());
+        assert!(validate_key("ANTHROPIC_API_KEY", ConfigLayer::Project, false).is_err());
+        assert!(validate_key("OMNI_DEV_CLAUDE_CLI_BIN", ConfigLayer::Project, true).is_err());
+        assert!(validate_key("OMNI_DEV_MODLE", ConfigLayer::Global, false).is_err());
+        assert!(validate_key("OMNI_DEV_MODLE", ConfigLayer::Global, true).is_ok());
+    }
+
+    #[test]
+    fn project_config_keeps_only_project_keys() {
+        let content = "OMNI_DEV_MODEL: claude-sonnet-4-6\nOMNI_DEV_DEFAULT_DRAFT_PR: true\n\
+                       OMNI_DEV_COMMIT_EXAMPLES: 3\nOMNI_DEV_CLAUDE_CLI_BIN: ./evil\n\
+                       ANTHROPIC_API_KEY: sk-leak\nOMNI_DEV_MERGE_STRATEGY: [squash]\n";
+        let config = parse_project_config(content, Path::new("config.yaml"));
+        assert_eq!(
+            config.into_iter().collect::<Vec<_>>(),
+            [
+                ("OMNI_DEV_COMMIT_EXAMPLES".to_string(), "3".to_string()),
+                ("OMNI_DEV_DEFAULT_DRAFT_PR".to_string(), "true".to_string()),
+                (
+                    "OMNI_DEV_MODEL".to_string(),
+                    "claude-sonnet-4-6".to_string()
+                ),
+            ]
+        );
+        assert!(parse_project_config("not: [valid", Path::new("x")).is_empty());
+    }
+
+    #[test]
+    fn project_config_accepts_flag_aliases() {
+        let content = "concurrency: 8\nauto_apply: true\ndraft: false\n\
+                       OMNI_DEV_MODEL: claude-sonnet-4-6\nmodel: ignored\n";
+        let config = parse_project_config(content, Path::new("config.yaml"));
+        assert_eq!(
+            config.into_iter().collect::<Vec<_>>(),
+            [
+                ("OMNI_DEV_AUTO_APPLY".to_string(), "true".to_string()),
+                ("OMNI_DEV_CONCURRENCY".to_string(), "8".to_string()),
+                ("OMNI_DEV_DEFAULT_DRAFT_PR".to_string(), "false".to_string()),
+                (
+                    "OMNI_DEV_MODEL".to_string(),
+                    "claude-sonnet-4-6".to_string()
+                ),
+            ]
+        );
+        assert_eq!(canonical_key("commit_range"), "OMNI_DEV_COMMIT_RANGE");
+        assert_eq!(canonical_key("OMNI_DEV_MODEL"), "OMNI_DEV_MODEL");
+    }
+
+    #[test]
+    fn edits_project_values_in_place() {
+        let content = "# Shared settings\nOMNI_DEV_MODEL: old\nOMNI_DEV_MODEL_X: keep\n";
+        let updated = set_project_value(Some(content), "OMNI_DEV_MODEL", "new");
+        assert_eq!(
+            updated,
+            "# Shared settings\nOMNI_DEV_MODEL: \"new\"\nOMNI_DEV_MODEL_X: keep\n"
+        );
+        let appended = set_project_value(Some(&updated), "OMNI_DEV_COMMIT_EXAMPLES", "3");
+        assert!(appended.ends_with("OMNI_DEV_COMMIT_EXAMPLES: \"3\"\n"));
+        assert_eq!(
+            unset_project_value(&updated, "OMNI_DEV_MODEL").unwrap(),
+            "# Shared settings\nOMNI_DEV_MODEL_X: keep\n"
+        );
+        assert_eq!(unset_project_value(&updated, "OMNI_DEV_AI_BACKEND"), None);
+        assert_eq!(
+            set_project_value(None, "OMNI_DEV_MODEL", "a\"b"),
+            "OMNI_DEV_MODEL: \"a\\\"b\"\n"
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Hex digests for cache keys and content checks.
+
+use sha2::{Digest, Sha256};
+
+/// Lower-case hex encoding of a byte slice.
+///
+/// Replaces the `format!("{:x}", Sha256::digest(...))` idiom, which broke when
+/// `sha2` 0.11 changed the digest output type to `hybrid_array::Array`, which
+/// does not implement `LowerHex`.
+#[must_use]
+pub fn hex_encode(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            let _ = write!(s, "{b:02x}");
+            s
+        })
+}
+
+/// Lower-case hex SHA-256 of `bytes`.
+#[must_use]
+pub fn sha256_hex(bytes: impl AsRef<[u8]>) -> String {
+    hex_encode(&Sha256::digest(bytes.as_ref()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_hex_matches_known_digest() {
+        assert_eq!(hex_encode(&[0x00, 0xab, 0xff]), "00abff");
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Message catalog for the interactive prompts and status lines.
+//!
+//! Each language is a catalog embedded from `i18n/<lang>.ftl`, written in the
+//! single-line subset of Fluent: `key = text`, with `{ $name }` placeholders
+//! and `#` comments. `OMNI_DEV_LANG` (`de`, `de-DE`, `de_DE.UTF-8`) selects
+//! the language; a message missing from it falls back to English. Only the
+//! tool's own text is translated — the language of generated commit and PR
+//! content is set by the guidelines.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::utils::env::EnvSource;
+
+/// Variable selecting the language of prompts and status lines.
+pub const LANG_ENV_VAR: &str = "OMNI_DEV_LANG";
+
+/// A language with a bundled catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    /// English, the fallback for every other language.
+    En,
+    /// German.
+    De,
+}
+
+impl Lang {
+    /// Every bundled language.
+    pub const ALL: &'static [Self] = &[Self::En, Self::De];
+
+    /// Parses a language tag, ignoring its region and encoding (`de_DE.UTF-8`
+    /// is German); `None` when no catalog exists for it.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag
+            .trim()
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(Self::En),
+            "de" => Some(Self::De),
+            _ => None,
+        }
+    }
+
+    /// The language named by [`LANG_ENV_VAR`], else English. An unknown tag
+    /// is logged and ignored.
+    pub fn from_env(env: &impl EnvSource) -> Self {
+        let Some(tag) = env.var(LANG_ENV_VAR).filter(|v| !v.trim().is_empty()) else {
+            return Self::En;
+        };
+        Self::from_tag(&tag).unwrap_or_else(|| {
+            tracing::warn!("Ignoring {LANG_ENV_VAR}={tag}: no catalog for it, using English");
+            Self::En
+        })
+    }
+
+    /// The raw catalog source.
+    const fn source(self) -> &'static str {
+        match self {
+            Self::En => include_str!("i18n/en.ftl"),
+            Self::De => include_str!("i18n/de.ftl"),
+        }
+    }
+
+    /// The parsed catalog, built on first use.
+    fn catalog(self) -> &'static HashMap<&'static str, &'static str> {
+        static EN: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        static DE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+        let slot = match self {
+            Self::En => &EN,
+            Self::De => &DE,
+        };
+        slot.get_or_init(|| parse(self.source()))
+    }
+}
+
+/// Parses `key = text` lines, skipping blanks and `#` comments.
+fn parse(source: &'static str) -> HashMap<&'static str, &'static str> {
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, text)| (key.trim(), text.trim()))
+        .collect()
+}
+
+/// The language of this process, read from settings once.
+pub fn current() -> Lang {
+    static CURRENT: OnceLock<Lang> = OnceLock::new();
+    *CURRENT.get_or_init(|| Lang::from_env(&crate::utils::settings::SettingsEnv::load()))
+}
+
+/// The message `key` in the current language, with each `{ $name }`
+/// replaced by the argument of that name.
+pub fn tr(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    translate(current(), key, args)
+}
+
+/// The message `key` in `lang`, falling back to English and then to the key
+/// itself, so a missing message degrades instead of failing.
+pub fn translate(lang: Lang, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let template = lang
+        .catalog()
+        .get(key)
+        .or_else(|| Lang::En.catalog().get(key))
+        .copied()
+        .unwrap_or(key);
+    args.iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{ ${name} }}"), &value.to_string())
+        })
+}
+
+/// Whether `input` answers "yes" at a `[y/N]` prompt: `y`/`yes`, or the
+/// current language's own answers (`j`/`ja` in German).
+pub fn is_yes(input: &str) -> bool {
+    is_yes_in(current(), input)
+}
+
+/// [`is_yes`] for `lang`.
+fn is_yes_in(lang: Lang, input: &str) -> bool {
+    let answer = input.trim().to_lowercase();
+    [Lang::En, lang]
+        .into_iter()
+        .filter_map(|language| language.catalog().get("yes-answers"))
+        .flat_map(|answers| answers.split(','))
+        .any(|yes| yes.trim() == answer)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    /// The `{ $name }` placeholders of a message, sorted.
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split("{ $")
+            .skip(1)
+            .filter_map(|rest| rest.split_once(" }").map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn every_catalog_matches_the_english_keys_and_placeholders() {
+        let english = Lang::En.catalog();
+        for lang in Lang::ALL {
+            let catalog = lang.catalog();
+            let mut missing: Vec<&&str> = english
+                .keys()
+                .filter(|k| !catalog.contains_key(*k))
+                .collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{lang:?} lacks {missing:?}");
+            for (key, text) in catalog {
+                let reference = english
+                    .get(*key)
+                    .unwrap_or_else(|| panic!("{lang:?} has unknown {key}"));
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(reference),
+                    "{lang:?} {key}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn translate_fills_placeholders_and_falls_back() {
+        let prompt = translate(Lang::De, "confirm-ready", &[("number", &42)]);
+        assert_eq!(prompt, "PR #42 als bereit zum Review markieren? [j/N]");
+        assert_eq!(
+            translate(Lang::De, "no-such-message", &[]),
+            "no-such-message"
+        );
+    }
+
+    #[test]
+    fn language_tags_ignore_region_and_encoding() {
+        assert_eq!(Lang::from_tag("de_DE.UTF-8"), Some(Lang::De));
+        assert_eq!(Lang::from_tag("EN-us"), Some(Lang::En));
+        assert_eq!(Lang::from_tag("xx"), None);
+        let env = MapEnv::new().with(LANG_ENV_VAR, "fr");
+        assert_eq!(Lang::from_env(&env), Lang::En);
+        assert_eq!(
+            Lang::from_env(&MapEnv::new().with(LANG_ENV_VAR, "de")),
+            Lang::De
+        );
+    }
+
+    #[test]
+    fn yes_answers_accept_english_and_the_active_language() {
+        assert!(is_yes_in(Lang::De, "Ja\n"));
+        assert!(is_yes_in(Lang::De, "y"));
+        assert!(!is_yes_in(Lang::En, "j"));
+        assert!(!is_yes_in(Lang::En, ""));
+    }
+}
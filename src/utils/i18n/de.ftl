@@ -0,0 +1,25 @@
+# German catalog. Menu letters match the English ones so answers work in
+# either language.
+
+yes-answers = j, ja
+
+confirm-backport = Backport-Branch { $branch } anlegen? [j/N]
+confirm-base-change = Aktuelle Basis: { $current } → Neue Basis: { $base }. Ändern? [j/N]:
+confirm-cleanup = { $count } Branch(es) löschen? [j/N]
+confirm-cleanup-remote = { $count } Branch(es) lokal und auf origin löschen? [j/N]
+confirm-merge = PR #{ $number } mergen ({ $strategy })? [j/N]
+confirm-ready = PR #{ $number } als bereit zum Review markieren? [j/N]
+confirm-release = HEAD als { $tag } taggen, nach { $remote } pushen und das Release veröffentlichen? [j/N]
+confirm-update-pr = PR #{ $number } mit diesem Titel und dieser Beschreibung aktualisieren? [j/N]
+
+twiddle-found = { $count } Commits lassen sich verbessern.
+twiddle-saved = Änderungen gespeichert unter: { $path }
+twiddle-menu = Änderungen anwenden [a], Datei anzeigen [s], bearbeiten [e] oder beenden [q]? [A/s/e/q]
+twiddle-invalid-choice = Ungültige Auswahl. Bitte 'a' zum Anwenden, 's' zum Anzeigen, 'e' zum Bearbeiten oder 'q' zum Beenden eingeben.
+
+check-fixes-available = { $count } Commit(s) haben Probleme mit verfügbaren Korrekturvorschlägen.
+check-menu = Korrekturvorschläge anwenden [a] oder beenden [q]? [A/q]
+check-invalid-choice = Ungültige Auswahl. Bitte 'a' zum Anwenden oder 'q' zum Beenden eingeben.
+
+merge-assist-menu = Vorschlag übernehmen [a], Konflikt überspringen [s] oder beenden [q]? [a/s/q]
+merge-assist-invalid-choice = Ungültige Auswahl. Bitte 'a' zum Übernehmen, 's' zum Überspringen oder 'q' zum Beenden eingeben.
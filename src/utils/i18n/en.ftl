@@ -0,0 +1,28 @@
+# English catalog: the reference every other language is checked against.
+#
+# One `key = text` message per line; `{ $name }` is replaced by the caller's
+# argument of that name. Emoji and trailing spaces stay in the code.
+
+# Answers accepted for "yes" at a [y/N] prompt, comma-separated.
+yes-answers = y, yes
+
+confirm-backport = Create backport branch { $branch }? [y/N]
+confirm-base-change = Current base: { $current } → New base: { $base }. Change? [y/N]:
+confirm-cleanup = Delete { $count } branch(es)? [y/N]
+confirm-cleanup-remote = Delete { $count } branch(es) locally and on origin? [y/N]
+confirm-merge = Merge PR #{ $number } ({ $strategy })? [y/N]
+confirm-ready = Mark PR #{ $number } ready for review? [y/N]
+confirm-release = Tag HEAD as { $tag }, push it to { $remote }, and publish the release? [y/N]
+confirm-update-pr = Update PR #{ $number } with this title and description? [y/N]
+
+twiddle-found = Found { $count } commits that could be improved.
+twiddle-saved = Amendments saved to: { $path }
+twiddle-menu = [A]pply amendments, [S]how file, [E]dit file, or [Q]uit? [A/s/e/q]
+twiddle-invalid-choice = Invalid choice. Please enter 'a' to apply, 's' to show, 'e' to edit, or 'q' to quit.
+
+check-fixes-available = { $count } commit(s) have issues with suggested fixes available.
+check-menu = [A]pply suggested fixes, or [Q]uit? [A/q]
+check-invalid-choice = Invalid choice. Please enter 'a' to apply or 'q' to quit.
+
+merge-assist-menu = [A]ccept the proposal, [S]kip this conflict, or [Q]uit? [a/s/q]
+merge-assist-invalid-choice = Invalid choice. Please enter 'a' to accept, 's' to skip, or 'q' to quit.
@@ -0,0 +1,108 @@
+//! Non-interactive mode (`--yes` / `--non-interactive`).
+//!
+//! The global flag sets [`NON_INTERACTIVE_ENV_VAR`], which CI can also set
+//! directly. In that mode no command waits on stdin: routine confirmations
+//! take the "yes" answer ([`assume_yes`]), destructive ones are refused
+//! unless the command's own flag allows them ([`destructive_preapproved`]),
+//! optional prompts such as retrying failed commits are skipped
+//! ([`stdin_is_interactive`]), and prompts that need real input fail fast
+//! ([`ensure_interactive`]).
+//!
+//! Every command's `--auto-apply`, `--force` or `-y` confirmation resolves
+//! through one of the first two, so the per-command flags and the global
+//! mode combine the same way in every command. The git commands share one
+//! `--auto-apply` flag, `crate::cli::git::AutoApply`.
+
+use std::io::IsTerminal;
+
+use anyhow::{bail, Result};
+
+use crate::utils::env::{EnvSource, SystemEnv};
+
+/// Key enabling non-interactive mode; set by `--yes`.
+pub const NON_INTERACTIVE_ENV_VAR: &str = "OMNI_DEV_NON_INTERACTIVE";
+
+/// Whether non-interactive mode is on in `env`.
+pub fn non_interactive(env: &impl EnvSource) -> bool {
+    env.var(NON_INTERACTIVE_ENV_VAR)
+        .is_some_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+}
+
+/// Whether a routine confirmation (applying amendments, writing a PR
+/// description) is answered "yes": the command's own flag (`--auto-apply`,
+/// ...) or non-interactive mode.
+pub fn assume_yes(flag: bool) -> bool {
+    assume_yes_in(flag, &SystemEnv)
+}
+
+/// [`assume_yes`] reading non-interactive mode from `env`.
+pub fn assume_yes_in(flag: bool, env: &impl EnvSource) -> bool {
+    flag || non_interactive(env)
+}
+
+/// Whether a destructive action (deleting, merging, publishing, overwriting
+/// remote content) may go ahead without asking, which only the command's own
+/// `flag` allows. `Ok(false)` means ask. In non-interactive mode without the
+/// flag there is no one to ask, so the action is refused with an error
+/// naming `flag_name`.
+pub fn destructive_preapproved(flag: bool, flag_name: &str) -> Result<bool> {
+    destructive_preapproved_in(flag, flag_name, &SystemEnv)
+}
+
+fn destructive_preapproved_in(flag: bool, flag_name: &str, env: &impl EnvSource) -> Result<bool> {
+    if flag {
+        return Ok(true);
+    }
+    if non_interactive(env) {
+        bail!(
+            "Refusing a destructive action without confirmation; \
+             --yes/--non-interactive does not confirm it, pass {flag_name} to proceed"
+        );
+    }
+    Ok(false)
+}
+
+/// Whether optional prompts may read stdin: it is a terminal and
+/// non-interactive mode is off.
+pub fn stdin_is_interactive() -> bool {
+    !non_interactive(&SystemEnv) && std::io::stdin().is_terminal()
+}
+
+/// Fails when non-interactive mode is on; `what` names the prompt that
+/// cannot be answered with a default.
+pub fn ensure_interactive(what: &str) -> Result<()> {
+    if non_interactive(&SystemEnv) {
+        bail!("{what} needs interactive input, which --yes/--non-interactive disables");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    #[test]
+    fn non_interactive_reads_boolean_values() {
+        assert!(!non_interactive(&MapEnv::new()));
+        for value in ["true", "1", "YES", " true "] {
+            let env = MapEnv::new().with(NON_INTERACTIVE_ENV_VAR, value);
+            assert!(non_interactive(&env), "{value:?}");
+        }
+        let env = MapEnv::new().with(NON_INTERACTIVE_ENV_VAR, "false");
+        assert!(!non_interactive(&env));
+    }
+
+    #[test]
+    fn destructive_actions_need_their_own_flag() {
+        let ci = MapEnv::new().with(NON_INTERACTIVE_ENV_VAR, "true");
+        assert!(destructive_preapproved_in(true, "--force", &ci).unwrap());
+        let err = destructive_preapproved_in(false, "--force", &ci).unwrap_err();
+        assert!(err.to_string().contains("pass --force"), "{err}");
+
+        let terminal = MapEnv::new();
+        assert!(!destructive_preapproved_in(false, "--force", &terminal).unwrap());
+        assert!(destructive_preapproved_in(true, "--force", &terminal).unwrap());
+    }
+}
@@ -0,0 +1,79 @@
+//! Best-effort JSON files shared by the on-disk caches and saved jobs.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Reads the JSON file at `path`, or `None` when it is missing or does not
+/// parse. A corrupt file is logged at `tracing::debug`, prefixed with `what`.
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path, what: &str) -> Option<T> {
+    let content = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            tracing::debug!("{what}: ignoring corrupt entry {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Serializes `value` to `path` with [`write_atomic`].
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    write_atomic(path, &serde_json::to_string(value)?)
+}
+
+/// Like [`write_json`], but first drops a `.gitignore` of `*` into the parent
+/// directory, which keeps a cache out of version control without touching
+/// the project's own `.gitignore`.
+pub(crate) fn write_ignored_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let dir = parent(path)?;
+    std::fs::create_dir_all(dir)?;
+    let gitignore = dir.join(".gitignore");
+    if !gitignore.exists() {
+        std::fs::write(&gitignore, "*\n")?;
+    }
+    write_json(path, value)
+}
+
+/// Writes `contents` to `path`, creating its parent directory.
+///
+/// Write-then-rename so a concurrent reader never sees a torn file and an
+/// interrupted write leaves the previous one in place.
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let dir = parent(path)?;
+    std::fs::create_dir_all(dir)?;
+    let tmp = tempfile::NamedTempFile::new_in(dir)?;
+    std::fs::write(tmp.path(), contents)?;
+    tmp.persist(path)?;
+    Ok(())
+}
+
+fn parent(path: &Path) -> Result<&Path> {
+    path.parent()
+        .with_context(|| format!("{} has no parent directory", path.display()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_treats_corrupt_files_as_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/entry.json");
+        assert_eq!(read_json::<Vec<u32>>(&path, "test"), None);
+
+        write_ignored_json(&path, &vec![1_u32, 2]).unwrap();
+        assert_eq!(read_json::<Vec<u32>>(&path, "test"), Some(vec![1, 2]));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("nested/.gitignore")).unwrap(),
+            "*\n"
+        );
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(read_json::<Vec<u32>>(&path, "test"), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! Diagnostics logging for the `omni-dev` binary.
+//!
+//! Diagnostics are `tracing` events. They never go to stdout, which carries
+//! command output, so `--log-level debug` can be turned on without breaking
+//! a pipeline:
+//!
+//! - `--log-level` sets the filter (a level or a `RUST_LOG`-style directive
+//!   such as `omni_dev::claude=debug`), overriding `RUST_LOG`. Without either,
+//!   the binary picks its own default.
+//! - `--log-file` appends to a file instead of writing to stderr.
+//! - `--log-format json` writes one JSON object per event, for log shippers
+//!   and `jq`.
+//!
+//! Not to be confused with the request log (`omni-dev log`), which records
+//! each invocation and HTTP request regardless of these settings.
+
+use std::fmt::{self, Write as _};
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Format of diagnostics events.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+#[value(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// One human-readable line per event.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+/// How to set up diagnostics for one run.
+#[derive(Debug, Clone, Copy)]
+pub struct LogOptions<'a> {
+    /// `--log-level`, overriding `RUST_LOG`.
+    pub level: Option<&'a str>,
+    /// `--log-file`; stderr when absent.
+    pub file: Option<&'a Path>,
+    /// `--log-format`.
+    pub format: LogFormat,
+    /// Filter used when neither `--log-level` nor `RUST_LOG` is set.
+    pub default_level: &'a str,
+}
+
+/// Installs the global `tracing` subscriber described by `options`.
+///
+/// Fails on an invalid `--log-level`, on a log file that cannot be opened,
+/// or when a subscriber is already installed.
+pub fn init(options: &LogOptions<'_>) -> Result<()> {
+    let filter = match options.level {
+        Some(level) => {
+            EnvFilter::try_new(level).with_context(|| format!("Invalid --log-level '{level}'"))?
+        }
+        None => EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new(options.default_level)),
+    };
+    let writer = match options.file {
+        Some(path) => BoxMakeWriter::new(Mutex::new(open_log_file(path)?)),
+        None => BoxMakeWriter::new(std::io::stderr),
+    };
+    let builder = tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(options.file.is_none() && options.format == LogFormat::Text)
+        .with_env_filter(filter);
+    match options.format {
+        LogFormat::Text => builder.try_init(),
+        LogFormat::Json => builder.event_format(JsonLines).try_init(),
+    }
+    .map_err(|e| anyhow!("tracing subscriber already set: {e}"))
+}
+
+/// Opens `path` for appending, creating it (and on Unix restricting it to
+/// `0600`, since debug events can quote prompts and responses).
+fn open_log_file(path: &Path) -> Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))
+}
+
+/// Formats each event as a single-line JSON object: `timestamp`, `level`,
+/// `target`, the enclosing `spans` (outermost first), and the event's
+/// fields, `message` included.
+struct JsonLines;
+
+impl<S, N> FormatEvent<S, N> for JsonLines
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut record = Map::new();
+        record.insert(
+            "timestamp".to_string(),
+            Value::from(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)),
+        );
+        record.insert("level".to_string(), Value::from(meta.level().to_string()));
+        record.insert("target".to_string(), Value::from(meta.target()));
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope
+                .from_root()
+                .map(|span| Value::from(span.name()))
+                .collect();
+            record.insert("spans".to_string(), Value::Array(spans));
+        }
+        let mut fields = JsonFields(record);
+        event.record(&mut fields);
+        writeln!(writer, "{}", Value::Object(fields.0))
+    }
+}
+
+/// Collects event fields into a JSON object, keeping numbers and booleans
+/// typed.
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let mut rendered = String::new();
+        let _ = write!(rendered, "{value:?}");
+        self.0
+            .insert(field.name().to_string(), Value::from(rendered));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn json_lines_carry_level_target_spans_and_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("omni.log");
+        let file = open_log_file(&path).unwrap();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(Mutex::new(file))
+                .event_format(JsonLines),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("request");
+            let _entered = span.enter();
+            tracing::warn!(status = 529, retry = true, "AI backend overloaded");
+        });
+
+        let line = std::fs::read_to_string(&path).unwrap();
+        let record: Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(record["level"], "WARN");
+        assert_eq!(record["target"], module_path!());
+        assert_eq!(record["spans"], serde_json::json!(["request"]));
+        assert_eq!(record["message"], "AI backend overloaded");
+        assert_eq!(record["status"], 529);
+        assert_eq!(record["retry"], true);
+    }
+
+    #[test]
+    fn invalid_level_is_rejected() {
+        let err = init(&LogOptions {
+            level: Some("omni_dev=loud"),
+            file: None,
+            format: LogFormat::Text,
+            default_level: "warn",
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("Invalid --log-level"), "{err}");
+    }
+}
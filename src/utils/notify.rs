@@ -0,0 +1,208 @@
+//! Notifications for unattended runs (`twiddle --notify`).
+//!
+//! A notification goes to the desktop and, when [`NOTIFY_WEBHOOK_KEY`] is
+//! set, to a webhook. The desktop side shells out to the platform's own
+//! notifier (`notify-send` on Linux, `osascript` on macOS) rather than
+//! linking a notification library; elsewhere only the webhook is used. The
+//! webhook receives a JSON object whose `text` field is what Slack, Mattermost,
+//! and Discord-compatible incoming webhooks display.
+//!
+//! Delivery is best effort: a missing notifier or a failed webhook is logged
+//! and never fails the run being reported on.
+
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::utils::env::EnvSource;
+
+/// Key holding the webhook URL notifications are posted to.
+pub const NOTIFY_WEBHOOK_KEY: &str = "OMNI_DEV_NOTIFY_WEBHOOK";
+
+/// Why a notification is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// The run finished.
+    Completed,
+    /// The run stopped with an error.
+    Failed,
+    /// The run is waiting at a prompt.
+    NeedsInput,
+}
+
+/// One notification.
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    /// Why it is sent.
+    pub event: NotifyEvent,
+    /// The command, e.g. `twiddle`.
+    pub command: String,
+    /// One line saying what happened.
+    pub message: String,
+}
+
+impl Notification {
+    /// The notification for a finished run of `command`.
+    pub fn finished<T>(command: &str, result: &anyhow::Result<T>) -> Self {
+        let (event, message) = match result {
+            Ok(_) => (NotifyEvent::Completed, format!("{command} finished")),
+            Err(e) => (NotifyEvent::Failed, format!("{command} failed: {e}")),
+        };
+        Self {
+            event,
+            command: command.to_string(),
+            message,
+        }
+    }
+
+    /// The notification for a run of `command` waiting at a prompt.
+    pub fn needs_input(command: &str, message: impl Into<String>) -> Self {
+        Self {
+            event: NotifyEvent::NeedsInput,
+            command: command.to_string(),
+            message: message.into(),
+        }
+    }
+
+    fn title(&self) -> &'static str {
+        match self.event {
+            NotifyEvent::Completed => "omni-dev: done",
+            NotifyEvent::Failed => "omni-dev: failed",
+            NotifyEvent::NeedsInput => "omni-dev: input needed",
+        }
+    }
+
+    /// The webhook body: the notification plus a display `text`.
+    fn payload(&self) -> serde_json::Value {
+        let mut payload = serde_json::json!({
+            "text": format!("{} — {}", self.title(), self.message),
+        });
+        if let (Some(object), Ok(serde_json::Value::Object(fields))) =
+            (payload.as_object_mut(), serde_json::to_value(self))
+        {
+            object.extend(fields);
+        }
+        payload
+    }
+}
+
+/// Sends notifications to the desktop and the configured webhook.
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    webhook: Option<String>,
+}
+
+impl Notifier {
+    /// A notifier posting to the webhook in [`NOTIFY_WEBHOOK_KEY`], if any.
+    pub fn from_env(env: &impl EnvSource) -> Self {
+        Self {
+            webhook: env
+                .var(NOTIFY_WEBHOOK_KEY)
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty()),
+        }
+    }
+
+    /// Delivers `notification`, logging rather than returning failures.
+    pub async fn send(&self, notification: &Notification) {
+        show_on_desktop(notification);
+        if let Some(url) = &self.webhook {
+            if let Err(e) = post_webhook(url, notification).await {
+                tracing::warn!("Failed to post the {NOTIFY_WEBHOOK_KEY} notification: {e:#}");
+            }
+        }
+    }
+}
+
+async fn post_webhook(url: &str, notification: &Notification) -> anyhow::Result<()> {
+    reqwest::Client::new()
+        .post(url)
+        .timeout(crate::utils::http::REQUEST_TIMEOUT)
+        .json(&notification.payload())
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+/// The platform command showing `notification` on the desktop.
+fn desktop_command(notification: &Notification) -> Option<Command> {
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {} with title {}",
+            applescript_string(&notification.message),
+            applescript_string(notification.title())
+        ));
+        Some(command)
+    } else if cfg!(all(unix, not(target_os = "macos"))) {
+        let mut command = Command::new("notify-send");
+        command
+            .args(["--app-name", "omni-dev"])
+            .arg(notification.title())
+            .arg(&notification.message);
+        Some(command)
+    } else {
+        None
+    }
+}
+
+fn show_on_desktop(notification: &Notification) {
+    let Some(mut command) = desktop_command(notification) else {
+        return;
+    };
+    match command.output() {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => tracing::debug!(
+            "Desktop notification failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => tracing::debug!("No desktop notifier available: {e}"),
+    }
+}
+
+/// Quotes `text` as an AppleScript string literal.
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    #[test]
+    fn webhook_payload_has_display_text_and_fields() {
+        let result: anyhow::Result<()> = Err(anyhow::anyhow!("AI backend overloaded"));
+        let payload = Notification::finished("twiddle", &result).payload();
+        assert_eq!(
+            payload["text"],
+            "omni-dev: failed — twiddle failed: AI backend overloaded"
+        );
+        assert_eq!(payload["event"], "failed");
+        assert_eq!(payload["command"], "twiddle");
+
+        let payload = Notification::needs_input("twiddle", "12 amendments to review").payload();
+        assert_eq!(payload["event"], "needs_input");
+    }
+
+    #[test]
+    fn webhook_comes_from_settings() {
+        assert!(Notifier::from_env(&MapEnv::new()).webhook.is_none());
+        let env = MapEnv::new().with(NOTIFY_WEBHOOK_KEY, " https://hooks.example/T1 ");
+        assert_eq!(
+            Notifier::from_env(&env).webhook.as_deref(),
+            Some("https://hooks.example/T1")
+        );
+    }
+
+    #[test]
+    fn applescript_strings_are_escaped() {
+        assert_eq!(
+            applescript_string(r#"say "hi" \ bye"#),
+            r#""say \"hi\" \\ bye""#
+        );
+    }
+}
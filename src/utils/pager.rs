@@ -0,0 +1,142 @@
+//! Pager for long command output.
+//!
+//! [`page`] sends a finished block of output (a check report, an amendments
+//! preview) through a pager when stdout is a terminal, so thousands of lines
+//! don't scroll past; [`page_stream`] does the same for output written as it
+//! is produced, such as a repository view. The pager is `OMNI_DEV_PAGER`,
+//! then `PAGER`, then `less -FRX`: `-F` exits at once when the text fits on
+//! one screen, `-R` keeps colors, and `-X` leaves the text on screen
+//! afterwards.
+//!
+//! Output is printed directly when stdout is not a terminal (pipes, CI,
+//! `--plain`), with `--no-pager` (`OMNI_DEV_NO_PAGER=true`), or when the
+//! pager is empty or `cat`, as with git.
+
+use std::io::{BufWriter, ErrorKind, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+
+use crate::utils::env::{EnvSource, SystemEnv};
+
+/// Key selecting the pager, before `PAGER`.
+pub const PAGER_ENV_VAR: &str = "OMNI_DEV_PAGER";
+
+/// Key disabling the pager; set by `--no-pager`.
+pub const NO_PAGER_ENV_VAR: &str = "OMNI_DEV_NO_PAGER";
+
+/// Pager used when neither `OMNI_DEV_PAGER` nor `PAGER` is set.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// The pager command line, or `None` when output should be printed directly.
+fn pager_command(env: &impl EnvSource, is_terminal: bool) -> Option<String> {
+    let disabled = env
+        .var(NO_PAGER_ENV_VAR)
+        .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"));
+    if !is_terminal || disabled {
+        return None;
+    }
+    let command = env
+        .var(PAGER_ENV_VAR)
+        .or_else(|| env.var("PAGER"))
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let command = command.trim();
+    (!command.is_empty() && command != "cat").then(|| command.to_string())
+}
+
+/// Prints `text` through the pager, or directly when paging is off. A
+/// trailing newline is added when missing, as `println!` would.
+pub fn page(text: &str) -> Result<()> {
+    page_stream(|out| {
+        out.write_all(text.as_bytes())?;
+        if !text.ends_with('\n') {
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    })
+}
+
+/// Like [`page`], but `write` produces the output straight into the pager
+/// (or stdout) as it goes, so a large document is never held whole in
+/// memory. `write` ends the output with a newline itself.
+pub fn page_stream(write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    let command = pager_command(&SystemEnv, std::io::stdout().is_terminal());
+    let Some(command) = command else {
+        return print_direct(write);
+    };
+    let mut words = command.split_whitespace();
+    let program = words.next().unwrap_or(DEFAULT_PAGER);
+    let Ok(mut child) = Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+    else {
+        tracing::debug!("Pager '{command}' could not be started; printing directly");
+        return print_direct(write);
+    };
+    if let Some(stdin) = child.stdin.take() {
+        let mut stdin = BufWriter::new(stdin);
+        let written = write(&mut stdin).and_then(|()| Ok(stdin.flush()?));
+        ignore_broken_pipe(written).context("Failed to write to the pager")?;
+    }
+    child.wait().context("Failed to wait for the pager")?;
+    Ok(())
+}
+
+fn print_direct(write: impl FnOnce(&mut dyn Write) -> Result<()>) -> Result<()> {
+    let mut stdout = BufWriter::new(std::io::stdout().lock());
+    let written = write(&mut stdout).and_then(|()| Ok(stdout.flush()?));
+    ignore_broken_pipe(written).context("Failed to write to stdout")
+}
+
+/// Treats a closed reader — the user quit the pager, or piped into `head` —
+/// as success.
+fn ignore_broken_pipe(result: Result<()>) -> Result<()> {
+    match result {
+        Err(e)
+            if e.chain().any(|cause| {
+                cause
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|io| io.kind() == ErrorKind::BrokenPipe)
+            }) =>
+        {
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    #[test]
+    fn pager_resolution_order() {
+        let env = MapEnv::new()
+            .with(PAGER_ENV_VAR, "bat --paging=always")
+            .with("PAGER", "more");
+        assert_eq!(
+            pager_command(&env, true).as_deref(),
+            Some("bat --paging=always")
+        );
+        let env = MapEnv::new().with("PAGER", "more");
+        assert_eq!(pager_command(&env, true).as_deref(), Some("more"));
+        assert_eq!(
+            pager_command(&MapEnv::new(), true).as_deref(),
+            Some(DEFAULT_PAGER)
+        );
+    }
+
+    #[test]
+    fn paging_is_off_without_a_terminal_or_when_disabled() {
+        assert_eq!(pager_command(&MapEnv::new(), false), None);
+        let env = MapEnv::new().with(NO_PAGER_ENV_VAR, "true");
+        assert_eq!(pager_command(&env, true), None);
+        let env = MapEnv::new().with("PAGER", "cat");
+        assert_eq!(pager_command(&env, true), None);
+        let env = MapEnv::new().with(PAGER_ENV_VAR, "");
+        assert_eq!(pager_command(&env, true), None);
+    }
+}
@@ -0,0 +1,389 @@
+//! Plain output mode: emoji and ANSI escape codes stripped from all output.
+//!
+//! Progress and result lines use emoji and hard-coded ANSI colors, which CI
+//! log viewers render as mojibake. Rather than threading a flag through every
+//! `println!`, [`install`] redirects the process's stdout and stderr through
+//! pipes and a [`Sanitizer`] thread each, so every writer — including tracing
+//! and child processes that inherit the descriptors — is filtered.
+//!
+//! `--plain` or `OMNI_DEV_PLAIN=true` strips both emoji and escape codes.
+//! `NO_COLOR` (see <https://no-color.org>) only strips escape codes: it is
+//! commonly set globally, and emoji are data in commit messages and JSON
+//! output, so it must not rewrite them.
+//!
+//! A few status glyphs carry meaning and become tags (`✅` → `[ok]`, `❌` →
+//! `[error]`, `⚠️` → `[warn]`); other emoji are dropped with the spaces that
+//! follow them.
+
+use crate::utils::env::EnvSource;
+
+/// Key enabling plain output.
+pub const PLAIN_ENV_VAR: &str = "OMNI_DEV_PLAIN";
+
+/// What the output filter removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Output is passed through untouched.
+    Off,
+    /// ANSI escape codes are removed (`NO_COLOR`).
+    StripAnsi,
+    /// ANSI escape codes and emoji are removed (`--plain`).
+    Plain,
+}
+
+/// Resolves the mode from the `--plain` flag and `env`.
+pub fn mode(flag: bool, env: &impl EnvSource) -> Mode {
+    // The flag settles it without consulting `env`, which may read settings.
+    let plain = flag
+        || env
+            .var(PLAIN_ENV_VAR)
+            .is_some_and(|v| matches!(v.to_lowercase().as_str(), "true" | "1" | "yes"));
+    if plain {
+        Mode::Plain
+    } else if env.var("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        Mode::StripAnsi
+    } else {
+        Mode::Off
+    }
+}
+
+/// Where the sanitizer is within an ANSI escape sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// After `ESC`.
+    Start,
+    /// Inside a CSI sequence (`ESC [`), until its final byte.
+    Csi,
+    /// Inside an OSC string (`ESC ]`), until `BEL` or `ESC \`.
+    Osc,
+    /// After `ESC` inside an OSC string.
+    OscEscape,
+}
+
+/// Streaming filter over output bytes. Incomplete UTF-8 characters and
+/// escape sequences are carried over between chunks, so the input may be
+/// split anywhere.
+#[derive(Debug)]
+pub struct Sanitizer {
+    mode: Mode,
+    pending: Vec<u8>,
+    escape: Escape,
+    /// Set after a removed or replaced glyph: the spaces that follow it are
+    /// skipped, and one is re-emitted before the next text when `true`.
+    after_glyph: Option<bool>,
+}
+
+impl Sanitizer {
+    /// Creates a sanitizer for `mode`.
+    pub fn new(mode: Mode) -> Self {
+        Self {
+            mode,
+            pending: Vec::new(),
+            escape: Escape::None,
+            after_glyph: None,
+        }
+    }
+
+    /// Filters the next chunk of output.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<u8> {
+        self.pending.extend_from_slice(bytes);
+        let input = std::mem::take(&mut self.pending);
+        let mut out = Vec::with_capacity(input.len());
+        let mut rest = input.as_slice();
+        while !rest.is_empty() {
+            match std::str::from_utf8(rest) {
+                Ok(text) => {
+                    self.push_str(text, &mut out);
+                    rest = &[];
+                }
+                Err(e) => {
+                    let (valid, after) = rest.split_at(e.valid_up_to());
+                    // `valid_up_to` bytes are valid UTF-8 by definition.
+                    self.push_str(std::str::from_utf8(valid).unwrap_or_default(), &mut out);
+                    match e.error_len() {
+                        Some(len) => {
+                            out.extend_from_slice(&after[..len]);
+                            rest = &after[len..];
+                        }
+                        None => {
+                            self.pending = after.to_vec();
+                            rest = &[];
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Flushes bytes held back for an incomplete character.
+    pub fn finish(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.pending)
+    }
+
+    fn push_str(&mut self, text: &str, out: &mut Vec<u8>) {
+        let mut buf = [0; 4];
+        for c in text.chars() {
+            if self.mode != Mode::Off && self.in_escape(c) {
+                continue;
+            }
+            if self.mode == Mode::Plain && !self.keep_glyph(c, out) {
+                continue;
+            }
+            out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+    }
+
+    /// Advances the escape-sequence state; returns whether `c` belongs to a
+    /// sequence and is dropped.
+    fn in_escape(&mut self, c: char) -> bool {
+        self.escape = match (self.escape, c) {
+            (Escape::None, '\x1b') => Escape::Start,
+            (Escape::None, _) => return false,
+            (Escape::Start, '[') => Escape::Csi,
+            (Escape::Start, ']') => Escape::Osc,
+            (Escape::Start, _) => Escape::None,
+            (Escape::Csi, '\x40'..='\x7e') => Escape::None,
+            (Escape::Csi, _) => Escape::Csi,
+            (Escape::Osc, '\x07') => Escape::None,
+            (Escape::Osc | Escape::OscEscape, '\x1b') => Escape::OscEscape,
+            (Escape::OscEscape, '\\') => Escape::None,
+            (Escape::Osc | Escape::OscEscape, _) => Escape::Osc,
+        };
+        true
+    }
+
+    /// Handles emoji; returns whether `c` itself should be written.
+    fn keep_glyph(&mut self, c: char, out: &mut Vec<u8>) -> bool {
+        if is_emoji_modifier(c) {
+            return false;
+        }
+        if let Some(tag) = replacement(c) {
+            out.extend_from_slice(tag.as_bytes());
+            self.after_glyph = Some(true);
+            return false;
+        }
+        if is_emoji(c) {
+            self.after_glyph = Some(self.after_glyph.unwrap_or(false));
+            return false;
+        }
+        match self.after_glyph {
+            Some(_) if c == ' ' => false,
+            Some(space) => {
+                if space && c != '\n' {
+                    out.push(b' ');
+                }
+                self.after_glyph = None;
+                true
+            }
+            None => true,
+        }
+    }
+}
+
+/// ASCII tags for the status glyphs whose meaning should survive.
+fn replacement(c: char) -> Option<&'static str> {
+    match c {
+        '✅' | '✔' | '✓' => Some("[ok]"),
+        '❌' | '✖' | '✗' => Some("[error]"),
+        '⚠' => Some("[warn]"),
+        _ => None,
+    }
+}
+
+/// Whether `c` is an emoji or pictographic symbol.
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c,
+        '\u{1F000}'..='\u{1FAFF}'
+            | '\u{2300}'..='\u{23FF}'
+            | '\u{2600}'..='\u{27BF}'
+            | '\u{2B00}'..='\u{2BFF}'
+            | '\u{2139}'
+            | '\u{25B6}'
+            | '\u{25C0}'
+    )
+}
+
+/// Whether `c` only modifies the preceding emoji (variation selectors,
+/// zero-width joiner, keycap, tags).
+fn is_emoji_modifier(c: char) -> bool {
+    matches!(
+        c,
+        '\u{FE0E}' | '\u{FE0F}' | '\u{200D}' | '\u{20E3}' | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+#[cfg(unix)]
+mod redirect {
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::os::fd::OwnedFd;
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    use anyhow::{Context, Result};
+    use nix::unistd::{dup, dup2_stderr, dup2_stdout, pipe};
+
+    use super::{Mode, Sanitizer};
+
+    /// One redirected stream: the original descriptor to restore and the
+    /// filter thread's completion signal.
+    struct Stream {
+        original: OwnedFd,
+        done: mpsc::Receiver<()>,
+    }
+
+    static ACTIVE: Mutex<Option<[Stream; 2]>> = Mutex::new(None);
+
+    /// Starts filtering stdout and stderr for `mode`.
+    pub fn install(mode: Mode) -> Result<()> {
+        if mode == Mode::Off {
+            return Ok(());
+        }
+        let stdout = redirect(mode, &std::io::stdout(), dup2_stdout::<&OwnedFd>)?;
+        let stderr = redirect(mode, &std::io::stderr(), dup2_stderr::<&OwnedFd>)?;
+        if let Ok(mut active) = ACTIVE.lock() {
+            *active = Some([stdout, stderr]);
+        }
+        Ok(())
+    }
+
+    fn redirect(
+        mode: Mode,
+        stream: &impl std::os::fd::AsFd,
+        replace: impl Fn(&OwnedFd) -> nix::Result<()>,
+    ) -> Result<Stream> {
+        let original = dup(stream).context("Failed to duplicate an output stream")?;
+        let target = dup(&original).context("Failed to duplicate an output stream")?;
+        let (read, write) = pipe().context("Failed to create an output pipe")?;
+        replace(&write).context("Failed to redirect an output stream")?;
+        drop(write);
+
+        let (tx, done) = mpsc::channel();
+        thread::spawn(move || {
+            let mut reader = File::from(read);
+            let mut writer = File::from(target);
+            let mut sanitizer = Sanitizer::new(mode);
+            let mut buf = [0; 8192];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                let _ = writer.write_all(&sanitizer.feed(&buf[..n]));
+            }
+            let _ = writer.write_all(&sanitizer.finish());
+            let _ = tx.send(());
+        });
+        Ok(Stream { original, done })
+    }
+
+    /// Restores the original stdout and stderr and waits briefly for the
+    /// filter threads to drain. A child process still holding the pipe (a
+    /// detached daemon) would keep it open, so the wait is bounded.
+    pub fn finish() {
+        let Some(streams) = ACTIVE.lock().ok().and_then(|mut active| active.take()) else {
+            return;
+        };
+        let _ = std::io::stdout().flush();
+        let _ = std::io::stderr().flush();
+        let [stdout, stderr] = streams;
+        let _ = dup2_stdout(&stdout.original);
+        let _ = dup2_stderr(&stderr.original);
+        for stream in [stdout, stderr] {
+            let _ = stream.done.recv_timeout(Duration::from_secs(2));
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod redirect {
+    use anyhow::Result;
+
+    use super::Mode;
+
+    /// Output redirection needs Unix file descriptors; elsewhere plain mode
+    /// is a no-op.
+    pub fn install(mode: Mode) -> Result<()> {
+        if mode != Mode::Off {
+            tracing::warn!("Plain output is only supported on Unix");
+        }
+        Ok(())
+    }
+
+    pub fn finish() {}
+}
+
+pub use redirect::{finish, install};
+
+/// Flushes plain output and exits with `code`. Use instead of
+/// [`std::process::exit`] so the last lines are not lost in the filter.
+pub fn exit(code: i32) -> ! {
+    finish();
+    std::process::exit(code)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use crate::test_support::env::MapEnv;
+
+    fn filter(mode: Mode, chunks: &[&[u8]]) -> String {
+        let mut sanitizer = Sanitizer::new(mode);
+        let mut out = Vec::new();
+        for chunk in chunks {
+            out.extend(sanitizer.feed(chunk));
+        }
+        out.extend(sanitizer.finish());
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn plain_strips_emoji_and_colors() {
+        let text = "🔍 Validating 3 file(s)\n   📡 Model: \x1b[33mclaude\x1b[0m\n\
+                    ✅ Done\n⚠️  Skipped\n❌ config.yaml:2: bad\n🏷️  Labels: a → b\n";
+        assert_eq!(
+            filter(Mode::Plain, &[text.as_bytes()]),
+            "Validating 3 file(s)\n   Model: claude\n[ok] Done\n[warn] Skipped\n\
+             [error] config.yaml:2: bad\nLabels: a → b\n"
+        );
+    }
+
+    #[test]
+    fn no_color_keeps_emoji() {
+        let text = "\x1b[31mERROR\x1b[0m 🚀 \x1b]8;;https://x\x07link\x1b]8;;\x1b\\\n";
+        assert_eq!(
+            filter(Mode::StripAnsi, &[text.as_bytes()]),
+            "ERROR 🚀 link\n"
+        );
+        assert_eq!(filter(Mode::Off, &[text.as_bytes()]), text);
+    }
+
+    #[test]
+    fn sequences_split_across_chunks_are_handled() {
+        let text = "✅ ok \x1b[33mwarn\x1b[0m\n".as_bytes();
+        let chunks: Vec<&[u8]> = text.chunks(1).collect();
+        assert_eq!(filter(Mode::Plain, &chunks), "[ok] ok warn\n");
+        // Invalid UTF-8 passes through untouched.
+        assert_eq!(Sanitizer::new(Mode::Plain).feed(b"a\xffb"), b"a\xffb");
+    }
+
+    #[test]
+    fn mode_follows_flag_and_environment() {
+        assert_eq!(mode(true, &MapEnv::new()), Mode::Plain);
+        assert_eq!(
+            mode(false, &MapEnv::new().with(PLAIN_ENV_VAR, "1")),
+            Mode::Plain
+        );
+        assert_eq!(
+            mode(false, &MapEnv::new().with("NO_COLOR", "1")),
+            Mode::StripAnsi
+        );
+        assert_eq!(mode(false, &MapEnv::new().with("NO_COLOR", "")), Mode::Off);
+        assert_eq!(mode(false, &MapEnv::new()), Mode::Off);
+    }
+}
@@ -218,6 +218,10 @@ pub(crate) fn check_ai_credentials_with(
 /// `repo_root` anchors the repository-access probe to the injected repository
 /// rather than the process current working directory.
 pub fn check_github_cli(repo_root: &std::path::Path) -> Result<()> {
+    let forge = crate::forge::Forge::detect(repo_root);
+    let host = forge
+        .github_host()
+        .unwrap_or(crate::github_api::GITHUB_HOST);
     let gh_bin = crate::pr_status::resolve_gh_binary();
     // Check if gh CLI is available. This probe is a PATH availability check
     // (CWD-independent), so it is not anchored to `repo_root`. `--version` is a
@@ -240,12 +244,12 @@ pub fn check_github_cli(repo_root: &std::path::Path) -> Result<()> {
                     let error_details = String::from_utf8_lossy(&repo_output.stderr);
                     if error_details.contains("authentication") || error_details.contains("login") {
                         bail!(
-                            "GitHub CLI authentication failed.\n\
-                             Please run 'gh auth login' or set GITHUB_TOKEN environment variable."
+                            "GitHub CLI authentication failed for {host}.\n{}",
+                            gh_login_hint(host)
                         )
                     }
                     bail!(
-                        "GitHub CLI cannot access this repository.\n\
+                        "GitHub CLI cannot access this repository on {host}.\n\
                          Error: {}",
                         error_details.trim()
                     )
@@ -260,6 +264,18 @@ pub fn check_github_cli(repo_root: &std::path::Path) -> Result<()> {
     }
 }
 
+/// Tells the user how to authenticate `gh` (or omni-dev) against `host`.
+fn gh_login_hint(host: &str) -> String {
+    if host == crate::github_api::GITHUB_HOST {
+        "Please run 'gh auth login' or set GITHUB_TOKEN environment variable.".to_string()
+    } else {
+        format!(
+            "Please run 'gh auth login --hostname {host}' or set GH_ENTERPRISE_TOKEN \
+             environment variable (and GH_HOST={host})."
+        )
+    }
+}
+
 /// Validates that `repo_root` is a valid git repository.
 ///
 /// A lightweight check that opens the repository without loading commit data.
@@ -322,28 +338,50 @@ pub fn check_ai_command_prerequisites(
 /// Validates:
 /// - Git repository access
 /// - AI credentials
-/// - GitHub CLI availability and authentication
+/// - GitHub CLI availability and authentication, or forge API access when
+///   the `origin` remote is hosted on Bitbucket Cloud, Gitea, or Forgejo
 ///
 /// Returns information about the AI provider that will be used.
 ///
 /// `repo_root` anchors the git-repository and GitHub CLI checks to the injected
 /// repository rather than the process current working directory.
-pub fn check_pr_command_prerequisites(
+pub async fn check_pr_command_prerequisites(
     model_override: Option<&str>,
     repo_root: &std::path::Path,
 ) -> Result<AiCredentialInfo> {
     check_git_repository_at(repo_root)?;
     let ai_info = check_ai_credentials(model_override)?;
-    check_github_cli(repo_root)?;
+    check_forge_access(&crate::forge::Forge::detect(repo_root), repo_root).await?;
     Ok(ai_info)
 }
 
+/// Validates pull request access for `forge`: the GitHub CLI for GitHub,
+/// otherwise that the forge's API credentials are configured and can read
+/// the repository.
+pub async fn check_forge_access(
+    forge: &crate::forge::Forge,
+    repo_root: &std::path::Path,
+) -> Result<()> {
+    match forge.api_client()? {
+        Some(api) => api.verify_access().await,
+        None => check_github_cli(repo_root),
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
     use crate::test_support::env::MapEnv;
 
+    #[test]
+    fn gh_login_hint_names_enterprise_host() {
+        assert!(gh_login_hint("github.com").contains("GITHUB_TOKEN"));
+        let hint = gh_login_hint("github.example.com");
+        assert!(hint.contains("--hostname github.example.com"));
+        assert!(hint.contains("GH_ENTERPRISE_TOKEN"));
+    }
+
     #[test]
     fn ai_provider_display() {
         assert_eq!(format!("{}", AiProvider::Claude), "Claude API");
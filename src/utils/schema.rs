@@ -0,0 +1,648 @@
+//! Validation of configuration files against JSON Schemas.
+//!
+//! Covers the subset of JSON Schema that `schemars` emits for the
+//! configuration types: `type`, `properties` / `required` /
+//! `additionalProperties`, `items`, `enum` / `const`, `anyOf` / `oneOf` /
+//! `allOf`, `minimum`, and `$ref`s into the document's own `$defs`. `oneOf` is
+//! checked like `anyOf`; the derived schemas only use it for mutually
+//! exclusive constants.
+//!
+//! Errors carry the path to the offending value, and [`locate_line`] maps
+//! that path back onto the source text, so `omni-dev config validate` can
+//! point at `file:line` for YAML and JSON alike.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+/// One step of the path from the document root to a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A key of an object.
+    Key(String),
+    /// An element of an array.
+    Index(usize),
+}
+
+/// A value that does not conform to its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// Path to the offending value; for an unknown key, the key itself.
+    pub path: Vec<PathSegment>,
+    /// What is wrong with the value.
+    pub message: String,
+}
+
+impl SchemaError {
+    /// Renders the path as `scopes[0].name`, or `(root)` for the document.
+    pub fn pointer(&self) -> String {
+        let mut pointer = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Key(key) if pointer.is_empty() => pointer.push_str(key),
+                PathSegment::Key(key) => {
+                    pointer.push('.');
+                    pointer.push_str(key);
+                }
+                PathSegment::Index(index) => pointer.push_str(&format!("[{index}]")),
+            }
+        }
+        if pointer.is_empty() {
+            pointer.push_str("(root)");
+        }
+        pointer
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.pointer(), self.message)
+    }
+}
+
+/// Syntax of the document passed to [`locate_line`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// Block-style YAML.
+    Yaml,
+    /// JSON.
+    Json,
+}
+
+/// Validates `value` against `schema`, returning every violation found.
+pub fn validate(schema: &Value, value: &Value) -> Vec<SchemaError> {
+    let mut errors = Vec::new();
+    check(schema, schema, value, &mut Vec::new(), &mut errors);
+    errors
+}
+
+/// Validates `value` against `schema`, resolving `$ref`s against `root`.
+fn check(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &mut Vec<PathSegment>,
+    errors: &mut Vec<SchemaError>,
+) {
+    let keywords = match schema {
+        Value::Object(keywords) => keywords,
+        Value::Bool(false) => {
+            push(errors, path, "no value is allowed here".to_string());
+            return;
+        }
+        _ => return,
+    };
+
+    if let Some(target) = keywords
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| resolve_ref(root, reference))
+    {
+        check(root, target, value, path, errors);
+    }
+    if let Some(all) = keywords.get("allOf").and_then(Value::as_array) {
+        for branch in all {
+            check(root, branch, value, path, errors);
+        }
+    }
+    if let Some(branches) = keywords
+        .get("anyOf")
+        .or_else(|| keywords.get("oneOf"))
+        .and_then(Value::as_array)
+    {
+        check_alternatives(root, branches, value, path, errors);
+    }
+
+    if let Some(expected) = keywords.get("type") {
+        if !type_matches(expected, value) {
+            let message = format!(
+                "expected {}, found {}",
+                describe_type(expected),
+                type_name(value)
+            );
+            push(errors, path, message);
+            return;
+        }
+    }
+    if let Some(allowed) = allowed_values(keywords) {
+        if !allowed.contains(value) {
+            push(errors, path, describe_choices(&allowed, value));
+            return;
+        }
+    }
+    if let (Some(minimum), Some(actual)) = (
+        keywords.get("minimum").and_then(Value::as_f64),
+        value.as_f64(),
+    ) {
+        if actual < minimum {
+            push(errors, path, format!("must be at least {minimum}"));
+        }
+    }
+
+    match value {
+        Value::Object(map) => check_object(root, keywords, map, path, errors),
+        Value::Array(items) => {
+            if let Some(item_schema) = keywords.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    path.push(PathSegment::Index(index));
+                    check(root, item_schema, item, path, errors);
+                    path.pop();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Validates the keys of an object against `properties`, `required`, and
+/// `additionalProperties`.
+fn check_object(
+    root: &Value,
+    keywords: &Map<String, Value>,
+    map: &Map<String, Value>,
+    path: &mut Vec<PathSegment>,
+    errors: &mut Vec<SchemaError>,
+) {
+    let properties = keywords.get("properties").and_then(Value::as_object);
+    for (key, child) in map {
+        path.push(PathSegment::Key(key.clone()));
+        match (
+            properties.and_then(|p| p.get(key)),
+            keywords.get("additionalProperties"),
+        ) {
+            (Some(property), _) => check(root, property, child, path, errors),
+            (None, Some(Value::Bool(false))) => {
+                let mut message = format!("unknown key '{key}'");
+                if let Some(suggestion) =
+                    properties.and_then(|p| closest(key, p.keys().map(String::as_str)))
+                {
+                    message.push_str(&format!(" (did you mean '{suggestion}'?)"));
+                }
+                push(errors, path, message);
+            }
+            (None, Some(additional)) => check(root, additional, child, path, errors),
+            (None, None) => {}
+        }
+        path.pop();
+    }
+    for name in keywords
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+    {
+        if !map.contains_key(name) {
+            push(errors, path, format!("missing required key '{name}'"));
+        }
+    }
+}
+
+/// Validates `value` against the first fitting `anyOf` / `oneOf` branch.
+///
+/// A branch fits when the value has the branch's type (or one of its
+/// constants); its errors are reported, so an `Option<Struct>` with a typo
+/// inside points at the typo rather than at "expected null". When no branch
+/// fits, a single error lists what would have.
+fn check_alternatives(
+    root: &Value,
+    branches: &[Value],
+    value: &Value,
+    path: &mut Vec<PathSegment>,
+    errors: &mut Vec<SchemaError>,
+) {
+    let mut best: Option<Vec<SchemaError>> = None;
+    for branch in branches.iter().filter(|b| fits(root, b, value)) {
+        let mut branch_errors = Vec::new();
+        check(root, branch, value, path, &mut branch_errors);
+        if branch_errors.is_empty() {
+            return;
+        }
+        match &best {
+            Some(fewest) if fewest.len() <= branch_errors.len() => {}
+            _ => best = Some(branch_errors),
+        }
+    }
+    if let Some(best) = best {
+        errors.extend(best);
+        return;
+    }
+
+    let constants: Option<Vec<Value>> = branches
+        .iter()
+        .map(|b| {
+            resolve(root, b)
+                .and_then(Value::as_object)
+                .and_then(allowed_values)
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|sets| sets.into_iter().flatten().collect());
+    let message = match constants {
+        Some(constants) => describe_choices(&constants, value),
+        None => {
+            let expected: Vec<String> = branches
+                .iter()
+                .filter_map(|b| resolve(root, b)?.get("type"))
+                .map(describe_type)
+                .collect();
+            format!(
+                "expected {}, found {}",
+                expected.join(" or "),
+                type_name(value)
+            )
+        }
+    };
+    push(errors, path, message);
+}
+
+/// Whether `value` matches the top-level `type`, `enum`, and `const` of
+/// `schema`, ignoring anything nested.
+fn fits(root: &Value, schema: &Value, value: &Value) -> bool {
+    let Some(keywords) = resolve(root, schema).and_then(Value::as_object) else {
+        return true;
+    };
+    if let Some(expected) = keywords.get("type") {
+        if !type_matches(expected, value) {
+            return false;
+        }
+    }
+    match allowed_values(keywords) {
+        Some(allowed) => allowed.contains(value),
+        None => true,
+    }
+}
+
+/// Follows a top-level `$ref` of `schema`, if any.
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> Option<&'a Value> {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => resolve_ref(root, reference),
+        None => Some(schema),
+    }
+}
+
+/// Resolves a document-local reference such as `#/$defs/ScopeDefinition`.
+fn resolve_ref<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+/// The values permitted by `enum` or `const`, if either is present.
+fn allowed_values(keywords: &Map<String, Value>) -> Option<Vec<Value>> {
+    if let Some(constant) = keywords.get("const") {
+        return Some(vec![constant.clone()]);
+    }
+    keywords.get("enum").and_then(Value::as_array).cloned()
+}
+
+fn type_matches(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => type_is(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| type_is(name, value)),
+        _ => true,
+    }
+}
+
+fn type_is(name: &str, value: &Value) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        _ => true,
+    }
+}
+
+/// Describes a `type` keyword for messages ("a string or null").
+fn describe_type(expected: &Value) -> String {
+    let names: Vec<&str> = match expected {
+        Value::String(name) => vec![name.as_str()],
+        Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .map(|name| match name {
+            "null" => "null",
+            "boolean" => "a boolean",
+            "string" => "a string",
+            "array" => "a list",
+            "object" => "a mapping",
+            "integer" => "an integer",
+            _ => "a number",
+        })
+        .collect::<Vec<_>>()
+        .join(" or ")
+}
+
+/// Describes the JSON type of `value` for messages.
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "a mapping",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "an integer",
+        Value::Number(_) => "a number",
+    }
+}
+
+fn describe_choices(allowed: &[Value], value: &Value) -> String {
+    let choices: Vec<String> = allowed.iter().map(Value::to_string).collect();
+    format!("expected one of {}, found {value}", choices.join(", "))
+}
+
+fn push(errors: &mut Vec<SchemaError>, path: &[PathSegment], message: String) {
+    errors.push(SchemaError {
+        path: path.to_vec(),
+        message,
+    });
+}
+
+/// The candidate closest to `key` by edit distance, when it is close enough
+/// to be a likely typo.
+pub fn closest<'a>(key: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (key.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between `a` and `b`, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Finds the 1-based line of `content` holding the value at `path`.
+///
+/// This is a line scanner, not a parser: it follows block-style YAML keys and
+/// `-` items by indentation, and JSON keys by their quoted names. When a
+/// segment cannot be found (flow-style collections, JSON array elements) the
+/// line of the deepest segment found is returned instead.
+pub fn locate_line(content: &str, path: &[PathSegment], format: SourceFormat) -> Option<usize> {
+    let lines: Vec<&str> = content.lines().collect();
+    match format {
+        SourceFormat::Yaml => locate_yaml(&lines, path),
+        SourceFormat::Json => locate_json(&lines, path),
+    }
+    .map(|index| index + 1)
+}
+
+fn locate_yaml(lines: &[&str], path: &[PathSegment]) -> Option<usize> {
+    let mut found = None;
+    let mut start = 0;
+    // Column of the enclosing key or item; children are indented past it.
+    let mut parent: Option<usize> = None;
+    // Set after an item: its first key shares the `- ` line.
+    let mut on_item = false;
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                let Some((index, column)) = (start..lines.len())
+                    .take_while(|&i| (on_item && i == start) || !left_block(lines[i], parent))
+                    .find_map(|i| {
+                        yaml_entry(lines[i])
+                            .filter(|(column, name)| {
+                                name == key
+                                    && match parent {
+                                        Some(p) => *column > p,
+                                        None => *column == 0,
+                                    }
+                            })
+                            .map(|(column, _)| (i, column))
+                    })
+                else {
+                    break;
+                };
+                found = Some(index);
+                start = index + 1;
+                parent = Some(column);
+                on_item = false;
+            }
+            PathSegment::Index(target) => {
+                let items: Vec<(usize, usize)> = (start..lines.len())
+                    .take_while(|&i| !leaves_sequence(lines[i], parent))
+                    .filter_map(|i| dash_column(lines[i]).map(|column| (i, column)))
+                    .collect();
+                let Some(&(_, item_column)) = items.first() else {
+                    break;
+                };
+                let Some(&(index, _)) = items
+                    .iter()
+                    .filter(|(_, column)| *column == item_column)
+                    .nth(*target)
+                else {
+                    break;
+                };
+                found = Some(index);
+                start = index;
+                parent = Some(item_column);
+                on_item = true;
+            }
+        }
+    }
+    found
+}
+
+/// Whether `line` is content indented at or before `parent`, ending the block
+/// that began under it.
+fn left_block(line: &str, parent: Option<usize>) -> bool {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return false;
+    }
+    parent.is_some_and(|p| line.len() - trimmed.len() <= p)
+}
+
+/// Whether `line` ends the sequence under the key at column `parent`. Items
+/// may sit at the key's own indentation, so only other content there ends it.
+fn leaves_sequence(line: &str, parent: Option<usize>) -> bool {
+    left_block(line, parent) && !(dash_column(line).is_some() && parent == dash_column(line))
+}
+
+/// The column and name of the mapping key on `line`, looking past any
+/// leading `- ` item markers.
+fn yaml_entry(line: &str) -> Option<(usize, &str)> {
+    let mut rest = line.trim_start();
+    while let Some(item) = rest.strip_prefix("- ") {
+        rest = item.trim_start();
+    }
+    if rest.starts_with('#') {
+        return None;
+    }
+    let (key, _) = rest.split_once(':')?;
+    let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+    Some((line.len() - rest.len(), key))
+}
+
+/// The column of the `-` marker when `line` starts a sequence item.
+fn dash_column(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    (trimmed == "-" || trimmed.starts_with("- ")).then(|| line.len() - trimmed.len())
+}
+
+fn locate_json(lines: &[&str], path: &[PathSegment]) -> Option<usize> {
+    let mut found = None;
+    let mut start = 0;
+    for segment in path {
+        let PathSegment::Key(key) = segment else {
+            break;
+        };
+        let needle = format!("\"{key}\"");
+        let Some(index) = (start..lines.len()).find(|&i| {
+            lines[i]
+                .match_indices(&needle)
+                .any(|(at, _)| lines[i][at + needle.len()..].trim_start().starts_with(':'))
+        }) else {
+            break;
+        };
+        found = Some(index);
+        start = index;
+    }
+    found
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn key(name: &str) -> PathSegment {
+        PathSegment::Key(name.to_string())
+    }
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "mode": { "oneOf": [
+                    { "type": "string", "const": "extend" },
+                    { "type": "string", "const": "replace" }
+                ]},
+                "scopes": { "type": "array", "items": { "$ref": "#/$defs/Scope" } },
+                "limit": { "type": ["integer", "null"], "minimum": 0 },
+                "nested": { "anyOf": [{ "$ref": "#/$defs/Scope" }, { "type": "null" }] }
+            },
+            "additionalProperties": false,
+            "$defs": {
+                "Scope": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "file_patterns": { "type": "array", "items": { "type": "string" } }
+                    },
+                    "required": ["name"],
+                    "additionalProperties": false
+                }
+            }
+        })
+    }
+
+    fn messages(value: &Value) -> Vec<String> {
+        let mut messages: Vec<String> = validate(&schema(), value)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn accepts_conforming_documents() {
+        let value = json!({
+            "mode": "replace",
+            "scopes": [{ "name": "cli", "file_patterns": ["src/cli/**"] }],
+            "limit": 3,
+            "nested": null
+        });
+        assert!(messages(&value).is_empty());
+    }
+
+    #[test]
+    fn reports_unknown_keys_with_suggestions() {
+        let value = json!({ "scopes": [{ "name": "cli", "file_pattern": [] }], "extra": 1 });
+        assert_eq!(
+            messages(&value),
+            [
+                "extra: unknown key 'extra'",
+                "scopes[0].file_pattern: unknown key 'file_pattern' (did you mean 'file_patterns'?)",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_type_enum_and_required_errors() {
+        let value = json!({
+            "mode": "append",
+            "scopes": "cli",
+            "limit": -1,
+            "nested": { "file_patterns": "src" }
+        });
+        assert_eq!(
+            messages(&value),
+            [
+                "limit: must be at least 0",
+                "mode: expected one of \"extend\", \"replace\", found \"append\"",
+                "nested.file_patterns: expected a list, found a string",
+                "nested: missing required key 'name'",
+                "scopes: expected a list, found a string",
+            ]
+        );
+    }
+
+    #[test]
+    fn locates_yaml_paths() {
+        let content = "\
+# comment
+scopes:
+  - name: cli
+    file_patterns: []
+  - name: git
+    description: Git
+    file_pattern:
+      - src/git/**
+types:
+- name: infra
+";
+        let at = |path: &[PathSegment]| locate_line(content, path, SourceFormat::Yaml);
+        assert_eq!(at(&[key("scopes")]), Some(2));
+        assert_eq!(at(&[key("scopes"), PathSegment::Index(1)]), Some(5));
+        assert_eq!(
+            at(&[key("scopes"), PathSegment::Index(1), key("file_pattern")]),
+            Some(7)
+        );
+        assert_eq!(
+            at(&[key("scopes"), PathSegment::Index(0), key("name")]),
+            Some(3)
+        );
+        assert_eq!(at(&[key("types"), PathSegment::Index(0)]), Some(10));
+        assert_eq!(at(&[key("scopes"), PathSegment::Index(5)]), Some(2));
+    }
+
+    #[test]
+    fn locates_json_paths() {
+        let content = "{\n  \"env\": {\n    \"OMNI_DEV_MODEL\": \"x\"\n  },\n  \"mcp\": {\n    \"log_levl\": \"info\"\n  }\n}\n";
+        let at = |path: &[PathSegment]| locate_line(content, path, SourceFormat::Json);
+        assert_eq!(at(&[key("mcp"), key("log_levl")]), Some(6));
+        assert_eq!(at(&[key("env")]), Some(2));
+    }
+}
@@ -0,0 +1,173 @@
+//! Credentials fetched from an external command.
+//!
+//! A `secret_helpers` block in `$HOME/.omni-dev/settings.json` (or in a
+//! profile) names, per provider, a shell command that prints the credential
+//! on stdout — `op read …`, `vault kv get -field=…`, `pass show …`:
+//!
+//! ```json
+//! {
+//!   "secret_helpers": {
+//!     "anthropic": { "command": "op read op://Private/Anthropic/credential", "ttl_secs": 900 }
+//!   }
+//! }
+//! ```
+//!
+//! [`SettingsEnv`](super::settings::SettingsEnv) consults the helper for the
+//! provider's credential key after the process environment and before the
+//! `env` maps, so every AI client constructor and preflight check sees it
+//! without changes. Output is cached in memory only, for `ttl_secs`
+//! (default [`DEFAULT_TTL_SECS`]), so a long-lived process such as the MCP
+//! server re-runs the helper when a short-lived token expires, while a single
+//! invocation runs it once. Helper output is never written to disk.
+
+use std::collections::HashMap;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::utils::secret::Secret;
+
+/// Seconds a helper's output is reused when `ttl_secs` is not set.
+pub const DEFAULT_TTL_SECS: u64 = 300;
+
+/// Providers that accept a helper, and the credential key each one serves.
+pub const PROVIDER_KEYS: &[(&str, &str)] = &[
+    ("anthropic", "ANTHROPIC_API_KEY"),
+    ("bedrock", "ANTHROPIC_AUTH_TOKEN"),
+    ("openai", "OPENAI_API_KEY"),
+];
+
+/// One provider's helper configuration.
+#[derive(Debug, Clone, Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SecretHelper {
+    /// Shell command printing the credential on stdout. It runs through
+    /// `sh -c` (`cmd /C` on Windows) with the terminal's stdin and stderr, so
+    /// interactive unlock prompts still work.
+    pub command: String,
+
+    /// Seconds to reuse the output before running the command again; `0`
+    /// disables caching.
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+/// Helper results by command, with the time they were fetched. Failures are
+/// cached too, so a helper that errors is not re-run (and its prompt
+/// re-shown) for every key lookup in one invocation.
+static CACHE: Mutex<Option<HashMap<String, (Instant, Option<Secret>)>>> = Mutex::new(None);
+
+/// The provider whose helper serves `key`, if any.
+pub fn provider_for_key(key: &str) -> Option<&'static str> {
+    PROVIDER_KEYS
+        .iter()
+        .find(|(_, provider_key)| *provider_key == key)
+        .map(|(provider, _)| *provider)
+}
+
+/// Returns `helper`'s credential, from the cache while it is fresh, otherwise
+/// by running the command. A failing or empty helper is logged and yields
+/// `None`, so resolution falls through to the settings `env` maps.
+pub fn fetch(provider: &str, helper: &SecretHelper) -> Option<String> {
+    let ttl = Duration::from_secs(helper.ttl_secs.unwrap_or(DEFAULT_TTL_SECS));
+    let mut cache = CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let entries = cache.get_or_insert_with(HashMap::new);
+    if let Some((fetched, value)) = entries.get(&helper.command) {
+        if fetched.elapsed() < ttl {
+            return value.as_ref().map(|s| s.expose_secret().to_string());
+        }
+    }
+    // The lock is held while the helper runs so concurrent lookups wait for
+    // one result instead of each prompting.
+    let value = run(provider, &helper.command).map(Secret::new);
+    let result = value.as_ref().map(|s| s.expose_secret().to_string());
+    if !ttl.is_zero() {
+        entries.insert(helper.command.clone(), (Instant::now(), value));
+    }
+    result
+}
+
+/// Runs `command` and returns its trimmed stdout.
+fn run(provider: &str, command: &str) -> Option<String> {
+    tracing::debug!("Running secret helper for {provider}");
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let output = match shell
+        .arg(command)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            tracing::warn!("Secret helper for {provider} could not be started: {e}");
+            return None;
+        }
+    };
+    if !output.status.success() {
+        tracing::warn!(
+            "Secret helper for {provider} failed ({}); ignoring it",
+            output.status
+        );
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if value.is_empty() {
+        tracing::warn!("Secret helper for {provider} printed nothing; ignoring it");
+        return None;
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn helper(command: String, ttl_secs: Option<u64>) -> SecretHelper {
+        SecretHelper { command, ttl_secs }
+    }
+
+    #[test]
+    fn maps_credential_keys_to_providers() {
+        assert_eq!(provider_for_key("ANTHROPIC_API_KEY"), Some("anthropic"));
+        assert_eq!(provider_for_key("OPENAI_API_KEY"), Some("openai"));
+        assert_eq!(provider_for_key("OMNI_DEV_MODEL"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn caches_output_for_the_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("runs");
+        let command = format!("echo run >> '{}'; echo '  sk-from-helper  '", log.display());
+
+        let cached = helper(command.clone(), None);
+        assert_eq!(fetch("test", &cached).as_deref(), Some("sk-from-helper"));
+        assert_eq!(fetch("test", &cached).as_deref(), Some("sk-from-helper"));
+        assert_eq!(std::fs::read_to_string(&log).unwrap().lines().count(), 1);
+
+        let uncached = helper(format!("{command} # uncached"), Some(0));
+        fetch("test", &uncached);
+        fetch("test", &uncached);
+        assert_eq!(std::fs::read_to_string(&log).unwrap().lines().count(), 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failing_or_silent_helpers_yield_nothing() {
+        assert_eq!(fetch("test", &helper("exit 3".to_string(), Some(0))), None);
+        assert_eq!(fetch("test", &helper("true".to_string(), Some(0))), None);
+    }
+}
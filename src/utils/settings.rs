@@ -12,16 +12,17 @@
 //! credentials (Atlassian, Datadog), every write is hardened: parent directory
 //! `0700`, file `0600`, re-tightened on each write (issue #1128).
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::{Mutex, OnceLock};
 
 use anyhow::{Context, Result};
 use serde::Deserialize;
 
 use crate::utils::env::{EnvSource, SystemEnv};
+use crate::utils::secret_helper::{self, SecretHelper};
 
 /// Where a resolved environment value came from, for provenance reporting
 /// (issue #1143).
@@ -37,6 +38,8 @@ pub enum EnvValueSource {
     CliFlag,
     /// The process environment (a shell export or inherited variable).
     ProcessEnv,
+    /// The project configuration, `.omni-dev/config.yaml`.
+    ProjectConfig,
     /// The base `env` map in `$HOME/.omni-dev/settings.json`.
     SettingsEnv,
     /// The named profile's `env` map in `$HOME/.omni-dev/settings.json`.
@@ -48,6 +51,7 @@ impl fmt::Display for EnvValueSource {
         match self {
             Self::CliFlag => write!(f, "command-line flag"),
             Self::ProcessEnv => write!(f, "process environment variable (e.g. a shell export)"),
+            Self::ProjectConfig => write!(f, "the project config .omni-dev/config.yaml"),
             Self::SettingsEnv => write!(f, "the env map in $HOME/.omni-dev/settings.json"),
             Self::SettingsProfile(name) => {
                 write!(
@@ -97,11 +101,17 @@ pub const PROFILE_ENV_VAR: &str = "OMNI_DEV_PROFILE";
 
 /// A named credential/config bundle inside `settings.json` — its own `env` map,
 /// selected per invocation via `--profile` / `OMNI_DEV_PROFILE`.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct Profile {
     /// Environment variable overrides applied when this profile is active.
     #[serde(default)]
     pub env: HashMap<String, String>,
+
+    /// Credential helpers by provider, used instead of the base
+    /// `secret_helpers` when this profile is active.
+    #[serde(default)]
+    pub secret_helpers: HashMap<String, SecretHelper>,
 }
 
 /// The `mcp` section of `settings.json` — defaults for the `omni-dev-mcp`
@@ -110,7 +120,8 @@ pub struct Profile {
 /// Every field is optional; an unset field falls back to the built-in default,
 /// so an absent `mcp` block preserves the server's behaviour byte-for-byte. See
 /// [`Settings::load_mcp`] for the loader and `crate::mcp` for the wiring.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct McpSettings {
     /// Default AI model for the `ai_chat` tool, used when the tool's own
     /// `model` parameter is absent. Falls back to the model registry default.
@@ -131,8 +142,14 @@ pub struct McpSettings {
 }
 
 /// Settings loaded from $HOME/.omni-dev/settings.json.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Deserialize, schemars::JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct Settings {
+    /// Layout version of the file; absent in files older than versioning. See
+    /// [`crate::utils::settings_migration`].
+    #[serde(default)]
+    pub version: Option<u64>,
+
     /// Environment variable overrides — the default bundle, consulted only when
     /// **no** profile is active.
     #[serde(default)]
@@ -147,6 +164,19 @@ pub struct Settings {
     /// [`McpSettings::default`].
     #[serde(default)]
     pub mcp: McpSettings,
+
+    /// Commands that print a provider's credential (see
+    /// [`crate::utils::secret_helper`]), consulted only when **no** profile
+    /// is active, like `env`.
+    #[serde(default)]
+    pub secret_helpers: HashMap<String, SecretHelper>,
+
+    /// Values from the project configuration (`.omni-dev/config.yaml`),
+    /// filled in by [`Settings::load`] and consulted before either `env` map.
+    /// Only keys the project layer may set are present; see
+    /// [`crate::utils::config`].
+    #[serde(skip)]
+    pub project: BTreeMap<String, String>,
 }
 
 /// Returns the active profile name from `raw` (the process environment), or
@@ -176,14 +206,18 @@ pub fn profile_suffix(profile: Option<&str>) -> String {
 /// Pass `&SettingsEnv::load()` from a thin production wrapper; tests inject a
 /// pure `MapEnv` into the same `*_with(&impl EnvSource, …)` seam instead of
 /// mutating the process environment.
+///
+/// The settings file is read on the first lookup the process environment
+/// cannot answer, so a caller whose keys are all set in the environment —
+/// or that never looks one up — pays for no disk reads or context discovery.
 #[derive(Debug, Default)]
 pub struct SettingsEnv {
-    settings: Settings,
+    settings: OnceLock<Settings>,
     active_profile: Option<String>,
 }
 
 impl SettingsEnv {
-    /// Loads settings from the default location, falling back to an empty
+    /// Prepares settings from the default location, falling back to an empty
     /// settings map if they are absent or unreadable (env-only behaviour). The
     /// active profile is read from `OMNI_DEV_PROFILE`.
     pub fn load() -> Self {
@@ -195,24 +229,52 @@ impl SettingsEnv {
     /// setting `OMNI_DEV_PROFILE` in the process environment.
     pub fn load_with_profile(profile: Option<&str>) -> Self {
         Self {
-            settings: Settings::load().unwrap_or_default(),
+            settings: OnceLock::new(),
             active_profile: profile.map(str::to_string),
         }
     }
+
+    fn settings(&self) -> &Settings {
+        self.settings
+            .get_or_init(|| Settings::load().unwrap_or_default())
+    }
 }
 
 impl EnvSource for SettingsEnv {
+    /// Resolves `key` like [`Settings::resolve_with`], except that a
+    /// configured secret helper for the key's provider is run after the
+    /// process environment misses and before the `env` maps are consulted.
     fn var(&self, key: &str) -> Option<String> {
-        self.settings
-            .resolve_with(&SystemEnv, self.active_profile.as_deref(), key)
+        if let Some(value) = SystemEnv.var(key) {
+            return Some(value);
+        }
+        let profile = self.active_profile.as_deref();
+        if let Some((provider, helper)) = self.settings().secret_helper_for(profile, key) {
+            if let Some(value) = secret_helper::fetch(provider, helper) {
+                return Some(value);
+            }
+        }
+        self.settings().resolve_with(&SystemEnv, profile, key)
     }
 }
 
 impl Settings {
-    /// Loads settings from the default location.
+    /// Loads settings from the default location, together with the project
+    /// configuration of the context directory found from the current
+    /// directory.
     pub fn load() -> Result<Self> {
         let settings_path = Self::get_settings_path()?;
-        Self::load_from_path(&settings_path)
+        let mut settings = Self::load_from_path(&settings_path)?;
+        settings.project =
+            crate::utils::config::load_project_config(&crate::utils::config::project_config_path(
+                &crate::claude::context::resolve_context_dir(None),
+            ));
+        Ok(settings)
+    }
+
+    /// JSON Schema of `settings.json`, for `omni-dev config validate`.
+    pub fn schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(Self)).unwrap_or(serde_json::Value::Null)
     }
 
     /// Loads just the [`mcp`](McpSettings) section, falling back to its defaults
@@ -235,6 +297,7 @@ impl Settings {
         // Read and parse the settings file
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read settings file: {}", path.display()))?;
+        crate::utils::settings_migration::warn_if_outdated(path, &content);
 
         serde_json::from_str::<Self>(&content)
             .with_context(|| format!("Failed to parse settings file: {}", path.display()))
@@ -254,7 +317,8 @@ impl Settings {
     }
 
     /// Isolated / AWS-faithful resolution: `raw` (the process environment) wins;
-    /// then the active profile's `env` if `active` is set, else the base `env`.
+    /// then the project configuration; then the active profile's `env` if
+    /// `active` is set, else the base `env`.
     /// The base map is **not** consulted when a profile is active, so a missing
     /// key fails loud rather than silently reusing a default credential against
     /// the wrong tenant.
@@ -272,8 +336,9 @@ impl Settings {
     }
 
     /// Like [`Settings::resolve_with`], but also reports which layer supplied
-    /// the value: the raw process environment, the active profile's `env`, or
-    /// the base `env` (issue #1143). Same precedence, same profile isolation.
+    /// the value: the raw process environment, the project configuration, the
+    /// active profile's `env`, or the base `env` (issue #1143). Same
+    /// precedence, same profile isolation.
     ///
     /// A [`EnvValueSource::CliFlag`] attribution is layered on top by
     /// [`get_env_var_sourced`], which knows about flag exports; this resolver
@@ -287,6 +352,9 @@ impl Settings {
         if let Some(value) = raw.var(key) {
             return Some((value, EnvValueSource::ProcessEnv));
         }
+        if let Some(value) = self.project.get(key) {
+            return Some((value.clone(), EnvValueSource::ProjectConfig));
+        }
         match active {
             Some(name) => self
                 .profiles
@@ -377,6 +445,22 @@ impl Settings {
         Ok(removed)
     }
 
+    /// The secret helper serving `key`, with its provider name: from the
+    /// active profile's `secret_helpers` when `active` is set, else the base
+    /// map — the same isolation as the `env` maps.
+    pub fn secret_helper_for(
+        &self,
+        active: Option<&str>,
+        key: &str,
+    ) -> Option<(&'static str, &SecretHelper)> {
+        let provider = secret_helper::provider_for_key(key)?;
+        let helpers = match active {
+            Some(name) => &self.profiles.get(name)?.secret_helpers,
+            None => &self.secret_helpers,
+        };
+        helpers.get(provider).map(|helper| (provider, helper))
+    }
+
     /// Validates that `name` is a known profile, returning a hard error that
     /// lists the known profiles (sorted) otherwise. Called once at the CLI
     /// boundary so a typo never silently falls back to base credentials.
@@ -463,7 +547,7 @@ fn read_or_default_settings(path: &Path) -> Result<serde_json::Value> {
 /// directory `0700`, writes the pretty-printed JSON through a `0600` handle
 /// (no window where a fresh file is world-readable), and re-tightens a
 /// pre-existing looser-permission file on every write (issue #1128).
-fn write_settings(path: &Path, value: &serde_json::Value) -> Result<()> {
+pub(crate) fn write_settings(path: &Path, value: &serde_json::Value) -> Result<()> {
     if let Some(parent) = path.parent() {
         if !parent.as_os_str().is_empty() {
             crate::daemon::paths::ensure_dir_0700(parent)?;
@@ -597,7 +681,13 @@ mod tests {
         work_env.insert("ATLASSIAN_EMAIL".to_string(), "me@work.com".to_string());
 
         let mut profiles = HashMap::new();
-        profiles.insert("work".to_string(), Profile { env: work_env });
+        profiles.insert(
+            "work".to_string(),
+            Profile {
+                env: work_env,
+                ..Profile::default()
+            },
+        );
 
         Settings {
             env: base,
@@ -771,6 +861,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_with_source_project_config_sits_between_env_and_settings() {
+        let mut settings = settings_with_profile();
+        settings
+            .project
+            .insert("SHARED".to_string(), "project-shared".to_string());
+        assert_eq!(
+            settings.resolve_with_source(&MapEnv::new(), Some("work"), "SHARED"),
+            Some(("project-shared".to_string(), EnvValueSource::ProjectConfig))
+        );
+        let raw = MapEnv::new().with("SHARED", "exported");
+        assert_eq!(
+            settings.resolve_with(&raw, None, "SHARED"),
+            Some("exported".to_string())
+        );
+    }
+
+    #[test]
+    fn secret_helpers_follow_profile_isolation() {
+        let settings: Settings = serde_json::from_str(
+            r#"{
+                "secret_helpers": {"anthropic": {"command": "op read base"}},
+                "profiles": {
+                    "work": {"secret_helpers": {"openai": {"command": "pass openai", "ttl_secs": 60}}},
+                    "home": {}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let (provider, helper) = settings
+            .secret_helper_for(None, "ANTHROPIC_API_KEY")
+            .unwrap();
+        assert_eq!(
+            (provider, helper.command.as_str()),
+            ("anthropic", "op read base")
+        );
+        assert!(settings.secret_helper_for(None, "OMNI_DEV_MODEL").is_none());
+
+        let (_, helper) = settings
+            .secret_helper_for(Some("work"), "OPENAI_API_KEY")
+            .unwrap();
+        assert_eq!(helper.ttl_secs, Some(60));
+        assert!(settings
+            .secret_helper_for(Some("work"), "ANTHROPIC_API_KEY")
+            .is_none());
+        assert!(settings
+            .secret_helper_for(Some("home"), "ANTHROPIC_API_KEY")
+            .is_none());
+    }
+
     #[test]
     fn resolve_with_source_missing_key_is_none() {
         let settings = settings_with_profile();
@@ -0,0 +1,217 @@
+//! Versioned layout of `settings.json` and upgrades between versions.
+//!
+//! `settings.json` records the layout it was written for in a top-level
+//! `version` (absent means 0). Each [`MIGRATIONS`] step rewrites a document
+//! from the previous version to the next, so `omni-dev config migrate` can
+//! bring any older file up to [`CURRENT_VERSION`] in one go, keeping a backup
+//! of the original. Loading an outdated file warns instead of silently
+//! honouring or ignoring stale keys (see [`warn_if_outdated`]).
+//!
+//! Steps work on the raw JSON rather than [`Settings`](super::settings::Settings)
+//! so keys the current layout no longer knows survive until a step moves
+//! them.
+
+use std::sync::Once;
+
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+
+use crate::claude::backend::{AI_BACKEND_ENV, USE_BEDROCK_ENV, USE_OLLAMA_ENV, USE_OPENAI_ENV};
+
+/// The settings layout this version of omni-dev reads and writes.
+pub const CURRENT_VERSION: u64 = 1;
+
+/// One upgrade step, from `to - 1` to `to`.
+struct Migration {
+    to: u64,
+    /// Rewrites the document, returning a line per change made.
+    apply: fn(&mut Map<String, Value>) -> Vec<String>,
+}
+
+/// Every upgrade step, in version order.
+const MIGRATIONS: &[Migration] = &[Migration {
+    to: 1,
+    apply: replace_legacy_backend_selectors,
+}];
+
+/// The layout version recorded in `settings`.
+pub fn version_of(settings: &Value) -> u64 {
+    settings.get("version").and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Upgrades `settings` to [`CURRENT_VERSION`] in place and returns a
+/// description of each change. A file already at the current version is left
+/// untouched; one from a newer omni-dev is refused.
+pub fn migrate(settings: &mut Value) -> Result<Vec<String>> {
+    let from = version_of(settings);
+    if from > CURRENT_VERSION {
+        bail!(
+            "settings.json has version {from}, newer than this omni-dev supports \
+             ({CURRENT_VERSION}); upgrade omni-dev instead"
+        );
+    }
+    let Some(root) = settings.as_object_mut() else {
+        bail!("settings.json must contain a JSON object");
+    };
+    if from == CURRENT_VERSION {
+        return Ok(Vec::new());
+    }
+    let mut changes = Vec::new();
+    for step in MIGRATIONS.iter().filter(|step| step.to > from) {
+        changes.extend((step.apply)(root));
+    }
+    root.insert("version".to_string(), Value::from(CURRENT_VERSION));
+    Ok(changes)
+}
+
+/// Warns once per process when the settings file at `path` (with raw
+/// `content`) needs `omni-dev config migrate`, or was written by a newer
+/// omni-dev.
+pub fn warn_if_outdated(path: &std::path::Path, content: &str) {
+    static WARNED: Once = Once::new();
+    let Ok(mut settings) = serde_json::from_str::<Value>(content) else {
+        return;
+    };
+    let version = version_of(&settings);
+    let message = if version > CURRENT_VERSION {
+        format!(
+            "{} has settings version {version}, newer than this omni-dev supports \
+             ({CURRENT_VERSION}); some settings may be ignored",
+            path.display()
+        )
+    } else {
+        match migrate(&mut settings) {
+            Ok(changes) if !changes.is_empty() => format!(
+                "{} uses an outdated layout ({} change(s) pending); run \
+                 `omni-dev config migrate` to upgrade it",
+                path.display(),
+                changes.len()
+            ),
+            _ => return,
+        }
+    };
+    WARNED.call_once(|| eprintln!("warning: {message}"));
+}
+
+/// The `env` maps of the document: the base map and each profile's.
+fn env_maps(root: &mut Map<String, Value>) -> Vec<(String, &mut Map<String, Value>)> {
+    let mut maps = Vec::new();
+    let mut env = None;
+    let mut profiles = None;
+    for (key, value) in root.iter_mut() {
+        match key.as_str() {
+            "env" => env = value.as_object_mut(),
+            "profiles" => profiles = value.as_object_mut(),
+            _ => {}
+        }
+    }
+    if let Some(env) = env {
+        maps.push(("env".to_string(), env));
+    }
+    for (name, profile) in profiles.into_iter().flatten() {
+        if let Some(env) = profile.get_mut("env").and_then(Value::as_object_mut) {
+            maps.push((format!("profiles.{name}.env"), env));
+        }
+    }
+    maps
+}
+
+/// Version 1: the `USE_OLLAMA` / `USE_OPENAI` / `CLAUDE_CODE_USE_BEDROCK`
+/// selectors become `OMNI_DEV_AI_BACKEND`, and its `claude_cli` spelling
+/// becomes `claude-cli`. Resolution order is preserved: an existing
+/// `OMNI_DEV_AI_BACKEND` wins, then the first selector set to `true`.
+fn replace_legacy_backend_selectors(root: &mut Map<String, Value>) -> Vec<String> {
+    let selectors = [
+        (USE_OLLAMA_ENV, "ollama"),
+        (USE_OPENAI_ENV, "openai"),
+        (USE_BEDROCK_ENV, "bedrock"),
+    ];
+    let mut changes = Vec::new();
+    for (scope, env) in env_maps(root) {
+        if env.get(AI_BACKEND_ENV).and_then(Value::as_str) == Some("claude_cli") {
+            env.insert(AI_BACKEND_ENV.to_string(), Value::from("claude-cli"));
+            changes.push(format!(
+                "{scope}: {AI_BACKEND_ENV} 'claude_cli' is now spelled 'claude-cli'"
+            ));
+        }
+        let backend_set = env
+            .get(AI_BACKEND_ENV)
+            .and_then(Value::as_str)
+            .is_some_and(|v| !v.is_empty());
+        let mut selected = None;
+        for (key, backend) in selectors {
+            let Some(value) = env.remove(key) else {
+                continue;
+            };
+            let value = value.as_str().map(str::to_string).unwrap_or_default();
+            if !backend_set && selected.is_none() && value == "true" {
+                selected = Some(backend);
+                changes.push(format!(
+                    "{scope}: {key}=true replaced by {AI_BACKEND_ENV}={backend}"
+                ));
+            } else {
+                changes.push(format!("{scope}: removed {key}, which had no effect"));
+            }
+        }
+        if let Some(backend) = selected {
+            env.insert(AI_BACKEND_ENV.to_string(), Value::from(backend));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn legacy_selectors_become_the_backend_key() {
+        let mut settings = json!({
+            "env": { "USE_OPENAI": "true", "CLAUDE_CODE_USE_BEDROCK": "true", "OTHER": "x" },
+            "profiles": {
+                "work": { "env": { "OMNI_DEV_AI_BACKEND": "claude_cli", "USE_OLLAMA": "true" } },
+                "home": { "env": { "USE_OLLAMA": "false" } }
+            }
+        });
+        let mut changes = migrate(&mut settings).unwrap();
+        changes.sort();
+        assert_eq!(
+            settings,
+            json!({
+                "version": 1,
+                "env": { "OMNI_DEV_AI_BACKEND": "openai", "OTHER": "x" },
+                "profiles": {
+                    "work": { "env": { "OMNI_DEV_AI_BACKEND": "claude-cli" } },
+                    "home": { "env": {} }
+                }
+            })
+        );
+        assert_eq!(
+            changes,
+            [
+                "env: USE_OPENAI=true replaced by OMNI_DEV_AI_BACKEND=openai",
+                "env: removed CLAUDE_CODE_USE_BEDROCK, which had no effect",
+                "profiles.home.env: removed USE_OLLAMA, which had no effect",
+                "profiles.work.env: OMNI_DEV_AI_BACKEND 'claude_cli' is now spelled 'claude-cli'",
+                "profiles.work.env: removed USE_OLLAMA, which had no effect",
+            ]
+        );
+    }
+
+    #[test]
+    fn current_and_newer_versions_are_not_rewritten() {
+        let mut current = json!({ "version": 1, "env": { "USE_OPENAI": "true" } });
+        assert!(migrate(&mut current).unwrap().is_empty());
+        assert_eq!(current["env"]["USE_OPENAI"], "true");
+
+        let mut newer = json!({ "version": 2 });
+        let err = migrate(&mut newer).unwrap_err().to_string();
+        assert!(err.contains("newer than this omni-dev supports"), "{err}");
+
+        let mut empty = json!({});
+        assert!(migrate(&mut empty).unwrap().is_empty());
+        assert_eq!(empty, json!({ "version": 1 }));
+    }
+}
@@ -93,6 +93,7 @@ impl TestRepo {
 
     fn create_amendment_file(&self, amendments: Vec<(usize, &str)>) -> Result<PathBuf> {
         let amendment_file = AmendmentFile {
+            schema_version: omni_dev::data::SCHEMA_VERSION,
             amendments: amendments
                 .iter()
                 .filter_map(|(index, message)| {
@@ -517,6 +518,7 @@ fn test_repo_create_amendment_file_roundtrip() -> Result<()> {
 
     // Build the AmendmentFile directly (avoid filter_map silently dropping items)
     let amendment_file = AmendmentFile {
+        schema_version: omni_dev::data::SCHEMA_VERSION,
         amendments: vec![
             Amendment::new(hash0, "improved initial".to_string()),
             Amendment::new(hash1, "improved second".to_string()),
@@ -547,6 +549,7 @@ fn test_repo_create_amendment_file_roundtrip() -> Result<()> {
 
 #[tokio::test]
 async fn cli_execute_dispatches_git_commit_message_view() {
+    use omni_dev::cli::format::YamlOrJson;
     use omni_dev::cli::git::{
         CommitCommand, CommitSubcommands, GitCommand, GitSubcommands, MessageCommand,
         MessageSubcommands, ViewCommand,
@@ -562,6 +565,13 @@ async fn cli_execute_dispatches_git_commit_message_view() {
         claude_cli_max_budget_usd: None,
         models_yaml: None,
         repo: None,
+        plain: false,
+        no_pager: false,
+        yes: false,
+        output: None,
+        log_level: None,
+        log_file: None,
+        log_format: Default::default(),
         profile: None,
         instance: None,
         command: Commands::Git(GitCommand {
@@ -569,6 +579,12 @@ async fn cli_execute_dispatches_git_commit_message_view() {
                 command: CommitSubcommands::Message(MessageCommand {
                     command: MessageSubcommands::View(ViewCommand {
                         commit_range: Some("HEAD".to_string()),
+                        output: Some(YamlOrJson::Yaml),
+                        author: None,
+                        since: None,
+                        paths: Vec::new(),
+                        limit: None,
+                        no_diff: false,
                     }),
                 }),
             }),
@@ -579,6 +595,7 @@ async fn cli_execute_dispatches_git_commit_message_view() {
 
 #[tokio::test]
 async fn cli_execute_dispatches_git_branch_info() {
+    use omni_dev::cli::format::YamlOrJson;
     use omni_dev::cli::git::{
         BranchCommand, BranchSubcommands, GitCommand, GitSubcommands, InfoCommand,
     };
@@ -593,11 +610,24 @@ async fn cli_execute_dispatches_git_branch_info() {
         claude_cli_max_budget_usd: None,
         models_yaml: None,
         repo: None,
+        plain: false,
+        no_pager: false,
+        yes: false,
+        output: None,
+        log_level: None,
+        log_file: None,
+        log_format: Default::default(),
         profile: None,
         instance: None,
         command: Commands::Git(GitCommand {
             command: GitSubcommands::Branch(BranchCommand {
-                command: BranchSubcommands::Info(InfoCommand { base_branch: None }),
+                command: BranchSubcommands::Info(InfoCommand {
+                    base_branch: None,
+                    output: Some(YamlOrJson::Yaml),
+                    paths: Vec::new(),
+                    limit: None,
+                    no_diff: false,
+                }),
             }),
         }),
     };
@@ -618,6 +648,13 @@ async fn cli_execute_dispatches_ai_chat() {
         claude_cli_max_budget_usd: None,
         models_yaml: None,
         repo: None,
+        plain: false,
+        no_pager: false,
+        yes: false,
+        output: None,
+        log_level: None,
+        log_file: None,
+        log_format: Default::default(),
         profile: None,
         instance: None,
         command: Commands::Ai(AiCommand {